@@ -0,0 +1,79 @@
+//! Contains common definitions for CEF and LEEF codec support
+
+/// CEF header fields. Definitions from the [ArcSight Common Event Format][cef] specification.
+///
+/// The names match the ones used by the `parse_cef` VRL function.
+///
+/// [cef]: https://www.microfocus.com/documentation/arcsight/arcsight-smartconnectors/pdfdoc/common-event-format-v25/common-event-format-v25.pdf
+pub mod cef_fields {
+    /// (not a field) The prefix of every CEF message.
+    pub const CEF_PREFIX: &str = "CEF:";
+
+    /// (required) The version of the CEF format. Defaults to `0` when encoding.
+    pub const CEF_VERSION: &str = "cefVersion";
+
+    /// (required) The vendor of the sending device.
+    pub const DEVICE_VENDOR: &str = "deviceVendor";
+
+    /// (required) The product name of the sending device.
+    pub const DEVICE_PRODUCT: &str = "deviceProduct";
+
+    /// (required) The version of the sending device.
+    pub const DEVICE_VERSION: &str = "deviceVersion";
+
+    /// (required) A unique identifier of the type of event.
+    pub const DEVICE_EVENT_CLASS_ID: &str = "deviceEventClassId";
+
+    /// (required) A human-readable description of the event.
+    pub const NAME: &str = "name";
+
+    /// (required) The importance of the event.
+    pub const SEVERITY: &str = "severity";
+
+    /// (not a field) The header fields, in the order in which they appear in a message.
+    pub const HEADER_FIELDS: [&str; 7] = [
+        CEF_VERSION,
+        DEVICE_VENDOR,
+        DEVICE_PRODUCT,
+        DEVICE_VERSION,
+        DEVICE_EVENT_CLASS_ID,
+        NAME,
+        SEVERITY,
+    ];
+
+    // < Every other field is an extension. >
+}
+
+/// LEEF header fields. Definitions from the [IBM QRadar LEEF][leef] specification.
+///
+/// [leef]: https://www.ibm.com/docs/en/dsm?topic=leef-overview
+pub mod leef_fields {
+    /// (not a field) The prefix of every LEEF message.
+    pub const LEEF_PREFIX: &str = "LEEF:";
+
+    /// (required) The version of the LEEF format. Defaults to `2.0` when encoding.
+    pub const LEEF_VERSION: &str = "leefVersion";
+
+    /// (required) The vendor of the sending device.
+    pub const DEVICE_VENDOR: &str = "deviceVendor";
+
+    /// (required) The product name of the sending device.
+    pub const DEVICE_PRODUCT: &str = "deviceProduct";
+
+    /// (required) The version of the sending device.
+    pub const DEVICE_VERSION: &str = "deviceVersion";
+
+    /// (required) A unique identifier of the type of event.
+    pub const EVENT_ID: &str = "eventId";
+
+    /// (not a field) The header fields, in the order in which they appear in a message.
+    pub const HEADER_FIELDS: [&str; 5] = [
+        LEEF_VERSION,
+        DEVICE_VENDOR,
+        DEVICE_PRODUCT,
+        DEVICE_VERSION,
+        EVENT_ID,
+    ];
+
+    // < Every other field is an attribute. >
+}
@@ -0,0 +1,383 @@
+use std::collections::BTreeMap;
+
+use bytes::Bytes;
+use chrono::{TimeZone, Utc};
+use ordered_float::NotNan;
+use smallvec::{smallvec, SmallVec};
+use vector_config::configurable_component;
+use vector_core::{
+    config::{log_schema, DataType, LogNamespace},
+    event::{Event, LogEvent},
+    schema,
+};
+use vrl::value::{Kind, Value};
+
+use super::Deserializer;
+
+/// The magic byte that starts the header prepended to Avro datums by schema registry clients.
+const SCHEMA_ID_PREFIX_MAGIC_BYTE: u8 = 0;
+
+/// The length of the header prepended to Avro datums by schema registry clients: the magic byte
+/// followed by the schema ID as a 32-bit big-endian integer.
+const SCHEMA_ID_PREFIX_LEN: usize = 5;
+
+/// Config used to build an `AvroDeserializer`.
+#[derive(Debug, Clone)]
+pub struct AvroDeserializerConfig {
+    /// Options for the Avro deserializer.
+    pub avro: AvroDeserializerOptions,
+}
+
+impl AvroDeserializerConfig {
+    /// Creates a new `AvroDeserializerConfig`.
+    pub const fn new(schema: String, strip_schema_id_prefix: bool) -> Self {
+        Self {
+            avro: AvroDeserializerOptions {
+                schema,
+                strip_schema_id_prefix,
+            },
+        }
+    }
+
+    /// Build the `AvroDeserializer` from this configuration.
+    pub fn build(&self) -> vector_common::Result<AvroDeserializer> {
+        let schema = apache_avro::Schema::parse_str(&self.avro.schema)
+            .map_err(|error| format!("Failed building Avro deserializer: {}", error))?;
+        Ok(AvroDeserializer::new(
+            schema,
+            self.avro.strip_schema_id_prefix,
+        ))
+    }
+
+    /// Build an `AvroDeserializer` for datums written with another schema, such as one resolved
+    /// from a schema registry.
+    ///
+    /// The datums are resolved to the schema of this configuration, following the Avro schema
+    /// resolution rules.
+    pub fn build_with_writer_schema(
+        &self,
+        writer_schema: &str,
+    ) -> vector_common::Result<AvroDeserializer> {
+        let writer_schema = apache_avro::Schema::parse_str(writer_schema)
+            .map_err(|error| format!("Failed parsing Avro writer schema: {}", error))?;
+        let mut deserializer = self.build()?;
+        deserializer.writer_schema = Some(writer_schema);
+        Ok(deserializer)
+    }
+
+    /// Return the type of event build by this deserializer.
+    pub fn output_type(&self) -> DataType {
+        DataType::Log
+    }
+
+    /// The schema produced by the deserializer.
+    pub fn schema_definition(&self, log_namespace: LogNamespace) -> schema::Definition {
+        // TODO: Convert the Avro schema to a vector schema definition.
+        match log_namespace {
+            LogNamespace::Legacy => {
+                let mut definition =
+                    schema::Definition::empty_legacy_namespace().unknown_fields(Kind::any());
+
+                if let Some(timestamp_key) = log_schema().timestamp_key() {
+                    definition = definition.try_with_field(
+                        timestamp_key,
+                        // The Avro decoder will try to insert a new `timestamp`-type value into the
+                        // "timestamp_key" field, but only if that field doesn't already exist.
+                        Kind::any().or_timestamp(),
+                        Some("timestamp"),
+                    );
+                }
+                definition
+            }
+            LogNamespace::Vector => {
+                schema::Definition::new_with_default_metadata(Kind::any(), [log_namespace])
+            }
+        }
+    }
+}
+
+/// Apache Avro deserializer options.
+#[configurable_component]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct AvroDeserializerOptions {
+    /// The Avro schema.
+    #[configurable(metadata(
+        docs::examples = r#"{ "type": "record", "name": "log", "fields": [{ "name": "message", "type": "string" }] }"#
+    ))]
+    #[configurable(metadata(docs::human_name = "Schema JSON"))]
+    pub schema: String,
+
+    /// Whether to strip the schema ID header from each message before decoding it.
+    ///
+    /// Schema registry clients, such as the Confluent serializers, prepend a five byte header to
+    /// each message: a zero magic byte followed by the ID of the schema as a 32-bit big-endian
+    /// integer. When enabled, messages without this header are rejected.
+    #[serde(default)]
+    pub strip_schema_id_prefix: bool,
+}
+
+/// Deserializer that builds `Event`s from a byte frame containing an Avro datum.
+#[derive(Debug, Clone)]
+pub struct AvroDeserializer {
+    schema: apache_avro::Schema,
+    writer_schema: Option<apache_avro::Schema>,
+    strip_schema_id_prefix: bool,
+}
+
+impl AvroDeserializer {
+    /// Creates a new `AvroDeserializer`.
+    pub const fn new(schema: apache_avro::Schema, strip_schema_id_prefix: bool) -> Self {
+        Self {
+            schema,
+            writer_schema: None,
+            strip_schema_id_prefix,
+        }
+    }
+}
+
+impl Deserializer for AvroDeserializer {
+    fn parse(
+        &self,
+        bytes: Bytes,
+        log_namespace: LogNamespace,
+    ) -> vector_common::Result<SmallVec<[Event; 1]>> {
+        let bytes = if self.strip_schema_id_prefix {
+            if bytes.len() < SCHEMA_ID_PREFIX_LEN || bytes[0] != SCHEMA_ID_PREFIX_MAGIC_BYTE {
+                return Err("Expected Avro datum to be prefixed with a schema ID header".into());
+            }
+            bytes.slice(SCHEMA_ID_PREFIX_LEN..)
+        } else {
+            bytes
+        };
+
+        let value = match &self.writer_schema {
+            Some(writer_schema) => {
+                apache_avro::from_avro_datum(writer_schema, &mut bytes.as_ref(), Some(&self.schema))
+            }
+            None => apache_avro::from_avro_datum(&self.schema, &mut bytes.as_ref(), None),
+        }
+        .map_err(|error| format!("Error parsing Avro: {}", error))?;
+
+        let value = match to_vrl(value)? {
+            value @ Value::Object(_) => value,
+            value => Err(format!(
+                "Expected Avro datum to be a record or map, found {}",
+                value.kind_str()
+            ))?,
+        };
+        let mut event = Event::Log(LogEvent::from(value));
+        if log_namespace == LogNamespace::Legacy {
+            if let Some(timestamp_key) = log_schema().timestamp_key_target_path() {
+                let log = event.as_mut_log();
+                if !log.contains(timestamp_key) {
+                    log.insert(timestamp_key, Utc::now());
+                }
+            }
+        }
+
+        Ok(smallvec![event])
+    }
+}
+
+fn to_vrl(value: apache_avro::types::Value) -> vector_common::Result<Value> {
+    use apache_avro::types::Value as AvroValue;
+
+    let value = match value {
+        AvroValue::Null => Value::Null,
+        AvroValue::Boolean(b) => Value::from(b),
+        AvroValue::Int(i) | AvroValue::TimeMillis(i) => Value::from(i),
+        AvroValue::Long(i) | AvroValue::TimeMicros(i) => Value::from(i),
+        AvroValue::Float(f) => {
+            Value::Float(NotNan::new(f64::from(f)).map_err(|_| "Float number cannot be NaN")?)
+        }
+        AvroValue::Double(f) => {
+            Value::Float(NotNan::new(f).map_err(|_| "Double number cannot be NaN")?)
+        }
+        AvroValue::Bytes(b) | AvroValue::Fixed(_, b) => Value::from(Bytes::from(b)),
+        AvroValue::String(s) | AvroValue::Enum(_, s) => Value::from(s),
+        AvroValue::Uuid(uuid) => Value::from(uuid.to_string()),
+        AvroValue::Union(_, value) => to_vrl(*value)?,
+        AvroValue::Array(values) => Value::Array(
+            values
+                .into_iter()
+                .map(to_vrl)
+                .collect::<vector_common::Result<_>>()?,
+        ),
+        AvroValue::Map(map) => Value::Object(
+            map.into_iter()
+                .map(|(key, value)| Ok((key, to_vrl(value)?)))
+                .collect::<vector_common::Result<BTreeMap<_, _>>>()?,
+        ),
+        AvroValue::Record(fields) => Value::Object(
+            fields
+                .into_iter()
+                .map(|(key, value)| Ok((key, to_vrl(value)?)))
+                .collect::<vector_common::Result<BTreeMap<_, _>>>()?,
+        ),
+        AvroValue::Date(days) => Value::from(
+            Utc.timestamp_opt(i64::from(days) * 86_400, 0)
+                .single()
+                .ok_or_else(|| format!("Date {} is out of range", days))?,
+        ),
+        AvroValue::TimestampMillis(millis) => Value::from(
+            Utc.timestamp_millis_opt(millis)
+                .single()
+                .ok_or_else(|| format!("Timestamp {} is out of range", millis))?,
+        ),
+        AvroValue::TimestampMicros(micros) => Value::from(
+            Utc.timestamp_opt(
+                micros.div_euclid(1_000_000),
+                (micros.rem_euclid(1_000_000) * 1_000) as u32,
+            )
+            .single()
+            .ok_or_else(|| format!("Timestamp {} is out of range", micros))?,
+        ),
+        value => Err(format!("Unsupported Avro value: {:?}", value))?,
+    };
+    Ok(value)
+}
+
+#[cfg(test)]
+mod tests {
+    use bytes::{BufMut, BytesMut};
+    use indoc::indoc;
+    use tokio_util::codec::Encoder;
+    use vrl::btreemap;
+
+    use super::*;
+    use crate::encoding::AvroSerializerConfig;
+
+    const SCHEMA: &str = indoc! {r#"
+        {
+            "type": "record",
+            "name": "Log",
+            "fields": [
+                { "name": "message", "type": "string" },
+                { "name": "count", "type": ["null", "long"] },
+                { "name": "tags", "type": { "type": "array", "items": "string" } },
+                { "name": "time", "type": { "type": "long", "logicalType": "timestamp-millis" } }
+            ]
+        }
+    "#};
+
+    fn encode(log: LogEvent) -> Bytes {
+        let mut serializer = AvroSerializerConfig::new(SCHEMA.to_owned())
+            .build()
+            .unwrap();
+        let mut bytes = BytesMut::new();
+        serializer.encode(Event::Log(log), &mut bytes).unwrap();
+        bytes.freeze()
+    }
+
+    fn test_log() -> LogEvent {
+        LogEvent::from(btreemap! {
+            "message" => "hello",
+            "count" => 3,
+            "tags" => vec!["a", "b"],
+            "time" => 1_600_000_000_123_i64,
+        })
+    }
+
+    #[test]
+    fn deserialize_avro() {
+        let input = encode(test_log());
+        let deserializer = AvroDeserializerConfig::new(SCHEMA.to_owned(), false)
+            .build()
+            .unwrap();
+
+        for namespace in [LogNamespace::Legacy, LogNamespace::Vector] {
+            let events = deserializer.parse(input.clone(), namespace).unwrap();
+            let mut events = events.into_iter();
+
+            {
+                let event = events.next().unwrap();
+                let log = event.as_log();
+                assert_eq!(log["message"], "hello".into());
+                assert_eq!(log["count"], 3.into());
+                assert_eq!(log["tags"], vec!["a", "b"].into());
+                assert_eq!(
+                    log["time"],
+                    Utc.timestamp_millis_opt(1_600_000_000_123).unwrap().into()
+                );
+                assert_eq!(
+                    log.get(log_schema().timestamp_key_target_path().unwrap())
+                        .is_some(),
+                    namespace == LogNamespace::Legacy
+                );
+            }
+
+            assert_eq!(events.next(), None);
+        }
+    }
+
+    #[test]
+    fn deserialize_avro_with_schema_id_prefix() {
+        let mut input = BytesMut::new();
+        input.put_u8(SCHEMA_ID_PREFIX_MAGIC_BYTE);
+        input.put_u32(42);
+        input.extend_from_slice(&encode(test_log()));
+        let deserializer = AvroDeserializerConfig::new(SCHEMA.to_owned(), true)
+            .build()
+            .unwrap();
+
+        let events = deserializer
+            .parse(input.freeze(), LogNamespace::Vector)
+            .unwrap();
+        assert_eq!(events[0].as_log()["message"], "hello".into());
+    }
+
+    #[test]
+    fn deserialize_avro_with_writer_schema() {
+        let reader_schema = indoc! {r#"
+            {
+                "type": "record",
+                "name": "Log",
+                "fields": [
+                    { "name": "message", "type": "string" },
+                    { "name": "level", "type": "string", "default": "info" }
+                ]
+            }
+        "#};
+        let input = encode(test_log());
+        let deserializer = AvroDeserializerConfig::new(reader_schema.to_owned(), false)
+            .build_with_writer_schema(SCHEMA)
+            .unwrap();
+
+        let events = deserializer.parse(input, LogNamespace::Vector).unwrap();
+        assert_eq!(
+            events[0].as_log().value(),
+            &Value::from(btreemap! {
+                "message" => "hello",
+                "level" => "info",
+            })
+        );
+    }
+
+    #[test]
+    fn deserialize_error_missing_schema_id_prefix() {
+        let input = encode(test_log());
+        let deserializer = AvroDeserializerConfig::new(SCHEMA.to_owned(), true)
+            .build()
+            .unwrap();
+
+        assert!(deserializer.parse(input, LogNamespace::Vector).is_err());
+    }
+
+    #[test]
+    fn deserialize_error_invalid_avro() {
+        let input = Bytes::from_static(b"\x02");
+        let deserializer = AvroDeserializerConfig::new(SCHEMA.to_owned(), false)
+            .build()
+            .unwrap();
+
+        for namespace in [LogNamespace::Legacy, LogNamespace::Vector] {
+            assert!(deserializer.parse(input.clone(), namespace).is_err());
+        }
+    }
+
+    #[test]
+    fn build_error_invalid_schema() {
+        let config = AvroDeserializerConfig::new("{ foo".to_owned(), false);
+        assert!(config.build().is_err());
+    }
+}
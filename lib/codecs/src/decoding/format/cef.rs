@@ -0,0 +1,341 @@
+use std::collections::BTreeMap;
+
+use bytes::Bytes;
+use chrono::Utc;
+use lookup::owned_value_path;
+use serde::{Deserialize, Serialize};
+use smallvec::{smallvec, SmallVec};
+use vector_core::{
+    config::{log_schema, DataType, LogNamespace},
+    event::{Event, LogEvent},
+    schema,
+};
+use vrl::value::{kind::Collection, Kind, Value};
+
+use super::Deserializer;
+use crate::cef::cef_fields::*;
+
+/// Config used to build a `CefDeserializer`.
+#[derive(Debug, Clone, Default, Deserialize, Serialize)]
+pub struct CefDeserializerConfig;
+
+impl CefDeserializerConfig {
+    /// Creates a new `CefDeserializerConfig`.
+    pub const fn new() -> Self {
+        Self
+    }
+
+    /// Build the `CefDeserializer` from this configuration.
+    pub const fn build(&self) -> CefDeserializer {
+        CefDeserializer
+    }
+
+    /// Return the type of event build by this deserializer.
+    pub fn output_type(&self) -> DataType {
+        DataType::Log
+    }
+
+    /// The schema produced by the deserializer.
+    pub fn schema_definition(&self, log_namespace: LogNamespace) -> schema::Definition {
+        let mut definition = schema::Definition::new_with_default_metadata(
+            Kind::object(Collection::empty()),
+            [log_namespace],
+        );
+        for field in HEADER_FIELDS {
+            definition =
+                definition.with_event_field(&owned_value_path!(field), Kind::bytes(), None);
+        }
+        // Every other field is an extension, whose value is always decoded as a string.
+        definition = definition.unknown_fields(Kind::bytes());
+
+        if log_namespace == LogNamespace::Legacy {
+            if let Some(timestamp_key) = log_schema().timestamp_key() {
+                definition = definition.try_with_field(
+                    timestamp_key,
+                    // The CEF decoder will try to insert a new `timestamp`-type value into the
+                    // "timestamp_key" field, but only if that field doesn't already exist.
+                    Kind::bytes().or_timestamp(),
+                    Some("timestamp"),
+                );
+            }
+        }
+        definition
+    }
+}
+
+/// Deserializer that builds `Event`s from a byte frame containing an [ArcSight CEF][cef] message.
+///
+/// The header fields are inserted into the event under the names defined in
+/// [`cef_fields`](crate::cef::cef_fields), and each extension is inserted as a field of its own.
+/// All values are decoded as strings. Anything before the `CEF:` prefix, such as a syslog header,
+/// is ignored.
+///
+/// [cef]: https://www.microfocus.com/documentation/arcsight/arcsight-smartconnectors/pdfdoc/common-event-format-v25/common-event-format-v25.pdf
+#[derive(Debug, Clone, Default)]
+pub struct CefDeserializer;
+
+impl CefDeserializer {
+    /// Creates a new `CefDeserializer`.
+    pub const fn new() -> Self {
+        Self
+    }
+}
+
+impl Deserializer for CefDeserializer {
+    fn parse(
+        &self,
+        bytes: Bytes,
+        log_namespace: LogNamespace,
+    ) -> vector_common::Result<SmallVec<[Event; 1]>> {
+        let input = String::from_utf8_lossy(&bytes);
+        if input.trim().is_empty() {
+            return Ok(smallvec![]);
+        }
+
+        let mut log = LogEvent::from(parse_cef(&input)?);
+        if log_namespace == LogNamespace::Legacy {
+            if let Some(timestamp_key) = log_schema().timestamp_key_target_path() {
+                if !log.contains(timestamp_key) {
+                    log.insert(timestamp_key, Utc::now());
+                }
+            }
+        }
+
+        Ok(smallvec![Event::Log(log)])
+    }
+}
+
+fn parse_cef(input: &str) -> vector_common::Result<BTreeMap<String, Value>> {
+    let start = input
+        .find(CEF_PREFIX)
+        .ok_or("Error parsing CEF: message doesn't contain the \"CEF:\" prefix")?;
+    let mut rest = &input[start + CEF_PREFIX.len()..];
+
+    let mut fields = BTreeMap::new();
+    for field in HEADER_FIELDS {
+        let end = find_unescaped(rest, b'|').ok_or_else(|| {
+            format!(
+                "Error parsing CEF: header is missing the \"{}\" field",
+                field
+            )
+        })?;
+        fields.insert(field.to_owned(), Value::from(unescape_header(&rest[..end])));
+        rest = &rest[end + 1..];
+    }
+
+    for (key, value) in parse_extension(rest)? {
+        fields.insert(key.to_owned(), Value::from(value));
+    }
+
+    Ok(fields)
+}
+
+/// Parses the `key=value` pairs of the extension.
+///
+/// Values may contain spaces, so a value ends where the next key starts. A key is a run of key
+/// characters preceded by whitespace and followed by an unescaped `=`.
+fn parse_extension(extension: &str) -> vector_common::Result<Vec<(&str, String)>> {
+    let bytes = extension.as_bytes();
+
+    // The ranges of the keys, from their start to their `=`.
+    let mut keys = Vec::new();
+    let mut escaped = false;
+    for (index, byte) in bytes.iter().enumerate() {
+        if escaped {
+            escaped = false;
+            continue;
+        }
+        match byte {
+            b'\\' => escaped = true,
+            b'=' => {
+                let start = bytes[..index]
+                    .iter()
+                    .rposition(|byte| !is_key_char(*byte))
+                    .map_or(0, |position| position + 1);
+                if start < index && (start == 0 || bytes[start - 1].is_ascii_whitespace()) {
+                    keys.push((start, index));
+                }
+            }
+            _ => {}
+        }
+    }
+
+    let leading = &extension[..keys.first().map_or(extension.len(), |(start, _)| *start)];
+    if !leading.trim().is_empty() {
+        return Err(format!(
+            "Error parsing CEF: found an extension value without a key: \"{}\"",
+            leading.trim()
+        )
+        .into());
+    }
+
+    Ok(keys
+        .iter()
+        .enumerate()
+        .map(|(index, (start, equals))| {
+            let end = keys
+                .get(index + 1)
+                .map_or(extension.len(), |(next, _)| *next);
+            let value = extension[equals + 1..end].trim_end_matches(|c: char| c.is_whitespace());
+            (&extension[*start..*equals], unescape_extension(value))
+        })
+        .collect())
+}
+
+const fn is_key_char(byte: u8) -> bool {
+    byte.is_ascii_alphanumeric() || matches!(byte, b'_' | b'.' | b'-' | b'[' | b']')
+}
+
+/// Returns the index of the first occurrence of `needle` that isn't escaped by a backslash.
+fn find_unescaped(haystack: &str, needle: u8) -> Option<usize> {
+    let mut escaped = false;
+    haystack.bytes().position(|byte| {
+        let found = !escaped && byte == needle;
+        escaped = !escaped && byte == b'\\';
+        found
+    })
+}
+
+/// Unescapes `\|` and `\\` in a header field. Other backslashes are kept as-is.
+fn unescape_header(field: &str) -> String {
+    unescape(field, |c| matches!(c, '|' | '\\').then_some(c))
+}
+
+/// Unescapes `\=`, `\\`, `\n` and `\r` in an extension value. `\|` is also accepted, even though
+/// pipes don't need to be escaped in extensions. Other backslashes are kept as-is.
+fn unescape_extension(value: &str) -> String {
+    unescape(value, |c| match c {
+        '=' | '\\' | '|' => Some(c),
+        'n' => Some('\n'),
+        'r' => Some('\r'),
+        _ => None,
+    })
+}
+
+fn unescape(input: &str, unescape_char: impl Fn(char) -> Option<char>) -> String {
+    let mut output = String::with_capacity(input.len());
+    let mut chars = input.chars();
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            output.push(c);
+            continue;
+        }
+        match chars.next() {
+            Some(next) => match unescape_char(next) {
+                Some(unescaped) => output.push(unescaped),
+                None => {
+                    output.push('\\');
+                    output.push(next);
+                }
+            },
+            None => output.push('\\'),
+        }
+    }
+    output
+}
+
+#[cfg(test)]
+mod tests {
+    use bytes::BytesMut;
+    use tokio_util::codec::Encoder;
+    use vrl::btreemap;
+
+    use super::*;
+    use crate::encoding::CefSerializer;
+
+    #[test]
+    fn deserialize_cef() {
+        let input = Bytes::from(
+            r"<134>Feb 27 15:04:49 fw01 CEF:0|Security|threat\|manager|1.0|100|worm successfully stopped|10|src=10.0.0.1 msg=Detected a threat\=worm\nNo action needed act=blocked",
+        );
+        let deserializer = CefDeserializer::new();
+
+        for namespace in [LogNamespace::Legacy, LogNamespace::Vector] {
+            let events = deserializer.parse(input.clone(), namespace).unwrap();
+            let mut events = events.into_iter();
+
+            {
+                let event = events.next().unwrap();
+                let log = event.as_log();
+                assert_eq!(log[CEF_VERSION], "0".into());
+                assert_eq!(log[DEVICE_VENDOR], "Security".into());
+                assert_eq!(log[DEVICE_PRODUCT], "threat|manager".into());
+                assert_eq!(log[DEVICE_VERSION], "1.0".into());
+                assert_eq!(log[DEVICE_EVENT_CLASS_ID], "100".into());
+                assert_eq!(log[NAME], "worm successfully stopped".into());
+                assert_eq!(log[SEVERITY], "10".into());
+                assert_eq!(log["src"], "10.0.0.1".into());
+                assert_eq!(
+                    log["msg"],
+                    "Detected a threat=worm\nNo action needed".into()
+                );
+                assert_eq!(log["act"], "blocked".into());
+                assert_eq!(
+                    log.get(log_schema().timestamp_key_target_path().unwrap())
+                        .is_some(),
+                    namespace == LogNamespace::Legacy
+                );
+            }
+
+            assert_eq!(events.next(), None);
+        }
+    }
+
+    #[test]
+    fn deserialize_cef_without_extension() {
+        let input = Bytes::from("CEF:1|Vendor|Product|2.0|login|User logged in|Low|");
+        let deserializer = CefDeserializer::new();
+
+        let events = deserializer.parse(input, LogNamespace::Vector).unwrap();
+        let log = events[0].as_log();
+        assert_eq!(log[CEF_VERSION], "1".into());
+        assert_eq!(log[SEVERITY], "Low".into());
+        assert_eq!(log.value().as_object().unwrap().len(), HEADER_FIELDS.len());
+    }
+
+    #[test]
+    fn deserialize_error_missing_header_field() {
+        let input = Bytes::from("CEF:0|Vendor|Product|1.0|100|name");
+        let deserializer = CefDeserializer::new();
+
+        let error = deserializer.parse(input, LogNamespace::Vector).unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "Error parsing CEF: header is missing the \"severity\" field"
+        );
+    }
+
+    #[test]
+    fn deserialize_error_value_without_key() {
+        let input = Bytes::from("CEF:0|Vendor|Product|1.0|100|name|5|oops src=10.0.0.1");
+        let deserializer = CefDeserializer::new();
+
+        for namespace in [LogNamespace::Legacy, LogNamespace::Vector] {
+            assert!(deserializer.parse(input.clone(), namespace).is_err());
+        }
+    }
+
+    #[test]
+    fn roundtrip_with_serializer() {
+        let log = LogEvent::from(btreemap! {
+            CEF_VERSION => "0",
+            DEVICE_VENDOR => "Vendor|Inc",
+            DEVICE_PRODUCT => r"C:\Product",
+            DEVICE_VERSION => "1.0",
+            DEVICE_EVENT_CLASS_ID => "100",
+            NAME => "name",
+            SEVERITY => "5",
+            "msg" => "a=b\r\nc d",
+            "path" => r"C:\Windows",
+        });
+        let mut bytes = BytesMut::new();
+        CefSerializer::new()
+            .encode(Event::Log(log.clone()), &mut bytes)
+            .unwrap();
+
+        let events = CefDeserializer::new()
+            .parse(bytes.freeze(), LogNamespace::Vector)
+            .unwrap();
+        assert_eq!(events[0].as_log().value(), log.value());
+    }
+}
@@ -0,0 +1,385 @@
+use std::collections::{BTreeMap, HashMap};
+
+use bytes::Bytes;
+use chrono::Utc;
+use smallvec::SmallVec;
+use vector_common::conversion::Conversion;
+use vector_config::configurable_component;
+use vector_core::{
+    config::{log_schema, DataType, LogNamespace},
+    event::{Event, LogEvent},
+    schema,
+};
+use vrl::{
+    compiler::TimeZone,
+    value::{Kind, Value},
+};
+
+use super::Deserializer;
+
+/// Config used to build a `CsvDeserializer`.
+#[configurable_component]
+#[derive(Debug, Clone)]
+pub struct CsvDeserializerConfig {
+    /// CSV-specific decoding options.
+    pub csv: CsvDeserializerOptions,
+}
+
+impl CsvDeserializerConfig {
+    /// Creates a new `CsvDeserializerConfig`.
+    pub const fn new(csv: CsvDeserializerOptions) -> Self {
+        Self { csv }
+    }
+
+    /// Build the `CsvDeserializer` from this configuration.
+    pub fn build(&self) -> vector_common::Result<CsvDeserializer> {
+        if self.csv.columns.is_empty() {
+            return Err("At least one CSV column must be specified".into());
+        }
+
+        let conversions = self
+            .csv
+            .columns
+            .iter()
+            .map(|column| {
+                self.csv
+                    .types
+                    .get(column)
+                    .map(|name| {
+                        Conversion::parse(name, TimeZone::default()).map_err(|error| {
+                            format!("Invalid type for CSV column '{}': {}", column, error)
+                        })
+                    })
+                    .transpose()
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        if let Some(column) = self
+            .csv
+            .types
+            .keys()
+            .find(|column| !self.csv.columns.contains(column))
+        {
+            return Err(format!("Type specified for unknown CSV column '{}'", column).into());
+        }
+
+        Ok(CsvDeserializer {
+            columns: self.csv.columns.clone(),
+            conversions,
+            delimiter: self.csv.delimiter,
+            skip_header: self.csv.skip_header,
+        })
+    }
+
+    /// Return the type of event build by this deserializer.
+    pub fn output_type(&self) -> DataType {
+        DataType::Log
+    }
+
+    /// The schema produced by the deserializer.
+    pub fn schema_definition(&self, log_namespace: LogNamespace) -> schema::Definition {
+        match log_namespace {
+            LogNamespace::Legacy => {
+                let mut definition =
+                    schema::Definition::empty_legacy_namespace().unknown_fields(Kind::any());
+
+                if let Some(timestamp_key) = log_schema().timestamp_key() {
+                    definition = definition.try_with_field(
+                        timestamp_key,
+                        // The CSV decoder will try to insert a new `timestamp`-type value into the
+                        // "timestamp_key" field, but only if that field doesn't already exist.
+                        Kind::any().or_timestamp(),
+                        Some("timestamp"),
+                    );
+                }
+                definition
+            }
+            LogNamespace::Vector => {
+                schema::Definition::new_with_default_metadata(Kind::any(), [log_namespace])
+            }
+        }
+    }
+}
+
+/// CSV-specific decoding options.
+#[configurable_component]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CsvDeserializerOptions {
+    /// The names of the columns, in the order in which they appear in each row.
+    ///
+    /// Each value of a row is inserted into the field named after its column. Rows with more
+    /// values than there are columns are rejected, while missing values at the end of a row are
+    /// left unset.
+    #[configurable(metadata(docs::examples = "timestamp", docs::examples = "message"))]
+    pub columns: Vec<String>,
+
+    /// The ASCII (7-bit) character that separates the values of a row.
+    #[serde(default = "default_delimiter", with = "vector_core::serde::ascii_char")]
+    pub delimiter: u8,
+
+    /// Whether to skip header rows.
+    ///
+    /// When enabled, rows whose values are identical to the configured column names are dropped.
+    #[serde(default)]
+    pub skip_header: bool,
+
+    /// The types to coerce the values of columns to.
+    ///
+    /// The available types are `bool`, `float`, `int`, `string`, `timestamp` (parsed from a number
+    /// of common formats), and `timestamp|<format>` (parsed with the given [`strptime`][strptime]
+    /// format). Empty values of typed columns are decoded as `null`. Values of columns without a
+    /// type are kept as strings.
+    ///
+    /// [strptime]: https://docs.rs/chrono/latest/chrono/format/strftime/index.html#specifiers
+    #[serde(default)]
+    #[configurable(metadata(docs::additional_props_description = "The type of a column."))]
+    #[configurable(metadata(docs::examples = "example_types()"))]
+    pub types: HashMap<String, String>,
+}
+
+const fn default_delimiter() -> u8 {
+    b','
+}
+
+fn example_types() -> HashMap<String, String> {
+    HashMap::from_iter([
+        ("status".to_string(), "int".to_string()),
+        ("duration".to_string(), "float".to_string()),
+        ("timestamp".to_string(), "timestamp|%F %T".to_string()),
+    ])
+}
+
+/// Deserializer that builds `Event`s from a byte frame containing CSV rows.
+#[derive(Debug, Clone)]
+pub struct CsvDeserializer {
+    columns: Vec<String>,
+    conversions: Vec<Option<Conversion>>,
+    delimiter: u8,
+    skip_header: bool,
+}
+
+impl CsvDeserializer {
+    fn is_header(&self, record: &csv::ByteRecord) -> bool {
+        self.skip_header
+            && record.len() == self.columns.len()
+            && record
+                .iter()
+                .zip(&self.columns)
+                .all(|(value, column)| value == column.as_bytes())
+    }
+
+    fn parse_record(&self, record: &csv::ByteRecord) -> vector_common::Result<LogEvent> {
+        if record.len() > self.columns.len() {
+            return Err(format!(
+                "CSV row has {} values, but only {} columns are configured",
+                record.len(),
+                self.columns.len()
+            )
+            .into());
+        }
+
+        let mut fields = BTreeMap::new();
+        for ((value, column), conversion) in record.iter().zip(&self.columns).zip(&self.conversions)
+        {
+            let value = match conversion {
+                Some(_) if value.is_empty() => Value::Null,
+                Some(conversion) => conversion
+                    .convert::<Value>(Bytes::copy_from_slice(value))
+                    .map_err(|error| {
+                        format!("Invalid value for CSV column '{}': {}", column, error)
+                    })?,
+                None => Value::from(Bytes::copy_from_slice(value)),
+            };
+            fields.insert(column.clone(), value);
+        }
+        Ok(LogEvent::from(fields))
+    }
+}
+
+impl Deserializer for CsvDeserializer {
+    fn parse(
+        &self,
+        bytes: Bytes,
+        log_namespace: LogNamespace,
+    ) -> vector_common::Result<SmallVec<[Event; 1]>> {
+        let mut reader = csv::ReaderBuilder::new()
+            .has_headers(false)
+            .flexible(true)
+            .delimiter(self.delimiter)
+            .from_reader(bytes.as_ref());
+
+        let mut events = SmallVec::new();
+        for record in reader.byte_records() {
+            let record = record.map_err(|error| format!("Error parsing CSV: {}", error))?;
+            if self.is_header(&record) {
+                continue;
+            }
+            events.push(Event::Log(self.parse_record(&record)?));
+        }
+
+        if log_namespace == LogNamespace::Legacy {
+            if let Some(timestamp_key) = log_schema().timestamp_key_target_path() {
+                let timestamp = Utc::now();
+                for event in &mut events {
+                    let log = event.as_mut_log();
+                    if !log.contains(timestamp_key) {
+                        log.insert(timestamp_key, timestamp);
+                    }
+                }
+            }
+        }
+
+        Ok(events)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bytes::BytesMut;
+    use chrono::{TimeZone as _, Utc};
+    use lookup::lookup_v2::ConfigTargetPath;
+    use tokio_util::codec::Encoder;
+    use vrl::btreemap;
+
+    use super::*;
+    use crate::encoding::{CsvSerializerConfig, CsvSerializerOptions};
+
+    fn build_deserializer(types: &[(&str, &str)], skip_header: bool) -> CsvDeserializer {
+        CsvDeserializerConfig::new(CsvDeserializerOptions {
+            columns: vec!["time".into(), "status".into(), "message".into()],
+            delimiter: b',',
+            skip_header,
+            types: types
+                .iter()
+                .map(|(column, ty)| (column.to_string(), ty.to_string()))
+                .collect(),
+        })
+        .build()
+        .unwrap()
+    }
+
+    #[test]
+    fn deserialize_csv() {
+        let input = Bytes::from("2023-02-27 15:04:49,200,\"hello, world\"");
+        let deserializer = build_deserializer(&[], false);
+
+        for namespace in [LogNamespace::Legacy, LogNamespace::Vector] {
+            let events = deserializer.parse(input.clone(), namespace).unwrap();
+            let mut events = events.into_iter();
+
+            {
+                let event = events.next().unwrap();
+                let log = event.as_log();
+                assert_eq!(log["time"], "2023-02-27 15:04:49".into());
+                assert_eq!(log["status"], "200".into());
+                assert_eq!(log["message"], "hello, world".into());
+                assert_eq!(
+                    log.get(log_schema().timestamp_key_target_path().unwrap())
+                        .is_some(),
+                    namespace == LogNamespace::Legacy
+                );
+            }
+
+            assert_eq!(events.next(), None);
+        }
+    }
+
+    #[test]
+    fn deserialize_csv_with_types() {
+        let input = Bytes::from("2023-02-27T15:04:49Z,,hello\n2023-02-27T15:04:50Z,404,");
+        let deserializer = build_deserializer(&[("time", "timestamp"), ("status", "int")], false);
+
+        let events = deserializer.parse(input, LogNamespace::Vector).unwrap();
+        assert_eq!(events.len(), 2);
+
+        let log = events[0].as_log();
+        assert_eq!(
+            log["time"],
+            Utc.with_ymd_and_hms(2023, 2, 27, 15, 4, 49).unwrap().into()
+        );
+        assert_eq!(log["status"], Value::Null);
+        assert_eq!(log["message"], "hello".into());
+
+        let log = events[1].as_log();
+        assert_eq!(log["status"], 404.into());
+        assert_eq!(log["message"], "".into());
+    }
+
+    #[test]
+    fn deserialize_csv_skip_header() {
+        let input = Bytes::from("time,status,message\nnow,200,hello");
+        let deserializer = build_deserializer(&[], true);
+
+        let events = deserializer.parse(input, LogNamespace::Vector).unwrap();
+        assert_eq!(events.len(), 1);
+        assert_eq!(events[0].as_log()["message"], "hello".into());
+    }
+
+    #[test]
+    fn deserialize_error_too_many_values() {
+        let input = Bytes::from("now,200,hello,extra");
+        let deserializer = build_deserializer(&[], false);
+
+        let error = deserializer.parse(input, LogNamespace::Vector).unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "CSV row has 4 values, but only 3 columns are configured"
+        );
+    }
+
+    #[test]
+    fn deserialize_error_invalid_type() {
+        let input = Bytes::from("now,ok,hello");
+        let deserializer = build_deserializer(&[("status", "int")], false);
+
+        assert!(deserializer.parse(input, LogNamespace::Vector).is_err());
+    }
+
+    #[test]
+    fn build_error_on_empty_columns() {
+        let config = CsvDeserializerConfig::new(CsvDeserializerOptions {
+            columns: vec![],
+            delimiter: b',',
+            skip_header: false,
+            types: HashMap::new(),
+        });
+
+        let error = config.build().unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "At least one CSV column must be specified"
+        );
+    }
+
+    #[test]
+    fn roundtrip_with_serializer() {
+        let mut serializer = CsvSerializerConfig::new(CsvSerializerOptions {
+            fields: ["time", "status", "message"]
+                .into_iter()
+                .map(|field| ConfigTargetPath::try_from(field.to_string()).unwrap())
+                .collect(),
+        })
+        .build()
+        .unwrap();
+        let deserializer = build_deserializer(&[("status", "int")], false);
+
+        let mut bytes = BytesMut::new();
+        serializer
+            .encode(
+                Event::Log(LogEvent::from(btreemap! {
+                    "time" => "now",
+                    "status" => 200,
+                    "message" => "quote \" and, comma",
+                })),
+                &mut bytes,
+            )
+            .unwrap();
+
+        let events = deserializer
+            .parse(bytes.freeze(), LogNamespace::Vector)
+            .unwrap();
+        let log = events[0].as_log();
+        assert_eq!(log["status"], 200.into());
+        assert_eq!(log["message"], "quote \" and, comma".into());
+    }
+}
@@ -0,0 +1,282 @@
+use std::collections::BTreeMap;
+
+use bytes::Bytes;
+use chrono::Utc;
+use lookup::owned_value_path;
+use serde::{Deserialize, Serialize};
+use smallvec::{smallvec, SmallVec};
+use vector_core::{
+    config::{log_schema, DataType, LogNamespace},
+    event::{Event, LogEvent},
+    schema,
+};
+use vrl::value::{kind::Collection, Kind, Value};
+
+use super::Deserializer;
+use crate::cef::leef_fields::*;
+
+/// The attribute delimiter of LEEF 1.0 messages, and of LEEF 2.0 messages that don't specify one.
+const DEFAULT_DELIMITER: char = '\t';
+
+/// Config used to build a `LeefDeserializer`.
+#[derive(Debug, Clone, Default, Deserialize, Serialize)]
+pub struct LeefDeserializerConfig;
+
+impl LeefDeserializerConfig {
+    /// Creates a new `LeefDeserializerConfig`.
+    pub const fn new() -> Self {
+        Self
+    }
+
+    /// Build the `LeefDeserializer` from this configuration.
+    pub const fn build(&self) -> LeefDeserializer {
+        LeefDeserializer
+    }
+
+    /// Return the type of event build by this deserializer.
+    pub fn output_type(&self) -> DataType {
+        DataType::Log
+    }
+
+    /// The schema produced by the deserializer.
+    pub fn schema_definition(&self, log_namespace: LogNamespace) -> schema::Definition {
+        let mut definition = schema::Definition::new_with_default_metadata(
+            Kind::object(Collection::empty()),
+            [log_namespace],
+        );
+        for field in HEADER_FIELDS {
+            definition =
+                definition.with_event_field(&owned_value_path!(field), Kind::bytes(), None);
+        }
+        // Every other field is an attribute, whose value is always decoded as a string.
+        definition = definition.unknown_fields(Kind::bytes());
+
+        if log_namespace == LogNamespace::Legacy {
+            if let Some(timestamp_key) = log_schema().timestamp_key() {
+                definition = definition.try_with_field(
+                    timestamp_key,
+                    // The LEEF decoder will try to insert a new `timestamp`-type value into the
+                    // "timestamp_key" field, but only if that field doesn't already exist.
+                    Kind::bytes().or_timestamp(),
+                    Some("timestamp"),
+                );
+            }
+        }
+        definition
+    }
+}
+
+/// Deserializer that builds `Event`s from a byte frame containing an [IBM QRadar LEEF][leef]
+/// message.
+///
+/// The header fields are inserted into the event under the names defined in
+/// [`leef_fields`](crate::cef::leef_fields), and each attribute is inserted as a field of its own.
+/// All values are decoded as strings. Both LEEF 1.0 messages, whose attributes are separated by
+/// tabs, and LEEF 2.0 messages, which may specify a custom delimiter in their header, are
+/// supported. Anything before the `LEEF:` prefix, such as a syslog header, is ignored.
+///
+/// [leef]: https://www.ibm.com/docs/en/dsm?topic=leef-overview
+#[derive(Debug, Clone, Default)]
+pub struct LeefDeserializer;
+
+impl LeefDeserializer {
+    /// Creates a new `LeefDeserializer`.
+    pub const fn new() -> Self {
+        Self
+    }
+}
+
+impl Deserializer for LeefDeserializer {
+    fn parse(
+        &self,
+        bytes: Bytes,
+        log_namespace: LogNamespace,
+    ) -> vector_common::Result<SmallVec<[Event; 1]>> {
+        let input = String::from_utf8_lossy(&bytes);
+        if input.trim().is_empty() {
+            return Ok(smallvec![]);
+        }
+
+        let mut log = LogEvent::from(parse_leef(&input)?);
+        if log_namespace == LogNamespace::Legacy {
+            if let Some(timestamp_key) = log_schema().timestamp_key_target_path() {
+                if !log.contains(timestamp_key) {
+                    log.insert(timestamp_key, Utc::now());
+                }
+            }
+        }
+
+        Ok(smallvec![Event::Log(log)])
+    }
+}
+
+fn parse_leef(input: &str) -> vector_common::Result<BTreeMap<String, Value>> {
+    let start = input
+        .find(LEEF_PREFIX)
+        .ok_or("Error parsing LEEF: message doesn't contain the \"LEEF:\" prefix")?;
+    let mut rest = &input[start + LEEF_PREFIX.len()..];
+
+    let mut fields = BTreeMap::new();
+    for field in HEADER_FIELDS {
+        let (value, remainder) = rest.split_once('|').ok_or_else(|| {
+            format!(
+                "Error parsing LEEF: header is missing the \"{}\" field",
+                field
+            )
+        })?;
+        fields.insert(field.to_owned(), Value::from(value));
+        rest = remainder;
+    }
+
+    // LEEF 2.0 headers have an optional sixth field containing the attribute delimiter.
+    let mut delimiter = DEFAULT_DELIMITER;
+    if fields[LEEF_VERSION]
+        .as_str()
+        .map_or(false, |version| version.starts_with('2'))
+    {
+        if let Some((field, remainder)) = rest.split_once('|') {
+            if let Some(parsed) = parse_delimiter(field) {
+                delimiter = parsed;
+                rest = remainder;
+            }
+        }
+    }
+
+    for attribute in rest
+        .split(delimiter)
+        .filter(|attribute| !attribute.is_empty())
+    {
+        let (key, value) = attribute.split_once('=').ok_or_else(|| {
+            format!(
+                "Error parsing LEEF: found an attribute without a value: \"{}\"",
+                attribute
+            )
+        })?;
+        fields.insert(key.to_owned(), Value::from(value));
+    }
+
+    Ok(fields)
+}
+
+/// Parses the delimiter field of a LEEF 2.0 header, which is either a single character, or its
+/// code as a hex value prefixed by `x` or `0x`. An empty field stands for the default delimiter.
+fn parse_delimiter(field: &str) -> Option<char> {
+    let mut chars = field.chars();
+    match (chars.next(), chars.next()) {
+        (None, _) => Some(DEFAULT_DELIMITER),
+        (Some(c), None) => Some(c),
+        _ if field == r"\t" => Some('\t'),
+        _ => field
+            .strip_prefix("0x")
+            .or_else(|| field.strip_prefix('x'))
+            .and_then(|hex| u32::from_str_radix(hex, 16).ok())
+            .and_then(char::from_u32),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bytes::BytesMut;
+    use tokio_util::codec::Encoder;
+    use vrl::btreemap;
+
+    use super::*;
+    use crate::encoding::{LeefSerializerConfig, LeefSerializerOptions};
+
+    #[test]
+    fn deserialize_leef_1() {
+        let input = Bytes::from(
+            "<13>Feb 27 15:04:49 ids01 LEEF:1.0|Vendor|IDS|1.2|portscan|src=10.0.0.1\tdst=10.0.0.2\tmsg=a = b",
+        );
+        let deserializer = LeefDeserializer::new();
+
+        for namespace in [LogNamespace::Legacy, LogNamespace::Vector] {
+            let events = deserializer.parse(input.clone(), namespace).unwrap();
+            let mut events = events.into_iter();
+
+            {
+                let event = events.next().unwrap();
+                let log = event.as_log();
+                assert_eq!(log[LEEF_VERSION], "1.0".into());
+                assert_eq!(log[DEVICE_VENDOR], "Vendor".into());
+                assert_eq!(log[DEVICE_PRODUCT], "IDS".into());
+                assert_eq!(log[DEVICE_VERSION], "1.2".into());
+                assert_eq!(log[EVENT_ID], "portscan".into());
+                assert_eq!(log["src"], "10.0.0.1".into());
+                assert_eq!(log["dst"], "10.0.0.2".into());
+                assert_eq!(log["msg"], "a = b".into());
+                assert_eq!(
+                    log.get(log_schema().timestamp_key_target_path().unwrap())
+                        .is_some(),
+                    namespace == LogNamespace::Legacy
+                );
+            }
+
+            assert_eq!(events.next(), None);
+        }
+    }
+
+    #[test]
+    fn deserialize_leef_2_with_delimiter() {
+        let deserializer = LeefDeserializer::new();
+
+        for input in [
+            "LEEF:2.0|Vendor|IDS|1.2|portscan|^|src=10.0.0.1^dst=10.0.0.2",
+            "LEEF:2.0|Vendor|IDS|1.2|portscan|x5E|src=10.0.0.1^dst=10.0.0.2",
+            "LEEF:2.0|Vendor|IDS|1.2|portscan|0x5e|src=10.0.0.1^dst=10.0.0.2",
+        ] {
+            let events = deserializer
+                .parse(Bytes::from(input), LogNamespace::Vector)
+                .unwrap();
+            let log = events[0].as_log();
+            assert_eq!(log[LEEF_VERSION], "2.0".into());
+            assert_eq!(log["src"], "10.0.0.1".into());
+            assert_eq!(log["dst"], "10.0.0.2".into());
+        }
+    }
+
+    #[test]
+    fn deserialize_leef_2_without_delimiter() {
+        let input = Bytes::from("LEEF:2.0|Vendor|IDS|1.2|portscan|src=10.0.0.1\tdst=10.0.0.2");
+        let deserializer = LeefDeserializer::new();
+
+        let events = deserializer.parse(input, LogNamespace::Vector).unwrap();
+        let log = events[0].as_log();
+        assert_eq!(log["src"], "10.0.0.1".into());
+        assert_eq!(log["dst"], "10.0.0.2".into());
+    }
+
+    #[test]
+    fn deserialize_error_attribute_without_value() {
+        let input = Bytes::from("LEEF:1.0|Vendor|IDS|1.2|portscan|src=10.0.0.1\toops");
+        let deserializer = LeefDeserializer::new();
+
+        for namespace in [LogNamespace::Legacy, LogNamespace::Vector] {
+            assert!(deserializer.parse(input.clone(), namespace).is_err());
+        }
+    }
+
+    #[test]
+    fn roundtrip_with_serializer() {
+        let log = LogEvent::from(btreemap! {
+            LEEF_VERSION => "2.0",
+            DEVICE_VENDOR => "Vendor",
+            DEVICE_PRODUCT => "IDS",
+            DEVICE_VERSION => "1.2",
+            EVENT_ID => "portscan",
+            "msg" => "tab\tseparated",
+            "src" => "10.0.0.1",
+        });
+        let mut serializer =
+            LeefSerializerConfig::new(LeefSerializerOptions { delimiter: b'^' }).build();
+        let mut bytes = BytesMut::new();
+        serializer
+            .encode(Event::Log(log.clone()), &mut bytes)
+            .unwrap();
+
+        let events = LeefDeserializer::new()
+            .parse(bytes.freeze(), LogNamespace::Vector)
+            .unwrap();
+        assert_eq!(events[0].as_log().value(), log.value());
+    }
+}
@@ -0,0 +1,230 @@
+use std::{collections::BTreeMap, iter::Peekable, str::Chars};
+
+use bytes::Bytes;
+use chrono::Utc;
+use serde::{Deserialize, Serialize};
+use smallvec::{smallvec, SmallVec};
+use vector_core::{
+    config::{log_schema, DataType, LogNamespace},
+    event::{Event, LogEvent},
+    schema,
+};
+use vrl::value::{kind::Collection, Kind, Value};
+
+use super::Deserializer;
+
+/// Config used to build a `LogfmtDeserializer`.
+#[derive(Debug, Clone, Default, Deserialize, Serialize)]
+pub struct LogfmtDeserializerConfig;
+
+impl LogfmtDeserializerConfig {
+    /// Creates a new `LogfmtDeserializerConfig`.
+    pub const fn new() -> Self {
+        Self
+    }
+
+    /// Build the `LogfmtDeserializer` from this configuration.
+    pub const fn build(&self) -> LogfmtDeserializer {
+        LogfmtDeserializer
+    }
+
+    /// Return the type of event build by this deserializer.
+    pub fn output_type(&self) -> DataType {
+        DataType::Log
+    }
+
+    /// The schema produced by the deserializer.
+    pub fn schema_definition(&self, log_namespace: LogNamespace) -> schema::Definition {
+        match log_namespace {
+            LogNamespace::Legacy => {
+                let mut definition = schema::Definition::empty_legacy_namespace()
+                    .unknown_fields(Kind::bytes().or_boolean());
+
+                if let Some(timestamp_key) = log_schema().timestamp_key() {
+                    definition = definition.try_with_field(
+                        timestamp_key,
+                        // The logfmt decoder will try to insert a new `timestamp`-type value into the
+                        // "timestamp_key" field, but only if that field doesn't already exist.
+                        Kind::bytes().or_boolean().or_timestamp(),
+                        Some("timestamp"),
+                    );
+                }
+                definition
+            }
+            LogNamespace::Vector => schema::Definition::new_with_default_metadata(
+                Kind::object(Collection::empty().with_unknown(Kind::bytes().or_boolean())),
+                [log_namespace],
+            ),
+        }
+    }
+}
+
+/// Deserializer that builds `Event`s from a byte frame containing a [logfmt][logfmt] message.
+///
+/// Each `key=value` pair of the message is inserted as a field of the event, with the value as a
+/// string. Keys without a value are inserted with the value `true`. Values containing whitespace
+/// are expected to be quoted, with `"` and `\` escaped by a backslash.
+///
+/// [logfmt]: https://brandur.org/logfmt
+#[derive(Debug, Clone, Default)]
+pub struct LogfmtDeserializer;
+
+impl LogfmtDeserializer {
+    /// Creates a new `LogfmtDeserializer`.
+    pub const fn new() -> Self {
+        Self
+    }
+}
+
+impl Deserializer for LogfmtDeserializer {
+    fn parse(
+        &self,
+        bytes: Bytes,
+        log_namespace: LogNamespace,
+    ) -> vector_common::Result<SmallVec<[Event; 1]>> {
+        let input = String::from_utf8_lossy(&bytes);
+        if input.trim().is_empty() {
+            return Ok(smallvec![]);
+        }
+
+        let mut log = LogEvent::from(parse_logfmt(&input)?);
+        if log_namespace == LogNamespace::Legacy {
+            if let Some(timestamp_key) = log_schema().timestamp_key_target_path() {
+                if !log.contains(timestamp_key) {
+                    log.insert(timestamp_key, Utc::now());
+                }
+            }
+        }
+
+        Ok(smallvec![Event::Log(log)])
+    }
+}
+
+fn parse_logfmt(input: &str) -> vector_common::Result<BTreeMap<String, Value>> {
+    let mut fields = BTreeMap::new();
+    let mut chars = input.chars().peekable();
+
+    loop {
+        while chars.next_if(|c| c.is_whitespace()).is_some() {}
+        if chars.peek().is_none() {
+            break;
+        }
+
+        let key = parse_token(&mut chars, true)?;
+        if key.is_empty() {
+            return Err("Error parsing logfmt: found a value without a key".into());
+        }
+        let value = if chars.next_if_eq(&'=').is_some() {
+            Value::from(parse_token(&mut chars, false)?)
+        } else {
+            Value::Boolean(true)
+        };
+        fields.insert(key, value);
+    }
+
+    Ok(fields)
+}
+
+/// Parses a key, or a value, which can either be quoted or end at the next whitespace. Unquoted
+/// keys also end at the next `=`.
+fn parse_token(chars: &mut Peekable<Chars<'_>>, is_key: bool) -> vector_common::Result<String> {
+    let mut token = String::new();
+
+    if chars.next_if_eq(&'"').is_some() {
+        loop {
+            match chars.next() {
+                Some('"') => break,
+                Some('\\') => match chars.next() {
+                    Some('n') => token.push('\n'),
+                    Some('r') => token.push('\r'),
+                    Some('t') => token.push('\t'),
+                    Some(c) => token.push(c),
+                    None => break,
+                },
+                Some(c) => token.push(c),
+                None => return Err("Error parsing logfmt: unterminated quoted string".into()),
+            }
+        }
+    } else {
+        while let Some(c) = chars.next_if(|c| !c.is_whitespace() && !(is_key && *c == '=')) {
+            token.push(c);
+        }
+    }
+
+    Ok(token)
+}
+
+#[cfg(test)]
+mod tests {
+    use bytes::BytesMut;
+    use tokio_util::codec::Encoder;
+    use vrl::btreemap;
+
+    use super::*;
+    use crate::encoding::LogfmtSerializer;
+
+    #[test]
+    fn deserialize_logfmt() {
+        let input = Bytes::from(r#"level=info msg="hello \"world\"" debug path=/a=b empty="#);
+        let deserializer = LogfmtDeserializer::new();
+
+        for namespace in [LogNamespace::Legacy, LogNamespace::Vector] {
+            let events = deserializer.parse(input.clone(), namespace).unwrap();
+            let mut events = events.into_iter();
+
+            {
+                let event = events.next().unwrap();
+                let log = event.as_log();
+                assert_eq!(log["level"], "info".into());
+                assert_eq!(log["msg"], r#"hello "world""#.into());
+                assert_eq!(log["debug"], true.into());
+                assert_eq!(log["path"], "/a=b".into());
+                assert_eq!(log["empty"], "".into());
+                assert_eq!(
+                    log.get(log_schema().timestamp_key_target_path().unwrap())
+                        .is_some(),
+                    namespace == LogNamespace::Legacy
+                );
+            }
+
+            assert_eq!(events.next(), None);
+        }
+    }
+
+    #[test]
+    fn deserialize_empty_frame() {
+        let deserializer = LogfmtDeserializer::new();
+
+        for namespace in [LogNamespace::Legacy, LogNamespace::Vector] {
+            let events = deserializer.parse(Bytes::from("  "), namespace).unwrap();
+            assert!(events.is_empty());
+        }
+    }
+
+    #[test]
+    fn deserialize_error_unterminated_quote() {
+        let input = Bytes::from(r#"msg="hello"#);
+        let deserializer = LogfmtDeserializer::new();
+
+        for namespace in [LogNamespace::Legacy, LogNamespace::Vector] {
+            assert!(deserializer.parse(input.clone(), namespace).is_err());
+        }
+    }
+
+    #[test]
+    fn roundtrip_with_serializer() {
+        let log = LogEvent::from(btreemap! {
+            "msg" => "with spaces and \"quotes\"",
+            "level" => "warn",
+        });
+        let mut bytes = BytesMut::new();
+        LogfmtSerializer::new()
+            .encode(Event::Log(log.clone()), &mut bytes)
+            .unwrap();
+
+        let events = LogfmtDeserializer::new()
+            .parse(bytes.freeze(), LogNamespace::Vector)
+            .unwrap();
+        assert_eq!(events[0].as_log().value(), log.value());
+    }
+}
@@ -3,24 +3,34 @@
 
 #![deny(missing_docs)]
 
+mod avro;
 mod bytes;
+mod cef;
+mod csv;
 mod gelf;
 mod json;
+mod leef;
+mod logfmt;
 mod native;
 mod native_json;
 mod protobuf;
 #[cfg(feature = "syslog")]
 mod syslog;
 
+pub use self::csv::{CsvDeserializer, CsvDeserializerConfig, CsvDeserializerOptions};
 use ::bytes::Bytes;
+pub use avro::{AvroDeserializer, AvroDeserializerConfig, AvroDeserializerOptions};
+pub use cef::{CefDeserializer, CefDeserializerConfig};
 use dyn_clone::DynClone;
 pub use gelf::{GelfDeserializer, GelfDeserializerConfig, GelfDeserializerOptions};
 pub use json::{JsonDeserializer, JsonDeserializerConfig, JsonDeserializerOptions};
+pub use leef::{LeefDeserializer, LeefDeserializerConfig};
+pub use logfmt::{LogfmtDeserializer, LogfmtDeserializerConfig};
 pub use native::{NativeDeserializer, NativeDeserializerConfig};
 pub use native_json::{
     NativeJsonDeserializer, NativeJsonDeserializerConfig, NativeJsonDeserializerOptions,
 };
-pub use protobuf::{ProtobufDeserializer, ProtobufDeserializerConfig};
+pub use protobuf::{ProtobufDeserializer, ProtobufDeserializerConfig, ProtobufDeserializerOptions};
 use smallvec::SmallVec;
 #[cfg(feature = "syslog")]
 pub use syslog::{SyslogDeserializer, SyslogDeserializerConfig, SyslogDeserializerOptions};
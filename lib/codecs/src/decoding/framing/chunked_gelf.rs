@@ -0,0 +1,647 @@
+use std::{
+    collections::{hash_map::Entry, HashMap},
+    io::Read,
+    sync::{Arc, Mutex},
+    time::{Duration, Instant},
+};
+
+use bytes::{Buf, Bytes, BytesMut};
+use derivative::Derivative;
+use flate2::read::{MultiGzDecoder, ZlibDecoder};
+use snafu::Snafu;
+use tokio_util::codec::Decoder;
+use tracing::{trace, warn};
+use vector_config::configurable_component;
+
+use super::{BoxedFramingError, FramingError};
+use crate::{
+    decoding::StreamDecodingError,
+    gelf::gelf_chunks::{HEADER_LENGTH, MAGIC, MAX_TOTAL_CHUNKS},
+};
+
+const GZIP_MAGIC: [u8; 2] = [0x1f, 0x8b];
+
+/// Errors that can occur while reassembling chunked GELF messages.
+#[derive(Debug, Snafu)]
+pub enum ChunkedGelfDecoderError {
+    #[snafu(display(
+        "Received a GELF chunk too short to hold a chunk header. length = {}",
+        length
+    ))]
+    InvalidChunkHeader { length: usize },
+    #[snafu(display(
+        "Received GELF chunk {} of {} for message {}, expected a sequence number below the sequence count and at most {} chunks",
+        sequence_number,
+        sequence_count,
+        message_id,
+        MAX_TOTAL_CHUNKS
+    ))]
+    InvalidSequence {
+        message_id: u64,
+        sequence_number: u8,
+        sequence_count: u8,
+    },
+    #[snafu(display(
+        "Received a GELF chunk with a sequence count of {} for message {}, which previously had a sequence count of {}. Discarding the message.",
+        sequence_count,
+        message_id,
+        expected_sequence_count
+    ))]
+    SequenceCountMismatch {
+        message_id: u64,
+        sequence_count: u8,
+        expected_sequence_count: usize,
+    },
+    #[snafu(display(
+        "Discarding GELF chunk of message {}, as {} incomplete messages are already pending.",
+        message_id,
+        pending_messages_limit
+    ))]
+    PendingMessagesLimitReached {
+        message_id: u64,
+        pending_messages_limit: usize,
+    },
+    #[snafu(display(
+        "Discarding GELF message larger than max_length. max_length = {}",
+        max_length
+    ))]
+    MaxLengthExceeded { max_length: usize },
+    #[snafu(display("Failed to decompress GELF message: {}", source))]
+    Decompression { source: std::io::Error },
+}
+
+impl StreamDecodingError for ChunkedGelfDecoderError {
+    fn can_continue(&self) -> bool {
+        true
+    }
+}
+
+impl FramingError for ChunkedGelfDecoderError {}
+
+impl From<ChunkedGelfDecoderError> for BoxedFramingError {
+    fn from(error: ChunkedGelfDecoderError) -> Self {
+        Box::new(error)
+    }
+}
+
+/// Config used to build a `ChunkedGelfDecoder`.
+#[configurable_component]
+#[derive(Debug, Clone, Default)]
+pub struct ChunkedGelfDecoderConfig {
+    /// Options for the chunked GELF decoder.
+    #[serde(
+        default,
+        skip_serializing_if = "vector_core::serde::skip_serializing_if_default"
+    )]
+    pub chunked_gelf: ChunkedGelfDecoderOptions,
+}
+
+impl ChunkedGelfDecoderConfig {
+    /// Build the `ChunkedGelfDecoder` from this configuration.
+    pub fn build(&self) -> ChunkedGelfDecoder {
+        ChunkedGelfDecoder::new(self.chunked_gelf.clone())
+    }
+}
+
+/// Options for building a `ChunkedGelfDecoder`.
+#[configurable_component]
+#[derive(Clone, Debug, Derivative, PartialEq, Eq)]
+#[derivative(Default)]
+pub struct ChunkedGelfDecoderOptions {
+    /// The time to wait for all the chunks of a message to be received.
+    ///
+    /// The chunks of messages that are still incomplete after this amount of time, counted from
+    /// their first chunk, are discarded.
+    #[serde(default = "default_timeout_secs")]
+    #[derivative(Default(value = "default_timeout_secs()"))]
+    #[configurable(metadata(docs::type_unit = "seconds"))]
+    #[configurable(metadata(docs::human_name = "Timeout"))]
+    pub timeout_secs: u64,
+
+    /// The maximum number of incomplete messages to hold at once.
+    ///
+    /// Once reached, the chunks of new messages are discarded until some of the pending messages
+    /// complete or time out. Along with `max_length`, this bounds the memory used to reassemble
+    /// messages.
+    #[serde(default = "default_pending_messages_limit")]
+    #[derivative(Default(value = "default_pending_messages_limit()"))]
+    pub pending_messages_limit: usize,
+
+    /// The maximum length of a message, in bytes.
+    ///
+    /// Messages whose chunks add up to more than this length, or that decompress to more than this
+    /// length, are discarded.
+    #[serde(default = "default_max_length")]
+    #[derivative(Default(value = "default_max_length()"))]
+    #[configurable(metadata(docs::type_unit = "bytes"))]
+    pub max_length: usize,
+
+    /// The decompression applied to messages once reassembled.
+    #[configurable(derived)]
+    #[serde(default)]
+    pub decompression: ChunkedGelfDecompression,
+}
+
+const fn default_timeout_secs() -> u64 {
+    5
+}
+
+const fn default_pending_messages_limit() -> usize {
+    1000
+}
+
+const fn default_max_length() -> usize {
+    8 * 1024 * 1024
+}
+
+/// The decompression applied to GELF messages.
+#[configurable_component]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum ChunkedGelfDecompression {
+    /// Detects the compression of each message from its leading bytes, and decompresses
+    /// [Gzip][gzip] and [Zlib][zlib] messages.
+    ///
+    /// [gzip]: https://www.gzip.org/
+    /// [zlib]: https://www.zlib.net/
+    #[default]
+    Auto,
+
+    /// Decompresses messages with [Gzip][gzip].
+    ///
+    /// [gzip]: https://www.gzip.org/
+    Gzip,
+
+    /// Decompresses messages with [Zlib][zlib].
+    ///
+    /// [zlib]: https://www.zlib.net/
+    Zlib,
+
+    /// Passes messages through as-is.
+    None,
+}
+
+impl ChunkedGelfDecompression {
+    /// Picks the decompression of a message from its leading bytes.
+    fn detect(message: &[u8]) -> Self {
+        match message {
+            [a, b, ..] if [*a, *b] == GZIP_MAGIC => Self::Gzip,
+            // The first byte of a Zlib stream holds the deflate method and window size, and
+            // the first two bytes form a multiple of 31.
+            [a, b, ..] if (*a & 0x0f) == 0x08 && u16::from_be_bytes([*a, *b]) % 31 == 0 => {
+                Self::Zlib
+            }
+            _ => Self::None,
+        }
+    }
+}
+
+/// A GELF message whose chunks are being received.
+#[derive(Debug)]
+struct PendingMessage {
+    chunks: Vec<Option<Bytes>>,
+    received_chunks: usize,
+    length: usize,
+    first_chunk_at: Instant,
+}
+
+impl PendingMessage {
+    fn new(sequence_count: u8) -> Self {
+        Self {
+            chunks: vec![None; sequence_count as usize],
+            received_chunks: 0,
+            length: 0,
+            first_chunk_at: Instant::now(),
+        }
+    }
+}
+
+/// A decoder for [chunked GELF][chunked_gelf] messages, received one chunk per message.
+///
+/// Messages that are not chunked are passed through as-is, and every message is decompressed
+/// according to the configured decompression.
+///
+/// Clones of the decoder share their pending messages, as message-based sources clone their
+/// decoder for each message they receive.
+///
+/// [chunked_gelf]: https://go2docs.graylog.org/current/getting_in_log_data/gelf.html#GELFviaUDP
+#[derive(Debug, Clone)]
+pub struct ChunkedGelfDecoder {
+    pending_messages: Arc<Mutex<HashMap<u64, PendingMessage>>>,
+    timeout: Duration,
+    pending_messages_limit: usize,
+    max_length: usize,
+    decompression: ChunkedGelfDecompression,
+}
+
+impl ChunkedGelfDecoder {
+    /// Creates a new `ChunkedGelfDecoder`.
+    pub fn new(options: ChunkedGelfDecoderOptions) -> Self {
+        Self {
+            pending_messages: Arc::new(Mutex::new(HashMap::new())),
+            timeout: Duration::from_secs(options.timeout_secs),
+            pending_messages_limit: options.pending_messages_limit,
+            max_length: options.max_length,
+            decompression: options.decompression,
+        }
+    }
+
+    /// Adds a chunk to its message, returning the message once all of its chunks were received.
+    fn decode_chunk(&self, mut chunk: Bytes) -> Result<Option<Bytes>, ChunkedGelfDecoderError> {
+        if chunk.len() < HEADER_LENGTH {
+            return Err(ChunkedGelfDecoderError::InvalidChunkHeader {
+                length: chunk.len(),
+            });
+        }
+
+        chunk.advance(MAGIC.len());
+        let message_id = chunk.get_u64();
+        let sequence_number = chunk.get_u8();
+        let sequence_count = chunk.get_u8();
+
+        if sequence_count == 0
+            || sequence_count > MAX_TOTAL_CHUNKS
+            || sequence_number >= sequence_count
+        {
+            return Err(ChunkedGelfDecoderError::InvalidSequence {
+                message_id,
+                sequence_number,
+                sequence_count,
+            });
+        }
+
+        let mut pending_messages = self
+            .pending_messages
+            .lock()
+            .expect("chunked GELF decoder mutex poisoned");
+        self.discard_timed_out(&mut pending_messages);
+
+        let pending_messages_count = pending_messages.len();
+        let message = match pending_messages.entry(message_id) {
+            Entry::Occupied(entry) => entry.into_mut(),
+            Entry::Vacant(entry) => {
+                if pending_messages_count >= self.pending_messages_limit {
+                    return Err(ChunkedGelfDecoderError::PendingMessagesLimitReached {
+                        message_id,
+                        pending_messages_limit: self.pending_messages_limit,
+                    });
+                }
+                entry.insert(PendingMessage::new(sequence_count))
+            }
+        };
+
+        let expected_sequence_count = message.chunks.len();
+        if expected_sequence_count != sequence_count as usize {
+            pending_messages.remove(&message_id);
+            return Err(ChunkedGelfDecoderError::SequenceCountMismatch {
+                message_id,
+                sequence_count,
+                expected_sequence_count,
+            });
+        }
+
+        let slot = &mut message.chunks[sequence_number as usize];
+        if slot.is_none() {
+            message.length += chunk.len();
+            message.received_chunks += 1;
+            *slot = Some(chunk);
+        }
+
+        if message.length > self.max_length {
+            pending_messages.remove(&message_id);
+            return Err(ChunkedGelfDecoderError::MaxLengthExceeded {
+                max_length: self.max_length,
+            });
+        }
+
+        if message.received_chunks < expected_sequence_count {
+            return Ok(None);
+        }
+
+        let message = pending_messages
+            .remove(&message_id)
+            .expect("message must be pending");
+        let mut buffer = BytesMut::with_capacity(message.length);
+        for chunk in message.chunks.into_iter().flatten() {
+            buffer.extend_from_slice(&chunk);
+        }
+        trace!(
+            message = "Reassembled chunked GELF message.",
+            chunks = expected_sequence_count,
+            bytes_processed = buffer.len()
+        );
+
+        Ok(Some(buffer.freeze()))
+    }
+
+    /// Discards the messages that haven't been completed within the timeout.
+    fn discard_timed_out(&self, pending_messages: &mut HashMap<u64, PendingMessage>) {
+        let count = pending_messages.len();
+        pending_messages.retain(|_, message| message.first_chunk_at.elapsed() < self.timeout);
+
+        let discarded = count - pending_messages.len();
+        if discarded > 0 {
+            warn!(
+                message = "Discarding incomplete chunked GELF messages after timeout.",
+                count = discarded,
+                timeout_secs = self.timeout.as_secs(),
+                internal_log_rate_limit = true
+            );
+        }
+    }
+
+    fn decompress(&self, message: Bytes) -> Result<Bytes, ChunkedGelfDecoderError> {
+        let decompression = match self.decompression {
+            ChunkedGelfDecompression::Auto => ChunkedGelfDecompression::detect(&message),
+            decompression => decompression,
+        };
+
+        let reader: Box<dyn Read> = match decompression {
+            ChunkedGelfDecompression::Gzip => Box::new(MultiGzDecoder::new(message.reader())),
+            ChunkedGelfDecompression::Zlib => Box::new(ZlibDecoder::new(message.reader())),
+            ChunkedGelfDecompression::Auto | ChunkedGelfDecompression::None => {
+                return if message.len() > self.max_length {
+                    Err(ChunkedGelfDecoderError::MaxLengthExceeded {
+                        max_length: self.max_length,
+                    })
+                } else {
+                    Ok(message)
+                };
+            }
+        };
+
+        // Read one more byte than allowed, to tell apart messages that are too long.
+        let mut decompressed = Vec::new();
+        reader
+            .take(self.max_length as u64 + 1)
+            .read_to_end(&mut decompressed)
+            .map_err(|source| ChunkedGelfDecoderError::Decompression { source })?;
+
+        if decompressed.len() > self.max_length {
+            return Err(ChunkedGelfDecoderError::MaxLengthExceeded {
+                max_length: self.max_length,
+            });
+        }
+
+        Ok(decompressed.into())
+    }
+}
+
+impl Default for ChunkedGelfDecoder {
+    fn default() -> Self {
+        Self::new(ChunkedGelfDecoderOptions::default())
+    }
+}
+
+impl Decoder for ChunkedGelfDecoder {
+    type Item = Bytes;
+    type Error = BoxedFramingError;
+
+    fn decode(&mut self, _src: &mut BytesMut) -> Result<Option<Self::Item>, Self::Error> {
+        // Each message is a single chunk, which is only complete at the end of the input.
+        Ok(None)
+    }
+
+    fn decode_eof(&mut self, src: &mut BytesMut) -> Result<Option<Self::Item>, Self::Error> {
+        if src.is_empty() {
+            return Ok(None);
+        }
+
+        let frame = src.split().freeze();
+        let message = if frame.starts_with(&MAGIC) {
+            match self.decode_chunk(frame)? {
+                Some(message) => message,
+                None => return Ok(None),
+            }
+        } else {
+            frame
+        };
+
+        Ok(Some(self.decompress(message)?))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Write;
+
+    use bytes::BufMut;
+    use flate2::{
+        write::{GzEncoder, ZlibEncoder},
+        Compression,
+    };
+
+    use super::*;
+
+    fn chunk(message_id: u64, sequence_number: u8, sequence_count: u8, payload: &[u8]) -> BytesMut {
+        let mut chunk = BytesMut::new();
+        chunk.put_slice(&MAGIC);
+        chunk.put_u64(message_id);
+        chunk.put_u8(sequence_number);
+        chunk.put_u8(sequence_count);
+        chunk.put_slice(payload);
+        chunk
+    }
+
+    fn decode(decoder: &mut ChunkedGelfDecoder, mut src: BytesMut) -> Option<Bytes> {
+        assert_eq!(decoder.decode(&mut src).unwrap(), None);
+        decoder.decode_eof(&mut src).unwrap()
+    }
+
+    #[test]
+    fn decode_unchunked_message() {
+        let mut decoder = ChunkedGelfDecoder::default();
+
+        let message = decode(&mut decoder, BytesMut::from(r#"{"short_message":"foo"}"#));
+        assert_eq!(message.unwrap(), r#"{"short_message":"foo"}"#);
+
+        let mut src = BytesMut::new();
+        assert_eq!(decoder.decode_eof(&mut src).unwrap(), None);
+    }
+
+    #[test]
+    fn decode_chunked_message() {
+        let mut decoder = ChunkedGelfDecoder::default();
+
+        assert_eq!(decode(&mut decoder, chunk(1, 0, 3, b"foo")), None);
+        assert_eq!(decode(&mut decoder, chunk(1, 1, 3, b"bar")), None);
+        assert_eq!(
+            decode(&mut decoder, chunk(1, 2, 3, b"baz")).unwrap(),
+            "foobarbaz"
+        );
+        assert!(decoder.pending_messages.lock().unwrap().is_empty());
+    }
+
+    #[test]
+    fn decode_interleaved_out_of_order_chunks() {
+        let mut decoder = ChunkedGelfDecoder::default();
+
+        assert_eq!(decode(&mut decoder, chunk(1, 1, 2, b"bar")), None);
+        assert_eq!(decode(&mut decoder, chunk(2, 1, 2, b"qux")), None);
+        assert_eq!(decode(&mut decoder, chunk(2, 1, 2, b"qux")), None);
+        assert_eq!(
+            decode(&mut decoder, chunk(2, 0, 2, b"baz")).unwrap(),
+            "bazqux"
+        );
+        assert_eq!(
+            decode(&mut decoder, chunk(1, 0, 2, b"foo")).unwrap(),
+            "foobar"
+        );
+    }
+
+    #[test]
+    fn decode_single_chunk_message() {
+        let mut decoder = ChunkedGelfDecoder::default();
+
+        assert_eq!(decode(&mut decoder, chunk(1, 0, 1, b"foo")).unwrap(), "foo");
+    }
+
+    #[test]
+    fn clones_share_pending_messages() {
+        let decoder = ChunkedGelfDecoder::default();
+
+        assert_eq!(decode(&mut decoder.clone(), chunk(1, 0, 2, b"foo")), None);
+        assert_eq!(
+            decode(&mut decoder.clone(), chunk(1, 1, 2, b"bar")).unwrap(),
+            "foobar"
+        );
+    }
+
+    #[test]
+    fn decode_compressed_messages() {
+        let mut gzip = GzEncoder::new(Vec::new(), Compression::default());
+        gzip.write_all(b"foobarbaz").unwrap();
+        let gzip = gzip.finish().unwrap();
+
+        let mut zlib = ZlibEncoder::new(Vec::new(), Compression::default());
+        zlib.write_all(b"foobarbaz").unwrap();
+        let zlib = zlib.finish().unwrap();
+
+        let mut decoder = ChunkedGelfDecoder::default();
+
+        let message = decode(&mut decoder, BytesMut::from(&gzip[..]));
+        assert_eq!(message.unwrap(), "foobarbaz");
+
+        let message = decode(&mut decoder, BytesMut::from(&zlib[..]));
+        assert_eq!(message.unwrap(), "foobarbaz");
+
+        let (head, tail) = gzip.split_at(gzip.len() / 2);
+        assert_eq!(decode(&mut decoder, chunk(1, 0, 2, head)), None);
+        assert_eq!(
+            decode(&mut decoder, chunk(1, 1, 2, tail)).unwrap(),
+            "foobarbaz"
+        );
+    }
+
+    #[test]
+    fn decode_without_decompression() {
+        let mut gzip = GzEncoder::new(Vec::new(), Compression::default());
+        gzip.write_all(b"foobarbaz").unwrap();
+        let gzip = gzip.finish().unwrap();
+
+        let mut decoder = ChunkedGelfDecoder::new(ChunkedGelfDecoderOptions {
+            decompression: ChunkedGelfDecompression::None,
+            ..Default::default()
+        });
+
+        let message = decode(&mut decoder, BytesMut::from(&gzip[..]));
+        assert_eq!(message.unwrap(), gzip);
+    }
+
+    #[test]
+    fn decode_error_invalid_chunks() {
+        let mut decoder = ChunkedGelfDecoder::default();
+
+        let mut src = BytesMut::from(&MAGIC[..]);
+        let error = decoder.decode_eof(&mut src).unwrap_err();
+        assert!(error.can_continue());
+        assert!(error.to_string().contains("too short"));
+
+        for (sequence_number, sequence_count) in [(0, 0), (2, 2), (0, 129)] {
+            let mut src = chunk(1, sequence_number, sequence_count, b"foo");
+            let error = decoder.decode_eof(&mut src).unwrap_err();
+            assert!(error.to_string().contains("sequence number"));
+        }
+
+        assert_eq!(decode(&mut decoder, chunk(1, 0, 2, b"foo")), None);
+        let mut src = chunk(1, 1, 3, b"bar");
+        let error = decoder.decode_eof(&mut src).unwrap_err();
+        assert!(error.to_string().contains("sequence count"));
+        assert!(decoder.pending_messages.lock().unwrap().is_empty());
+    }
+
+    #[test]
+    fn discard_timed_out_messages() {
+        let mut decoder = ChunkedGelfDecoder::new(ChunkedGelfDecoderOptions {
+            timeout_secs: 0,
+            ..Default::default()
+        });
+
+        assert_eq!(decode(&mut decoder, chunk(1, 0, 2, b"foo")), None);
+        assert_eq!(decode(&mut decoder, chunk(1, 1, 2, b"bar")), None);
+        assert_eq!(decoder.pending_messages.lock().unwrap().len(), 1);
+    }
+
+    #[test]
+    fn discard_chunks_over_pending_messages_limit() {
+        let mut decoder = ChunkedGelfDecoder::new(ChunkedGelfDecoderOptions {
+            pending_messages_limit: 1,
+            ..Default::default()
+        });
+
+        assert_eq!(decode(&mut decoder, chunk(1, 0, 2, b"foo")), None);
+        let mut src = chunk(2, 0, 2, b"baz");
+        let error = decoder.decode_eof(&mut src).unwrap_err();
+        assert!(error.to_string().contains("already pending"));
+        assert_eq!(
+            decode(&mut decoder, chunk(1, 1, 2, b"bar")).unwrap(),
+            "foobar"
+        );
+    }
+
+    #[test]
+    fn discard_messages_over_max_length() {
+        let mut decoder = ChunkedGelfDecoder::new(ChunkedGelfDecoderOptions {
+            max_length: 5,
+            ..Default::default()
+        });
+
+        assert_eq!(decode(&mut decoder, chunk(1, 0, 2, b"foo")), None);
+        let mut src = chunk(1, 1, 2, b"bar");
+        let error = decoder.decode_eof(&mut src).unwrap_err();
+        assert!(error.to_string().contains("max_length"));
+        assert!(decoder.pending_messages.lock().unwrap().is_empty());
+
+        let mut zlib = ZlibEncoder::new(Vec::new(), Compression::default());
+        zlib.write_all(b"foobar").unwrap();
+        let mut src = BytesMut::from(&zlib.finish().unwrap()[..]);
+        let error = decoder.decode_eof(&mut src).unwrap_err();
+        assert!(error.to_string().contains("max_length"));
+
+        let mut src = BytesMut::from("foobar");
+        let error = decoder.decode_eof(&mut src).unwrap_err();
+        assert!(error.to_string().contains("max_length"));
+    }
+
+    #[test]
+    fn discard_compressed_messages_over_default_max_length() {
+        let max_length = default_max_length();
+        let mut gzip = GzEncoder::new(Vec::new(), Compression::best());
+        gzip.write_all(&vec![0; max_length + 1]).unwrap();
+        let gzip = gzip.finish().unwrap();
+        assert!(gzip.len() < max_length / 100);
+
+        let mut decoder = ChunkedGelfDecoder::default();
+
+        let mut src = BytesMut::from(&gzip[..]);
+        let error = decoder.decode_eof(&mut src).unwrap_err();
+        assert!(error.can_continue());
+        assert!(error.to_string().contains("max_length"));
+
+        let (head, tail) = gzip.split_at(gzip.len() / 2);
+        assert_eq!(decode(&mut decoder, chunk(1, 0, 2, head)), None);
+        let mut src = chunk(1, 1, 2, tail);
+        let error = decoder.decode_eof(&mut src).unwrap_err();
+        assert!(error.to_string().contains("max_length"));
+    }
+}
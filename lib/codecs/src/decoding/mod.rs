@@ -8,17 +8,21 @@ pub mod framing;
 use bytes::{Bytes, BytesMut};
 pub use error::StreamDecodingError;
 pub use format::{
-    BoxedDeserializer, BytesDeserializer, BytesDeserializerConfig, GelfDeserializer,
+    AvroDeserializer, AvroDeserializerConfig, AvroDeserializerOptions, BoxedDeserializer,
+    BytesDeserializer, BytesDeserializerConfig, CefDeserializer, CefDeserializerConfig,
+    CsvDeserializer, CsvDeserializerConfig, CsvDeserializerOptions, GelfDeserializer,
     GelfDeserializerConfig, GelfDeserializerOptions, JsonDeserializer, JsonDeserializerConfig,
-    JsonDeserializerOptions, NativeDeserializer, NativeDeserializerConfig, NativeJsonDeserializer,
+    JsonDeserializerOptions, LeefDeserializer, LeefDeserializerConfig, LogfmtDeserializer,
+    LogfmtDeserializerConfig, NativeDeserializer, NativeDeserializerConfig, NativeJsonDeserializer,
     NativeJsonDeserializerConfig, NativeJsonDeserializerOptions, ProtobufDeserializer,
-    ProtobufDeserializerConfig,
+    ProtobufDeserializerConfig, ProtobufDeserializerOptions,
 };
 #[cfg(feature = "syslog")]
 pub use format::{SyslogDeserializer, SyslogDeserializerConfig, SyslogDeserializerOptions};
 pub use framing::{
     BoxedFramer, BoxedFramingError, BytesDecoder, BytesDecoderConfig, CharacterDelimitedDecoder,
-    CharacterDelimitedDecoderConfig, CharacterDelimitedDecoderOptions, FramingError,
+    CharacterDelimitedDecoderConfig, CharacterDelimitedDecoderOptions, ChunkedGelfDecoder,
+    ChunkedGelfDecoderConfig, ChunkedGelfDecoderOptions, ChunkedGelfDecompression, FramingError,
     LengthDelimitedDecoder, LengthDelimitedDecoderConfig, NewlineDelimitedDecoder,
     NewlineDelimitedDecoderConfig, NewlineDelimitedDecoderOptions, OctetCountingDecoder,
     OctetCountingDecoderConfig, OctetCountingDecoderOptions,
@@ -85,6 +89,16 @@ pub enum FramingConfig {
     /// Byte frames which are delimited by a chosen character.
     CharacterDelimited(CharacterDelimitedDecoderConfig),
 
+    /// Byte frames which are [chunked GELF][chunked_gelf] messages, one chunk per message.
+    ///
+    /// The chunks of each message are reassembled into a single frame, which is then decompressed.
+    /// Messages that are not chunked are passed through as-is. This is only supported by
+    /// message-based sources, such as the `socket` source in UDP mode, along with the `gelf` codec.
+    /// Stream-based sources, such as the `socket` source in TCP mode or the `stdin` source, reject it.
+    ///
+    /// [chunked_gelf]: https://go2docs.graylog.org/current/getting_in_log_data/gelf.html#GELFviaUDP
+    ChunkedGelf(ChunkedGelfDecoderConfig),
+
     /// Byte frames which are prefixed by an unsigned big-endian 32-bit integer indicating the length.
     LengthDelimited,
 
@@ -109,6 +123,12 @@ impl From<CharacterDelimitedDecoderConfig> for FramingConfig {
     }
 }
 
+impl From<ChunkedGelfDecoderConfig> for FramingConfig {
+    fn from(config: ChunkedGelfDecoderConfig) -> Self {
+        Self::ChunkedGelf(config)
+    }
+}
+
 impl From<LengthDelimitedDecoderConfig> for FramingConfig {
     fn from(_: LengthDelimitedDecoderConfig) -> Self {
         Self::LengthDelimited
@@ -133,6 +153,7 @@ impl FramingConfig {
         match self {
             FramingConfig::Bytes => Framer::Bytes(BytesDecoderConfig.build()),
             FramingConfig::CharacterDelimited(config) => Framer::CharacterDelimited(config.build()),
+            FramingConfig::ChunkedGelf(config) => Framer::ChunkedGelf(config.build()),
             FramingConfig::LengthDelimited => {
                 Framer::LengthDelimited(LengthDelimitedDecoderConfig.build())
             }
@@ -149,6 +170,8 @@ pub enum Framer {
     Bytes(BytesDecoder),
     /// Uses a `CharacterDelimitedDecoder` for framing.
     CharacterDelimited(CharacterDelimitedDecoder),
+    /// Uses a `ChunkedGelfDecoder` for framing.
+    ChunkedGelf(ChunkedGelfDecoder),
     /// Uses a `LengthDelimitedDecoder` for framing.
     LengthDelimited(LengthDelimitedDecoder),
     /// Uses a `NewlineDelimitedDecoder` for framing.
@@ -167,6 +190,7 @@ impl tokio_util::codec::Decoder for Framer {
         match self {
             Framer::Bytes(framer) => framer.decode(src),
             Framer::CharacterDelimited(framer) => framer.decode(src),
+            Framer::ChunkedGelf(framer) => framer.decode(src),
             Framer::LengthDelimited(framer) => framer.decode(src),
             Framer::NewlineDelimited(framer) => framer.decode(src),
             Framer::OctetCounting(framer) => framer.decode(src),
@@ -178,6 +202,7 @@ impl tokio_util::codec::Decoder for Framer {
         match self {
             Framer::Bytes(framer) => framer.decode_eof(src),
             Framer::CharacterDelimited(framer) => framer.decode_eof(src),
+            Framer::ChunkedGelf(framer) => framer.decode_eof(src),
             Framer::LengthDelimited(framer) => framer.decode_eof(src),
             Framer::NewlineDelimited(framer) => framer.decode_eof(src),
             Framer::OctetCounting(framer) => framer.decode_eof(src),
@@ -193,14 +218,53 @@ impl tokio_util::codec::Decoder for Framer {
 #[configurable(description = "Configures how events are decoded from raw bytes.")]
 #[configurable(metadata(docs::enum_tag_description = "The codec to use for decoding events."))]
 pub enum DeserializerConfig {
+    /// Decodes the raw bytes as an [Apache Avro][apache_avro] datum.
+    ///
+    /// [apache_avro]: https://avro.apache.org/
+    Avro {
+        /// Apache Avro-specific decoder options.
+        avro: AvroDeserializerOptions,
+    },
+
     /// Uses the raw bytes as-is.
     Bytes,
 
+    /// Decodes the raw bytes as an [ArcSight CEF][cef] message.
+    ///
+    /// Header fields and extensions are decoded as strings. Anything before the `CEF:` prefix, such
+    /// as a syslog header, is ignored.
+    ///
+    /// [cef]: https://www.microfocus.com/documentation/arcsight/arcsight-smartconnectors/pdfdoc/common-event-format-v25/common-event-format-v25.pdf
+    Cef,
+
+    /// Decodes the raw bytes as [CSV][csv] rows.
+    ///
+    /// Each row is decoded into a separate event.
+    ///
+    /// [csv]: https://datatracker.ietf.org/doc/html/rfc4180
+    Csv(CsvDeserializerConfig),
+
     /// Decodes the raw bytes as [JSON][json].
     ///
     /// [json]: https://www.json.org/
     Json(JsonDeserializerConfig),
 
+    /// Decodes the raw bytes as an [IBM QRadar LEEF][leef] message.
+    ///
+    /// Header fields and attributes are decoded as strings. The attribute delimiter of LEEF 2.0
+    /// messages is read from their header. Anything before the `LEEF:` prefix, such as a syslog
+    /// header, is ignored.
+    ///
+    /// [leef]: https://www.ibm.com/docs/en/dsm?topic=leef-overview
+    Leef,
+
+    /// Decodes the raw bytes as a [logfmt][logfmt] message.
+    ///
+    /// Values are decoded as strings, and keys without a value are decoded as `true`.
+    ///
+    /// [logfmt]: https://brandur.org/logfmt
+    Logfmt,
+
     /// Decodes the raw bytes as [protobuf][protobuf].
     ///
     /// [protobuf]: https://protobuf.dev/
@@ -238,18 +302,48 @@ pub enum DeserializerConfig {
     Gelf(GelfDeserializerConfig),
 }
 
+impl From<AvroDeserializerConfig> for DeserializerConfig {
+    fn from(config: AvroDeserializerConfig) -> Self {
+        Self::Avro { avro: config.avro }
+    }
+}
+
 impl From<BytesDeserializerConfig> for DeserializerConfig {
     fn from(_: BytesDeserializerConfig) -> Self {
         Self::Bytes
     }
 }
 
+impl From<CefDeserializerConfig> for DeserializerConfig {
+    fn from(_: CefDeserializerConfig) -> Self {
+        Self::Cef
+    }
+}
+
+impl From<CsvDeserializerConfig> for DeserializerConfig {
+    fn from(config: CsvDeserializerConfig) -> Self {
+        Self::Csv(config)
+    }
+}
+
 impl From<JsonDeserializerConfig> for DeserializerConfig {
     fn from(config: JsonDeserializerConfig) -> Self {
         Self::Json(config)
     }
 }
 
+impl From<LeefDeserializerConfig> for DeserializerConfig {
+    fn from(_: LeefDeserializerConfig) -> Self {
+        Self::Leef
+    }
+}
+
+impl From<LogfmtDeserializerConfig> for DeserializerConfig {
+    fn from(_: LogfmtDeserializerConfig) -> Self {
+        Self::Logfmt
+    }
+}
+
 #[cfg(feature = "syslog")]
 impl From<SyslogDeserializerConfig> for DeserializerConfig {
     fn from(config: SyslogDeserializerConfig) -> Self {
@@ -279,8 +373,17 @@ impl DeserializerConfig {
     /// Build the `Deserializer` from this configuration.
     pub fn build(&self) -> vector_common::Result<Deserializer> {
         match self {
+            DeserializerConfig::Avro { avro } => Ok(Deserializer::Avro(
+                AvroDeserializerConfig { avro: avro.clone() }.build()?,
+            )),
             DeserializerConfig::Bytes => Ok(Deserializer::Bytes(BytesDeserializerConfig.build())),
+            DeserializerConfig::Cef => Ok(Deserializer::Cef(CefDeserializerConfig.build())),
+            DeserializerConfig::Csv(config) => Ok(Deserializer::Csv(config.build()?)),
             DeserializerConfig::Json(config) => Ok(Deserializer::Json(config.build())),
+            DeserializerConfig::Leef => Ok(Deserializer::Leef(LeefDeserializerConfig.build())),
+            DeserializerConfig::Logfmt => {
+                Ok(Deserializer::Logfmt(LogfmtDeserializerConfig.build()))
+            }
             DeserializerConfig::Protobuf(config) => Ok(Deserializer::Protobuf(config.build()?)),
             #[cfg(feature = "syslog")]
             DeserializerConfig::Syslog(config) => Ok(Deserializer::Syslog(config.build())),
@@ -297,12 +400,18 @@ impl DeserializerConfig {
         match self {
             DeserializerConfig::Native => FramingConfig::LengthDelimited,
             DeserializerConfig::Bytes
+            | DeserializerConfig::Cef
+            | DeserializerConfig::Csv(_)
             | DeserializerConfig::Json(_)
             | DeserializerConfig::Gelf(_)
+            | DeserializerConfig::Leef
+            | DeserializerConfig::Logfmt
             | DeserializerConfig::NativeJson(_) => {
                 FramingConfig::NewlineDelimited(Default::default())
             }
-            DeserializerConfig::Protobuf(_) => FramingConfig::Bytes,
+            DeserializerConfig::Avro { .. } | DeserializerConfig::Protobuf(_) => {
+                FramingConfig::Bytes
+            }
             #[cfg(feature = "syslog")]
             DeserializerConfig::Syslog(_) => FramingConfig::NewlineDelimited(Default::default()),
         }
@@ -311,8 +420,15 @@ impl DeserializerConfig {
     /// Return the type of event build by this deserializer.
     pub fn output_type(&self) -> DataType {
         match self {
+            DeserializerConfig::Avro { avro } => {
+                AvroDeserializerConfig { avro: avro.clone() }.output_type()
+            }
             DeserializerConfig::Bytes => BytesDeserializerConfig.output_type(),
+            DeserializerConfig::Cef => CefDeserializerConfig.output_type(),
+            DeserializerConfig::Csv(config) => config.output_type(),
             DeserializerConfig::Json(config) => config.output_type(),
+            DeserializerConfig::Leef => LeefDeserializerConfig.output_type(),
+            DeserializerConfig::Logfmt => LogfmtDeserializerConfig.output_type(),
             DeserializerConfig::Protobuf(config) => config.output_type(),
             #[cfg(feature = "syslog")]
             DeserializerConfig::Syslog(config) => config.output_type(),
@@ -325,8 +441,15 @@ impl DeserializerConfig {
     /// The schema produced by the deserializer.
     pub fn schema_definition(&self, log_namespace: LogNamespace) -> schema::Definition {
         match self {
+            DeserializerConfig::Avro { avro } => {
+                AvroDeserializerConfig { avro: avro.clone() }.schema_definition(log_namespace)
+            }
             DeserializerConfig::Bytes => BytesDeserializerConfig.schema_definition(log_namespace),
+            DeserializerConfig::Cef => CefDeserializerConfig.schema_definition(log_namespace),
+            DeserializerConfig::Csv(config) => config.schema_definition(log_namespace),
             DeserializerConfig::Json(config) => config.schema_definition(log_namespace),
+            DeserializerConfig::Leef => LeefDeserializerConfig.schema_definition(log_namespace),
+            DeserializerConfig::Logfmt => LogfmtDeserializerConfig.schema_definition(log_namespace),
             DeserializerConfig::Protobuf(config) => config.schema_definition(log_namespace),
             #[cfg(feature = "syslog")]
             DeserializerConfig::Syslog(config) => config.schema_definition(log_namespace),
@@ -357,11 +480,16 @@ impl DeserializerConfig {
             ) => "application/json",
             (DeserializerConfig::Native, _) => "application/octet-stream",
             (DeserializerConfig::Protobuf(_), _) => "application/octet-stream",
+            (DeserializerConfig::Avro { .. }, _) => "application/octet-stream",
             (
                 DeserializerConfig::Json(_)
                 | DeserializerConfig::NativeJson(_)
                 | DeserializerConfig::Bytes
-                | DeserializerConfig::Gelf(_),
+                | DeserializerConfig::Cef
+                | DeserializerConfig::Csv(_)
+                | DeserializerConfig::Gelf(_)
+                | DeserializerConfig::Leef
+                | DeserializerConfig::Logfmt,
                 _,
             ) => "text/plain",
             #[cfg(feature = "syslog")]
@@ -373,10 +501,20 @@ impl DeserializerConfig {
 /// Parse structured events from bytes.
 #[derive(Clone)]
 pub enum Deserializer {
+    /// Uses an `AvroDeserializer` for deserialization.
+    Avro(AvroDeserializer),
     /// Uses a `BytesDeserializer` for deserialization.
     Bytes(BytesDeserializer),
+    /// Uses a `CefDeserializer` for deserialization.
+    Cef(CefDeserializer),
+    /// Uses a `CsvDeserializer` for deserialization.
+    Csv(CsvDeserializer),
     /// Uses a `JsonDeserializer` for deserialization.
     Json(JsonDeserializer),
+    /// Uses a `LeefDeserializer` for deserialization.
+    Leef(LeefDeserializer),
+    /// Uses a `LogfmtDeserializer` for deserialization.
+    Logfmt(LogfmtDeserializer),
     /// Uses a `ProtobufDeserializer` for deserialization.
     Protobuf(ProtobufDeserializer),
     #[cfg(feature = "syslog")]
@@ -399,8 +537,13 @@ impl format::Deserializer for Deserializer {
         log_namespace: LogNamespace,
     ) -> vector_common::Result<SmallVec<[Event; 1]>> {
         match self {
+            Deserializer::Avro(deserializer) => deserializer.parse(bytes, log_namespace),
             Deserializer::Bytes(deserializer) => deserializer.parse(bytes, log_namespace),
+            Deserializer::Cef(deserializer) => deserializer.parse(bytes, log_namespace),
+            Deserializer::Csv(deserializer) => deserializer.parse(bytes, log_namespace),
             Deserializer::Json(deserializer) => deserializer.parse(bytes, log_namespace),
+            Deserializer::Leef(deserializer) => deserializer.parse(bytes, log_namespace),
+            Deserializer::Logfmt(deserializer) => deserializer.parse(bytes, log_namespace),
             Deserializer::Protobuf(deserializer) => deserializer.parse(bytes, log_namespace),
             #[cfg(feature = "syslog")]
             Deserializer::Syslog(deserializer) => deserializer.parse(bytes, log_namespace),
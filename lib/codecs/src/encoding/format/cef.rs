@@ -0,0 +1,206 @@
+use bytes::{BufMut, BytesMut};
+use lookup::event_path;
+use serde::{Deserialize, Serialize};
+use snafu::Snafu;
+use tokio_util::codec::Encoder;
+use vector_core::{config::DataType, event::Event, schema};
+
+use crate::cef::cef_fields::*;
+
+/// The CEF version used when the event doesn't contain one.
+const DEFAULT_CEF_VERSION: &str = "0";
+
+/// Errors that can occur during CEF serialization.
+#[derive(Debug, Snafu)]
+pub enum CefSerializerError {
+    #[snafu(display(r#"LogEvent does not contain required field: "{}""#, field))]
+    MissingField { field: String },
+    #[snafu(display(r#"LogEvent contains an invalid field name. field = "{}""#, field))]
+    InvalidFieldName { field: String },
+}
+
+/// Config used to build a `CefSerializer`.
+#[derive(Debug, Clone, Default, Deserialize, Serialize)]
+pub struct CefSerializerConfig;
+
+impl CefSerializerConfig {
+    /// Creates a new `CefSerializerConfig`.
+    pub const fn new() -> Self {
+        Self
+    }
+
+    /// Build the `CefSerializer` from this configuration.
+    pub const fn build(&self) -> CefSerializer {
+        CefSerializer
+    }
+
+    /// The data type of events that are accepted by `CefSerializer`.
+    pub fn input_type(&self) -> DataType {
+        DataType::Log
+    }
+
+    /// The schema required by the serializer.
+    pub fn schema_requirement(&self) -> schema::Requirement {
+        schema::Requirement::empty()
+    }
+}
+
+/// Serializer that converts an `Event` to bytes using the [ArcSight CEF][cef] format.
+///
+/// The header is built from the fields named in [`cef_fields`](crate::cef::cef_fields), which
+/// must all be present except for the CEF version. Every other top-level field, except those set
+/// to `null`, is encoded as an extension.
+///
+/// [cef]: https://www.microfocus.com/documentation/arcsight/arcsight-smartconnectors/pdfdoc/common-event-format-v25/common-event-format-v25.pdf
+#[derive(Debug, Clone, Default)]
+pub struct CefSerializer;
+
+impl CefSerializer {
+    /// Creates a new `CefSerializer`.
+    pub const fn new() -> Self {
+        Self
+    }
+}
+
+impl Encoder<Event> for CefSerializer {
+    type Error = vector_common::Error;
+
+    fn encode(&mut self, event: Event, buffer: &mut BytesMut) -> Result<(), Self::Error> {
+        let log = event.into_log();
+
+        let mut message = String::from(CEF_PREFIX);
+        for field in HEADER_FIELDS {
+            let value = match log.get(event_path!(field)).filter(|value| !value.is_null()) {
+                Some(value) => value.to_string_lossy(),
+                None if field == CEF_VERSION => DEFAULT_CEF_VERSION.into(),
+                None => return Err(MissingFieldSnafu { field }.build().into()),
+            };
+            escape_header(&value, &mut message);
+            message.push('|');
+        }
+
+        let extensions = log
+            .as_map()
+            .into_iter()
+            .flatten()
+            .filter(|(key, value)| !HEADER_FIELDS.contains(&key.as_str()) && !value.is_null());
+        let mut separator = "";
+        for (key, value) in extensions {
+            if key.is_empty() || !key.bytes().all(is_key_char) {
+                return Err(InvalidFieldNameSnafu { field: key }.build().into());
+            }
+
+            message.push_str(separator);
+            message.push_str(key);
+            message.push('=');
+            escape_extension(&value.to_string_lossy(), &mut message);
+            separator = " ";
+        }
+
+        buffer.put_slice(message.as_bytes());
+        Ok(())
+    }
+}
+
+const fn is_key_char(byte: u8) -> bool {
+    byte.is_ascii_alphanumeric() || matches!(byte, b'_' | b'.' | b'-' | b'[' | b']')
+}
+
+/// Escapes `\` and `|` in a header field.
+fn escape_header(value: &str, output: &mut String) {
+    for c in value.chars() {
+        if matches!(c, '\\' | '|') {
+            output.push('\\');
+        }
+        output.push(c);
+    }
+}
+
+/// Escapes `\`, `=`, and line breaks in an extension value.
+fn escape_extension(value: &str, output: &mut String) {
+    for c in value.chars() {
+        match c {
+            '\\' | '=' => {
+                output.push('\\');
+                output.push(c);
+            }
+            '\n' => output.push_str(r"\n"),
+            '\r' => output.push_str(r"\r"),
+            c => output.push(c),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bytes::BytesMut;
+    use chrono::{TimeZone, Utc};
+    use vector_core::event::{LogEvent, Value};
+    use vrl::btreemap;
+
+    use super::*;
+
+    fn header_fields() -> Vec<(&'static str, Value)> {
+        vec![
+            (DEVICE_VENDOR, "Security".into()),
+            (DEVICE_PRODUCT, "threat|manager".into()),
+            (DEVICE_VERSION, "1.0".into()),
+            (DEVICE_EVENT_CLASS_ID, "100".into()),
+            (NAME, "worm stopped".into()),
+            (SEVERITY, 10.into()),
+        ]
+    }
+
+    fn serialize(log: LogEvent) -> Result<String, vector_common::Error> {
+        let mut bytes = BytesMut::new();
+        CefSerializer::new().encode(Event::Log(log), &mut bytes)?;
+        Ok(String::from_utf8(bytes.to_vec()).unwrap())
+    }
+
+    #[test]
+    fn serialize_cef() {
+        let mut log = LogEvent::from(btreemap! {
+            "src" => "10.0.0.1",
+            "msg" => "a=b\\c\nd",
+            "count" => 3,
+            "time" => Utc.with_ymd_and_hms(2023, 2, 27, 15, 4, 49).unwrap(),
+            "tags" => vec!["a", "b"],
+            "ignored" => Value::Null,
+        });
+        for (field, value) in header_fields() {
+            log.insert(field, value);
+        }
+
+        assert_eq!(
+            serialize(log).unwrap(),
+            r#"CEF:0|Security|threat\|manager|1.0|100|worm stopped|10|count=3 msg=a\=b\\c\nd src=10.0.0.1 tags=["a","b"] time=2023-02-27T15:04:49Z"#
+        );
+    }
+
+    #[test]
+    fn serialize_error_missing_field() {
+        let mut log = LogEvent::default();
+        for (field, value) in header_fields() {
+            if field != NAME {
+                log.insert(field, value);
+            }
+        }
+
+        assert_eq!(
+            serialize(log).unwrap_err().to_string(),
+            r#"LogEvent does not contain required field: "name""#
+        );
+    }
+
+    #[test]
+    fn serialize_error_invalid_field_name() {
+        let mut log = LogEvent::from(btreemap! {
+            "with space" => "value",
+        });
+        for (field, value) in header_fields() {
+            log.insert(field, value);
+        }
+
+        assert!(serialize(log).is_err());
+    }
+}
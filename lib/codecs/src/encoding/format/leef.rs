@@ -0,0 +1,246 @@
+use bytes::{BufMut, BytesMut};
+use derivative::Derivative;
+use lookup::event_path;
+use snafu::Snafu;
+use tokio_util::codec::Encoder;
+use vector_core::{config::DataType, event::Event, schema};
+
+use crate::cef::leef_fields::*;
+
+/// The LEEF version used when the event doesn't contain one.
+const DEFAULT_LEEF_VERSION: &str = "2.0";
+
+/// Errors that can occur during LEEF serialization.
+#[derive(Debug, Snafu)]
+pub enum LeefSerializerError {
+    #[snafu(display(r#"LogEvent does not contain required field: "{}""#, field))]
+    MissingField { field: String },
+    #[snafu(display(r#"LogEvent contains an invalid field name. field = "{}""#, field))]
+    InvalidFieldName { field: String },
+    #[snafu(display(
+        r#"LogEvent contains a value with a reserved character. field = "{}" character = {:?}"#,
+        field,
+        character
+    ))]
+    ReservedCharacter { field: String, character: char },
+}
+
+/// Config used to build a `LeefSerializer`.
+#[crate::configurable_component]
+#[derive(Debug, Clone, Default)]
+pub struct LeefSerializerConfig {
+    /// LEEF-specific encoding options.
+    #[serde(
+        default,
+        skip_serializing_if = "vector_core::serde::skip_serializing_if_default"
+    )]
+    pub leef: LeefSerializerOptions,
+}
+
+impl LeefSerializerConfig {
+    /// Creates a new `LeefSerializerConfig`.
+    pub const fn new(leef: LeefSerializerOptions) -> Self {
+        Self { leef }
+    }
+
+    /// Build the `LeefSerializer` from this configuration.
+    pub const fn build(&self) -> LeefSerializer {
+        LeefSerializer::new(self.leef.delimiter)
+    }
+
+    /// The data type of events that are accepted by `LeefSerializer`.
+    pub fn input_type(&self) -> DataType {
+        DataType::Log
+    }
+
+    /// The schema required by the serializer.
+    pub fn schema_requirement(&self) -> schema::Requirement {
+        schema::Requirement::empty()
+    }
+}
+
+/// LEEF serializer options.
+#[crate::configurable_component]
+#[derive(Debug, Clone, Derivative, PartialEq, Eq)]
+#[derivative(Default)]
+pub struct LeefSerializerOptions {
+    /// The ASCII (7-bit) character that separates the attributes of LEEF 2.0 messages.
+    ///
+    /// The delimiter is announced in the header of each message. LEEF 1.0 messages always use a
+    /// tab.
+    #[serde(
+        default = "default_delimiter",
+        with = "vector_core::serde::ascii_char",
+        skip_serializing_if = "vector_core::serde::skip_serializing_if_default"
+    )]
+    #[derivative(Default(value = "default_delimiter()"))]
+    pub delimiter: u8,
+}
+
+const fn default_delimiter() -> u8 {
+    b'\t'
+}
+
+/// Serializer that converts an `Event` to bytes using the [IBM QRadar LEEF][leef] format.
+///
+/// The header is built from the fields named in [`leef_fields`](crate::cef::leef_fields), which
+/// must all be present except for the LEEF version. Every other top-level field, except those set
+/// to `null`, is encoded as an attribute.
+///
+/// [leef]: https://www.ibm.com/docs/en/dsm?topic=leef-overview
+#[derive(Debug, Clone)]
+pub struct LeefSerializer {
+    delimiter: u8,
+}
+
+impl LeefSerializer {
+    /// Creates a new `LeefSerializer`.
+    pub const fn new(delimiter: u8) -> Self {
+        Self { delimiter }
+    }
+}
+
+impl Encoder<Event> for LeefSerializer {
+    type Error = vector_common::Error;
+
+    fn encode(&mut self, event: Event, buffer: &mut BytesMut) -> Result<(), Self::Error> {
+        let log = event.into_log();
+
+        let mut message = String::from(LEEF_PREFIX);
+        let mut is_leef_1 = false;
+        for field in HEADER_FIELDS {
+            let value = match log.get(event_path!(field)).filter(|value| !value.is_null()) {
+                Some(value) => value.to_string_lossy(),
+                None if field == LEEF_VERSION => DEFAULT_LEEF_VERSION.into(),
+                None => return Err(MissingFieldSnafu { field }.build().into()),
+            };
+            check_reserved(field, &value, &['|'])?;
+            message.push_str(&value);
+            message.push('|');
+            if field == LEEF_VERSION {
+                is_leef_1 = value.starts_with('1');
+            }
+        }
+
+        // Only LEEF 2.0 supports custom delimiters, which are announced in the header.
+        let delimiter = if is_leef_1 {
+            '\t'
+        } else {
+            let delimiter = char::from(self.delimiter);
+            if delimiter.is_ascii_graphic() && delimiter != '|' {
+                message.push(delimiter);
+            } else {
+                message.push_str(&format!("x{:02X}", self.delimiter));
+            }
+            message.push('|');
+            delimiter
+        };
+
+        let attributes = log
+            .as_map()
+            .into_iter()
+            .flatten()
+            .filter(|(key, value)| !HEADER_FIELDS.contains(&key.as_str()) && !value.is_null());
+        let mut separator = None;
+        for (key, value) in attributes {
+            if key.is_empty() || key.contains(['=', delimiter]) {
+                return Err(InvalidFieldNameSnafu { field: key }.build().into());
+            }
+            let value = value.to_string_lossy();
+            check_reserved(key, &value, &[delimiter])?;
+
+            message.extend(separator);
+            message.push_str(key);
+            message.push('=');
+            message.push_str(&value);
+            separator = Some(delimiter);
+        }
+
+        buffer.put_slice(message.as_bytes());
+        Ok(())
+    }
+}
+
+/// LEEF doesn't define any escaping, so values containing the characters that separate them
+/// can't be encoded.
+fn check_reserved(field: &str, value: &str, reserved: &[char]) -> Result<(), LeefSerializerError> {
+    match value.chars().find(|c| reserved.contains(c)) {
+        Some(character) => ReservedCharacterSnafu { field, character }.fail(),
+        None => Ok(()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bytes::BytesMut;
+    use vector_core::event::{LogEvent, Value};
+    use vrl::btreemap;
+
+    use super::*;
+
+    fn serialize(log: LogEvent, delimiter: u8) -> Result<String, vector_common::Error> {
+        let mut bytes = BytesMut::new();
+        LeefSerializerConfig::new(LeefSerializerOptions { delimiter })
+            .build()
+            .encode(Event::Log(log), &mut bytes)?;
+        Ok(String::from_utf8(bytes.to_vec()).unwrap())
+    }
+
+    fn test_log() -> LogEvent {
+        LogEvent::from(btreemap! {
+            DEVICE_VENDOR => "Vendor",
+            DEVICE_PRODUCT => "IDS",
+            DEVICE_VERSION => "1.2",
+            EVENT_ID => "portscan",
+            "src" => "10.0.0.1",
+            "dst" => "10.0.0.2",
+            "port" => 22,
+            "ignored" => Value::Null,
+        })
+    }
+
+    #[test]
+    fn serialize_leef_2() {
+        assert_eq!(
+            serialize(test_log(), b'^').unwrap(),
+            "LEEF:2.0|Vendor|IDS|1.2|portscan|^|dst=10.0.0.2^port=22^src=10.0.0.1"
+        );
+        assert_eq!(
+            serialize(test_log(), b'\t').unwrap(),
+            "LEEF:2.0|Vendor|IDS|1.2|portscan|x09|dst=10.0.0.2\tport=22\tsrc=10.0.0.1"
+        );
+    }
+
+    #[test]
+    fn serialize_leef_1() {
+        let mut log = test_log();
+        log.insert(LEEF_VERSION, "1.0");
+
+        assert_eq!(
+            serialize(log, b'^').unwrap(),
+            "LEEF:1.0|Vendor|IDS|1.2|portscan|dst=10.0.0.2\tport=22\tsrc=10.0.0.1"
+        );
+    }
+
+    #[test]
+    fn serialize_error_missing_field() {
+        let mut log = test_log();
+        log.remove(EVENT_ID);
+
+        assert_eq!(
+            serialize(log, b'\t').unwrap_err().to_string(),
+            r#"LogEvent does not contain required field: "eventId""#
+        );
+    }
+
+    #[test]
+    fn serialize_error_reserved_character() {
+        let mut log = test_log();
+        log.insert("msg", "a^b");
+
+        assert_eq!(
+            serialize(log, b'^').unwrap_err().to_string(),
+            r#"LogEvent contains a value with a reserved character. field = "msg" character = '^'"#
+        );
+    }
+}
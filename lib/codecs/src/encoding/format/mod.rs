@@ -4,27 +4,40 @@
 #![deny(missing_docs)]
 
 mod avro;
+mod cef;
 mod common;
 mod csv;
 mod gelf;
 mod json;
+mod leef;
 mod logfmt;
 mod native;
 mod native_json;
+mod parquet;
+mod protobuf;
 mod raw_message;
+mod syslog;
 mod text;
 
 use std::fmt::Debug;
 
-pub use self::csv::{CsvSerializer, CsvSerializerConfig};
+pub use self::csv::{CsvSerializer, CsvSerializerConfig, CsvSerializerOptions};
+pub use self::parquet::{
+    ParquetColumnType, ParquetCompression, ParquetSerializer, ParquetSerializerConfig,
+    ParquetSerializerOptions,
+};
 pub use avro::{AvroSerializer, AvroSerializerConfig, AvroSerializerOptions};
+pub use cef::{CefSerializer, CefSerializerConfig};
 use dyn_clone::DynClone;
 pub use gelf::{GelfSerializer, GelfSerializerConfig};
 pub use json::{JsonSerializer, JsonSerializerConfig};
+pub use leef::{LeefSerializer, LeefSerializerConfig, LeefSerializerOptions};
 pub use logfmt::{LogfmtSerializer, LogfmtSerializerConfig};
 pub use native::{NativeSerializer, NativeSerializerConfig};
 pub use native_json::{NativeJsonSerializer, NativeJsonSerializerConfig};
+pub use protobuf::{ProtobufSerializer, ProtobufSerializerConfig, ProtobufSerializerOptions};
 pub use raw_message::{RawMessageSerializer, RawMessageSerializerConfig};
+pub use syslog::{SyslogRfc, SyslogSerializer, SyslogSerializerConfig, SyslogSerializerOptions};
 pub use text::{TextSerializer, TextSerializerConfig};
 use vector_core::event::Event;
 
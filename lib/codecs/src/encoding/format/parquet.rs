@@ -0,0 +1,719 @@
+use std::{collections::BTreeMap, sync::Arc};
+
+use bytes::{BufMut, BytesMut};
+use derivative::Derivative;
+use lookup::event_path;
+use parquet::{
+    basic::{Compression, GzipLevel, LogicalType, Repetition, Type as PhysicalType, ZstdLevel},
+    column::writer::ColumnWriter,
+    data_type::ByteArray,
+    file::{
+        properties::{WriterProperties, WriterPropertiesPtr},
+        writer::SerializedFileWriter,
+    },
+    format::{MicroSeconds, TimeUnit},
+    schema::types::{Type, TypePtr},
+};
+use snafu::Snafu;
+use tokio_util::codec::Encoder;
+use vector_config::configurable_component;
+use vector_core::{
+    config::DataType,
+    event::{Event, LogEvent, Value},
+    schema,
+};
+use vrl::value::Kind;
+
+use crate::encoding::BuildError;
+
+/// Errors that can occur during Parquet serialization.
+#[derive(Debug, Snafu)]
+pub enum ParquetSerializerError {
+    #[snafu(display(
+        r#"LogEvent contains a value with an invalid type. field = "{}" type = "{}" expected type = "{}""#,
+        field,
+        actual_type,
+        expected_type
+    ))]
+    InvalidValueType {
+        field: String,
+        actual_type: String,
+        expected_type: String,
+    },
+    #[snafu(display(
+        "Unable to derive a Parquet schema from the schema definition of the events. Configure the schema of the `parquet` codec, or enable schema support."
+    ))]
+    UnknownSchema,
+}
+
+/// Config used to build a `ParquetSerializer`.
+#[configurable_component]
+#[derive(Debug, Clone, Default)]
+pub struct ParquetSerializerConfig {
+    /// Parquet-specific encoding options.
+    #[serde(
+        default,
+        skip_serializing_if = "vector_core::serde::skip_serializing_if_default"
+    )]
+    pub parquet: ParquetSerializerOptions,
+}
+
+impl ParquetSerializerConfig {
+    /// Creates a new `ParquetSerializerConfig`.
+    pub const fn new(parquet: ParquetSerializerOptions) -> Self {
+        Self { parquet }
+    }
+
+    /// Build the `ParquetSerializer` from this configuration.
+    pub fn build(&self) -> Result<ParquetSerializer, BuildError> {
+        if self.parquet.row_group_size == 0 {
+            return Err("Parquet row group size must be greater than zero".into());
+        }
+
+        let properties = WriterProperties::builder()
+            .set_compression(self.parquet.compression.into())
+            .set_max_row_group_size(self.parquet.row_group_size)
+            .build();
+        let schema = self
+            .parquet
+            .schema
+            .as_ref()
+            .map(|columns| build_schema(columns.clone()))
+            .transpose()?;
+
+        Ok(ParquetSerializer {
+            schema,
+            properties: Arc::new(properties),
+            row_group_size: self.parquet.row_group_size,
+        })
+    }
+
+    /// The data type of events that are accepted by `ParquetSerializer`.
+    pub fn input_type(&self) -> DataType {
+        DataType::Log
+    }
+
+    /// The schema required by the serializer.
+    pub fn schema_requirement(&self) -> schema::Requirement {
+        schema::Requirement::empty()
+    }
+}
+
+/// Parquet-specific encoding options.
+#[configurable_component]
+#[derive(Debug, Clone, Derivative, PartialEq, Eq)]
+#[derivative(Default)]
+pub struct ParquetSerializerOptions {
+    /// The columns of the Parquet files, and their types.
+    ///
+    /// Each column is filled from the top-level event field of the same name. Missing fields and
+    /// `null` values are written as nulls, while values that cannot be converted to the type of
+    /// their column cause the whole batch to be rejected. Other fields are not written.
+    ///
+    /// When not set, the columns are derived from the fields known by the [schema
+    /// definition][schema] of the events, which requires schema support to be enabled. Fields
+    /// with more than one possible type, other than integers and floats, are written as JSON.
+    ///
+    /// [schema]: https://vector.dev/docs/reference/configuration/global-options/#schema
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[configurable(metadata(docs::additional_props_description = "The type of a column."))]
+    #[configurable(metadata(docs::examples = "example_schema()"))]
+    pub schema: Option<BTreeMap<String, ParquetColumnType>>,
+
+    /// The maximum number of rows in each row group.
+    ///
+    /// Batches with more events are written as several row groups in the same file.
+    #[serde(default = "default_row_group_size")]
+    #[derivative(Default(value = "default_row_group_size()"))]
+    pub row_group_size: usize,
+
+    /// The compression applied to the pages of the Parquet files.
+    #[configurable(derived)]
+    #[serde(default)]
+    pub compression: ParquetCompression,
+}
+
+const fn default_row_group_size() -> usize {
+    1024 * 1024
+}
+
+fn example_schema() -> BTreeMap<String, ParquetColumnType> {
+    BTreeMap::from_iter([
+        ("message".to_string(), ParquetColumnType::String),
+        ("status".to_string(), ParquetColumnType::Int64),
+        ("timestamp".to_string(), ParquetColumnType::Timestamp),
+    ])
+}
+
+/// The type of a Parquet column.
+#[configurable_component]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum ParquetColumnType {
+    /// Booleans.
+    Boolean,
+
+    /// 64-bit signed integers.
+    Int64,
+
+    /// 64-bit floating point numbers. Integers are converted to floats.
+    Float64,
+
+    /// UTF-8 strings. Values that aren't strings are written as their string representation.
+    String,
+
+    /// Timestamps, with a precision of microseconds, in UTC.
+    Timestamp,
+
+    /// Any value, written as a JSON string.
+    Json,
+}
+
+impl ParquetColumnType {
+    /// Picks the type of the column that holds the values of a field of the given kind.
+    fn from_kind(kind: &Kind) -> Self {
+        if kind.contains_object() || kind.contains_array() || kind.contains_regex() {
+            return Self::Json;
+        }
+        match (
+            kind.contains_bytes(),
+            kind.contains_integer(),
+            kind.contains_float(),
+            kind.contains_boolean(),
+            kind.contains_timestamp(),
+        ) {
+            (true, false, false, false, false) => Self::String,
+            (false, true, false, false, false) => Self::Int64,
+            (false, _, true, false, false) => Self::Float64,
+            (false, false, false, true, false) => Self::Boolean,
+            (false, false, false, false, true) => Self::Timestamp,
+            _ => Self::Json,
+        }
+    }
+
+    const fn name(self) -> &'static str {
+        match self {
+            Self::Boolean => "boolean",
+            Self::Int64 => "int64",
+            Self::Float64 => "float64",
+            Self::String => "string",
+            Self::Timestamp => "timestamp",
+            Self::Json => "json",
+        }
+    }
+}
+
+/// The compression applied to the pages of Parquet files.
+#[configurable_component]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum ParquetCompression {
+    /// No compression.
+    None,
+
+    /// [Snappy][snappy] compression.
+    ///
+    /// [snappy]: https://github.com/google/snappy
+    #[default]
+    Snappy,
+
+    /// [Gzip][gzip] compression.
+    ///
+    /// [gzip]: https://www.gzip.org/
+    Gzip,
+
+    /// [LZ4][lz4] compression.
+    ///
+    /// [lz4]: https://lz4.github.io/lz4/
+    Lz4,
+
+    /// [Zstandard][zstd] compression.
+    ///
+    /// [zstd]: https://facebook.github.io/zstd/
+    Zstd,
+}
+
+impl From<ParquetCompression> for Compression {
+    fn from(compression: ParquetCompression) -> Self {
+        match compression {
+            ParquetCompression::None => Compression::UNCOMPRESSED,
+            ParquetCompression::Snappy => Compression::SNAPPY,
+            ParquetCompression::Gzip => Compression::GZIP(GzipLevel::default()),
+            ParquetCompression::Lz4 => Compression::LZ4_RAW,
+            ParquetCompression::Zstd => Compression::ZSTD(ZstdLevel::default()),
+        }
+    }
+}
+
+/// The columns of a Parquet schema, in the order in which they are written.
+#[derive(Debug, Clone)]
+struct ParquetSchema {
+    columns: Vec<(String, ParquetColumnType)>,
+    schema: TypePtr,
+}
+
+fn build_schema(
+    columns: impl IntoIterator<Item = (String, ParquetColumnType)>,
+) -> Result<ParquetSchema, BuildError> {
+    let columns = columns.into_iter().collect::<Vec<_>>();
+    let fields = columns
+        .iter()
+        .map(|(name, column_type)| {
+            let (physical_type, logical_type) = match column_type {
+                ParquetColumnType::Boolean => (PhysicalType::BOOLEAN, None),
+                ParquetColumnType::Int64 => (PhysicalType::INT64, None),
+                ParquetColumnType::Float64 => (PhysicalType::DOUBLE, None),
+                ParquetColumnType::String => (PhysicalType::BYTE_ARRAY, Some(LogicalType::String)),
+                ParquetColumnType::Timestamp => (
+                    PhysicalType::INT64,
+                    Some(LogicalType::Timestamp {
+                        is_adjusted_to_u_t_c: true,
+                        unit: TimeUnit::MICROS(MicroSeconds {}),
+                    }),
+                ),
+                ParquetColumnType::Json => (PhysicalType::BYTE_ARRAY, Some(LogicalType::Json)),
+            };
+            Type::primitive_type_builder(name, physical_type)
+                .with_repetition(Repetition::OPTIONAL)
+                .with_logical_type(logical_type)
+                .build()
+                .map(Arc::new)
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let schema = Type::group_type_builder("schema")
+        .with_fields(fields)
+        .build()?;
+
+    Ok(ParquetSchema {
+        columns,
+        schema: Arc::new(schema),
+    })
+}
+
+/// Serializer that converts a batch of `Event`s to a single [Apache Parquet][parquet] file.
+///
+/// Each event is written as a row, whose columns are filled from the top-level fields of the
+/// event. The schema of the file is either configured, or derived from the schema definitions of
+/// all the events of the batch.
+///
+/// [parquet]: https://parquet.apache.org/
+#[derive(Debug, Clone)]
+pub struct ParquetSerializer {
+    schema: Option<ParquetSchema>,
+    properties: WriterPropertiesPtr,
+    row_group_size: usize,
+}
+
+impl ParquetSerializer {
+    /// Derives a schema with a column for each field known by the schema definition of any of the
+    /// events, so that events from different sources in the same batch all have their fields
+    /// written.
+    fn derive_schema(events: &[Event]) -> vector_common::Result<ParquetSchema> {
+        let mut definitions: Vec<&schema::Definition> = Vec::new();
+        let mut fields = BTreeMap::<String, Kind>::new();
+        for event in events {
+            // Events from the same source share their schema definition, so each one is only
+            // merged once.
+            let definition = event.metadata().schema_definition();
+            if definitions
+                .iter()
+                .any(|merged| std::ptr::eq(*merged, definition))
+            {
+                continue;
+            }
+            definitions.push(definition);
+
+            let object = definition
+                .event_kind()
+                .as_object()
+                .filter(|object| !object.known().is_empty())
+                .ok_or_else(|| UnknownSchemaSnafu.build())?;
+            for (field, kind) in object.known() {
+                fields
+                    .entry(field.to_string())
+                    .and_modify(|merged| *merged = merged.union(kind.clone()))
+                    .or_insert_with(|| kind.clone());
+            }
+        }
+
+        Ok(build_schema(fields.into_iter().map(|(field, kind)| {
+            (field, ParquetColumnType::from_kind(&kind))
+        }))?)
+    }
+
+    /// Serializes a batch of events, without requiring mutable access to the serializer so that
+    /// it can be shared between requests.
+    pub fn serialize(
+        &self,
+        events: Vec<Event>,
+        buffer: &mut BytesMut,
+    ) -> vector_common::Result<()> {
+        if events.is_empty() {
+            return Ok(());
+        }
+        let derived_schema;
+        let schema = match &self.schema {
+            Some(schema) => schema,
+            None => {
+                derived_schema = Self::derive_schema(&events)?;
+                &derived_schema
+            }
+        };
+        let logs = events.into_iter().map(Event::into_log).collect::<Vec<_>>();
+
+        let mut file = Vec::new();
+        let mut writer = SerializedFileWriter::new(
+            &mut file,
+            Arc::clone(&schema.schema),
+            Arc::clone(&self.properties),
+        )?;
+        for rows in logs.chunks(self.row_group_size) {
+            let mut row_group = writer.next_row_group()?;
+            for (name, column_type) in &schema.columns {
+                let mut column = row_group
+                    .next_column()?
+                    .expect("the schema defines a column for each configured column");
+                write_column(column.untyped(), name, *column_type, rows)?;
+                column.close()?;
+            }
+            row_group.close()?;
+        }
+        writer.close()?;
+
+        buffer.put_slice(&file);
+        Ok(())
+    }
+}
+
+impl Encoder<Vec<Event>> for ParquetSerializer {
+    type Error = vector_common::Error;
+
+    fn encode(&mut self, events: Vec<Event>, buffer: &mut BytesMut) -> Result<(), Self::Error> {
+        self.serialize(events, buffer)
+    }
+}
+
+/// Writes the values of the field `name` of each row to an optional column.
+fn write_column(
+    writer: &mut ColumnWriter<'_>,
+    name: &str,
+    column_type: ParquetColumnType,
+    rows: &[LogEvent],
+) -> vector_common::Result<()> {
+    let values = rows
+        .iter()
+        .map(|log| log.get(event_path!(name)).filter(|value| !value.is_null()));
+    let definition_levels = values
+        .clone()
+        .map(|value| i16::from(value.is_some()))
+        .collect::<Vec<_>>();
+    let values = values.flatten();
+
+    match (writer, column_type) {
+        (ColumnWriter::BoolColumnWriter(writer), ParquetColumnType::Boolean) => {
+            let values = values
+                .map(|value| match value {
+                    Value::Boolean(value) => Ok(*value),
+                    value => Err(invalid_value_type(name, value, column_type)),
+                })
+                .collect::<Result<Vec<_>, _>>()?;
+            writer.write_batch(&values, Some(&definition_levels), None)?;
+        }
+        (ColumnWriter::Int64ColumnWriter(writer), ParquetColumnType::Int64) => {
+            let values = values
+                .map(|value| match value {
+                    Value::Integer(value) => Ok(*value),
+                    value => Err(invalid_value_type(name, value, column_type)),
+                })
+                .collect::<Result<Vec<_>, _>>()?;
+            writer.write_batch(&values, Some(&definition_levels), None)?;
+        }
+        (ColumnWriter::Int64ColumnWriter(writer), ParquetColumnType::Timestamp) => {
+            let values = values
+                .map(|value| match value {
+                    Value::Timestamp(value) => Ok(value.timestamp_micros()),
+                    value => Err(invalid_value_type(name, value, column_type)),
+                })
+                .collect::<Result<Vec<_>, _>>()?;
+            writer.write_batch(&values, Some(&definition_levels), None)?;
+        }
+        (ColumnWriter::DoubleColumnWriter(writer), ParquetColumnType::Float64) => {
+            let values = values
+                .map(|value| match value {
+                    Value::Float(value) => Ok(value.into_inner()),
+                    Value::Integer(value) => Ok(*value as f64),
+                    value => Err(invalid_value_type(name, value, column_type)),
+                })
+                .collect::<Result<Vec<_>, _>>()?;
+            writer.write_batch(&values, Some(&definition_levels), None)?;
+        }
+        (ColumnWriter::ByteArrayColumnWriter(writer), ParquetColumnType::String) => {
+            let values = values
+                .map(|value| match value {
+                    Value::Bytes(bytes) => ByteArray::from(bytes.to_vec()),
+                    value => ByteArray::from(value.to_string_lossy().into_owned().into_bytes()),
+                })
+                .collect::<Vec<_>>();
+            writer.write_batch(&values, Some(&definition_levels), None)?;
+        }
+        (ColumnWriter::ByteArrayColumnWriter(writer), ParquetColumnType::Json) => {
+            let values = values
+                .map(|value| serde_json::to_vec(value).map(ByteArray::from))
+                .collect::<Result<Vec<_>, _>>()?;
+            writer.write_batch(&values, Some(&definition_levels), None)?;
+        }
+        _ => unreachable!("the column writer matches the physical type of the column"),
+    }
+    Ok(())
+}
+
+fn invalid_value_type(
+    field: &str,
+    value: &Value,
+    expected_type: ParquetColumnType,
+) -> ParquetSerializerError {
+    InvalidValueTypeSnafu {
+        field,
+        actual_type: value.kind_str(),
+        expected_type: expected_type.name(),
+    }
+    .build()
+}
+
+#[cfg(test)]
+mod tests {
+    use bytes::Bytes;
+    use chrono::{TimeZone, Utc};
+    use lookup::owned_value_path;
+    use ordered_float::NotNan;
+    use parquet::{
+        file::reader::{FileReader, SerializedFileReader},
+        record::Field,
+    };
+    use vector_core::event::EventMetadata;
+    use vrl::{btreemap, value::kind::Collection};
+
+    use super::*;
+
+    fn serialize(options: ParquetSerializerOptions, events: Vec<Event>) -> Bytes {
+        let mut bytes = BytesMut::new();
+        ParquetSerializerConfig::new(options)
+            .build()
+            .unwrap()
+            .encode(events, &mut bytes)
+            .unwrap();
+        bytes.freeze()
+    }
+
+    fn test_options() -> ParquetSerializerOptions {
+        ParquetSerializerOptions {
+            schema: Some(BTreeMap::from_iter([
+                ("message".to_string(), ParquetColumnType::String),
+                ("status".to_string(), ParquetColumnType::Int64),
+                ("duration".to_string(), ParquetColumnType::Float64),
+                ("success".to_string(), ParquetColumnType::Boolean),
+                ("timestamp".to_string(), ParquetColumnType::Timestamp),
+                ("details".to_string(), ParquetColumnType::Json),
+            ])),
+            ..Default::default()
+        }
+    }
+
+    fn test_events() -> Vec<Event> {
+        vec![
+            Event::Log(LogEvent::from(btreemap! {
+                "message" => "first",
+                "status" => 200,
+                "duration" => Value::Float(NotNan::new(1.5).unwrap()),
+                "success" => true,
+                "timestamp" => Utc.with_ymd_and_hms(2023, 2, 27, 15, 4, 49).unwrap(),
+                "details" => Value::Object(BTreeMap::from([("user".to_string(), Value::from("alice"))])),
+                "ignored" => "ignored",
+            })),
+            Event::Log(LogEvent::from(btreemap! {
+                "message" => "second",
+                "duration" => 2,
+                "success" => Value::Null,
+            })),
+        ]
+    }
+
+    #[test]
+    fn serialize_parquet() {
+        let bytes = serialize(test_options(), test_events());
+
+        let reader = SerializedFileReader::new(bytes).unwrap();
+        let metadata = reader.metadata();
+        assert_eq!(metadata.file_metadata().num_rows(), 2);
+        assert_eq!(metadata.file_metadata().schema_descr().num_columns(), 6);
+
+        let rows = reader
+            .get_row_iter(None)
+            .unwrap()
+            .map(|row| {
+                row.get_column_iter()
+                    .map(|(name, field)| (name.clone(), field.clone()))
+                    .collect::<BTreeMap<_, _>>()
+            })
+            .collect::<Vec<_>>();
+
+        assert_eq!(rows[0]["message"], Field::Str("first".into()));
+        assert_eq!(rows[0]["status"], Field::Long(200));
+        assert_eq!(rows[0]["duration"], Field::Double(1.5));
+        assert_eq!(rows[0]["success"], Field::Bool(true));
+        assert_eq!(
+            rows[0]["timestamp"],
+            Field::TimestampMicros(
+                Utc.with_ymd_and_hms(2023, 2, 27, 15, 4, 49)
+                    .unwrap()
+                    .timestamp_micros()
+            )
+        );
+        assert_eq!(rows[0]["details"], Field::Str(r#"{"user":"alice"}"#.into()));
+
+        assert_eq!(rows[1]["message"], Field::Str("second".into()));
+        assert_eq!(rows[1]["status"], Field::Null);
+        assert_eq!(rows[1]["duration"], Field::Double(2.0));
+        assert_eq!(rows[1]["success"], Field::Null);
+    }
+
+    #[test]
+    fn serialize_row_groups() {
+        let events = (0..5_i64)
+            .map(|index| Event::Log(LogEvent::from(btreemap! { "status" => index })))
+            .collect();
+        let options = ParquetSerializerOptions {
+            row_group_size: 2,
+            ..test_options()
+        };
+
+        let reader = SerializedFileReader::new(serialize(options, events)).unwrap();
+        assert_eq!(reader.metadata().file_metadata().num_rows(), 5);
+        assert_eq!(reader.metadata().num_row_groups(), 3);
+    }
+
+    #[test]
+    fn serialize_with_derived_schema() {
+        let definition = schema::Definition::new_with_default_metadata(
+            Kind::object(Collection::empty()),
+            [vector_core::config::LogNamespace::Vector],
+        )
+        .with_event_field(&owned_value_path!("message"), Kind::bytes(), None)
+        .with_event_field(
+            &owned_value_path!("count"),
+            Kind::integer().or_float(),
+            None,
+        )
+        .with_event_field(
+            &owned_value_path!("tags"),
+            Kind::array(Collection::any()),
+            None,
+        );
+        let metadata = EventMetadata::default().with_schema_definition(&Arc::new(definition));
+        let log = LogEvent::from_map(
+            btreemap! {
+                "message" => "hello",
+                "count" => 3,
+                "tags" => vec!["a", "b"],
+            },
+            metadata,
+        );
+
+        let reader = SerializedFileReader::new(serialize(
+            ParquetSerializerOptions::default(),
+            vec![Event::Log(log)],
+        ))
+        .unwrap();
+        let row = reader.get_row_iter(None).unwrap().next().unwrap();
+        let row = row
+            .get_column_iter()
+            .map(|(name, field)| (name.clone(), field.clone()))
+            .collect::<BTreeMap<_, _>>();
+        assert_eq!(row["message"], Field::Str("hello".into()));
+        assert_eq!(row["count"], Field::Double(3.0));
+        assert_eq!(row["tags"], Field::Str(r#"["a","b"]"#.into()));
+    }
+
+    #[test]
+    fn serialize_with_schema_derived_from_batch() {
+        let definition = |field: &str, kind: Kind| {
+            Arc::new(
+                schema::Definition::new_with_default_metadata(
+                    Kind::object(Collection::empty()),
+                    [vector_core::config::LogNamespace::Vector],
+                )
+                .with_event_field(&owned_value_path!(field), kind, None),
+            )
+        };
+        let log = |fields: BTreeMap<String, Value>, definition: &Arc<schema::Definition>| {
+            Event::Log(LogEvent::from_map(
+                fields,
+                EventMetadata::default().with_schema_definition(definition),
+            ))
+        };
+        let first = definition("message", Kind::bytes());
+        let second = definition("count", Kind::integer());
+        let third = definition("count", Kind::float());
+        let events = vec![
+            log(btreemap! { "message" => "hello" }, &first),
+            log(btreemap! { "count" => 3 }, &second),
+            log(btreemap! { "count" => 1.5 }, &third),
+        ];
+
+        let reader =
+            SerializedFileReader::new(serialize(ParquetSerializerOptions::default(), events))
+                .unwrap();
+        assert_eq!(
+            reader
+                .metadata()
+                .file_metadata()
+                .schema_descr()
+                .num_columns(),
+            2
+        );
+        let rows = reader
+            .get_row_iter(None)
+            .unwrap()
+            .map(|row| {
+                row.get_column_iter()
+                    .map(|(name, field)| (name.clone(), field.clone()))
+                    .collect::<BTreeMap<_, _>>()
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(rows[0]["message"], Field::Str("hello".into()));
+        assert_eq!(rows[0]["count"], Field::Null);
+        assert_eq!(rows[1]["count"], Field::Double(3.0));
+        assert_eq!(rows[2]["count"], Field::Double(1.5));
+        assert_eq!(rows[2]["message"], Field::Null);
+    }
+
+    #[test]
+    fn serialize_error_invalid_value_type() {
+        let mut bytes = BytesMut::new();
+        let error = ParquetSerializerConfig::new(test_options())
+            .build()
+            .unwrap()
+            .encode(
+                vec![Event::Log(LogEvent::from(btreemap! { "status" => "ok" }))],
+                &mut bytes,
+            )
+            .unwrap_err();
+
+        assert_eq!(
+            error.to_string(),
+            r#"LogEvent contains a value with an invalid type. field = "status" type = "string" expected type = "int64""#
+        );
+    }
+
+    #[test]
+    fn serialize_error_unknown_schema() {
+        let mut bytes = BytesMut::new();
+        let result = ParquetSerializerConfig::default()
+            .build()
+            .unwrap()
+            .encode(vec![Event::Log(LogEvent::from("hello"))], &mut bytes);
+
+        assert!(result.is_err());
+    }
+}
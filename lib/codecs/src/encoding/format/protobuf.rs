@@ -0,0 +1,596 @@
+use std::{collections::HashMap, fs, path::PathBuf};
+
+use bytes::BytesMut;
+use prost::Message as _;
+use prost_reflect::{
+    DescriptorPool, DynamicMessage, FieldDescriptor, Kind, MapKey, MessageDescriptor,
+};
+use snafu::Snafu;
+use tokio_util::codec::Encoder;
+use vector_config::configurable_component;
+use vector_core::{config::DataType, event::Event, event::Value, schema};
+
+use crate::encoding::BuildError;
+
+/// The full name of the well-known `google.protobuf.Timestamp` message type.
+const TIMESTAMP_MESSAGE_TYPE: &str = "google.protobuf.Timestamp";
+
+/// Errors that can occur during Protobuf serialization.
+#[derive(Debug, Snafu)]
+pub enum ProtobufSerializerError {
+    #[snafu(display(
+        r#"LogEvent contains a value with an invalid type. field = "{}" type = "{}" expected type = "{}""#,
+        field,
+        actual_type,
+        expected_type
+    ))]
+    InvalidValueType {
+        field: String,
+        actual_type: String,
+        expected_type: String,
+    },
+    #[snafu(display(
+        r#"LogEvent contains a value that is out of range. field = "{}" value = "{}" expected type = "{}""#,
+        field,
+        value,
+        expected_type
+    ))]
+    ValueOutOfRange {
+        field: String,
+        value: String,
+        expected_type: String,
+    },
+    #[snafu(display(
+        r#"LogEvent contains an unknown enum value. field = "{}" value = "{}" enum type = "{}""#,
+        field,
+        value,
+        enum_type
+    ))]
+    UnknownEnumValue {
+        field: String,
+        value: String,
+        enum_type: String,
+    },
+}
+
+/// Config used to build a `ProtobufSerializer`.
+#[configurable_component]
+#[derive(Debug, Clone)]
+pub struct ProtobufSerializerConfig {
+    /// Options for the Protobuf serializer.
+    pub protobuf: ProtobufSerializerOptions,
+}
+
+impl ProtobufSerializerConfig {
+    /// Build the `ProtobufSerializer` from this configuration.
+    pub fn build(&self) -> Result<ProtobufSerializer, BuildError> {
+        let message_descriptor =
+            get_message_descriptor(&self.protobuf.desc_file, &self.protobuf.message_type)?;
+        Ok(ProtobufSerializer::new(message_descriptor))
+    }
+
+    /// The data type of events that are accepted by `ProtobufSerializer`.
+    pub fn input_type(&self) -> DataType {
+        DataType::Log
+    }
+
+    /// The schema required by the serializer.
+    pub fn schema_requirement(&self) -> schema::Requirement {
+        // TODO: Convert the message descriptor to a vector schema requirement.
+        schema::Requirement::empty()
+    }
+}
+
+/// Protobuf serializer options.
+#[configurable_component]
+#[derive(Clone, Debug)]
+pub struct ProtobufSerializerOptions {
+    /// The path to the protobuf descriptor set file.
+    ///
+    /// This file is the output of `protoc -o <path> ...`, and must contain the definition of
+    /// `message_type` along with any message types it depends on.
+    #[configurable(metadata(docs::examples = "/etc/vector/protobuf_descriptor_set.desc"))]
+    pub desc_file: PathBuf,
+
+    /// The name of the message type to use for serializing.
+    #[configurable(metadata(docs::examples = "package.Message"))]
+    pub message_type: String,
+}
+
+/// Serializer that converts an `Event` to bytes using a Protobuf message type
+/// loaded from a descriptor set.
+#[derive(Debug, Clone)]
+pub struct ProtobufSerializer {
+    message_descriptor: MessageDescriptor,
+}
+
+impl ProtobufSerializer {
+    /// Creates a new `ProtobufSerializer`.
+    pub const fn new(message_descriptor: MessageDescriptor) -> Self {
+        Self { message_descriptor }
+    }
+
+    /// Get a description of the message type used for serialization.
+    pub const fn descriptor(&self) -> &MessageDescriptor {
+        &self.message_descriptor
+    }
+
+    /// Get the path of the message type within its file, as the index of each message type in
+    /// its parent, starting from the top-level one.
+    ///
+    /// This is how schema registries identify the message type of a schema.
+    pub fn message_indexes(&self) -> Vec<i32> {
+        let mut indexes = Vec::new();
+        let mut message = self.message_descriptor.clone();
+        loop {
+            let parent = message.parent_message();
+            let index = match &parent {
+                Some(parent) => parent
+                    .child_messages()
+                    .position(|child| child.full_name() == message.full_name()),
+                None => message
+                    .parent_file()
+                    .messages()
+                    .position(|sibling| sibling.full_name() == message.full_name()),
+            };
+            indexes.push(index.expect("message is a child of its parent") as i32);
+            match parent {
+                Some(parent) => message = parent,
+                None => break,
+            }
+        }
+        indexes.reverse();
+        indexes
+    }
+}
+
+impl Encoder<Event> for ProtobufSerializer {
+    type Error = vector_common::Error;
+
+    fn encode(&mut self, event: Event, buffer: &mut BytesMut) -> Result<(), Self::Error> {
+        let (value, _) = event.into_log().into_parts();
+        let message = encode_message(&self.message_descriptor, value, "")?;
+        message.encode(buffer)?;
+        Ok(())
+    }
+}
+
+fn get_message_descriptor(
+    desc_file: &PathBuf,
+    message_type: &str,
+) -> Result<MessageDescriptor, BuildError> {
+    let b = fs::read(desc_file)
+        .map_err(|e| format!("Failed to open protobuf desc file '{desc_file:?}': {e}"))?;
+    let pool = DescriptorPool::decode(b.as_slice())
+        .map_err(|e| format!("Failed to parse protobuf desc file '{desc_file:?}': {e}"))?;
+    pool.get_message_by_name(message_type).ok_or_else(|| {
+        format!("The message type '{message_type}' could not be found in '{desc_file:?}'").into()
+    })
+}
+
+/// Builds a message from an object, setting each field whose name matches a field of the message.
+///
+/// Keys that don't correspond to a message field, and null values, are skipped.
+fn encode_message(
+    descriptor: &MessageDescriptor,
+    value: Value,
+    path: &str,
+) -> Result<DynamicMessage, ProtobufSerializerError> {
+    let object = match value {
+        Value::Object(object) => object,
+        value => return invalid_type(path, descriptor.full_name(), &value),
+    };
+
+    let mut message = DynamicMessage::new(descriptor.clone());
+    for (key, value) in object {
+        let Some(field) = descriptor.get_field_by_name(&key) else {
+            continue;
+        };
+        if value.is_null() {
+            continue;
+        }
+
+        let field_path = if path.is_empty() {
+            key
+        } else {
+            format!("{path}.{key}")
+        };
+        let value = convert_field(&field, value, &field_path)?;
+        message.set_field(&field, value);
+    }
+    Ok(message)
+}
+
+fn convert_field(
+    field: &FieldDescriptor,
+    value: Value,
+    path: &str,
+) -> Result<prost_reflect::Value, ProtobufSerializerError> {
+    if field.is_map() {
+        let kind = field.kind();
+        let entry = kind
+            .as_message()
+            .expect("map fields should always have a map entry message type");
+        let key_kind = entry.map_entry_key_field().kind();
+        let value_kind = entry.map_entry_value_field().kind();
+
+        let object = match value {
+            Value::Object(object) => object,
+            value => return invalid_type(path, "map", &value),
+        };
+        object
+            .into_iter()
+            .map(|(key, value)| {
+                let entry_path = format!("{path}.{key}");
+                let value = convert_value(&value_kind, value, &entry_path)?;
+                let key = convert_map_key(&key_kind, key, &entry_path)?;
+                Ok((key, value))
+            })
+            .collect::<Result<HashMap<_, _>, _>>()
+            .map(prost_reflect::Value::Map)
+    } else if field.is_list() {
+        let kind = field.kind();
+        let array = match value {
+            Value::Array(array) => array,
+            value => return invalid_type(path, "array", &value),
+        };
+        array
+            .into_iter()
+            .enumerate()
+            .map(|(i, value)| convert_value(&kind, value, &format!("{path}[{i}]")))
+            .collect::<Result<Vec<_>, _>>()
+            .map(prost_reflect::Value::List)
+    } else {
+        convert_value(&field.kind(), value, path)
+    }
+}
+
+fn convert_value(
+    kind: &Kind,
+    value: Value,
+    path: &str,
+) -> Result<prost_reflect::Value, ProtobufSerializerError> {
+    let value = match (kind, value) {
+        (Kind::Double, Value::Float(f)) => prost_reflect::Value::F64(f.into_inner()),
+        (Kind::Double, Value::Integer(i)) => prost_reflect::Value::F64(i as f64),
+        (Kind::Float, Value::Float(f)) => prost_reflect::Value::F32(f.into_inner() as f32),
+        (Kind::Float, Value::Integer(i)) => prost_reflect::Value::F32(i as f32),
+        (Kind::Int32 | Kind::Sint32 | Kind::Sfixed32, Value::Integer(i)) => {
+            prost_reflect::Value::I32(in_range(i, kind, path)?)
+        }
+        (Kind::Int64 | Kind::Sint64 | Kind::Sfixed64, Value::Integer(i)) => {
+            prost_reflect::Value::I64(i)
+        }
+        (Kind::Uint32 | Kind::Fixed32, Value::Integer(i)) => {
+            prost_reflect::Value::U32(in_range(i, kind, path)?)
+        }
+        (Kind::Uint64 | Kind::Fixed64, Value::Integer(i)) => {
+            prost_reflect::Value::U64(in_range(i, kind, path)?)
+        }
+        (Kind::Bool, Value::Boolean(b)) => prost_reflect::Value::Bool(b),
+        (Kind::String, Value::Bytes(b)) => {
+            prost_reflect::Value::String(String::from_utf8_lossy(&b).into_owned())
+        }
+        (Kind::Bytes, Value::Bytes(b)) => prost_reflect::Value::Bytes(b),
+        (Kind::Enum(descriptor), Value::Bytes(b)) => {
+            let name = String::from_utf8_lossy(&b);
+            let value = descriptor.get_value_by_name(&name).ok_or_else(|| {
+                ProtobufSerializerError::UnknownEnumValue {
+                    field: path.to_string(),
+                    value: name.to_string(),
+                    enum_type: descriptor.full_name().to_string(),
+                }
+            })?;
+            prost_reflect::Value::EnumNumber(value.number())
+        }
+        (Kind::Enum(descriptor), Value::Integer(i)) => {
+            let number = in_range(i, &Kind::Int32, path)?;
+            if descriptor.get_value(number).is_none() {
+                return Err(ProtobufSerializerError::UnknownEnumValue {
+                    field: path.to_string(),
+                    value: i.to_string(),
+                    enum_type: descriptor.full_name().to_string(),
+                });
+            }
+            prost_reflect::Value::EnumNumber(number)
+        }
+        (Kind::Message(descriptor), Value::Timestamp(ts))
+            if descriptor.full_name() == TIMESTAMP_MESSAGE_TYPE =>
+        {
+            let mut message = DynamicMessage::new(descriptor.clone());
+            message.set_field_by_name("seconds", prost_reflect::Value::I64(ts.timestamp()));
+            message.set_field_by_name(
+                "nanos",
+                prost_reflect::Value::I32(ts.timestamp_subsec_nanos() as i32),
+            );
+            prost_reflect::Value::Message(message)
+        }
+        (Kind::Message(descriptor), value) => {
+            prost_reflect::Value::Message(encode_message(descriptor, value, path)?)
+        }
+        (kind, value) => return invalid_type(path, &kind_name(kind), &value),
+    };
+    Ok(value)
+}
+
+fn convert_map_key(
+    kind: &Kind,
+    key: String,
+    path: &str,
+) -> Result<MapKey, ProtobufSerializerError> {
+    let invalid = || ProtobufSerializerError::InvalidValueType {
+        field: path.to_string(),
+        actual_type: "string".to_string(),
+        expected_type: kind_name(kind),
+    };
+    let key = match kind {
+        Kind::String => MapKey::String(key),
+        Kind::Bool => MapKey::Bool(key.parse().map_err(|_| invalid())?),
+        Kind::Int32 | Kind::Sint32 | Kind::Sfixed32 => {
+            MapKey::I32(key.parse().map_err(|_| invalid())?)
+        }
+        Kind::Int64 | Kind::Sint64 | Kind::Sfixed64 => {
+            MapKey::I64(key.parse().map_err(|_| invalid())?)
+        }
+        Kind::Uint32 | Kind::Fixed32 => MapKey::U32(key.parse().map_err(|_| invalid())?),
+        Kind::Uint64 | Kind::Fixed64 => MapKey::U64(key.parse().map_err(|_| invalid())?),
+        _ => return Err(invalid()),
+    };
+    Ok(key)
+}
+
+fn in_range<T: TryFrom<i64>>(
+    value: i64,
+    kind: &Kind,
+    path: &str,
+) -> Result<T, ProtobufSerializerError> {
+    T::try_from(value).map_err(|_| ProtobufSerializerError::ValueOutOfRange {
+        field: path.to_string(),
+        value: value.to_string(),
+        expected_type: kind_name(kind),
+    })
+}
+
+fn invalid_type<T>(
+    path: &str,
+    expected_type: &str,
+    value: &Value,
+) -> Result<T, ProtobufSerializerError> {
+    Err(ProtobufSerializerError::InvalidValueType {
+        field: path.to_string(),
+        actual_type: value.kind_str().to_string(),
+        expected_type: expected_type.to_string(),
+    })
+}
+
+fn kind_name(kind: &Kind) -> String {
+    match kind {
+        Kind::Double => "double",
+        Kind::Float => "float",
+        Kind::Int32 => "int32",
+        Kind::Int64 => "int64",
+        Kind::Uint32 => "uint32",
+        Kind::Uint64 => "uint64",
+        Kind::Sint32 => "sint32",
+        Kind::Sint64 => "sint64",
+        Kind::Fixed32 => "fixed32",
+        Kind::Fixed64 => "fixed64",
+        Kind::Sfixed32 => "sfixed32",
+        Kind::Sfixed64 => "sfixed64",
+        Kind::Bool => "bool",
+        Kind::String => "string",
+        Kind::Bytes => "bytes",
+        Kind::Message(descriptor) => return descriptor.full_name().to_string(),
+        Kind::Enum(descriptor) => return descriptor.full_name().to_string(),
+    }
+    .to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::env;
+
+    use bytes::Bytes;
+    use chrono::{TimeZone, Utc};
+    use vector_core::{config::LogNamespace, event::LogEvent};
+    use vrl::btreemap;
+
+    use super::*;
+    use crate::decoding::{Deserializer, ProtobufDeserializerConfig, ProtobufDeserializerOptions};
+
+    fn test_data_dir() -> PathBuf {
+        PathBuf::from(env::var_os("CARGO_MANIFEST_DIR").unwrap())
+            .join("tests/data/decoding/protobuf")
+    }
+
+    fn build_serializer(message_type: &str) -> ProtobufSerializer {
+        ProtobufSerializerConfig {
+            protobuf: ProtobufSerializerOptions {
+                desc_file: test_data_dir().join("test_protobuf3.desc"),
+                message_type: message_type.to_string(),
+            },
+        }
+        .build()
+        .unwrap()
+    }
+
+    fn encode(serializer: &mut ProtobufSerializer, log: LogEvent) -> vector_common::Result<Bytes> {
+        let mut buffer = BytesMut::new();
+        serializer.encode(Event::Log(log), &mut buffer)?;
+        Ok(buffer.freeze())
+    }
+
+    #[test]
+    fn message_indexes() {
+        assert_eq!(
+            build_serializer("test_protobuf3.Person").message_indexes(),
+            vec![0]
+        );
+        assert_eq!(
+            build_serializer("test_protobuf3.Person.PhoneNumber").message_indexes(),
+            vec![0, 0]
+        );
+        assert_eq!(
+            build_serializer("test_protobuf3.AddressBook").message_indexes(),
+            vec![1]
+        );
+    }
+
+    #[test]
+    fn build_error_unknown_message_type() {
+        let config = ProtobufSerializerConfig {
+            protobuf: ProtobufSerializerOptions {
+                desc_file: test_data_dir().join("test_protobuf3.desc"),
+                message_type: "test_protobuf3.Unknown".to_string(),
+            },
+        };
+
+        assert!(config.build().is_err());
+    }
+
+    #[test]
+    fn serialize_protobuf3() {
+        let mut serializer = build_serializer("test_protobuf3.Person");
+        let log = LogEvent::from(btreemap! {
+            "name" => "someone",
+            "id" => 42,
+            "data" => btreemap! {
+                "data_phone" => "HOME",
+            },
+            "phones" => vec![Value::from(btreemap! {
+                "number" => "1234",
+                "type" => 2,
+            })],
+            "source_type" => "demo_logs",
+        });
+
+        let bytes = encode(&mut serializer, log).unwrap();
+        let message = DynamicMessage::decode(serializer.descriptor().clone(), bytes).unwrap();
+
+        assert_eq!(
+            message.get_field_by_name("name").unwrap().as_str(),
+            Some("someone")
+        );
+        assert_eq!(message.get_field_by_name("id").unwrap().as_i32(), Some(42));
+        let phones = message.get_field_by_name("phones").unwrap();
+        let phone = phones.as_list().unwrap()[0].as_message().unwrap();
+        assert_eq!(
+            phone.get_field_by_name("number").unwrap().as_str(),
+            Some("1234")
+        );
+        assert_eq!(
+            phone.get_field_by_name("type").unwrap().as_enum_number(),
+            Some(2)
+        );
+        let data = message.get_field_by_name("data").unwrap();
+        assert_eq!(
+            data.as_map()
+                .unwrap()
+                .get(&MapKey::String("data_phone".to_string()))
+                .and_then(|value| value.as_enum_number()),
+            Some(1)
+        );
+    }
+
+    #[test]
+    fn roundtrip_with_deserializer() {
+        let mut serializer = build_serializer("test_protobuf3.Person");
+        let deserializer = ProtobufDeserializerConfig {
+            protobuf: ProtobufDeserializerOptions {
+                desc_file: test_data_dir().join("test_protobuf3.desc"),
+                message_type: "test_protobuf3.Person".to_string(),
+            },
+        }
+        .build()
+        .unwrap();
+        let log = LogEvent::from(btreemap! {
+            "name" => "someone",
+            "phones" => vec![Value::from(btreemap! {
+                "number" => "1234",
+            })],
+        });
+
+        let bytes = encode(&mut serializer, log).unwrap();
+        let events = deserializer.parse(bytes, LogNamespace::Vector).unwrap();
+        let log = events[0].as_log();
+
+        assert_eq!(log["name"], "someone".into());
+        assert_eq!(
+            log["phones"].as_array().unwrap()[0].as_object().unwrap()["number"],
+            "1234".into()
+        );
+    }
+
+    #[test]
+    fn serialize_timestamp() {
+        let pool = DescriptorPool::global();
+        let descriptor = pool.get_message_by_name(TIMESTAMP_MESSAGE_TYPE).unwrap();
+        let ts = Utc.timestamp_opt(1_600_000_000, 123_000_000).unwrap();
+
+        let value = convert_value(
+            &Kind::Message(descriptor),
+            Value::Timestamp(ts),
+            "timestamp",
+        )
+        .unwrap();
+        let message = value.as_message().unwrap();
+
+        assert_eq!(
+            message.get_field_by_name("seconds").unwrap().as_i64(),
+            Some(1_600_000_000)
+        );
+        assert_eq!(
+            message.get_field_by_name("nanos").unwrap().as_i32(),
+            Some(123_000_000)
+        );
+    }
+
+    #[test]
+    fn serialize_error_invalid_type() {
+        let mut serializer = build_serializer("test_protobuf3.Person");
+        let log = LogEvent::from(btreemap! {
+            "phones" => vec![Value::from(btreemap! {
+                "number" => 1234,
+            })],
+        });
+
+        let error = encode(&mut serializer, log).unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            r#"LogEvent contains a value with an invalid type. field = "phones[0].number" type = "integer" expected type = "string""#
+        );
+    }
+
+    #[test]
+    fn serialize_error_out_of_range() {
+        let mut serializer = build_serializer("test_protobuf3.Person");
+        let log = LogEvent::from(btreemap! {
+            "id" => i64::MAX,
+        });
+
+        let error = encode(&mut serializer, log).unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            format!(
+                r#"LogEvent contains a value that is out of range. field = "id" value = "{}" expected type = "int32""#,
+                i64::MAX
+            )
+        );
+    }
+
+    #[test]
+    fn serialize_error_unknown_enum_value() {
+        let mut serializer = build_serializer("test_protobuf3.Person");
+        let log = LogEvent::from(btreemap! {
+            "phones" => vec![Value::from(btreemap! {
+                "type" => "FAX",
+            })],
+        });
+
+        let error = encode(&mut serializer, log).unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            r#"LogEvent contains an unknown enum value. field = "phones[0].type" value = "FAX" enum type = "test_protobuf3.Person.PhoneType""#
+        );
+    }
+}
@@ -0,0 +1,516 @@
+use std::fmt::Write as _;
+
+use bytes::{BufMut, BytesMut};
+use chrono::{DateTime, SecondsFormat, Utc};
+use derivative::Derivative;
+use lookup::lookup_v2::OptionalTargetPath;
+use tokio_util::codec::Encoder;
+use vector_core::{
+    config::DataType,
+    event::{Event, LogEvent, Value},
+    schema,
+};
+
+/// The facility used when the event doesn't contain a valid one: `user`.
+const DEFAULT_FACILITY: u8 = 1;
+
+/// The severity used when the event doesn't contain a valid one: `informational`.
+const DEFAULT_SEVERITY: u8 = 6;
+
+/// The value of header fields that aren't set.
+const NILVALUE: &str = "-";
+
+/// Config used to build a `SyslogSerializer`.
+#[crate::configurable_component]
+#[derive(Debug, Clone, Default)]
+pub struct SyslogSerializerConfig {
+    /// Syslog-specific encoding options.
+    #[serde(
+        default,
+        skip_serializing_if = "vector_core::serde::skip_serializing_if_default"
+    )]
+    pub syslog: SyslogSerializerOptions,
+}
+
+impl SyslogSerializerConfig {
+    /// Creates a new `SyslogSerializerConfig`.
+    pub const fn new(syslog: SyslogSerializerOptions) -> Self {
+        Self { syslog }
+    }
+
+    /// Build the `SyslogSerializer` from this configuration.
+    pub fn build(&self) -> SyslogSerializer {
+        SyslogSerializer::new(self.syslog.clone())
+    }
+
+    /// The data type of events that are accepted by `SyslogSerializer`.
+    pub fn input_type(&self) -> DataType {
+        DataType::Log
+    }
+
+    /// The schema required by the serializer.
+    pub fn schema_requirement(&self) -> schema::Requirement {
+        schema::Requirement::empty()
+    }
+}
+
+/// The syslog protocol to encode messages with.
+#[crate::configurable_component]
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum SyslogRfc {
+    /// The legacy [BSD syslog protocol][rfc3164].
+    ///
+    /// Structured data isn't supported by this protocol, and is left out of the message.
+    ///
+    /// [rfc3164]: https://datatracker.ietf.org/doc/html/rfc3164
+    Rfc3164,
+
+    /// The [syslog protocol][rfc5424].
+    ///
+    /// [rfc5424]: https://datatracker.ietf.org/doc/html/rfc5424
+    #[default]
+    Rfc5424,
+}
+
+/// Syslog serializer options.
+#[crate::configurable_component]
+#[derive(Debug, Clone, Derivative, PartialEq, Eq)]
+#[derivative(Default)]
+pub struct SyslogSerializerOptions {
+    #[configurable(derived)]
+    #[serde(default)]
+    pub rfc: SyslogRfc,
+
+    /// The field containing the facility of the message.
+    ///
+    /// The facility can either be a name, such as `local0`, or its numerical code. If the field is
+    /// missing or has an invalid value, the `user` facility is used.
+    #[serde(default = "default_facility_field")]
+    #[derivative(Default(value = "default_facility_field()"))]
+    pub facility_field: OptionalTargetPath,
+
+    /// The field containing the severity of the message.
+    ///
+    /// The severity can either be a name, such as `err` or `warning`, or its numerical code. If the
+    /// field is missing or has an invalid value, the `informational` severity is used.
+    #[serde(default = "default_severity_field")]
+    #[derivative(Default(value = "default_severity_field()"))]
+    pub severity_field: OptionalTargetPath,
+
+    /// The field containing the name of the application that originated the message.
+    ///
+    /// This is used as the tag of RFC 3164 messages.
+    #[serde(default = "default_app_name_field")]
+    #[derivative(Default(value = "default_app_name_field()"))]
+    pub app_name_field: OptionalTargetPath,
+
+    /// The field containing the ID of the process that originated the message.
+    #[serde(default = "default_proc_id_field")]
+    #[derivative(Default(value = "default_proc_id_field()"))]
+    pub proc_id_field: OptionalTargetPath,
+
+    /// The field containing the type of the message.
+    ///
+    /// This is only used by RFC 5424 messages.
+    #[serde(default = "default_msg_id_field")]
+    #[derivative(Default(value = "default_msg_id_field()"))]
+    pub msg_id_field: OptionalTargetPath,
+
+    /// The field containing the structured data of the message.
+    ///
+    /// The field must be a map of structured data IDs to maps of parameter names to their values,
+    /// such as `{"exampleSDID@32473": {"iut": "3", "eventSource": "Application"}}`. Arrays of
+    /// values are encoded as repeated parameters.
+    ///
+    /// This is only used by RFC 5424 messages.
+    #[serde(default)]
+    #[configurable(metadata(docs::examples = "structured_data"))]
+    pub structured_data_field: OptionalTargetPath,
+}
+
+fn default_facility_field() -> OptionalTargetPath {
+    OptionalTargetPath::event("facility")
+}
+
+fn default_severity_field() -> OptionalTargetPath {
+    OptionalTargetPath::event("severity")
+}
+
+fn default_app_name_field() -> OptionalTargetPath {
+    OptionalTargetPath::event("appname")
+}
+
+fn default_proc_id_field() -> OptionalTargetPath {
+    OptionalTargetPath::event("procid")
+}
+
+fn default_msg_id_field() -> OptionalTargetPath {
+    OptionalTargetPath::event("msgid")
+}
+
+/// Serializer that converts an `Event` to bytes using the syslog format.
+#[derive(Debug, Clone)]
+pub struct SyslogSerializer {
+    options: SyslogSerializerOptions,
+}
+
+impl SyslogSerializer {
+    /// Creates a new `SyslogSerializer`.
+    pub const fn new(options: SyslogSerializerOptions) -> Self {
+        Self { options }
+    }
+
+    fn get<'a>(&self, log: &'a LogEvent, field: &OptionalTargetPath) -> Option<&'a Value> {
+        field.as_ref().and_then(|path| log.get(path))
+    }
+
+    fn get_string(&self, log: &LogEvent, field: &OptionalTargetPath) -> Option<String> {
+        self.get(log, field)
+            .map(|value| value.to_string_lossy().into_owned())
+    }
+
+    fn priority(&self, log: &LogEvent) -> u8 {
+        let facility = self
+            .get(log, &self.options.facility_field)
+            .and_then(|value| parse_code(value, FACILITIES))
+            .unwrap_or(DEFAULT_FACILITY);
+        let severity = self
+            .get(log, &self.options.severity_field)
+            .and_then(|value| parse_code(value, SEVERITIES))
+            .unwrap_or(DEFAULT_SEVERITY);
+        facility * 8 + severity
+    }
+}
+
+impl Encoder<Event> for SyslogSerializer {
+    type Error = vector_common::Error;
+
+    fn encode(&mut self, event: Event, buffer: &mut BytesMut) -> Result<(), Self::Error> {
+        let log = event.into_log();
+
+        let priority = self.priority(&log);
+        let timestamp = match log.get_timestamp() {
+            Some(Value::Timestamp(timestamp)) => *timestamp,
+            _ => Utc::now(),
+        };
+        let hostname = log
+            .get_host()
+            .map(|value| value.to_string_lossy().into_owned());
+        let app_name = self.get_string(&log, &self.options.app_name_field);
+        let proc_id = self.get_string(&log, &self.options.proc_id_field);
+        let message = log
+            .get_message()
+            .map(|value| value.to_string_lossy().into_owned())
+            .unwrap_or_default();
+
+        let mut output = String::new();
+        match self.options.rfc {
+            SyslogRfc::Rfc5424 => {
+                let msg_id = self.get_string(&log, &self.options.msg_id_field);
+                let structured_data =
+                    encode_structured_data(self.get(&log, &self.options.structured_data_field));
+                write!(
+                    output,
+                    "<{}>1 {} {} {} {} {} {}",
+                    priority,
+                    timestamp.to_rfc3339_opts(SecondsFormat::Micros, true),
+                    header_field(hostname.as_deref(), 255),
+                    header_field(app_name.as_deref(), 48),
+                    header_field(proc_id.as_deref(), 128),
+                    header_field(msg_id.as_deref(), 32),
+                    structured_data,
+                )?;
+                if !message.is_empty() {
+                    write!(output, " {}", message)?;
+                }
+            }
+            SyslogRfc::Rfc3164 => {
+                write!(
+                    output,
+                    "<{}>{} {} ",
+                    priority,
+                    bsd_timestamp(timestamp),
+                    header_field(hostname.as_deref(), 255),
+                )?;
+                // The tag is left out altogether when there is no application name, as it can't be
+                // told apart from the message otherwise.
+                if let Some(app_name) = app_name.as_deref().filter(|name| !name.is_empty()) {
+                    output.push_str(&header_field(Some(app_name), 32));
+                    if let Some(proc_id) = proc_id.as_deref().filter(|id| !id.is_empty()) {
+                        write!(output, "[{}]", header_field(Some(proc_id), 128))?;
+                    }
+                    output.push_str(": ");
+                }
+                output.push_str(&message);
+            }
+        }
+
+        buffer.put_slice(output.as_bytes());
+        Ok(())
+    }
+}
+
+/// Facility names, indexed by their numerical code.
+///
+/// Names that are commonly used for the same facility are listed together.
+const FACILITIES: &[&[&str]] = &[
+    &["kern"],
+    &["user"],
+    &["mail"],
+    &["daemon"],
+    &["auth", "security"],
+    &["syslog"],
+    &["lpr"],
+    &["news"],
+    &["uucp"],
+    &["cron"],
+    &["authpriv"],
+    &["ftp"],
+    &["ntp"],
+    &["audit", "logaudit"],
+    &["alert", "logalert"],
+    &["clockd", "clock"],
+    &["local0"],
+    &["local1"],
+    &["local2"],
+    &["local3"],
+    &["local4"],
+    &["local5"],
+    &["local6"],
+    &["local7"],
+];
+
+/// Severity names, indexed by their numerical code.
+const SEVERITIES: &[&[&str]] = &[
+    &["emerg", "emergency", "panic"],
+    &["alert"],
+    &["crit", "critical"],
+    &["err", "error"],
+    &["warning", "warn"],
+    &["notice"],
+    &["info", "informational"],
+    &["debug"],
+];
+
+/// Parses a facility or severity, given either by name or by numerical code.
+fn parse_code(value: &Value, names: &[&[&str]]) -> Option<u8> {
+    let code = match value {
+        Value::Integer(code) => usize::try_from(*code).ok()?,
+        Value::Bytes(bytes) => {
+            let name = String::from_utf8_lossy(bytes).trim().to_ascii_lowercase();
+            match name.parse::<usize>() {
+                Ok(code) => code,
+                Err(_) => names.iter().position(|aliases| aliases.contains(&&*name))?,
+            }
+        }
+        _ => return None,
+    };
+    (code < names.len()).then_some(code as u8)
+}
+
+/// Formats a header field, which may only contain printable ASCII characters other than spaces,
+/// and is limited in length.
+fn header_field(value: Option<&str>, max_len: usize) -> String {
+    match value {
+        Some(value) if !value.is_empty() => value
+            .chars()
+            .map(|c| if c.is_ascii_graphic() { c } else { '_' })
+            .take(max_len)
+            .collect(),
+        _ => NILVALUE.to_string(),
+    }
+}
+
+/// Formats a timestamp as in RFC 3164, which doesn't include the year.
+fn bsd_timestamp(timestamp: DateTime<Utc>) -> String {
+    timestamp.format("%b %e %H:%M:%S").to_string()
+}
+
+/// Formats the name of a structured data element or parameter, which may not contain `=`, `]` or
+/// `"` on top of the restrictions of header fields.
+fn sd_name(name: &str) -> String {
+    name.chars()
+        .map(|c| match c {
+            '=' | ']' | '"' => '_',
+            c if c.is_ascii_graphic() => c,
+            _ => '_',
+        })
+        .take(32)
+        .collect()
+}
+
+/// Formats a structured data parameter, escaping the characters that would end its value.
+fn write_sd_param(output: &mut String, name: &str, value: &Value) {
+    write!(output, " {}=\"", sd_name(name)).expect("writing to a string never fails");
+    for c in value.to_string_lossy().chars() {
+        if matches!(c, '"' | '\\' | ']') {
+            output.push('\\');
+        }
+        output.push(c);
+    }
+    output.push('"');
+}
+
+fn encode_structured_data(value: Option<&Value>) -> String {
+    let elements = match value {
+        Some(Value::Object(elements)) => elements,
+        _ => return NILVALUE.to_string(),
+    };
+
+    let mut output = String::new();
+    for (id, params) in elements {
+        let params = match params {
+            Value::Object(params) => params,
+            _ => continue,
+        };
+        output.push('[');
+        output.push_str(&sd_name(id));
+        for (name, value) in params {
+            match value {
+                Value::Array(values) => values
+                    .iter()
+                    .filter(|value| !matches!(value, Value::Array(_) | Value::Object(_)))
+                    .for_each(|value| write_sd_param(&mut output, name, value)),
+                Value::Object(_) => {}
+                value => write_sd_param(&mut output, name, value),
+            }
+        }
+        output.push(']');
+    }
+
+    if output.is_empty() {
+        NILVALUE.to_string()
+    } else {
+        output
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bytes::BytesMut;
+    use chrono::{TimeZone, Timelike};
+    use vector_common::btreemap;
+    use vector_core::event::{LogEvent, Value};
+
+    use super::*;
+
+    fn event() -> LogEvent {
+        let mut log = LogEvent::from("an application event");
+        log.insert(
+            "timestamp",
+            Utc.with_ymd_and_hms(2023, 8, 3, 22, 14, 15)
+                .unwrap()
+                .with_nanosecond(3_000_000)
+                .unwrap(),
+        );
+        log.insert("host", "mymachine.example.com");
+        log.insert("facility", "local4");
+        log.insert("severity", "notice");
+        log.insert("appname", "evntslog");
+        log.insert("procid", 1234);
+        log.insert("msgid", "ID47");
+        log
+    }
+
+    fn serialize(options: SyslogSerializerOptions, log: LogEvent) -> String {
+        let mut serializer = SyslogSerializerConfig::new(options).build();
+        let mut bytes = BytesMut::new();
+        serializer.encode(Event::from(log), &mut bytes).unwrap();
+        String::from_utf8(bytes.to_vec()).unwrap()
+    }
+
+    #[test]
+    fn serialize_rfc5424() {
+        let mut log = event();
+        log.insert(
+            "sd",
+            Value::from(btreemap! {
+                "exampleSDID@32473" => Value::from(btreemap! {
+                    "iut" => Value::from(3),
+                    "eventSource" => Value::from("Appli\"cation]"),
+                }),
+                "examplePriority@32473" => Value::from(btreemap! {
+                    "class" => Value::from(vec![Value::from("high"), Value::from("urgent")]),
+                }),
+            }),
+        );
+        let options = SyslogSerializerOptions {
+            structured_data_field: OptionalTargetPath::event("sd"),
+            ..Default::default()
+        };
+
+        assert_eq!(
+            serialize(options, log),
+            "<165>1 2023-08-03T22:14:15.003000Z mymachine.example.com evntslog 1234 ID47 \
+             [examplePriority@32473 class=\"high\" class=\"urgent\"]\
+             [exampleSDID@32473 eventSource=\"Appli\\\"cation\\]\" iut=\"3\"] an application event"
+        );
+    }
+
+    #[test]
+    fn serialize_rfc3164() {
+        let options = SyslogSerializerOptions {
+            rfc: SyslogRfc::Rfc3164,
+            ..Default::default()
+        };
+
+        assert_eq!(
+            serialize(options.clone(), event()),
+            "<165>Aug  3 22:14:15 mymachine.example.com evntslog[1234]: an application event"
+        );
+
+        let mut log = event();
+        log.remove("appname");
+        assert_eq!(
+            serialize(options, log),
+            "<165>Aug  3 22:14:15 mymachine.example.com an application event"
+        );
+    }
+
+    #[test]
+    fn serialize_missing_fields() {
+        let mut log = LogEvent::from("hello");
+        log.insert(
+            "timestamp",
+            Utc.with_ymd_and_hms(2023, 8, 3, 22, 14, 15).unwrap(),
+        );
+
+        assert_eq!(
+            serialize(SyslogSerializerOptions::default(), log),
+            "<14>1 2023-08-03T22:14:15.000000Z - - - - - hello"
+        );
+    }
+
+    #[test]
+    fn serialize_numerical_and_invalid_codes() {
+        let mut log = event();
+        log.insert("facility", 16);
+        log.insert("severity", "3");
+        assert!(serialize(SyslogSerializerOptions::default(), log).starts_with("<131>1 "));
+
+        let mut log = event();
+        log.insert("facility", 24);
+        log.insert("severity", "loud");
+        assert!(serialize(SyslogSerializerOptions::default(), log).starts_with("<14>1 "));
+    }
+
+    #[test]
+    fn serialize_sanitizes_header_fields() {
+        let mut log = event();
+        log.insert("appname", "my app");
+        log.insert("msgid", "a".repeat(40));
+        let options = SyslogSerializerOptions {
+            proc_id_field: OptionalTargetPath::none(),
+            ..Default::default()
+        };
+
+        assert_eq!(
+            serialize(options, log),
+            format!(
+                "<165>1 2023-08-03T22:14:15.003000Z mymachine.example.com my_app - {} - an application event",
+                "a".repeat(32)
+            )
+        );
+    }
+}
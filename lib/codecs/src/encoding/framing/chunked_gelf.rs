@@ -0,0 +1,207 @@
+use bytes::{BufMut, Bytes, BytesMut};
+use derivative::Derivative;
+use snafu::Snafu;
+use vector_config::configurable_component;
+
+use super::{BoxedFramingError, FramingError};
+use crate::{
+    encoding::BuildError,
+    gelf::gelf_chunks::{HEADER_LENGTH, MAGIC, MAX_TOTAL_CHUNKS},
+};
+
+/// Errors that can occur while splitting GELF messages into chunks.
+#[derive(Debug, Snafu)]
+pub enum ChunkedGelfEncoderError {
+    #[snafu(display(
+        "GELF message of {} bytes needs more than {} chunks of {} bytes.",
+        length,
+        MAX_TOTAL_CHUNKS,
+        max_chunk_size
+    ))]
+    TooManyChunks {
+        length: usize,
+        max_chunk_size: usize,
+    },
+}
+
+impl FramingError for ChunkedGelfEncoderError {}
+
+impl From<ChunkedGelfEncoderError> for BoxedFramingError {
+    fn from(error: ChunkedGelfEncoderError) -> Self {
+        Box::new(error)
+    }
+}
+
+/// Config used to build a `ChunkedGelfEncoder`.
+#[configurable_component]
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ChunkedGelfEncoderConfig {
+    /// Options for the chunked GELF encoder.
+    #[serde(
+        default,
+        skip_serializing_if = "vector_core::serde::skip_serializing_if_default"
+    )]
+    pub chunked_gelf: ChunkedGelfEncoderOptions,
+}
+
+impl ChunkedGelfEncoderConfig {
+    /// Build the `ChunkedGelfEncoder` from this configuration.
+    pub fn build(&self) -> Result<ChunkedGelfEncoder, BuildError> {
+        let max_chunk_size = self.chunked_gelf.max_chunk_size;
+        if max_chunk_size <= HEADER_LENGTH {
+            return Err(format!(
+                "The maximum chunk size must be larger than the {} bytes of the chunk header, got {}.",
+                HEADER_LENGTH, max_chunk_size
+            )
+            .into());
+        }
+
+        Ok(ChunkedGelfEncoder::new(max_chunk_size))
+    }
+}
+
+/// Options for building a `ChunkedGelfEncoder`.
+#[configurable_component]
+#[derive(Clone, Debug, Derivative, PartialEq, Eq)]
+#[derivative(Default)]
+pub struct ChunkedGelfEncoderOptions {
+    /// The maximum size of a datagram, in bytes.
+    ///
+    /// Messages larger than this are split into chunks, each sent as a datagram of at most this
+    /// size, chunk header included. Messages that would need more than 128 chunks are discarded.
+    ///
+    /// This should be set below the MTU of the network path, minus the IP and UDP headers.
+    #[serde(default = "default_max_chunk_size")]
+    #[derivative(Default(value = "default_max_chunk_size()"))]
+    #[configurable(metadata(docs::type_unit = "bytes"))]
+    pub max_chunk_size: usize,
+}
+
+const fn default_max_chunk_size() -> usize {
+    8192
+}
+
+/// An encoder that splits messages into [chunked GELF][chunked_gelf] messages, to be sent one
+/// chunk per datagram.
+///
+/// Messages that fit in a single datagram are left as-is.
+///
+/// [chunked_gelf]: https://go2docs.graylog.org/current/getting_in_log_data/gelf.html#GELFviaUDP
+#[derive(Debug, Clone)]
+pub struct ChunkedGelfEncoder {
+    max_chunk_size: usize,
+}
+
+impl ChunkedGelfEncoder {
+    /// Creates a new `ChunkedGelfEncoder`.
+    ///
+    /// The maximum chunk size must be larger than the chunk header.
+    pub const fn new(max_chunk_size: usize) -> Self {
+        Self { max_chunk_size }
+    }
+
+    /// Splits a message into the datagrams to send.
+    pub fn chunk(&self, message: Bytes) -> Result<Vec<Bytes>, BoxedFramingError> {
+        if message.len() <= self.max_chunk_size {
+            return Ok(vec![message]);
+        }
+
+        let chunk_length = self.max_chunk_size - HEADER_LENGTH;
+        let sequence_count = (message.len() + chunk_length - 1) / chunk_length;
+        if sequence_count > MAX_TOTAL_CHUNKS as usize {
+            return Err(ChunkedGelfEncoderError::TooManyChunks {
+                length: message.len(),
+                max_chunk_size: self.max_chunk_size,
+            }
+            .into());
+        }
+
+        let message_id = rand::random::<u64>();
+        let chunks = message
+            .chunks(chunk_length)
+            .enumerate()
+            .map(|(sequence_number, payload)| {
+                let mut chunk = BytesMut::with_capacity(HEADER_LENGTH + payload.len());
+                chunk.put_slice(&MAGIC);
+                chunk.put_u64(message_id);
+                chunk.put_u8(sequence_number as u8);
+                chunk.put_u8(sequence_count as u8);
+                chunk.put_slice(payload);
+                chunk.freeze()
+            })
+            .collect();
+
+        Ok(chunks)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use tokio_util::codec::Decoder;
+
+    use super::*;
+    use crate::decoding::ChunkedGelfDecoder;
+
+    #[test]
+    fn chunk_small_message() {
+        let encoder = ChunkedGelfEncoder::new(16);
+
+        let datagrams = encoder.chunk(Bytes::from("foobar")).unwrap();
+        assert_eq!(datagrams, vec![Bytes::from("foobar")]);
+    }
+
+    #[test]
+    fn chunk_large_message() {
+        let encoder = ChunkedGelfEncoder::new(HEADER_LENGTH + 4);
+
+        let datagrams = encoder.chunk(Bytes::from("foobarbaz")).unwrap();
+        assert_eq!(datagrams.len(), 3);
+
+        let message_id = &datagrams[0][2..10];
+        for (sequence_number, datagram) in datagrams.iter().enumerate() {
+            assert!(datagram.len() <= HEADER_LENGTH + 4);
+            assert_eq!(datagram[..2], MAGIC);
+            assert_eq!(&datagram[2..10], message_id);
+            assert_eq!(datagram[10], sequence_number as u8);
+            assert_eq!(datagram[11], 3);
+        }
+        assert_eq!(datagrams[0][HEADER_LENGTH..], *b"foob");
+        assert_eq!(datagrams[1][HEADER_LENGTH..], *b"arba");
+        assert_eq!(datagrams[2][HEADER_LENGTH..], *b"z");
+    }
+
+    #[test]
+    fn chunk_error_too_many_chunks() {
+        let encoder = ChunkedGelfEncoder::new(HEADER_LENGTH + 1);
+
+        assert!(encoder.chunk(Bytes::from(vec![b'a'; 128])).is_ok());
+        let error = encoder.chunk(Bytes::from(vec![b'a'; 129])).unwrap_err();
+        assert!(error.to_string().contains("more than 128 chunks"));
+    }
+
+    #[test]
+    fn build_error_max_chunk_size() {
+        let config = ChunkedGelfEncoderConfig {
+            chunked_gelf: ChunkedGelfEncoderOptions {
+                max_chunk_size: HEADER_LENGTH,
+            },
+        };
+
+        assert!(config.build().is_err());
+    }
+
+    #[test]
+    fn roundtrip() {
+        let encoder = ChunkedGelfEncoder::new(HEADER_LENGTH + 10);
+        let mut decoder = ChunkedGelfDecoder::default();
+
+        let message = Bytes::from(r#"{"short_message":"foo","_bar":"baz"}"#);
+        let mut decoded = Vec::new();
+        for datagram in encoder.chunk(message.clone()).unwrap().into_iter().rev() {
+            let mut src = BytesMut::from(&datagram[..]);
+            decoded.extend(decoder.decode_eof(&mut src).unwrap());
+        }
+
+        assert_eq!(decoded, vec![message]);
+    }
+}
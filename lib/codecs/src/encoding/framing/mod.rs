@@ -5,17 +5,21 @@
 
 mod bytes;
 mod character_delimited;
+mod chunked_gelf;
 mod length_delimited;
 mod newline_delimited;
+mod octet_counting;
 
 use std::fmt::Debug;
 
 pub use character_delimited::{
     CharacterDelimitedEncoder, CharacterDelimitedEncoderConfig, CharacterDelimitedEncoderOptions,
 };
+pub use chunked_gelf::{ChunkedGelfEncoder, ChunkedGelfEncoderConfig, ChunkedGelfEncoderOptions};
 use dyn_clone::DynClone;
 pub use length_delimited::{LengthDelimitedEncoder, LengthDelimitedEncoderConfig};
 pub use newline_delimited::{NewlineDelimitedEncoder, NewlineDelimitedEncoderConfig};
+pub use octet_counting::{OctetCountingEncoder, OctetCountingEncoderConfig};
 use tokio_util::codec::LinesCodecError;
 
 pub use self::bytes::{BytesEncoder, BytesEncoderConfig};
@@ -0,0 +1,87 @@
+use bytes::{BufMut, BytesMut};
+use serde::{Deserialize, Serialize};
+use tokio_util::codec::Encoder;
+
+use super::BoxedFramingError;
+
+/// Config used to build a `OctetCountingEncoder`.
+#[derive(Debug, Clone, Default, Deserialize, Serialize, PartialEq, Eq)]
+pub struct OctetCountingEncoderConfig;
+
+impl OctetCountingEncoderConfig {
+    /// Creates a new `OctetCountingEncoderConfig`.
+    pub const fn new() -> Self {
+        Self
+    }
+
+    /// Build the `OctetCountingEncoder` from this configuration.
+    pub const fn build(&self) -> OctetCountingEncoder {
+        OctetCountingEncoder::new()
+    }
+}
+
+/// An encoder for handling bytes that are prefixed by their length in ASCII decimal digits,
+/// followed by a space, as described by [RFC 6587][rfc6587].
+///
+/// [rfc6587]: https://tools.ietf.org/html/rfc6587#section-3.4.1
+#[derive(Debug, Clone, Default)]
+pub struct OctetCountingEncoder;
+
+impl OctetCountingEncoder {
+    /// Creates a new `OctetCountingEncoder`.
+    pub const fn new() -> Self {
+        Self
+    }
+}
+
+impl Encoder<()> for OctetCountingEncoder {
+    type Error = BoxedFramingError;
+
+    fn encode(&mut self, _: (), buffer: &mut BytesMut) -> Result<(), BoxedFramingError> {
+        let frame = buffer.split();
+        let prefix = format!("{} ", frame.len());
+        buffer.reserve(prefix.len() + frame.len());
+        buffer.put_slice(prefix.as_bytes());
+        buffer.put_slice(&frame);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use tokio_util::codec::Decoder;
+
+    use super::*;
+    use crate::decoding::OctetCountingDecoder;
+
+    #[test]
+    fn encode() {
+        let mut encoder = OctetCountingEncoder::new();
+
+        let mut buffer = BytesMut::from("<13>1 - - - - - - multi\nline");
+        encoder.encode((), &mut buffer).unwrap();
+        assert_eq!(buffer, "28 <13>1 - - - - - - multi\nline");
+
+        let mut buffer = BytesMut::new();
+        encoder.encode((), &mut buffer).unwrap();
+        assert_eq!(buffer, "0 ");
+    }
+
+    #[test]
+    fn roundtrip() {
+        let mut encoder = OctetCountingEncoder::new();
+        let mut decoder = OctetCountingDecoder::new();
+
+        let mut stream = BytesMut::new();
+        for message in ["foo", "bar\nbaz", "qux"] {
+            let mut frame = BytesMut::from(message);
+            encoder.encode((), &mut frame).unwrap();
+            stream.extend_from_slice(&frame);
+        }
+
+        assert_eq!(decoder.decode(&mut stream).unwrap().unwrap(), "foo");
+        assert_eq!(decoder.decode(&mut stream).unwrap().unwrap(), "bar\nbaz");
+        assert_eq!(decoder.decode(&mut stream).unwrap().unwrap(), "qux");
+        assert!(stream.is_empty());
+    }
+}
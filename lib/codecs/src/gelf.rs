@@ -41,6 +41,20 @@ pub mod gelf_fields {
     // < Every field with an underscore (_) prefix will be treated as an additional field. >
 }
 
+/// Chunked GELF definitions. Definitions from
+/// <https://go2docs.graylog.org/current/getting_in_log_data/gelf.html#GELFviaUDP>.
+pub mod gelf_chunks {
+    /// The magic bytes that start every chunk of a chunked GELF message.
+    pub const MAGIC: [u8; 2] = [0x1e, 0x0f];
+
+    /// The maximum number of chunks a GELF message can be split into.
+    pub const MAX_TOTAL_CHUNKS: u8 = 128;
+
+    /// The length of the header of a chunk: the magic bytes, an 8 byte message ID, a 1 byte
+    /// sequence number and a 1 byte sequence count.
+    pub const HEADER_LENGTH: usize = 12;
+}
+
 /// GELF owned target paths.
 pub(crate) struct GelfTargetPaths {
     pub version: OwnedTargetPath,
@@ -4,28 +4,36 @@
 #![deny(missing_docs)]
 #![deny(warnings)]
 
+pub mod cef;
 pub mod decoding;
 pub mod encoding;
 pub mod gelf;
 
+pub use cef::{cef_fields, leef_fields};
 pub use decoding::{
-    BytesDecoder, BytesDecoderConfig, BytesDeserializer, BytesDeserializerConfig,
-    CharacterDelimitedDecoder, CharacterDelimitedDecoderConfig, GelfDeserializer,
-    GelfDeserializerConfig, JsonDeserializer, JsonDeserializerConfig, LengthDelimitedDecoder,
-    LengthDelimitedDecoderConfig, NativeDeserializer, NativeDeserializerConfig,
-    NativeJsonDeserializer, NativeJsonDeserializerConfig, NewlineDelimitedDecoder,
-    NewlineDelimitedDecoderConfig, OctetCountingDecoder, OctetCountingDecoderConfig,
-    StreamDecodingError,
+    AvroDeserializer, AvroDeserializerConfig, BytesDecoder, BytesDecoderConfig, BytesDeserializer,
+    BytesDeserializerConfig, CefDeserializer, CefDeserializerConfig, CharacterDelimitedDecoder,
+    CharacterDelimitedDecoderConfig, ChunkedGelfDecoder, ChunkedGelfDecoderConfig, CsvDeserializer,
+    CsvDeserializerConfig, GelfDeserializer, GelfDeserializerConfig, JsonDeserializer,
+    JsonDeserializerConfig, LeefDeserializer, LeefDeserializerConfig, LengthDelimitedDecoder,
+    LengthDelimitedDecoderConfig, LogfmtDeserializer, LogfmtDeserializerConfig, NativeDeserializer,
+    NativeDeserializerConfig, NativeJsonDeserializer, NativeJsonDeserializerConfig,
+    NewlineDelimitedDecoder, NewlineDelimitedDecoderConfig, OctetCountingDecoder,
+    OctetCountingDecoderConfig, StreamDecodingError,
 };
 #[cfg(feature = "syslog")]
 pub use decoding::{SyslogDeserializer, SyslogDeserializerConfig};
 pub use encoding::{
-    BytesEncoder, BytesEncoderConfig, CharacterDelimitedEncoder, CharacterDelimitedEncoderConfig,
-    CsvSerializer, CsvSerializerConfig, GelfSerializer, GelfSerializerConfig, JsonSerializer,
-    JsonSerializerConfig, LengthDelimitedEncoder, LengthDelimitedEncoderConfig, LogfmtSerializer,
+    BytesEncoder, BytesEncoderConfig, CefSerializer, CefSerializerConfig,
+    CharacterDelimitedEncoder, CharacterDelimitedEncoderConfig, ChunkedGelfEncoder,
+    ChunkedGelfEncoderConfig, CsvSerializer, CsvSerializerConfig, GelfSerializer,
+    GelfSerializerConfig, JsonSerializer, JsonSerializerConfig, LeefSerializer,
+    LeefSerializerConfig, LengthDelimitedEncoder, LengthDelimitedEncoderConfig, LogfmtSerializer,
     LogfmtSerializerConfig, NativeJsonSerializer, NativeJsonSerializerConfig, NativeSerializer,
     NativeSerializerConfig, NewlineDelimitedEncoder, NewlineDelimitedEncoderConfig,
-    RawMessageSerializer, RawMessageSerializerConfig, TextSerializer, TextSerializerConfig,
+    OctetCountingEncoder, OctetCountingEncoderConfig, ParquetSerializer, ParquetSerializerConfig,
+    ProtobufSerializer, ProtobufSerializerConfig, RawMessageSerializer, RawMessageSerializerConfig,
+    SyslogSerializer, SyslogSerializerConfig, TextSerializer, TextSerializerConfig,
 };
 pub use gelf::{gelf_fields, VALID_FIELD_REGEX};
 use vector_config::configurable_component;
@@ -5,14 +5,26 @@ use ordered_float::NotNan;
 use std::collections::BTreeMap;
 use vector_core::{
     config::{log_schema, LegacyKey, LogNamespace},
-    event::{Event, LogEvent},
+    event::{
+        metric::{Bucket, Quantile},
+        Event, LogEvent, Metric, MetricKind, MetricTags, MetricValue, TraceEvent,
+    },
 };
 use vrl::value::Value;
 
 use super::proto::{
-    common::v1::{any_value::Value as PBValue, KeyValue},
+    common::v1::{any_value::Value as PBValue, InstrumentationScope, KeyValue},
     logs::v1::{LogRecord, ResourceLogs, SeverityNumber},
+    metrics::v1::{
+        exponential_histogram_data_point::Buckets, metric::Data, number_data_point,
+        AggregationTemporality, DataPointFlags, ExponentialHistogramDataPoint, HistogramDataPoint,
+        Metric as PBMetric, NumberDataPoint, ResourceMetrics, SummaryDataPoint,
+    },
     resource::v1::Resource,
+    trace::v1::{
+        span::{Event as SpanEvent, Link},
+        ResourceSpans, Span, Status as SpanStatus,
+    },
 };
 
 const SOURCE_NAME: &str = "opentelemetry";
@@ -27,6 +39,24 @@ pub const OBSERVED_TIMESTAMP_KEY: &str = "observed_timestamp";
 pub const DROPPED_ATTRIBUTES_COUNT_KEY: &str = "dropped_attributes_count";
 pub const FLAGS_KEY: &str = "flags";
 
+pub const PARENT_SPAN_ID_KEY: &str = "parent_span_id";
+pub const TRACE_STATE_KEY: &str = "trace_state";
+pub const NAME_KEY: &str = "name";
+pub const KIND_KEY: &str = "kind";
+pub const START_TIME_KEY: &str = "start_time_unix_nano";
+pub const END_TIME_KEY: &str = "end_time_unix_nano";
+pub const EVENTS_KEY: &str = "events";
+pub const DROPPED_EVENTS_COUNT_KEY: &str = "dropped_events_count";
+pub const LINKS_KEY: &str = "links";
+pub const DROPPED_LINKS_COUNT_KEY: &str = "dropped_links_count";
+pub const STATUS_KEY: &str = "status";
+pub const INGEST_TIMESTAMP_KEY: &str = "ingest_timestamp";
+
+/// Prefix applied to resource attributes when they are converted into metric tags.
+pub const RESOURCE_TAG_PREFIX: &str = "resource.";
+/// Prefix applied to instrumentation scope fields when they are converted into metric tags.
+pub const SCOPE_TAG_PREFIX: &str = "scope.";
+
 impl ResourceLogs {
     pub fn into_event_iter(self, log_namespace: LogNamespace) -> impl Iterator<Item = Event> {
         let resource = self.resource;
@@ -45,6 +75,47 @@ impl ResourceLogs {
     }
 }
 
+impl ResourceMetrics {
+    pub fn into_event_iter(self) -> impl Iterator<Item = Event> {
+        let resource_tags = self
+            .resource
+            .map(|resource| kv_list_into_tags(RESOURCE_TAG_PREFIX, resource.attributes))
+            .unwrap_or_default();
+
+        self.scope_metrics
+            .into_iter()
+            .flat_map(move |scope_metrics| {
+                let mut tags = resource_tags.clone();
+                if let Some(scope) = scope_metrics.scope {
+                    tags.extend(scope_into_tags(scope));
+                }
+
+                scope_metrics
+                    .metrics
+                    .into_iter()
+                    .flat_map(move |metric| metric_into_events(metric, &tags))
+            })
+    }
+}
+
+impl ResourceSpans {
+    pub fn into_event_iter(self) -> impl Iterator<Item = Event> {
+        let resource = self.resource;
+        let now = Utc::now();
+
+        self.scope_spans
+            .into_iter()
+            .flat_map(|scope_span| scope_span.spans)
+            .map(move |span| {
+                ResourceSpan {
+                    resource: resource.clone(),
+                    span,
+                }
+                .into_event(now)
+            })
+    }
+}
+
 impl From<PBValue> for Value {
     fn from(av: PBValue) -> Self {
         match av {
@@ -69,6 +140,11 @@ struct ResourceLog {
     log_record: LogRecord,
 }
 
+struct ResourceSpan {
+    resource: Option<Resource>,
+    span: Span,
+}
+
 fn kv_list_into_value(arr: Vec<KeyValue>) -> Value {
     Value::Object(
         arr.into_iter()
@@ -221,3 +297,358 @@ impl ResourceLog {
         log.into()
     }
 }
+
+fn kv_list_into_tags(prefix: &str, arr: Vec<KeyValue>) -> Vec<(String, String)> {
+    arr.into_iter()
+        .map(|kv| {
+            let value = kv
+                .value
+                .and_then(|av| av.value)
+                .map(|v| match Value::from(v) {
+                    Value::Bytes(bytes) => String::from_utf8_lossy(&bytes).into_owned(),
+                    value => value.to_string(),
+                })
+                .unwrap_or_default();
+            (format!("{prefix}{}", kv.key), value)
+        })
+        .collect()
+}
+
+fn scope_into_tags(scope: InstrumentationScope) -> Vec<(String, String)> {
+    let mut tags = kv_list_into_tags(SCOPE_TAG_PREFIX, scope.attributes);
+    if !scope.name.is_empty() {
+        tags.push((format!("{SCOPE_TAG_PREFIX}name"), scope.name));
+    }
+    if !scope.version.is_empty() {
+        tags.push((format!("{SCOPE_TAG_PREFIX}version"), scope.version));
+    }
+    tags
+}
+
+fn nanos_into_timestamp(nanos: u64) -> Option<DateTime<Utc>> {
+    (nanos > 0).then(|| Utc.timestamp_nanos(nanos as i64))
+}
+
+const fn has_no_recorded_value(flags: u32) -> bool {
+    flags & DataPointFlags::NoRecordedValueMask as u32 != 0
+}
+
+// https://github.com/open-telemetry/opentelemetry-specification/blob/v1.15.0/specification/metrics/data-model.md
+fn metric_into_events(metric: PBMetric, tags: &[(String, String)]) -> Vec<Event> {
+    let name = metric.name;
+    let build = |attributes: Vec<KeyValue>, time_unix_nano: u64, kind, value| {
+        let tags = tags
+            .iter()
+            .cloned()
+            .chain(kv_list_into_tags("", attributes))
+            .collect::<MetricTags>();
+        Event::from(
+            Metric::new(name.clone(), kind, value)
+                .with_tags(tags.as_option())
+                .with_timestamp(nanos_into_timestamp(time_unix_nano)),
+        )
+    };
+
+    match metric.data {
+        Some(Data::Gauge(gauge)) => gauge
+            .data_points
+            .into_iter()
+            .filter(|point| !has_no_recorded_value(point.flags))
+            .map(|point| {
+                let value = MetricValue::Gauge {
+                    value: number_value(&point),
+                };
+                build(
+                    point.attributes,
+                    point.time_unix_nano,
+                    MetricKind::Absolute,
+                    value,
+                )
+            })
+            .collect(),
+        Some(Data::Sum(sum)) => {
+            let kind = temporality_into_kind(sum.aggregation_temporality);
+            let is_monotonic = sum.is_monotonic;
+            sum.data_points
+                .into_iter()
+                .filter(|point| !has_no_recorded_value(point.flags))
+                .map(|point| {
+                    let value = number_value(&point);
+                    // Non-monotonic sums can go down, which Vector's counters cannot represent.
+                    let value = if is_monotonic {
+                        MetricValue::Counter { value }
+                    } else {
+                        MetricValue::Gauge { value }
+                    };
+                    build(point.attributes, point.time_unix_nano, kind, value)
+                })
+                .collect()
+        }
+        Some(Data::Histogram(histogram)) => {
+            let kind = temporality_into_kind(histogram.aggregation_temporality);
+            histogram
+                .data_points
+                .into_iter()
+                .filter(|point| !has_no_recorded_value(point.flags))
+                .map(|point| {
+                    let value = histogram_value(&point);
+                    build(point.attributes, point.time_unix_nano, kind, value)
+                })
+                .collect()
+        }
+        Some(Data::ExponentialHistogram(histogram)) => {
+            let kind = temporality_into_kind(histogram.aggregation_temporality);
+            histogram
+                .data_points
+                .into_iter()
+                .filter(|point| !has_no_recorded_value(point.flags))
+                .map(|point| {
+                    let value = exponential_histogram_value(&point);
+                    build(point.attributes, point.time_unix_nano, kind, value)
+                })
+                .collect()
+        }
+        Some(Data::Summary(summary)) => summary
+            .data_points
+            .into_iter()
+            .filter(|point| !has_no_recorded_value(point.flags))
+            .map(|point| {
+                let value = summary_value(&point);
+                build(
+                    point.attributes,
+                    point.time_unix_nano,
+                    MetricKind::Absolute,
+                    value,
+                )
+            })
+            .collect(),
+        None => Vec::new(),
+    }
+}
+
+fn temporality_into_kind(temporality: i32) -> MetricKind {
+    if temporality == AggregationTemporality::Delta as i32 {
+        MetricKind::Incremental
+    } else {
+        MetricKind::Absolute
+    }
+}
+
+fn number_value(point: &NumberDataPoint) -> f64 {
+    match point.value {
+        Some(number_data_point::Value::AsDouble(value)) => value,
+        Some(number_data_point::Value::AsInt(value)) => value as f64,
+        None => 0.0,
+    }
+}
+
+fn histogram_value(point: &HistogramDataPoint) -> MetricValue {
+    // OTLP bucket counts are not cumulative, same as ours. The last bucket holds everything
+    // above the highest explicit bound, which is implied by the total count and thus dropped.
+    let buckets = point
+        .explicit_bounds
+        .iter()
+        .zip(point.bucket_counts.iter())
+        .map(|(upper_limit, count)| Bucket {
+            upper_limit: *upper_limit,
+            count: *count,
+        })
+        .collect();
+
+    MetricValue::AggregatedHistogram {
+        buckets,
+        count: point.count,
+        sum: point.sum.unwrap_or_default(),
+    }
+}
+
+fn exponential_histogram_value(point: &ExponentialHistogramDataPoint) -> MetricValue {
+    // Bucket `index` covers `(base^index, base^(index + 1)]`, mirrored for negative values.
+    let base = 2f64.powf(2f64.powi(-point.scale));
+    let mut buckets = Vec::new();
+
+    if let Some(Buckets {
+        offset,
+        bucket_counts,
+    }) = &point.negative
+    {
+        buckets.extend(
+            bucket_counts
+                .iter()
+                .enumerate()
+                .rev()
+                .map(|(i, count)| Bucket {
+                    upper_limit: -base.powi(offset + i as i32),
+                    count: *count,
+                }),
+        );
+    }
+
+    buckets.push(Bucket {
+        upper_limit: 0.0,
+        count: point.zero_count,
+    });
+
+    if let Some(Buckets {
+        offset,
+        bucket_counts,
+    }) = &point.positive
+    {
+        buckets.extend(bucket_counts.iter().enumerate().map(|(i, count)| Bucket {
+            upper_limit: base.powi(offset + i as i32 + 1),
+            count: *count,
+        }));
+    }
+
+    MetricValue::AggregatedHistogram {
+        buckets,
+        count: point.count,
+        sum: point.sum.unwrap_or_default(),
+    }
+}
+
+fn summary_value(point: &SummaryDataPoint) -> MetricValue {
+    MetricValue::AggregatedSummary {
+        quantiles: point
+            .quantile_values
+            .iter()
+            .map(|q| Quantile {
+                quantile: q.quantile,
+                value: q.value,
+            })
+            .collect(),
+        count: point.count,
+        sum: point.sum,
+    }
+}
+
+fn nanos_into_value(nanos: u64) -> Value {
+    nanos_into_timestamp(nanos).map_or(Value::Null, Value::Timestamp)
+}
+
+fn span_event_into_value(event: SpanEvent) -> Value {
+    let mut obj = BTreeMap::new();
+    obj.insert("name".to_string(), event.name.into());
+    obj.insert(
+        "time_unix_nano".to_string(),
+        nanos_into_value(event.time_unix_nano),
+    );
+    obj.insert(
+        ATTRIBUTES_KEY.to_string(),
+        kv_list_into_value(event.attributes),
+    );
+    obj.insert(
+        DROPPED_ATTRIBUTES_COUNT_KEY.to_string(),
+        Value::Integer(event.dropped_attributes_count as i64),
+    );
+    Value::Object(obj)
+}
+
+fn link_into_value(link: Link) -> Value {
+    let mut obj = BTreeMap::new();
+    obj.insert(
+        TRACE_ID_KEY.to_string(),
+        Value::from(hex::encode(link.trace_id)),
+    );
+    obj.insert(
+        SPAN_ID_KEY.to_string(),
+        Value::from(hex::encode(link.span_id)),
+    );
+    obj.insert(TRACE_STATE_KEY.to_string(), link.trace_state.into());
+    obj.insert(
+        ATTRIBUTES_KEY.to_string(),
+        kv_list_into_value(link.attributes),
+    );
+    obj.insert(
+        DROPPED_ATTRIBUTES_COUNT_KEY.to_string(),
+        Value::Integer(link.dropped_attributes_count as i64),
+    );
+    Value::Object(obj)
+}
+
+fn status_into_value(status: SpanStatus) -> Value {
+    let mut obj = BTreeMap::new();
+    obj.insert("message".to_string(), status.message.into());
+    obj.insert("code".to_string(), Value::Integer(status.code as i64));
+    Value::Object(obj)
+}
+
+// https://github.com/open-telemetry/opentelemetry-specification/blob/v1.15.0/specification/trace/api.md
+impl ResourceSpan {
+    fn into_event(self, now: DateTime<Utc>) -> Event {
+        let span = self.span;
+        let mut trace = BTreeMap::new();
+
+        trace.insert(
+            TRACE_ID_KEY.to_string(),
+            Value::from(hex::encode(span.trace_id)),
+        );
+        trace.insert(
+            SPAN_ID_KEY.to_string(),
+            Value::from(hex::encode(span.span_id)),
+        );
+        trace.insert(TRACE_STATE_KEY.to_string(), span.trace_state.into());
+        trace.insert(
+            PARENT_SPAN_ID_KEY.to_string(),
+            Value::from(hex::encode(span.parent_span_id)),
+        );
+        trace.insert(NAME_KEY.to_string(), span.name.into());
+        trace.insert(KIND_KEY.to_string(), Value::Integer(span.kind as i64));
+        trace.insert(
+            START_TIME_KEY.to_string(),
+            nanos_into_value(span.start_time_unix_nano),
+        );
+        trace.insert(
+            END_TIME_KEY.to_string(),
+            nanos_into_value(span.end_time_unix_nano),
+        );
+        if !span.attributes.is_empty() {
+            trace.insert(
+                ATTRIBUTES_KEY.to_string(),
+                kv_list_into_value(span.attributes),
+            );
+        }
+        trace.insert(
+            DROPPED_ATTRIBUTES_COUNT_KEY.to_string(),
+            Value::Integer(span.dropped_attributes_count as i64),
+        );
+        if !span.events.is_empty() {
+            trace.insert(
+                EVENTS_KEY.to_string(),
+                Value::Array(span.events.into_iter().map(span_event_into_value).collect()),
+            );
+        }
+        trace.insert(
+            DROPPED_EVENTS_COUNT_KEY.to_string(),
+            Value::Integer(span.dropped_events_count as i64),
+        );
+        if !span.links.is_empty() {
+            trace.insert(
+                LINKS_KEY.to_string(),
+                Value::Array(span.links.into_iter().map(link_into_value).collect()),
+            );
+        }
+        trace.insert(
+            DROPPED_LINKS_COUNT_KEY.to_string(),
+            Value::Integer(span.dropped_links_count as i64),
+        );
+        if let Some(status) = span.status {
+            trace.insert(STATUS_KEY.to_string(), status_into_value(status));
+        }
+        if let Some(resource) = self.resource {
+            if !resource.attributes.is_empty() {
+                trace.insert(
+                    RESOURCE_KEY.to_string(),
+                    kv_list_into_value(resource.attributes),
+                );
+            }
+        }
+        trace.insert(INGEST_TIMESTAMP_KEY.to_string(), Value::Timestamp(now));
+
+        let mut trace = TraceEvent::from(trace);
+        trace.maybe_insert(log_schema().source_type_key_target_path(), || {
+            Bytes::from_static(SOURCE_NAME.as_bytes()).into()
+        });
+        trace.into()
+    }
+}
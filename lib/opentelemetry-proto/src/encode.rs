@@ -0,0 +1,895 @@
+//! Conversion of Vector events into OTLP export requests.
+//!
+//! This is the inverse of [`crate::convert`]: fields and tags produced by the `opentelemetry`
+//! source are mapped back onto their OTLP counterparts, so that events can round-trip through
+//! Vector without losing their resource, scope, or identifiers.
+
+use std::collections::{hash_map::Entry, BTreeMap, HashMap};
+
+use chrono::{DateTime, Utc};
+use lookup::{event_path, metadata_path};
+use prost::Message;
+use vector_core::{
+    config::{log_schema, LogNamespace},
+    event::{
+        metric::{Bucket, MetricSketch, Quantile, Sample},
+        LogEvent, Metric, MetricKind, MetricValue, TraceEvent,
+    },
+};
+use vrl::value::Value;
+
+use super::{
+    convert::{
+        ATTRIBUTES_KEY, DROPPED_ATTRIBUTES_COUNT_KEY, DROPPED_EVENTS_COUNT_KEY,
+        DROPPED_LINKS_COUNT_KEY, END_TIME_KEY, EVENTS_KEY, FLAGS_KEY, KIND_KEY, LINKS_KEY,
+        NAME_KEY, OBSERVED_TIMESTAMP_KEY, PARENT_SPAN_ID_KEY, RESOURCE_KEY, RESOURCE_TAG_PREFIX,
+        SCOPE_TAG_PREFIX, SEVERITY_NUMBER_KEY, SEVERITY_TEXT_KEY, SPAN_ID_KEY, START_TIME_KEY,
+        STATUS_KEY, TRACE_ID_KEY, TRACE_STATE_KEY,
+    },
+    proto::{
+        collector::{
+            logs::v1::ExportLogsServiceRequest, metrics::v1::ExportMetricsServiceRequest,
+            trace::v1::ExportTraceServiceRequest,
+        },
+        common::v1::{
+            any_value::Value as PBValue, AnyValue, ArrayValue, InstrumentationScope, KeyValue,
+            KeyValueList,
+        },
+        logs::v1::{LogRecord, ResourceLogs, ScopeLogs},
+        metrics::v1::{
+            metric::Data, number_data_point, summary_data_point::ValueAtQuantile,
+            AggregationTemporality, Gauge, Histogram, HistogramDataPoint, Metric as PBMetric,
+            NumberDataPoint, ResourceMetrics, ScopeMetrics, Sum, Summary, SummaryDataPoint,
+        },
+        resource::v1::Resource,
+        trace::v1::{
+            span::{Event as SpanEvent, Link},
+            ResourceSpans, ScopeSpans, Span, Status as SpanStatus,
+        },
+    },
+};
+
+const SOURCE_NAME: &str = "opentelemetry";
+
+/// Quantiles reported when a sketch is converted into an OTLP summary.
+const SKETCH_QUANTILES: [f64; 5] = [0.5, 0.75, 0.9, 0.95, 0.99];
+
+/// Builds a logs export request out of the given log events.
+///
+/// Log records sharing the same resource are grouped under a single `ResourceLogs`.
+pub fn logs_into_request(logs: impl IntoIterator<Item = LogEvent>) -> ExportLogsServiceRequest {
+    let mut resource_logs: Vec<ResourceLogs> = Vec::new();
+    let mut groups = HashMap::new();
+
+    for log in logs {
+        let (resource, record) = log_into_record(log);
+        let mut key = Vec::new();
+        put_group_key(&mut key, &resource);
+        match groups.entry(key) {
+            Entry::Occupied(entry) => {
+                resource_logs[*entry.get()].scope_logs[0]
+                    .log_records
+                    .push(record);
+            }
+            Entry::Vacant(entry) => {
+                entry.insert(resource_logs.len());
+                resource_logs.push(ResourceLogs {
+                    resource,
+                    scope_logs: vec![ScopeLogs {
+                        scope: None,
+                        log_records: vec![record],
+                        schema_url: String::new(),
+                    }],
+                    schema_url: String::new(),
+                });
+            }
+        }
+    }
+
+    ExportLogsServiceRequest { resource_logs }
+}
+
+/// Builds a metrics export request out of the given metrics.
+///
+/// Tags prefixed with `resource.` and `scope.` are mapped back onto the resource and
+/// instrumentation scope, respectively, and metrics are grouped accordingly.
+pub fn metrics_into_request(
+    metrics: impl IntoIterator<Item = Metric>,
+) -> ExportMetricsServiceRequest {
+    let mut resource_metrics: Vec<ResourceMetrics> = Vec::new();
+    let mut groups = HashMap::new();
+    let now = Utc::now();
+
+    for metric in metrics {
+        let (resource, scope, metric) = metric_into_proto(metric, now);
+        let mut key = Vec::new();
+        put_group_key(&mut key, &resource);
+        put_group_key(&mut key, &scope);
+        match groups.entry(key) {
+            Entry::Occupied(entry) => {
+                resource_metrics[*entry.get()].scope_metrics[0]
+                    .metrics
+                    .push(metric);
+            }
+            Entry::Vacant(entry) => {
+                entry.insert(resource_metrics.len());
+                resource_metrics.push(ResourceMetrics {
+                    resource,
+                    scope_metrics: vec![ScopeMetrics {
+                        scope,
+                        metrics: vec![metric],
+                        schema_url: String::new(),
+                    }],
+                    schema_url: String::new(),
+                });
+            }
+        }
+    }
+
+    ExportMetricsServiceRequest { resource_metrics }
+}
+
+/// Builds a trace export request out of the given trace events.
+///
+/// Spans sharing the same resource are grouped under a single `ResourceSpans`.
+pub fn traces_into_request(
+    traces: impl IntoIterator<Item = TraceEvent>,
+) -> ExportTraceServiceRequest {
+    let mut resource_spans: Vec<ResourceSpans> = Vec::new();
+    let mut groups = HashMap::new();
+
+    for trace in traces {
+        let (resource, span) = trace_into_span(trace);
+        let mut key = Vec::new();
+        put_group_key(&mut key, &resource);
+        match groups.entry(key) {
+            Entry::Occupied(entry) => resource_spans[*entry.get()].scope_spans[0].spans.push(span),
+            Entry::Vacant(entry) => {
+                entry.insert(resource_spans.len());
+                resource_spans.push(ResourceSpans {
+                    resource,
+                    scope_spans: vec![ScopeSpans {
+                        scope: None,
+                        spans: vec![span],
+                        schema_url: String::new(),
+                    }],
+                    schema_url: String::new(),
+                });
+            }
+        }
+    }
+
+    ExportTraceServiceRequest { resource_spans }
+}
+
+/// Appends an optional message to the key of the group it belongs to.
+///
+/// Messages can't be hashed, as they may hold floats, so groups are keyed by their encoding
+/// instead. Messages are length delimited, so that keys made of several messages are unambiguous.
+fn put_group_key<M: Message>(key: &mut Vec<u8>, message: &Option<M>) {
+    match message {
+        Some(message) => {
+            key.push(1);
+            key.extend(message.encode_length_delimited_to_vec());
+        }
+        None => key.push(0),
+    }
+}
+
+fn value_into_any_value(value: Value) -> AnyValue {
+    let value = match value {
+        Value::Bytes(bytes) => Some(PBValue::StringValue(
+            String::from_utf8_lossy(&bytes).into_owned(),
+        )),
+        Value::Regex(regex) => Some(PBValue::StringValue(regex.as_str().to_string())),
+        Value::Integer(i) => Some(PBValue::IntValue(i)),
+        Value::Float(f) => Some(PBValue::DoubleValue(f.into_inner())),
+        Value::Boolean(b) => Some(PBValue::BoolValue(b)),
+        Value::Timestamp(ts) => Some(PBValue::StringValue(ts.to_rfc3339())),
+        Value::Object(map) => Some(PBValue::KvlistValue(KeyValueList {
+            values: map_into_kv_list(map),
+        })),
+        Value::Array(arr) => Some(PBValue::ArrayValue(ArrayValue {
+            values: arr.into_iter().map(value_into_any_value).collect(),
+        })),
+        Value::Null => None,
+    };
+    AnyValue { value }
+}
+
+fn map_into_kv_list(map: BTreeMap<String, Value>) -> Vec<KeyValue> {
+    map.into_iter()
+        .map(|(key, value)| KeyValue {
+            key,
+            value: Some(value_into_any_value(value)),
+        })
+        .collect()
+}
+
+fn value_into_kv_list(value: Option<Value>) -> Vec<KeyValue> {
+    match value {
+        Some(Value::Object(map)) => map_into_kv_list(map),
+        _ => Vec::new(),
+    }
+}
+
+fn value_into_resource(value: Option<Value>) -> Option<Resource> {
+    let attributes = value_into_kv_list(value);
+    (!attributes.is_empty()).then_some(Resource {
+        attributes,
+        dropped_attributes_count: 0,
+    })
+}
+
+fn value_into_u64(value: Option<&Value>) -> u64 {
+    match value {
+        Some(Value::Integer(i)) => u64::try_from(*i).unwrap_or_default(),
+        _ => 0,
+    }
+}
+
+fn value_into_u32(value: Option<&Value>) -> u32 {
+    u32::try_from(value_into_u64(value)).unwrap_or(u32::MAX)
+}
+
+fn value_into_string(value: Option<Value>) -> String {
+    match value {
+        Some(Value::Bytes(bytes)) => String::from_utf8_lossy(&bytes).into_owned(),
+        Some(Value::Null) | None => String::new(),
+        Some(value) => value.to_string(),
+    }
+}
+
+fn value_into_id(value: Option<Value>) -> Vec<u8> {
+    match value {
+        Some(Value::Bytes(bytes)) => hex::decode(&bytes).unwrap_or_default(),
+        _ => Vec::new(),
+    }
+}
+
+fn value_into_nanos(value: Option<&Value>) -> u64 {
+    match value {
+        Some(Value::Timestamp(ts)) => timestamp_into_nanos(*ts),
+        Some(Value::Integer(i)) => u64::try_from(*i).unwrap_or_default(),
+        _ => 0,
+    }
+}
+
+fn timestamp_into_nanos(timestamp: DateTime<Utc>) -> u64 {
+    u64::try_from(timestamp.timestamp_nanos()).unwrap_or_default()
+}
+
+/// Removes an OpenTelemetry-specific field from the log, looking in the source metadata when the
+/// log uses the Vector namespace and at the root of the event otherwise.
+fn take_log_field(log: &mut LogEvent, key: &str) -> Option<Value> {
+    match log.namespace() {
+        LogNamespace::Vector => log.remove(metadata_path!(SOURCE_NAME, key)),
+        LogNamespace::Legacy => log.remove(event_path!(key)),
+    }
+}
+
+fn log_into_record(mut log: LogEvent) -> (Option<Resource>, LogRecord) {
+    let resource = value_into_resource(take_log_field(&mut log, RESOURCE_KEY));
+    let trace_id = value_into_id(take_log_field(&mut log, TRACE_ID_KEY));
+    let span_id = value_into_id(take_log_field(&mut log, SPAN_ID_KEY));
+    let severity_text = value_into_string(take_log_field(&mut log, SEVERITY_TEXT_KEY));
+    let severity_number = i32::try_from(value_into_u64(
+        take_log_field(&mut log, SEVERITY_NUMBER_KEY).as_ref(),
+    ))
+    .unwrap_or_default();
+    let flags = value_into_u32(take_log_field(&mut log, FLAGS_KEY).as_ref());
+    let dropped_attributes_count =
+        value_into_u32(take_log_field(&mut log, DROPPED_ATTRIBUTES_COUNT_KEY).as_ref());
+    let observed_time_unix_nano =
+        value_into_nanos(take_log_field(&mut log, OBSERVED_TIMESTAMP_KEY).as_ref());
+    let time_unix_nano = log
+        .timestamp_path()
+        .cloned()
+        .and_then(|path| log.remove(&path))
+        .as_ref()
+        .map_or(0, |ts| value_into_nanos(Some(ts)));
+    let mut attributes = take_log_field(&mut log, ATTRIBUTES_KEY);
+
+    let body = match log.namespace() {
+        LogNamespace::Vector => Some(log.value().clone()),
+        LogNamespace::Legacy => {
+            let body = log
+                .message_path()
+                .cloned()
+                .and_then(|path| log.remove(&path));
+            if let Some(source_type_key) = log_schema().source_type_key_target_path() {
+                log.remove(source_type_key);
+            }
+            // Without an explicit `attributes` field, whatever is left on the event is the
+            // closest equivalent.
+            if attributes.is_none() {
+                attributes = log
+                    .as_map()
+                    .filter(|map| !map.is_empty())
+                    .cloned()
+                    .map(Value::Object);
+            }
+            body
+        }
+    };
+
+    let record = LogRecord {
+        time_unix_nano,
+        observed_time_unix_nano,
+        severity_number,
+        severity_text,
+        body: body.map(value_into_any_value),
+        attributes: value_into_kv_list(attributes),
+        dropped_attributes_count,
+        flags,
+        trace_id,
+        span_id,
+    };
+
+    (resource, record)
+}
+
+fn metric_into_proto(
+    metric: Metric,
+    now: DateTime<Utc>,
+) -> (Option<Resource>, Option<InstrumentationScope>, PBMetric) {
+    let name = match metric.namespace() {
+        Some(namespace) => format!("{namespace}.{}", metric.name()),
+        None => metric.name().to_string(),
+    };
+    let kind = metric.kind();
+    let time_unix_nano = timestamp_into_nanos(metric.timestamp().unwrap_or(now));
+
+    let mut resource_attributes = Vec::new();
+    let mut scope = InstrumentationScope::default();
+    let mut attributes = Vec::new();
+    if let Some(tags) = metric.tags() {
+        for (key, value) in tags.iter_single() {
+            let string_value = |value: &str| {
+                Some(AnyValue {
+                    value: Some(PBValue::StringValue(value.to_string())),
+                })
+            };
+            if let Some(key) = key.strip_prefix(RESOURCE_TAG_PREFIX) {
+                resource_attributes.push(KeyValue {
+                    key: key.to_string(),
+                    value: string_value(value),
+                });
+            } else if let Some(key) = key.strip_prefix(SCOPE_TAG_PREFIX) {
+                match key {
+                    "name" => scope.name = value.to_string(),
+                    "version" => scope.version = value.to_string(),
+                    _ => scope.attributes.push(KeyValue {
+                        key: key.to_string(),
+                        value: string_value(value),
+                    }),
+                }
+            } else {
+                attributes.push(KeyValue {
+                    key: key.to_string(),
+                    value: string_value(value),
+                });
+            }
+        }
+    }
+
+    let number_point = |value: f64, attributes: Vec<KeyValue>| NumberDataPoint {
+        attributes,
+        start_time_unix_nano: 0,
+        time_unix_nano,
+        exemplars: Vec::new(),
+        flags: 0,
+        value: Some(number_data_point::Value::AsDouble(value)),
+    };
+    let temporality = match kind {
+        MetricKind::Incremental => AggregationTemporality::Delta,
+        MetricKind::Absolute => AggregationTemporality::Cumulative,
+    } as i32;
+
+    let data = match metric.value() {
+        MetricValue::Counter { value } => Data::Sum(Sum {
+            data_points: vec![number_point(*value, attributes)],
+            aggregation_temporality: temporality,
+            is_monotonic: true,
+        }),
+        // Incremental gauges carry a change rather than a value, which only a non-monotonic
+        // delta sum can express.
+        MetricValue::Gauge { value } if kind == MetricKind::Incremental => Data::Sum(Sum {
+            data_points: vec![number_point(*value, attributes)],
+            aggregation_temporality: temporality,
+            is_monotonic: false,
+        }),
+        MetricValue::Gauge { value } => Data::Gauge(Gauge {
+            data_points: vec![number_point(*value, attributes)],
+        }),
+        MetricValue::Set { values } => Data::Gauge(Gauge {
+            data_points: vec![number_point(values.len() as f64, attributes)],
+        }),
+        MetricValue::Distribution { samples, .. } => Data::Histogram(Histogram {
+            data_points: vec![distribution_into_point(samples, attributes, time_unix_nano)],
+            aggregation_temporality: temporality,
+        }),
+        MetricValue::AggregatedHistogram {
+            buckets,
+            count,
+            sum,
+        } => Data::Histogram(Histogram {
+            data_points: vec![histogram_into_point(
+                buckets,
+                *count,
+                *sum,
+                attributes,
+                time_unix_nano,
+            )],
+            aggregation_temporality: temporality,
+        }),
+        MetricValue::AggregatedSummary {
+            quantiles,
+            count,
+            sum,
+        } => Data::Summary(Summary {
+            data_points: vec![summary_into_point(
+                quantiles,
+                *count,
+                *sum,
+                attributes,
+                time_unix_nano,
+            )],
+        }),
+        MetricValue::Sketch {
+            sketch: MetricSketch::AgentDDSketch(sketch),
+        } => {
+            let quantiles = SKETCH_QUANTILES
+                .iter()
+                .filter_map(|q| {
+                    sketch.quantile(*q).map(|value| Quantile {
+                        quantile: *q,
+                        value,
+                    })
+                })
+                .collect::<Vec<_>>();
+            Data::Summary(Summary {
+                data_points: vec![summary_into_point(
+                    &quantiles,
+                    u64::from(sketch.count()),
+                    sketch.sum().unwrap_or_default(),
+                    attributes,
+                    time_unix_nano,
+                )],
+            })
+        }
+    };
+
+    let resource = (!resource_attributes.is_empty()).then_some(Resource {
+        attributes: resource_attributes,
+        dropped_attributes_count: 0,
+    });
+    let scope = (scope != InstrumentationScope::default()).then_some(scope);
+    let metric = PBMetric {
+        name,
+        description: String::new(),
+        unit: String::new(),
+        data: Some(data),
+    };
+
+    (resource, scope, metric)
+}
+
+fn distribution_into_point(
+    samples: &[Sample],
+    attributes: Vec<KeyValue>,
+    time_unix_nano: u64,
+) -> HistogramDataPoint {
+    // Every distinct sample value gets its own bucket, which keeps the conversion lossless.
+    let mut counts = BTreeMap::new();
+    let mut sum = 0.0;
+    let mut count = 0;
+    for sample in samples {
+        let rate = u64::from(sample.rate);
+        *counts
+            .entry(ordered_float::OrderedFloat(sample.value))
+            .or_insert(0) += rate;
+        sum += sample.value * f64::from(sample.rate);
+        count += rate;
+    }
+
+    let explicit_bounds = counts.keys().map(|value| value.into_inner()).collect();
+    let mut bucket_counts = counts.into_values().collect::<Vec<_>>();
+    bucket_counts.push(0);
+
+    HistogramDataPoint {
+        attributes,
+        start_time_unix_nano: 0,
+        time_unix_nano,
+        count,
+        sum: Some(sum),
+        bucket_counts,
+        explicit_bounds,
+        exemplars: Vec::new(),
+        flags: 0,
+        min: None,
+        max: None,
+    }
+}
+
+fn histogram_into_point(
+    buckets: &[Bucket],
+    count: u64,
+    sum: f64,
+    attributes: Vec<KeyValue>,
+    time_unix_nano: u64,
+) -> HistogramDataPoint {
+    let buckets = buckets
+        .iter()
+        .filter(|bucket| bucket.upper_limit.is_finite())
+        .collect::<Vec<_>>();
+    let explicit_bounds = buckets.iter().map(|bucket| bucket.upper_limit).collect();
+    let mut bucket_counts = buckets
+        .iter()
+        .map(|bucket| bucket.count)
+        .collect::<Vec<_>>();
+    // The overflow bucket holds whatever is not accounted for by the explicit buckets.
+    let overflow = count.saturating_sub(bucket_counts.iter().sum());
+    bucket_counts.push(overflow);
+
+    HistogramDataPoint {
+        attributes,
+        start_time_unix_nano: 0,
+        time_unix_nano,
+        count,
+        sum: Some(sum),
+        bucket_counts,
+        explicit_bounds,
+        exemplars: Vec::new(),
+        flags: 0,
+        min: None,
+        max: None,
+    }
+}
+
+fn summary_into_point(
+    quantiles: &[Quantile],
+    count: u64,
+    sum: f64,
+    attributes: Vec<KeyValue>,
+    time_unix_nano: u64,
+) -> SummaryDataPoint {
+    SummaryDataPoint {
+        attributes,
+        start_time_unix_nano: 0,
+        time_unix_nano,
+        count,
+        sum,
+        quantile_values: quantiles
+            .iter()
+            .map(|q| ValueAtQuantile {
+                quantile: q.quantile,
+                value: q.value,
+            })
+            .collect(),
+        flags: 0,
+    }
+}
+
+fn value_into_span_event(value: Value) -> Option<SpanEvent> {
+    let mut obj = value.into_object()?;
+    Some(SpanEvent {
+        time_unix_nano: value_into_nanos(obj.get("time_unix_nano")),
+        name: value_into_string(obj.remove("name")),
+        dropped_attributes_count: value_into_u32(obj.get(DROPPED_ATTRIBUTES_COUNT_KEY)),
+        attributes: value_into_kv_list(obj.remove(ATTRIBUTES_KEY)),
+    })
+}
+
+fn value_into_link(value: Value) -> Option<Link> {
+    let mut obj = value.into_object()?;
+    Some(Link {
+        trace_id: value_into_id(obj.remove(TRACE_ID_KEY)),
+        span_id: value_into_id(obj.remove(SPAN_ID_KEY)),
+        trace_state: value_into_string(obj.remove(TRACE_STATE_KEY)),
+        dropped_attributes_count: value_into_u32(obj.get(DROPPED_ATTRIBUTES_COUNT_KEY)),
+        attributes: value_into_kv_list(obj.remove(ATTRIBUTES_KEY)),
+    })
+}
+
+fn value_into_status(value: Value) -> Option<SpanStatus> {
+    let mut obj = value.into_object()?;
+    Some(SpanStatus {
+        message: value_into_string(obj.remove("message")),
+        code: i32::try_from(value_into_u64(obj.get("code"))).unwrap_or_default(),
+    })
+}
+
+fn value_into_vec<T>(value: Option<Value>, f: impl Fn(Value) -> Option<T>) -> Vec<T> {
+    match value {
+        Some(Value::Array(arr)) => arr.into_iter().filter_map(f).collect(),
+        _ => Vec::new(),
+    }
+}
+
+fn trace_into_span(trace: TraceEvent) -> (Option<Resource>, Span) {
+    let (mut fields, _metadata) = trace.into_parts();
+
+    let resource = value_into_resource(fields.remove(RESOURCE_KEY));
+    let span = Span {
+        trace_id: value_into_id(fields.remove(TRACE_ID_KEY)),
+        span_id: value_into_id(fields.remove(SPAN_ID_KEY)),
+        trace_state: value_into_string(fields.remove(TRACE_STATE_KEY)),
+        parent_span_id: value_into_id(fields.remove(PARENT_SPAN_ID_KEY)),
+        name: value_into_string(fields.remove(NAME_KEY)),
+        kind: i32::try_from(value_into_u64(fields.get(KIND_KEY))).unwrap_or_default(),
+        start_time_unix_nano: value_into_nanos(fields.get(START_TIME_KEY)),
+        end_time_unix_nano: value_into_nanos(fields.get(END_TIME_KEY)),
+        attributes: value_into_kv_list(fields.remove(ATTRIBUTES_KEY)),
+        dropped_attributes_count: value_into_u32(fields.get(DROPPED_ATTRIBUTES_COUNT_KEY)),
+        events: value_into_vec(fields.remove(EVENTS_KEY), value_into_span_event),
+        dropped_events_count: value_into_u32(fields.get(DROPPED_EVENTS_COUNT_KEY)),
+        links: value_into_vec(fields.remove(LINKS_KEY), value_into_link),
+        dropped_links_count: value_into_u32(fields.get(DROPPED_LINKS_COUNT_KEY)),
+        status: fields.remove(STATUS_KEY).and_then(value_into_status),
+    };
+
+    (resource, span)
+}
+
+#[cfg(test)]
+mod tests {
+    use vector_core::event::{Event, MetricTags};
+
+    use super::*;
+
+    fn string_kv(key: &str, value: &str) -> KeyValue {
+        KeyValue {
+            key: key.to_string(),
+            value: Some(AnyValue {
+                value: Some(PBValue::StringValue(value.to_string())),
+            }),
+        }
+    }
+
+    fn resource(service: &str) -> Option<Resource> {
+        Some(Resource {
+            attributes: vec![string_kv("service", service)],
+            dropped_attributes_count: 0,
+        })
+    }
+
+    fn number_point(value: f64) -> NumberDataPoint {
+        NumberDataPoint {
+            attributes: vec![string_kv("host", "localhost")],
+            start_time_unix_nano: 0,
+            time_unix_nano: 1_579_134_612_000_000_011,
+            exemplars: Vec::new(),
+            flags: 0,
+            value: Some(number_data_point::Value::AsDouble(value)),
+        }
+    }
+
+    fn counter(name: &str, tags: &[(&str, &str)]) -> Metric {
+        let tags = tags
+            .iter()
+            .map(|(key, value)| (key.to_string(), value.to_string()))
+            .collect::<MetricTags>();
+        Metric::new(
+            name,
+            MetricKind::Incremental,
+            MetricValue::Counter { value: 1.0 },
+        )
+        .with_tags(Some(tags))
+    }
+
+    #[test]
+    fn logs_round_trip() {
+        let resource_logs = ResourceLogs {
+            resource: resource("api"),
+            scope_logs: vec![ScopeLogs {
+                scope: None,
+                log_records: vec![LogRecord {
+                    time_unix_nano: 1_579_134_612_000_000_011,
+                    observed_time_unix_nano: 1_579_134_612_000_000_022,
+                    severity_number: 9,
+                    severity_text: "info".to_string(),
+                    body: Some(AnyValue {
+                        value: Some(PBValue::StringValue("foo".to_string())),
+                    }),
+                    attributes: vec![string_kv("a", "b"), string_kv("c", "d")],
+                    dropped_attributes_count: 0,
+                    flags: 1,
+                    trace_id: vec![1; 16],
+                    span_id: vec![2; 8],
+                }],
+                schema_url: String::new(),
+            }],
+            schema_url: String::new(),
+        };
+
+        let logs = resource_logs
+            .clone()
+            .into_event_iter(LogNamespace::Legacy)
+            .map(Event::into_log);
+        let request = logs_into_request(logs);
+
+        assert_eq!(request.resource_logs, vec![resource_logs]);
+    }
+
+    #[test]
+    fn metrics_round_trip() {
+        let resource_metrics = ResourceMetrics {
+            resource: resource("api"),
+            scope_metrics: vec![ScopeMetrics {
+                scope: Some(InstrumentationScope {
+                    name: "meter".to_string(),
+                    version: "1.0".to_string(),
+                    attributes: Vec::new(),
+                    dropped_attributes_count: 0,
+                }),
+                metrics: vec![
+                    PBMetric {
+                        name: "requests".to_string(),
+                        description: String::new(),
+                        unit: String::new(),
+                        data: Some(Data::Sum(Sum {
+                            data_points: vec![number_point(3.0)],
+                            aggregation_temporality: AggregationTemporality::Delta as i32,
+                            is_monotonic: true,
+                        })),
+                    },
+                    PBMetric {
+                        name: "temperature".to_string(),
+                        description: String::new(),
+                        unit: String::new(),
+                        data: Some(Data::Gauge(Gauge {
+                            data_points: vec![number_point(21.5)],
+                        })),
+                    },
+                ],
+                schema_url: String::new(),
+            }],
+            schema_url: String::new(),
+        };
+
+        let metrics = resource_metrics
+            .clone()
+            .into_event_iter()
+            .map(Event::into_metric);
+        let request = metrics_into_request(metrics);
+
+        assert_eq!(request.resource_metrics, vec![resource_metrics]);
+    }
+
+    #[test]
+    fn traces_round_trip() {
+        let resource_spans = ResourceSpans {
+            resource: resource("api"),
+            scope_spans: vec![ScopeSpans {
+                scope: None,
+                spans: vec![Span {
+                    trace_id: vec![1; 16],
+                    span_id: vec![2; 8],
+                    trace_state: "foo=bar".to_string(),
+                    parent_span_id: vec![3; 8],
+                    name: "request".to_string(),
+                    kind: 2,
+                    start_time_unix_nano: 1_579_134_612_000_000_011,
+                    end_time_unix_nano: 1_579_134_612_000_000_022,
+                    attributes: vec![string_kv("a", "b")],
+                    dropped_attributes_count: 1,
+                    events: vec![SpanEvent {
+                        time_unix_nano: 1_579_134_612_000_000_015,
+                        name: "retry".to_string(),
+                        attributes: vec![string_kv("attempt", "2")],
+                        dropped_attributes_count: 0,
+                    }],
+                    dropped_events_count: 2,
+                    links: Vec::new(),
+                    dropped_links_count: 3,
+                    status: Some(SpanStatus {
+                        message: "ok".to_string(),
+                        code: 1,
+                    }),
+                }],
+                schema_url: String::new(),
+            }],
+            schema_url: String::new(),
+        };
+
+        let traces = resource_spans
+            .clone()
+            .into_event_iter()
+            .map(Event::into_trace);
+        let request = traces_into_request(traces);
+
+        assert_eq!(request.resource_spans, vec![resource_spans]);
+    }
+
+    #[test]
+    fn metric_namespace_prefixes_name() {
+        let request = metrics_into_request([
+            counter("requests", &[]).with_namespace(Some("app")),
+            counter("requests", &[]),
+        ]);
+
+        let names = request.resource_metrics[0].scope_metrics[0]
+            .metrics
+            .iter()
+            .map(|metric| metric.name.as_str())
+            .collect::<Vec<_>>();
+        assert_eq!(names, ["app.requests", "requests"]);
+    }
+
+    #[test]
+    fn metrics_grouped_by_resource_and_scope() {
+        let request = metrics_into_request([
+            counter("a", &[("resource.service", "api"), ("scope.name", "meter")]),
+            counter("b", &[("resource.service", "db"), ("scope.name", "meter")]),
+            counter("c", &[("resource.service", "api"), ("scope.name", "meter")]),
+            counter("d", &[("resource.service", "api"), ("scope.name", "other")]),
+            counter("e", &[]),
+        ]);
+
+        let groups = request
+            .resource_metrics
+            .iter()
+            .map(|resource_metrics| {
+                let scope_metrics = &resource_metrics.scope_metrics[0];
+                let service = resource_metrics
+                    .resource
+                    .as_ref()
+                    .map(|resource| resource.attributes[0].clone());
+                let scope = scope_metrics
+                    .scope
+                    .as_ref()
+                    .map(|scope| scope.name.as_str());
+                let names = scope_metrics
+                    .metrics
+                    .iter()
+                    .map(|metric| metric.name.as_str())
+                    .collect::<Vec<_>>();
+                (service, scope, names)
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(
+            groups,
+            vec![
+                (
+                    Some(string_kv("service", "api")),
+                    Some("meter"),
+                    vec!["a", "c"]
+                ),
+                (Some(string_kv("service", "db")), Some("meter"), vec!["b"]),
+                (Some(string_kv("service", "api")), Some("other"), vec!["d"]),
+                (None, None, vec!["e"]),
+            ]
+        );
+    }
+
+    #[test]
+    fn logs_grouped_by_resource() {
+        let log = |service: &str| {
+            let mut log = LogEvent::default();
+            log.insert(event_path!("message"), "foo");
+            log.insert(
+                event_path!(RESOURCE_KEY),
+                Value::from(BTreeMap::from([(
+                    "service".to_string(),
+                    Value::from(service),
+                )])),
+            );
+            log
+        };
+
+        let request = logs_into_request([log("api"), log("db"), log("api")]);
+
+        let groups = request
+            .resource_logs
+            .iter()
+            .map(|resource_logs| {
+                (
+                    resource_logs.resource.clone(),
+                    resource_logs.scope_logs[0].log_records.len(),
+                )
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(groups, vec![(resource("api"), 2), (resource("db"), 1)]);
+    }
+}
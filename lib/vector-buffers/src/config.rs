@@ -8,7 +8,9 @@ use std::{
 use serde::{de, Deserialize, Deserializer, Serialize};
 use snafu::{ResultExt, Snafu};
 use tracing::Span;
-use vector_common::{config::ComponentKey, finalization::Finalizable};
+use vector_common::{
+    config::ComponentKey, finalization::Finalizable, sensitive_string::SensitiveString,
+};
 use vector_config::configurable_component;
 
 use crate::{
@@ -16,7 +18,10 @@ use crate::{
         builder::{TopologyBuilder, TopologyError},
         channel::{BufferReceiver, BufferSender},
     },
-    variants::{DiskV2Buffer, MemoryBuffer},
+    variants::{
+        disk_v2::{EncryptionError, Keyring},
+        DiskV2Buffer, MemoryBuffer,
+    },
     Bufferable, WhenFull,
 };
 
@@ -28,6 +33,12 @@ pub enum BufferBuildError {
     FailedToBuildTopology { source: TopologyError },
     #[snafu(display("`max_events` must be greater than zero"))]
     InvalidMaxEvents,
+    #[snafu(display("invalid disk buffer encryption configuration: {}", source))]
+    InvalidEncryption { source: EncryptionError },
+    #[snafu(display("the `{}` buffer type can only be used once per buffer", stage))]
+    DuplicateStage { stage: &'static str },
+    #[snafu(display("a disk buffer can only be used as the last stage of a chained buffer"))]
+    DiskStageNotLast,
 }
 
 #[derive(Deserialize, Serialize)]
@@ -38,7 +49,7 @@ enum BufferTypeKind {
     DiskV2,
 }
 
-const ALL_FIELDS: [&str; 4] = ["type", "max_events", "max_size", "when_full"];
+const ALL_FIELDS: [&str; 5] = ["type", "max_events", "max_size", "when_full", "encryption"];
 
 struct BufferTypeVisitor;
 
@@ -51,6 +62,7 @@ impl BufferTypeVisitor {
         let mut max_events: Option<NonZeroUsize> = None;
         let mut max_size: Option<NonZeroU64> = None;
         let mut when_full: Option<WhenFull> = None;
+        let mut encryption: Option<DiskBufferEncryption> = None;
         while let Some(key) = map.next_key::<String>()? {
             match key.as_str() {
                 "type" => {
@@ -77,6 +89,12 @@ impl BufferTypeVisitor {
                     }
                     when_full = Some(map.next_value()?);
                 }
+                "encryption" => {
+                    if encryption.is_some() {
+                        return Err(de::Error::duplicate_field("encryption"));
+                    }
+                    encryption = Some(map.next_value()?);
+                }
                 other => {
                     return Err(de::Error::unknown_field(other, &ALL_FIELDS));
                 }
@@ -92,6 +110,12 @@ impl BufferTypeVisitor {
                         &["type", "max_events", "when_full"],
                     ));
                 }
+                if encryption.is_some() {
+                    return Err(de::Error::unknown_field(
+                        "encryption",
+                        &["type", "max_events", "when_full"],
+                    ));
+                }
                 Ok(BufferType::Memory {
                     max_events: max_events.unwrap_or_else(memory_buffer_default_max_events),
                     when_full,
@@ -101,12 +125,13 @@ impl BufferTypeVisitor {
                 if max_events.is_some() {
                     return Err(de::Error::unknown_field(
                         "max_events",
-                        &["type", "max_size", "when_full"],
+                        &["type", "max_size", "when_full", "encryption"],
                     ));
                 }
                 Ok(BufferType::DiskV2 {
                     max_size: max_size.ok_or_else(|| de::Error::missing_field("max_size"))?,
                     when_full,
+                    encryption,
                 })
             }
         }
@@ -175,9 +200,46 @@ impl DiskUsage {
     }
 }
 
+/// Encryption at rest for disk buffers.
+///
+/// Records are encrypted with XChaCha20-Poly1305 before being written to disk. Keys are 256 bits,
+/// encoded as 64 hexadecimal characters, and are best provided through the secrets subsystem (for
+/// example, `SECRET[backend.buffer_key]`) rather than directly in the configuration.
+#[configurable_component]
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[serde(deny_unknown_fields)]
+pub struct DiskBufferEncryption {
+    /// The key used to encrypt records written to the buffer.
+    #[configurable(metadata(docs::examples = "SECRET[backend.buffer_key]"))]
+    pub key: SensitiveString,
+
+    /// Keys that records already in the buffer may have been encrypted with.
+    ///
+    /// When rotating keys, move the old key here so that records written before the rotation can
+    /// still be read. The buffer refuses to start if it contains records encrypted with a key that
+    /// is neither the current key nor one of the previous keys. A previous key can be removed once
+    /// the buffer has been fully drained.
+    #[serde(default)]
+    pub previous_keys: Vec<SensitiveString>,
+}
+
+impl DiskBufferEncryption {
+    /// Builds the keyring used by the disk buffer from the configured keys.
+    ///
+    /// # Errors
+    ///
+    /// If any of the configured keys is invalid, an error variant will be returned.
+    pub(crate) fn keyring(&self) -> Result<Keyring, EncryptionError> {
+        Keyring::from_hex(
+            self.key.inner(),
+            self.previous_keys.iter().map(SensitiveString::inner),
+        )
+    }
+}
+
 /// A specific type of buffer stage.
 #[configurable_component(no_deser)]
-#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[derive(Clone, Debug, PartialEq, Eq)]
 #[serde(rename_all = "snake_case", tag = "type")]
 #[configurable(metadata(docs::enum_tag_description = "The type of buffer to use."))]
 pub enum BufferType {
@@ -218,10 +280,22 @@ pub enum BufferType {
         #[configurable(derived)]
         #[serde(default)]
         when_full: WhenFull,
+
+        #[configurable(derived)]
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        encryption: Option<DiskBufferEncryption>,
     },
 }
 
 impl BufferType {
+    /// Gets the name of this buffer type, as used in configuration.
+    pub const fn type_name(&self) -> &'static str {
+        match self {
+            Self::Memory { .. } => "memory",
+            Self::DiskV2 { .. } => "disk",
+        }
+    }
+
     /// Gets the metadata around disk usage by the buffer, if supported.
     ///
     /// For buffer types that write to disk, `Some(value)` is returned with their usage metadata,
@@ -276,19 +350,28 @@ impl BufferType {
     where
         T: Bufferable + Clone + Finalizable,
     {
-        match *self {
+        match self {
             BufferType::Memory {
                 when_full,
                 max_events,
             } => {
-                builder.stage(MemoryBuffer::new(max_events), when_full);
+                builder.stage(MemoryBuffer::new(*max_events), *when_full);
             }
             BufferType::DiskV2 {
                 when_full,
                 max_size,
+                encryption,
             } => {
                 let data_dir = data_dir.ok_or(BufferBuildError::RequiresDataDir)?;
-                builder.stage(DiskV2Buffer::new(id, data_dir, max_size), when_full);
+                let keyring = encryption
+                    .as_ref()
+                    .map(DiskBufferEncryption::keyring)
+                    .transpose()
+                    .context(InvalidEncryptionSnafu)?;
+                builder.stage(
+                    DiskV2Buffer::new(id, data_dir, *max_size, keyring),
+                    *when_full,
+                );
             }
         };
 
@@ -310,10 +393,9 @@ impl BufferType {
 /// functionality to allow chaining buffers together, you'll see "buffer topology" used in internal
 /// documentation to correctly reflect the internal structure.
 ///
-// TODO: We need to limit chained buffers to only allowing a single copy of each buffer type to be
-// defined, otherwise, for example, two instances of the same disk buffer type in a single chained
-// buffer topology would try to both open the same buffer files on disk, which wouldn't work or
-// would go horribly wrong.
+/// Chained buffers can only contain a single stage of each buffer type, as two disk buffer stages
+/// would otherwise try to open the same buffer files on disk.  A disk buffer stage must also be the
+/// last stage, which makes the typical chained buffer a memory buffer that overflows to a disk buffer.
 #[configurable_component]
 #[derive(Clone, Debug, PartialEq, Eq)]
 #[serde(untagged)]
@@ -362,6 +444,9 @@ impl BufferConfig {
     ///
     /// If a disk buffer stage is configured and the data directory provided is `None`, an error
     /// variant will be thrown.
+    ///
+    /// If the same buffer type is used for more than one stage, or a disk buffer stage is followed
+    /// by another stage, an error variant will be thrown.
     #[allow(clippy::needless_pass_by_value)]
     pub async fn build<T>(
         &self,
@@ -372,6 +457,8 @@ impl BufferConfig {
     where
         T: Bufferable + Clone + Finalizable,
     {
+        self.validate_stages()?;
+
         let mut builder = TopologyBuilder::default();
 
         for stage in self.stages() {
@@ -383,13 +470,34 @@ impl BufferConfig {
             .await
             .context(FailedToBuildTopologySnafu)
     }
+
+    fn validate_stages(&self) -> Result<(), BufferBuildError> {
+        let stages = self.stages();
+        for (i, stage) in stages.iter().enumerate() {
+            if stages[..i]
+                .iter()
+                .any(|previous| previous.type_name() == stage.type_name())
+            {
+                return Err(BufferBuildError::DuplicateStage {
+                    stage: stage.type_name(),
+                });
+            }
+
+            if matches!(stage, BufferType::DiskV2 { .. }) && i + 1 < stages.len() {
+                return Err(BufferBuildError::DiskStageNotLast);
+            }
+        }
+
+        Ok(())
+    }
 }
 
 #[cfg(test)]
 mod test {
     use std::num::{NonZeroU64, NonZeroUsize};
 
-    use crate::{BufferConfig, BufferType, WhenFull};
+    use super::BufferBuildError;
+    use crate::{BufferConfig, BufferType, DiskBufferEncryption, WhenFull};
 
     fn check_single_stage(source: &str, expected: BufferType) {
         let config: BufferConfig = serde_yaml::from_str(source).unwrap();
@@ -519,7 +627,86 @@ max_events: 42
             BufferType::DiskV2 {
                 max_size: NonZeroU64::new(1024).unwrap(),
                 when_full: WhenFull::Block,
+                encryption: None,
             },
         );
     }
+
+    #[test]
+    fn parse_disk_encryption() {
+        check_single_stage(
+            r#"
+          type: disk
+          max_size: 1024
+          encryption:
+            key: "000102030405060708090a0b0c0d0e0f101112131415161718191a1b1c1d1e1f"
+            previous_keys:
+              - "1f1e1d1c1b1a191817161514131211100f0e0d0c0b0a09080706050403020100"
+          "#,
+            BufferType::DiskV2 {
+                max_size: NonZeroU64::new(1024).unwrap(),
+                when_full: WhenFull::Block,
+                encryption: Some(DiskBufferEncryption {
+                    key: "000102030405060708090a0b0c0d0e0f101112131415161718191a1b1c1d1e1f"
+                        .to_string()
+                        .into(),
+                    previous_keys: vec![
+                        "1f1e1d1c1b1a191817161514131211100f0e0d0c0b0a09080706050403020100"
+                            .to_string()
+                            .into(),
+                    ],
+                }),
+            },
+        );
+
+        let source = r#"
+          type: memory
+          encryption:
+            key: "000102030405060708090a0b0c0d0e0f101112131415161718191a1b1c1d1e1f"
+          "#;
+        let error = serde_yaml::from_str::<BufferConfig>(source).unwrap_err();
+        assert_eq!(error.to_string(), BUFFER_CONFIG_NO_MATCH_ERR);
+    }
+
+    #[test]
+    fn validate_chained_stages() {
+        let memory_overflow_to_disk: BufferConfig = serde_yaml::from_str(
+            r#"
+          - type: memory
+            max_events: 100
+            when_full: overflow
+          - type: disk
+            max_size: 268435488
+          "#,
+        )
+        .unwrap();
+        assert!(memory_overflow_to_disk.validate_stages().is_ok());
+
+        let duplicate: BufferConfig = serde_yaml::from_str(
+            r#"
+          - type: memory
+            when_full: overflow
+          - type: memory
+          "#,
+        )
+        .unwrap();
+        assert!(matches!(
+            duplicate.validate_stages(),
+            Err(BufferBuildError::DuplicateStage { stage: "memory" })
+        ));
+
+        let disk_first: BufferConfig = serde_yaml::from_str(
+            r#"
+          - type: disk
+            max_size: 268435488
+            when_full: overflow
+          - type: memory
+          "#,
+        )
+        .unwrap();
+        assert!(matches!(
+            disk_first.validate_stages(),
+            Err(BufferBuildError::DiskStageNotLast)
+        ));
+    }
 }
@@ -51,6 +51,19 @@ impl InternalEvent for BufferEventsSent {
     }
 }
 
+pub struct BufferEventsOverflowed {
+    pub idx: usize,
+    pub count: u64,
+    pub byte_size: u64,
+}
+
+impl InternalEvent for BufferEventsOverflowed {
+    fn emit(self) {
+        counter!("buffer_overflowed_events_total", self.count, "stage" => self.idx.to_string());
+        counter!("buffer_overflowed_bytes_total", self.byte_size, "stage" => self.idx.to_string());
+    }
+}
+
 pub struct BufferEventsDropped {
     pub idx: usize,
     pub count: u64,
@@ -16,7 +16,7 @@ extern crate tracing;
 mod buffer_usage_data;
 
 pub mod config;
-pub use config::{BufferConfig, BufferType};
+pub use config::{BufferConfig, BufferType, DiskBufferEncryption};
 use encoding::Encodable;
 use vector_config::configurable_component;
 
@@ -66,16 +66,15 @@ pub enum WhenFull {
     /// potentially be able to buffer the event, but it may also block or drop the event.
     ///
     /// This mode can only be used when two or more buffer stages are configured.
-    #[configurable(metadata(docs::hidden))]
     Overflow,
 }
 
 #[cfg(test)]
 impl Arbitrary for WhenFull {
     fn arbitrary(g: &mut Gen) -> Self {
-        // TODO: We explicitly avoid generating "overflow" as a possible value because nothing yet
-        // supports handling it, and will be defaulted to to using "block" if they encounter
-        // "overflow".  Thus, there's no reason to emit it here... yet.
+        // We explicitly avoid generating "overflow" as a possible value because it is only valid
+        // for a stage that is followed by another stage, and arbitrary buffer variants are only
+        // ever used as standalone stages.
         if bool::arbitrary(g) {
             WhenFull::Block
         } else {
@@ -1,11 +1,10 @@
 use std::{
-    mem,
+    fmt, mem,
     pin::Pin,
     task::{ready, Context, Poll},
 };
 
-use async_recursion::async_recursion;
-use futures::Stream;
+use futures::{Stream, StreamExt};
 use tokio::select;
 use tokio_util::sync::ReusableBoxFuture;
 use vector_common::internal_event::emit;
@@ -72,10 +71,16 @@ where
 /// for querying the overflow buffer as well.  The ordering of events when operating in "overflow"
 /// is undefined, as the receiver will try to manage polling both its own buffer, as well as the
 /// overflow buffer, in order to fairly balance throughput.
+///
+/// The overflow buffer is polled as a stream so that a read which is interrupted by an item arriving
+/// on the base buffer is resumed, rather than restarted, the next time the receiver is polled.  Not
+/// every buffer can safely abandon a read that is in progress -- the disk buffer, for example, may
+/// have already consumed part of a record -- so this is what allows a disk buffer to be used as the
+/// overflow buffer without losing data.
 #[derive(Debug)]
 pub struct BufferReceiver<T: Bufferable> {
     base: ReceiverAdapter<T>,
-    overflow: Option<Box<BufferReceiver<T>>>,
+    overflow: Option<Pin<Box<BufferReceiverStream<T>>>>,
     instrumentation: Option<BufferUsageHandle>,
 }
 
@@ -93,7 +98,7 @@ impl<T: Bufferable> BufferReceiver<T> {
     pub fn with_overflow(base: ReceiverAdapter<T>, overflow: BufferReceiver<T>) -> Self {
         Self {
             base,
-            overflow: Some(Box::new(overflow)),
+            overflow: Some(Box::pin(overflow.into_stream())),
             instrumentation: None,
         }
     }
@@ -104,7 +109,7 @@ impl<T: Bufferable> BufferReceiver<T> {
     /// when initially constructing `BufferSender<T>`.
     #[cfg(test)]
     pub fn switch_to_overflow(&mut self, overflow: BufferReceiver<T>) {
-        self.overflow = Some(Box::new(overflow));
+        self.overflow = Some(Box::pin(overflow.into_stream()));
     }
 
     /// Configures this receiver to instrument the items passing through it.
@@ -112,7 +117,6 @@ impl<T: Bufferable> BufferReceiver<T> {
         self.instrumentation = Some(handle);
     }
 
-    #[async_recursion]
     pub async fn next(&mut self) -> Option<T> {
         // We want to poll both our base and overflow receivers without waiting for one or the
         // other to entirely drain before checking the other.  This ensures that we're fairly
@@ -122,14 +126,14 @@ impl<T: Bufferable> BufferReceiver<T> {
         // occurred, and is over, and items are flowing through the base receiver.  If we waited to
         // entirely drain the overflow receiver, we might cause another small stall of the pipeline
         // attached to the base receiver.
-        let overflow = self.overflow.as_mut().map(Pin::new);
+        let overflow = self.overflow.as_mut();
 
         let (item, from_base) = match overflow {
             None => match self.base.next().await {
                 Some(item) => (item, true),
                 None => return None,
             },
-            Some(mut overflow) => {
+            Some(overflow) => {
                 select! {
                     Some(item) = overflow.next() => (item, false),
                     Some(item) = self.base.next() => (item, true),
@@ -168,6 +172,20 @@ pub struct BufferReceiverStream<T: Bufferable> {
     recv_fut: ReusableBoxFuture<'static, (Option<T>, BufferReceiver<T>)>,
 }
 
+impl<T: Bufferable> fmt::Debug for BufferReceiverStream<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let state = match &self.state {
+            StreamState::Idle(_) => "idle",
+            StreamState::Polling => "polling",
+            StreamState::Closed(_) => "closed",
+        };
+
+        f.debug_struct("BufferReceiverStream")
+            .field("state", &state)
+            .finish_non_exhaustive()
+    }
+}
+
 impl<T: Bufferable> BufferReceiverStream<T> {
     pub fn new(receiver: BufferReceiver<T>) -> Self {
         Self {
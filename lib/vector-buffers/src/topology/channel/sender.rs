@@ -215,6 +215,11 @@ impl<T: Bufferable> BufferSender<T> {
                         item_count as u64,
                         item_size as u64,
                     );
+                } else {
+                    instrumentation.increment_overflowed_event_count_and_byte_size(
+                        item_count as u64,
+                        item_size as u64,
+                    );
                 }
 
                 if was_dropped {
@@ -1,5 +1,6 @@
 use std::{
     path::{Path, PathBuf},
+    sync::Arc,
     time::Duration,
 };
 
@@ -7,6 +8,7 @@ use crc32fast::Hasher;
 use snafu::Snafu;
 
 use super::{
+    encryption::Keyring,
     io::{Filesystem, ProductionFilesystem},
     ledger::LEDGER_LEN,
     record::RECORD_HEADER_LEN,
@@ -159,6 +161,12 @@ pub struct DiskBufferConfig<FS> {
     /// implementation essentially defines how we open and delete data files, as well as the type of
     /// the data file objects we get when opening a data file.
     pub(crate) filesystem: FS,
+
+    /// Keys used to encrypt and decrypt records.
+    ///
+    /// When present, record payloads are encrypted with the current key before being written to a
+    /// data file, and may be decrypted with any key in the keyring.
+    pub(crate) keyring: Option<Arc<Keyring>>,
 }
 
 /// Builder for [`DiskBufferConfig`].
@@ -174,6 +182,7 @@ where
     pub(crate) write_buffer_size: Option<usize>,
     pub(crate) flush_interval: Option<Duration>,
     pub(crate) filesystem: FS,
+    pub(crate) keyring: Option<Keyring>,
 }
 
 impl DiskBufferConfigBuilder {
@@ -189,6 +198,7 @@ impl DiskBufferConfigBuilder {
             write_buffer_size: None,
             flush_interval: None,
             filesystem: ProductionFilesystem,
+            keyring: None,
         }
     }
 }
@@ -268,6 +278,19 @@ where
         self
     }
 
+    /// Sets the keys used to encrypt records at rest.
+    ///
+    /// Records are encrypted with the current key of the keyring, and can be decrypted with any key
+    /// in the keyring, which allows rotating keys while records written with a previous key are
+    /// still in the buffer.
+    ///
+    /// Defaults to no encryption.
+    #[allow(dead_code)]
+    pub fn encryption(mut self, keyring: Keyring) -> Self {
+        self.keyring = Some(keyring);
+        self
+    }
+
     /// Filesystem implementation for opening data files.
     ///
     /// We allow parameterizing the filesystem implementation for ease of testing.  The "filesystem"
@@ -288,6 +311,7 @@ where
             write_buffer_size: self.write_buffer_size,
             flush_interval: self.flush_interval,
             filesystem,
+            keyring: self.keyring,
         }
     }
 
@@ -413,6 +437,7 @@ where
             write_buffer_size,
             flush_interval,
             filesystem,
+            keyring: self.keyring.map(Arc::new),
         })
     }
 }
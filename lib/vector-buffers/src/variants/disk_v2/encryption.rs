@@ -0,0 +1,351 @@
+use std::fmt;
+
+use chacha20poly1305::{
+    aead::{Aead, Payload},
+    Key, KeyInit, XChaCha20Poly1305, XNonce,
+};
+use rand::RngCore;
+use snafu::Snafu;
+
+/// Record metadata bit used to mark a record whose payload has been encrypted.
+///
+/// `Encodable::Metadata` implementations never use the high bit, so we reserve it for the buffer
+/// itself.  The bit is stripped from the record metadata before it is handed back to the decoder.
+pub const ENCRYPTED_RECORD_FLAG: u32 = 1 << 31;
+
+const KEY_LEN: usize = 32;
+const KEY_ID_LEN: usize = 8;
+const NONCE_LEN: usize = 24;
+const TAG_LEN: usize = 16;
+
+/// Number of bytes that encryption adds to an encoded record.
+///
+/// Encrypted payloads are laid out as `BE(key ID) + nonce + ciphertext + tag`.
+pub const ENCRYPTION_OVERHEAD: usize = KEY_ID_LEN + NONCE_LEN + TAG_LEN;
+
+/// Fixed associated data used when deriving the identifier of a key.
+const KEY_ID_CONTEXT: &[u8] = b"vector disk buffer key id";
+
+/// Error that occurred when encrypting or decrypting a record.
+#[derive(Debug, Snafu)]
+pub enum EncryptionError {
+    /// The configured key could not be parsed.
+    #[snafu(display("invalid encryption key: {}", reason))]
+    InvalidKey { reason: String },
+
+    /// The record was encrypted with a key that is not part of the configured keyring.
+    #[snafu(display("record was encrypted with unknown key {:016x}", key_id))]
+    UnknownKey { key_id: u64 },
+
+    /// The record was encrypted, but the buffer was not configured with any encryption keys.
+    #[snafu(display("record is encrypted but no encryption key is configured"))]
+    NotConfigured,
+
+    /// The encrypted payload was too short to contain the encryption envelope.
+    #[snafu(display("encrypted payload truncated: {} bytes", len))]
+    Truncated { len: usize },
+
+    /// The payload could not be encrypted.
+    #[snafu(display("failed to encrypt payload"))]
+    Encrypt,
+
+    /// The payload could not be decrypted, either because it was modified or because it was
+    /// encrypted for a different record.
+    #[snafu(display("failed to authenticate encrypted payload"))]
+    Decrypt,
+}
+
+/// A single key used to encrypt records.
+#[derive(Clone)]
+struct EncryptionKey {
+    id: u64,
+    cipher: XChaCha20Poly1305,
+}
+
+impl EncryptionKey {
+    /// Parses a key from its hexadecimal representation.
+    ///
+    /// Keys must be exactly 256 bits, or 64 hexadecimal characters.
+    fn from_hex(hex: &str) -> Result<Self, EncryptionError> {
+        let hex = hex.trim();
+        if hex.len() != KEY_LEN * 2 {
+            return Err(EncryptionError::InvalidKey {
+                reason: format!(
+                    "expected {} hexadecimal characters, got {}",
+                    KEY_LEN * 2,
+                    hex.len()
+                ),
+            });
+        }
+
+        if !hex.bytes().all(|b| b.is_ascii_hexdigit()) {
+            return Err(EncryptionError::InvalidKey {
+                reason: "key must only contain hexadecimal characters".to_string(),
+            });
+        }
+
+        let mut key = [0u8; KEY_LEN];
+        for (i, byte) in key.iter_mut().enumerate() {
+            *byte = u8::from_str_radix(&hex[i * 2..i * 2 + 2], 16)
+                .expect("key was already checked to be hexadecimal");
+        }
+
+        let cipher = XChaCha20Poly1305::new(Key::from_slice(&key));
+
+        // The key ID is derived from the key itself, which lets us recognize a key across restarts
+        // without ever persisting the key material.  Authenticating an empty message under a fixed
+        // nonce gives us a value that depends on the key but reveals nothing about it.
+        let tag = cipher
+            .encrypt(
+                &XNonce::default(),
+                Payload {
+                    msg: &[],
+                    aad: KEY_ID_CONTEXT,
+                },
+            )
+            .map_err(|_| EncryptionError::Encrypt)?;
+        let id = u64::from_be_bytes(
+            tag[..KEY_ID_LEN]
+                .try_into()
+                .expect("tag is always longer than key ID"),
+        );
+
+        Ok(Self { id, cipher })
+    }
+}
+
+/// The set of keys available to a disk buffer.
+///
+/// New records are always encrypted with the current key, while records written with any of the
+/// previous keys can still be decrypted.  This allows rotating keys without losing records that
+/// were buffered before the rotation.
+#[derive(Clone)]
+pub struct Keyring {
+    current: EncryptionKey,
+    previous: Vec<EncryptionKey>,
+}
+
+impl Keyring {
+    /// Creates a new `Keyring` from hex-encoded keys.
+    ///
+    /// # Errors
+    ///
+    /// If any of the keys is not a valid 256-bit hex-encoded key, an error variant will be returned.
+    pub fn from_hex<I, S>(current: &str, previous: I) -> Result<Self, EncryptionError>
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
+        Ok(Self {
+            current: EncryptionKey::from_hex(current)?,
+            previous: previous
+                .into_iter()
+                .map(|key| EncryptionKey::from_hex(key.as_ref()))
+                .collect::<Result<_, _>>()?,
+        })
+    }
+
+    /// Gets the ID of the key used to encrypt new records.
+    pub fn current_key_id(&self) -> u64 {
+        self.current.id
+    }
+
+    /// Whether or not the keyring can decrypt records encrypted with the given key.
+    pub fn contains(&self, key_id: u64) -> bool {
+        self.get(key_id).is_some()
+    }
+
+    fn get(&self, key_id: u64) -> Option<&EncryptionKey> {
+        std::iter::once(&self.current)
+            .chain(self.previous.iter())
+            .find(|key| key.id == key_id)
+    }
+
+    /// Encrypts the payload of a record with the current key, writing the envelope to `dst`.
+    ///
+    /// The record ID and metadata are authenticated alongside the payload, so an encrypted
+    /// payload cannot be swapped into a different record without being detected.
+    pub fn encrypt(
+        &self,
+        record_id: u64,
+        metadata: u32,
+        plaintext: &[u8],
+        dst: &mut Vec<u8>,
+    ) -> Result<(), EncryptionError> {
+        let mut nonce = XNonce::default();
+        rand::thread_rng().fill_bytes(&mut nonce);
+
+        let aad = associated_data(record_id, metadata);
+        let ciphertext = self
+            .current
+            .cipher
+            .encrypt(
+                &nonce,
+                Payload {
+                    msg: plaintext,
+                    aad: &aad,
+                },
+            )
+            .map_err(|_| EncryptionError::Encrypt)?;
+
+        dst.clear();
+        dst.reserve(KEY_ID_LEN + NONCE_LEN + ciphertext.len());
+        dst.extend_from_slice(&self.current.id.to_be_bytes());
+        dst.extend_from_slice(&nonce);
+        dst.extend_from_slice(&ciphertext);
+        Ok(())
+    }
+
+    /// Decrypts the payload of a record.
+    ///
+    /// # Errors
+    ///
+    /// If the key used to encrypt the record is not part of this keyring, or if the payload fails
+    /// authentication, an error variant will be returned.
+    pub fn decrypt(
+        &self,
+        record_id: u64,
+        metadata: u32,
+        envelope: &[u8],
+    ) -> Result<Vec<u8>, EncryptionError> {
+        if envelope.len() < ENCRYPTION_OVERHEAD {
+            return Err(EncryptionError::Truncated {
+                len: envelope.len(),
+            });
+        }
+
+        let (key_id, rest) = envelope.split_at(KEY_ID_LEN);
+        let (nonce, ciphertext) = rest.split_at(NONCE_LEN);
+        let key_id = u64::from_be_bytes(key_id.try_into().expect("length already checked"));
+        let key = self
+            .get(key_id)
+            .ok_or(EncryptionError::UnknownKey { key_id })?;
+
+        let aad = associated_data(record_id, metadata);
+        key.cipher
+            .decrypt(
+                XNonce::from_slice(nonce),
+                Payload {
+                    msg: ciphertext,
+                    aad: &aad,
+                },
+            )
+            .map_err(|_| EncryptionError::Decrypt)
+    }
+}
+
+impl fmt::Debug for Keyring {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Keyring")
+            .field("current", &format_args!("{:016x}", self.current.id))
+            .field("previous", &self.previous.len())
+            .finish()
+    }
+}
+
+fn associated_data(record_id: u64, metadata: u32) -> [u8; 12] {
+    let mut aad = [0u8; 12];
+    aad[..8].copy_from_slice(&record_id.to_be_bytes());
+    aad[8..].copy_from_slice(&metadata.to_be_bytes());
+    aad
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const KEY_A: &str = "000102030405060708090a0b0c0d0e0f101112131415161718191a1b1c1d1e1f";
+    const KEY_B: &str = "1f1e1d1c1b1a191817161514131211100f0e0d0c0b0a09080706050403020100";
+
+    fn keyring(current: &str, previous: &[&str]) -> Keyring {
+        Keyring::from_hex(current, previous).expect("keys should be valid")
+    }
+
+    #[test]
+    fn round_trip() {
+        let keyring = keyring(KEY_A, &[]);
+        let mut envelope = Vec::new();
+        keyring
+            .encrypt(42, 1, b"hello world", &mut envelope)
+            .unwrap();
+
+        assert_eq!(envelope.len(), b"hello world".len() + ENCRYPTION_OVERHEAD);
+        assert!(!envelope
+            .windows(b"hello world".len())
+            .any(|window| window == b"hello world"));
+        assert_eq!(keyring.decrypt(42, 1, &envelope).unwrap(), b"hello world");
+    }
+
+    #[test]
+    fn key_ids_are_stable() {
+        assert_eq!(
+            keyring(KEY_A, &[]).current_key_id(),
+            keyring(KEY_A, &[KEY_B]).current_key_id()
+        );
+        assert_ne!(
+            keyring(KEY_A, &[]).current_key_id(),
+            keyring(KEY_B, &[]).current_key_id()
+        );
+    }
+
+    #[test]
+    fn detects_tampering() {
+        let keyring = keyring(KEY_A, &[]);
+        let mut envelope = Vec::new();
+        keyring
+            .encrypt(42, 1, b"hello world", &mut envelope)
+            .unwrap();
+
+        // Payload moved to a different record, or with different metadata.
+        assert!(matches!(
+            keyring.decrypt(43, 1, &envelope),
+            Err(EncryptionError::Decrypt)
+        ));
+        assert!(matches!(
+            keyring.decrypt(42, 0, &envelope),
+            Err(EncryptionError::Decrypt)
+        ));
+
+        // Flipped bit in the ciphertext.
+        let last = envelope.len() - 1;
+        envelope[last] ^= 0x01;
+        assert!(matches!(
+            keyring.decrypt(42, 1, &envelope),
+            Err(EncryptionError::Decrypt)
+        ));
+
+        assert!(matches!(
+            keyring.decrypt(42, 1, &envelope[..10]),
+            Err(EncryptionError::Truncated { len: 10 })
+        ));
+    }
+
+    #[test]
+    fn decrypts_with_previous_keys() {
+        let old = keyring(KEY_A, &[]);
+        let mut envelope = Vec::new();
+        old.encrypt(7, 0, b"before rotation", &mut envelope)
+            .unwrap();
+
+        let rotated = keyring(KEY_B, &[KEY_A]);
+        assert!(rotated.contains(old.current_key_id()));
+        assert_eq!(
+            rotated.decrypt(7, 0, &envelope).unwrap(),
+            b"before rotation"
+        );
+
+        let forgotten = keyring(KEY_B, &[]);
+        let key_id = old.current_key_id();
+        assert!(matches!(
+            forgotten.decrypt(7, 0, &envelope),
+            Err(EncryptionError::UnknownKey { key_id: id }) if id == key_id
+        ));
+    }
+
+    #[test]
+    fn rejects_invalid_keys() {
+        assert!(Keyring::from_hex("abcd", Vec::<&str>::new()).is_err());
+        assert!(Keyring::from_hex(&"zz".repeat(32), Vec::<&str>::new()).is_err());
+        assert!(Keyring::from_hex(KEY_A, ["not a key"]).is_err());
+    }
+}
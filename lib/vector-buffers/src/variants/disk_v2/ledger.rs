@@ -70,6 +70,17 @@ pub enum LedgerLoadCreateError {
     /// buffers required for the serialization step.
     #[snafu(display("failed to serialize ledger to buffer: {}", reason))]
     FailedToSerialize { reason: String },
+
+    /// The buffer contains records encrypted with a key that is not configured.
+    ///
+    /// The identity of every key used to encrypt records is tracked alongside the ledger, and keys
+    /// may only be dropped from the configuration once the buffer no longer holds any records
+    /// encrypted with them.  Otherwise, those records could not be read back.
+    #[snafu(display(
+        "buffer contains records encrypted with key {:016x}, which is not configured",
+        key_id
+    ))]
+    MissingEncryptionKey { key_id: u64 },
 }
 
 /// Ledger state.
@@ -652,6 +663,7 @@ where
             usage_handle,
         };
         ledger.update_buffer_size().await?;
+        ledger.update_encryption_keys().await?;
 
         Ok(ledger)
     }
@@ -703,6 +715,71 @@ where
         Ok(())
     }
 
+    async fn update_encryption_keys(&self) -> Result<(), LedgerLoadCreateError> {
+        // We track the IDs of every key that records in this buffer may have been encrypted with.
+        // This lets us refuse to load the buffer when a key that is still needed was removed from
+        // the configuration, rather than failing to decrypt records one by one later on.
+        //
+        // Key IDs are only ever removed once the buffer has been fully drained, as we don't track
+        // which data files were written with which key.
+        let keys_path = self.config.data_dir.join("buffer.keys");
+        let mut key_ids = match fs::read_to_string(&keys_path).await {
+            Ok(contents) => contents
+                .lines()
+                .map(str::trim)
+                .filter(|line| !line.is_empty())
+                .map(|line| {
+                    u64::from_str_radix(line, 16).map_err(|_| {
+                        LedgerLoadCreateError::FailedToDeserialize {
+                            reason: format!("invalid key ID in buffer.keys: {}", line),
+                        }
+                    })
+                })
+                .collect::<Result<Vec<_>, _>>()?,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Vec::new(),
+            Err(e) => return Err(LedgerLoadCreateError::Io { source: e }),
+        };
+
+        if self.get_total_buffer_size() == 0 {
+            key_ids.clear();
+        }
+
+        for key_id in &key_ids {
+            if !self
+                .config
+                .keyring
+                .as_ref()
+                .map_or(false, |keyring| keyring.contains(*key_id))
+            {
+                return Err(LedgerLoadCreateError::MissingEncryptionKey { key_id: *key_id });
+            }
+        }
+
+        match self.config.keyring.as_ref() {
+            Some(keyring) => {
+                let current_key_id = keyring.current_key_id();
+                if !key_ids.contains(&current_key_id) {
+                    key_ids.push(current_key_id);
+                }
+
+                let contents = key_ids
+                    .iter()
+                    .map(|key_id| format!("{:016x}\n", key_id))
+                    .collect::<String>();
+                let tmp_path = self.config.data_dir.join("buffer.keys.tmp");
+                fs::write(&tmp_path, contents).await.context(IoSnafu)?;
+                fs::rename(&tmp_path, &keys_path).await.context(IoSnafu)?;
+            }
+            None => match fs::remove_file(&keys_path).await {
+                Ok(()) => {}
+                Err(e) if e.kind() == io::ErrorKind::NotFound => {}
+                Err(e) => return Err(LedgerLoadCreateError::Io { source: e }),
+            },
+        }
+
+        Ok(())
+    }
+
     #[must_use]
     pub(super) fn spawn_finalizer(self: Arc<Self>) -> OrderedFinalizer<u64> {
         let (finalizer, mut stream) = OrderedFinalizer::new(None);
@@ -184,6 +184,7 @@ use vector_common::finalization::Finalizable;
 
 mod backed_archive;
 mod common;
+mod encryption;
 mod io;
 mod ledger;
 mod reader;
@@ -197,6 +198,7 @@ mod tests;
 use self::ledger::Ledger;
 pub use self::{
     common::{DiskBufferConfig, DiskBufferConfigBuilder},
+    encryption::{EncryptionError, Keyring},
     io::{Filesystem, ProductionFilesystem},
     ledger::LedgerLoadCreateError,
     reader::{Reader, ReaderError},
@@ -301,14 +303,21 @@ pub struct DiskV2Buffer {
     id: String,
     data_dir: PathBuf,
     max_size: NonZeroU64,
+    keyring: Option<Keyring>,
 }
 
 impl DiskV2Buffer {
-    pub fn new(id: String, data_dir: PathBuf, max_size: NonZeroU64) -> Self {
+    pub fn new(
+        id: String,
+        data_dir: PathBuf,
+        max_size: NonZeroU64,
+        keyring: Option<Keyring>,
+    ) -> Self {
         Self {
             id,
             data_dir,
             max_size,
+            keyring,
         }
     }
 }
@@ -331,6 +340,7 @@ where
             &self.data_dir,
             self.id.as_str(),
             self.max_size,
+            self.keyring,
         )
         .await?;
 
@@ -343,6 +353,7 @@ async fn build_disk_v2_buffer<T>(
     data_dir: &Path,
     id: &str,
     max_size: NonZeroU64,
+    keyring: Option<Keyring>,
 ) -> Result<
     (
         Writer<T, ProductionFilesystem>,
@@ -356,9 +367,12 @@ where
     usage_handle.set_buffer_limits(Some(max_size.get()), None);
 
     let buffer_path = get_disk_v2_data_dir_path(data_dir, id);
-    let config = DiskBufferConfigBuilder::from_path(buffer_path)
-        .max_buffer_size(max_size.get())
-        .build()?;
+    let mut builder =
+        DiskBufferConfigBuilder::from_path(buffer_path).max_buffer_size(max_size.get());
+    if let Some(keyring) = keyring {
+        builder = builder.encryption(keyring);
+    }
+    let config = builder.build()?;
     Buffer::from_config(config, usage_handle)
         .await
         .map_err(Into::into)
@@ -15,6 +15,7 @@ use vector_common::{finalization::BatchNotifier, finalizer::OrderedFinalizer};
 
 use super::{
     common::create_crc32c_hasher,
+    encryption::{EncryptionError, Keyring, ENCRYPTED_RECORD_FLAG},
     ledger::Ledger,
     record::{validate_record_archive, ArchivedRecord, Record, RecordStatus},
     Filesystem,
@@ -108,6 +109,14 @@ where
     #[snafu(display("record version not compatible: {}", reason))]
     Incompatible { reason: String },
 
+    /// The reader failed to decrypt the record.
+    ///
+    /// As the checksum was validated, this generally indicates that the record was encrypted with
+    /// a key that is no longer configured, or that the data file was deliberately modified in a
+    /// way that kept the checksum intact.
+    #[snafu(display("failed to decrypt record: {}", source))]
+    Decryption { source: EncryptionError },
+
     /// The reader detected that a data file contains a partially-written record.
     ///
     /// Records should never be partially written to a data file (we don't split records across data
@@ -145,6 +154,7 @@ where
             ReaderError::Checksum { .. } => "checksum_mismatch",
             ReaderError::Decode { .. } => "decode_failed",
             ReaderError::Incompatible { .. } => "incompatible_record_version",
+            ReaderError::Decryption { .. } => "decryption_failed",
             ReaderError::PartialWrite => "partial_write",
             ReaderError::EmptyRecord => "empty_record",
         }
@@ -160,6 +170,7 @@ where
             | ReaderError::Checksum { .. }
             | ReaderError::Decode { .. }
             | ReaderError::Incompatible { .. }
+            | ReaderError::Decryption { .. }
             | ReaderError::PartialWrite => Some(BufferReadError { error_code, error }),
         }
     }
@@ -199,6 +210,7 @@ pub(super) struct RecordReader<R, T> {
     reader: BufReader<R>,
     aligned_buf: AlignedVec,
     checksummer: Hasher,
+    keyring: Option<Arc<Keyring>>,
     current_record_id: u64,
     _t: PhantomData<T>,
 }
@@ -212,11 +224,12 @@ where
     ///
     /// Internally, the reader is wrapped in a [`BufReader`], so callers should not pass in an
     /// already buffered reader.
-    pub fn new(reader: R) -> Self {
+    pub fn new(reader: R, keyring: Option<Arc<Keyring>>) -> Self {
         Self {
             reader: BufReader::with_capacity(256 * 1024, reader),
             aligned_buf: AlignedVec::new(),
             checksummer: create_crc32c_hasher(),
+            keyring,
             current_record_id: 0,
             _t: PhantomData,
         }
@@ -374,7 +387,7 @@ where
         // - `try_next_record` does all the archive checks, checksum validation, etc
         let record = unsafe { archived_root::<Record<'_>>(&self.aligned_buf) };
 
-        decode_record_payload(record)
+        decode_record_payload(record, self.keyring.as_deref())
     }
 }
 
@@ -785,7 +798,10 @@ where
                 "Opened data file for reading."
             );
 
-            self.reader = Some(RecordReader::new(data_file));
+            self.reader = Some(RecordReader::new(
+                data_file,
+                self.ledger.config().keyring.clone(),
+            ));
             return Ok(());
         }
     }
@@ -851,7 +867,7 @@ where
                     let record = try_as_record_archive(data_file_mmap.as_ref())
                         .expect("record was already validated");
 
-                    let Ok(item) = decode_record_payload::<T>(record) else {
+                    let Ok(item) = decode_record_payload::<T>(record, self.ledger.config().keyring.as_deref()) else {
                         // If there's an error decoding the item, just fall back to the slow path,
                         // because this file might actually be where we left off, so we don't want
                         // to incorrectly skip ahead or anything.
@@ -1131,12 +1147,20 @@ where
 
 pub(crate) fn decode_record_payload<T: Bufferable>(
     record: &ArchivedRecord<'_>,
+    keyring: Option<&Keyring>,
 ) -> Result<T, ReaderError<T>> {
+    // The high bit of the record metadata is reserved by the buffer to mark encrypted records, so
+    // strip it before handing the metadata to `T`.
+    let raw_metadata = record.metadata();
+    let is_encrypted = raw_metadata & ENCRYPTED_RECORD_FLAG != 0;
+
     // Try and convert the raw record metadata into the true metadata type used by `T`, and then
     // also verify that `T` is able to decode records with the metadata used for this record in particular.
-    let metadata = T::Metadata::from_u32(record.metadata()).ok_or(ReaderError::Incompatible {
-        reason: format!("invalid metadata for {}", std::any::type_name::<T>()),
-    })?;
+    let metadata = T::Metadata::from_u32(raw_metadata & !ENCRYPTED_RECORD_FLAG).ok_or(
+        ReaderError::Incompatible {
+            reason: format!("invalid metadata for {}", std::any::type_name::<T>()),
+        },
+    )?;
 
     if !T::can_decode(metadata) {
         return Err(ReaderError::Incompatible {
@@ -1147,6 +1171,17 @@ pub(crate) fn decode_record_payload<T: Bufferable>(
         });
     }
 
-    // Now we can finally try decoding.
-    T::decode(metadata, record.payload()).context(DecodeSnafu)
+    // Records written before encryption was enabled are still readable as-is, but encrypted records
+    // can only be read if the key they were encrypted with is still available.
+    if is_encrypted {
+        let payload = keyring
+            .ok_or(EncryptionError::NotConfigured)
+            .and_then(|keyring| keyring.decrypt(record.id(), raw_metadata, record.payload()))
+            .context(DecryptionSnafu)?;
+
+        T::decode(metadata, &payload[..]).context(DecodeSnafu)
+    } else {
+        // Now we can finally try decoding.
+        T::decode(metadata, record.payload()).context(DecodeSnafu)
+    }
 }
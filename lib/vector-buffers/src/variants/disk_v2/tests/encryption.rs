@@ -0,0 +1,157 @@
+use tracing::Instrument;
+
+use crate::{
+    test::{acknowledge, install_tracing_helpers, with_temp_dir, SizedRecord},
+    variants::disk_v2::{
+        tests::create_buffer_v2_with_keyring, BufferError, Keyring, LedgerLoadCreateError,
+    },
+};
+
+const KEY_A: &str = "000102030405060708090a0b0c0d0e0f101112131415161718191a1b1c1d1e1f";
+const KEY_B: &str = "1f1e1d1c1b1a191817161514131211100f0e0d0c0b0a09080706050403020100";
+
+fn keyring(current: &str, previous: &[&str]) -> Option<Keyring> {
+    Some(Keyring::from_hex(current, previous).expect("keys should be valid"))
+}
+
+#[tokio::test]
+async fn encrypted_records_roundtrip() {
+    let _a = install_tracing_helpers();
+
+    let fut = with_temp_dir(|dir| {
+        let data_dir = dir.to_path_buf();
+
+        async move {
+            let (mut writer, mut reader, _ledger) =
+                create_buffer_v2_with_keyring(data_dir, keyring(KEY_A, &[]))
+                    .await
+                    .expect("should not fail to create buffer");
+
+            writer
+                .write_record(SizedRecord::new(64))
+                .await
+                .expect("should not fail to write");
+            writer.flush().await.expect("flush should not fail");
+            writer.close();
+
+            let read = reader
+                .next()
+                .await
+                .expect("should not fail to read record")
+                .expect("should contain record");
+            assert_eq!(SizedRecord::new(64), read);
+            acknowledge(read).await;
+        }
+    });
+
+    let parent = trace_span!("encrypted_records_roundtrip");
+    fut.instrument(parent.or_current()).await;
+}
+
+#[tokio::test]
+async fn rotated_keys_read_existing_records() {
+    let _a = install_tracing_helpers();
+
+    let fut = with_temp_dir(|dir| {
+        let data_dir = dir.to_path_buf();
+
+        async move {
+            // Write a record with the original key, and close the buffer without reading it.
+            let (mut writer, reader, ledger) =
+                create_buffer_v2_with_keyring(data_dir.clone(), keyring(KEY_A, &[]))
+                    .await
+                    .expect("should not fail to create buffer");
+            writer
+                .write_record(SizedRecord::new(64))
+                .await
+                .expect("should not fail to write");
+            writer.flush().await.expect("flush should not fail");
+            writer.close();
+            ledger.flush().expect("should not fail to flush ledger");
+            drop(reader);
+            drop(writer);
+            drop(ledger);
+
+            // Reopen the buffer with a new current key, keeping the original key as a previous key.
+            let (mut writer, mut reader, ledger) =
+                create_buffer_v2_with_keyring(data_dir.clone(), keyring(KEY_B, &[KEY_A]))
+                    .await
+                    .expect("should not fail to reopen buffer with rotated key");
+            writer
+                .write_record(SizedRecord::new(32))
+                .await
+                .expect("should not fail to write");
+            writer.flush().await.expect("flush should not fail");
+            writer.close();
+
+            for expected in [SizedRecord::new(64), SizedRecord::new(32)] {
+                let read = reader
+                    .next()
+                    .await
+                    .expect("should not fail to read record")
+                    .expect("should contain record");
+                assert_eq!(expected, read);
+                acknowledge(read).await;
+            }
+
+            ledger.flush().expect("should not fail to flush ledger");
+        }
+    });
+
+    let parent = trace_span!("rotated_keys_read_existing_records");
+    fut.instrument(parent.or_current()).await;
+}
+
+#[tokio::test]
+async fn missing_key_fails_to_load() {
+    let _a = install_tracing_helpers();
+
+    let fut = with_temp_dir(|dir| {
+        let data_dir = dir.to_path_buf();
+
+        async move {
+            let original = keyring(KEY_A, &[]);
+            let original_key_id = original.as_ref().unwrap().current_key_id();
+
+            let (mut writer, reader, ledger) =
+                create_buffer_v2_with_keyring(data_dir.clone(), original)
+                    .await
+                    .expect("should not fail to create buffer");
+            writer
+                .write_record(SizedRecord::new(64))
+                .await
+                .expect("should not fail to write");
+            writer.flush().await.expect("flush should not fail");
+            writer.close();
+            ledger.flush().expect("should not fail to flush ledger");
+            drop(reader);
+            drop(writer);
+            drop(ledger);
+
+            // Dropping the original key while its records are still buffered must be refused.
+            let result = create_buffer_v2_with_keyring::<_, SizedRecord>(
+                data_dir.clone(),
+                keyring(KEY_B, &[]),
+            )
+            .await;
+            assert!(matches!(
+                result,
+                Err(BufferError::LedgerError {
+                    source: LedgerLoadCreateError::MissingEncryptionKey { key_id }
+                }) if key_id == original_key_id
+            ));
+
+            // Likewise when encryption is disabled entirely.
+            let result = create_buffer_v2_with_keyring::<_, SizedRecord>(data_dir, None).await;
+            assert!(matches!(
+                result,
+                Err(BufferError::LedgerError {
+                    source: LedgerLoadCreateError::MissingEncryptionKey { .. }
+                })
+            ));
+        }
+    });
+
+    let parent = trace_span!("missing_key_fails_to_load");
+    fut.instrument(parent.or_current()).await;
+}
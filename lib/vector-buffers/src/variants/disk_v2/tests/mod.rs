@@ -14,7 +14,7 @@ use super::{
     io::{AsyncFile, Metadata, ProductionFilesystem, ReadableMemoryMap, WritableMemoryMap},
     ledger::LEDGER_LEN,
     record::RECORD_HEADER_LEN,
-    Buffer, DiskBufferConfigBuilder, Filesystem, Ledger, Reader, Writer,
+    Buffer, BufferError, DiskBufferConfigBuilder, Filesystem, Keyring, Ledger, Reader, Writer,
 };
 use crate::{
     buffer_usage_data::BufferUsageHandle, encoding::FixedEncodable,
@@ -25,6 +25,7 @@ type FilesystemUnderTest = ProductionFilesystem;
 
 mod acknowledgements;
 mod basic;
+mod encryption;
 mod initialization;
 mod invariants;
 mod known_errors;
@@ -339,6 +340,34 @@ where
         .expect("should not fail to create buffer")
 }
 
+/// Creates a disk v2 buffer that encrypts records with the given keyring, if any.
+///
+/// As loading an encrypted buffer can fail when keys are missing, the result is returned as-is.
+pub(crate) async fn create_buffer_v2_with_keyring<P, R>(
+    data_dir: P,
+    keyring: Option<Keyring>,
+) -> Result<
+    (
+        Writer<R, FilesystemUnderTest>,
+        Reader<R, FilesystemUnderTest>,
+        Arc<Ledger<FilesystemUnderTest>>,
+    ),
+    BufferError<R>,
+>
+where
+    P: AsRef<Path>,
+    R: Bufferable,
+{
+    let mut builder = DiskBufferConfigBuilder::from_path(data_dir);
+    if let Some(keyring) = keyring {
+        builder = builder.encryption(keyring);
+    }
+    let config = builder.build().expect("creating buffer should not fail");
+    let usage_handle = BufferUsageHandle::noop();
+
+    Buffer::from_config_inner(config, usage_handle).await
+}
+
 /// Creates a disk v2 buffer with the specified write buffer size.
 pub(crate) async fn create_buffer_v2_with_write_buffer_size<P, R>(
     data_dir: P,
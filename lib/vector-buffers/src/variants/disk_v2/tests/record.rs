@@ -1,8 +1,8 @@
-use std::io::Cursor;
+use std::{io::Cursor, sync::Arc};
 
 use crate::{
     test::SizedRecord,
-    variants::disk_v2::{reader::RecordReader, writer::RecordWriter},
+    variants::disk_v2::{reader::RecordReader, writer::RecordWriter, Keyring, ReaderError},
 };
 
 #[tokio::test]
@@ -10,8 +10,8 @@ async fn roundtrip_through_record_writer_and_record_reader() {
     // Create a duplex stream that's more than big enough to ship a record through.
     let (writer_io, reader_io) = tokio::io::duplex(4096);
 
-    let mut record_writer = RecordWriter::new(writer_io, 0, 16_384, u64::MAX, 2048);
-    let mut record_reader = RecordReader::new(reader_io);
+    let mut record_writer = RecordWriter::new(writer_io, 0, 16_384, u64::MAX, 2048, None);
+    let mut record_reader = RecordReader::new(reader_io, None);
 
     let record = SizedRecord::new(73);
 
@@ -42,10 +42,90 @@ async fn roundtrip_through_record_writer_and_record_reader() {
 async fn record_reader_always_returns_none_when_no_data() {
     let reader_io = Cursor::new(Vec::new());
 
-    let mut record_reader = RecordReader::<_, SizedRecord>::new(reader_io);
+    let mut record_reader = RecordReader::<_, SizedRecord>::new(reader_io, None);
     let read_token = record_reader
         .try_next_record(false)
         .await
         .expect("read should not fail");
     assert!(read_token.is_none());
 }
+
+#[tokio::test]
+async fn roundtrip_encrypted_record_through_record_writer_and_record_reader() {
+    let keyring = Arc::new(
+        Keyring::from_hex(
+            "000102030405060708090a0b0c0d0e0f101112131415161718191a1b1c1d1e1f",
+            Vec::<&str>::new(),
+        )
+        .expect("key should be valid"),
+    );
+
+    let (writer_io, reader_io) = tokio::io::duplex(4096);
+
+    let mut record_writer = RecordWriter::new(
+        writer_io,
+        0,
+        16_384,
+        u64::MAX,
+        2048,
+        Some(Arc::clone(&keyring)),
+    );
+    let mut record_reader = RecordReader::new(reader_io, Some(keyring));
+
+    let record = SizedRecord::new(73);
+
+    let (bytes_written, _) = record_writer
+        .write_record(314, record.clone())
+        .await
+        .expect("write should not fail");
+    record_writer.flush().await.expect("flush should not fail");
+
+    let read_token = record_reader
+        .try_next_record(false)
+        .await
+        .expect("read should not fail")
+        .expect("record should be present");
+    assert_eq!(bytes_written, read_token.record_bytes());
+
+    let roundtrip_record = record_reader
+        .read_record(read_token)
+        .expect("read should not fail");
+    assert_eq!(record, roundtrip_record);
+}
+
+#[tokio::test]
+async fn encrypted_record_requires_keyring() {
+    let keyring = Keyring::from_hex(
+        "000102030405060708090a0b0c0d0e0f101112131415161718191a1b1c1d1e1f",
+        Vec::<&str>::new(),
+    )
+    .expect("key should be valid");
+
+    let (writer_io, reader_io) = tokio::io::duplex(4096);
+
+    let mut record_writer = RecordWriter::new(
+        writer_io,
+        0,
+        16_384,
+        u64::MAX,
+        2048,
+        Some(Arc::new(keyring)),
+    );
+    let mut record_reader = RecordReader::<_, SizedRecord>::new(reader_io, None);
+
+    record_writer
+        .write_record(314, SizedRecord::new(73))
+        .await
+        .expect("write should not fail");
+    record_writer.flush().await.expect("flush should not fail");
+
+    let read_token = record_reader
+        .try_next_record(false)
+        .await
+        .expect("read should not fail")
+        .expect("record should be present");
+    assert!(matches!(
+        record_reader.read_record(read_token),
+        Err(ReaderError::Decryption { .. })
+    ));
+}
@@ -25,6 +25,7 @@ use tokio::io::{AsyncWrite, AsyncWriteExt};
 
 use super::{
     common::{create_crc32c_hasher, DiskBufferConfig},
+    encryption::{EncryptionError, Keyring, ENCRYPTED_RECORD_FLAG, ENCRYPTION_OVERHEAD},
     io::Filesystem,
     ledger::Ledger,
     record::{validate_record_archive, Record, RecordStatus},
@@ -95,6 +96,13 @@ where
         source: <T as Encodable>::EncodeError,
     },
 
+    /// The writer failed to encrypt the encoded record.
+    ///
+    /// This should only occur if the system is unable to allocate enough memory for the encrypted
+    /// form of the record.
+    #[snafu(display("failed to encrypt record: {}", source))]
+    FailedToEncrypt { source: EncryptionError },
+
     /// The writer failed to serialize the record.
     ///
     /// As records are encoded and then wrapped in a container which carries metadata about the size
@@ -360,6 +368,8 @@ impl<W: fmt::Debug> fmt::Debug for TrackingBufWriter<W> {
 pub(super) struct RecordWriter<W, T> {
     writer: TrackingBufWriter<W>,
     encode_buf: Vec<u8>,
+    encrypt_buf: Vec<u8>,
+    keyring: Option<Arc<Keyring>>,
     ser_buf: AlignedVec,
     ser_scratch: AlignedVec,
     checksummer: Hasher,
@@ -384,6 +394,7 @@ where
         write_buffer_size: usize,
         max_data_file_size: u64,
         max_record_size: usize,
+        keyring: Option<Arc<Keyring>>,
     ) -> Self {
         // These should also be getting checked at a higher level, but we're double-checking them here to be absolutely sure.
         let max_record_size_converted = u64::try_from(max_record_size)
@@ -405,11 +416,21 @@ where
         // This could lead to us reducing the encode buffer size limit by slightly more than necessary, since
         // `RECORD_HEADER_LEN` might be overaligned compared to what it would be necessary when we look at the
         // encoded/serialized record... but that's OK, but it's only going to differ by 8 bytes at most.
+        //
+        // Likewise, encrypted records carry the key ID, nonce and authentication tag alongside the
+        // encoded record, so we account for those as well.
         let max_record_size = max_record_size - RECORD_HEADER_LEN;
+        let max_record_size = if keyring.is_some() {
+            max_record_size.saturating_sub(ENCRYPTION_OVERHEAD)
+        } else {
+            max_record_size
+        };
 
         Self {
             writer: TrackingBufWriter::with_capacity(write_buffer_size, writer),
             encode_buf: Vec::with_capacity(16_384),
+            encrypt_buf: Vec::new(),
+            keyring,
             ser_buf: AlignedVec::with_capacity(16_384),
             ser_scratch: AlignedVec::with_capacity(16_384),
             checksummer: create_crc32c_hasher(),
@@ -480,9 +501,20 @@ where
             });
         }
 
-        let metadata = T::get_metadata().into_u32();
-        let wrapped_record =
-            Record::with_checksum(id, metadata, &self.encode_buf, &self.checksummer);
+        // If encryption is enabled, the encoded record is encrypted and marked as such in the
+        // record metadata, which is authenticated along with the record ID.
+        let mut metadata = T::get_metadata().into_u32();
+        let payload = match self.keyring.as_ref() {
+            Some(keyring) => {
+                metadata |= ENCRYPTED_RECORD_FLAG;
+                keyring
+                    .encrypt(id, metadata, &self.encode_buf, &mut self.encrypt_buf)
+                    .context(FailedToEncryptSnafu)?;
+                &self.encrypt_buf
+            }
+            None => &self.encode_buf,
+        };
+        let wrapped_record = Record::with_checksum(id, metadata, payload, &self.checksummer);
 
         // Push 8 dummy bytes where our length delimiter will sit.  We'll fix this up after
         // serialization.  Notably, `AlignedSerializer` will report the serializer position as
@@ -882,11 +914,11 @@ where
                 // next writer record ID should be.
                 let record = try_as_record_archive(data_file_mmap.as_ref())
                     .expect("record was already validated");
-                let item = decode_record_payload::<T>(record).map_err(|e| {
-                    WriterError::FailedToValidate {
-                        reason: e.to_string(),
-                    }
-                })?;
+                let item =
+                    decode_record_payload::<T>(record, self.ledger.config().keyring.as_deref())
+                        .map_err(|e| WriterError::FailedToValidate {
+                            reason: e.to_string(),
+                        })?;
 
                 // Since we have a valid record, checksum and all, see if the writer record ID
                 // in the ledger lines up with the record ID we have here.  Specifically, the record
@@ -1126,6 +1158,7 @@ where
                     self.config.write_buffer_size,
                     self.config.max_data_file_size,
                     self.config.max_record_size,
+                    self.config.keyring.clone(),
                 ));
                 self.data_file_size = data_file_size;
 
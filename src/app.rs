@@ -454,13 +454,20 @@ pub async fn load_configs(
     let config_paths = config::process_paths(config_paths).ok_or(exitcode::CONFIG)?;
 
     if watch_config {
-        // Start listening for config changes immediately.
-        config::watcher::spawn_thread(config_paths.iter().map(Into::into), None).map_err(
-            |error| {
-                error!(message = "Unable to start config watcher.", %error);
-                exitcode::CONFIG
-            },
-        )?;
+        // Start listening for config changes immediately, including changes to any files that
+        // secrets are read from.
+        let secret_paths = config::load_secret_backend_paths(&config_paths);
+        config::watcher::spawn_thread(
+            config_paths
+                .iter()
+                .map(Into::into)
+                .chain(secret_paths.iter()),
+            None,
+        )
+        .map_err(|error| {
+            error!(message = "Unable to start config watcher.", %error);
+            exitcode::CONFIG
+        })?;
     }
 
     info!(
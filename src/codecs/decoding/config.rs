@@ -50,4 +50,18 @@ impl DecodingConfig {
 
         Ok(Decoder::new(framer, deserializer).with_log_namespace(self.log_namespace))
     }
+
+    /// Builds a `Decoder` for a stream-based source from the provided configuration.
+    ///
+    /// Framings that only emit frames once their input ends, such as `chunked_gelf`, are rejected
+    /// since they would buffer the stream without limit.
+    pub fn build_stream(&self) -> vector_common::Result<Decoder> {
+        if matches!(self.framing, FramingConfig::ChunkedGelf(_)) {
+            return Err(
+                "The `chunked_gelf` framing is only supported by message-based sources.".into(),
+            );
+        }
+
+        self.build()
+    }
 }
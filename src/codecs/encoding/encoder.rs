@@ -1,6 +1,6 @@
 use bytes::BytesMut;
 use codecs::{
-    encoding::{Error, Framer, Serializer},
+    encoding::{BatchSerializer, Error, Framer, Serializer},
     CharacterDelimitedEncoder, NewlineDelimitedEncoder, TextSerializerConfig,
 };
 use tokio_util::codec::Encoder as _;
@@ -10,6 +10,74 @@ use crate::{
     internal_events::{EncoderFramingError, EncoderSerializeError},
 };
 
+#[derive(Debug, Clone)]
+/// An encoder that can encode batches of structured events into a single payload.
+pub struct BatchEncoder {
+    serializer: BatchSerializer,
+}
+
+impl BatchEncoder {
+    /// Creates a new `BatchEncoder` with the specified `BatchSerializer` to produce bytes from a
+    /// batch of structured events.
+    pub const fn new(serializer: BatchSerializer) -> Self {
+        Self { serializer }
+    }
+
+    /// Get the serializer.
+    pub const fn serializer(&self) -> &BatchSerializer {
+        &self.serializer
+    }
+
+    /// Get the HTTP content type.
+    pub const fn content_type(&self) -> &'static str {
+        match self.serializer {
+            BatchSerializer::Parquet(_) => "application/vnd.apache.parquet",
+        }
+    }
+
+    /// Get the extension of the files produced by this encoder.
+    pub const fn extension(&self) -> &'static str {
+        match self.serializer {
+            BatchSerializer::Parquet(_) => "parquet",
+        }
+    }
+
+    /// Encodes a batch of events, without requiring mutable access to the encoder.
+    pub fn encode_batch(&self, events: Vec<Event>, buffer: &mut BytesMut) -> Result<(), Error> {
+        self.serializer.serialize(events, buffer).map_err(|error| {
+            emit!(EncoderSerializeError { error: &error });
+            Error::SerializingError(error)
+        })
+    }
+}
+
+impl tokio_util::codec::Encoder<Vec<Event>> for BatchEncoder {
+    type Error = Error;
+
+    fn encode(&mut self, events: Vec<Event>, buffer: &mut BytesMut) -> Result<(), Self::Error> {
+        self.encode_batch(events, buffer)
+    }
+}
+
+/// An encoder that either encodes each event as a byte frame, or whole batches of events at once.
+#[derive(Debug, Clone)]
+pub enum EncoderKind {
+    /// Encodes each event with a framed `Encoder`.
+    Framed(Box<Encoder<Framer>>),
+    /// Encodes whole batches of events with a `BatchEncoder`.
+    Batch(BatchEncoder),
+}
+
+impl EncoderKind {
+    /// Get the HTTP content type.
+    pub fn content_type(&self) -> &'static str {
+        match self {
+            EncoderKind::Framed(encoder) => encoder.content_type(),
+            EncoderKind::Batch(encoder) => encoder.content_type(),
+        }
+    }
+}
+
 #[derive(Debug, Clone)]
 /// An encoder that can encode structured events into byte frames.
 pub struct Encoder<Framer>
@@ -113,15 +181,18 @@ impl Encoder<Framer> {
                 Serializer::Gelf(_) | Serializer::Json(_) | Serializer::NativeJson(_),
                 Framer::CharacterDelimited(CharacterDelimitedEncoder { delimiter: b',' }),
             ) => "application/json",
-            (Serializer::Native(_), _) => "application/octet-stream",
+            (Serializer::Native(_) | Serializer::Protobuf(_), _) => "application/octet-stream",
             (
                 Serializer::Avro(_)
+                | Serializer::Cef(_)
                 | Serializer::Csv(_)
                 | Serializer::Gelf(_)
                 | Serializer::Json(_)
+                | Serializer::Leef(_)
                 | Serializer::Logfmt(_)
                 | Serializer::NativeJson(_)
                 | Serializer::RawMessage(_)
+                | Serializer::Syslog(_)
                 | Serializer::Text(_),
                 _,
             ) => "text/plain",
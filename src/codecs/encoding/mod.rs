@@ -3,5 +3,5 @@ mod encoder;
 mod transformer;
 
 pub use config::{EncodingConfig, EncodingConfigWithFraming, SinkType};
-pub use encoder::Encoder;
+pub use encoder::{BatchEncoder, Encoder, EncoderKind};
 pub use transformer::{TimestampFormat, Transformer};
@@ -1,3 +1,5 @@
+//! Fluent Forward protocol messages, shared by the `fluent` source and sink.
+
 use std::{collections::BTreeMap, convert::TryInto};
 
 use chrono::{serde::ts_seconds, DateTime, TimeZone, Utc};
@@ -11,12 +13,12 @@ use vector_core::event::Value;
 /// The spec refers to 4 ways, but really CompressedPackedForward is encoded the
 /// same as PackedForward, it just has an additional decompression step.
 ///
-/// Not yet handled are the handshake messages.
+/// The handshake messages are not handled here, as only the sink supports them.
 ///
 /// <https://github.com/fluent/fluentd/wiki/Forward-Protocol-Specification-v1#event-modes>
 #[derive(Debug, Deserialize, Serialize)]
 #[serde(untagged)]
-pub(super) enum FluentMessage {
+pub(crate) enum FluentMessage {
     Message(FluentTag, FluentTimestamp, FluentRecord),
     // I attempted to just one variant for each of these, with and without options, using an
     // `Option` for the last element, but rmp expected the number of elements to match in that case
@@ -41,29 +43,46 @@ pub(super) enum FluentMessage {
 /// <https://github.com/fluent/fluentd/wiki/Forward-Protocol-Specification-v1#option>
 #[derive(Default, Debug, Deserialize, Serialize)]
 #[serde(default)]
-pub(super) struct FluentMessageOptions {
-    pub(super) size: Option<u64>, // client provided hint for the number of entries
-    pub(super) chunk: Option<String>, // client provided chunk identifier for acks
-    pub(super) compressed: Option<String>, // this one is required if present
+pub(crate) struct FluentMessageOptions {
+    pub(crate) size: Option<u64>, // client provided hint for the number of entries
+    pub(crate) chunk: Option<String>, // client provided chunk identifier for acks
+    pub(crate) compressed: Option<String>, // this one is required if present
 }
 
 /// Fluent entry consisting of timestamp and record.
 ///
 /// <https://github.com/fluent/fluentd/wiki/Forward-Protocol-Specification-v1#forward-mode>
 #[derive(Debug, Deserialize, Serialize)]
-pub(super) struct FluentEntry(pub(super) FluentTimestamp, pub(super) FluentRecord);
+pub(crate) struct FluentEntry(pub(crate) FluentTimestamp, pub(crate) FluentRecord);
 
 /// Fluent record is just key/value pairs.
-pub(super) type FluentRecord = BTreeMap<String, FluentValue>;
+pub(crate) type FluentRecord = BTreeMap<String, FluentValue>;
 
 /// Fluent message tag.
-pub(super) type FluentTag = String;
+pub(crate) type FluentTag = String;
 
 /// Custom decoder for Fluent's EventTime msgpack extension.
 ///
 /// <https://github.com/fluent/fluentd/wiki/Forward-Protocol-Specification-v1#eventtime-ext-format>
-#[derive(Clone, Debug, PartialEq, Serialize)]
-pub(super) struct FluentEventTime(DateTime<Utc>);
+#[derive(Clone, Debug, PartialEq)]
+pub(crate) struct FluentEventTime(pub(crate) DateTime<Utc>);
+
+impl Serialize for FluentEventTime {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        // Seconds beyond 2106 don't fit the extension, which is a limitation of the format itself.
+        let mut bytes = [0; 8];
+        bytes[..4].copy_from_slice(&(self.0.timestamp() as u32).to_be_bytes());
+        bytes[4..].copy_from_slice(&self.0.timestamp_subsec_nanos().to_be_bytes());
+
+        serializer.serialize_newtype_struct(
+            rmp_serde::MSGPACK_EXT_STRUCT_NAME,
+            &(0_i8, serde_bytes::Bytes::new(&bytes)),
+        )
+    }
+}
 
 impl<'de> serde::de::Deserialize<'de> for FluentEventTime {
     fn deserialize<D>(deserializer: D) -> Result<FluentEventTime, D::Error>
@@ -133,7 +152,7 @@ impl<'de> serde::de::Deserialize<'de> for FluentEventTime {
 ///
 /// Used mostly just to implement value conversion.
 #[derive(Debug, Deserialize, PartialEq, Serialize)]
-pub(super) struct FluentValue(rmpv::Value);
+pub(crate) struct FluentValue(rmpv::Value);
 
 impl From<rmpv::Value> for FluentValue {
     fn from(value: rmpv::Value) -> Self {
@@ -141,6 +160,39 @@ impl From<rmpv::Value> for FluentValue {
     }
 }
 
+impl From<Value> for FluentValue {
+    fn from(value: Value) -> Self {
+        Self(match value {
+            Value::Null => rmpv::Value::Nil,
+            Value::Boolean(b) => rmpv::Value::Boolean(b),
+            Value::Integer(i) => rmpv::Value::Integer(i.into()),
+            Value::Float(f) => rmpv::Value::F64(f.into_inner()),
+            Value::Bytes(bytes) => match String::from_utf8(bytes.to_vec()) {
+                Ok(s) => rmpv::Value::String(s.into()),
+                Err(error) => rmpv::Value::Binary(error.into_bytes()),
+            },
+            Value::Regex(regex) => rmpv::Value::String(regex.as_str().into()),
+            Value::Timestamp(timestamp) => rmpv::Value::String(
+                timestamp
+                    .to_rfc3339_opts(chrono::SecondsFormat::AutoSi, true)
+                    .into(),
+            ),
+            Value::Array(values) => rmpv::Value::Array(
+                values
+                    .into_iter()
+                    .map(|value| FluentValue::from(value).0)
+                    .collect(),
+            ),
+            Value::Object(fields) => rmpv::Value::Map(
+                fields
+                    .into_iter()
+                    .map(|(key, value)| (key.into(), FluentValue::from(value).0))
+                    .collect(),
+            ),
+        })
+    }
+}
+
 impl From<FluentValue> for Value {
     fn from(value: FluentValue) -> Self {
         match value.0 {
@@ -207,7 +259,7 @@ impl From<FluentValue> for Value {
 /// Message timestamps can be a unix timestamp or EventTime messagepack ext.
 #[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
 #[serde(untagged)]
-pub(super) enum FluentTimestamp {
+pub(crate) enum FluentTimestamp {
     #[serde(with = "ts_seconds")]
     Unix(DateTime<Utc>),
     Ext(FluentEventTime),
@@ -231,7 +283,7 @@ mod test {
     use quickcheck::quickcheck;
     use vector_core::event::Value;
 
-    use crate::sources::fluent::message::FluentValue;
+    use crate::common::fluent::FluentValue;
 
     quickcheck! {
       fn from_bool(input: bool) -> () {
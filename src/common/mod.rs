@@ -17,3 +17,9 @@ pub(crate) mod sqs;
 
 #[cfg(any(feature = "sources-aws_s3", feature = "sinks-aws_s3"))]
 pub(crate) mod s3;
+
+#[cfg(any(feature = "sources-fluent", feature = "sinks-fluent"))]
+pub(crate) mod fluent;
+
+#[cfg(any(feature = "sources-kafka", feature = "sinks-kafka"))]
+pub(crate) mod schema_registry;
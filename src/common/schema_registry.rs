@@ -0,0 +1,758 @@
+//! A schema registry client, shared by the `kafka` source and sink.
+//!
+//! Messages produced with a schema registry use the Confluent wire format: a zero magic byte,
+//! followed by the ID of the writer schema as a 32-bit big-endian integer, then the encoded data.
+//! For Protobuf, the ID is followed by the indexes of the message type in its file, as a count
+//! and the indexes themselves, all zigzag varints. The common `[0]` case is a single `0` byte.
+//!
+//! <https://docs.confluent.io/platform/current/schema-registry/fundamentals/serdes-develop/index.html#wire-format>
+
+use std::{
+    collections::HashMap,
+    sync::{Arc, Mutex},
+};
+
+use bytes::{Buf, BufMut, Bytes, BytesMut};
+use codecs::{
+    decoding::{AvroDeserializerConfig, AvroDeserializerOptions, Deserializer, DeserializerConfig},
+    encoding::SerializerConfig,
+};
+use http::{Request, StatusCode};
+use hyper::Body;
+use percent_encoding::{utf8_percent_encode, NON_ALPHANUMERIC};
+use serde::{Deserialize, Serialize};
+use snafu::{ResultExt, Snafu};
+use vector_config::configurable_component;
+
+use crate::{
+    codecs::{Decoder, DecodingConfig},
+    config::ProxyConfig,
+    http::{Auth, HttpClient, HttpError},
+    tls::{TlsConfig, TlsSettings},
+};
+
+const CONTENT_TYPE: &str = "application/vnd.schemaregistry.v1+json";
+
+const MAGIC_BYTE: u8 = 0;
+
+const HEADER_LEN: usize = 5;
+
+#[derive(Debug, Snafu)]
+pub enum SchemaRegistryError {
+    #[snafu(display("failed to build schema registry request: {}", source))]
+    BuildRequest { source: http::Error },
+    #[snafu(display("schema registry request failed: {}", source))]
+    Request { source: HttpError },
+    #[snafu(display("failed to read schema registry response: {}", source))]
+    ReadResponse { source: hyper::Error },
+    #[snafu(display("schema registry responded with {}: {}", status, body))]
+    UnexpectedStatus { status: StatusCode, body: String },
+    #[snafu(display("failed to parse schema registry response: {}", source))]
+    ParseResponse { source: serde_json::Error },
+    #[snafu(display("schema {} not found in the schema registry", id))]
+    SchemaNotFound { id: u32 },
+    #[snafu(display("subject {:?} not found in the schema registry", subject))]
+    SubjectNotFound { subject: String },
+    #[snafu(display("invalid schema {}: {}", id, message))]
+    InvalidSchema { id: u32, message: String },
+    #[snafu(display("message has no schema registry header"))]
+    MissingHeader,
+    #[snafu(display("only the avro and protobuf codecs are supported with a schema registry"))]
+    UnsupportedCodec,
+}
+
+impl SchemaRegistryError {
+    /// Whether the error is bound to happen again, as opposed to failing to reach the registry,
+    /// which is worth retrying.
+    ///
+    /// Server errors, timeouts and throttling are retried, while other client errors, such as
+    /// rejected credentials or schemas, are permanent.
+    pub(crate) fn is_permanent(&self) -> bool {
+        match self {
+            Self::UnexpectedStatus { status, .. } => {
+                !(status.is_server_error()
+                    || *status == StatusCode::REQUEST_TIMEOUT
+                    || *status == StatusCode::TOO_MANY_REQUESTS)
+            }
+            Self::Request { .. } | Self::ReadResponse { .. } | Self::ParseResponse { .. } => false,
+            Self::BuildRequest { .. }
+            | Self::SchemaNotFound { .. }
+            | Self::SubjectNotFound { .. }
+            | Self::InvalidSchema { .. }
+            | Self::MissingHeader
+            | Self::UnsupportedCodec => true,
+        }
+    }
+}
+
+/// Schema registry configuration.
+///
+/// The registry must implement the [Confluent Schema Registry API][api].
+///
+/// [api]: https://docs.confluent.io/platform/current/schema-registry/develop/api.html
+#[configurable_component]
+#[derive(Clone, Debug)]
+pub struct SchemaRegistryConfig {
+    /// The URL of the schema registry.
+    #[configurable(metadata(docs::examples = "http://localhost:8081"))]
+    pub url: String,
+
+    #[configurable(derived)]
+    pub auth: Option<Auth>,
+
+    #[configurable(derived)]
+    pub tls: Option<TlsConfig>,
+}
+
+impl SchemaRegistryConfig {
+    pub(crate) fn build(&self, proxy: &ProxyConfig) -> crate::Result<SchemaRegistryClient> {
+        let tls = TlsSettings::from_options(&self.tls)?;
+        let client = HttpClient::new(tls, proxy)?;
+        Ok(SchemaRegistryClient::new(Backend::Http(HttpRegistry {
+            client,
+            url: self.url.trim_end_matches('/').to_owned(),
+            auth: self.auth.clone(),
+        })))
+    }
+}
+
+#[derive(Deserialize)]
+struct SchemaResponse {
+    schema: String,
+}
+
+#[derive(Deserialize)]
+struct IdResponse {
+    id: u32,
+}
+
+#[derive(Serialize)]
+struct RegisterRequest<'a> {
+    schema: &'a str,
+}
+
+#[derive(Clone)]
+enum Backend {
+    Http(HttpRegistry),
+    #[cfg(test)]
+    Local(Arc<Mutex<LocalRegistry>>),
+}
+
+/// An in-memory stand-in for a schema registry.
+#[cfg(test)]
+#[derive(Debug, Default)]
+pub(crate) struct LocalRegistry {
+    schemas: Vec<String>,
+    subjects: HashMap<String, Vec<u32>>,
+    /// The number of requests served, to check the caching.
+    pub(crate) requests: usize,
+}
+
+#[cfg(test)]
+impl LocalRegistry {
+    /// Registers a schema under a subject, returning its ID. IDs start at 1, like in the
+    /// Confluent registry.
+    pub(crate) fn register(&mut self, subject: &str, schema: &str) -> u32 {
+        let id = match self.schemas.iter().position(|known| known == schema) {
+            Some(index) => index as u32 + 1,
+            None => {
+                self.schemas.push(schema.to_owned());
+                self.schemas.len() as u32
+            }
+        };
+        let versions = self.subjects.entry(subject.to_owned()).or_default();
+        if !versions.contains(&id) {
+            versions.push(id);
+        }
+        id
+    }
+}
+
+/// A client of a schema registry, caching the schemas and IDs it resolves.
+#[derive(Clone)]
+pub(crate) struct SchemaRegistryClient {
+    backend: Backend,
+    schemas: Arc<Mutex<HashMap<u32, Arc<str>>>>,
+    ids: Arc<Mutex<HashMap<(String, String), u32>>>,
+}
+
+impl SchemaRegistryClient {
+    fn new(backend: Backend) -> Self {
+        Self {
+            backend,
+            schemas: Arc::default(),
+            ids: Arc::default(),
+        }
+    }
+
+    /// Creates a client of an in-memory registry.
+    #[cfg(test)]
+    pub(crate) fn local(registry: Arc<Mutex<LocalRegistry>>) -> Self {
+        Self::new(Backend::Local(registry))
+    }
+
+    /// Gets the schema with the given ID.
+    pub(crate) async fn schema(&self, id: u32) -> Result<Arc<str>, SchemaRegistryError> {
+        let cached = self
+            .schemas
+            .lock()
+            .expect("cache lock poisoned")
+            .get(&id)
+            .cloned();
+        if let Some(schema) = cached {
+            return Ok(schema);
+        }
+
+        let schema: Arc<str> = match &self.backend {
+            Backend::Http(registry) => {
+                let path = format!("/schemas/ids/{}", id);
+                match registry.get::<SchemaResponse>(&path).await? {
+                    Some(response) => response.schema.into(),
+                    None => return Err(SchemaRegistryError::SchemaNotFound { id }),
+                }
+            }
+            #[cfg(test)]
+            Backend::Local(registry) => {
+                let mut registry = registry.lock().unwrap();
+                registry.requests += 1;
+                match registry.schemas.get((id as usize).wrapping_sub(1)) {
+                    Some(schema) => schema.as_str().into(),
+                    None => return Err(SchemaRegistryError::SchemaNotFound { id }),
+                }
+            }
+        };
+
+        self.schemas
+            .lock()
+            .expect("cache lock poisoned")
+            .insert(id, Arc::clone(&schema));
+        Ok(schema)
+    }
+
+    /// Registers a schema under a subject, returning its ID. Registering a schema that is
+    /// already registered only looks up its ID.
+    pub(crate) async fn register(
+        &self,
+        subject: &str,
+        schema: &str,
+    ) -> Result<u32, SchemaRegistryError> {
+        let key = (subject.to_owned(), schema.to_owned());
+        let cached = self
+            .ids
+            .lock()
+            .expect("cache lock poisoned")
+            .get(&key)
+            .copied();
+        if let Some(id) = cached {
+            return Ok(id);
+        }
+
+        let id = match &self.backend {
+            Backend::Http(registry) => {
+                let path = format!("/subjects/{}/versions", encode_subject(subject));
+                let body = serde_json::to_vec(&RegisterRequest { schema })
+                    .expect("request is always serializable");
+                registry
+                    .send::<IdResponse>(http::Method::POST, &path, body.into())
+                    .await?
+                    .ok_or_else(|| SchemaRegistryError::SubjectNotFound {
+                        subject: subject.to_owned(),
+                    })?
+                    .id
+            }
+            #[cfg(test)]
+            Backend::Local(registry) => {
+                let mut registry = registry.lock().unwrap();
+                registry.requests += 1;
+                registry.register(subject, schema)
+            }
+        };
+
+        self.ids
+            .lock()
+            .expect("cache lock poisoned")
+            .insert(key, id);
+        Ok(id)
+    }
+
+    /// Gets the ID of the latest schema registered under a subject.
+    pub(crate) async fn latest_id(&self, subject: &str) -> Result<u32, SchemaRegistryError> {
+        let id = match &self.backend {
+            Backend::Http(registry) => {
+                let path = format!("/subjects/{}/versions/latest", encode_subject(subject));
+                registry
+                    .get::<IdResponse>(&path)
+                    .await?
+                    .map(|response| response.id)
+            }
+            #[cfg(test)]
+            Backend::Local(registry) => {
+                let mut registry = registry.lock().unwrap();
+                registry.requests += 1;
+                registry
+                    .subjects
+                    .get(subject)
+                    .and_then(|versions| versions.last().copied())
+            }
+        };
+        id.ok_or_else(|| SchemaRegistryError::SubjectNotFound {
+            subject: subject.to_owned(),
+        })
+    }
+
+    /// Resolves the header to prefix the messages encoded with a serializer with, registering or
+    /// looking up the schema of the serializer under the subject.
+    ///
+    /// Avro schemas are registered, while Protobuf ones must already be registered, as only the
+    /// compiled descriptors of the messages are known.
+    pub(crate) async fn header(
+        &self,
+        subject: &str,
+        serializer: &SerializerConfig,
+    ) -> crate::Result<Bytes> {
+        let mut header = BytesMut::new();
+        match serializer {
+            SerializerConfig::Avro { avro } => {
+                let id = self.register(subject, &avro.schema).await?;
+                put_header(&mut header, id);
+            }
+            SerializerConfig::Protobuf(config) => {
+                let message_indexes = config.build()?.message_indexes();
+                let id = self.latest_id(subject).await?;
+                put_header(&mut header, id);
+                put_message_indexes(&mut header, &message_indexes);
+            }
+            _ => return Err(SchemaRegistryError::UnsupportedCodec.into()),
+        }
+        Ok(header.freeze())
+    }
+}
+
+#[derive(Clone)]
+struct HttpRegistry {
+    client: HttpClient,
+    url: String,
+    auth: Option<Auth>,
+}
+
+impl HttpRegistry {
+    async fn get<T>(&self, path: &str) -> Result<Option<T>, SchemaRegistryError>
+    where
+        T: for<'de> Deserialize<'de>,
+    {
+        self.send(http::Method::GET, path, Body::empty()).await
+    }
+
+    /// Sends a request to the registry, returning `None` when what it refers to is not found.
+    async fn send<T>(
+        &self,
+        method: http::Method,
+        path: &str,
+        body: Body,
+    ) -> Result<Option<T>, SchemaRegistryError>
+    where
+        T: for<'de> Deserialize<'de>,
+    {
+        let mut builder = Request::builder()
+            .method(method)
+            .uri(format!("{}{}", self.url, path))
+            .header(http::header::ACCEPT, CONTENT_TYPE)
+            .header(http::header::CONTENT_TYPE, CONTENT_TYPE);
+        if let Some(auth) = &self.auth {
+            builder = auth.apply_builder(builder);
+        }
+        let request = builder.body(body).context(BuildRequestSnafu)?;
+
+        let response = self.client.send(request).await.context(RequestSnafu)?;
+        let status = response.status();
+        let body = hyper::body::to_bytes(response.into_body())
+            .await
+            .context(ReadResponseSnafu)?;
+
+        match status {
+            status if status.is_success() => serde_json::from_slice(&body)
+                .map(Some)
+                .context(ParseResponseSnafu),
+            StatusCode::NOT_FOUND => Ok(None),
+            status => Err(SchemaRegistryError::UnexpectedStatus {
+                status,
+                body: String::from_utf8_lossy(&body).into_owned(),
+            }),
+        }
+    }
+}
+
+fn encode_subject(subject: &str) -> String {
+    utf8_percent_encode(subject, NON_ALPHANUMERIC).to_string()
+}
+
+/// Writes the magic byte and schema ID of the wire format.
+fn put_header(buf: &mut BytesMut, id: u32) {
+    buf.put_u8(MAGIC_BYTE);
+    buf.put_u32(id);
+}
+
+/// Splits a message into the ID of its schema and the data following the header.
+fn split_header(message: &[u8]) -> Option<(u32, &[u8])> {
+    if message.len() < HEADER_LEN || message[0] != MAGIC_BYTE {
+        return None;
+    }
+    let (mut header, data) = message.split_at(HEADER_LEN);
+    header.advance(1);
+    Some((header.get_u32(), data))
+}
+
+fn put_message_indexes(buf: &mut BytesMut, indexes: &[i32]) {
+    if indexes == [0] {
+        buf.put_u8(0);
+        return;
+    }
+    put_varint(buf, indexes.len() as i32);
+    for index in indexes {
+        put_varint(buf, *index);
+    }
+}
+
+/// Skips the Protobuf message indexes following the header, returning the data after them.
+fn skip_message_indexes(mut data: &[u8]) -> Option<&[u8]> {
+    let count = get_varint(&mut data)?;
+    for _ in 0..count {
+        get_varint(&mut data)?;
+    }
+    Some(data)
+}
+
+fn put_varint(buf: &mut BytesMut, value: i32) {
+    let mut value = ((value << 1) ^ (value >> 31)) as u32;
+    while value >= 0x80 {
+        buf.put_u8(value as u8 | 0x80);
+        value >>= 7;
+    }
+    buf.put_u8(value as u8);
+}
+
+fn get_varint(data: &mut &[u8]) -> Option<i32> {
+    let mut value = 0_u32;
+    for shift in (0..35).step_by(7) {
+        let (&byte, rest) = data.split_first()?;
+        *data = rest;
+        value |= u32::from(byte & 0x7f) << shift;
+        if byte & 0x80 == 0 {
+            return Some((value >> 1) as i32 ^ -((value & 1) as i32));
+        }
+    }
+    None
+}
+
+/// Resolves the decoder of each message from the schema ID in its header.
+///
+/// Avro messages are decoded with the writer schema from the registry, resolved to the configured
+/// schema. Protobuf messages are decoded with the configured message type.
+#[derive(Clone)]
+pub(crate) struct SchemaRegistryDecoders {
+    client: SchemaRegistryClient,
+    decoder: Decoder,
+    avro: Option<AvroDeserializerOptions>,
+    decoders: Arc<Mutex<HashMap<u32, Decoder>>>,
+}
+
+impl SchemaRegistryDecoders {
+    pub(crate) fn new(
+        client: SchemaRegistryClient,
+        decoding: &DecodingConfig,
+    ) -> crate::Result<Self> {
+        let avro = match decoding.config() {
+            DeserializerConfig::Avro { avro } => Some(avro.clone()),
+            DeserializerConfig::Protobuf(_) => None,
+            _ => return Err(SchemaRegistryError::UnsupportedCodec.into()),
+        };
+
+        Ok(Self {
+            client,
+            decoder: decoding.build()?,
+            avro,
+            decoders: Arc::default(),
+        })
+    }
+
+    /// Gets the decoder of a message, and the data to decode with it.
+    pub(crate) async fn decoder<'a>(
+        &self,
+        message: &'a [u8],
+    ) -> Result<(Decoder, &'a [u8]), SchemaRegistryError> {
+        let (id, data) = split_header(message).ok_or(SchemaRegistryError::MissingHeader)?;
+
+        let Some(avro) = &self.avro else {
+            let data = skip_message_indexes(data).ok_or(SchemaRegistryError::MissingHeader)?;
+            return Ok((self.decoder.clone(), data));
+        };
+
+        let cached = self
+            .decoders
+            .lock()
+            .expect("cache lock poisoned")
+            .get(&id)
+            .cloned();
+        if let Some(decoder) = cached {
+            return Ok((decoder, data));
+        }
+
+        let writer_schema = self.client.schema(id).await?;
+        // The header is already stripped, whatever the configuration says.
+        let deserializer = AvroDeserializerConfig::new(avro.schema.clone(), false)
+            .build_with_writer_schema(&writer_schema)
+            .map_err(|error| SchemaRegistryError::InvalidSchema {
+                id,
+                message: error.to_string(),
+            })?;
+        let decoder = Decoder {
+            deserializer: Deserializer::Avro(deserializer),
+            ..self.decoder.clone()
+        };
+
+        self.decoders
+            .lock()
+            .expect("cache lock poisoned")
+            .insert(id, decoder.clone());
+        Ok((decoder, data))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::PathBuf;
+
+    use codecs::{
+        decoding::{FramingConfig, ProtobufDeserializerConfig, ProtobufDeserializerOptions},
+        encoding::{
+            AvroSerializerConfig, AvroSerializerOptions, ProtobufSerializerConfig,
+            ProtobufSerializerOptions,
+        },
+    };
+    use indoc::indoc;
+    use tokio_util::codec::Encoder as _;
+    use vector_core::config::LogNamespace;
+    use vrl::btreemap;
+
+    use super::*;
+    use crate::event::LogEvent;
+
+    const SCHEMA: &str = indoc! {r#"
+        {
+            "type": "record",
+            "name": "Log",
+            "fields": [{ "name": "message", "type": "string" }]
+        }
+    "#};
+
+    fn local() -> (Arc<Mutex<LocalRegistry>>, SchemaRegistryClient) {
+        let registry = Arc::new(Mutex::new(LocalRegistry::default()));
+        let client = SchemaRegistryClient::local(Arc::clone(&registry));
+        (registry, client)
+    }
+
+    fn protobuf_desc_file() -> PathBuf {
+        PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+            .join("lib/codecs/tests/data/decoding/protobuf/test_protobuf3.desc")
+    }
+
+    #[test]
+    fn header_round_trip() {
+        let mut message = BytesMut::new();
+        put_header(&mut message, 42);
+        message.put_slice(b"data");
+
+        assert_eq!(&message[..HEADER_LEN], &[0, 0, 0, 0, 42]);
+        assert_eq!(split_header(&message), Some((42, &b"data"[..])));
+        assert_eq!(split_header(b"\x01\x00\x00\x00\x2adata"), None);
+        assert_eq!(split_header(b"\x00\x00"), None);
+    }
+
+    #[test]
+    fn message_indexes_round_trip() {
+        for indexes in [vec![0], vec![1], vec![0, 2], vec![3, 70]] {
+            let mut header = BytesMut::new();
+            put_message_indexes(&mut header, &indexes);
+            header.put_slice(b"data");
+
+            assert_eq!(skip_message_indexes(&header), Some(&b"data"[..]));
+        }
+
+        let mut header = BytesMut::new();
+        put_message_indexes(&mut header, &[0]);
+        assert_eq!(&header[..], &[0]);
+
+        let mut header = BytesMut::new();
+        put_message_indexes(&mut header, &[1, 70]);
+        assert_eq!(&header[..], &[4, 2, 140, 1]);
+    }
+
+    #[test]
+    fn permanent_errors() {
+        assert!(SchemaRegistryError::MissingHeader.is_permanent());
+        assert!(SchemaRegistryError::SchemaNotFound { id: 1 }.is_permanent());
+
+        let unexpected_status = |status| SchemaRegistryError::UnexpectedStatus {
+            status,
+            body: String::new(),
+        };
+        for status in [
+            StatusCode::INTERNAL_SERVER_ERROR,
+            StatusCode::SERVICE_UNAVAILABLE,
+            StatusCode::REQUEST_TIMEOUT,
+            StatusCode::TOO_MANY_REQUESTS,
+        ] {
+            assert!(!unexpected_status(status).is_permanent(), "{}", status);
+        }
+        for status in [
+            StatusCode::BAD_REQUEST,
+            StatusCode::UNAUTHORIZED,
+            StatusCode::FORBIDDEN,
+            StatusCode::CONFLICT,
+            StatusCode::UNPROCESSABLE_ENTITY,
+        ] {
+            assert!(unexpected_status(status).is_permanent(), "{}", status);
+        }
+    }
+
+    #[tokio::test]
+    async fn caches_schemas_and_ids() {
+        let (registry, client) = local();
+
+        let id = client.register("logs-value", SCHEMA).await.unwrap();
+        assert_eq!(client.register("logs-value", SCHEMA).await.unwrap(), id);
+        assert_eq!(client.latest_id("logs-value").await.unwrap(), id);
+        assert_eq!(&*client.schema(id).await.unwrap(), SCHEMA);
+        assert_eq!(&*client.schema(id).await.unwrap(), SCHEMA);
+        assert_eq!(registry.lock().unwrap().requests, 3);
+
+        assert!(matches!(
+            client.schema(id + 1).await,
+            Err(SchemaRegistryError::SchemaNotFound { .. })
+        ));
+        assert!(matches!(
+            client.latest_id("metrics-value").await,
+            Err(SchemaRegistryError::SubjectNotFound { .. })
+        ));
+    }
+
+    #[tokio::test]
+    async fn avro_header() {
+        let (registry, client) = local();
+        registry
+            .lock()
+            .unwrap()
+            .register("other-value", "\"string\"");
+
+        let serializer = SerializerConfig::Avro {
+            avro: AvroSerializerOptions {
+                schema: SCHEMA.to_owned(),
+            },
+        };
+        let header = client.header("logs-value", &serializer).await.unwrap();
+
+        assert_eq!(&header[..], &[0, 0, 0, 0, 2]);
+        assert_eq!(client.latest_id("logs-value").await.unwrap(), 2);
+    }
+
+    #[tokio::test]
+    async fn protobuf_header() {
+        let (registry, client) = local();
+        registry.lock().unwrap().register("people-value", "proto");
+
+        let serializer = SerializerConfig::Protobuf(ProtobufSerializerConfig {
+            protobuf: ProtobufSerializerOptions {
+                desc_file: protobuf_desc_file(),
+                message_type: "test_protobuf3.AddressBook".to_owned(),
+            },
+        });
+        let header = client.header("people-value", &serializer).await.unwrap();
+
+        assert_eq!(&header[..], &[0, 0, 0, 0, 1, 2, 2]);
+        assert!(client.header("unknown-value", &serializer).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn decodes_avro_with_writer_schema() {
+        let (registry, client) = local();
+        let writer_schema = indoc! {r#"
+            {
+                "type": "record",
+                "name": "Log",
+                "fields": [
+                    { "name": "message", "type": "string" },
+                    { "name": "host", "type": "string" }
+                ]
+            }
+        "#};
+        let id = registry
+            .lock()
+            .unwrap()
+            .register("logs-value", writer_schema);
+
+        let decoding = DecodingConfig::new(
+            FramingConfig::Bytes,
+            AvroDeserializerConfig::new(SCHEMA.to_owned(), false).into(),
+            LogNamespace::Legacy,
+        );
+        let decoders = SchemaRegistryDecoders::new(client, &decoding).unwrap();
+
+        let mut message = BytesMut::new();
+        put_header(&mut message, id);
+        let log = LogEvent::from(btreemap! {
+            "message" => "hello",
+            "host" => "localhost",
+        });
+        AvroSerializerConfig::new(writer_schema.to_owned())
+            .build()
+            .unwrap()
+            .encode(log.into(), &mut message)
+            .unwrap();
+
+        let (decoder, data) = decoders.decoder(&message).await.unwrap();
+        let (events, _) = decoder
+            .deserializer_parse(Bytes::copy_from_slice(data))
+            .unwrap();
+        let log = events[0].as_log();
+        assert_eq!(log["message"], "hello".into());
+        assert!(!log.contains("host"));
+
+        // The decoder is cached.
+        decoders.decoder(&message).await.unwrap();
+        assert_eq!(registry.lock().unwrap().requests, 1);
+
+        assert!(matches!(
+            decoders.decoder(b"hello").await,
+            Err(SchemaRegistryError::MissingHeader)
+        ));
+    }
+
+    #[tokio::test]
+    async fn skips_protobuf_message_indexes() {
+        let (_, client) = local();
+        let decoding = DecodingConfig::new(
+            FramingConfig::Bytes,
+            DeserializerConfig::Protobuf(ProtobufDeserializerConfig {
+                protobuf: ProtobufDeserializerOptions {
+                    desc_file: protobuf_desc_file(),
+                    message_type: "test_protobuf3.Person".to_owned(),
+                },
+            }),
+            LogNamespace::Legacy,
+        );
+        let decoders = SchemaRegistryDecoders::new(client, &decoding).unwrap();
+
+        let (_, data) = decoders
+            .decoder(b"\x00\x00\x00\x00\x01\x00data")
+            .await
+            .unwrap();
+        assert_eq!(data, b"data");
+
+        let bytes = DecodingConfig::new(
+            FramingConfig::Bytes,
+            DeserializerConfig::Bytes,
+            LogNamespace::Legacy,
+        );
+        assert!(SchemaRegistryDecoders::new(local().1, &bytes).is_err());
+    }
+}
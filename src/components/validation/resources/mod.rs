@@ -2,12 +2,17 @@ mod event;
 mod http;
 
 use codecs::{
-    decoding::{self, DeserializerConfig},
+    decoding::{
+        self, AvroDeserializerOptions, CsvDeserializerConfig, CsvDeserializerOptions,
+        DeserializerConfig, ProtobufDeserializerConfig, ProtobufDeserializerOptions,
+    },
     encoding::{
-        self, Framer, FramingConfig, JsonSerializerConfig, SerializerConfig, TextSerializerConfig,
+        self, AvroSerializerOptions, CsvSerializerConfig, CsvSerializerOptions, Framer,
+        FramingConfig, JsonSerializerConfig, SerializerConfig, TextSerializerConfig,
     },
     BytesEncoder,
 };
+use lookup::lookup_v2::{ConfigTargetPath, OwnedTargetPath, OwnedValuePath};
 use tokio::sync::mpsc;
 use vector_core::{config::DataType, event::Event};
 
@@ -140,8 +145,30 @@ fn deserializer_config_to_serializer(config: &DeserializerConfig) -> encoding::S
         // TODO: This isn't necessarily a one-to-one conversion, at least not in the future when
         // "bytes" can be a top-level field and we aren't implicitly decoding everything into the
         // `message` field... but it's close enough for now.
+        DeserializerConfig::Avro { avro } => SerializerConfig::Avro {
+            avro: AvroSerializerOptions {
+                schema: avro.schema.clone(),
+            },
+        },
         DeserializerConfig::Bytes => SerializerConfig::Text(TextSerializerConfig::default()),
+        DeserializerConfig::Cef => SerializerConfig::Cef,
+        DeserializerConfig::Csv(config) => {
+            SerializerConfig::Csv(CsvSerializerConfig::new(CsvSerializerOptions {
+                fields: config
+                    .csv
+                    .columns
+                    .iter()
+                    .map(|column| {
+                        ConfigTargetPath(OwnedTargetPath::event(OwnedValuePath::single_field(
+                            column,
+                        )))
+                    })
+                    .collect(),
+            }))
+        }
         DeserializerConfig::Json { .. } => SerializerConfig::Json(JsonSerializerConfig::default()),
+        DeserializerConfig::Leef => SerializerConfig::Leef(Default::default()),
+        DeserializerConfig::Logfmt => SerializerConfig::Logfmt,
         DeserializerConfig::Protobuf(_) => unimplemented!(),
         // TODO: We need to create an Avro serializer because, certainly, for any source decoding
         // the data as Avro, we can't possibly send anything else without the source just
@@ -168,11 +195,10 @@ fn decoder_framing_to_encoding_framer(framing: &decoding::FramingConfig) -> enco
                 },
             })
         }
+        decoding::FramingConfig::ChunkedGelf(_) => encoding::FramingConfig::Bytes,
         decoding::FramingConfig::LengthDelimited => encoding::FramingConfig::LengthDelimited,
         decoding::FramingConfig::NewlineDelimited(_) => encoding::FramingConfig::NewlineDelimited,
-        // TODO: There's no equivalent octet counting framer for encoding... although
-        // there's no particular reason that would make it hard to write.
-        decoding::FramingConfig::OctetCounting(_) => todo!(),
+        decoding::FramingConfig::OctetCounting(_) => encoding::FramingConfig::OctetCounting,
     };
 
     framing_config.build()
@@ -182,14 +208,45 @@ fn serializer_config_to_deserializer(
     config: &SerializerConfig,
 ) -> vector_common::Result<decoding::Deserializer> {
     let deserializer_config = match config {
-        SerializerConfig::Avro { .. } => todo!(),
-        SerializerConfig::Csv { .. } => todo!(),
+        SerializerConfig::Avro { avro } => DeserializerConfig::Avro {
+            avro: AvroDeserializerOptions {
+                schema: avro.schema.clone(),
+                strip_schema_id_prefix: false,
+            },
+        },
+        SerializerConfig::Cef => DeserializerConfig::Cef,
+        SerializerConfig::Csv(config) => {
+            DeserializerConfig::Csv(CsvDeserializerConfig::new(CsvDeserializerOptions {
+                columns: config
+                    .csv
+                    .fields
+                    .iter()
+                    .map(|field| field.0.path.to_string())
+                    .collect(),
+                delimiter: b',',
+                skip_header: false,
+                types: Default::default(),
+            }))
+        }
         SerializerConfig::Gelf => DeserializerConfig::Gelf(Default::default()),
         SerializerConfig::Json(_) => DeserializerConfig::Json(Default::default()),
-        SerializerConfig::Logfmt => todo!(),
+        SerializerConfig::Leef(_) => DeserializerConfig::Leef,
+        SerializerConfig::Logfmt => DeserializerConfig::Logfmt,
         SerializerConfig::Native => DeserializerConfig::Native,
         SerializerConfig::NativeJson => DeserializerConfig::NativeJson(Default::default()),
+        SerializerConfig::Protobuf(config) => {
+            DeserializerConfig::Protobuf(ProtobufDeserializerConfig {
+                protobuf: ProtobufDeserializerOptions {
+                    desc_file: config.protobuf.desc_file.clone(),
+                    message_type: config.protobuf.message_type.clone(),
+                },
+            })
+        }
         SerializerConfig::RawMessage | SerializerConfig::Text(_) => DeserializerConfig::Bytes,
+        #[cfg(feature = "sources-syslog")]
+        SerializerConfig::Syslog(_) => DeserializerConfig::Syslog(Default::default()),
+        #[cfg(not(feature = "sources-syslog"))]
+        SerializerConfig::Syslog(_) => DeserializerConfig::Bytes,
     };
 
     deserializer_config.build()
@@ -210,6 +267,9 @@ fn encoder_framing_to_decoding_framer(framing: encoding::FramingConfig) -> decod
         encoding::FramingConfig::NewlineDelimited => {
             decoding::FramingConfig::NewlineDelimited(Default::default())
         }
+        encoding::FramingConfig::OctetCounting => {
+            decoding::FramingConfig::OctetCounting(Default::default())
+        }
     };
 
     framing_config.build()
@@ -321,3 +381,82 @@ impl ExternalResource {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use bytes::BytesMut;
+    use codecs::decoding::format::Deserializer as _;
+    use tokio_util::codec::Encoder as _;
+    use vector_core::{config::LogNamespace, event::LogEvent};
+
+    use super::*;
+
+    fn test_event() -> LogEvent {
+        LogEvent::from_iter([("host", "localhost"), ("message", "hello, world")])
+    }
+
+    fn assert_round_trips(
+        mut serializer: encoding::Serializer,
+        deserializer: decoding::Deserializer,
+        fields: &[&str],
+    ) {
+        let event = test_event();
+        let mut bytes = BytesMut::new();
+        serializer
+            .encode(Event::Log(event.clone()), &mut bytes)
+            .unwrap();
+
+        let events = deserializer
+            .parse(bytes.freeze(), LogNamespace::Legacy)
+            .unwrap();
+        assert_eq!(events.len(), 1);
+        let decoded = events[0].as_log();
+        for field in fields {
+            assert_eq!(decoded.get(*field), event.get(*field), "field `{}`", field);
+        }
+    }
+
+    #[test]
+    fn csv_decoding_round_trips() {
+        let config = DeserializerConfig::Csv(CsvDeserializerConfig::new(CsvDeserializerOptions {
+            columns: vec!["host".into(), "message".into()],
+            delimiter: b',',
+            skip_header: false,
+            types: Default::default(),
+        }));
+
+        assert_round_trips(
+            deserializer_config_to_serializer(&config),
+            config.build().unwrap(),
+            &["host", "message"],
+        );
+    }
+
+    #[test]
+    fn csv_encoding_round_trips() {
+        let config = SerializerConfig::Csv(CsvSerializerConfig::new(CsvSerializerOptions {
+            fields: vec![
+                ConfigTargetPath::try_from("host".to_string()).unwrap(),
+                ConfigTargetPath::try_from("message".to_string()).unwrap(),
+            ],
+        }));
+
+        assert_round_trips(
+            config.build().unwrap(),
+            serializer_config_to_deserializer(&config).unwrap(),
+            &["host", "message"],
+        );
+    }
+
+    #[cfg(feature = "sources-syslog")]
+    #[test]
+    fn syslog_encoding_round_trips() {
+        let config = SerializerConfig::Syslog(Default::default());
+
+        assert_round_trips(
+            config.build().unwrap(),
+            serializer_config_to_deserializer(&config).unwrap(),
+            &["message"],
+        );
+    }
+}
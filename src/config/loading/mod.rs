@@ -245,6 +245,15 @@ pub fn load_secret_backends_from_paths(
     loader_from_paths(SecretBackendLoader::new(), config_paths)
 }
 
+/// Gets the local paths that the secret backends configured in `config_paths` read secrets from.
+///
+/// Any errors are ignored here, as they are reported when the configuration itself is loaded.
+pub fn load_secret_backend_paths(config_paths: &[ConfigPath]) -> Vec<PathBuf> {
+    load_secret_backends_from_paths(config_paths)
+        .map(|(loader, _)| loader.watched_paths())
+        .unwrap_or_default()
+}
+
 pub fn load_from_str(input: &str, format: Format) -> Result<Config, Vec<String>> {
     let (builder, load_warnings) = load_from_inputs(std::iter::once((input.as_bytes(), format)))?;
     let (config, build_warnings) = builder.build_with_warnings()?;
@@ -1,6 +1,7 @@
 use std::{
     collections::{HashMap, HashSet},
     io::Read,
+    path::PathBuf,
 };
 
 use indexmap::IndexMap;
@@ -82,6 +83,14 @@ impl SecretBackendLoader {
     pub(crate) fn has_secrets_to_retrieve(&self) -> bool {
         !self.secret_keys.is_empty()
     }
+
+    /// Gets the local paths that the configured backends read secrets from.
+    pub(crate) fn watched_paths(&self) -> Vec<PathBuf> {
+        self.backends
+            .values()
+            .flat_map(SecretBackend::watched_paths)
+            .collect()
+    }
 }
 
 impl Process for SecretBackendLoader {
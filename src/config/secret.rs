@@ -1,4 +1,7 @@
-use std::collections::{HashMap, HashSet};
+use std::{
+    collections::{HashMap, HashSet},
+    path::PathBuf,
+};
 
 use enum_dispatch::enum_dispatch;
 use vector_config::NamedComponent;
@@ -13,4 +16,12 @@ pub trait SecretBackend: NamedComponent + core::fmt::Debug + Send + Sync {
         secret_keys: HashSet<String>,
         signal_rx: &mut signal::SignalRx,
     ) -> crate::Result<HashMap<String, String>>;
+
+    /// Gets the local paths that secrets are read from, if any.
+    ///
+    /// When watching the configuration for changes, these paths are watched as well, so that
+    /// updated secrets are picked up without having to modify the configuration itself.
+    fn watched_paths(&self) -> Vec<PathBuf> {
+        Vec::new()
+    }
 }
@@ -1,4 +1,6 @@
 use crate::emit;
+use metrics::counter;
+use vector_common::internal_event::{error_stage, error_type};
 use vector_core::internal_event::{ComponentEventsDropped, InternalEvent, INTENTIONAL};
 
 #[derive(Debug)]
@@ -14,3 +16,27 @@ impl InternalEvent for DedupeEventsDropped {
         });
     }
 }
+
+#[derive(Debug)]
+pub struct DedupeCacheError<E> {
+    pub error: E,
+    pub backend: &'static str,
+}
+
+impl<E: std::fmt::Display> InternalEvent for DedupeCacheError<E> {
+    fn emit(self) {
+        error!(
+            message = "Failed to access deduplication cache.",
+            error = %self.error,
+            backend = self.backend,
+            error_type = error_type::IO_FAILED,
+            stage = error_stage::PROCESSING,
+            internal_log_rate_limit = true,
+        );
+        counter!(
+            "component_errors_total", 1,
+            "error_type" => error_type::IO_FAILED,
+            "stage" => error_stage::PROCESSING,
+        );
+    }
+}
@@ -1,14 +1,21 @@
 use metrics::counter;
 use vector_core::internal_event::InternalEvent;
 
+#[cfg(feature = "sinks-fluent")]
+use crate::emit;
+#[cfg(feature = "sources-fluent")]
 use crate::sources::fluent::DecodeError;
 use vector_common::internal_event::{error_stage, error_type};
+#[cfg(feature = "sinks-fluent")]
+use vector_common::internal_event::{ComponentEventsDropped, UNINTENTIONAL};
 
+#[cfg(feature = "sources-fluent")]
 #[derive(Debug)]
 pub struct FluentMessageReceived {
     pub byte_size: u64,
 }
 
+#[cfg(feature = "sources-fluent")]
 impl InternalEvent for FluentMessageReceived {
     fn emit(self) {
         trace!(message = "Received fluent message.", byte_size = %self.byte_size);
@@ -16,12 +23,14 @@ impl InternalEvent for FluentMessageReceived {
     }
 }
 
+#[cfg(feature = "sources-fluent")]
 #[derive(Debug)]
 pub struct FluentMessageDecodeError<'a> {
     pub error: &'a DecodeError,
     pub base64_encoded_message: String,
 }
 
+#[cfg(feature = "sources-fluent")]
 impl<'a> InternalEvent for FluentMessageDecodeError<'a> {
     fn emit(self) {
         error!(
@@ -41,3 +50,35 @@ impl<'a> InternalEvent for FluentMessageDecodeError<'a> {
         counter!("decode_errors_total", 1);
     }
 }
+
+#[cfg(feature = "sinks-fluent")]
+#[derive(Debug)]
+pub struct FluentMessageSendError<E> {
+    pub error: E,
+    pub count: usize,
+}
+
+#[cfg(feature = "sinks-fluent")]
+impl<E: std::fmt::Display> InternalEvent for FluentMessageSendError<E> {
+    fn emit(self) {
+        let reason = "Failed to send fluent message.";
+        error!(
+            message = reason,
+            error = %self.error,
+            error_code = "fluent_send",
+            error_type = error_type::WRITER_FAILED,
+            stage = error_stage::SENDING,
+            internal_log_rate_limit = true,
+        );
+        counter!(
+            "component_errors_total", 1,
+            "error_code" => "fluent_send",
+            "error_type" => error_type::WRITER_FAILED,
+            "stage" => error_stage::SENDING,
+        );
+        emit!(ComponentEventsDropped::<UNINTENTIONAL> {
+            count: self.count,
+            reason
+        });
+    }
+}
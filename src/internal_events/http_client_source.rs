@@ -87,3 +87,27 @@ impl InternalEvent for HttpClientHttpError {
         counter!("http_request_errors_total", 1);
     }
 }
+
+#[derive(Debug)]
+pub struct HttpClientCursorCheckpointError {
+    pub error: std::io::Error,
+    pub filename: String,
+}
+
+impl InternalEvent for HttpClientCursorCheckpointError {
+    fn emit(self) {
+        error!(
+            message = "Could not checkpoint cursor.",
+            filename = ?self.filename,
+            error = %self.error,
+            error_type = error_type::IO_FAILED,
+            stage = error_stage::PROCESSING,
+            internal_log_rate_limit = true,
+        );
+        counter!(
+            "component_errors_total", 1,
+            "stage" => error_stage::PROCESSING,
+            "error_type" => error_type::IO_FAILED,
+        );
+    }
+}
@@ -1,11 +1,16 @@
 use metrics::{counter, gauge};
 use vector_core::{internal_event::InternalEvent, update_counter};
 
+#[cfg(feature = "sinks-kafka")]
+use vector_common::internal_event::{ComponentEventsDropped, UNINTENTIONAL};
 use vector_common::{
     internal_event::{error_stage, error_type},
     json_size::JsonSize,
 };
 
+#[cfg(feature = "sinks-kafka")]
+use crate::emit;
+
 #[derive(Debug)]
 pub struct KafkaBytesReceived<'a> {
     pub byte_size: usize,
@@ -110,6 +115,36 @@ impl InternalEvent for KafkaReadError {
     }
 }
 
+#[cfg(feature = "sources-kafka")]
+#[derive(Debug)]
+pub struct KafkaSchemaRegistryError<'a> {
+    pub error: &'a crate::common::schema_registry::SchemaRegistryError,
+    pub topic: &'a str,
+    pub partition: i32,
+}
+
+#[cfg(feature = "sources-kafka")]
+impl InternalEvent for KafkaSchemaRegistryError<'_> {
+    fn emit(self) {
+        error!(
+            message = "Failed to resolve the schema of message.",
+            error = %self.error,
+            error_code = "resolving_schema",
+            error_type = error_type::PARSER_FAILED,
+            stage = error_stage::PROCESSING,
+            topic = self.topic,
+            partition = %self.partition,
+            internal_log_rate_limit = true,
+        );
+        counter!(
+            "component_errors_total", 1,
+            "error_code" => "resolving_schema",
+            "error_type" => error_type::PARSER_FAILED,
+            "stage" => error_stage::PROCESSING,
+        );
+    }
+}
+
 #[derive(Debug)]
 pub struct KafkaStatisticsReceived<'a> {
     pub statistics: &'a rdkafka::Statistics,
@@ -184,3 +219,37 @@ impl InternalEvent for KafkaHeaderExtractionError<'_> {
         counter!("kafka_header_extraction_failures_total", 1);
     }
 }
+
+#[cfg(feature = "sinks-kafka")]
+#[derive(Debug)]
+pub struct KafkaTransactionError<'a> {
+    pub error: &'a rdkafka::error::KafkaError,
+    pub count: usize,
+}
+
+#[cfg(feature = "sinks-kafka")]
+impl InternalEvent for KafkaTransactionError<'_> {
+    fn emit(self) {
+        let reason = "Kafka transaction failed.";
+        error!(
+            message = reason,
+            error = %self.error,
+            error_code = "kafka_transaction",
+            error_type = error_type::REQUEST_FAILED,
+            stage = error_stage::SENDING,
+            internal_log_rate_limit = true,
+        );
+        counter!(
+            "component_errors_total", 1,
+            "error_code" => "kafka_transaction",
+            "error_type" => error_type::REQUEST_FAILED,
+            "stage" => error_stage::SENDING,
+        );
+        if self.count > 0 {
+            emit!(ComponentEventsDropped::<UNINTENTIONAL> {
+                count: self.count,
+                reason,
+            });
+        }
+    }
+}
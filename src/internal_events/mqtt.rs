@@ -0,0 +1,62 @@
+use metrics::counter;
+use rumqttc::ConnectionError;
+use vector_common::internal_event::error_type;
+#[cfg(feature = "sinks-mqtt")]
+use vector_common::internal_event::{error_stage, ComponentEventsDropped, UNINTENTIONAL};
+use vector_core::internal_event::InternalEvent;
+
+#[cfg(feature = "sinks-mqtt")]
+use crate::emit;
+
+#[derive(Debug)]
+pub struct MqttConnectionError {
+    pub error: ConnectionError,
+    pub stage: &'static str,
+}
+
+impl InternalEvent for MqttConnectionError {
+    fn emit(self) {
+        error!(
+            message = "MQTT connection error.",
+            error = %self.error,
+            error_code = "mqtt_connection_error",
+            error_type = error_type::CONNECTION_FAILED,
+            stage = self.stage,
+            internal_log_rate_limit = true,
+        );
+        counter!(
+            "component_errors_total", 1,
+            "error_code" => "mqtt_connection_error",
+            "error_type" => error_type::CONNECTION_FAILED,
+            "stage" => self.stage,
+        );
+    }
+}
+
+#[cfg(feature = "sinks-mqtt")]
+#[derive(Debug)]
+pub struct MqttEventSendError {
+    pub error: rumqttc::ClientError,
+}
+
+#[cfg(feature = "sinks-mqtt")]
+impl InternalEvent for MqttEventSendError {
+    fn emit(self) {
+        let reason = "Failed to send message.";
+        error!(
+            message = reason,
+            error = %self.error,
+            error_code = "mqtt_client_error",
+            error_type = error_type::WRITER_FAILED,
+            stage = error_stage::SENDING,
+            internal_log_rate_limit = true,
+        );
+        counter!(
+            "component_errors_total", 1,
+            "error_code" => "mqtt_client_error",
+            "error_type" => error_type::WRITER_FAILED,
+            "stage" => error_stage::SENDING,
+        );
+        emit!(ComponentEventsDropped::<UNINTENTIONAL> { count: 1, reason });
+    }
+}
@@ -1,19 +1,22 @@
 use std::io::Error;
 
+#[cfg(feature = "sinks-nats")]
 use crate::emit;
 use metrics::counter;
-use vector_common::internal_event::{
-    error_stage, error_type, ComponentEventsDropped, UNINTENTIONAL,
-};
+use vector_common::internal_event::{error_stage, error_type};
+#[cfg(feature = "sinks-nats")]
+use vector_common::internal_event::{ComponentEventsDropped, UNINTENTIONAL};
 use vector_core::internal_event::InternalEvent;
 
 use super::prelude::io_error_code;
 
+#[cfg(feature = "sinks-nats")]
 #[derive(Debug)]
 pub struct NatsEventSendError {
     pub error: Error,
 }
 
+#[cfg(feature = "sinks-nats")]
 impl InternalEvent for NatsEventSendError {
     fn emit(self) {
         let reason = "Failed to send message.";
@@ -37,3 +40,55 @@ impl InternalEvent for NatsEventSendError {
         counter!("send_errors_total", 1);
     }
 }
+
+#[cfg(feature = "sources-nats")]
+#[derive(Debug)]
+pub struct NatsReceiveError {
+    pub error: Error,
+}
+
+#[cfg(feature = "sources-nats")]
+impl InternalEvent for NatsReceiveError {
+    fn emit(self) {
+        error!(
+            message = "Failed to receive messages.",
+            error = %self.error,
+            error_type = error_type::REQUEST_FAILED,
+            error_code = io_error_code(&self.error),
+            stage = error_stage::RECEIVING,
+            internal_log_rate_limit = true,
+        );
+        counter!(
+            "component_errors_total", 1,
+            "error_type" => error_type::REQUEST_FAILED,
+            "error_code" => io_error_code(&self.error),
+            "stage" => error_stage::RECEIVING,
+        );
+    }
+}
+
+#[cfg(feature = "sources-nats")]
+#[derive(Debug)]
+pub struct NatsAckError {
+    pub error: Error,
+}
+
+#[cfg(feature = "sources-nats")]
+impl InternalEvent for NatsAckError {
+    fn emit(self) {
+        error!(
+            message = "Unable to acknowledge message.",
+            error = %self.error,
+            error_type = error_type::ACKNOWLEDGMENT_FAILED,
+            error_code = io_error_code(&self.error),
+            stage = error_stage::RECEIVING,
+            internal_log_rate_limit = true,
+        );
+        counter!(
+            "component_errors_total", 1,
+            "error_type" => error_type::ACKNOWLEDGMENT_FAILED,
+            "error_code" => io_error_code(&self.error),
+            "stage" => error_stage::RECEIVING,
+        );
+    }
+}
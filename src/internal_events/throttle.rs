@@ -1,5 +1,6 @@
 use crate::emit;
 use metrics::counter;
+use vector_common::internal_event::{error_stage, error_type};
 use vector_core::internal_event::{ComponentEventsDropped, InternalEvent, INTENTIONAL};
 
 #[derive(Debug)]
@@ -29,3 +30,20 @@ impl InternalEvent for ThrottleEventDiscarded {
         })
     }
 }
+
+#[derive(Debug)]
+pub(crate) struct ThrottleThresholdError {
+    pub error: String,
+}
+
+impl InternalEvent for ThrottleThresholdError {
+    fn emit(self) {
+        warn!(
+            message = "Failed to resolve the threshold of the key, using the default threshold.",
+            error = %self.error,
+            error_type = error_type::CONVERSION_FAILED,
+            stage = error_stage::PROCESSING,
+            internal_log_rate_limit = true,
+        );
+    }
+}
@@ -0,0 +1,158 @@
+use rand::{thread_rng, Rng};
+use rand_distr::Alphanumeric;
+use rumqttc::{MqttOptions, QoS, TlsConfiguration, Transport};
+use snafu::{ResultExt, Snafu};
+use vector_common::sensitive_string::SensitiveString;
+use vector_config::configurable_component;
+
+use crate::tls::{MaybeTlsSettings, TlsEnableableConfig, TlsError};
+
+#[derive(Debug, Snafu)]
+pub enum MqttConfigError {
+    #[snafu(display("MQTT Config Error: `user` and `password` must be set together"))]
+    BadCredentials,
+    #[snafu(display(
+        "MQTT Config Error: `client_id` must be set when `clean_session` is disabled"
+    ))]
+    MissingClientId,
+    #[snafu(display("MQTT TLS Config Error: {}", source))]
+    Tls { source: TlsError },
+    #[snafu(display("MQTT TLS Config Error: `tls.ca_file` must be set"))]
+    TlsMissingCa,
+}
+
+/// Quality of service levels for delivering MQTT messages.
+#[configurable_component]
+#[derive(Clone, Copy, Debug, Derivative, Eq, PartialEq)]
+#[derivative(Default)]
+#[serde(rename_all = "snake_case")]
+pub enum MqttQoS {
+    /// Messages are delivered at most once, and may be lost.
+    AtMostOnce,
+
+    /// Messages are delivered at least once, and may be duplicated.
+    #[derivative(Default)]
+    AtLeastOnce,
+
+    /// Messages are delivered exactly once.
+    ExactlyOnce,
+}
+
+impl From<MqttQoS> for QoS {
+    fn from(qos: MqttQoS) -> Self {
+        match qos {
+            MqttQoS::AtMostOnce => QoS::AtMostOnce,
+            MqttQoS::AtLeastOnce => QoS::AtLeastOnce,
+            MqttQoS::ExactlyOnce => QoS::ExactlyOnce,
+        }
+    }
+}
+
+/// The maximum size of packets sent and received, which is the largest size allowed by MQTT.
+///
+/// The client otherwise only allows packets of up to 10 KiB.
+const MAX_PACKET_SIZE: usize = 256 * 1024 * 1024;
+
+pub(crate) const fn default_port() -> u16 {
+    1883
+}
+
+pub(crate) const fn default_keep_alive() -> u16 {
+    60
+}
+
+pub(crate) const fn default_clean_session() -> bool {
+    true
+}
+
+/// Gets the client ID to connect with.
+///
+/// Persistent sessions are tied to the client ID, so one must be configured when the session isn't
+/// clean. Otherwise, a random one is generated, so that multiple instances don't disconnect each
+/// other.
+pub(crate) fn client_id(
+    client_id: &Option<String>,
+    clean_session: bool,
+) -> Result<String, MqttConfigError> {
+    match client_id {
+        Some(client_id) => Ok(client_id.clone()),
+        None if clean_session => {
+            let suffix = thread_rng()
+                .sample_iter(&Alphanumeric)
+                .take(10)
+                .map(char::from)
+                .collect::<String>();
+            Ok(format!("vector-{}", suffix))
+        }
+        None => Err(MqttConfigError::MissingClientId),
+    }
+}
+
+/// Applies the credentials and TLS settings shared by the MQTT source and sink.
+pub(crate) fn configure_options(
+    options: &mut MqttOptions,
+    user: &Option<String>,
+    password: &Option<SensitiveString>,
+    tls: &Option<TlsEnableableConfig>,
+) -> Result<(), MqttConfigError> {
+    options.set_max_packet_size(MAX_PACKET_SIZE, MAX_PACKET_SIZE);
+
+    match (user, password) {
+        (Some(user), Some(password)) => {
+            options.set_credentials(user, password.inner());
+        }
+        (None, None) => {}
+        _ => return Err(MqttConfigError::BadCredentials),
+    }
+
+    let tls = MaybeTlsSettings::from_config(tls, false).context(TlsSnafu)?;
+    if let Some(tls) = tls.tls() {
+        // The MQTT client only trusts the certificate authorities it's given, rather than those of
+        // the system.
+        let ca = tls.authorities_pem().flatten().collect::<Vec<_>>();
+        if ca.is_empty() {
+            return Err(MqttConfigError::TlsMissingCa);
+        }
+        options.set_transport(Transport::Tls(TlsConfiguration::Simple {
+            ca,
+            alpn: None,
+            client_auth: tls.identity_pem(),
+        }));
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn credentials_must_be_set_together() {
+        let mut options = MqttOptions::new("vector", "localhost", default_port());
+        assert!(configure_options(
+            &mut options,
+            &Some("user".to_string()),
+            &Some("password".to_string().into()),
+            &None
+        )
+        .is_ok());
+        assert!(matches!(
+            configure_options(&mut options, &Some("user".to_string()), &None, &None),
+            Err(MqttConfigError::BadCredentials)
+        ));
+    }
+
+    #[test]
+    fn persistent_sessions_require_client_id() {
+        assert_eq!(
+            client_id(&Some("vector".to_string()), false).unwrap(),
+            "vector"
+        );
+        assert!(client_id(&None, true).unwrap().starts_with("vector-"));
+        assert!(matches!(
+            client_id(&None, false),
+            Err(MqttConfigError::MissingClientId)
+        ));
+    }
+}
@@ -1,4 +1,7 @@
+use std::{fmt, io, time::Duration};
+
 use nkeys::error::Error as NKeysError;
+use serde::{de::DeserializeOwned, Deserialize};
 use snafu::{ResultExt, Snafu};
 use vector_common::sensitive_string::SensitiveString;
 use vector_config::configurable_component;
@@ -184,10 +187,98 @@ pub(crate) fn from_tls_auth_config(
     }
 }
 
+/// The amount of time to wait for a response to a JetStream request.
+const JETSTREAM_REQUEST_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Error returned by the JetStream API.
+#[derive(Debug, Deserialize)]
+pub(crate) struct JetStreamApiError {
+    code: u16,
+    #[serde(default)]
+    description: String,
+}
+
+impl fmt::Display for JetStreamApiError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "JetStream error {}: {}", self.code, self.description)
+    }
+}
+
+impl std::error::Error for JetStreamApiError {}
+
+/// Response to a JetStream request.
+///
+/// Successful responses don't share a common shape, so anything that isn't an error is parsed as
+/// the expected response.
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum JetStreamResponse<T> {
+    Error { error: JetStreamApiError },
+    Ok(T),
+}
+
+pub(crate) fn parse_jetstream_response<T: DeserializeOwned>(data: &[u8]) -> io::Result<T> {
+    match serde_json::from_slice(data)
+        .map_err(|error| io::Error::new(io::ErrorKind::InvalidData, error))?
+    {
+        JetStreamResponse::Error { error } => Err(io::Error::new(io::ErrorKind::Other, error)),
+        JetStreamResponse::Ok(response) => Ok(response),
+    }
+}
+
+/// Sends a request to JetStream, and waits for its response.
+///
+/// Publishing a message to a subject that's captured by a stream is also a JetStream request, with
+/// the response acknowledging that the message was stored.
+pub(crate) async fn jetstream_request<T: DeserializeOwned>(
+    connection: &nats::asynk::Connection,
+    subject: &str,
+    payload: impl AsRef<[u8]>,
+) -> io::Result<T> {
+    let response = tokio::time::timeout(
+        JETSTREAM_REQUEST_TIMEOUT,
+        connection.request(subject, payload),
+    )
+    .await
+    .map_err(|_| io::Error::new(io::ErrorKind::TimedOut, "JetStream request timed out"))??;
+
+    parse_jetstream_response(&response.data)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[derive(Debug, Deserialize)]
+    struct PublishAck {
+        stream: String,
+        seq: u64,
+    }
+
+    #[test]
+    fn jetstream_response_ok() {
+        let ack: PublishAck =
+            parse_jetstream_response(br#"{"stream":"logs","seq":42,"duplicate":false}"#).unwrap();
+        assert_eq!(ack.stream, "logs");
+        assert_eq!(ack.seq, 42);
+    }
+
+    #[test]
+    fn jetstream_response_error() {
+        let error = parse_jetstream_response::<PublishAck>(
+            br#"{"error":{"code":503,"err_code":10039,"description":"jetstream not enabled"}}"#,
+        )
+        .unwrap_err();
+        assert_eq!(error.kind(), io::ErrorKind::Other);
+        assert_eq!(
+            error.to_string(),
+            "JetStream error 503: jetstream not enabled"
+        );
+
+        let error = parse_jetstream_response::<PublishAck>(b"not json").unwrap_err();
+        assert_eq!(error.kind(), io::ErrorKind::InvalidData);
+    }
+
     fn parse_auth(s: &str) -> Result<nats::asynk::Options, crate::Error> {
         toml::from_str(s)
             .map_err(Into::into)
@@ -0,0 +1,135 @@
+use std::{
+    collections::{HashMap, HashSet},
+    path::{Component, Path, PathBuf},
+};
+
+use vector_config::{component::GenerateConfig, configurable_component};
+
+use crate::{config::SecretBackend, signal};
+
+/// Configuration for the `directory` secrets backend.
+///
+/// Each secret is read from the file in the directory named after the secret key, which matches
+/// the layout of secrets mounted as volumes in Kubernetes.
+#[configurable_component(secrets("directory"))]
+#[derive(Clone, Debug)]
+pub struct DirectoryBackend {
+    /// Directory path to read secrets from.
+    #[configurable(metadata(docs::examples = "/var/run/secrets/vector"))]
+    pub path: PathBuf,
+
+    /// Remove trailing whitespace from the secret values.
+    ///
+    /// Files that hold secrets often end with a newline, which is usually not part of the secret.
+    #[serde(default)]
+    pub remove_trailing_whitespace: bool,
+}
+
+impl GenerateConfig for DirectoryBackend {
+    fn generate_config() -> toml::Value {
+        toml::Value::try_from(DirectoryBackend {
+            path: PathBuf::from("/path/to/secrets"),
+            remove_trailing_whitespace: false,
+        })
+        .unwrap()
+    }
+}
+
+impl SecretBackend for DirectoryBackend {
+    fn retrieve(
+        &mut self,
+        secret_keys: HashSet<String>,
+        _: &mut signal::SignalRx,
+    ) -> crate::Result<HashMap<String, String>> {
+        let mut secrets = HashMap::new();
+        for k in secret_keys.into_iter() {
+            // Secret keys can contain dots, so make sure that a key can't be used to read a file
+            // outside of the configured directory.
+            if !Path::new(&k)
+                .components()
+                .all(|component| matches!(component, Component::Normal(_)))
+            {
+                return Err(format!("secret key '{}' is not a valid file name", k).into());
+            }
+
+            let path = self.path.join(&k);
+            let mut value = std::fs::read_to_string(&path).map_err(|e| {
+                format!(
+                    "secret for key '{}' was not retrieved from {:?}: {}",
+                    k, path, e
+                )
+            })?;
+            if self.remove_trailing_whitespace {
+                value.truncate(value.trim_end().len());
+            }
+            if value.is_empty() {
+                return Err(format!("secret for key '{}' was empty", k).into());
+            }
+            secrets.insert(k, value);
+        }
+        Ok(secrets)
+    }
+
+    fn watched_paths(&self) -> Vec<PathBuf> {
+        vec![self.path.clone()]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashSet;
+
+    use tokio::sync::broadcast;
+
+    use super::DirectoryBackend;
+    use crate::{config::SecretBackend, test_util::temp_dir};
+
+    fn backend(remove_trailing_whitespace: bool) -> DirectoryBackend {
+        let path = temp_dir();
+        std::fs::create_dir_all(&path).unwrap();
+        std::fs::write(path.join("username"), "vector").unwrap();
+        std::fs::write(path.join("password"), "hunter2\n").unwrap();
+        std::fs::write(path.join("empty"), "\n").unwrap();
+
+        DirectoryBackend {
+            path,
+            remove_trailing_whitespace,
+        }
+    }
+
+    fn retrieve(backend: &mut DirectoryBackend, key: &str) -> crate::Result<String> {
+        let (_tx, mut rx) = broadcast::channel(1);
+        let mut secrets = backend.retrieve(HashSet::from([key.to_string()]), &mut rx)?;
+        Ok(secrets.remove(key).unwrap())
+    }
+
+    #[test]
+    fn generate_config() {
+        crate::test_util::test_generate_config::<DirectoryBackend>();
+    }
+
+    #[test]
+    fn reads_one_secret_per_file() {
+        let mut backend = backend(false);
+        assert_eq!(retrieve(&mut backend, "username").unwrap(), "vector");
+        assert_eq!(retrieve(&mut backend, "password").unwrap(), "hunter2\n");
+        assert!(retrieve(&mut backend, "missing").is_err());
+    }
+
+    #[test]
+    fn removes_trailing_whitespace() {
+        let mut backend = backend(true);
+        assert_eq!(retrieve(&mut backend, "password").unwrap(), "hunter2");
+        assert!(retrieve(&mut backend, "empty").is_err());
+    }
+
+    #[test]
+    fn rejects_keys_outside_of_directory() {
+        let mut backend = backend(false);
+        let nested = backend.path.join("nested");
+        std::fs::create_dir_all(&nested).unwrap();
+        backend.path = nested;
+
+        assert!(retrieve(&mut backend, "..").is_err());
+    }
+}
@@ -0,0 +1,158 @@
+use std::{
+    collections::{HashMap, HashSet},
+    path::{Path, PathBuf},
+};
+
+use vector_config::{component::GenerateConfig, configurable_component};
+
+use crate::{config::SecretBackend, signal};
+
+/// Configuration for the `file` secrets backend.
+///
+/// The file is read every time secrets are retrieved, so changes to it are picked up whenever the
+/// configuration is reloaded. When Vector is started with `--watch-config`, changes to the file
+/// trigger a reload themselves.
+#[configurable_component(secrets("file"))]
+#[derive(Clone, Debug)]
+pub struct FileBackend {
+    /// File path to read secrets from.
+    ///
+    /// The file must contain a single JSON object, or YAML mapping, of secret keys to their values.
+    /// Files ending in `.json` are parsed as JSON, and all other files are parsed as YAML.
+    #[configurable(metadata(docs::examples = "/etc/vector/secrets.json"))]
+    pub path: PathBuf,
+}
+
+impl GenerateConfig for FileBackend {
+    fn generate_config() -> toml::Value {
+        toml::Value::try_from(FileBackend {
+            path: PathBuf::from("/path/to/secrets.json"),
+        })
+        .unwrap()
+    }
+}
+
+impl SecretBackend for FileBackend {
+    fn retrieve(
+        &mut self,
+        secret_keys: HashSet<String>,
+        _: &mut signal::SignalRx,
+    ) -> crate::Result<HashMap<String, String>> {
+        let mut output = read_secrets_file(&self.path)?;
+        let mut secrets = HashMap::new();
+        for k in secret_keys.into_iter() {
+            match output.remove(&k) {
+                Some(v) if v.is_empty() => {
+                    return Err(format!("secret for key '{}' was empty", k).into());
+                }
+                Some(v) => {
+                    secrets.insert(k, v);
+                }
+                None => return Err(format!("secret for key '{}' was not retrieved", k).into()),
+            }
+        }
+        Ok(secrets)
+    }
+
+    fn watched_paths(&self) -> Vec<PathBuf> {
+        vec![self.path.clone()]
+    }
+}
+
+fn read_secrets_file(path: &Path) -> crate::Result<HashMap<String, String>> {
+    let contents = std::fs::read(path)
+        .map_err(|e| format!("unable to read secrets file {:?}: {}", path, e))?;
+
+    let is_json = path
+        .extension()
+        .map_or(false, |extension| extension.eq_ignore_ascii_case("json"));
+    let secrets = if is_json {
+        serde_json::from_slice(&contents)
+            .map_err(|e| format!("unable to parse secrets file {:?}: {}", path, e))?
+    } else {
+        serde_yaml::from_slice(&contents)
+            .map_err(|e| format!("unable to parse secrets file {:?}: {}", path, e))?
+    };
+
+    Ok(secrets)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashSet;
+
+    use tokio::sync::broadcast;
+
+    use super::FileBackend;
+    use crate::{config::SecretBackend, test_util::temp_dir};
+
+    fn retrieve(backend: &mut FileBackend, keys: &[&str]) -> crate::Result<Vec<(String, String)>> {
+        let (_tx, mut rx) = broadcast::channel(1);
+        let keys = keys.iter().map(|k| k.to_string()).collect::<HashSet<_>>();
+        let mut secrets = backend
+            .retrieve(keys, &mut rx)?
+            .into_iter()
+            .collect::<Vec<_>>();
+        secrets.sort();
+        Ok(secrets)
+    }
+
+    #[test]
+    fn generate_config() {
+        crate::test_util::test_generate_config::<FileBackend>();
+    }
+
+    #[test]
+    fn reads_json_and_yaml() {
+        let dir = temp_dir();
+        std::fs::create_dir_all(&dir).unwrap();
+
+        let json = dir.join("secrets.json");
+        std::fs::write(&json, r#"{"username": "vector", "password": "hunter2"}"#).unwrap();
+        let yaml = dir.join("secrets.yaml");
+        std::fs::write(&yaml, "username: vector\npassword: hunter2\n").unwrap();
+
+        for path in [json, yaml] {
+            let mut backend = FileBackend { path };
+            assert_eq!(
+                retrieve(&mut backend, &["username", "password"]).unwrap(),
+                vec![
+                    ("password".to_string(), "hunter2".to_string()),
+                    ("username".to_string(), "vector".to_string()),
+                ]
+            );
+        }
+    }
+
+    #[test]
+    fn rereads_file_on_retrieve() {
+        let dir = temp_dir();
+        std::fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("secrets.json");
+        let mut backend = FileBackend { path: path.clone() };
+
+        std::fs::write(&path, r#"{"token": "first"}"#).unwrap();
+        assert_eq!(
+            retrieve(&mut backend, &["token"]).unwrap(),
+            vec![("token".to_string(), "first".to_string())]
+        );
+
+        std::fs::write(&path, r#"{"token": "second"}"#).unwrap();
+        assert_eq!(
+            retrieve(&mut backend, &["token"]).unwrap(),
+            vec![("token".to_string(), "second".to_string())]
+        );
+    }
+
+    #[test]
+    fn missing_or_empty_secret_is_an_error() {
+        let dir = temp_dir();
+        std::fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("secrets.json");
+        std::fs::write(&path, r#"{"empty": ""}"#).unwrap();
+        let mut backend = FileBackend { path };
+
+        assert!(retrieve(&mut backend, &["missing"]).is_err());
+        assert!(retrieve(&mut backend, &["empty"]).is_err());
+    }
+}
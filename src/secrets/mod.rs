@@ -1,13 +1,19 @@
 #![allow(missing_docs)]
-use std::collections::{HashMap, HashSet};
+use std::{
+    collections::{HashMap, HashSet},
+    path::PathBuf,
+};
 
 use enum_dispatch::enum_dispatch;
 use vector_config::{configurable_component, NamedComponent};
 
 use crate::{config::SecretBackend, signal};
 
+mod directory;
 mod exec;
+mod file;
 mod test;
+mod vault;
 
 /// Configurable secret backends in Vector.
 #[configurable_component]
@@ -15,9 +21,18 @@ mod test;
 #[enum_dispatch(SecretBackend)]
 #[serde(tag = "type", rename_all = "snake_case")]
 pub enum SecretBackends {
+    /// Directory.
+    Directory(directory::DirectoryBackend),
+
     /// Exec.
     Exec(exec::ExecBackend),
 
+    /// File.
+    File(file::FileBackend),
+
+    /// Vault.
+    Vault(vault::VaultBackend),
+
     /// Test.
     #[configurable(metadata(docs::hidden))]
     Test(test::TestBackend),
@@ -27,7 +42,10 @@ pub enum SecretBackends {
 impl NamedComponent for SecretBackends {
     fn get_component_name(&self) -> &'static str {
         match self {
+            Self::Directory(config) => config.get_component_name(),
             Self::Exec(config) => config.get_component_name(),
+            Self::File(config) => config.get_component_name(),
+            Self::Vault(config) => config.get_component_name(),
             Self::Test(config) => config.get_component_name(),
         }
     }
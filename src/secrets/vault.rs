@@ -0,0 +1,293 @@
+use std::collections::{HashMap, HashSet};
+
+use futures::executor;
+use http::{Request, StatusCode, Uri};
+use hyper::Body;
+use serde::Deserialize;
+use tokio::time;
+use vector_common::sensitive_string::SensitiveString;
+use vector_config::{component::GenerateConfig, configurable_component};
+
+use crate::{
+    config::{ProxyConfig, SecretBackend},
+    http::HttpClient,
+    signal,
+    tls::{TlsConfig, TlsSettings},
+};
+
+/// Configuration for the `vault` secrets backend.
+///
+/// Secrets are read from a single secret in a [KV version 2][kv_v2] secrets engine, with each secret
+/// key being looked up as a field of that secret.
+///
+/// [kv_v2]: https://developer.hashicorp.com/vault/docs/secrets/kv/kv-v2
+#[configurable_component(secrets("vault"))]
+#[derive(Clone, Debug)]
+pub struct VaultBackend {
+    /// The address of the Vault server.
+    #[configurable(metadata(docs::examples = "https://vault.example.com:8200"))]
+    pub address: String,
+
+    /// The path the KV secrets engine is mounted at.
+    #[serde(default = "default_mount")]
+    pub mount: String,
+
+    /// The path of the secret within the KV secrets engine.
+    #[configurable(metadata(docs::examples = "vector/production"))]
+    pub path: String,
+
+    /// The token used to authenticate with the Vault server.
+    ///
+    /// As secrets can't be used to configure secret backends, this is best provided through an
+    /// environment variable, such as `${VAULT_TOKEN}`.
+    #[configurable(metadata(docs::examples = "${VAULT_TOKEN}"))]
+    pub token: SensitiveString,
+
+    /// The namespace of the KV secrets engine, when using Vault Enterprise.
+    #[configurable(metadata(docs::examples = "ns1"))]
+    pub namespace: Option<String>,
+
+    /// The timeout, in seconds, to wait for the Vault server to respond.
+    #[serde(default = "default_timeout_secs")]
+    pub timeout: u64,
+
+    #[configurable(derived)]
+    pub tls: Option<TlsConfig>,
+}
+
+impl GenerateConfig for VaultBackend {
+    fn generate_config() -> toml::Value {
+        toml::Value::try_from(VaultBackend {
+            address: String::from("https://127.0.0.1:8200"),
+            mount: default_mount(),
+            path: String::from("vector"),
+            token: String::from("${VAULT_TOKEN}").into(),
+            namespace: None,
+            timeout: default_timeout_secs(),
+            tls: None,
+        })
+        .unwrap()
+    }
+}
+
+fn default_mount() -> String {
+    String::from("secret")
+}
+
+const fn default_timeout_secs() -> u64 {
+    5
+}
+
+#[derive(Deserialize)]
+struct KvResponse {
+    data: KvData,
+}
+
+#[derive(Deserialize)]
+struct KvData {
+    data: HashMap<String, serde_json::Value>,
+}
+
+impl VaultBackend {
+    fn secret_uri(&self) -> crate::Result<Uri> {
+        let uri = format!(
+            "{}/v1/{}/data/{}",
+            self.address.trim_end_matches('/'),
+            self.mount.trim_matches('/'),
+            self.path.trim_matches('/')
+        );
+        Ok(uri.parse()?)
+    }
+}
+
+impl SecretBackend for VaultBackend {
+    fn retrieve(
+        &mut self,
+        secret_keys: HashSet<String>,
+        signal_rx: &mut signal::SignalRx,
+    ) -> crate::Result<HashMap<String, String>> {
+        let mut output = executor::block_on(async { query_backend(self, signal_rx).await })?;
+        let mut secrets = HashMap::new();
+        for k in secret_keys.into_iter() {
+            match output.remove(&k) {
+                Some(serde_json::Value::String(v)) => {
+                    if v.is_empty() {
+                        return Err(format!("secret for key '{}' was empty", k).into());
+                    }
+                    secrets.insert(k, v);
+                }
+                Some(_) => return Err(format!("secret for key '{}' is not a string", k).into()),
+                None => return Err(format!("secret for key '{}' was not retrieved", k).into()),
+            }
+        }
+        Ok(secrets)
+    }
+}
+
+async fn query_backend(
+    backend: &VaultBackend,
+    signal_rx: &mut signal::SignalRx,
+) -> crate::Result<HashMap<String, serde_json::Value>> {
+    let tls_settings = TlsSettings::from_options(&backend.tls)?;
+    let client = HttpClient::new(tls_settings, &ProxyConfig::from_env())?;
+
+    let mut request =
+        Request::get(backend.secret_uri()?).header("X-Vault-Token", backend.token.inner());
+    if let Some(namespace) = &backend.namespace {
+        request = request.header("X-Vault-Namespace", namespace);
+    }
+    let request = request.body(Body::empty())?;
+
+    let query = async {
+        let response = client.send(request).await?;
+        let status = response.status();
+        let body = hyper::body::to_bytes(response.into_body()).await?;
+        Ok::<_, crate::Error>((status, body))
+    };
+    let timeout = time::timeout(time::Duration::from_secs(backend.timeout), query);
+
+    let (status, body) = tokio::select! {
+        biased;
+        Ok(signal::SignalTo::Shutdown | signal::SignalTo::Quit) = signal_rx.recv() => {
+            return Err("Secret retrieval was interrupted.".into());
+        }
+        result = timeout => result.map_err(|_| "Request timed-out")??,
+    };
+
+    match status {
+        StatusCode::OK => {}
+        StatusCode::NOT_FOUND => {
+            return Err(format!("secret '{}' was not found", backend.path).into());
+        }
+        status => {
+            return Err(format!(
+                "Vault responded with {}: {}",
+                status,
+                String::from_utf8_lossy(&body)
+            )
+            .into());
+        }
+    }
+
+    let response = serde_json::from_slice::<KvResponse>(&body)?;
+    Ok(response.data.data)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{collections::HashSet, convert::Infallible, net::SocketAddr};
+
+    use hyper::{
+        service::{make_service_fn, service_fn},
+        Body, Request, Response, Server, StatusCode,
+    };
+    use tokio::sync::broadcast;
+
+    use super::{default_mount, default_timeout_secs, VaultBackend};
+    use crate::{config::SecretBackend, test_util::next_addr};
+
+    const TOKEN: &str = "s.test-token";
+
+    /// Spawns a stand-in for a Vault server, serving a single secret from a KV version 2 engine.
+    fn spawn_vault(addr: SocketAddr) {
+        let make_svc = make_service_fn(|_| async {
+            Ok::<_, Infallible>(service_fn(|req: Request<Body>| async move {
+                let authorized = req
+                    .headers()
+                    .get("X-Vault-Token")
+                    .map_or(false, |token| token == TOKEN);
+                let response = if !authorized {
+                    Response::builder()
+                        .status(StatusCode::FORBIDDEN)
+                        .body(Body::from(r#"{"errors":["permission denied"]}"#))
+                } else if req.uri().path() == "/v1/secret/data/vector" {
+                    Response::builder().body(Body::from(
+                        r#"{"data":{"data":{"username":"vector","password":"hunter2","port":8200},"metadata":{"version":3}}}"#,
+                    ))
+                } else {
+                    Response::builder()
+                        .status(StatusCode::NOT_FOUND)
+                        .body(Body::from(r#"{"errors":[]}"#))
+                };
+                Ok::<_, Infallible>(response.unwrap())
+            }))
+        });
+        tokio::spawn(Server::bind(&addr).serve(make_svc));
+    }
+
+    fn backend(addr: SocketAddr, path: &str, token: &str) -> VaultBackend {
+        VaultBackend {
+            address: format!("http://{}/", addr),
+            mount: default_mount(),
+            path: path.to_string(),
+            token: token.to_string().into(),
+            namespace: None,
+            timeout: default_timeout_secs(),
+            tls: None,
+        }
+    }
+
+    async fn retrieve(
+        mut backend: VaultBackend,
+        keys: &[&str],
+    ) -> crate::Result<Vec<(String, String)>> {
+        let keys = keys.iter().map(|k| k.to_string()).collect::<HashSet<_>>();
+        tokio::task::spawn_blocking(move || {
+            let (_tx, mut rx) = broadcast::channel(1);
+            let mut secrets = backend
+                .retrieve(keys, &mut rx)?
+                .into_iter()
+                .collect::<Vec<_>>();
+            secrets.sort();
+            Ok(secrets)
+        })
+        .await
+        .unwrap()
+    }
+
+    #[test]
+    fn generate_config() {
+        crate::test_util::test_generate_config::<VaultBackend>();
+    }
+
+    #[tokio::test]
+    async fn reads_secret_fields() {
+        let addr = next_addr();
+        spawn_vault(addr);
+        crate::test_util::wait_for_tcp(addr).await;
+
+        let secrets = retrieve(backend(addr, "vector", TOKEN), &["username", "password"])
+            .await
+            .unwrap();
+        assert_eq!(
+            secrets,
+            vec![
+                ("password".to_string(), "hunter2".to_string()),
+                ("username".to_string(), "vector".to_string()),
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn reports_errors() {
+        let addr = next_addr();
+        spawn_vault(addr);
+        crate::test_util::wait_for_tcp(addr).await;
+
+        // Field missing from the secret, or not a string.
+        assert!(retrieve(backend(addr, "vector", TOKEN), &["missing"])
+            .await
+            .is_err());
+        assert!(retrieve(backend(addr, "vector", TOKEN), &["port"])
+            .await
+            .is_err());
+
+        // Secret that doesn't exist, or a token that isn't allowed to read it.
+        assert!(retrieve(backend(addr, "missing", TOKEN), &["username"])
+            .await
+            .is_err());
+        assert!(retrieve(backend(addr, "vector", "s.wrong"), &["username"])
+            .await
+            .is_err());
+    }
+}
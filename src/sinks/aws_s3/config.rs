@@ -2,7 +2,7 @@ use std::convert::TryInto;
 
 use aws_sdk_s3::Client as S3Client;
 use codecs::{
-    encoding::{Framer, FramingConfig},
+    encoding::{BatchSerializerConfig, Framer, FramingConfig},
     TextSerializerConfig,
 };
 use tower::ServiceBuilder;
@@ -12,7 +12,7 @@ use vector_core::sink::VectorSink;
 use super::sink::S3RequestOptions;
 use crate::{
     aws::{AwsAuthentication, RegionOrEndpoint},
-    codecs::{Encoder, EncodingConfigWithFraming, SinkType},
+    codecs::{BatchEncoder, Encoder, EncoderKind, EncodingConfigWithFraming, SinkType},
     config::{AcknowledgementsConfig, GenerateConfig, Input, ProxyConfig, SinkConfig, SinkContext},
     sinks::{
         s3_common::{
@@ -23,8 +23,8 @@ use crate::{
             sink::S3Sink,
         },
         util::{
-            BatchConfig, BulkSizeBasedDefaultBatchSettings, Compression, ServiceBuilderExt,
-            TowerRequestConfig,
+            AnyCompression, BatchConfig, BulkSizeBasedDefaultBatchSettings, Compression,
+            ServiceBuilderExt, TowerRequestConfig,
         },
         Healthcheck,
     },
@@ -91,7 +91,8 @@ pub struct S3SinkConfig {
 
     /// The filename extension to use in the object key.
     ///
-    /// This overrides setting the extension based on the configured `compression`.
+    /// This overrides setting the extension based on the configured `compression`, or on the
+    /// configured `batch_encoding`.
     #[configurable(metadata(docs::examples = "json"))]
     pub filename_extension: Option<String>,
 
@@ -104,6 +105,16 @@ pub struct S3SinkConfig {
     #[serde(flatten)]
     pub encoding: EncodingConfigWithFraming,
 
+    /// Batch encoding configuration.
+    ///
+    /// When set, each batch of events is encoded as a single object with the configured codec,
+    /// such as a Parquet file, instead of as a stream of framed events. The codec and framing of
+    /// `encoding` are then ignored, but its other options still apply. As the codec handles its own
+    /// compression, `compression` is ignored as well.
+    #[configurable(derived)]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub batch_encoding: Option<BatchSerializerConfig>,
+
     /// Compression configuration.
     ///
     /// All compression algorithms use the default compression level unless otherwise specified.
@@ -111,8 +122,8 @@ pub struct S3SinkConfig {
     /// Some cloud storage API clients and browsers handle decompression transparently, so
     /// depending on how they are accessed, files may not always appear to be compressed.
     #[configurable(derived)]
-    #[serde(default = "Compression::gzip_default")]
-    pub compression: Compression,
+    #[serde(default = "AnyCompression::gzip_default")]
+    pub compression: AnyCompression,
 
     #[configurable(derived)]
     #[serde(default)]
@@ -157,7 +168,8 @@ impl GenerateConfig for S3SinkConfig {
             options: S3Options::default(),
             region: RegionOrEndpoint::default(),
             encoding: (None::<FramingConfig>, TextSerializerConfig::default()).into(),
-            compression: Compression::gzip_default(),
+            batch_encoding: None,
+            compression: AnyCompression::gzip_default(),
             batch: BatchConfig::default(),
             request: TowerRequestConfig::default(),
             tls: Some(TlsConfig::default()),
@@ -179,7 +191,11 @@ impl SinkConfig for S3SinkConfig {
     }
 
     fn input(&self) -> Input {
-        Input::new(self.encoding.config().1.input_type())
+        let input_type = match &self.batch_encoding {
+            Some(batch_encoding) => batch_encoding.input_type(),
+            None => self.encoding.config().1.input_type(),
+        };
+        Input::new(input_type)
     }
 
     fn acknowledgements(&self) -> &AcknowledgementsConfig {
@@ -211,17 +227,31 @@ impl S3SinkConfig {
         let partitioner = S3KeyPartitioner::new(key_prefix, ssekms_key_id);
 
         let transformer = self.encoding.transformer();
-        let (framer, serializer) = self.encoding.build(SinkType::MessageBased)?;
-        let encoder = Encoder::<Framer>::new(framer, serializer);
+        let mut filename_extension = self.filename_extension.clone();
+        let (encoder, compression) = match &self.batch_encoding {
+            Some(batch_encoding) => {
+                let encoder = BatchEncoder::new(batch_encoding.build()?);
+                filename_extension.get_or_insert_with(|| encoder.extension().to_string());
+                (EncoderKind::Batch(encoder), Compression::None)
+            }
+            None => {
+                let (framer, serializer) = self.encoding.build(SinkType::MessageBased)?;
+                let encoder = Encoder::<Framer>::new(framer, serializer);
+                (
+                    EncoderKind::Framed(Box::new(encoder)),
+                    self.compression.into(),
+                )
+            }
+        };
 
         let request_options = S3RequestOptions {
             bucket: self.bucket.clone(),
             api_options: self.options.clone(),
-            filename_extension: self.filename_extension.clone(),
+            filename_extension,
             filename_time_format: self.filename_time_format.clone(),
             filename_append_uuid: self.filename_append_uuid,
             encoder: (transformer, encoder),
-            compression: self.compression,
+            compression,
         };
 
         let sink = S3Sink::new(service, request_options, partitioner, batch_settings);
@@ -222,7 +222,7 @@ async fn s3_gzip() {
     let batch_size = 1_000;
     let batch_multiplier = 3;
     let config = S3SinkConfig {
-        compression: Compression::gzip_default(),
+        compression: Compression::gzip_default().into(),
         filename_time_format: "%s%f".into(),
         ..config(&bucket, batch_size)
     };
@@ -267,7 +267,7 @@ async fn s3_zstd() {
     let batch_size = 1_000;
     let batch_multiplier = 3;
     let config = S3SinkConfig {
-        compression: Compression::zstd_default(),
+        compression: Compression::zstd_default().into(),
         filename_time_format: "%s%f".into(),
         ..config(&bucket, batch_size)
     };
@@ -428,7 +428,8 @@ async fn s3_flush_on_exhaustion() {
             options: S3Options::default(),
             region: RegionOrEndpoint::with_both("minio", s3_address()),
             encoding: (None::<FramingConfig>, TextSerializerConfig::default()).into(),
-            compression: Compression::None,
+            batch_encoding: None,
+            compression: Compression::None.into(),
             batch,
             request: TowerRequestConfig::default(),
             tls: Default::default(),
@@ -511,7 +512,8 @@ fn config(bucket: &str, batch_size: usize) -> S3SinkConfig {
         options: S3Options::default(),
         region: RegionOrEndpoint::with_both("minio", s3_address()),
         encoding: (None::<FramingConfig>, TextSerializerConfig::default()).into(),
-        compression: Compression::None,
+        batch_encoding: None,
+        compression: Compression::None.into(),
         batch,
         request: TowerRequestConfig::default(),
         tls: Default::default(),
@@ -131,7 +131,7 @@ async fn azure_blob_insert_lines_into_blob_gzip() {
     let config = AzureBlobSinkConfig::new_emulator().await;
     let config = AzureBlobSinkConfig {
         blob_prefix: blob_prefix.clone().try_into().unwrap(),
-        compression: Compression::gzip_default(),
+        compression: Compression::gzip_default().into(),
         ..config
     };
     let (lines, events) = random_lines_with_stream(100, 10, None);
@@ -161,7 +161,7 @@ async fn azure_blob_insert_json_into_blob_gzip() {
             JsonSerializerConfig::default(),
         )
             .into(),
-        compression: Compression::gzip_default(),
+        compression: Compression::gzip_default().into(),
         ..config
     };
     let (events, input) = random_events_with_stream(100, 10, None);
@@ -230,7 +230,8 @@ impl AzureBlobSinkConfig {
                 blob_time_format: None,
                 blob_append_uuid: None,
                 encoding: (None::<FramingConfig>, TextSerializerConfig::default()).into(),
-                compression: Compression::None,
+                batch_encoding: None,
+                compression: Compression::None.into(),
                 batch: Default::default(),
                 request: TowerRequestConfig::default(),
                 acknowledgements: Default::default(),
@@ -316,7 +317,7 @@ impl AzureBlobSinkConfig {
     fn get_blob_content(&self, data: Vec<u8>) -> Vec<String> {
         let body = BytesMut::from(data.as_slice()).freeze().reader();
 
-        if self.compression == Compression::None {
+        if self.compression.0 == Compression::None {
             BufReader::new(body).lines().map(|l| l.unwrap()).collect()
         } else {
             BufReader::new(GzDecoder::new(body))
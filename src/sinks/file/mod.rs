@@ -1,11 +1,12 @@
 use std::convert::TryFrom;
+use std::io::Write as _;
+use std::num::NonZeroUsize;
 use std::time::{Duration, Instant};
 
-use async_compression::tokio::write::{GzipEncoder, ZstdEncoder};
 use async_trait::async_trait;
 use bytes::{Bytes, BytesMut};
 use codecs::{
-    encoding::{Framer, FramingConfig},
+    encoding::{BatchSerializerConfig, Framer, FramingConfig},
     TextSerializerConfig,
 };
 use futures::{
@@ -21,17 +22,20 @@ use tokio::{
 use tokio_util::codec::Encoder as _;
 use vector_config::configurable_component;
 use vector_core::{
+    event::EventFinalizers,
     internal_event::{CountByteSize, EventsSent, InternalEventHandle as _, Output, Registered},
-    EstimatedJsonEncodedSizeOf,
+    ByteSizeOf, EstimatedJsonEncodedSizeOf,
 };
 
 use crate::{
-    codecs::{Encoder, EncodingConfigWithFraming, SinkType, Transformer},
+    codecs::{
+        BatchEncoder, Encoder, EncoderKind, EncodingConfigWithFraming, SinkType, Transformer,
+    },
     config::{AcknowledgementsConfig, DataType, GenerateConfig, Input, SinkConfig, SinkContext},
     event::{Event, EventStatus, Finalizable},
     expiring_hash_map::ExpiringHashMap,
     internal_events::{FileBytesSent, FileIoError, FileOpen, TemplateRenderingError},
-    sinks::util::StreamSink,
+    sinks::util::{AnyCompression, Compression, Compressor, StreamSink},
     template::Template,
 };
 mod bytes_path;
@@ -67,12 +71,37 @@ pub struct FileSinkConfig {
     #[serde(flatten)]
     pub encoding: EncodingConfigWithFraming,
 
+    /// Batch encoding configuration.
+    ///
+    /// When set, the events of each file are encoded together with the configured codec, such as a
+    /// Parquet file, instead of as a stream of framed events. The events are held in memory until
+    /// the file is closed, either once it has been idle for `idle_timeout_secs` or when Vector
+    /// shuts down, and only then written out to a new file. Writing to a file that already exists
+    /// fails, so `path` must contain a component that changes often enough, such as a timestamp.
+    ///
+    /// Once the held events reach one of the limits of `batch`, they are written out early, and the
+    /// following events go to a new file. Its name is `path` with a sequence number inserted
+    /// before the extension, such as `/tmp/vector.1.parquet` for `/tmp/vector.parquet`.
+    ///
+    /// The codec and framing of `encoding` are then ignored, but its other options still apply. As
+    /// the codec handles its own compression, `compression` is ignored as well.
+    #[configurable(derived)]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub batch_encoding: Option<BatchSerializerConfig>,
+
+    #[configurable(derived)]
+    #[serde(
+        default,
+        skip_serializing_if = "crate::serde::skip_serializing_if_default"
+    )]
+    pub batch: FileBatchConfig,
+
     #[configurable(derived)]
     #[serde(
         default,
         skip_serializing_if = "crate::serde::skip_serializing_if_default"
     )]
-    pub compression: Compression,
+    pub compression: AnyCompression,
 
     #[configurable(derived)]
     #[serde(
@@ -89,6 +118,8 @@ impl GenerateConfig for FileSinkConfig {
             path: Template::try_from("/tmp/vector-%Y-%m-%d.log").unwrap(),
             idle_timeout: default_idle_timeout(),
             encoding: (None::<FramingConfig>, TextSerializerConfig::default()).into(),
+            batch_encoding: None,
+            batch: Default::default(),
             compression: Default::default(),
             acknowledgements: Default::default(),
         })
@@ -100,64 +131,209 @@ const fn default_idle_timeout() -> Duration {
     Duration::from_secs(30)
 }
 
-/// Compression configuration.
-// TODO: Why doesn't this already use `crate::sinks::util::Compression`
-// `crate::sinks::util::Compression` doesn't support zstd yet
+/// Limits on the events held in memory for a file when `batch_encoding` is set.
 #[configurable_component]
-#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
-#[serde(rename_all = "snake_case")]
-pub enum Compression {
-    /// [Gzip][gzip] compression.
-    ///
-    /// [gzip]: https://www.gzip.org/
-    Gzip,
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct FileBatchConfig {
+    /// The maximum number of events held for a file before they are written out.
+    #[serde(default)]
+    #[configurable(metadata(docs::type_unit = "events"))]
+    pub max_events: Option<NonZeroUsize>,
 
-    /// [Zstandard][zstd] compression.
+    /// The maximum size of the events held for a file before they are written out.
     ///
-    /// [zstd]: https://facebook.github.io/zstd/
-    Zstd,
+    /// This is based on the in-memory size of the events, before they are encoded.
+    #[serde(default = "default_max_bytes")]
+    #[configurable(metadata(docs::type_unit = "bytes"))]
+    pub max_bytes: Option<NonZeroUsize>,
+}
 
-    /// No compression.
-    #[default]
-    None,
+impl Default for FileBatchConfig {
+    fn default() -> Self {
+        Self {
+            max_events: None,
+            max_bytes: default_max_bytes(),
+        }
+    }
+}
+
+const fn default_max_bytes() -> Option<NonZeroUsize> {
+    NonZeroUsize::new(10_000_000)
+}
+
+impl FileBatchConfig {
+    fn is_reached(&self, events: usize, byte_size: usize) -> bool {
+        self.max_events.map_or(false, |max| events >= max.get())
+            || self.max_bytes.map_or(false, |max| byte_size >= max.get())
+    }
 }
 
 enum OutFile {
     Regular(File),
-    Gzip(GzipEncoder<File>),
-    Zstd(ZstdEncoder<File>),
+    Compressed {
+        file: File,
+        // Only `None` once the compressor has been finished when shutting down.
+        compressor: Option<Compressor>,
+    },
+    /// Holds the events until the file is closed or the batch is full, as the batch encoder
+    /// encodes them all at once.
+    Batched {
+        path: Bytes,
+        // The number of batches already written out, used to name the file of the next one.
+        part: usize,
+        encoder: BatchEncoder,
+        events: Vec<Event>,
+        byte_size: usize,
+        finalizers: EventFinalizers,
+        events_sent: Registered<EventsSent>,
+    },
 }
 
 impl OutFile {
     fn new(file: File, compression: Compression) -> Self {
         match compression {
             Compression::None => OutFile::Regular(file),
-            Compression::Gzip => OutFile::Gzip(GzipEncoder::new(file)),
-            Compression::Zstd => OutFile::Zstd(ZstdEncoder::new(file)),
+            compression => OutFile::Compressed {
+                file,
+                compressor: Some(Compressor::from(compression)),
+            },
+        }
+    }
+
+    fn batched(path: Bytes, encoder: BatchEncoder, events_sent: Registered<EventsSent>) -> Self {
+        OutFile::Batched {
+            path,
+            part: 0,
+            encoder,
+            events: Vec::new(),
+            byte_size: 0,
+            finalizers: EventFinalizers::default(),
+            events_sent,
+        }
+    }
+
+    /// Holds an event until the batch is written out, returning whether the batch is now full.
+    fn buffer_event(&mut self, mut event: Event, limits: &FileBatchConfig) -> bool {
+        let OutFile::Batched {
+            events,
+            byte_size,
+            finalizers,
+            ..
+        } = self
+        else {
+            unreachable!("only batched files buffer events");
+        };
+        *byte_size += event.size_of();
+        finalizers.merge(event.take_finalizers());
+        events.push(event);
+        limits.is_reached(events.len(), *byte_size)
+    }
+
+    /// The number of events that are dropped if closing the file fails.
+    fn buffered_events(&self) -> usize {
+        match self {
+            OutFile::Batched { events, .. } => events.len(),
+            OutFile::Regular(_) | OutFile::Compressed { .. } => 0,
         }
     }
 
     async fn sync_all(&mut self) -> Result<(), std::io::Error> {
         match self {
             OutFile::Regular(file) => file.sync_all().await,
-            OutFile::Gzip(gzip) => gzip.get_mut().sync_all().await,
-            OutFile::Zstd(zstd) => zstd.get_mut().sync_all().await,
+            OutFile::Compressed { file, .. } => file.sync_all().await,
+            OutFile::Batched { .. } => Ok(()),
         }
     }
 
     async fn shutdown(&mut self) -> Result<(), std::io::Error> {
         match self {
             OutFile::Regular(file) => file.shutdown().await,
-            OutFile::Gzip(gzip) => gzip.shutdown().await,
-            OutFile::Zstd(zstd) => zstd.shutdown().await,
+            OutFile::Compressed { file, compressor } => {
+                if let Some(compressor) = compressor.take() {
+                    file.write_all(&compressor.finish()?).await?;
+                }
+                file.shutdown().await
+            }
+            OutFile::Batched { .. } => self.write_batch().await,
+        }
+    }
+
+    /// Encodes the buffered events all at once, and writes them out to a new file.
+    async fn write_batch(&mut self) -> Result<(), std::io::Error> {
+        let OutFile::Batched {
+            path,
+            part,
+            encoder,
+            events,
+            byte_size,
+            finalizers,
+            events_sent,
+        } = self
+        else {
+            return Ok(());
+        };
+        let events = std::mem::take(events);
+        let finalizers = std::mem::take(finalizers);
+        *byte_size = 0;
+        if events.is_empty() {
+            return Ok(());
+        }
+        let path = part_path(path, *part);
+        *part += 1;
+
+        let count = events.len();
+        let event_size = events.estimated_json_encoded_size_of();
+        let mut buffer = BytesMut::new();
+        let result = async {
+            encoder
+                .encode(events, &mut buffer)
+                .map_err(|error| std::io::Error::new(std::io::ErrorKind::InvalidData, error))?;
+            let mut file = create_file(BytesPath::new(path.clone())).await?;
+            file.write_all(&buffer).await?;
+            file.shutdown().await?;
+            file.sync_all().await
+        }
+        .await;
+
+        match result {
+            Ok(()) => {
+                finalizers.update_status(EventStatus::Delivered);
+                events_sent.emit(CountByteSize(count, event_size));
+                emit!(FileBytesSent {
+                    byte_size: buffer.len(),
+                    file: String::from_utf8_lossy(&path[..]),
+                });
+                Ok(())
+            }
+            Err(error) => {
+                finalizers.update_status(EventStatus::Errored);
+                Err(error)
+            }
         }
     }
 
     async fn write_all(&mut self, src: &[u8]) -> Result<(), std::io::Error> {
         match self {
             OutFile::Regular(file) => file.write_all(src).await,
-            OutFile::Gzip(gzip) => gzip.write_all(src).await,
-            OutFile::Zstd(zstd) => zstd.write_all(src).await,
+            OutFile::Batched { .. } => Err(std::io::Error::new(
+                std::io::ErrorKind::Other,
+                "batched files are written when closed",
+            )),
+            OutFile::Compressed { file, compressor } => {
+                let compressor = compressor.as_mut().ok_or_else(|| {
+                    std::io::Error::new(std::io::ErrorKind::Other, "file already shut down")
+                })?;
+                compressor.write_all(src)?;
+                // Write out whatever the compressor has produced so far, so that compressed data
+                // doesn't pile up in memory until the file is closed.
+                let output = compressor.take_output();
+                if output.is_empty() {
+                    Ok(())
+                } else {
+                    file.write_all(&output).await
+                }
+            }
         }
     }
 
@@ -169,6 +345,30 @@ impl OutFile {
     }
 }
 
+/// Returns the path of the given part of a batched file, which is the path itself for the first
+/// part, and otherwise has the part number inserted before the extension.
+fn part_path(path: &Bytes, part: usize) -> Bytes {
+    if part == 0 {
+        return path.clone();
+    }
+
+    let file_name_start = path
+        .iter()
+        .rposition(|&byte| byte == b'/')
+        .map_or(0, |index| index + 1);
+    let split_at = path[file_name_start..]
+        .iter()
+        .rposition(|&byte| byte == b'.')
+        .filter(|&index| index > 0)
+        .map_or(path.len(), |index| file_name_start + index);
+
+    let mut buffer = BytesMut::with_capacity(path.len() + 8);
+    buffer.extend_from_slice(&path[..split_at]);
+    buffer.extend_from_slice(format!(".{}", part).as_bytes());
+    buffer.extend_from_slice(&path[split_at..]);
+    buffer.freeze()
+}
+
 #[async_trait::async_trait]
 #[typetag::serde(name = "file")]
 impl SinkConfig for FileSinkConfig {
@@ -184,7 +384,11 @@ impl SinkConfig for FileSinkConfig {
     }
 
     fn input(&self) -> Input {
-        Input::new(self.encoding.config().1.input_type() & DataType::Log)
+        let input_type = match &self.batch_encoding {
+            Some(batch_encoding) => batch_encoding.input_type(),
+            None => self.encoding.config().1.input_type(),
+        };
+        Input::new(input_type & DataType::Log)
     }
 
     fn acknowledgements(&self) -> &AcknowledgementsConfig {
@@ -195,18 +399,33 @@ impl SinkConfig for FileSinkConfig {
 pub struct FileSink {
     path: Template,
     transformer: Transformer,
-    encoder: Encoder<Framer>,
+    encoder: EncoderKind,
     idle_timeout: Duration,
     files: ExpiringHashMap<Bytes, OutFile>,
+    batch: FileBatchConfig,
     compression: Compression,
     events_sent: Registered<EventsSent>,
 }
 
 impl FileSink {
     pub fn new(config: &FileSinkConfig) -> crate::Result<Self> {
+        if config.compression.0 == Compression::Snappy {
+            // The raw Snappy format compresses its whole input as a single block, which would mean
+            // holding everything written to a file in memory until the file is closed.
+            return Err(
+                "The `snappy` compression can't be used with files, use `snappy_framed` instead."
+                    .into(),
+            );
+        }
+
         let transformer = config.encoding.transformer();
-        let (framer, serializer) = config.encoding.build(SinkType::StreamBased)?;
-        let encoder = Encoder::<Framer>::new(framer, serializer);
+        let encoder = match &config.batch_encoding {
+            Some(batch_encoding) => EncoderKind::Batch(BatchEncoder::new(batch_encoding.build()?)),
+            None => {
+                let (framer, serializer) = config.encoding.build(SinkType::StreamBased)?;
+                EncoderKind::Framed(Box::new(Encoder::<Framer>::new(framer, serializer)))
+            }
+        };
 
         Ok(Self {
             path: config.path.clone(),
@@ -214,7 +433,8 @@ impl FileSink {
             encoder,
             idle_timeout: config.idle_timeout,
             files: ExpiringHashMap::default(),
-            compression: config.compression,
+            batch: config.batch,
+            compression: config.compression.into(),
             events_sent: register!(EventsSent::from(Output(None))),
         })
     }
@@ -256,13 +476,14 @@ impl FileSink {
                             // Close all the open files.
                             debug!(message = "Closing all the open files.");
                             for (path, file) in self.files.iter_mut() {
+                                let dropped_events = file.buffered_events();
                                 if let Err(error) = file.close().await {
                                     emit!(FileIoError {
                                         error,
                                         code: "failed_closing_file",
                                         message: "Failed to close file.",
                                         path,
-                                        dropped_events: 0,
+                                        dropped_events,
                                     });
                                 } else{
                                     trace!(message = "Successfully closed file.", path = ?path);
@@ -285,13 +506,14 @@ impl FileSink {
                         Some((mut expired_file, path)) => {
                             // We got an expired file. All we really want is to
                             // flush and close it.
+                            let dropped_events = expired_file.buffered_events();
                             if let Err(error) = expired_file.close().await {
                                 emit!(FileIoError {
                                     error,
                                     code: "failed_closing_file",
                                     message: "Failed to close file.",
                                     path: &path,
-                                    dropped_events: 0,
+                                    dropped_events,
                                 });
                             }
                             drop(expired_file); // ignore close error
@@ -326,6 +548,15 @@ impl FileSink {
         let file = if let Some(file) = self.files.reset_at(&path, next_deadline) {
             trace!(message = "Working with an already opened file.", path = ?path);
             file
+        } else if let EncoderKind::Batch(encoder) = &self.encoder {
+            // The file is only created once all of its events have been encoded.
+            let outfile = OutFile::batched(path.clone(), encoder.clone(), self.events_sent.clone());
+
+            self.files.insert_at(path.clone(), outfile, next_deadline);
+            emit!(FileOpen {
+                count: self.files.len()
+            });
+            self.files.get_mut(&path).unwrap()
         } else {
             trace!(message = "Opening new file.", ?path);
             let file = match open_file(BytesPath::new(path.clone())).await {
@@ -355,10 +586,31 @@ impl FileSink {
             self.files.get_mut(&path).unwrap()
         };
 
+        if let OutFile::Batched { .. } = file {
+            trace!(message = "Buffering an event until the batch is written.", path = ?path);
+            self.transformer.transform(&mut event);
+            if file.buffer_event(event, &self.batch) {
+                let dropped_events = file.buffered_events();
+                if let Err(error) = file.write_batch().await {
+                    emit!(FileIoError {
+                        code: "failed_writing_file",
+                        message: "Failed to write the file.",
+                        error,
+                        path: &path,
+                        dropped_events,
+                    });
+                }
+            }
+            return;
+        }
+        let EncoderKind::Framed(encoder) = &mut self.encoder else {
+            unreachable!("only batch encoders buffer events");
+        };
+
         trace!(message = "Writing an event to file.", path = ?path);
         let event_size = event.estimated_json_encoded_size_of();
         let finalizers = event.take_finalizers();
-        match write_event_to_file(file, event, &self.transformer, &mut self.encoder).await {
+        match write_event_to_file(file, event, &self.transformer, encoder).await {
             Ok(byte_size) => {
                 finalizers.update_status(EventStatus::Delivered);
                 self.events_sent.emit(CountByteSize(1, event_size));
@@ -397,6 +649,22 @@ async fn open_file(path: impl AsRef<std::path::Path>) -> std::io::Result<File> {
         .await
 }
 
+/// Creates a file that doesn't exist yet, as the output of batch encoders can't be appended to.
+async fn create_file(path: impl AsRef<std::path::Path>) -> std::io::Result<File> {
+    let parent = path.as_ref().parent();
+
+    if let Some(parent) = parent {
+        fs::create_dir_all(parent).await?;
+    }
+
+    fs::OpenOptions::new()
+        .read(false)
+        .write(true)
+        .create_new(true)
+        .open(path)
+        .await
+}
+
 async fn write_event_to_file(
     file: &mut OutFile,
     mut event: Event,
@@ -423,7 +691,12 @@ impl StreamSink<Event> for FileSink {
 
 #[cfg(test)]
 mod tests {
-    use std::convert::TryInto;
+    use std::{collections::BTreeMap, convert::TryInto};
+
+    use codecs::{
+        encoding::{ParquetColumnType, ParquetSerializerOptions},
+        ParquetSerializerConfig,
+    };
 
     use futures::{stream, SinkExt};
     use similar_asserts::assert_eq;
@@ -434,7 +707,8 @@ mod tests {
         config::log_schema,
         test_util::{
             components::{assert_sink_compliance, FILE_SINK_TAGS},
-            lines_from_file, lines_from_gzip_file, lines_from_zstd_file, random_events_with_stream,
+            lines_from_file, lines_from_gzip_file, lines_from_lz4_file,
+            lines_from_snappy_framed_file, lines_from_zstd_file, random_events_with_stream,
             random_lines_with_stream, temp_dir, temp_file, trace_init,
         },
     };
@@ -452,7 +726,9 @@ mod tests {
             path: template.clone().try_into().unwrap(),
             idle_timeout: default_idle_timeout(),
             encoding: (None::<FramingConfig>, TextSerializerConfig::default()).into(),
-            compression: Compression::None,
+            batch_encoding: None,
+            batch: Default::default(),
+            compression: Compression::None.into(),
             acknowledgements: Default::default(),
         };
 
@@ -474,7 +750,9 @@ mod tests {
             path: template.clone().try_into().unwrap(),
             idle_timeout: default_idle_timeout(),
             encoding: (None::<FramingConfig>, TextSerializerConfig::default()).into(),
-            compression: Compression::Gzip,
+            batch_encoding: None,
+            batch: Default::default(),
+            compression: Compression::gzip_default().into(),
             acknowledgements: Default::default(),
         };
 
@@ -496,7 +774,9 @@ mod tests {
             path: template.clone().try_into().unwrap(),
             idle_timeout: default_idle_timeout(),
             encoding: (None::<FramingConfig>, TextSerializerConfig::default()).into(),
-            compression: Compression::Zstd,
+            batch_encoding: None,
+            batch: Default::default(),
+            compression: Compression::zstd_default().into(),
             acknowledgements: Default::default(),
         };
 
@@ -510,6 +790,157 @@ mod tests {
         }
     }
 
+    #[tokio::test]
+    async fn single_partition_lz4() {
+        let template = temp_file();
+
+        let config = FileSinkConfig {
+            path: template.clone().try_into().unwrap(),
+            idle_timeout: default_idle_timeout(),
+            encoding: (None::<FramingConfig>, TextSerializerConfig::default()).into(),
+            batch_encoding: None,
+            batch: Default::default(),
+            compression: Compression::Lz4.into(),
+            acknowledgements: Default::default(),
+        };
+
+        let (input, _) = random_lines_with_stream(100, 64, None);
+
+        run_assert_log_sink(config, input.clone()).await;
+
+        let output = lines_from_lz4_file(template);
+        assert_eq!(input, output);
+    }
+
+    #[tokio::test]
+    async fn single_partition_snappy_framed() {
+        let template = temp_file();
+
+        let config = FileSinkConfig {
+            path: template.clone().try_into().unwrap(),
+            idle_timeout: default_idle_timeout(),
+            encoding: (None::<FramingConfig>, TextSerializerConfig::default()).into(),
+            batch_encoding: None,
+            batch: Default::default(),
+            compression: Compression::SnappyFramed.into(),
+            acknowledgements: Default::default(),
+        };
+
+        let (input, _) = random_lines_with_stream(100, 64, None);
+
+        run_assert_log_sink(config, input.clone()).await;
+
+        let output = lines_from_snappy_framed_file(template);
+        assert_eq!(input, output);
+    }
+
+    #[tokio::test]
+    async fn single_partition_batch_encoding() {
+        let template = temp_file();
+
+        let config = FileSinkConfig {
+            path: template.clone().try_into().unwrap(),
+            idle_timeout: default_idle_timeout(),
+            encoding: (None::<FramingConfig>, TextSerializerConfig::default()).into(),
+            batch_encoding: Some(
+                ParquetSerializerConfig::new(ParquetSerializerOptions {
+                    schema: Some(BTreeMap::from([(
+                        log_schema().message_key().unwrap().to_string(),
+                        ParquetColumnType::String,
+                    )])),
+                    ..Default::default()
+                })
+                .into(),
+            ),
+            batch: Default::default(),
+            compression: Compression::None.into(),
+            acknowledgements: Default::default(),
+        };
+
+        let (input, _events) = random_lines_with_stream(100, 64, None);
+
+        run_assert_log_sink(config, input).await;
+
+        // The events are written out as a single Parquet file, which starts and ends with the
+        // `PAR1` magic number.
+        let output = std::fs::read(template).unwrap();
+        assert!(output.starts_with(b"PAR1"));
+        assert!(output.ends_with(b"PAR1"));
+    }
+
+    #[tokio::test]
+    async fn single_partition_batch_encoding_rolls_files() {
+        let template = temp_file();
+
+        let config = FileSinkConfig {
+            path: template.clone().try_into().unwrap(),
+            idle_timeout: default_idle_timeout(),
+            encoding: (None::<FramingConfig>, TextSerializerConfig::default()).into(),
+            batch_encoding: Some(
+                ParquetSerializerConfig::new(ParquetSerializerOptions {
+                    schema: Some(BTreeMap::from([(
+                        log_schema().message_key().unwrap().to_string(),
+                        ParquetColumnType::String,
+                    )])),
+                    ..Default::default()
+                })
+                .into(),
+            ),
+            batch: FileBatchConfig {
+                max_events: NonZeroUsize::new(40),
+                max_bytes: None,
+            },
+            compression: Compression::None.into(),
+            acknowledgements: Default::default(),
+        };
+
+        let (input, _events) = random_lines_with_stream(100, 64, None);
+
+        run_assert_log_sink(config, input).await;
+
+        // Two full batches are written out as they fill up, and the rest once the sink shuts down.
+        let path = Bytes::from(template.to_string_lossy().into_owned());
+        for part in 0..3 {
+            let output = std::fs::read(BytesPath::new(part_path(&path, part))).unwrap();
+            assert!(output.starts_with(b"PAR1"));
+            assert!(output.ends_with(b"PAR1"));
+        }
+        assert!(!BytesPath::new(part_path(&path, 3)).as_ref().exists());
+    }
+
+    #[test]
+    fn part_paths() {
+        for (path, part, expected) in [
+            ("/tmp/vector.parquet", 0, "/tmp/vector.parquet"),
+            ("/tmp/vector.parquet", 2, "/tmp/vector.2.parquet"),
+            ("/tmp/vector", 1, "/tmp/vector.1"),
+            ("/tmp/dir.d/vector", 1, "/tmp/dir.d/vector.1"),
+            ("/tmp/.vector", 1, "/tmp/.vector.1"),
+        ] {
+            assert_eq!(
+                part_path(&Bytes::from(path), part),
+                Bytes::from(expected),
+                "{}",
+                path
+            );
+        }
+    }
+
+    #[test]
+    fn rejects_raw_snappy() {
+        let config = FileSinkConfig {
+            path: temp_file().try_into().unwrap(),
+            idle_timeout: default_idle_timeout(),
+            encoding: (None::<FramingConfig>, TextSerializerConfig::default()).into(),
+            batch_encoding: None,
+            batch: Default::default(),
+            compression: Compression::Snappy.into(),
+            acknowledgements: Default::default(),
+        };
+
+        assert!(FileSink::new(&config).is_err());
+    }
+
     #[tokio::test]
     async fn many_partitions() {
         let directory = temp_dir();
@@ -523,7 +954,9 @@ mod tests {
             path: template.try_into().unwrap(),
             idle_timeout: default_idle_timeout(),
             encoding: (None::<FramingConfig>, TextSerializerConfig::default()).into(),
-            compression: Compression::None,
+            batch_encoding: None,
+            batch: Default::default(),
+            compression: Compression::None.into(),
             acknowledgements: Default::default(),
         };
 
@@ -601,7 +1034,9 @@ mod tests {
             path: template.clone().try_into().unwrap(),
             idle_timeout: Duration::from_secs(1),
             encoding: (None::<FramingConfig>, TextSerializerConfig::default()).into(),
-            compression: Compression::None,
+            batch_encoding: None,
+            batch: Default::default(),
+            compression: Compression::None.into(),
             acknowledgements: Default::default(),
         };
 
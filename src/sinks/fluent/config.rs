@@ -0,0 +1,223 @@
+use std::time::Duration;
+
+use vector_common::sensitive_string::SensitiveString;
+use vector_config::configurable_component;
+
+use crate::{
+    codecs::Transformer,
+    config::{AcknowledgementsConfig, GenerateConfig, Input, SinkConfig, SinkContext},
+    sinks::{
+        fluent::{
+            connection::{FluentConnector, SharedKeyAuth},
+            sink::FluentSink,
+        },
+        util::{
+            tcp::TcpConnector, BatchConfig, RealtimeEventBasedDefaultBatchSettings, SinkBuildError,
+        },
+        Healthcheck, VectorSink,
+    },
+    tcp::TcpKeepaliveConfig,
+    template::Template,
+    tls::{MaybeTlsSettings, TlsEnableableConfig},
+};
+
+/// Configuration for the `fluent` sink.
+#[configurable_component(sink(
+    "fluent",
+    "Deliver log events to a Fluentd or Fluent Bit server over the Forward protocol."
+))]
+#[derive(Clone, Debug)]
+pub struct FluentSinkConfig {
+    /// The address to connect to.
+    ///
+    /// Both IP address and hostname are accepted formats.
+    ///
+    /// The address _must_ include a port.
+    #[configurable(metadata(docs::examples = "127.0.0.1:24224"))]
+    #[configurable(metadata(docs::examples = "fluentd.example.com:24224"))]
+    pub address: String,
+
+    /// The tag of the messages.
+    ///
+    /// Events are grouped in messages by tag.
+    #[configurable(metadata(docs::examples = "vector"))]
+    #[configurable(metadata(docs::examples = "{{ tag }}"))]
+    pub tag: Template,
+
+    #[configurable(derived)]
+    #[serde(default)]
+    pub mode: FluentMode,
+
+    /// Whether to require the server to acknowledge each message.
+    ///
+    /// When enabled, each message is sent with a `chunk` option, and its events are only marked
+    /// as delivered once the server has acknowledged it. A message that is not acknowledged in
+    /// time is sent again on a new connection, up to five times in total, before its events are
+    /// marked as failed.
+    ///
+    /// This should be used along with end-to-end acknowledgements, for at-least-once delivery.
+    #[serde(default)]
+    pub require_ack: bool,
+
+    /// The amount of time to wait for the acknowledgement of a message.
+    #[serde(default = "default_ack_timeout_secs")]
+    #[configurable(metadata(docs::type_unit = "seconds"))]
+    #[configurable(metadata(docs::human_name = "Acknowledgement Timeout"))]
+    pub ack_timeout_secs: u64,
+
+    #[configurable(derived)]
+    pub auth: Option<FluentAuthConfig>,
+
+    #[configurable(derived)]
+    #[serde(
+        default,
+        skip_serializing_if = "crate::serde::skip_serializing_if_default"
+    )]
+    pub encoding: Transformer,
+
+    #[configurable(derived)]
+    #[serde(default)]
+    pub batch: BatchConfig<RealtimeEventBasedDefaultBatchSettings>,
+
+    #[configurable(derived)]
+    pub keepalive: Option<TcpKeepaliveConfig>,
+
+    #[configurable(derived)]
+    pub tls: Option<TlsEnableableConfig>,
+
+    #[configurable(derived)]
+    #[serde(
+        default,
+        deserialize_with = "crate::serde::bool_or_struct",
+        skip_serializing_if = "crate::serde::skip_serializing_if_default"
+    )]
+    pub acknowledgements: AcknowledgementsConfig,
+}
+
+const fn default_ack_timeout_secs() -> u64 {
+    190
+}
+
+/// The [event mode][event_modes] of the messages.
+///
+/// [event_modes]: https://github.com/fluent/fluentd/wiki/Forward-Protocol-Specification-v1#event-modes
+#[configurable_component]
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum FluentMode {
+    /// Each message holds its events as an array of entries.
+    Forward,
+
+    /// Each message holds its events as a binary stream of entries, which is cheaper for the
+    /// server to decode.
+    #[default]
+    PackedForward,
+
+    /// Same as `packed_forward`, with the stream of entries compressed with gzip.
+    CompressedPackedForward,
+}
+
+/// Shared key authentication configuration.
+///
+/// The server must be configured with the same shared key, in the `<security>` section of
+/// Fluentd, or with the `Shared_Key` option of Fluent Bit.
+#[configurable_component]
+#[derive(Clone, Debug)]
+pub struct FluentAuthConfig {
+    /// The shared key used to authenticate with the server.
+    #[configurable(metadata(docs::examples = "${FLUENT_SHARED_KEY}"))]
+    pub shared_key: SensitiveString,
+
+    /// The hostname sent to the server during the handshake.
+    ///
+    /// By default, the hostname of the machine running Vector is used.
+    #[configurable(metadata(docs::examples = "vector.example.com"))]
+    pub self_hostname: Option<String>,
+
+    /// The username, for servers that also require user authentication.
+    #[configurable(metadata(docs::examples = "vector"))]
+    pub username: Option<String>,
+
+    /// The password, for servers that also require user authentication.
+    #[configurable(metadata(docs::examples = "${FLUENT_PASSWORD}"))]
+    pub password: Option<SensitiveString>,
+}
+
+impl GenerateConfig for FluentSinkConfig {
+    fn generate_config() -> toml::Value {
+        toml::from_str(
+            r#"address = "127.0.0.1:24224"
+            tag = "vector""#,
+        )
+        .unwrap()
+    }
+}
+
+#[async_trait::async_trait]
+#[typetag::serde(name = "fluent")]
+impl SinkConfig for FluentSinkConfig {
+    async fn build(&self, _cx: SinkContext) -> crate::Result<(VectorSink, Healthcheck)> {
+        let connector = self.build_connector()?;
+        let sink = FluentSink::new(
+            connector.clone(),
+            self.tag.clone(),
+            self.mode,
+            self.require_ack,
+            Duration::from_secs(self.ack_timeout_secs),
+            self.encoding.clone(),
+            self.batch.into_batcher_settings()?,
+        );
+
+        Ok((
+            VectorSink::from_event_streamsink(sink),
+            Box::pin(async move { connector.healthcheck().await }),
+        ))
+    }
+
+    fn input(&self) -> Input {
+        Input::log()
+    }
+
+    fn acknowledgements(&self) -> &AcknowledgementsConfig {
+        &self.acknowledgements
+    }
+}
+
+impl FluentSinkConfig {
+    fn build_connector(&self) -> crate::Result<FluentConnector> {
+        let uri = self.address.parse::<http::Uri>()?;
+        let host = uri.host().ok_or(SinkBuildError::MissingHost)?.to_string();
+        let port = uri.port_u16().ok_or(SinkBuildError::MissingPort)?;
+        let tls = MaybeTlsSettings::from_config(&self.tls, false)?;
+        let tcp = TcpConnector::new(host, port, self.keepalive, tls, None);
+
+        let auth = match &self.auth {
+            Some(auth) => Some(SharedKeyAuth {
+                shared_key: auth.shared_key.inner().to_owned(),
+                self_hostname: match &auth.self_hostname {
+                    Some(hostname) => hostname.clone(),
+                    None => crate::get_hostname()?,
+                },
+                username: auth.username.clone().unwrap_or_default(),
+                password: auth
+                    .password
+                    .as_ref()
+                    .map(|password| password.inner().to_owned())
+                    .unwrap_or_default(),
+            }),
+            None => None,
+        };
+
+        Ok(FluentConnector::new(tcp, auth))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn generate_config() {
+        crate::test_util::test_generate_config::<FluentSinkConfig>();
+    }
+}
@@ -0,0 +1,459 @@
+//! Connections to Fluent servers, with the shared key handshake and the acknowledgements of the
+//! Forward protocol.
+
+use std::{io, time::Duration};
+
+use bytes::{Buf, BytesMut};
+use serde::{de::DeserializeOwned, Deserialize};
+use sha2::{Digest, Sha512};
+use snafu::{ResultExt, Snafu};
+use tokio::{
+    io::{AsyncReadExt, AsyncWriteExt},
+    net::TcpStream,
+    time::{sleep, timeout},
+};
+
+use crate::{
+    internal_events::{TcpSocketConnectionEstablished, TcpSocketOutgoingConnectionError},
+    sinks::util::tcp::{TcpConnector, TcpError},
+    tls::MaybeTlsStream,
+};
+
+#[derive(Debug, Snafu)]
+pub(super) enum FluentError {
+    #[snafu(display("Connect error: {}", source))]
+    Connect { source: TcpError },
+    #[snafu(display("IO error: {}", source))]
+    Io { source: io::Error },
+    #[snafu(display("Failed to encode message: {}", source))]
+    Encode { source: rmp_serde::encode::Error },
+    #[snafu(display("Failed to decode message from server: {}", source))]
+    Decode { source: rmp_serde::decode::Error },
+    #[snafu(display("Unexpected message from server, expected {}.", expected))]
+    UnexpectedMessage { expected: &'static str },
+    #[snafu(display("Authentication failed: {}", reason))]
+    AuthenticationFailed { reason: String },
+    #[snafu(display("Server failed to prove it knows the shared key."))]
+    ServerDigestMismatch,
+    #[snafu(display("Timed out waiting for acknowledgement."))]
+    AckTimeout,
+    #[snafu(display(
+        "Acknowledgement for chunk {} received, expected {}.",
+        received,
+        expected
+    ))]
+    AckMismatch { expected: String, received: String },
+    #[snafu(display("Connection closed by server."))]
+    ConnectionClosed,
+}
+
+/// The credentials of the shared key handshake.
+#[derive(Clone)]
+pub(super) struct SharedKeyAuth {
+    pub(super) shared_key: String,
+    pub(super) self_hostname: String,
+    /// Empty when the server doesn't require user authentication.
+    pub(super) username: String,
+    pub(super) password: String,
+}
+
+impl SharedKeyAuth {
+    /// Builds the `PING` message answering the `HELO` of the server.
+    fn ping(&self, shared_key_salt: &str, helo: &Helo) -> rmpv::Value {
+        let shared_key_digest = hex_sha512(&[
+            shared_key_salt.as_bytes(),
+            self.self_hostname.as_bytes(),
+            &helo.nonce,
+            self.shared_key.as_bytes(),
+        ]);
+        let password_digest = if self.username.is_empty() {
+            String::new()
+        } else {
+            hex_sha512(&[
+                &helo.auth_salt,
+                self.username.as_bytes(),
+                self.password.as_bytes(),
+            ])
+        };
+
+        rmpv::Value::Array(vec![
+            "PING".into(),
+            self.self_hostname.as_str().into(),
+            shared_key_salt.into(),
+            shared_key_digest.into(),
+            self.username.as_str().into(),
+            password_digest.into(),
+        ])
+    }
+
+    /// Checks the `PONG` message of the server, which must both accept the authentication and
+    /// prove that it knows the shared key.
+    fn check_pong(
+        &self,
+        pong: &rmpv::Value,
+        shared_key_salt: &str,
+        helo: &Helo,
+    ) -> Result<(), FluentError> {
+        let unexpected = || FluentError::UnexpectedMessage { expected: "PONG" };
+        let fields = pong.as_array().ok_or_else(unexpected)?;
+        if fields.len() != 5 || fields[0].as_str() != Some("PONG") {
+            return Err(unexpected());
+        }
+
+        if fields[1].as_bool() != Some(true) {
+            return Err(FluentError::AuthenticationFailed {
+                reason: fields[2].as_str().unwrap_or_default().to_owned(),
+            });
+        }
+
+        let server_hostname = message_bytes(&fields[3]).ok_or_else(unexpected)?;
+        let expected_digest = hex_sha512(&[
+            shared_key_salt.as_bytes(),
+            &server_hostname,
+            &helo.nonce,
+            self.shared_key.as_bytes(),
+        ]);
+        if message_bytes(&fields[4]).as_deref() != Some(expected_digest.as_bytes()) {
+            return Err(FluentError::ServerDigestMismatch);
+        }
+
+        Ok(())
+    }
+}
+
+/// The `HELO` message the server starts the handshake with.
+struct Helo {
+    nonce: Vec<u8>,
+    auth_salt: Vec<u8>,
+}
+
+impl Helo {
+    fn parse(helo: &rmpv::Value) -> Option<Self> {
+        let fields = helo.as_array()?;
+        if fields.len() != 2 || fields[0].as_str() != Some("HELO") {
+            return None;
+        }
+
+        let options = fields[1].as_map()?;
+        let option = |name: &str| {
+            options
+                .iter()
+                .find(|(key, _)| key.as_str() == Some(name))
+                .and_then(|(_, value)| message_bytes(value))
+        };
+
+        Some(Self {
+            nonce: option("nonce")?,
+            auth_salt: option("auth").unwrap_or_default(),
+        })
+    }
+}
+
+/// The acknowledgement of a message sent with a `chunk` option.
+#[derive(Deserialize)]
+struct AckResponse {
+    ack: String,
+}
+
+/// Servers send the handshake fields either as strings or as binaries.
+fn message_bytes(value: &rmpv::Value) -> Option<Vec<u8>> {
+    match value {
+        rmpv::Value::String(s) => Some(s.as_bytes().to_vec()),
+        rmpv::Value::Binary(bytes) => Some(bytes.clone()),
+        _ => None,
+    }
+}
+
+fn hex_sha512(parts: &[&[u8]]) -> String {
+    let mut hasher = Sha512::new();
+    for part in parts {
+        hasher.update(part);
+    }
+    hex::encode(hasher.finalize())
+}
+
+/// Opens connections to a Fluent server.
+#[derive(Clone)]
+pub(super) struct FluentConnector {
+    tcp: TcpConnector,
+    auth: Option<SharedKeyAuth>,
+}
+
+impl FluentConnector {
+    pub(super) const fn new(tcp: TcpConnector, auth: Option<SharedKeyAuth>) -> Self {
+        Self { tcp, auth }
+    }
+
+    async fn connect(&self) -> Result<FluentConnection, FluentError> {
+        let stream = self.tcp.connect().await.context(ConnectSnafu)?;
+        let mut connection = FluentConnection {
+            stream,
+            buffer: BytesMut::new(),
+        };
+
+        if let Some(auth) = &self.auth {
+            connection.handshake(auth).await?;
+        }
+
+        Ok(connection)
+    }
+
+    pub(super) async fn connect_backoff(&self) -> FluentConnection {
+        let mut backoff = TcpConnector::fresh_backoff();
+        loop {
+            match self.connect().await {
+                Ok(connection) => {
+                    emit!(TcpSocketConnectionEstablished {
+                        peer_addr: connection.stream.peer_addr().ok(),
+                    });
+                    return connection;
+                }
+                Err(error) => {
+                    emit!(TcpSocketOutgoingConnectionError { error });
+                    sleep(backoff.next().unwrap()).await;
+                }
+            }
+        }
+    }
+
+    pub(super) async fn healthcheck(&self) -> crate::Result<()> {
+        self.connect().await.map(|_| ()).map_err(Into::into)
+    }
+}
+
+/// An open, and authenticated if needed, connection to a Fluent server.
+pub(super) struct FluentConnection {
+    stream: MaybeTlsStream<TcpStream>,
+    /// The bytes received from the server that weren't decoded yet.
+    buffer: BytesMut,
+}
+
+impl FluentConnection {
+    async fn handshake(&mut self, auth: &SharedKeyAuth) -> Result<(), FluentError> {
+        let helo = self.read::<rmpv::Value>().await?;
+        let helo = Helo::parse(&helo).ok_or(FluentError::UnexpectedMessage { expected: "HELO" })?;
+
+        let shared_key_salt = hex::encode(rand::random::<[u8; 16]>());
+        let ping = rmp_serde::to_vec(&auth.ping(&shared_key_salt, &helo)).context(EncodeSnafu)?;
+        self.stream.write_all(&ping).await.context(IoSnafu)?;
+
+        let pong = self.read::<rmpv::Value>().await?;
+        auth.check_pong(&pong, &shared_key_salt, &helo)
+    }
+
+    /// Sends an encoded message, and waits for its acknowledgement if it was sent with a `chunk`
+    /// option.
+    pub(super) async fn send(
+        &mut self,
+        message: &[u8],
+        chunk: Option<&str>,
+        ack_timeout: Duration,
+    ) -> Result<(), FluentError> {
+        self.stream.write_all(message).await.context(IoSnafu)?;
+        self.stream.flush().await.context(IoSnafu)?;
+
+        if let Some(chunk) = chunk {
+            let response = timeout(ack_timeout, self.read::<AckResponse>())
+                .await
+                .map_err(|_| FluentError::AckTimeout)??;
+            if response.ack != chunk {
+                return Err(FluentError::AckMismatch {
+                    expected: chunk.to_owned(),
+                    received: response.ack,
+                });
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Reads the next message from the server.
+    async fn read<T: DeserializeOwned>(&mut self) -> Result<T, FluentError> {
+        loop {
+            let mut reader = io::Cursor::new(&self.buffer[..]);
+            match rmp_serde::from_read::<_, T>(&mut reader) {
+                Ok(message) => {
+                    let position = reader.position() as usize;
+                    self.buffer.advance(position);
+                    return Ok(message);
+                }
+                Err(error) if !is_incomplete(&error) => {
+                    return Err(FluentError::Decode { source: error })
+                }
+                Err(_) => {}
+            }
+
+            if self
+                .stream
+                .read_buf(&mut self.buffer)
+                .await
+                .context(IoSnafu)?
+                == 0
+            {
+                return Err(FluentError::ConnectionClosed);
+            }
+        }
+    }
+}
+
+/// Whether decoding failed only because the message wasn't fully received yet.
+fn is_incomplete(error: &rmp_serde::decode::Error) -> bool {
+    use rmp_serde::decode::Error;
+
+    matches!(
+        error,
+        Error::InvalidMarkerRead(error) | Error::InvalidDataRead(error)
+            if error.kind() == io::ErrorKind::UnexpectedEof
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use tokio::net::TcpListener;
+
+    use super::*;
+    use crate::{test_util::next_addr, tls::MaybeTlsSettings};
+
+    fn auth() -> SharedKeyAuth {
+        SharedKeyAuth {
+            shared_key: "secret".to_owned(),
+            self_hostname: "client".to_owned(),
+            username: String::new(),
+            password: String::new(),
+        }
+    }
+
+    fn helo() -> Helo {
+        Helo {
+            nonce: b"nonce".to_vec(),
+            auth_salt: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn parse_helo() {
+        let message = rmpv::Value::Array(vec![
+            "HELO".into(),
+            rmpv::Value::Map(vec![
+                ("nonce".into(), rmpv::Value::Binary(b"nonce".to_vec())),
+                ("auth".into(), "".into()),
+                ("keepalive".into(), true.into()),
+            ]),
+        ]);
+
+        let helo = Helo::parse(&message).unwrap();
+        assert_eq!(helo.nonce, b"nonce");
+        assert!(helo.auth_salt.is_empty());
+
+        assert!(Helo::parse(&rmpv::Value::Array(vec!["PONG".into()])).is_none());
+    }
+
+    #[test]
+    fn ping_digest() {
+        let ping = auth().ping("salt", &helo());
+        let fields = ping.as_array().unwrap();
+
+        assert_eq!(fields[0].as_str(), Some("PING"));
+        assert_eq!(fields[1].as_str(), Some("client"));
+        assert_eq!(fields[2].as_str(), Some("salt"));
+        assert_eq!(
+            fields[3].as_str(),
+            Some(hex_sha512(&[b"salt", b"client", b"nonce", b"secret"]).as_str())
+        );
+        assert_eq!(fields[4].as_str(), Some(""));
+        assert_eq!(fields[5].as_str(), Some(""));
+    }
+
+    #[test]
+    fn check_pong() {
+        let pong = |auth_result: bool, shared_key: &str| {
+            rmpv::Value::Array(vec![
+                "PONG".into(),
+                auth_result.into(),
+                "shared key mismatch".into(),
+                "server".into(),
+                hex_sha512(&[b"salt", b"server", b"nonce", shared_key.as_bytes()]).into(),
+            ])
+        };
+
+        assert!(auth()
+            .check_pong(&pong(true, "secret"), "salt", &helo())
+            .is_ok());
+        assert!(matches!(
+            auth().check_pong(&pong(false, "secret"), "salt", &helo()),
+            Err(FluentError::AuthenticationFailed { .. })
+        ));
+        assert!(matches!(
+            auth().check_pong(&pong(true, "other"), "salt", &helo()),
+            Err(FluentError::ServerDigestMismatch)
+        ));
+    }
+
+    /// Reads a message sent by the client.
+    async fn read_value(socket: &mut TcpStream) -> rmpv::Value {
+        let mut buffer = BytesMut::new();
+        loop {
+            socket.read_buf(&mut buffer).await.unwrap();
+            if let Ok(value) = rmp_serde::from_slice::<rmpv::Value>(&buffer) {
+                return value;
+            }
+        }
+    }
+
+    #[tokio::test]
+    async fn shared_key_handshake() {
+        let addr = next_addr();
+        let listener = TcpListener::bind(addr).await.unwrap();
+        let server = tokio::spawn(async move {
+            let (mut socket, _) = listener.accept().await.unwrap();
+            let helo = rmpv::Value::Array(vec![
+                "HELO".into(),
+                rmpv::Value::Map(vec![
+                    ("nonce".into(), rmpv::Value::Binary(b"nonce".to_vec())),
+                    ("auth".into(), "".into()),
+                    ("keepalive".into(), true.into()),
+                ]),
+            ]);
+            socket
+                .write_all(&rmp_serde::to_vec(&helo).unwrap())
+                .await
+                .unwrap();
+
+            let ping = read_value(&mut socket).await;
+            let fields = ping.as_array().unwrap();
+            assert_eq!(fields[0].as_str(), Some("PING"));
+            assert_eq!(fields[1].as_str(), Some("client"));
+            let salt = fields[2].as_str().unwrap().to_owned();
+            assert_eq!(
+                fields[3].as_str(),
+                Some(hex_sha512(&[salt.as_bytes(), b"client", b"nonce", b"secret"]).as_str())
+            );
+
+            let pong = rmpv::Value::Array(vec![
+                "PONG".into(),
+                true.into(),
+                "".into(),
+                "server".into(),
+                hex_sha512(&[salt.as_bytes(), b"server", b"nonce", b"secret"]).into(),
+            ]);
+            socket
+                .write_all(&rmp_serde::to_vec(&pong).unwrap())
+                .await
+                .unwrap();
+        });
+
+        let tcp = TcpConnector::new(
+            addr.ip().to_string(),
+            addr.port(),
+            None,
+            MaybeTlsSettings::Raw(()),
+            None,
+        );
+        FluentConnector::new(tcp, Some(auth()))
+            .connect()
+            .await
+            .unwrap();
+
+        server.await.unwrap();
+    }
+}
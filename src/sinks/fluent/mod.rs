@@ -0,0 +1,9 @@
+//! `fluent` sink.
+//! Sends log events to Fluentd and Fluent Bit servers over the [Forward protocol][forward].
+//!
+//! [forward]: https://github.com/fluent/fluentd/wiki/Forward-Protocol-Specification-v1
+mod config;
+mod connection;
+mod sink;
+
+pub use config::FluentSinkConfig;
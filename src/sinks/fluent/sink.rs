@@ -0,0 +1,444 @@
+use std::{io::Write, time::Duration};
+
+use async_trait::async_trait;
+use base64::prelude::{Engine as _, BASE64_STANDARD};
+use chrono::Utc;
+use flate2::{write::GzEncoder, Compression};
+use futures::{future::ready, stream::BoxStream, StreamExt};
+use rmp_serde::Serializer;
+use serde::Serialize;
+use snafu::ResultExt;
+use tokio::time::sleep;
+use vector_common::json_size::JsonSize;
+use vector_core::{
+    internal_event::{
+        ByteSize, BytesSent, CountByteSize, EventsSent, InternalEventHandle as _, Output, Protocol,
+    },
+    partition::Partitioner,
+    stream::BatcherSettings,
+    EstimatedJsonEncodedSizeOf,
+};
+
+use super::{
+    config::FluentMode,
+    connection::{EncodeSnafu, FluentConnector, FluentError, IoSnafu},
+};
+use crate::{
+    codecs::Transformer,
+    common::fluent::{
+        FluentEntry, FluentEventTime, FluentMessage, FluentMessageOptions, FluentRecord,
+        FluentTimestamp, FluentValue,
+    },
+    event::{Event, EventFinalizers, EventStatus, Finalizable, LogEvent, Value},
+    internal_events::{FluentMessageSendError, SinkRequestBuildError, TemplateRenderingError},
+    sinks::util::{tcp::TcpConnector, SinkBuilderExt, StreamSink},
+    template::Template,
+};
+
+/// The number of times a message is sent, each time on a new connection, before its events are
+/// marked as failed.
+const MAX_SEND_ATTEMPTS: usize = 5;
+
+/// Partitions events by their rendered tag.
+struct TagPartitioner(Template);
+
+impl Partitioner for TagPartitioner {
+    type Item = Event;
+    type Key = Option<String>;
+
+    fn partition(&self, item: &Self::Item) -> Self::Key {
+        self.0
+            .render_string(item)
+            .map_err(|error| {
+                emit!(TemplateRenderingError {
+                    error,
+                    field: Some("tag"),
+                    drop_event: true,
+                });
+            })
+            .ok()
+    }
+}
+
+/// An encoded message, along with the events it holds.
+struct FluentRequest {
+    message: Vec<u8>,
+    chunk: Option<String>,
+    finalizers: EventFinalizers,
+    event_count: usize,
+    events_byte_size: JsonSize,
+}
+
+pub(super) struct FluentSink {
+    connector: FluentConnector,
+    tag: Template,
+    mode: FluentMode,
+    require_ack: bool,
+    ack_timeout: Duration,
+    transformer: Transformer,
+    batch_settings: BatcherSettings,
+}
+
+impl FluentSink {
+    pub(super) const fn new(
+        connector: FluentConnector,
+        tag: Template,
+        mode: FluentMode,
+        require_ack: bool,
+        ack_timeout: Duration,
+        transformer: Transformer,
+        batch_settings: BatcherSettings,
+    ) -> Self {
+        Self {
+            connector,
+            tag,
+            mode,
+            require_ack,
+            ack_timeout,
+            transformer,
+            batch_settings,
+        }
+    }
+
+    fn build_request(&self, tag: String, mut events: Vec<Event>) -> Option<FluentRequest> {
+        let finalizers = events.take_finalizers();
+        let event_count = events.len();
+        let events_byte_size = events.estimated_json_encoded_size_of();
+
+        let entries = events
+            .into_iter()
+            .map(|mut event| {
+                self.transformer.transform(&mut event);
+                entry(event.into_log())
+            })
+            .collect();
+        let chunk = self
+            .require_ack
+            .then(|| BASE64_STANDARD.encode(rand::random::<[u8; 16]>()));
+
+        match encode_message(self.mode, tag, entries, chunk.clone()) {
+            Ok(message) => Some(FluentRequest {
+                message,
+                chunk,
+                finalizers,
+                event_count,
+                events_byte_size,
+            }),
+            Err(error) => {
+                emit!(SinkRequestBuildError { error });
+                finalizers.update_status(EventStatus::Rejected);
+                None
+            }
+        }
+    }
+}
+
+/// Converts a log event into a Fluent entry, timestamped with the event timestamp if it has any.
+fn entry(log: LogEvent) -> FluentEntry {
+    let timestamp = log
+        .get_timestamp()
+        .and_then(Value::as_timestamp)
+        .copied()
+        .unwrap_or_else(Utc::now);
+
+    let record = match log.into_parts().0 {
+        Value::Object(fields) => fields
+            .into_iter()
+            .map(|(key, value)| (key, FluentValue::from(value)))
+            .collect(),
+        value => FluentRecord::from([("message".to_owned(), FluentValue::from(value))]),
+    };
+
+    FluentEntry(FluentTimestamp::Ext(FluentEventTime(timestamp)), record)
+}
+
+fn encode_message(
+    mode: FluentMode,
+    tag: String,
+    entries: Vec<FluentEntry>,
+    chunk: Option<String>,
+) -> Result<Vec<u8>, FluentError> {
+    let mut options = FluentMessageOptions {
+        size: Some(entries.len() as u64),
+        chunk,
+        compressed: None,
+    };
+
+    let message = match mode {
+        FluentMode::Forward => FluentMessage::ForwardWithOptions(tag, entries, options),
+        FluentMode::PackedForward | FluentMode::CompressedPackedForward => {
+            let mut packed = Vec::new();
+            for entry in &entries {
+                entry
+                    .serialize(&mut Serializer::new(&mut packed))
+                    .context(EncodeSnafu)?;
+            }
+
+            if mode == FluentMode::CompressedPackedForward {
+                let mut encoder = GzEncoder::new(Vec::new(), Compression::default());
+                encoder.write_all(&packed).context(IoSnafu)?;
+                packed = encoder.finish().context(IoSnafu)?;
+                options.compressed = Some("gzip".to_owned());
+            }
+
+            FluentMessage::PackedForwardWithOptions(tag, packed.into(), options)
+        }
+    };
+
+    // Options are encoded as a map, as the protocol requires.
+    let mut buffer = Vec::new();
+    message
+        .serialize(&mut Serializer::new(&mut buffer).with_struct_map())
+        .context(EncodeSnafu)?;
+    Ok(buffer)
+}
+
+#[async_trait]
+impl StreamSink<Event> for FluentSink {
+    async fn run(self: Box<Self>, input: BoxStream<'_, Event>) -> Result<(), ()> {
+        let mut requests = input
+            .batched_partitioned(TagPartitioner(self.tag.clone()), self.batch_settings)
+            .filter_map(|(tag, events)| {
+                // A `TemplateRenderingError` is already emitted when the tag couldn't be rendered.
+                ready(tag.and_then(|tag| self.build_request(tag, events)))
+            })
+            .boxed();
+
+        let events_sent = register!(EventsSent::from(Output(None)));
+        let bytes_sent = register!(BytesSent::from(Protocol::TCP));
+
+        let mut connection = None;
+        while let Some(request) = requests.next().await {
+            let mut backoff = TcpConnector::fresh_backoff();
+            let mut attempt = 1;
+            let result = loop {
+                if connection.is_none() {
+                    connection = Some(self.connector.connect_backoff().await);
+                }
+
+                let result = connection
+                    .as_mut()
+                    .expect("connection should be open")
+                    .send(&request.message, request.chunk.as_deref(), self.ack_timeout)
+                    .await;
+                match result {
+                    Ok(()) => break Ok(()),
+                    Err(error) => {
+                        // The state of the connection is unknown, so start over with a new one.
+                        connection = None;
+                        if attempt == MAX_SEND_ATTEMPTS {
+                            break Err(error);
+                        }
+                        warn!(
+                            message = "Failed to send fluent message, retrying.",
+                            %error,
+                            attempt,
+                            internal_log_rate_limit = true,
+                        );
+                        attempt += 1;
+                        sleep(backoff.next().expect("backoff never ends")).await;
+                    }
+                }
+            };
+
+            match result {
+                Ok(()) => {
+                    request.finalizers.update_status(EventStatus::Delivered);
+                    events_sent.emit(CountByteSize(request.event_count, request.events_byte_size));
+                    bytes_sent.emit(ByteSize(request.message.len()));
+                }
+                Err(error) => {
+                    request.finalizers.update_status(EventStatus::Errored);
+                    emit!(FluentMessageSendError {
+                        error,
+                        count: request.event_count,
+                    });
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{io::Cursor, net::SocketAddr};
+
+    use bytes::BytesMut;
+    use chrono::TimeZone;
+    use futures::stream;
+    use tokio::{
+        io::{AsyncReadExt, AsyncWriteExt},
+        net::{TcpListener, TcpStream},
+    };
+
+    use super::*;
+    use crate::{
+        config::{SinkConfig, SinkContext},
+        sinks::fluent::FluentSinkConfig,
+        test_util::{
+            components::{run_and_assert_sink_compliance, SINK_TAGS},
+            next_addr, trace_init,
+        },
+    };
+
+    fn log_entry() -> FluentEntry {
+        let mut log = LogEvent::from("hello");
+        log.insert(
+            "timestamp",
+            Utc.with_ymd_and_hms(2023, 1, 1, 0, 0, 0).unwrap(),
+        );
+        entry(log)
+    }
+
+    fn decode_packed(mut packed: &[u8]) -> Vec<FluentEntry> {
+        let mut entries = Vec::new();
+        while !packed.is_empty() {
+            entries.push(rmp_serde::from_read(&mut packed).unwrap());
+        }
+        entries
+    }
+
+    #[test]
+    fn entry_from_log() {
+        let FluentEntry(timestamp, record) = log_entry();
+
+        assert_eq!(
+            timestamp,
+            FluentTimestamp::Ext(FluentEventTime(
+                Utc.with_ymd_and_hms(2023, 1, 1, 0, 0, 0).unwrap()
+            ))
+        );
+        assert_eq!(
+            record.get("message"),
+            Some(&FluentValue::from(Value::from("hello")))
+        );
+    }
+
+    #[test]
+    fn encode_forward() {
+        let message = encode_message(
+            FluentMode::Forward,
+            "vector".to_owned(),
+            vec![log_entry(), log_entry()],
+            Some("chunk".to_owned()),
+        )
+        .unwrap();
+
+        match rmp_serde::from_slice(&message).unwrap() {
+            FluentMessage::ForwardWithOptions(tag, entries, options) => {
+                assert_eq!(tag, "vector");
+                assert_eq!(entries.len(), 2);
+                assert_eq!(options.size, Some(2));
+                assert_eq!(options.chunk.as_deref(), Some("chunk"));
+            }
+            message => panic!("unexpected message {:?}", message),
+        }
+    }
+
+    #[test]
+    fn encode_compressed_packed_forward() {
+        let message = encode_message(
+            FluentMode::CompressedPackedForward,
+            "vector".to_owned(),
+            vec![log_entry(), log_entry()],
+            None,
+        )
+        .unwrap();
+
+        match rmp_serde::from_slice(&message).unwrap() {
+            FluentMessage::PackedForwardWithOptions(tag, packed, options) => {
+                assert_eq!(tag, "vector");
+                assert_eq!(options.compressed.as_deref(), Some("gzip"));
+
+                let mut decoder = flate2::read::GzDecoder::new(Cursor::new(packed.into_vec()));
+                let mut packed = Vec::new();
+                std::io::Read::read_to_end(&mut decoder, &mut packed).unwrap();
+                assert_eq!(decode_packed(&packed).len(), 2);
+            }
+            message => panic!("unexpected message {:?}", message),
+        }
+    }
+
+    async fn read_message(socket: &mut TcpStream) -> FluentMessage {
+        let mut buffer = BytesMut::new();
+        loop {
+            socket.read_buf(&mut buffer).await.unwrap();
+            if let Ok(message) = rmp_serde::from_slice::<FluentMessage>(&buffer) {
+                return message;
+            }
+        }
+    }
+
+    async fn ack_message(socket: &mut TcpStream) -> (String, Vec<FluentEntry>) {
+        let message = read_message(socket).await;
+        let FluentMessage::PackedForwardWithOptions(tag, packed, options) = message else {
+            panic!("unexpected message {:?}", message);
+        };
+        let ack = rmpv::Value::Map(vec![("ack".into(), options.chunk.unwrap().into())]);
+        socket
+            .write_all(&rmp_serde::to_vec(&ack).unwrap())
+            .await
+            .unwrap();
+
+        (tag, decode_packed(&packed))
+    }
+
+    async fn run_sink(addr: SocketAddr) {
+        let config: FluentSinkConfig = toml::from_str(&format!(
+            r#"
+            address = "{}"
+            tag = "vector"
+            require_ack = true
+            "#,
+            addr
+        ))
+        .unwrap();
+        let (sink, _healthcheck) = config.build(SinkContext::default()).await.unwrap();
+
+        let events = (0..3).map(|i| Event::Log(LogEvent::from(format!("line {}", i))));
+        run_and_assert_sink_compliance(sink, stream::iter(events), &SINK_TAGS).await;
+    }
+
+    #[tokio::test]
+    async fn acknowledged_messages() {
+        trace_init();
+
+        let addr = next_addr();
+        let listener = TcpListener::bind(addr).await.unwrap();
+        let server = tokio::spawn(async move {
+            let (mut socket, _) = listener.accept().await.unwrap();
+            ack_message(&mut socket).await
+        });
+
+        run_sink(addr).await;
+
+        let (tag, entries) = server.await.unwrap();
+        assert_eq!(tag, "vector");
+        assert_eq!(entries.len(), 3);
+    }
+
+    #[tokio::test]
+    async fn resends_unacknowledged_messages() {
+        trace_init();
+
+        let addr = next_addr();
+        let listener = TcpListener::bind(addr).await.unwrap();
+        let server = tokio::spawn(async move {
+            // The first connection is closed without acknowledging the message.
+            let (mut socket, _) = listener.accept().await.unwrap();
+            read_message(&mut socket).await;
+            drop(socket);
+
+            let (mut socket, _) = listener.accept().await.unwrap();
+            ack_message(&mut socket).await
+        });
+
+        run_sink(addr).await;
+
+        let (tag, entries) = server.await.unwrap();
+        assert_eq!(tag, "vector");
+        assert_eq!(entries.len(), 3);
+    }
+}
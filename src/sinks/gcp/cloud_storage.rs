@@ -2,7 +2,7 @@ use std::{collections::HashMap, convert::TryFrom, io};
 
 use bytes::Bytes;
 use chrono::Utc;
-use codecs::encoding::Framer;
+use codecs::encoding::{BatchSerializerConfig, Framer};
 use http::header::{HeaderName, HeaderValue};
 use http::Uri;
 use indoc::indoc;
@@ -16,7 +16,9 @@ use vector_core::event::{EventFinalizers, Finalizable};
 
 use crate::sinks::util::metadata::RequestMetadataBuilder;
 use crate::{
-    codecs::{Encoder, EncodingConfigWithFraming, SinkType, Transformer},
+    codecs::{
+        BatchEncoder, Encoder, EncoderKind, EncodingConfigWithFraming, SinkType, Transformer,
+    },
     config::{AcknowledgementsConfig, DataType, GenerateConfig, Input, SinkConfig, SinkContext},
     event::Event,
     gcp::{GcpAuthConfig, GcpAuthenticator, Scope},
@@ -32,8 +34,8 @@ use crate::{
         },
         util::{
             batch::BatchConfig, partitioner::KeyPartitioner, request_builder::EncodeResult,
-            BulkSizeBasedDefaultBatchSettings, Compression, RequestBuilder, ServiceBuilderExt,
-            TowerRequestConfig,
+            AnyCompression, BulkSizeBasedDefaultBatchSettings, Compression, RequestBuilder,
+            ServiceBuilderExt, TowerRequestConfig,
         },
         Healthcheck, VectorSink,
     },
@@ -132,16 +134,27 @@ pub struct GcsSinkConfig {
 
     /// The filename extension to use in the object key.
     ///
-    /// If not specified, the extension is determined by the compression scheme used.
+    /// If not specified, the extension is determined by the compression scheme used, or by the
+    /// configured `batch_encoding`.
     #[configurable(metadata(docs::advanced))]
     filename_extension: Option<String>,
 
     #[serde(flatten)]
     encoding: EncodingConfigWithFraming,
 
+    /// Batch encoding configuration.
+    ///
+    /// When set, each batch of events is encoded as a single object with the configured codec,
+    /// such as a Parquet file, instead of as a stream of framed events. The codec and framing of
+    /// `encoding` are then ignored, but its other options still apply. As the codec handles its own
+    /// compression, `compression` is ignored as well.
+    #[configurable(derived)]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    batch_encoding: Option<BatchSerializerConfig>,
+
     #[configurable(derived)]
     #[serde(default)]
-    compression: Compression,
+    compression: AnyCompression,
 
     #[configurable(derived)]
     #[serde(default)]
@@ -182,7 +195,8 @@ fn default_config(encoding: EncodingConfigWithFraming) -> GcsSinkConfig {
         filename_append_uuid: true,
         filename_extension: Default::default(),
         encoding,
-        compression: Compression::gzip_default(),
+        batch_encoding: None,
+        compression: AnyCompression::gzip_default(),
         batch: Default::default(),
         request: Default::default(),
         auth: Default::default(),
@@ -224,7 +238,11 @@ impl SinkConfig for GcsSinkConfig {
     }
 
     fn input(&self) -> Input {
-        Input::new(self.encoding.config().1.input_type() & DataType::Log)
+        let input_type = match &self.batch_encoding {
+            Some(batch_encoding) => batch_encoding.input_type(),
+            None => self.encoding.config().1.input_type(),
+        };
+        Input::new(input_type & DataType::Log)
     }
 
     fn acknowledgements(&self) -> &AcknowledgementsConfig {
@@ -282,14 +300,14 @@ struct RequestSettings {
     extension: String,
     time_format: String,
     append_uuid: bool,
-    encoder: (Transformer, Encoder<Framer>),
+    encoder: (Transformer, EncoderKind),
     compression: Compression,
 }
 
 impl RequestBuilder<(String, Vec<Event>)> for RequestSettings {
     type Metadata = (String, EventFinalizers);
     type Events = Vec<Event>;
-    type Encoder = (Transformer, Encoder<Framer>);
+    type Encoder = (Transformer, EncoderKind);
     type Payload = Bytes;
     type Request = GcsRequest;
     type Error = io::Error;
@@ -354,14 +372,25 @@ impl RequestBuilder<(String, Vec<Event>)> for RequestSettings {
 impl RequestSettings {
     fn new(config: &GcsSinkConfig) -> crate::Result<Self> {
         let transformer = config.encoding.transformer();
-        let (framer, serializer) = config.encoding.build(SinkType::MessageBased)?;
-        let encoder = Encoder::<Framer>::new(framer, serializer);
+        let (encoder, compression) = match &config.batch_encoding {
+            Some(batch_encoding) => (
+                EncoderKind::Batch(BatchEncoder::new(batch_encoding.build()?)),
+                Compression::None,
+            ),
+            None => {
+                let (framer, serializer) = config.encoding.build(SinkType::MessageBased)?;
+                let encoder = Encoder::<Framer>::new(framer, serializer);
+                (
+                    EncoderKind::Framed(Box::new(encoder)),
+                    config.compression.into(),
+                )
+            }
+        };
         let acl = config
             .acl
             .map(|acl| HeaderValue::from_str(&to_string(acl)).unwrap());
         let content_type = HeaderValue::from_str(encoder.content_type()).unwrap();
-        let content_encoding = config
-            .compression
+        let content_encoding = compression
             .content_encoding()
             .map(|ce| HeaderValue::from_str(&to_string(ce)).unwrap());
         let storage_class = config.storage_class.unwrap_or_default();
@@ -379,7 +408,10 @@ impl RequestSettings {
         let extension = config
             .filename_extension
             .clone()
-            .unwrap_or_else(|| config.compression.extension().into());
+            .unwrap_or_else(|| match &encoder {
+                EncoderKind::Batch(encoder) => encoder.extension().into(),
+                EncoderKind::Framed(_) => compression.extension().into(),
+            });
         let time_format = config.filename_time_format.clone();
         let append_uuid = config.filename_append_uuid;
         Ok(Self {
@@ -391,7 +423,7 @@ impl RequestSettings {
             extension,
             time_format,
             append_uuid,
-            compression: config.compression,
+            compression,
             encoder: (transformer, encoder),
         })
     }
@@ -408,7 +440,10 @@ fn make_header((name, value): (&String, &String)) -> crate::Result<(HeaderName,
 #[cfg(test)]
 mod tests {
     use codecs::encoding::FramingConfig;
-    use codecs::{JsonSerializerConfig, NewlineDelimitedEncoderConfig, TextSerializerConfig};
+    use codecs::{
+        JsonSerializerConfig, NewlineDelimitedEncoderConfig, ParquetSerializerConfig,
+        TextSerializerConfig,
+    };
     use futures_util::{future::ready, stream};
     use vector_common::request_metadata::GroupedCountByteSize;
     use vector_core::partition::Partitioner;
@@ -520,4 +555,21 @@ mod tests {
         let req = build_request(None, true, Compression::gzip_default());
         assert_ne!(req.key, "key/date.log.gz".to_string());
     }
+
+    #[test]
+    fn gcs_build_request_with_batch_encoding() {
+        let sink_config = GcsSinkConfig {
+            batch_encoding: Some(ParquetSerializerConfig::default().into()),
+            ..default_config((None::<FramingConfig>, JsonSerializerConfig::default()).into())
+        };
+
+        let request_settings = request_settings(&sink_config);
+        assert_eq!(request_settings.extension, "parquet");
+        assert_eq!(request_settings.compression, Compression::None);
+        assert_eq!(request_settings.content_encoding, None);
+        assert_eq!(
+            request_settings.content_type,
+            HeaderValue::from_static("application/vnd.apache.parquet")
+        );
+    }
 }
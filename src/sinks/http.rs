@@ -22,8 +22,8 @@ use crate::{
     sinks::util::{
         self,
         http::{BatchedHttpSink, HttpEventEncoder, RequestConfig},
-        BatchConfig, Buffer, Compression, Compressor, RealtimeSizeBasedDefaultBatchSettings,
-        TowerRequestConfig, UriSerde,
+        AnyCompression, BatchConfig, Buffer, Compression, Compressor,
+        RealtimeSizeBasedDefaultBatchSettings, TowerRequestConfig, UriSerde,
     },
     tls::{TlsConfig, TlsSettings},
 };
@@ -55,7 +55,7 @@ pub struct HttpSinkConfig {
 
     #[configurable(derived)]
     #[serde(default)]
-    pub compression: Compression,
+    pub compression: AnyCompression,
 
     #[serde(flatten)]
     pub encoding: EncodingConfigWithFraming,
@@ -230,7 +230,7 @@ impl SinkConfig for HttpSinkConfig {
             uri: self.uri.with_default_parts(),
             method: self.method,
             auth: self.auth.choose_one(&self.uri.auth)?,
-            compression: self.compression,
+            compression: self.compression.into(),
             transformer: self.encoding.transformer(),
             encoder,
             batch: self.batch,
@@ -283,7 +283,7 @@ impl ValidatableComponent for HttpSinkConfig {
             ),
             auth: None,
             headers: None,
-            compression: Compression::default(),
+            compression: AnyCompression::default(),
             batch: BatchConfig::default(),
             request: RequestConfig::default(),
             tls: None,
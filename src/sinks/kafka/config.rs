@@ -2,17 +2,20 @@ use std::{collections::HashMap, time::Duration};
 
 use codecs::JsonSerializerConfig;
 use futures::FutureExt;
+use lookup::{lookup_v2::ConfigValuePath, owned_value_path};
 use rdkafka::ClientConfig;
 use serde_with::serde_as;
 use vector_config::configurable_component;
 use vrl::value::Kind;
 
 use crate::{
+    common::schema_registry::SchemaRegistryConfig,
     kafka::{KafkaAuthConfig, KafkaCompression},
     serde::json::to_string,
     sinks::{
-        kafka::sink::{healthcheck, KafkaSink},
+        kafka::sink::{healthcheck, BuildError, KafkaSink, SchemaHeader},
         prelude::*,
+        util::RealtimeEventBasedDefaultBatchSettings,
     },
 };
 
@@ -110,6 +113,12 @@ pub struct KafkaSinkConfig {
     #[configurable(metadata(docs::examples = "headers"))]
     pub headers_key: Option<String>,
 
+    #[configurable(derived)]
+    pub transaction: Option<KafkaTransactionConfig>,
+
+    #[configurable(derived)]
+    pub schema_registry: Option<KafkaSchemaRegistryConfig>,
+
     #[configurable(derived)]
     #[serde(
         default,
@@ -119,6 +128,134 @@ pub struct KafkaSinkConfig {
     pub acknowledgements: AcknowledgementsConfig,
 }
 
+/// Configuration for delivering events in Kafka transactions.
+///
+/// Events are batched, and each batch is produced in its own transaction. Events are only
+/// acknowledged once their transaction is committed, and consumers reading with the
+/// `read_committed` isolation level never see the messages of aborted transactions.
+#[serde_as]
+#[configurable_component]
+#[derive(Clone, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct KafkaTransactionConfig {
+    /// The transactional ID of the producer.
+    ///
+    /// Kafka uses it to fence off the previous instances of the producer, aborting their pending
+    /// transaction. It must be stable across restarts, and unique to each Vector instance.
+    #[configurable(metadata(docs::examples = "vector-billing-0"))]
+    pub transactional_id: String,
+
+    /// The maximum duration of a transaction, in milliseconds.
+    ///
+    /// The broker aborts the transactions that aren't committed within this duration. It's also
+    /// the timeout of each attempt to commit or abort a transaction, and how long a commit failing
+    /// with retriable errors is retried before the transaction is aborted. The `message_timeout_ms`
+    /// of the sink is lowered to it if needed.
+    #[serde_as(as = "serde_with::DurationMilliSeconds<u64>")]
+    #[serde(default = "default_transaction_timeout_ms")]
+    #[configurable(metadata(docs::examples = 60000))]
+    #[configurable(metadata(docs::human_name = "Transaction Timeout"))]
+    pub timeout_ms: Duration,
+
+    #[configurable(derived)]
+    #[serde(default)]
+    pub batch: BatchConfig<RealtimeEventBasedDefaultBatchSettings>,
+
+    #[configurable(derived)]
+    pub source_offsets: Option<KafkaSourceOffsetsConfig>,
+}
+
+/// Configuration for producing messages in the Confluent wire format, with a schema registry.
+///
+/// Each message is prefixed with the ID of the schema of the `avro` or `protobuf` codec in the
+/// registry. Avro schemas are registered under the subject if needed, while Protobuf schemas must
+/// already be registered, and the latest version of the subject is used. The schema is resolved
+/// when the sink starts, which waits for the registry to be reachable.
+#[configurable_component]
+#[derive(Clone, Debug)]
+pub struct KafkaSchemaRegistryConfig {
+    #[configurable(derived)]
+    #[serde(flatten)]
+    pub registry: SchemaRegistryConfig,
+
+    /// The subject of the schema.
+    ///
+    /// If omitted, the `<topic>-value` subject of the topic name strategy is used, which requires
+    /// `topic` to not be templated.
+    #[configurable(metadata(docs::examples = "logs-value"))]
+    pub subject: Option<String>,
+}
+
+impl KafkaSchemaRegistryConfig {
+    fn subject(&self, topic: &Template) -> Result<String, BuildError> {
+        match &self.subject {
+            Some(subject) => Ok(subject.clone()),
+            None if topic.is_dynamic() => Err(BuildError::SchemaRegistrySubjectMissing),
+            None => Ok(format!("{}-value", topic.get_ref())),
+        }
+    }
+}
+
+/// Configuration for committing the offsets of the consumed messages in the transactions.
+///
+/// When the events come from a `kafka` source, committing the offsets of their messages in the
+/// same transaction as the messages produced from them provides exactly-once delivery while the
+/// partitions of the consumer group aren't reassigned. The source must then not commit them
+/// itself, by setting `enable.auto.commit` to `false` in its `librdkafka_options`.
+///
+/// The sink commits the offsets without being a member of the consumer group, so the broker can't
+/// fence off a source that lost its partitions in a rebalance. The events it consumed before the
+/// rebalance may still be delivered after another member resumed from the committed offsets,
+/// which duplicates them.
+#[configurable_component]
+#[derive(Clone, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct KafkaSourceOffsetsConfig {
+    /// The consumer group ID of the `kafka` source the events are consumed with.
+    #[configurable(metadata(docs::examples = "consumer-group-name"))]
+    pub group_id: String,
+
+    /// The log field holding the topic of the consumed message.
+    ///
+    /// This must match the `topic_key` of the source. It's only used for the events of the
+    /// `legacy` log namespace, as the topic is read from the source metadata otherwise.
+    #[serde(default = "default_topic_key")]
+    #[configurable(metadata(docs::examples = "topic"))]
+    pub topic_key: ConfigValuePath,
+
+    /// The log field holding the partition of the consumed message.
+    ///
+    /// This must match the `partition_key` of the source. It's only used for the events of the
+    /// `legacy` log namespace, as the partition is read from the source metadata otherwise.
+    #[serde(default = "default_partition_key")]
+    #[configurable(metadata(docs::examples = "partition"))]
+    pub partition_key: ConfigValuePath,
+
+    /// The log field holding the offset of the consumed message.
+    ///
+    /// This must match the `offset_key` of the source. It's only used for the events of the
+    /// `legacy` log namespace, as the offset is read from the source metadata otherwise.
+    #[serde(default = "default_offset_key")]
+    #[configurable(metadata(docs::examples = "offset"))]
+    pub offset_key: ConfigValuePath,
+}
+
+const fn default_transaction_timeout_ms() -> Duration {
+    Duration::from_millis(60000) // default in librdkafka
+}
+
+fn default_topic_key() -> ConfigValuePath {
+    ConfigValuePath(owned_value_path!("topic"))
+}
+
+fn default_partition_key() -> ConfigValuePath {
+    ConfigValuePath(owned_value_path!("partition"))
+}
+
+fn default_offset_key() -> ConfigValuePath {
+    ConfigValuePath(owned_value_path!("offset"))
+}
+
 const fn default_socket_timeout_ms() -> Duration {
     Duration::from_millis(60000) // default in librdkafka
 }
@@ -162,11 +299,23 @@ impl KafkaSinkConfig {
         match kafka_role {
             // All batch options are producer only.
             KafkaRole::Producer => {
+                let mut message_timeout = self.message_timeout_ms;
+                if let Some(transaction) = &self.transaction {
+                    // librdkafka requires messages to time out before their transaction does.
+                    message_timeout = message_timeout.min(transaction.timeout_ms);
+                    client_config
+                        .set("transactional.id", &transaction.transactional_id)
+                        .set(
+                            "transaction.timeout.ms",
+                            &transaction.timeout_ms.as_millis().to_string(),
+                        );
+                }
+
                 client_config
                     .set("compression.codec", &to_string(self.compression))
                     .set(
                         "message.timeout.ms",
-                        &self.message_timeout_ms.as_millis().to_string(),
+                        &message_timeout.as_millis().to_string(),
                     );
 
                 if let Some(value) = self.batch.timeout_secs {
@@ -258,6 +407,8 @@ impl GenerateConfig for KafkaSinkConfig {
             message_timeout_ms: default_message_timeout_ms(),
             librdkafka_options: Default::default(),
             headers_key: None,
+            transaction: None,
+            schema_registry: None,
             acknowledgements: Default::default(),
         })
         .unwrap()
@@ -267,8 +418,16 @@ impl GenerateConfig for KafkaSinkConfig {
 #[async_trait::async_trait]
 #[typetag::serde(name = "kafka")]
 impl SinkConfig for KafkaSinkConfig {
-    async fn build(&self, _cx: SinkContext) -> crate::Result<(VectorSink, Healthcheck)> {
-        let sink = KafkaSink::new(self.clone())?;
+    async fn build(&self, cx: SinkContext) -> crate::Result<(VectorSink, Healthcheck)> {
+        let schema_header = match &self.schema_registry {
+            Some(schema_registry) => {
+                let subject = schema_registry.subject(&self.topic)?;
+                let client = schema_registry.registry.build(cx.proxy())?;
+                Some(SchemaHeader::new(client, subject, self.encoding.config())?)
+            }
+            None => None,
+        };
+        let sink = KafkaSink::new(self.clone(), schema_header)?;
         let hc = healthcheck(self.clone()).boxed();
         Ok((VectorSink::from_event_streamsink(sink), hc))
     }
@@ -293,4 +452,55 @@ mod tests {
     fn generate_config() {
         KafkaSinkConfig::generate_config();
     }
+
+    #[test]
+    fn transactional_producer_options() {
+        let config: KafkaSinkConfig = toml::from_str(
+            r#"
+            bootstrap_servers = "localhost:9092"
+            topic = "logs"
+            encoding.codec = "json"
+            transaction.transactional_id = "vector-0"
+            transaction.timeout_ms = 30000
+            "#,
+        )
+        .unwrap();
+
+        let client_config = config.to_rdkafka(KafkaRole::Producer).unwrap();
+        assert_eq!(client_config.get("transactional.id"), Some("vector-0"));
+        assert_eq!(client_config.get("transaction.timeout.ms"), Some("30000"));
+        assert_eq!(client_config.get("message.timeout.ms"), Some("30000"));
+
+        let client_config = config.to_rdkafka(KafkaRole::Consumer).unwrap();
+        assert_eq!(client_config.get("transactional.id"), None);
+    }
+
+    #[test]
+    fn schema_registry_subject() {
+        let config: KafkaSchemaRegistryConfig =
+            toml::from_str(r#"url = "http://localhost:8081""#).unwrap();
+        assert_eq!(
+            config
+                .subject(&Template::try_from("logs").unwrap())
+                .unwrap(),
+            "logs-value"
+        );
+        assert!(config
+            .subject(&Template::try_from("logs-{{ app }}").unwrap())
+            .is_err());
+
+        let config: KafkaSchemaRegistryConfig = toml::from_str(
+            r#"
+            url = "http://localhost:8081"
+            subject = "apps-value"
+            "#,
+        )
+        .unwrap();
+        assert_eq!(
+            config
+                .subject(&Template::try_from("logs-{{ app }}").unwrap())
+                .unwrap(),
+            "apps-value"
+        );
+    }
 }
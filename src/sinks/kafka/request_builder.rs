@@ -21,6 +21,8 @@ pub struct KafkaRequestBuilder {
     pub topic_template: Template,
     pub transformer: Transformer,
     pub encoder: Encoder<()>,
+    /// The schema registry header to prefix the messages with.
+    pub schema_header: Option<Bytes>,
 }
 
 impl KafkaRequestBuilder {
@@ -46,6 +48,9 @@ impl KafkaRequestBuilder {
         };
         self.transformer.transform(&mut event);
         let mut body = BytesMut::new();
+        if let Some(schema_header) = &self.schema_header {
+            body.extend_from_slice(schema_header);
+        }
 
         // Ensure the metadata builder is built after transforming the event so we have the event
         // size taking into account any dropped fields.
@@ -13,12 +13,14 @@ use vector_core::internal_event::{
 
 use crate::{kafka::KafkaStatisticsContext, sinks::prelude::*};
 
+#[derive(Clone)]
 pub struct KafkaRequest {
     pub body: Bytes,
     pub metadata: KafkaRequestMetadata,
     pub request_metadata: RequestMetadata,
 }
 
+#[derive(Clone)]
 pub struct KafkaRequestMetadata {
     pub finalizers: EventFinalizers,
     pub key: Option<Bytes>,
@@ -1,3 +1,5 @@
+use bytes::Bytes;
+use codecs::encoding::SerializerConfig;
 use futures::future;
 use rdkafka::{
     consumer::{BaseConsumer, Consumer},
@@ -11,13 +13,19 @@ use tower::limit::ConcurrencyLimit;
 
 use super::config::{KafkaRole, KafkaSinkConfig};
 use crate::{
+    common::schema_registry::{SchemaRegistryClient, SchemaRegistryError},
     kafka::KafkaStatisticsContext,
     sinks::kafka::{
         config::QUEUED_MIN_MESSAGES, request_builder::KafkaRequestBuilder, service::KafkaService,
+        transaction::KafkaTransactions,
     },
     sinks::prelude::*,
+    sinks::util::retries::ExponentialBackoff,
 };
 
+/// The timeout of each attempt at resolving the schema registry header.
+const SCHEMA_HEADER_TIMEOUT: Duration = Duration::from_secs(10);
+
 #[derive(Debug, Snafu)]
 #[snafu(visibility(pub(crate)))]
 pub(super) enum BuildError {
@@ -25,6 +33,10 @@ pub(super) enum BuildError {
     KafkaCreateFailed { source: KafkaError },
     #[snafu(display("invalid topic template: {}", source))]
     TopicTemplate { source: TemplateParseError },
+    #[snafu(display("no consumer group metadata for group {:?}", group_id))]
+    ConsumerGroupMetadataMissing { group_id: String },
+    #[snafu(display("a schema registry subject is required when the topic is templated"))]
+    SchemaRegistrySubjectMissing,
 }
 
 pub struct KafkaSink {
@@ -34,6 +46,75 @@ pub struct KafkaSink {
     topic: Template,
     key_field: Option<String>,
     headers_key: Option<String>,
+    schema_header: Option<SchemaHeader>,
+    transactions: Option<KafkaTransactions>,
+}
+
+/// The schema registry header prefixed to the messages.
+///
+/// It's resolved when the sink starts rather than when it's built, so that validating the
+/// configuration doesn't reach the registry, nor registers schemas.
+pub(crate) struct SchemaHeader {
+    client: SchemaRegistryClient,
+    subject: String,
+    serializer: SerializerConfig,
+}
+
+impl SchemaHeader {
+    pub(crate) fn new(
+        client: SchemaRegistryClient,
+        subject: String,
+        serializer: &SerializerConfig,
+    ) -> Result<Self, SchemaRegistryError> {
+        match serializer {
+            SerializerConfig::Avro { .. } | SerializerConfig::Protobuf(_) => Ok(Self {
+                client,
+                subject,
+                serializer: serializer.clone(),
+            }),
+            _ => Err(SchemaRegistryError::UnsupportedCodec),
+        }
+    }
+
+    /// Resolves the header, retrying with a backoff while the registry can't be reached or fails
+    /// transiently.
+    async fn resolve(&self) -> Result<Bytes, ()> {
+        let mut backoff = ExponentialBackoff::from_millis(2)
+            .factor(250)
+            .max_delay(Duration::from_secs(30));
+        loop {
+            let attempt = self.client.header(&self.subject, &self.serializer);
+            match tokio::time::timeout(SCHEMA_HEADER_TIMEOUT, attempt).await {
+                Ok(Ok(header)) => return Ok(header),
+                Ok(Err(error)) => {
+                    let permanent = error
+                        .downcast_ref::<SchemaRegistryError>()
+                        .map_or(true, SchemaRegistryError::is_permanent);
+                    if permanent {
+                        error!(
+                            message = "Failed to resolve the schema registry header.",
+                            subject = %self.subject,
+                            %error,
+                        );
+                        return Err(());
+                    }
+                    warn!(
+                        message = "Failed to reach the schema registry, retrying.",
+                        subject = %self.subject,
+                        %error,
+                        internal_log_rate_limit = true,
+                    );
+                }
+                Err(_) => warn!(
+                    message = "Timed out reaching the schema registry, retrying.",
+                    subject = %self.subject,
+                    timeout_secs = SCHEMA_HEADER_TIMEOUT.as_secs(),
+                    internal_log_rate_limit = true,
+                ),
+            }
+            tokio::time::sleep(backoff.next().expect("backoff never ends")).await;
+        }
+    }
 }
 
 pub(crate) fn create_producer(
@@ -46,12 +127,20 @@ pub(crate) fn create_producer(
 }
 
 impl KafkaSink {
-    pub(crate) fn new(config: KafkaSinkConfig) -> crate::Result<Self> {
+    pub(crate) fn new(
+        config: KafkaSinkConfig,
+        schema_header: Option<SchemaHeader>,
+    ) -> crate::Result<Self> {
         let producer_config = config.to_rdkafka(KafkaRole::Producer)?;
         let producer = create_producer(producer_config)?;
         let transformer = config.encoding.transformer();
         let serializer = config.encoding.build()?;
         let encoder = Encoder::<()>::new(serializer);
+        let transactions = config
+            .transaction
+            .as_ref()
+            .map(|transaction| KafkaTransactions::new(&config, transaction, producer.clone()))
+            .transpose()?;
 
         Ok(KafkaSink {
             headers_key: config.headers_key,
@@ -60,20 +149,32 @@ impl KafkaSink {
             service: KafkaService::new(producer),
             topic: config.topic,
             key_field: config.key_field,
+            schema_header,
+            transactions,
         })
     }
 
     async fn run_inner(self: Box<Self>, input: BoxStream<'_, Event>) -> Result<(), ()> {
-        // rdkafka will internally retry forever, so we need some limit to prevent this from overflowing
-        let service = ConcurrencyLimit::new(self.service, QUEUED_MIN_MESSAGES as usize);
+        let schema_header = match &self.schema_header {
+            Some(schema_header) => Some(schema_header.resolve().await?),
+            None => None,
+        };
         let mut request_builder = KafkaRequestBuilder {
             key_field: self.key_field,
             headers_key: self.headers_key,
             topic_template: self.topic,
             transformer: self.transformer,
             encoder: self.encoder,
+            schema_header,
         };
 
+        if let Some(transactions) = self.transactions {
+            return transactions.run(input, request_builder, self.service).await;
+        }
+
+        // rdkafka will internally retry forever, so we need some limit to prevent this from overflowing
+        let service = ConcurrencyLimit::new(self.service, QUEUED_MIN_MESSAGES as usize);
+
         input
             .filter_map(|event|
                 // request_builder is fallible but the places it can fail are emitting
@@ -14,6 +14,8 @@ mod integration_test {
     use codecs::TextSerializerConfig;
     use futures::StreamExt;
     use rdkafka::{
+        admin::{AdminClient, AdminOptions, NewTopic, TopicReplication},
+        client::DefaultClientContext,
         consumer::{BaseConsumer, Consumer},
         message::Headers,
         Message, Offset, TopicPartitionList,
@@ -70,6 +72,8 @@ mod integration_test {
             message_timeout_ms: Duration::from_millis(300000),
             librdkafka_options: HashMap::new(),
             headers_key: None,
+            transaction: None,
+            schema_registry: None,
             acknowledgements: Default::default(),
         };
         self::sink::healthcheck(config).await.unwrap();
@@ -168,12 +172,14 @@ mod integration_test {
             batch,
             librdkafka_options,
             headers_key: None,
+            transaction: None,
+            schema_registry: None,
             acknowledgements: Default::default(),
         };
         config.clone().to_rdkafka(KafkaRole::Consumer)?;
         config.clone().to_rdkafka(KafkaRole::Producer)?;
         self::sink::healthcheck(config.clone()).await?;
-        KafkaSink::new(config)
+        KafkaSink::new(config, None)
     }
 
     #[tokio::test]
@@ -316,6 +322,8 @@ mod integration_test {
             message_timeout_ms: Duration::from_millis(300000),
             librdkafka_options: HashMap::new(),
             headers_key: Some(headers_key.clone()),
+            transaction: None,
+            schema_registry: None,
             acknowledgements: Default::default(),
         };
         let topic = format!("{}-{}", topic, chrono::Utc::now().format("%Y%m%d"));
@@ -342,7 +350,7 @@ mod integration_test {
 
         if test_telemetry_tags {
             assert_data_volume_sink_compliance(&DATA_VOLUME_SINK_TAGS, async move {
-                let sink = KafkaSink::new(config).unwrap();
+                let sink = KafkaSink::new(config, None).unwrap();
                 let sink = VectorSink::from_event_streamsink(sink);
                 sink.run(input_events).await
             })
@@ -350,7 +358,7 @@ mod integration_test {
             .expect("Running sink failed");
         } else {
             assert_sink_compliance(&SINK_TAGS, async move {
-                let sink = KafkaSink::new(config).unwrap();
+                let sink = KafkaSink::new(config, None).unwrap();
                 let sink = VectorSink::from_event_streamsink(sink);
                 sink.run(input_events).await
             })
@@ -415,4 +423,124 @@ mod integration_test {
         assert_eq!(out.len(), input.len());
         assert_eq!(out, input);
     }
+
+    #[tokio::test]
+    async fn kafka_transactions_with_source_offsets() {
+        crate::test_util::trace_init();
+
+        let server = kafka_address(9091);
+        let topic = format!("test-{}", random_string(10));
+        let source_topic = format!("source-{}", random_string(10));
+        let group_id = format!("group-{}", random_string(10));
+
+        // The offsets can only be committed for an existing topic.
+        let mut client_config = rdkafka::ClientConfig::new();
+        client_config.set("bootstrap.servers", server.as_str());
+        let admin: AdminClient<DefaultClientContext> = client_config.create().unwrap();
+        for result in admin
+            .create_topics(
+                [&NewTopic::new(&source_topic, 1, TopicReplication::Fixed(1))],
+                &AdminOptions::default(),
+            )
+            .await
+            .unwrap()
+        {
+            result.unwrap();
+        }
+
+        let transaction = toml::from_str(&format!(
+            r#"
+            transactional_id = "{}"
+            batch.max_events = 10
+            source_offsets.group_id = "{}"
+            "#,
+            random_string(10),
+            group_id
+        ))
+        .unwrap();
+        let config = KafkaSinkConfig {
+            bootstrap_servers: server.clone(),
+            topic: Template::try_from(topic.clone()).unwrap(),
+            key_field: None,
+            encoding: TextSerializerConfig::default().into(),
+            batch: BatchConfig::default(),
+            compression: KafkaCompression::None,
+            auth: KafkaAuthConfig::default(),
+            socket_timeout_ms: Duration::from_millis(60000),
+            message_timeout_ms: Duration::from_millis(300000),
+            librdkafka_options: HashMap::new(),
+            headers_key: None,
+            transaction: Some(transaction),
+            schema_registry: None,
+            acknowledgements: Default::default(),
+        };
+
+        let num_events = 100;
+        let (batch, mut receiver) = BatchNotifier::new_with_receiver();
+        let (input, events) = random_lines_with_stream(100, num_events, Some(batch));
+
+        // Pretend the events were consumed from the source topic by a `kafka` source.
+        let events_source_topic = source_topic.clone();
+        let mut offset = 0_i64;
+        let events = events.map(move |mut events| {
+            events.iter_logs_mut().for_each(|log| {
+                log.insert("topic", events_source_topic.clone());
+                log.insert("partition", 0_i64);
+                log.insert("offset", offset);
+                offset += 1;
+            });
+            events
+        });
+
+        assert_sink_compliance(&SINK_TAGS, async move {
+            let sink = KafkaSink::new(config, None).unwrap();
+            let sink = VectorSink::from_event_streamsink(sink);
+            sink.run(events).await
+        })
+        .await
+        .expect("Running sink failed");
+        assert_eq!(receiver.try_recv(), Ok(BatchStatus::Delivered));
+
+        client_config
+            .set("group.id", &group_id)
+            .set("isolation.level", "read_committed")
+            .set("enable.partition.eof", "true");
+        let consumer: BaseConsumer = client_config.create().unwrap();
+
+        // the next message of the source topic is the one following the last event
+        let mut source_partitions = TopicPartitionList::new();
+        source_partitions.add_partition(&source_topic, 0);
+        let committed = consumer
+            .committed_offsets(source_partitions, Duration::from_secs(3))
+            .unwrap();
+        assert_eq!(
+            committed.find_partition(&source_topic, 0).unwrap().offset(),
+            Offset::Offset(num_events as i64)
+        );
+
+        // read back everything from the beginning
+        let mut tpl = TopicPartitionList::new();
+        tpl.add_partition(&topic, 0)
+            .set_offset(Offset::Beginning)
+            .unwrap();
+        consumer.assign(&tpl).unwrap();
+
+        let mut failures = 0;
+        let mut out = Vec::new();
+        while failures < 100 {
+            match consumer.poll(Duration::from_secs(3)) {
+                Some(Ok(msg)) => {
+                    let s: &str = msg.payload_view().unwrap().unwrap();
+                    out.push(s.to_owned());
+                }
+                None if out.len() >= input.len() => break,
+                _ => {
+                    failures += 1;
+                    thread::sleep(Duration::from_millis(50));
+                }
+            }
+        }
+
+        assert_eq!(out, input);
+    }
 }
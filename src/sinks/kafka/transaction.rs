@@ -0,0 +1,413 @@
+use std::{
+    collections::BTreeMap,
+    sync::Arc,
+    time::{Duration, Instant},
+};
+
+use lookup::{path, OwnedValuePath, PathPrefix};
+use rdkafka::{
+    consumer::{BaseConsumer, Consumer, ConsumerGroupMetadata},
+    error::{KafkaError, KafkaResult},
+    producer::{FutureProducer, Producer},
+    Offset, TopicPartitionList,
+};
+use snafu::ResultExt;
+use tokio::time::sleep;
+use vector_common::internal_event::RegisteredEventCache;
+
+use super::{
+    config::{KafkaRole, KafkaSinkConfig, KafkaSourceOffsetsConfig, KafkaTransactionConfig},
+    request_builder::KafkaRequestBuilder,
+    service::{KafkaRequest, KafkaResponse, KafkaService},
+    sink::{BuildError, KafkaCreateFailedSnafu},
+};
+use crate::{
+    internal_events::KafkaTransactionError,
+    kafka::KafkaStatisticsContext,
+    sinks::{prelude::*, util::retries::ExponentialBackoff},
+};
+
+/// Delivers batches of events in Kafka transactions, acknowledging them once committed.
+pub(super) struct KafkaTransactions {
+    producer: FutureProducer<KafkaStatisticsContext>,
+    timeout: Duration,
+    batch_settings: BatcherSettings,
+    source_offsets: Option<SourceOffsets>,
+}
+
+impl KafkaTransactions {
+    pub(super) fn new(
+        config: &KafkaSinkConfig,
+        transaction: &KafkaTransactionConfig,
+        producer: FutureProducer<KafkaStatisticsContext>,
+    ) -> crate::Result<Self> {
+        let source_offsets = transaction
+            .source_offsets
+            .as_ref()
+            .map(|offsets| SourceOffsets::new(config, offsets))
+            .transpose()?;
+
+        Ok(Self {
+            producer,
+            timeout: transaction.timeout_ms,
+            batch_settings: transaction.batch.into_batcher_settings()?,
+            source_offsets,
+        })
+    }
+
+    pub(super) async fn run(
+        self,
+        input: BoxStream<'_, Event>,
+        mut request_builder: KafkaRequestBuilder,
+        service: KafkaService,
+    ) -> Result<(), ()> {
+        // This fences off the previous instances of the producer, and aborts their pending
+        // transaction.
+        if let Err(error) = self
+            .call(|producer, timeout| producer.init_transactions(timeout))
+            .await
+        {
+            emit!(KafkaTransactionError {
+                error: &error,
+                count: 0
+            });
+            return Err(());
+        }
+
+        let events_sent = RegisteredEventCache::new(());
+        let batches = input.batched(self.batch_settings.into_byte_size_config());
+        futures::pin_mut!(batches);
+
+        while let Some(events) = batches.next().await {
+            let offsets = self
+                .source_offsets
+                .as_ref()
+                .map(|source_offsets| source_offsets.fields.list(&events))
+                .transpose();
+
+            let mut finalizers = EventFinalizers::default();
+            let requests = events
+                .into_iter()
+                // request_builder is fallible but the places it can fail are emitting
+                // `Error` and `DroppedEvent` internal events appropriately so no need to here.
+                .filter_map(|event| request_builder.build_request(event))
+                .map(|mut request| {
+                    finalizers.merge(request.take_finalizers());
+                    request
+                })
+                .collect::<Vec<_>>();
+            let count = requests.len();
+
+            let result = match offsets {
+                Ok(offsets) => self.deliver(&service, requests, offsets.as_ref()).await,
+                Err(error) => Err(DeliveryError {
+                    error,
+                    fatal: false,
+                }),
+            };
+            match result {
+                Ok(responses) => {
+                    for response in responses {
+                        response.events_sent().emit_event(&events_sent);
+                    }
+                    finalizers.update_status(EventStatus::Delivered);
+                }
+                Err(DeliveryError { error, fatal }) => {
+                    emit!(KafkaTransactionError {
+                        error: &error,
+                        count
+                    });
+                    finalizers.update_status(EventStatus::Rejected);
+                    if fatal {
+                        return Err(());
+                    }
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Delivers a batch of requests in a transaction, which is retried until it's committed, or
+    /// fails with an error that can't be retried.
+    async fn deliver(
+        &self,
+        service: &KafkaService,
+        requests: Vec<KafkaRequest>,
+        offsets: Option<&TopicPartitionList>,
+    ) -> Result<Vec<KafkaResponse>, DeliveryError> {
+        let mut backoff = retry_backoff();
+        loop {
+            match self.transaction(service, requests.clone(), offsets).await {
+                Ok(responses) => return Ok(responses),
+                Err(error) => {
+                    if is_fatal(&error) {
+                        return Err(DeliveryError { error, fatal: true });
+                    }
+                    // Aborting purges the messages that are not delivered yet, so that none of
+                    // the batch is visible to `read_committed` consumers. If that fails, the
+                    // transaction is left open, and the producer can't begin the next one.
+                    if let Err(abort_error) = self
+                        .call(|producer, timeout| producer.abort_transaction(timeout))
+                        .await
+                    {
+                        warn!(message = "Failed to abort transaction.", %error);
+                        return Err(DeliveryError {
+                            error: abort_error,
+                            fatal: true,
+                        });
+                    }
+                    if !is_retriable(&error) {
+                        return Err(DeliveryError {
+                            error,
+                            fatal: false,
+                        });
+                    }
+                    warn!(
+                        message = "Retrying aborted transaction.",
+                        %error,
+                        internal_log_rate_limit = true,
+                    );
+                    sleep(backoff.next().unwrap()).await;
+                }
+            }
+        }
+    }
+
+    async fn transaction(
+        &self,
+        service: &KafkaService,
+        requests: Vec<KafkaRequest>,
+        offsets: Option<&TopicPartitionList>,
+    ) -> KafkaResult<Vec<KafkaResponse>> {
+        self.producer.begin_transaction()?;
+
+        let mut service = service.clone();
+        let responses =
+            future::try_join_all(requests.into_iter().map(|request| service.call(request))).await?;
+
+        if let Some((offsets, source_offsets)) = offsets.zip(self.source_offsets.as_ref()) {
+            let offsets = offsets.clone();
+            let group_metadata = Arc::clone(&source_offsets.group_metadata);
+            self.call(move |producer, timeout| {
+                producer.send_offsets_to_transaction(&offsets, &group_metadata, timeout)
+            })
+            .await?;
+        }
+
+        // A commit that keeps failing past the transaction timeout is given up, and the
+        // transaction is aborted and retried as a whole.
+        let deadline = Instant::now() + self.timeout;
+        let mut backoff = retry_backoff();
+        loop {
+            match self
+                .call(|producer, timeout| producer.commit_transaction(timeout))
+                .await
+            {
+                Err(KafkaError::Transaction(error))
+                    if error.is_retriable() && Instant::now() < deadline =>
+                {
+                    warn!(
+                        message = "Retrying transaction commit.",
+                        %error,
+                        internal_log_rate_limit = true,
+                    );
+                    let delay = backoff.next().expect("backoff never ends");
+                    sleep(delay.min(deadline.saturating_duration_since(Instant::now()))).await;
+                }
+                result => return result.map(|()| responses),
+            }
+        }
+    }
+
+    /// Runs one of the blocking transactional calls of the producer.
+    async fn call<F>(&self, f: F) -> KafkaResult<()>
+    where
+        F: FnOnce(&FutureProducer<KafkaStatisticsContext>, Duration) -> KafkaResult<()>
+            + Send
+            + 'static,
+    {
+        let producer = self.producer.clone();
+        let timeout = self.timeout;
+        tokio::task::spawn_blocking(move || f(&producer, timeout))
+            .await
+            .expect("transactional call panicked")
+    }
+}
+
+/// The failure to deliver a batch of events.
+struct DeliveryError {
+    error: KafkaError,
+    /// Whether the producer can't be used anymore, most likely because another instance with the
+    /// same transactional ID fenced it off, or because the failed transaction couldn't be aborted.
+    fatal: bool,
+}
+
+const fn retry_backoff() -> ExponentialBackoff {
+    ExponentialBackoff::from_millis(2)
+        .factor(250)
+        .max_delay(Duration::from_secs(60))
+}
+
+fn is_fatal(error: &KafkaError) -> bool {
+    matches!(error, KafkaError::Transaction(error) if error.is_fatal())
+}
+
+/// Whether a transaction that failed with this error may succeed once aborted and retried.
+///
+/// Delivery errors are not retried, as librdkafka already retries them until `message_timeout_ms`.
+fn is_retriable(error: &KafkaError) -> bool {
+    matches!(
+        error,
+        KafkaError::Transaction(error) if error.txn_requires_abort() || error.is_retriable()
+    )
+}
+
+/// The offsets of the messages consumed by a `kafka` source, committed in the transactions.
+struct SourceOffsets {
+    group_metadata: Arc<ConsumerGroupMetadata>,
+    fields: OffsetFields,
+}
+
+impl SourceOffsets {
+    fn new(config: &KafkaSinkConfig, offsets: &KafkaSourceOffsetsConfig) -> crate::Result<Self> {
+        let mut client_config = config.to_rdkafka(KafkaRole::Consumer)?;
+        client_config.set("group.id", &offsets.group_id);
+        // The consumer never joins the group, so its metadata has no generation nor member ID,
+        // and the broker only checks the group ID when the offsets are committed.
+        let consumer: BaseConsumer = client_config.create().context(KafkaCreateFailedSnafu)?;
+        let group_metadata =
+            consumer
+                .group_metadata()
+                .ok_or_else(|| BuildError::ConsumerGroupMetadataMissing {
+                    group_id: offsets.group_id.clone(),
+                })?;
+
+        Ok(Self {
+            group_metadata: Arc::new(group_metadata),
+            fields: OffsetFields {
+                topic: offsets.topic_key.0.clone(),
+                partition: offsets.partition_key.0.clone(),
+                offset: offsets.offset_key.0.clone(),
+            },
+        })
+    }
+}
+
+/// The log fields holding the topic, partition and offset of the consumed messages, in the
+/// `legacy` log namespace.
+struct OffsetFields {
+    topic: OwnedValuePath,
+    partition: OwnedValuePath,
+    offset: OwnedValuePath,
+}
+
+impl OffsetFields {
+    /// Reads the topic, partition and offset of the message an event was consumed from.
+    fn offset_of(&self, event: &Event) -> Option<(String, i32, i64)> {
+        let log = event.maybe_as_log()?;
+        let get = |name: &str, field: &OwnedValuePath| {
+            log.get((PathPrefix::Metadata, path!("kafka", name)))
+                .or_else(|| log.get((PathPrefix::Event, field)))
+        };
+
+        let topic = get("topic", &self.topic)?.as_str()?.into_owned();
+        let partition = get("partition", &self.partition)?.as_integer()?;
+        let offset = get("offset", &self.offset)?.as_integer()?;
+        Some((topic, partition.try_into().ok()?, offset))
+    }
+
+    /// Lists the offsets to commit for a batch of events, which are the ones following the last
+    /// consumed message of each partition.
+    fn list(&self, events: &[Event]) -> KafkaResult<TopicPartitionList> {
+        let mut next_offsets = BTreeMap::new();
+        for (topic, partition, offset) in events.iter().filter_map(|event| self.offset_of(event)) {
+            let next_offset = next_offsets.entry((topic, partition)).or_insert(offset + 1);
+            *next_offset = (*next_offset).max(offset + 1);
+        }
+
+        let mut list = TopicPartitionList::new();
+        for ((topic, partition), offset) in next_offsets {
+            list.add_partition_offset(&topic, partition, Offset::Offset(offset))?;
+        }
+        Ok(list)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use lookup::owned_value_path;
+
+    use super::*;
+
+    fn fields() -> OffsetFields {
+        OffsetFields {
+            topic: owned_value_path!("topic"),
+            partition: owned_value_path!("partition"),
+            offset: owned_value_path!("offset"),
+        }
+    }
+
+    fn legacy_event(topic: &str, partition: i64, offset: i64) -> Event {
+        let mut log = LogEvent::from("message");
+        log.insert("topic", topic);
+        log.insert("partition", partition);
+        log.insert("offset", offset);
+        Event::Log(log)
+    }
+
+    #[test]
+    fn offset_of_vector_namespace_event() {
+        let mut log = LogEvent::from("message");
+        log.insert("offset", 1_i64);
+        let metadata = log.metadata_mut().value_mut();
+        metadata.insert(path!("kafka", "topic"), "logs");
+        metadata.insert(path!("kafka", "partition"), 2_i64);
+        metadata.insert(path!("kafka", "offset"), 42_i64);
+
+        assert_eq!(
+            fields().offset_of(&Event::Log(log)),
+            Some(("logs".to_owned(), 2, 42))
+        );
+    }
+
+    #[test]
+    fn offset_of_event_without_offset() {
+        assert_eq!(
+            fields().offset_of(&Event::Log(LogEvent::from("message"))),
+            None
+        );
+    }
+
+    #[test]
+    fn list_next_offsets() {
+        let events = vec![
+            legacy_event("logs", 0, 7),
+            legacy_event("logs", 1, 3),
+            legacy_event("logs", 0, 5),
+            legacy_event("metrics", 0, 10),
+            Event::Log(LogEvent::from("message")),
+        ];
+
+        let list = fields().list(&events).unwrap();
+        let offsets = list
+            .elements()
+            .iter()
+            .map(|element| {
+                (
+                    element.topic().to_owned(),
+                    element.partition(),
+                    element.offset(),
+                )
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(
+            offsets,
+            vec![
+                ("logs".to_owned(), 0, Offset::Offset(8)),
+                ("logs".to_owned(), 1, Offset::Offset(4)),
+                ("metrics".to_owned(), 0, Offset::Offset(11)),
+            ]
+        );
+    }
+}
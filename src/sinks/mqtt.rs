@@ -0,0 +1,657 @@
+use std::{
+    collections::{HashMap, VecDeque},
+    convert::TryFrom,
+    sync::{Arc, Mutex},
+    time::Duration,
+};
+
+use async_trait::async_trait;
+use bytes::BytesMut;
+use codecs::JsonSerializerConfig;
+use futures::{stream::BoxStream, FutureExt, StreamExt};
+use rumqttc::{
+    AsyncClient, ConnectionError, Event as MqttEvent, EventLoop, MqttOptions, Outgoing, Packet,
+};
+use snafu::{ResultExt, Snafu};
+use tokio::sync::Notify;
+use tokio_util::codec::Encoder as _;
+use vector_common::{
+    internal_event::{
+        error_stage, ByteSize, BytesSent, CountByteSize, EventsSent, InternalEventHandle, Output,
+        Protocol,
+    },
+    sensitive_string::SensitiveString,
+};
+use vector_config::configurable_component;
+
+use crate::{
+    codecs::{Encoder, EncodingConfig, Transformer},
+    config::{AcknowledgementsConfig, DataType, GenerateConfig, Input, SinkConfig, SinkContext},
+    event::{EstimatedJsonEncodedSizeOf, Event, EventFinalizers, EventStatus, Finalizable},
+    internal_events::{MqttConnectionError, MqttEventSendError, TemplateRenderingError},
+    mqtt::{
+        client_id, configure_options, default_clean_session, default_keep_alive, default_port,
+        MqttConfigError, MqttQoS,
+    },
+    sinks::util::StreamSink,
+    template::{Template, TemplateParseError},
+    tls::TlsEnableableConfig,
+};
+
+/// The amount of time to wait before reconnecting after the connection to the broker is lost.
+const RECONNECT_DELAY: Duration = Duration::from_secs(1);
+
+/// The number of messages that can be queued up in the client before publishing blocks.
+const CLIENT_CAPACITY: usize = 1024;
+
+#[derive(Debug, Snafu)]
+enum BuildError {
+    #[snafu(display("invalid encoding: {}", source))]
+    Encoding {
+        source: codecs::encoding::BuildError,
+    },
+    #[snafu(display("invalid topic template: {}", source))]
+    TopicTemplate { source: TemplateParseError },
+    #[snafu(display("{}", source))]
+    Config { source: MqttConfigError },
+}
+
+#[derive(Debug, Snafu)]
+enum HealthcheckError {
+    #[snafu(display("MQTT Connect Error: {}", source))]
+    Connect { source: ConnectionError },
+    #[snafu(display(
+        "MQTT Connect Error: timed out waiting for the broker to accept the connection"
+    ))]
+    Timeout,
+}
+
+/// Configuration for the `mqtt` sink.
+#[configurable_component(sink("mqtt", "Publish observability data to topics on an MQTT broker."))]
+#[derive(Clone, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct MqttSinkConfig {
+    /// The host of the MQTT broker to connect to.
+    #[configurable(metadata(docs::examples = "mqtt.example.com"))]
+    #[configurable(metadata(docs::examples = "127.0.0.1"))]
+    host: String,
+
+    /// The port of the MQTT broker to connect to.
+    #[serde(default = "default_port")]
+    port: u16,
+
+    /// The username to authenticate with.
+    ///
+    /// Must be set together with `password`.
+    #[configurable(metadata(docs::examples = "vector"))]
+    user: Option<String>,
+
+    /// The password to authenticate with.
+    ///
+    /// Must be set together with `user`.
+    #[configurable(metadata(docs::examples = "${MQTT_PASSWORD}"))]
+    password: Option<SensitiveString>,
+
+    /// The client ID to connect with.
+    ///
+    /// Must be set when `clean_session` is disabled, as persistent sessions are tied to the client
+    /// ID. Otherwise, a random client ID is generated if not set.
+    #[configurable(metadata(docs::examples = "vector"))]
+    client_id: Option<String>,
+
+    /// The interval, in seconds, at which to send keep-alive messages to the broker.
+    #[serde(default = "default_keep_alive")]
+    #[configurable(metadata(docs::type_unit = "seconds"))]
+    keep_alive: u16,
+
+    /// Whether or not to start a clean session when connecting to the broker.
+    #[serde(default = "default_clean_session")]
+    clean_session: bool,
+
+    /// The MQTT [topic][mqtt_topics] to publish messages to.
+    ///
+    /// [mqtt_topics]: https://www.hivemq.com/blog/mqtt-essentials-part-5-mqtt-topics-best-practices/
+    #[configurable(metadata(docs::templateable))]
+    #[configurable(metadata(docs::examples = "vector"))]
+    #[configurable(metadata(docs::examples = "logs/{{ host }}"))]
+    topic: String,
+
+    #[configurable(derived)]
+    #[serde(default)]
+    qos: MqttQoS,
+
+    /// Whether or not the broker should retain the last message published to each topic.
+    ///
+    /// Retained messages are delivered to clients as soon as they subscribe to the topic.
+    #[serde(default)]
+    retain: bool,
+
+    #[configurable(derived)]
+    tls: Option<TlsEnableableConfig>,
+
+    #[configurable(derived)]
+    encoding: EncodingConfig,
+
+    #[configurable(derived)]
+    #[serde(
+        default,
+        deserialize_with = "crate::serde::bool_or_struct",
+        skip_serializing_if = "crate::serde::skip_serializing_if_default"
+    )]
+    pub acknowledgements: AcknowledgementsConfig,
+}
+
+impl GenerateConfig for MqttSinkConfig {
+    fn generate_config() -> toml::Value {
+        toml::Value::try_from(Self {
+            host: "127.0.0.1".into(),
+            port: default_port(),
+            user: None,
+            password: None,
+            client_id: None,
+            keep_alive: default_keep_alive(),
+            clean_session: default_clean_session(),
+            topic: "vector".into(),
+            qos: MqttQoS::default(),
+            retain: false,
+            tls: None,
+            encoding: JsonSerializerConfig::default().into(),
+            acknowledgements: Default::default(),
+        })
+        .unwrap()
+    }
+}
+
+#[async_trait::async_trait]
+#[typetag::serde(name = "mqtt")]
+impl SinkConfig for MqttSinkConfig {
+    async fn build(
+        &self,
+        _cx: SinkContext,
+    ) -> crate::Result<(super::VectorSink, super::Healthcheck)> {
+        let sink = MqttSink::new(self.clone())?;
+        let healthcheck = healthcheck(self.clone()).boxed();
+        Ok((super::VectorSink::from_event_streamsink(sink), healthcheck))
+    }
+
+    fn input(&self) -> Input {
+        Input::new(self.encoding.config().input_type() & DataType::Log)
+    }
+
+    fn acknowledgements(&self) -> &AcknowledgementsConfig {
+        &self.acknowledgements
+    }
+}
+
+impl MqttSinkConfig {
+    /// Creates the client, which connects to the broker once its event loop is polled.
+    fn connect(&self) -> Result<(AsyncClient, EventLoop), BuildError> {
+        let client_id = client_id(&self.client_id, self.clean_session).context(ConfigSnafu)?;
+        let mut options = MqttOptions::new(client_id, &self.host, self.port);
+        options.set_keep_alive(Duration::from_secs(self.keep_alive.into()));
+        options.set_clean_session(self.clean_session);
+        configure_options(&mut options, &self.user, &self.password, &self.tls)
+            .context(ConfigSnafu)?;
+
+        Ok(AsyncClient::new(options, CLIENT_CAPACITY))
+    }
+}
+
+async fn healthcheck(config: MqttSinkConfig) -> crate::Result<()> {
+    // Use a separate client ID, so that the connection of the sink itself isn't taken over.
+    let config = MqttSinkConfig {
+        client_id: None,
+        clean_session: true,
+        ..config
+    };
+    let (_client, mut eventloop) = config.connect()?;
+
+    tokio::time::timeout(Duration::from_secs(10), wait_for_connack(&mut eventloop))
+        .await
+        .map_err(|_| HealthcheckError::Timeout)?
+        .map_err(Into::into)
+}
+
+async fn wait_for_connack(eventloop: &mut EventLoop) -> Result<(), HealthcheckError> {
+    loop {
+        if let MqttEvent::Incoming(Packet::ConnAck(_)) =
+            eventloop.poll().await.context(ConnectSnafu)?
+        {
+            return Ok(());
+        }
+    }
+}
+
+pub struct MqttSink {
+    transformer: Transformer,
+    encoder: Encoder<()>,
+    client: AsyncClient,
+    eventloop: EventLoop,
+    topic: Template,
+    qos: MqttQoS,
+    retain: bool,
+}
+
+impl MqttSink {
+    fn new(config: MqttSinkConfig) -> Result<Self, BuildError> {
+        let (client, eventloop) = config.connect()?;
+        let transformer = config.encoding.transformer();
+        let serializer = config.encoding.build().context(EncodingSnafu)?;
+        let encoder = Encoder::<()>::new(serializer);
+
+        Ok(MqttSink {
+            transformer,
+            encoder,
+            client,
+            eventloop,
+            topic: Template::try_from(config.topic).context(TopicTemplateSnafu)?,
+            qos: config.qos,
+            retain: config.retain,
+        })
+    }
+}
+
+/// The finalizers of the messages that have been published, but not yet acknowledged by the broker.
+///
+/// The client doesn't tell which packet ID it assigns to a message, so the finalizers are queued in
+/// the order the messages are published, and matched up with the packet IDs as the event loop sends
+/// the messages out.
+#[derive(Default)]
+struct PendingAcks {
+    /// The finalizers of the messages queued in the client, in the order they were published.
+    queued: VecDeque<EventFinalizers>,
+
+    /// The finalizers of the messages sent to the broker, by packet ID.
+    ///
+    /// A packet ID can be used by two messages at once, as the client sends out a message that was
+    /// held back because of a packet ID collision before handling the acknowledgement that freed
+    /// the packet ID up.
+    sent: HashMap<u16, VecDeque<EventFinalizers>>,
+
+    /// The finalizers of the message held back by the client because its packet ID was still in use.
+    collided: Option<(u16, EventFinalizers)>,
+}
+
+impl PendingAcks {
+    fn is_empty(&self) -> bool {
+        self.queued.is_empty() && self.sent.is_empty() && self.collided.is_none()
+    }
+
+    fn handle_outgoing(&mut self, outgoing: &Outgoing) {
+        match *outgoing {
+            // Messages with a QoS of `at_most_once` are never acknowledged by the broker.
+            Outgoing::Publish(0) => {
+                if let Some(finalizers) = self.queued.pop_front() {
+                    finalizers.update_status(EventStatus::Delivered);
+                }
+            }
+            Outgoing::Publish(pkid) => {
+                let finalizers = match self.collided.take() {
+                    Some((collided, finalizers)) if collided == pkid => Some(finalizers),
+                    collided => {
+                        self.collided = collided;
+                        // Messages that are still waiting on an acknowledgement are sent out again
+                        // when reconnecting to the broker.
+                        if self.sent.contains_key(&pkid) {
+                            None
+                        } else {
+                            self.queued.pop_front()
+                        }
+                    }
+                };
+                if let Some(finalizers) = finalizers {
+                    self.sent.entry(pkid).or_default().push_back(finalizers);
+                }
+            }
+            Outgoing::AwaitAck(pkid) => {
+                if let Some(finalizers) = self.queued.pop_front() {
+                    self.collided = Some((pkid, finalizers));
+                }
+            }
+            _ => {}
+        }
+    }
+
+    fn handle_incoming(&mut self, packet: &Packet) {
+        // The broker acknowledges messages with a QoS of `at_least_once` with `PUBACK`, and those
+        // with a QoS of `exactly_once` with `PUBCOMP`, once it has taken ownership of them.
+        let pkid = match packet {
+            Packet::PubAck(ack) => ack.pkid,
+            Packet::PubComp(comp) => comp.pkid,
+            _ => return,
+        };
+        if let Some(sent) = self.sent.get_mut(&pkid) {
+            if let Some(finalizers) = sent.pop_front() {
+                finalizers.update_status(EventStatus::Delivered);
+            }
+            if sent.is_empty() {
+                self.sent.remove(&pkid);
+            }
+        }
+    }
+}
+
+/// Drives the connection to the broker, which is where messages are actually sent.
+///
+/// Returns once all handles to the client have been dropped.
+async fn drive_eventloop(
+    mut eventloop: EventLoop,
+    pending: Arc<Mutex<PendingAcks>>,
+    acked: Arc<Notify>,
+) {
+    loop {
+        match eventloop.poll().await {
+            Ok(MqttEvent::Outgoing(outgoing)) => {
+                pending.lock().unwrap().handle_outgoing(&outgoing);
+                acked.notify_waiters();
+            }
+            Ok(MqttEvent::Incoming(packet)) => {
+                pending.lock().unwrap().handle_incoming(&packet);
+                acked.notify_waiters();
+            }
+            Err(ConnectionError::RequestsDone) => break,
+            Err(error) => {
+                emit!(MqttConnectionError {
+                    error,
+                    stage: error_stage::SENDING,
+                });
+                // Polling the event loop again reconnects to the broker.
+                tokio::time::sleep(RECONNECT_DELAY).await;
+            }
+        }
+    }
+}
+
+#[async_trait]
+impl StreamSink<Event> for MqttSink {
+    async fn run(self: Box<Self>, mut input: BoxStream<'_, Event>) -> Result<(), ()> {
+        let bytes_sent = register!(BytesSent::from(Protocol::TCP));
+        let events_sent = register!(EventsSent::from(Output(None)));
+
+        let MqttSink {
+            transformer,
+            mut encoder,
+            client,
+            eventloop,
+            topic,
+            qos,
+            retain,
+        } = *self;
+        let pending = Arc::new(Mutex::new(PendingAcks::default()));
+        let acked = Arc::new(Notify::new());
+        let connection = tokio::spawn(drive_eventloop(
+            eventloop,
+            Arc::clone(&pending),
+            Arc::clone(&acked),
+        ));
+
+        while let Some(mut event) = input.next().await {
+            let finalizers = event.take_finalizers();
+
+            let topic = match topic.render_string(&event) {
+                Ok(topic) => topic,
+                Err(error) => {
+                    emit!(TemplateRenderingError {
+                        error,
+                        field: Some("topic"),
+                        drop_event: true,
+                    });
+                    finalizers.update_status(EventStatus::Rejected);
+                    continue;
+                }
+            };
+
+            transformer.transform(&mut event);
+
+            let event_byte_size = event.estimated_json_encoded_size_of();
+
+            let mut bytes = BytesMut::new();
+            if encoder.encode(event, &mut bytes).is_err() {
+                // Error is handled by `Encoder`.
+                finalizers.update_status(EventStatus::Rejected);
+                continue;
+            }
+            let byte_size = bytes.len();
+
+            // The client only queues the message, which is then sent by the event loop, retrying
+            // on reconnection for a QoS of `at_least_once` or `exactly_once`. The finalizers are
+            // queued first, as the event loop can send the message out before `publish` returns.
+            pending.lock().unwrap().queued.push_back(finalizers);
+            match client
+                .publish(topic, qos.into(), retain, bytes.freeze())
+                .await
+            {
+                Err(error) => {
+                    // Publishing only fails once the event loop is gone, so nothing else can have
+                    // taken the finalizers off the queue.
+                    if let Some(finalizers) = pending.lock().unwrap().queued.pop_back() {
+                        finalizers.update_status(EventStatus::Errored);
+                    }
+
+                    emit!(MqttEventSendError { error });
+                }
+                Ok(()) => {
+                    events_sent.emit(CountByteSize(1, event_byte_size));
+                    bytes_sent.emit(ByteSize(byte_size));
+                }
+            }
+        }
+
+        // Wait for the broker to acknowledge the messages still in flight, as the event loop stops
+        // once it has sent out the queued messages and sees that the client is gone.
+        loop {
+            let notified = acked.notified();
+            if pending.lock().unwrap().is_empty() {
+                break;
+            }
+            notified.await;
+        }
+        drop(client);
+        _ = connection.await;
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rumqttc::{PubAck, PubComp};
+    use vector_core::event::{BatchNotifier, BatchStatus, BatchStatusReceiver, EventFinalizer};
+
+    use super::*;
+
+    #[test]
+    fn generate_config() {
+        crate::test_util::test_generate_config::<MqttSinkConfig>();
+    }
+
+    fn publish(pending: &mut PendingAcks) -> BatchStatusReceiver {
+        let (batch, receiver) = BatchNotifier::new_with_receiver();
+        pending
+            .queued
+            .push_back(EventFinalizers::new(EventFinalizer::new(batch)));
+        receiver
+    }
+
+    #[test]
+    fn pending_acks_resolve_on_ack() {
+        let mut pending = PendingAcks::default();
+        let mut first = publish(&mut pending);
+        let mut second = publish(&mut pending);
+        let mut third = publish(&mut pending);
+
+        pending.handle_outgoing(&Outgoing::Publish(0));
+        pending.handle_outgoing(&Outgoing::Publish(1));
+        pending.handle_outgoing(&Outgoing::Publish(2));
+        assert_eq!(first.try_recv(), Ok(BatchStatus::Delivered));
+        assert!(second.try_recv().is_err());
+        assert!(third.try_recv().is_err());
+
+        pending.handle_incoming(&Packet::PubComp(PubComp::new(2)));
+        assert_eq!(third.try_recv(), Ok(BatchStatus::Delivered));
+        assert!(second.try_recv().is_err());
+
+        pending.handle_incoming(&Packet::PubAck(PubAck::new(1)));
+        assert_eq!(second.try_recv(), Ok(BatchStatus::Delivered));
+        assert!(pending.is_empty());
+    }
+
+    #[test]
+    fn pending_acks_ignore_retransmissions() {
+        let mut pending = PendingAcks::default();
+        let mut first = publish(&mut pending);
+        let mut second = publish(&mut pending);
+
+        pending.handle_outgoing(&Outgoing::Publish(1));
+        // Sent out again after reconnecting.
+        pending.handle_outgoing(&Outgoing::Publish(1));
+        pending.handle_outgoing(&Outgoing::Publish(2));
+
+        pending.handle_incoming(&Packet::PubAck(PubAck::new(1)));
+        assert_eq!(first.try_recv(), Ok(BatchStatus::Delivered));
+        assert!(second.try_recv().is_err());
+
+        pending.handle_incoming(&Packet::PubAck(PubAck::new(2)));
+        assert_eq!(second.try_recv(), Ok(BatchStatus::Delivered));
+        assert!(pending.is_empty());
+    }
+
+    #[test]
+    fn pending_acks_handle_collisions() {
+        let mut pending = PendingAcks::default();
+        let mut first = publish(&mut pending);
+        let mut second = publish(&mut pending);
+
+        pending.handle_outgoing(&Outgoing::Publish(1));
+        pending.handle_outgoing(&Outgoing::AwaitAck(1));
+        // The held back message is sent out before the acknowledgement for the first one is
+        // handled.
+        pending.handle_outgoing(&Outgoing::Publish(1));
+        pending.handle_incoming(&Packet::PubAck(PubAck::new(1)));
+        assert_eq!(first.try_recv(), Ok(BatchStatus::Delivered));
+        assert!(second.try_recv().is_err());
+
+        pending.handle_incoming(&Packet::PubAck(PubAck::new(1)));
+        assert_eq!(second.try_recv(), Ok(BatchStatus::Delivered));
+        assert!(pending.is_empty());
+    }
+
+    #[test]
+    fn invalid_topic_template() {
+        let config = MqttSinkConfig {
+            topic: "{{ broken".into(),
+            ..toml::from_str(
+                r#"
+                host = "127.0.0.1"
+                topic = "vector"
+                encoding.codec = "text""#,
+            )
+            .unwrap()
+        };
+        assert!(matches!(
+            MqttSink::new(config),
+            Err(BuildError::TopicTemplate { .. })
+        ));
+    }
+}
+
+#[cfg(feature = "mqtt-integration-tests")]
+#[cfg(test)]
+mod integration_tests {
+    use codecs::TextSerializerConfig;
+    use rumqttc::QoS;
+
+    use super::*;
+    use crate::sinks::VectorSink;
+    use crate::test_util::{
+        components::{run_and_assert_sink_compliance, SINK_TAGS},
+        random_lines_with_stream, random_string, trace_init,
+    };
+
+    fn mqtt_host() -> String {
+        std::env::var("MQTT_HOST").unwrap_or_else(|_| "localhost".into())
+    }
+
+    fn config(topic: &str, qos: MqttQoS) -> MqttSinkConfig {
+        MqttSinkConfig {
+            host: mqtt_host(),
+            port: default_port(),
+            user: None,
+            password: None,
+            client_id: None,
+            keep_alive: default_keep_alive(),
+            clean_session: default_clean_session(),
+            topic: topic.into(),
+            qos,
+            retain: false,
+            tls: None,
+            encoding: TextSerializerConfig::default().into(),
+            acknowledgements: Default::default(),
+        }
+    }
+
+    async fn publish_and_check(qos: MqttQoS) {
+        trace_init();
+
+        let topic = format!("test/{}", random_string(10));
+        let conf = config(&topic, qos);
+
+        // Establish the consumer subscription.
+        let options = MqttOptions::new(format!("test-{}", random_string(10)), mqtt_host(), 1883);
+        let (consumer, mut eventloop) = AsyncClient::new(options, 100);
+        consumer.subscribe(&topic, QoS::ExactlyOnce).await.unwrap();
+        loop {
+            if let MqttEvent::Incoming(Packet::SubAck(_)) = eventloop.poll().await.unwrap() {
+                break;
+            }
+        }
+
+        // Publish events.
+        let num_events = 100;
+        let (input, events) = random_lines_with_stream(100, num_events, None);
+
+        let sink = VectorSink::from_event_streamsink(MqttSink::new(conf).unwrap());
+        run_and_assert_sink_compliance(sink, events, &SINK_TAGS).await;
+
+        let mut output = Vec::new();
+        while output.len() < input.len() {
+            let event = tokio::time::timeout(Duration::from_secs(10), eventloop.poll())
+                .await
+                .expect("timed out waiting for messages")
+                .unwrap();
+            if let MqttEvent::Incoming(Packet::Publish(publish)) = event {
+                output.push(String::from_utf8_lossy(&publish.payload).to_string());
+            }
+        }
+
+        assert_eq!(output, input);
+    }
+
+    #[tokio::test]
+    async fn mqtt_happy_at_most_once() {
+        publish_and_check(MqttQoS::AtMostOnce).await;
+    }
+
+    #[tokio::test]
+    async fn mqtt_happy_at_least_once() {
+        publish_and_check(MqttQoS::AtLeastOnce).await;
+    }
+
+    #[tokio::test]
+    async fn mqtt_happy_exactly_once() {
+        publish_and_check(MqttQoS::ExactlyOnce).await;
+    }
+
+    #[tokio::test]
+    async fn mqtt_healthcheck() {
+        trace_init();
+
+        assert!(healthcheck(config("vector", MqttQoS::default()))
+            .await
+            .is_ok());
+
+        let mut conf = config("vector", MqttQoS::default());
+        conf.port = 1;
+        assert!(healthcheck(conf).await.is_err());
+    }
+}
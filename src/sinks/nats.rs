@@ -4,6 +4,7 @@ use async_trait::async_trait;
 use bytes::BytesMut;
 use codecs::JsonSerializerConfig;
 use futures::{stream::BoxStream, FutureExt, StreamExt, TryFutureExt};
+use serde::Deserialize;
 use snafu::{ResultExt, Snafu};
 use tokio_util::codec::Encoder as _;
 use vector_common::internal_event::{
@@ -16,7 +17,7 @@ use crate::{
     config::{AcknowledgementsConfig, DataType, GenerateConfig, Input, SinkConfig, SinkContext},
     event::{EstimatedJsonEncodedSizeOf, Event, EventStatus, Finalizable},
     internal_events::{NatsEventSendError, TemplateRenderingError},
-    nats::{from_tls_auth_config, NatsAuthConfig, NatsConfigError},
+    nats::{from_tls_auth_config, jetstream_request, NatsAuthConfig, NatsConfigError},
     sinks::util::StreamSink,
     template::{Template, TemplateParseError},
     tls::TlsEnableableConfig,
@@ -95,6 +96,16 @@ pub struct NatsSinkConfig {
 
     #[configurable(derived)]
     auth: Option<NatsAuthConfig>,
+
+    /// Publish messages to [JetStream][jetstream], and wait for the server to acknowledge them.
+    ///
+    /// Events are only marked as delivered once the message has been persisted by the stream that
+    /// captures the subject. The subject must be captured by an existing stream, otherwise
+    /// publishing fails.
+    ///
+    /// [jetstream]: https://docs.nats.io/nats-concepts/jetstream
+    #[serde(default)]
+    jetstream: bool,
 }
 
 fn default_name() -> String {
@@ -111,6 +122,7 @@ impl GenerateConfig for NatsSinkConfig {
             subject: "from.vector".into(),
             tls: None,
             url: "nats://127.0.0.1:4222".into(),
+            jetstream: false,
         })
         .unwrap()
     }
@@ -162,8 +174,15 @@ pub struct NatsSink {
     encoder: Encoder<()>,
     connection: nats::asynk::Connection,
     subject: Template,
+    jetstream: bool,
 }
 
+/// Acknowledgement of a message published to JetStream.
+///
+/// Its contents aren't needed, as it's only used to know that the message has been persisted.
+#[derive(Deserialize)]
+struct PublishAck {}
+
 impl NatsSink {
     async fn new(config: NatsSinkConfig) -> Result<Self, BuildError> {
         let connection = config.connect().await?;
@@ -176,6 +195,7 @@ impl NatsSink {
             transformer,
             encoder,
             subject: Template::try_from(config.subject).context(SubjectTemplateSnafu)?,
+            jetstream: config.jetstream,
         })
     }
 }
@@ -213,7 +233,15 @@ impl StreamSink<Event> for NatsSink {
                 continue;
             }
 
-            match self.connection.publish(&subject, &bytes).await {
+            let result = if self.jetstream {
+                jetstream_request::<PublishAck>(&self.connection, &subject, &bytes)
+                    .await
+                    .map(|_| ())
+            } else {
+                self.connection.publish(&subject, &bytes).await
+            };
+
+            match result {
                 Err(error) => {
                     finalizers.update_status(EventStatus::Errored);
 
@@ -316,6 +344,7 @@ mod integration_tests {
             url,
             tls: None,
             auth: None,
+            jetstream: false,
         };
 
         let r = publish_and_check(conf).await;
@@ -347,6 +376,7 @@ mod integration_tests {
                     password: "natspass".to_string().into(),
                 },
             }),
+            jetstream: false,
         };
 
         publish_and_check(conf)
@@ -375,6 +405,7 @@ mod integration_tests {
                     password: "wrongpass".to_string().into(),
                 },
             }),
+            jetstream: false,
         };
 
         let r = publish_and_check(conf).await;
@@ -405,6 +436,7 @@ mod integration_tests {
                     value: "secret".to_string().into(),
                 },
             }),
+            jetstream: false,
         };
 
         let r = publish_and_check(conf).await;
@@ -435,6 +467,7 @@ mod integration_tests {
                     value: "wrongsecret".to_string().into(),
                 },
             }),
+            jetstream: false,
         };
 
         let r = publish_and_check(conf).await;
@@ -466,6 +499,7 @@ mod integration_tests {
                     seed: "SUANIRXEZUROTXNFN3TJYMT27K7ZZVMD46FRIHF6KXKS4KGNVBS57YAFGY".into(),
                 },
             }),
+            jetstream: false,
         };
 
         let r = publish_and_check(conf).await;
@@ -497,6 +531,7 @@ mod integration_tests {
                     seed: "SBBBBBBBBBBBBBBBBBBBBBBBBBBBBBBBBBBBBBBBBBBBBBBBBBBBBBBBBB".into(),
                 },
             }),
+            jetstream: false,
         };
 
         let r = publish_and_check(conf).await;
@@ -529,6 +564,7 @@ mod integration_tests {
                 },
             }),
             auth: None,
+            jetstream: false,
         };
 
         let r = publish_and_check(conf).await;
@@ -555,6 +591,7 @@ mod integration_tests {
             url,
             tls: None,
             auth: None,
+            jetstream: false,
         };
 
         let r = publish_and_check(conf).await;
@@ -589,6 +626,7 @@ mod integration_tests {
                 },
             }),
             auth: None,
+            jetstream: false,
         };
 
         let r = publish_and_check(conf).await;
@@ -621,6 +659,7 @@ mod integration_tests {
                 },
             }),
             auth: None,
+            jetstream: false,
         };
 
         let r = publish_and_check(conf).await;
@@ -657,6 +696,7 @@ mod integration_tests {
                     path: "tests/data/nats/nats.creds".into(),
                 },
             }),
+            jetstream: false,
         };
 
         let r = publish_and_check(conf).await;
@@ -693,6 +733,7 @@ mod integration_tests {
                     path: "tests/data/nats/nats-bad.creds".into(),
                 },
             }),
+            jetstream: false,
         };
 
         let r = publish_and_check(conf).await;
@@ -702,4 +743,43 @@ mod integration_tests {
             r
         );
     }
+
+    #[tokio::test]
+    async fn nats_jetstream() {
+        trace_init();
+
+        let subject = format!("test-{}", random_string(10));
+        let url = std::env::var("NATS_JETSTREAM_ADDRESS")
+            .unwrap_or_else(|_| String::from("nats://localhost:4222"));
+
+        let conf = NatsSinkConfig {
+            acknowledgements: Default::default(),
+            encoding: TextSerializerConfig::default().into(),
+            connection_name: "".to_owned(),
+            subject: subject.clone(),
+            url,
+            tls: None,
+            auth: None,
+            jetstream: true,
+        };
+
+        // Messages are only acknowledged when they are captured by a stream.
+        let nc = conf.connect().await.unwrap();
+        let stream = format!("stream-{}", random_string(10));
+        let request = serde_json::json!({ "name": stream, "subjects": [subject] });
+        let _: PublishAck = jetstream_request(
+            &nc,
+            &format!("$JS.API.STREAM.CREATE.{}", stream),
+            request.to_string(),
+        )
+        .await
+        .expect("failed to create stream");
+
+        let r = publish_and_check(conf).await;
+        assert!(
+            r.is_ok(),
+            "publish_and_check failed, expected Ok(()), got: {:?}",
+            r
+        );
+    }
 }
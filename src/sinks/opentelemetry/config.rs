@@ -0,0 +1,218 @@
+use http::Uri;
+use hyper::client::HttpConnector;
+use hyper_openssl::HttpsConnector;
+use hyper_proxy::ProxyConnector;
+use tonic::body::BoxBody;
+
+use super::{
+    request_builder::OpentelemetryRequestBuilder,
+    service::{OpentelemetryRetryLogic, OpentelemetryService, Transport},
+    sink::OpentelemetrySink,
+};
+use crate::{
+    config::ProxyConfig,
+    http::{build_proxy_connector, HttpClient},
+    sinks::{prelude::*, util::RealtimeSizeBasedDefaultBatchSettings},
+    tls::{MaybeTlsSettings, TlsEnableableConfig},
+};
+
+/// The transport used to export data to the OTLP endpoint.
+#[configurable_component]
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum OpentelemetryProtocol {
+    /// Export over gRPC, as `opentelemetry.proto.collector.*.v1` service calls.
+    #[default]
+    Grpc,
+
+    /// Export over HTTP, as binary protobuf payloads `POST`ed to `/v1/logs`, `/v1/metrics` and
+    /// `/v1/traces`.
+    Http,
+}
+
+/// Configuration for the `opentelemetry` sink.
+#[configurable_component(sink(
+    "opentelemetry",
+    "Deliver OTLP logs, metrics and traces to an OpenTelemetry collector."
+))]
+#[derive(Clone, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct OpentelemetrySinkConfig {
+    /// The endpoint to export data to.
+    ///
+    /// When using the `http` protocol, the signal specific path (`/v1/logs`, `/v1/metrics` or
+    /// `/v1/traces`) is appended to the path of this endpoint.
+    ///
+    /// If no scheme is given, `http` is used, or `https` if TLS is enabled.
+    #[configurable(validation(format = "uri"))]
+    #[configurable(metadata(docs::examples = "http://localhost:4317"))]
+    #[configurable(metadata(docs::examples = "https://otel-collector.example.com:4318"))]
+    pub endpoint: String,
+
+    #[configurable(derived)]
+    #[serde(default)]
+    pub protocol: OpentelemetryProtocol,
+
+    /// Compression configuration.
+    ///
+    /// Only `none` and `gzip` are supported, as they are the only compressions OTLP receivers are
+    /// required to support.
+    #[configurable(derived)]
+    #[serde(default)]
+    pub compression: Compression,
+
+    #[configurable(derived)]
+    #[serde(default)]
+    pub batch: BatchConfig<RealtimeSizeBasedDefaultBatchSettings>,
+
+    #[configurable(derived)]
+    #[serde(default)]
+    pub request: TowerRequestConfig,
+
+    #[configurable(derived)]
+    #[serde(default)]
+    pub tls: Option<TlsEnableableConfig>,
+
+    #[configurable(derived)]
+    #[serde(
+        default,
+        deserialize_with = "crate::serde::bool_or_struct",
+        skip_serializing_if = "crate::serde::skip_serializing_if_default"
+    )]
+    pub acknowledgements: AcknowledgementsConfig,
+}
+
+impl GenerateConfig for OpentelemetrySinkConfig {
+    fn generate_config() -> toml::Value {
+        toml::from_str(
+            r#"endpoint = "http://localhost:4317"
+            protocol = "grpc""#,
+        )
+        .unwrap()
+    }
+}
+
+#[async_trait::async_trait]
+#[typetag::serde(name = "opentelemetry")]
+impl SinkConfig for OpentelemetrySinkConfig {
+    async fn build(&self, cx: SinkContext) -> crate::Result<(VectorSink, Healthcheck)> {
+        let tls = MaybeTlsSettings::from_config(&self.tls, false)?;
+        let endpoint = with_default_scheme(&self.endpoint, tls.is_tls())?;
+
+        let gzip = match self.compression {
+            Compression::None => false,
+            Compression::Gzip(_) => true,
+            _ => return Err("Only `none` and `gzip` compression are supported.".into()),
+        };
+
+        let (transport, builder_compression) = match self.protocol {
+            OpentelemetryProtocol::Grpc => {
+                // gRPC compresses each message on its own, so the payload is left untouched by the
+                // request builder and compression is negotiated by the client instead.
+                let client = new_grpc_client(&tls, cx.proxy())?;
+                (Transport::grpc(client, endpoint, gzip), Compression::None)
+            }
+            OpentelemetryProtocol::Http => {
+                let client = HttpClient::new(tls, cx.proxy())?;
+                (Transport::http(client, endpoint)?, self.compression)
+            }
+        };
+
+        let protocol = transport.protocol();
+        let request_settings = self.request.unwrap_with(&TowerRequestConfig::default());
+        let batch_settings = self.batch.into_batcher_settings()?;
+
+        let service = ServiceBuilder::new()
+            .settings(request_settings, OpentelemetryRetryLogic)
+            .service(OpentelemetryService::new(transport));
+
+        let sink = OpentelemetrySink::new(
+            batch_settings,
+            OpentelemetryRequestBuilder::new(builder_compression),
+            service,
+            protocol,
+        );
+
+        Ok((
+            VectorSink::from_event_streamsink(sink),
+            future::ok(()).boxed(),
+        ))
+    }
+
+    fn input(&self) -> Input {
+        Input::all()
+    }
+
+    fn acknowledgements(&self) -> &AcknowledgementsConfig {
+        &self.acknowledgements
+    }
+}
+
+/// Neither the gRPC nor the HTTP client accept an endpoint without a scheme, so we default to
+/// `http` or `https` depending on whether TLS is enabled.
+fn with_default_scheme(endpoint: &str, tls: bool) -> crate::Result<Uri> {
+    let uri: Uri = endpoint.parse()?;
+    if uri.scheme().is_some() {
+        return Ok(uri);
+    }
+
+    let scheme = if tls { "https" } else { "http" };
+    let mut parts = uri.into_parts();
+    parts.scheme = Some(scheme.parse()?);
+    if parts.path_and_query.is_none() {
+        parts.path_and_query = Some("/".parse()?);
+    }
+    Ok(Uri::from_parts(parts)?)
+}
+
+fn new_grpc_client(
+    tls_settings: &MaybeTlsSettings,
+    proxy_config: &ProxyConfig,
+) -> crate::Result<hyper::Client<ProxyConnector<HttpsConnector<HttpConnector>>, BoxBody>> {
+    let proxy = build_proxy_connector(tls_settings.clone(), proxy_config)?;
+
+    Ok(hyper::Client::builder().http2_only(true).build(proxy))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn generate_config() {
+        crate::test_util::test_generate_config::<OpentelemetrySinkConfig>();
+    }
+
+    #[test]
+    fn default_scheme() {
+        assert_eq!(
+            with_default_scheme("localhost:4317", false).unwrap(),
+            "http://localhost:4317/"
+        );
+        assert_eq!(
+            with_default_scheme("localhost:4317", true).unwrap(),
+            "https://localhost:4317/"
+        );
+        assert_eq!(
+            with_default_scheme("http://collector:4318/otlp", true).unwrap(),
+            "http://collector:4318/otlp"
+        );
+    }
+
+    #[tokio::test]
+    async fn rejects_unsupported_compression() {
+        for protocol in ["grpc", "http"] {
+            let config: OpentelemetrySinkConfig = toml::from_str(&format!(
+                r#"
+                endpoint = "http://localhost:4317"
+                protocol = "{}"
+                compression = "zstd"
+                "#,
+                protocol
+            ))
+            .unwrap();
+
+            assert!(config.build(SinkContext::default()).await.is_err());
+        }
+    }
+}
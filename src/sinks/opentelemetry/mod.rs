@@ -0,0 +1,40 @@
+//! The OpenTelemetry sink
+//!
+//! This sink delivers logs, metrics and traces to any service implementing the OpenTelemetry
+//! Protocol (OTLP), such as the OpenTelemetry Collector. Events are batched per telemetry type and
+//! encoded with the protobuf definitions from `opentelemetry-proto`, then exported either over gRPC
+//! or over HTTP.
+
+use snafu::Snafu;
+
+mod config;
+mod request_builder;
+mod service;
+mod sink;
+
+#[cfg(test)]
+mod tests;
+
+pub use config::OpentelemetrySinkConfig;
+
+#[derive(Debug, Snafu)]
+#[snafu(visibility(pub))]
+pub enum OpentelemetrySinkError {
+    #[snafu(display("gRPC request failed: {}", source))]
+    Grpc { source: tonic::Status },
+
+    #[snafu(display("gRPC transport not ready: {}", source))]
+    GrpcTransport { source: crate::Error },
+
+    #[snafu(display("HTTP request failed: {}", source))]
+    Http { source: crate::http::HttpError },
+
+    #[snafu(display("HTTP request failed with status {}: {}", status, body))]
+    HttpStatus {
+        status: http::StatusCode,
+        body: String,
+    },
+
+    #[snafu(display("Failed to build HTTP request: {}", source))]
+    BuildRequest { source: http::Error },
+}
@@ -0,0 +1,102 @@
+use std::io;
+
+use bytes::Bytes;
+use opentelemetry_proto::encode::{logs_into_request, metrics_into_request, traces_into_request};
+use prost::Message;
+use vector_core::config::telemetry;
+
+use super::{service::OpentelemetryRequest, sink::TelemetryType};
+use crate::sinks::prelude::*;
+
+/// Encodes a batch of events of a single telemetry type into the matching OTLP
+/// `Export*ServiceRequest` message.
+#[derive(Clone, Debug, Default)]
+pub(super) struct OpentelemetryEncoder;
+
+impl encoding::Encoder<(TelemetryType, Vec<Event>)> for OpentelemetryEncoder {
+    fn encode_input(
+        &self,
+        (telemetry_type, events): (TelemetryType, Vec<Event>),
+        writer: &mut dyn io::Write,
+    ) -> io::Result<(usize, GroupedCountByteSize)> {
+        let mut byte_size = telemetry().create_request_count_byte_size();
+        for event in &events {
+            byte_size.add_event(event, event.estimated_json_encoded_size_of());
+        }
+
+        let n_events = events.len();
+        let body = match telemetry_type {
+            TelemetryType::Logs => {
+                logs_into_request(events.into_iter().map(Event::into_log)).encode_to_vec()
+            }
+            TelemetryType::Metrics => {
+                metrics_into_request(events.into_iter().map(Event::into_metric)).encode_to_vec()
+            }
+            TelemetryType::Traces => {
+                traces_into_request(events.into_iter().map(Event::into_trace)).encode_to_vec()
+            }
+        };
+
+        write_all(writer, n_events, &body)?;
+        Ok((body.len(), byte_size))
+    }
+}
+
+pub(super) struct OpentelemetryRequestBuilder {
+    compression: Compression,
+    encoder: OpentelemetryEncoder,
+}
+
+impl OpentelemetryRequestBuilder {
+    pub(super) const fn new(compression: Compression) -> Self {
+        Self {
+            compression,
+            encoder: OpentelemetryEncoder,
+        }
+    }
+}
+
+impl RequestBuilder<(TelemetryType, Vec<Event>)> for OpentelemetryRequestBuilder {
+    type Metadata = (TelemetryType, EventFinalizers);
+    type Events = (TelemetryType, Vec<Event>);
+    type Encoder = OpentelemetryEncoder;
+    type Payload = Bytes;
+    type Request = OpentelemetryRequest;
+    type Error = io::Error;
+
+    fn compression(&self) -> Compression {
+        self.compression
+    }
+
+    fn encoder(&self) -> &Self::Encoder {
+        &self.encoder
+    }
+
+    fn split_input(
+        &self,
+        (telemetry_type, mut events): (TelemetryType, Vec<Event>),
+    ) -> (Self::Metadata, RequestMetadataBuilder, Self::Events) {
+        let finalizers = events.take_finalizers();
+        let builder = RequestMetadataBuilder::from_events(&events);
+        (
+            (telemetry_type, finalizers),
+            builder,
+            (telemetry_type, events),
+        )
+    }
+
+    fn build_request(
+        &self,
+        (telemetry_type, finalizers): Self::Metadata,
+        metadata: RequestMetadata,
+        payload: EncodeResult<Self::Payload>,
+    ) -> Self::Request {
+        OpentelemetryRequest {
+            telemetry_type,
+            compression: self.compression,
+            body: payload.into_payload(),
+            finalizers,
+            metadata,
+        }
+    }
+}
@@ -0,0 +1,283 @@
+use std::task::{Context, Poll};
+
+use bytes::{Buf, BufMut, Bytes};
+use http::{
+    header::{CONTENT_ENCODING, CONTENT_TYPE},
+    uri::PathAndQuery,
+    StatusCode, Uri,
+};
+use hyper::{client::HttpConnector, Body};
+use hyper_openssl::HttpsConnector;
+use hyper_proxy::ProxyConnector;
+use snafu::ResultExt;
+use tonic::{
+    body::BoxBody,
+    codec::{Codec, CompressionEncoding, DecodeBuf, Decoder, EncodeBuf, Encoder},
+    Status,
+};
+
+use super::{sink::TelemetryType, BuildRequestSnafu, GrpcSnafu, HttpSnafu, OpentelemetrySinkError};
+use crate::{http::HttpClient, sinks::prelude::*};
+
+type GrpcClient =
+    tonic::client::Grpc<hyper::Client<ProxyConnector<HttpsConnector<HttpConnector>>, BoxBody>>;
+
+#[derive(Clone)]
+pub(super) struct OpentelemetryRequest {
+    pub(super) telemetry_type: TelemetryType,
+    pub(super) compression: Compression,
+    pub(super) body: Bytes,
+    pub(super) finalizers: EventFinalizers,
+    pub(super) metadata: RequestMetadata,
+}
+
+impl Finalizable for OpentelemetryRequest {
+    fn take_finalizers(&mut self) -> EventFinalizers {
+        self.finalizers.take_finalizers()
+    }
+}
+
+impl MetaDescriptive for OpentelemetryRequest {
+    fn get_metadata(&self) -> &RequestMetadata {
+        &self.metadata
+    }
+
+    fn metadata_mut(&mut self) -> &mut RequestMetadata {
+        &mut self.metadata
+    }
+}
+
+pub(super) struct OpentelemetryResponse {
+    metadata: RequestMetadata,
+}
+
+impl DriverResponse for OpentelemetryResponse {
+    fn event_status(&self) -> EventStatus {
+        EventStatus::Delivered
+    }
+
+    fn events_sent(&self) -> &GroupedCountByteSize {
+        self.metadata.events_estimated_json_encoded_byte_size()
+    }
+
+    fn bytes_sent(&self) -> Option<usize> {
+        Some(self.metadata.request_encoded_size())
+    }
+}
+
+/// The client used to reach the OTLP endpoint.
+#[derive(Clone, Debug)]
+pub(super) enum Transport {
+    Grpc {
+        client: GrpcClient,
+    },
+    Http {
+        client: HttpClient,
+        logs: Uri,
+        metrics: Uri,
+        traces: Uri,
+    },
+}
+
+impl Transport {
+    pub(super) fn grpc(
+        client: hyper::Client<ProxyConnector<HttpsConnector<HttpConnector>>, BoxBody>,
+        endpoint: Uri,
+        gzip: bool,
+    ) -> Self {
+        let mut client = tonic::client::Grpc::with_origin(client, endpoint);
+        if gzip {
+            client = client.send_compressed(CompressionEncoding::Gzip);
+        }
+        Self::Grpc { client }
+    }
+
+    pub(super) fn http(client: HttpClient, endpoint: Uri) -> crate::Result<Self> {
+        let base = endpoint.to_string();
+        let base = base.trim_end_matches('/');
+        Ok(Self::Http {
+            client,
+            logs: format!("{}/v1/logs", base).parse()?,
+            metrics: format!("{}/v1/metrics", base).parse()?,
+            traces: format!("{}/v1/traces", base).parse()?,
+        })
+    }
+
+    pub(super) const fn protocol(&self) -> &'static str {
+        match self {
+            Self::Grpc { .. } => "grpc",
+            Self::Http { .. } => "http",
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+pub(super) struct OpentelemetryService {
+    transport: Transport,
+}
+
+impl OpentelemetryService {
+    pub(super) const fn new(transport: Transport) -> Self {
+        Self { transport }
+    }
+}
+
+impl Service<OpentelemetryRequest> for OpentelemetryService {
+    type Response = OpentelemetryResponse;
+    type Error = OpentelemetrySinkError;
+    type Future = BoxFuture<'static, Result<Self::Response, Self::Error>>;
+
+    // Emission of an internal event in case of errors is handled upstream by the caller.
+    fn poll_ready(&mut self, _cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        // Readiness of the gRPC client is awaited inside `call()`, and the HTTP client is always
+        // ready to accept a request.
+        Poll::Ready(Ok(()))
+    }
+
+    // Emission of internal events for errors and dropped events is handled upstream by the caller.
+    fn call(&mut self, mut request: OpentelemetryRequest) -> Self::Future {
+        let transport = self.transport.clone();
+        let metadata = std::mem::take(request.metadata_mut());
+
+        Box::pin(async move {
+            match transport {
+                Transport::Grpc { mut client } => {
+                    client.ready().await.map_err(|error| {
+                        OpentelemetrySinkError::GrpcTransport {
+                            source: error.into(),
+                        }
+                    })?;
+                    client
+                        .unary(
+                            tonic::Request::new(request.body),
+                            grpc_path(request.telemetry_type),
+                            PassthroughCodec,
+                        )
+                        .await
+                        .context(GrpcSnafu)?;
+                }
+                Transport::Http {
+                    client,
+                    logs,
+                    metrics,
+                    traces,
+                } => {
+                    let uri = match request.telemetry_type {
+                        TelemetryType::Logs => logs,
+                        TelemetryType::Metrics => metrics,
+                        TelemetryType::Traces => traces,
+                    };
+
+                    let mut builder =
+                        http::Request::post(uri).header(CONTENT_TYPE, "application/x-protobuf");
+                    if let Some(encoding) = request.compression.content_encoding() {
+                        builder = builder.header(CONTENT_ENCODING, encoding);
+                    }
+                    let http_request = builder
+                        .body(Body::from(request.body))
+                        .context(BuildRequestSnafu)?;
+
+                    let response = client.send(http_request).await.context(HttpSnafu)?;
+                    let status = response.status();
+                    if !status.is_success() {
+                        let body = hyper::body::to_bytes(response.into_body())
+                            .await
+                            .map(|body| String::from_utf8_lossy(&body).into_owned())
+                            .unwrap_or_default();
+                        return Err(OpentelemetrySinkError::HttpStatus { status, body });
+                    }
+                }
+            }
+
+            Ok(OpentelemetryResponse { metadata })
+        })
+    }
+}
+
+fn grpc_path(telemetry_type: TelemetryType) -> PathAndQuery {
+    PathAndQuery::from_static(match telemetry_type {
+        TelemetryType::Logs => "/opentelemetry.proto.collector.logs.v1.LogsService/Export",
+        TelemetryType::Metrics => "/opentelemetry.proto.collector.metrics.v1.MetricsService/Export",
+        TelemetryType::Traces => "/opentelemetry.proto.collector.trace.v1.TraceService/Export",
+    })
+}
+
+/// A gRPC codec sending requests that were already encoded by the request builder, and discarding
+/// the (empty) export responses.
+///
+/// This lets the gRPC and HTTP transports share the same protobuf payload.
+#[derive(Clone, Copy, Debug, Default)]
+struct PassthroughCodec;
+
+impl Codec for PassthroughCodec {
+    type Encode = Bytes;
+    type Decode = ();
+    type Encoder = Self;
+    type Decoder = Self;
+
+    fn encoder(&mut self) -> Self::Encoder {
+        *self
+    }
+
+    fn decoder(&mut self) -> Self::Decoder {
+        *self
+    }
+}
+
+impl Encoder for PassthroughCodec {
+    type Item = Bytes;
+    type Error = Status;
+
+    fn encode(&mut self, item: Self::Item, dst: &mut EncodeBuf<'_>) -> Result<(), Self::Error> {
+        dst.put(item);
+        Ok(())
+    }
+}
+
+impl Decoder for PassthroughCodec {
+    type Item = ();
+    type Error = Status;
+
+    fn decode(&mut self, src: &mut DecodeBuf<'_>) -> Result<Option<Self::Item>, Self::Error> {
+        src.advance(src.remaining());
+        Ok(Some(()))
+    }
+}
+
+#[derive(Debug, Clone)]
+pub(super) struct OpentelemetryRetryLogic;
+
+impl RetryLogic for OpentelemetryRetryLogic {
+    type Error = OpentelemetrySinkError;
+    type Response = OpentelemetryResponse;
+
+    fn is_retriable_error(&self, error: &Self::Error) -> bool {
+        use tonic::Code::*;
+
+        match error {
+            // Retryable codes are listed in the OTLP specification:
+            //
+            // <https://github.com/open-telemetry/opentelemetry-specification/blob/main/specification/protocol/otlp.md#failures>
+            OpentelemetrySinkError::Grpc { source } => matches!(
+                source.code(),
+                Cancelled
+                    | DeadlineExceeded
+                    | Aborted
+                    | OutOfRange
+                    | Unavailable
+                    | DataLoss
+                    | ResourceExhausted
+            ),
+            OpentelemetrySinkError::GrpcTransport { .. } => true,
+            OpentelemetrySinkError::Http { source } => source.is_retriable(),
+            OpentelemetrySinkError::HttpStatus { status, .. } => matches!(
+                *status,
+                StatusCode::TOO_MANY_REQUESTS
+                    | StatusCode::BAD_GATEWAY
+                    | StatusCode::SERVICE_UNAVAILABLE
+                    | StatusCode::GATEWAY_TIMEOUT
+            ),
+            OpentelemetrySinkError::BuildRequest { .. } => false,
+        }
+    }
+}
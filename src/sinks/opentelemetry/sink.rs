@@ -0,0 +1,83 @@
+use std::num::NonZeroUsize;
+
+use super::{
+    request_builder::OpentelemetryRequestBuilder,
+    service::{OpentelemetryRetryLogic, OpentelemetryService},
+};
+use crate::sinks::prelude::*;
+
+/// The OTLP signal an event is exported as.
+///
+/// Each signal has its own export request, so batches never mix telemetry types.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub(super) enum TelemetryType {
+    Logs,
+    Metrics,
+    Traces,
+}
+
+#[derive(Default)]
+struct TelemetryTypePartitioner;
+
+impl Partitioner for TelemetryTypePartitioner {
+    type Item = Event;
+    type Key = TelemetryType;
+
+    fn partition(&self, item: &Self::Item) -> Self::Key {
+        match item {
+            Event::Log(_) => TelemetryType::Logs,
+            Event::Metric(_) => TelemetryType::Metrics,
+            Event::Trace(_) => TelemetryType::Traces,
+        }
+    }
+}
+
+pub(super) struct OpentelemetrySink {
+    batch_settings: BatcherSettings,
+    request_builder: OpentelemetryRequestBuilder,
+    service: Svc<OpentelemetryService, OpentelemetryRetryLogic>,
+    protocol: &'static str,
+}
+
+impl OpentelemetrySink {
+    pub(super) const fn new(
+        batch_settings: BatcherSettings,
+        request_builder: OpentelemetryRequestBuilder,
+        service: Svc<OpentelemetryService, OpentelemetryRetryLogic>,
+        protocol: &'static str,
+    ) -> Self {
+        Self {
+            batch_settings,
+            request_builder,
+            service,
+            protocol,
+        }
+    }
+
+    async fn run_inner(self: Box<Self>, input: BoxStream<'_, Event>) -> Result<(), ()> {
+        let builder_limit = NonZeroUsize::new(64);
+        input
+            .batched_partitioned(TelemetryTypePartitioner, self.batch_settings)
+            .request_builder(builder_limit, self.request_builder)
+            .filter_map(|request| async move {
+                match request {
+                    Err(error) => {
+                        emit!(SinkRequestBuildError { error });
+                        None
+                    }
+                    Ok(req) => Some(req),
+                }
+            })
+            .into_driver(self.service)
+            .protocol(self.protocol)
+            .run()
+            .await
+    }
+}
+
+#[async_trait]
+impl StreamSink<Event> for OpentelemetrySink {
+    async fn run(self: Box<Self>, input: BoxStream<'_, Event>) -> Result<(), ()> {
+        self.run_inner(input).await
+    }
+}
@@ -0,0 +1,186 @@
+use std::io::Read;
+
+use bytes::Bytes;
+use flate2::read::MultiGzDecoder;
+use futures::{stream, StreamExt};
+use opentelemetry_proto::proto::{
+    collector::{
+        logs::v1::{
+            logs_service_server::{LogsService, LogsServiceServer},
+            ExportLogsServiceRequest, ExportLogsServiceResponse,
+        },
+        metrics::v1::ExportMetricsServiceRequest,
+    },
+    common::v1::any_value,
+    metrics::v1::metric::Data,
+};
+use prost::Message;
+use tokio::sync::mpsc;
+use tonic::{codec::CompressionEncoding, Request, Response, Status};
+use vector_core::event::{Metric, MetricKind, MetricValue};
+
+use super::OpentelemetrySinkConfig;
+use crate::{
+    config::{SinkConfig, SinkContext},
+    event::{Event, LogEvent},
+    sinks::util::test::build_test_server,
+    test_util::{
+        components::{run_and_assert_sink_compliance, SINK_TAGS},
+        next_addr,
+    },
+};
+
+fn log_event(message: &str) -> Event {
+    LogEvent::from(message).into()
+}
+
+fn counter_event(name: &str, value: f64) -> Event {
+    Metric::new(
+        name,
+        MetricKind::Incremental,
+        MetricValue::Counter { value },
+    )
+    .into()
+}
+
+fn log_bodies(request: &ExportLogsServiceRequest) -> Vec<String> {
+    request
+        .resource_logs
+        .iter()
+        .flat_map(|resource| &resource.scope_logs)
+        .flat_map(|scope| &scope.log_records)
+        .filter_map(|record| match record.body.as_ref()?.value.as_ref()? {
+            any_value::Value::StringValue(body) => Some(body.clone()),
+            _ => None,
+        })
+        .collect()
+}
+
+#[tokio::test]
+async fn http_exports_logs_and_metrics_to_separate_paths() {
+    let addr = next_addr();
+    let (rx, trigger, server) = build_test_server(addr);
+    tokio::spawn(server);
+
+    let config: OpentelemetrySinkConfig = toml::from_str(&format!(
+        r#"
+        endpoint = "http://{}/otlp/"
+        protocol = "http"
+        "#,
+        addr
+    ))
+    .unwrap();
+    let (sink, _) = config.build(SinkContext::default()).await.unwrap();
+
+    let events = vec![
+        log_event("first"),
+        counter_event("requests", 3.0),
+        log_event("second"),
+    ];
+    run_and_assert_sink_compliance(sink, stream::iter(events), &SINK_TAGS).await;
+    drop(trigger);
+
+    let mut requests = rx.collect::<Vec<_>>().await;
+    requests.sort_by(|(a, _), (b, _)| a.uri.path().cmp(b.uri.path()));
+    assert_eq!(requests.len(), 2);
+
+    let (parts, body) = &requests[0];
+    assert_eq!(parts.method, "POST");
+    assert_eq!(parts.uri.path(), "/otlp/v1/logs");
+    assert_eq!(parts.headers["content-type"], "application/x-protobuf");
+    let logs = ExportLogsServiceRequest::decode(body.clone()).unwrap();
+    assert_eq!(log_bodies(&logs), vec!["first", "second"]);
+
+    let (parts, body) = &requests[1];
+    assert_eq!(parts.uri.path(), "/otlp/v1/metrics");
+    let metrics = ExportMetricsServiceRequest::decode(body.clone()).unwrap();
+    let metric = &metrics.resource_metrics[0].scope_metrics[0].metrics[0];
+    assert_eq!(metric.name, "requests");
+    match metric.data.as_ref().unwrap() {
+        Data::Sum(sum) => {
+            assert!(sum.is_monotonic);
+            assert_eq!(sum.data_points.len(), 1);
+        }
+        data => panic!("unexpected metric data: {:?}", data),
+    }
+}
+
+#[tokio::test]
+async fn http_compresses_payloads() {
+    let addr = next_addr();
+    let (rx, trigger, server) = build_test_server(addr);
+    tokio::spawn(server);
+
+    let config: OpentelemetrySinkConfig = toml::from_str(&format!(
+        r#"
+        endpoint = "http://{}"
+        protocol = "http"
+        compression = "gzip"
+        "#,
+        addr
+    ))
+    .unwrap();
+    let (sink, _) = config.build(SinkContext::default()).await.unwrap();
+
+    run_and_assert_sink_compliance(sink, stream::iter(vec![log_event("zipped")]), &SINK_TAGS).await;
+    drop(trigger);
+
+    let requests = rx.collect::<Vec<_>>().await;
+    assert_eq!(requests.len(), 1);
+    let (parts, body) = &requests[0];
+    assert_eq!(parts.uri.path(), "/v1/logs");
+    assert_eq!(parts.headers["content-encoding"], "gzip");
+
+    let mut decoded = Vec::new();
+    MultiGzDecoder::new(body.as_ref())
+        .read_to_end(&mut decoded)
+        .unwrap();
+    let logs = ExportLogsServiceRequest::decode(Bytes::from(decoded)).unwrap();
+    assert_eq!(log_bodies(&logs), vec!["zipped"]);
+}
+
+struct CapturingLogsService {
+    tx: mpsc::UnboundedSender<ExportLogsServiceRequest>,
+}
+
+#[tonic::async_trait]
+impl LogsService for CapturingLogsService {
+    async fn export(
+        &self,
+        request: Request<ExportLogsServiceRequest>,
+    ) -> Result<Response<ExportLogsServiceResponse>, Status> {
+        _ = self.tx.send(request.into_inner());
+        Ok(Response::new(ExportLogsServiceResponse {}))
+    }
+}
+
+#[tokio::test]
+async fn grpc_exports_logs() {
+    let addr = next_addr();
+    let (tx, mut rx) = mpsc::unbounded_channel();
+    tokio::spawn(
+        tonic::transport::Server::builder()
+            .add_service(
+                LogsServiceServer::new(CapturingLogsService { tx })
+                    .accept_compressed(CompressionEncoding::Gzip),
+            )
+            .serve(addr),
+    );
+    crate::test_util::wait_for_tcp(addr).await;
+
+    let config: OpentelemetrySinkConfig = toml::from_str(&format!(
+        r#"
+        endpoint = "http://{}"
+        compression = "gzip"
+        "#,
+        addr
+    ))
+    .unwrap();
+    let (sink, _) = config.build(SinkContext::default()).await.unwrap();
+
+    let events = vec![log_event("one"), log_event("two")];
+    run_and_assert_sink_compliance(sink, stream::iter(events), &SINK_TAGS).await;
+
+    let request = rx.recv().await.unwrap();
+    assert_eq!(log_bodies(&request), vec!["one", "two"]);
+}
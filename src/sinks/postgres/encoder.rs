@@ -0,0 +1,205 @@
+use std::io;
+
+use lookup::{OwnedValuePath, PathPrefix};
+use vector_core::config::telemetry;
+
+use super::config::PostgresInsertMode;
+use crate::sinks::{prelude::*, util::encoding::Encoder as SinkEncoder};
+
+/// The columns events are inserted into.
+#[derive(Clone, Debug)]
+pub(super) struct PostgresColumns {
+    /// The columns filled from an event field.
+    pub(super) mapped: Vec<(String, OwnedValuePath)>,
+    /// The column filled with the fields that are not mapped.
+    pub(super) fallback: Option<String>,
+}
+
+impl PostgresColumns {
+    pub(super) fn names(&self) -> impl Iterator<Item = &str> {
+        self.mapped
+            .iter()
+            .map(|(column, _)| column.as_str())
+            .chain(self.fallback.as_deref())
+    }
+
+    /// Takes the values of each column out of the event, in the order of `names`.
+    fn row(&self, log: &mut LogEvent) -> Vec<Value> {
+        let mut row = self
+            .mapped
+            .iter()
+            .map(|(_, field)| {
+                log.remove((PathPrefix::Event, field))
+                    .unwrap_or(Value::Null)
+            })
+            .collect::<Vec<_>>();
+        if self.fallback.is_some() {
+            row.push(std::mem::replace(log.value_mut(), Value::Null));
+        }
+        row
+    }
+}
+
+/// Encodes batches of events into the payload of a `COPY` or `INSERT` statement.
+///
+/// For `COPY`, the payload is the CSV data streamed to the server. For `INSERT`, it is a JSON
+/// array of rows, which the server expands with `json_populate_recordset`.
+#[derive(Clone)]
+pub(super) struct PostgresEncoder {
+    transformer: Transformer,
+    columns: PostgresColumns,
+    mode: PostgresInsertMode,
+}
+
+impl PostgresEncoder {
+    pub(super) const fn new(
+        transformer: Transformer,
+        columns: PostgresColumns,
+        mode: PostgresInsertMode,
+    ) -> Self {
+        Self {
+            transformer,
+            columns,
+            mode,
+        }
+    }
+
+    fn write_csv_row(&self, row: Vec<Value>, writer: &mut dyn io::Write) -> io::Result<usize> {
+        let mut line = String::new();
+        for (i, value) in row.into_iter().enumerate() {
+            if i > 0 {
+                line.push(',');
+            }
+            // An unquoted empty value is `NULL`, while a quoted one is an empty string.
+            let text = match to_json(value)? {
+                serde_json::Value::Null => continue,
+                serde_json::Value::String(text) => text,
+                json => json.to_string(),
+            };
+            line.push('"');
+            line.push_str(&text.replace('"', "\"\""));
+            line.push('"');
+        }
+        line.push('\n');
+
+        writer.write_all(line.as_bytes())?;
+        Ok(line.len())
+    }
+
+    fn json_row(&self, row: Vec<Value>) -> io::Result<serde_json::Value> {
+        self.columns
+            .names()
+            .zip(row)
+            .map(|(column, value)| Ok((column.to_owned(), to_json(value)?)))
+            .collect::<io::Result<serde_json::Map<_, _>>>()
+            .map(serde_json::Value::Object)
+    }
+}
+
+fn to_json(value: Value) -> io::Result<serde_json::Value> {
+    serde_json::to_value(value).map_err(|error| io::Error::new(io::ErrorKind::InvalidData, error))
+}
+
+impl SinkEncoder<Vec<Event>> for PostgresEncoder {
+    fn encode_input(
+        &self,
+        events: Vec<Event>,
+        writer: &mut dyn io::Write,
+    ) -> io::Result<(usize, GroupedCountByteSize)> {
+        let mut byte_size = telemetry().create_request_count_byte_size();
+        let mut bytes_written = 0;
+        let mut json_rows = Vec::new();
+
+        for mut event in events {
+            self.transformer.transform(&mut event);
+            byte_size.add_event(&event, event.estimated_json_encoded_size_of());
+
+            let row = self.columns.row(event.as_mut_log());
+            match self.mode {
+                PostgresInsertMode::Copy => bytes_written += self.write_csv_row(row, writer)?,
+                PostgresInsertMode::Insert => json_rows.push(self.json_row(row)?),
+            }
+        }
+
+        if self.mode == PostgresInsertMode::Insert {
+            let json = serde_json::to_vec(&json_rows)
+                .map_err(|error| io::Error::new(io::ErrorKind::InvalidData, error))?;
+            writer.write_all(&json)?;
+            bytes_written += json.len();
+        }
+
+        Ok((bytes_written, byte_size))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::{TimeZone, Utc};
+    use lookup::owned_value_path;
+
+    use super::*;
+
+    fn columns(fallback: Option<&str>) -> PostgresColumns {
+        PostgresColumns {
+            mapped: vec![
+                ("message".to_owned(), owned_value_path!("message")),
+                ("timestamp".to_owned(), owned_value_path!("timestamp")),
+                ("user".to_owned(), owned_value_path!("user", "name")),
+            ],
+            fallback: fallback.map(str::to_owned),
+        }
+    }
+
+    fn event() -> Event {
+        let mut log = LogEvent::from("say \"hello\"");
+        log.insert(
+            "timestamp",
+            Utc.with_ymd_and_hms(2023, 1, 1, 0, 0, 0).unwrap(),
+        );
+        log.insert("level", "info");
+        log.insert("count", 3_i64);
+        Event::Log(log)
+    }
+
+    fn encode(encoder: &PostgresEncoder, events: Vec<Event>) -> String {
+        let mut payload = Vec::new();
+        encoder.encode_input(events, &mut payload).unwrap();
+        String::from_utf8(payload).unwrap()
+    }
+
+    #[test]
+    fn encode_copy() {
+        let encoder = PostgresEncoder::new(
+            Transformer::default(),
+            columns(Some("data")),
+            PostgresInsertMode::Copy,
+        );
+
+        assert_eq!(
+            encode(&encoder, vec![event()]),
+            concat!(
+                r#""say ""hello""","2023-01-01T00:00:00Z",,"{""count"":3,""level"":""info""}""#,
+                "\n"
+            )
+        );
+    }
+
+    #[test]
+    fn encode_insert() {
+        let encoder = PostgresEncoder::new(
+            Transformer::default(),
+            columns(None),
+            PostgresInsertMode::Insert,
+        );
+
+        let payload: serde_json::Value =
+            serde_json::from_str(&encode(&encoder, vec![event(), event()])).unwrap();
+        assert_eq!(
+            payload,
+            serde_json::json!([
+                {"message": "say \"hello\"", "timestamp": "2023-01-01T00:00:00Z", "user": null},
+                {"message": "say \"hello\"", "timestamp": "2023-01-01T00:00:00Z", "user": null},
+            ])
+        );
+    }
+}
@@ -0,0 +1,105 @@
+use futures::stream;
+use tokio_postgres::{Client, NoTls};
+use vector_core::event::{BatchNotifier, BatchStatus, Event, LogEvent};
+
+use super::*;
+use crate::{
+    config::{SinkConfig, SinkContext},
+    test_util::{
+        components::{run_and_assert_sink_compliance, SINK_TAGS},
+        random_string, trace_init,
+    },
+};
+
+fn pg_host() -> String {
+    std::env::var("PG_HOST").unwrap_or_else(|_| "localhost".into())
+}
+
+fn pg_url() -> String {
+    std::env::var("PG_URL")
+        .unwrap_or_else(|_| format!("postgres://vector:vector@{}/postgres", pg_host()))
+}
+
+async fn connect() -> Client {
+    let (client, connection) = tokio_postgres::connect(&pg_url(), NoTls).await.unwrap();
+    tokio::spawn(connection);
+    client
+}
+
+async fn create_table(client: &Client) -> String {
+    let table = format!("test_{}", random_string(10).to_lowercase());
+    client
+        .execute(
+            &format!(
+                "CREATE TABLE {} (message text, timestamp timestamptz, data jsonb)",
+                table
+            ),
+            &[],
+        )
+        .await
+        .unwrap();
+    table
+}
+
+async fn insert_events(mode: &str) {
+    trace_init();
+
+    let client = connect().await;
+    let table = create_table(&client).await;
+
+    let config: PostgresConfig = toml::from_str(&format!(
+        r#"
+        endpoint = "{}"
+        table = "{}"
+        mode = "{}"
+        fallback_column = "data"
+        columns.message = "message"
+        columns.timestamp = "timestamp"
+        "#,
+        pg_url(),
+        table,
+        mode
+    ))
+    .unwrap();
+    let (sink, healthcheck) = config.build(SinkContext::default()).await.unwrap();
+    healthcheck.await.unwrap();
+
+    let (batch, mut receiver) = BatchNotifier::new_with_receiver();
+    let events = (0..10_i64)
+        .map(|i| {
+            let mut log = LogEvent::from(format!("line {}", i)).with_batch_notifier(&batch);
+            log.insert("index", i);
+            Event::Log(log)
+        })
+        .collect::<Vec<_>>();
+    drop(batch);
+
+    run_and_assert_sink_compliance(sink, stream::iter(events), &SINK_TAGS).await;
+    assert_eq!(receiver.try_recv(), Ok(BatchStatus::Delivered));
+
+    let rows = client
+        .query(
+            &format!(
+                "SELECT message, data->>'index' FROM {} ORDER BY (data->>'index')::int",
+                table
+            ),
+            &[],
+        )
+        .await
+        .unwrap();
+    assert_eq!(rows.len(), 10);
+    for (i, row) in rows.iter().enumerate() {
+        assert_eq!(row.get::<_, String>(0), format!("line {}", i));
+        assert_eq!(row.get::<_, String>(1), i.to_string());
+    }
+}
+
+#[tokio::test]
+async fn insert_events_with_copy() {
+    insert_events("copy").await;
+}
+
+#[tokio::test]
+async fn insert_events_with_insert() {
+    insert_events("insert").await;
+}
@@ -0,0 +1,18 @@
+//! The PostgreSQL [`vector_core::sink::VectorSink`]
+//!
+//! This module contains the [`vector_core::sink::VectorSink`] instance that is responsible for
+//! taking a stream of [`vector_core::event::Event`] instances and inserting them into a PostgreSQL
+//! table.
+//!
+//! Event fields are mapped to columns, with an optional JSON column holding the fields that are not
+//! mapped. Each batch is inserted, either with a `COPY ... FROM STDIN` or with a multi-row `INSERT`,
+//! in its own transaction, and its events are acknowledged once that transaction is committed.
+
+mod config;
+mod encoder;
+#[cfg(all(test, feature = "postgres-integration-tests"))]
+mod integration_tests;
+mod service;
+mod sink;
+
+pub use self::config::PostgresConfig;
@@ -0,0 +1,297 @@
+use std::{
+    sync::{Arc, Mutex},
+    task::{Context, Poll},
+};
+
+use bytes::Bytes;
+use futures::SinkExt;
+use postgres_openssl::MakeTlsConnector;
+use snafu::{ResultExt, Snafu};
+use tokio_postgres::{Client, Config, Error as PgError, NoTls};
+
+use super::{config::PostgresInsertMode, encoder::PostgresColumns};
+use crate::sinks::prelude::*;
+
+/// The classes of SQLSTATE codes of the errors that may succeed when retried.
+///
+/// <https://www.postgresql.org/docs/current/errcodes-appendix.html>
+const RETRIABLE_ERROR_CLASSES: [&str; 4] = [
+    "08", // Connection exception
+    "40", // Transaction rollback, such as serialization failures and deadlocks
+    "53", // Insufficient resources
+    "57", // Operator intervention, such as server shutdowns
+];
+
+#[derive(Debug, Snafu)]
+pub(super) enum PostgresError {
+    #[snafu(display("failed to connect: {}", source))]
+    Connect { source: PgError },
+    #[snafu(display("failed to insert events: {}", source))]
+    Insert { source: PgError },
+}
+
+#[derive(Clone, Debug)]
+pub(super) struct PostgresRetryLogic;
+
+impl RetryLogic for PostgresRetryLogic {
+    type Error = PostgresError;
+    type Response = PostgresResponse;
+
+    fn is_retriable_error(&self, error: &Self::Error) -> bool {
+        match error {
+            PostgresError::Connect { .. } => true,
+            PostgresError::Insert { source } => match source.code() {
+                Some(code) => RETRIABLE_ERROR_CLASSES
+                    .iter()
+                    .any(|class| code.code().starts_with(class)),
+                // Errors without a code are client side ones, such as I/O errors.
+                None => true,
+            },
+        }
+    }
+}
+
+/// Opens connections to the server, and keeps the idle ones around for the next requests.
+#[derive(Clone)]
+pub(super) struct PostgresConnector {
+    config: Config,
+    tls: Option<MakeTlsConnector>,
+    idle: Arc<Mutex<Vec<Client>>>,
+}
+
+impl PostgresConnector {
+    pub(super) fn new(config: Config, tls: Option<MakeTlsConnector>) -> Self {
+        Self {
+            config,
+            tls,
+            idle: Arc::default(),
+        }
+    }
+
+    async fn connect(&self) -> Result<Client, PgError> {
+        let client = match &self.tls {
+            Some(tls) => {
+                let (client, connection) = self.config.connect(tls.clone()).await?;
+                tokio::spawn(connection);
+                client
+            }
+            None => {
+                let (client, connection) = self.config.connect(NoTls).await?;
+                tokio::spawn(connection);
+                client
+            }
+        };
+        Ok(client)
+    }
+
+    /// Takes an idle connection, or opens a new one if there is none.
+    async fn take(&self) -> Result<Client, PgError> {
+        loop {
+            let client = self.idle.lock().expect("idle lock poisoned").pop();
+            match client {
+                Some(client) if client.is_closed() => continue,
+                Some(client) => return Ok(client),
+                None => return self.connect().await,
+            }
+        }
+    }
+
+    fn put(&self, client: Client) {
+        self.idle.lock().expect("idle lock poisoned").push(client);
+    }
+
+    pub(super) async fn healthcheck(&self) -> crate::Result<()> {
+        let client = self.take().await?;
+        client.simple_query("SELECT 1").await?;
+        self.put(client);
+        Ok(())
+    }
+}
+
+#[derive(Clone)]
+pub(super) struct PostgresRequest {
+    pub(super) table: String,
+    pub(super) payload: Bytes,
+    pub(super) finalizers: EventFinalizers,
+    pub(super) metadata: RequestMetadata,
+}
+
+impl Finalizable for PostgresRequest {
+    fn take_finalizers(&mut self) -> EventFinalizers {
+        self.finalizers.take_finalizers()
+    }
+}
+
+impl MetaDescriptive for PostgresRequest {
+    fn get_metadata(&self) -> &RequestMetadata {
+        &self.metadata
+    }
+
+    fn metadata_mut(&mut self) -> &mut RequestMetadata {
+        &mut self.metadata
+    }
+}
+
+pub(super) struct PostgresResponse {
+    metadata: RequestMetadata,
+}
+
+impl DriverResponse for PostgresResponse {
+    fn event_status(&self) -> EventStatus {
+        EventStatus::Delivered
+    }
+
+    fn events_sent(&self) -> &GroupedCountByteSize {
+        self.metadata.events_estimated_json_encoded_byte_size()
+    }
+
+    fn bytes_sent(&self) -> Option<usize> {
+        Some(self.metadata.request_encoded_size())
+    }
+}
+
+/// `PostgresService` is a `Tower` service inserting each request in its own transaction, so that
+/// events are only acknowledged once committed.
+#[derive(Clone)]
+pub(super) struct PostgresService {
+    connector: PostgresConnector,
+    columns: String,
+    mode: PostgresInsertMode,
+}
+
+impl PostgresService {
+    pub(super) fn new(
+        connector: PostgresConnector,
+        columns: &PostgresColumns,
+        mode: PostgresInsertMode,
+    ) -> Self {
+        let columns = columns
+            .names()
+            .map(quote_identifier)
+            .collect::<Vec<_>>()
+            .join(", ");
+        Self {
+            connector,
+            columns,
+            mode,
+        }
+    }
+
+    fn statement(&self, table: &str) -> String {
+        let table = quote_table(table);
+        match self.mode {
+            PostgresInsertMode::Copy => format!(
+                "COPY {} ({}) FROM STDIN WITH (FORMAT csv)",
+                table, self.columns
+            ),
+            PostgresInsertMode::Insert => format!(
+                "INSERT INTO {table} ({columns}) SELECT {columns} FROM json_populate_recordset(NULL::{table}, $1::text::json)",
+                table = table,
+                columns = self.columns
+            ),
+        }
+    }
+
+    async fn insert(
+        &self,
+        client: &mut Client,
+        table: &str,
+        payload: Bytes,
+    ) -> Result<(), PgError> {
+        let statement = self.statement(table);
+        let transaction = client.transaction().await?;
+
+        match self.mode {
+            PostgresInsertMode::Copy => {
+                let sink = transaction.copy_in(&statement).await?;
+                futures::pin_mut!(sink);
+                sink.send(payload).await?;
+                sink.finish().await?;
+            }
+            PostgresInsertMode::Insert => {
+                let rows = String::from_utf8_lossy(&payload);
+                transaction.execute(&statement, &[&&*rows]).await?;
+            }
+        }
+
+        transaction.commit().await
+    }
+}
+
+/// Quotes an identifier, so that it's used as is.
+fn quote_identifier(name: &str) -> String {
+    format!("\"{}\"", name.replace('"', "\"\""))
+}
+
+/// Quotes a table name, which may be qualified by its schema.
+fn quote_table(table: &str) -> String {
+    table
+        .split('.')
+        .map(quote_identifier)
+        .collect::<Vec<_>>()
+        .join(".")
+}
+
+impl Service<PostgresRequest> for PostgresService {
+    type Response = PostgresResponse;
+    type Error = PostgresError;
+    type Future = BoxFuture<'static, Result<Self::Response, Self::Error>>;
+
+    fn poll_ready(&mut self, _cx: &mut Context) -> Poll<Result<(), Self::Error>> {
+        Poll::Ready(Ok(()))
+    }
+
+    fn call(&mut self, request: PostgresRequest) -> Self::Future {
+        let service = self.clone();
+
+        Box::pin(async move {
+            let mut client = service.connector.take().await.context(ConnectSnafu)?;
+            service
+                .insert(&mut client, &request.table, request.payload)
+                .await
+                .context(InsertSnafu)?;
+            // Connections are only reused after a successful request, as a failed one may have
+            // left them in an unknown state.
+            service.connector.put(client);
+
+            Ok(PostgresResponse {
+                metadata: request.metadata,
+            })
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use lookup::owned_value_path;
+
+    use super::*;
+
+    fn service(mode: PostgresInsertMode) -> PostgresService {
+        let columns = PostgresColumns {
+            mapped: vec![("message".to_owned(), owned_value_path!("message"))],
+            fallback: Some("data".to_owned()),
+        };
+        let config = "postgresql://vector@localhost/postgres".parse().unwrap();
+        PostgresService::new(PostgresConnector::new(config, None), &columns, mode)
+    }
+
+    #[test]
+    fn copy_statement() {
+        assert_eq!(
+            service(PostgresInsertMode::Copy).statement("audit.logs"),
+            r#"COPY "audit"."logs" ("message", "data") FROM STDIN WITH (FORMAT csv)"#
+        );
+    }
+
+    #[test]
+    fn insert_statement() {
+        assert_eq!(
+            service(PostgresInsertMode::Insert).statement(r#"my "logs""#),
+            concat!(
+                r#"INSERT INTO "my ""logs""" ("message", "data") SELECT "message", "data" "#,
+                r#"FROM json_populate_recordset(NULL::"my ""logs""", $1::text::json)"#
+            )
+        );
+    }
+}
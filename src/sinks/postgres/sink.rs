@@ -0,0 +1,130 @@
+use bytes::Bytes;
+
+use super::{
+    encoder::PostgresEncoder,
+    service::{PostgresRequest, PostgresRetryLogic, PostgresService},
+};
+use crate::sinks::prelude::*;
+
+pub(super) struct PostgresSink {
+    batch_settings: BatcherSettings,
+    encoder: PostgresEncoder,
+    service: Svc<PostgresService, PostgresRetryLogic>,
+    table: Template,
+}
+
+impl PostgresSink {
+    pub(super) const fn new(
+        batch_settings: BatcherSettings,
+        encoder: PostgresEncoder,
+        service: Svc<PostgresService, PostgresRetryLogic>,
+        table: Template,
+    ) -> Self {
+        Self {
+            batch_settings,
+            encoder,
+            service,
+            table,
+        }
+    }
+
+    async fn run_inner(self: Box<Self>, input: BoxStream<'_, Event>) -> Result<(), ()> {
+        input
+            .batched_partitioned(TablePartitioner(self.table), self.batch_settings)
+            .filter_map(|(table, batch)| async move { table.map(move |table| (table, batch)) })
+            .request_builder(
+                None,
+                PostgresRequestBuilder {
+                    encoder: self.encoder,
+                },
+            )
+            .filter_map(|request| async {
+                match request {
+                    Err(error) => {
+                        emit!(SinkRequestBuildError { error });
+                        None
+                    }
+                    Ok(req) => Some(req),
+                }
+            })
+            .into_driver(self.service)
+            .protocol("postgres")
+            .run()
+            .await
+    }
+}
+
+#[async_trait::async_trait]
+impl StreamSink<Event> for PostgresSink {
+    async fn run(self: Box<Self>, input: BoxStream<'_, Event>) -> Result<(), ()> {
+        self.run_inner(input).await
+    }
+}
+
+struct PostgresRequestBuilder {
+    encoder: PostgresEncoder,
+}
+
+impl RequestBuilder<(String, Vec<Event>)> for PostgresRequestBuilder {
+    type Metadata = (String, EventFinalizers);
+    type Events = Vec<Event>;
+    type Encoder = PostgresEncoder;
+    type Payload = Bytes;
+    type Request = PostgresRequest;
+    type Error = std::io::Error;
+
+    fn compression(&self) -> Compression {
+        Compression::None
+    }
+
+    fn encoder(&self) -> &Self::Encoder {
+        &self.encoder
+    }
+
+    fn split_input(
+        &self,
+        input: (String, Vec<Event>),
+    ) -> (Self::Metadata, RequestMetadataBuilder, Self::Events) {
+        let (table, mut events) = input;
+
+        let finalizers = events.take_finalizers();
+        let builder = RequestMetadataBuilder::from_events(&events);
+        ((table, finalizers), builder, events)
+    }
+
+    fn build_request(
+        &self,
+        metadata: Self::Metadata,
+        request_metadata: RequestMetadata,
+        payload: EncodeResult<Self::Payload>,
+    ) -> Self::Request {
+        let (table, finalizers) = metadata;
+        PostgresRequest {
+            table,
+            payload: payload.into_payload(),
+            finalizers,
+            metadata: request_metadata,
+        }
+    }
+}
+
+/// Partitions events by their rendered table.
+struct TablePartitioner(Template);
+
+impl Partitioner for TablePartitioner {
+    type Item = Event;
+    type Key = Option<String>;
+
+    fn partition(&self, item: &Self::Item) -> Self::Key {
+        self.0
+            .render_string(item)
+            .map_err(|error| {
+                emit!(TemplateRenderingError {
+                    error,
+                    field: Some("table"),
+                    drop_event: true,
+                });
+            })
+            .ok()
+    }
+}
@@ -1,8 +1,11 @@
-use std::task::{Context, Poll};
+use std::{
+    num::NonZeroUsize,
+    task::{Context, Poll},
+};
 
 use bytes::{Bytes, BytesMut};
 use futures::{future::BoxFuture, stream, FutureExt, SinkExt, StreamExt};
-use redis::{aio::ConnectionManager, RedisError, RedisResult};
+use redis::{aio::ConnectionManager, streams::StreamMaxlen, RedisError, RedisResult};
 use snafu::{ResultExt, Snafu};
 use tokio_util::codec::Encoder as _;
 use tower::{Service, ServiceBuilder};
@@ -53,6 +56,14 @@ pub enum DataTypeConfig {
     ///
     /// Redis channels function in a pub/sub fashion, allowing many-to-many broadcasting and receiving.
     Channel,
+
+    /// The Redis `stream` type.
+    ///
+    /// Messages are appended to a [stream][redis_streams] as entries, which can be read by
+    /// consumer groups.
+    ///
+    /// [redis_streams]: https://redis.io/docs/data-types/streams/
+    Stream,
 }
 
 /// List-specific options.
@@ -64,7 +75,38 @@ pub struct ListOption {
     method: Method,
 }
 
-#[derive(Clone, Copy, Debug, Derivative)]
+/// Stream-specific options.
+#[configurable_component]
+#[derive(Clone, Debug, Derivative)]
+#[derivative(Default)]
+#[serde(deny_unknown_fields)]
+pub struct StreamOption {
+    /// The field of the stream entries to store the message in.
+    #[serde(default = "default_field")]
+    #[derivative(Default(value = "default_field()"))]
+    #[configurable(metadata(docs::examples = "message"))]
+    field: String,
+
+    /// The maximum number of entries to keep in the stream.
+    ///
+    /// Older entries are trimmed as new ones are added. If not set, the stream isn't trimmed.
+    #[configurable(metadata(docs::type_unit = "entries"))]
+    max_len: Option<NonZeroUsize>,
+
+    /// Whether or not to trim the stream approximately.
+    ///
+    /// Approximate trimming only removes whole nodes of the stream, which is much more efficient,
+    /// but may leave slightly more entries than `max_len` in the stream.
+    #[serde(default = "crate::serde::default_true")]
+    #[derivative(Default(value = "true"))]
+    approximate: bool,
+}
+
+fn default_field() -> String {
+    String::from("message")
+}
+
+#[derive(Clone, Debug, Derivative)]
 #[derivative(Default)]
 pub enum DataType {
     /// The Redis `list` type.
@@ -77,6 +119,14 @@ pub enum DataType {
     ///
     /// Redis channels function in a pub/sub fashion, allowing many-to-many broadcasting and receiving.
     Channel,
+
+    /// The Redis `stream` type.
+    ///
+    /// Messages are appended to the stream as entries, optionally trimming it.
+    Stream {
+        field: String,
+        max_len: Option<StreamMaxlen>,
+    },
 }
 
 /// Method for pushing messages into a `list`.
@@ -124,6 +174,10 @@ pub struct RedisSinkConfig {
     #[serde(alias = "list")]
     list_option: Option<ListOption>,
 
+    #[configurable(derived)]
+    #[serde(alias = "stream")]
+    stream_option: Option<StreamOption>,
+
     /// The URL of the Redis endpoint to connect to.
     ///
     /// The URL _must_ take the form of `protocol://server:port/db` where the protocol can either be
@@ -213,6 +267,19 @@ impl RedisSinkConfig {
         let data_type = match self.data_type {
             DataTypeConfig::Channel => DataType::Channel,
             DataTypeConfig::List => DataType::List(method.unwrap_or_default()),
+            DataTypeConfig::Stream => {
+                let option = self.stream_option.clone().unwrap_or_default();
+                DataType::Stream {
+                    field: option.field,
+                    max_len: option.max_len.map(|max_len| {
+                        if option.approximate {
+                            StreamMaxlen::Approx(max_len.get())
+                        } else {
+                            StreamMaxlen::Equals(max_len.get())
+                        }
+                    }),
+                }
+            }
         };
 
         let batch = self.batch.into_batch_settings()?;
@@ -357,7 +424,7 @@ impl Service<Vec<RedisKvEntry>> for RedisSink {
 
         for kv in kvs {
             byte_size += kv.encoded_length();
-            match self.data_type {
+            match &self.data_type {
                 DataType::List(method) => match method {
                     Method::LPush => {
                         if count > 1 {
@@ -381,6 +448,19 @@ impl Service<Vec<RedisKvEntry>> for RedisSink {
                         pipe.publish(kv.key, kv.value.as_ref());
                     }
                 }
+                DataType::Stream { field, max_len } => {
+                    if count > 1 {
+                        pipe.atomic();
+                    }
+                    let items = [(field.as_str(), kv.value.as_ref())];
+                    match max_len {
+                        Some(max_len) => pipe.xadd_maxlen(kv.key, *max_len, "*", &items),
+                        None => pipe.xadd(kv.key, "*", &items),
+                    };
+                    // `XADD` replies with the ID of the added entry rather than a count, and only
+                    // fails with an error, so there is nothing to check in its reply.
+                    pipe.ignore();
+                }
             }
         }
 
@@ -414,6 +494,25 @@ mod tests {
         crate::test_util::test_generate_config::<RedisSinkConfig>();
     }
 
+    #[test]
+    fn parses_stream_config() {
+        let config: RedisSinkConfig = toml::from_str(
+            r#"
+            url = "redis://127.0.0.1:6379/0"
+            key = "vector"
+            data_type = "stream"
+            stream.max_len = 1000
+            encoding.codec = "json"
+            "#,
+        )
+        .unwrap();
+
+        let option = config.stream_option.unwrap();
+        assert_eq!(option.field, "message");
+        assert_eq!(option.max_len, NonZeroUsize::new(1000));
+        assert!(option.approximate);
+    }
+
     #[test]
     fn redis_event_json() {
         let msg = "hello_world".to_owned();
@@ -475,7 +574,7 @@ mod integration_tests {
     use codecs::JsonSerializerConfig;
     use futures::stream;
     use rand::Rng;
-    use redis::AsyncCommands;
+    use redis::{streams::StreamRangeReply, AsyncCommands};
     use vector_core::event::LogEvent;
 
     use super::*;
@@ -507,6 +606,7 @@ mod integration_tests {
             list_option: Some(ListOption {
                 method: Method::LPush,
             }),
+            stream_option: None,
             batch: BatchConfig::default(),
             request: TowerRequestConfig {
                 rate_limit_num: Some(u64::MAX),
@@ -570,6 +670,7 @@ mod integration_tests {
             list_option: Some(ListOption {
                 method: Method::RPush,
             }),
+            stream_option: None,
             batch: BatchConfig::default(),
             request: TowerRequestConfig {
                 rate_limit_num: Some(u64::MAX),
@@ -647,6 +748,7 @@ mod integration_tests {
             encoding: JsonSerializerConfig::default().into(),
             data_type: DataTypeConfig::Channel,
             list_option: None,
+            stream_option: None,
             batch: BatchConfig::default(),
             request: TowerRequestConfig {
                 rate_limit_num: Some(u64::MAX),
@@ -677,4 +779,62 @@ mod integration_tests {
             }
         }
     }
+
+    #[tokio::test]
+    async fn redis_sink_stream_xadd() {
+        trace_init();
+
+        let key = Template::try_from(format!("test-{}", random_string(10)))
+            .expect("should not fail to create key template");
+        debug!("Test key name: {}.", key);
+        let num_events = 100;
+        let max_len = 10;
+
+        let cnf = RedisSinkConfig {
+            endpoint: redis_server(),
+            key: key.clone(),
+            encoding: JsonSerializerConfig::default().into(),
+            data_type: DataTypeConfig::Stream,
+            list_option: None,
+            stream_option: Some(StreamOption {
+                max_len: NonZeroUsize::new(max_len),
+                approximate: false,
+                ..Default::default()
+            }),
+            batch: BatchConfig::default(),
+            request: TowerRequestConfig {
+                rate_limit_num: Some(u64::MAX),
+                ..Default::default()
+            },
+            acknowledgements: Default::default(),
+        };
+
+        let mut events: Vec<Event> = Vec::new();
+        for i in 0..num_events {
+            let s: String = i.to_string();
+            let e = LogEvent::from(s);
+            events.push(e.into());
+        }
+        let input = stream::iter(events.clone().into_iter().map(Into::into));
+
+        // Publish events.
+        let cnf2 = cnf.clone();
+        assert_sink_compliance(&SINK_TAGS, async move {
+            let conn = cnf2.build_client().await.unwrap();
+            cnf2.new(conn).unwrap().run(input).await
+        })
+        .await
+        .expect("Running sink failed");
+
+        let mut conn = cnf.build_client().await.unwrap();
+
+        // The stream is trimmed to the latest entries.
+        let reply: StreamRangeReply = conn.xrange_all(key.to_string()).await.unwrap();
+        assert_eq!(reply.ids.len(), max_len);
+
+        for (entry, event) in reply.ids.iter().zip(&events[num_events - max_len..]) {
+            let s = serde_json::to_string(event.as_log()).unwrap();
+            assert_eq!(entry.get::<String>("message").unwrap(), s);
+        }
+    }
 }
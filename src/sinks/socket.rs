@@ -164,7 +164,8 @@ mod test {
         net::{SocketAddr, UdpSocket},
     };
 
-    use codecs::JsonSerializerConfig;
+    use bytes::BytesMut;
+    use codecs::{ChunkedGelfDecoder, JsonSerializerConfig};
     use futures::stream::StreamExt;
     use futures_util::stream;
     use serde_json::Value;
@@ -173,7 +174,7 @@ mod test {
         time::{sleep, timeout, Duration},
     };
     use tokio_stream::wrappers::TcpListenerStream;
-    use tokio_util::codec::{FramedRead, LinesCodec};
+    use tokio_util::codec::{Decoder, FramedRead, LinesCodec};
 
     use super::*;
     use crate::{
@@ -238,6 +239,57 @@ mod test {
         test_udp(next_addr_v6()).await;
     }
 
+    #[tokio::test]
+    async fn udp_chunked_gelf() {
+        trace_init();
+
+        let addr = next_addr();
+        let receiver = UdpSocket::bind(addr).unwrap();
+
+        let config: SocketSinkConfig = toml::from_str(&format!(
+            r#"
+                mode = "udp"
+                address = "{}"
+                encoding.codec = "json"
+                chunking.method = "chunked_gelf"
+                chunking.chunked_gelf.max_chunk_size = 64
+            "#,
+            addr
+        ))
+        .unwrap();
+
+        let context = SinkContext::default();
+        assert_sink_compliance(&SINK_TAGS, async move {
+            let (sink, _healthcheck) = config.build(context).await.unwrap();
+
+            let event = Event::Log(LogEvent::from("a".repeat(200)));
+            sink.run(stream::once(ready(event.into()))).await
+        })
+        .await
+        .expect("Running sink failed");
+
+        let mut decoder = ChunkedGelfDecoder::default();
+        let mut datagrams = 0;
+        let packet = loop {
+            let mut buf = [0; 256];
+            let (size, _src_addr) = receiver
+                .recv_from(&mut buf)
+                .expect("Did not receive message");
+            assert!(size <= 64);
+            datagrams += 1;
+
+            let mut src = BytesMut::from(&buf[..size]);
+            if let Some(packet) = decoder.decode_eof(&mut src).unwrap() {
+                break packet;
+            }
+        };
+        assert!(datagrams > 1);
+
+        let data = serde_json::from_slice::<Value>(&packet).expect("Invalid JSON received");
+        let message = data.get("message").expect("No message in JSON");
+        assert_eq!(message, &Value::String("a".repeat(200)));
+    }
+
     #[tokio::test]
     async fn tcp_stream() {
         trace_init();
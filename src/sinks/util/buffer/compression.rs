@@ -37,6 +37,24 @@ pub enum Compression {
     ///
     /// [zstd]: https://facebook.github.io/zstd/
     Zstd(CompressionLevel),
+
+    /// [Snappy][snappy] compression, using the raw block format.
+    ///
+    /// This is the format expected by Prometheus remote write and Loki receivers. The whole payload
+    /// is compressed as a single block, so it is only suitable for payloads that are sent at once.
+    ///
+    /// [snappy]: https://github.com/google/snappy/blob/main/format_description.txt
+    Snappy,
+
+    /// [Snappy][snappy] compression, using the framing format.
+    ///
+    /// [snappy]: https://github.com/google/snappy/blob/main/framing_format.txt
+    SnappyFramed,
+
+    /// [LZ4][lz4] compression, using the frame format.
+    ///
+    /// [lz4]: https://github.com/lz4/lz4/blob/dev/doc/lz4_Frame_format.md
+    Lz4,
 }
 
 impl Compression {
@@ -70,6 +88,9 @@ impl Compression {
             Self::Gzip(_) => Some("gzip"),
             Self::Zlib(_) => Some("deflate"),
             Self::Zstd(_) => Some("zstd"),
+            Self::Snappy => Some("snappy"),
+            Self::SnappyFramed => Some("x-snappy-framed"),
+            Self::Lz4 => Some("lz4"),
         }
     }
 
@@ -78,6 +99,9 @@ impl Compression {
             Self::Gzip(_) => Some("gzip"),
             Self::Zlib(_) => Some("deflate"),
             Self::Zstd(_) => Some("zstd"),
+            Self::Snappy => Some("snappy"),
+            Self::SnappyFramed => Some("x-snappy-framed"),
+            Self::Lz4 => Some("lz4"),
             _ => None,
         }
     }
@@ -88,12 +112,18 @@ impl Compression {
             Self::Gzip(_) => "log.gz",
             Self::Zlib(_) => "log.zz",
             Self::Zstd(_) => "log.zst",
+            Self::Snappy => "log.snappy",
+            Self::SnappyFramed => "log.sz",
+            Self::Lz4 => "log.lz4",
         }
     }
 
     pub const fn max_compression_level_val(self) -> u32 {
         match self {
-            Compression::None => 0,
+            Compression::None
+            | Compression::Snappy
+            | Compression::SnappyFramed
+            | Compression::Lz4 => 0,
             Compression::Gzip(_) => 9,
             Compression::Zlib(_) => 9,
             Compression::Zstd(_) => 21,
@@ -102,7 +132,7 @@ impl Compression {
 
     pub const fn compression_level(self) -> CompressionLevel {
         match self {
-            Self::None => CompressionLevel::None,
+            Self::None | Self::Snappy | Self::SnappyFramed | Self::Lz4 => CompressionLevel::None,
             Self::Gzip(level) | Self::Zlib(level) | Self::Zstd(level) => level,
         }
     }
@@ -117,99 +147,143 @@ impl fmt::Display for Compression {
             Compression::Zstd(ref level) => {
                 write!(f, "zstd({})", ZstdCompressionLevel::from(*level))
             }
+            Compression::Snappy => write!(f, "snappy"),
+            Compression::SnappyFramed => write!(f, "snappy_framed"),
+            Compression::Lz4 => write!(f, "lz4"),
         }
     }
 }
 
-impl<'de> de::Deserialize<'de> for Compression {
-    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
-    where
-        D: de::Deserializer<'de>,
-    {
-        struct StringOrMap;
+/// The compression algorithms accepted by `Compression`.
+const ALGORITHMS: &[&str] = &["none", "gzip", "zlib", "zstd"];
+
+/// The compression algorithms accepted by `AnyCompression`.
+const ANY_ALGORITHMS: &[&str] = &[
+    "none",
+    "gzip",
+    "zlib",
+    "zstd",
+    "snappy",
+    "snappy_framed",
+    "lz4",
+];
+
+/// Deserializes a compression configuration, only accepting the Snappy and LZ4 algorithms when
+/// `any` is set.
+fn deserialize_compression<'de, D>(deserializer: D, any: bool) -> Result<Compression, D::Error>
+where
+    D: de::Deserializer<'de>,
+{
+    struct StringOrMap {
+        any: bool,
+    }
 
-        impl<'de> de::Visitor<'de> for StringOrMap {
-            type Value = Compression;
+    impl<'de> de::Visitor<'de> for StringOrMap {
+        type Value = Compression;
 
-            fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
-                f.write_str("string or map")
-            }
+        fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+            f.write_str("string or map")
+        }
 
-            fn visit_str<E>(self, s: &str) -> Result<Self::Value, E>
-            where
-                E: de::Error,
-            {
-                match s {
-                    "none" => Ok(Compression::None),
-                    "gzip" => Ok(Compression::gzip_default()),
-                    "zlib" => Ok(Compression::zlib_default()),
-                    "zstd" => Ok(Compression::zstd_default()),
-                    _ => Err(de::Error::invalid_value(
-                        de::Unexpected::Str(s),
-                        &r#""none" or "gzip" or "zlib" or "zstd""#,
-                    )),
-                }
+        fn visit_str<E>(self, s: &str) -> Result<Self::Value, E>
+        where
+            E: de::Error,
+        {
+            match s {
+                "none" => Ok(Compression::None),
+                "gzip" => Ok(Compression::gzip_default()),
+                "zlib" => Ok(Compression::zlib_default()),
+                "zstd" => Ok(Compression::zstd_default()),
+                "snappy" if self.any => Ok(Compression::Snappy),
+                "snappy_framed" if self.any => Ok(Compression::SnappyFramed),
+                "lz4" if self.any => Ok(Compression::Lz4),
+                _ if self.any => Err(de::Error::invalid_value(
+                    de::Unexpected::Str(s),
+                    &r#""none", "gzip", "zlib", "zstd", "snappy", "snappy_framed" or "lz4""#,
+                )),
+                _ => Err(de::Error::invalid_value(
+                    de::Unexpected::Str(s),
+                    &r#""none" or "gzip" or "zlib" or "zstd""#,
+                )),
             }
+        }
 
-            fn visit_map<A>(self, mut map: A) -> Result<Self::Value, A::Error>
-            where
-                A: de::MapAccess<'de>,
-            {
-                let mut algorithm = None;
-                let mut level = None;
-
-                while let Some(key) = map.next_key::<String>()? {
-                    match key.as_str() {
-                        "algorithm" => {
-                            if algorithm.is_some() {
-                                return Err(de::Error::duplicate_field("algorithm"));
-                            }
-                            algorithm = Some(map.next_value::<String>()?);
+        fn visit_map<A>(self, mut map: A) -> Result<Self::Value, A::Error>
+        where
+            A: de::MapAccess<'de>,
+        {
+            let mut algorithm = None;
+            let mut level = None;
+
+            while let Some(key) = map.next_key::<String>()? {
+                match key.as_str() {
+                    "algorithm" => {
+                        if algorithm.is_some() {
+                            return Err(de::Error::duplicate_field("algorithm"));
                         }
-                        "level" => {
-                            if level.is_some() {
-                                return Err(de::Error::duplicate_field("level"));
-                            }
-                            level = Some(map.next_value::<CompressionLevel>()?);
+                        algorithm = Some(map.next_value::<String>()?);
+                    }
+                    "level" => {
+                        if level.is_some() {
+                            return Err(de::Error::duplicate_field("level"));
                         }
-                        _ => return Err(de::Error::unknown_field(&key, &["algorithm", "level"])),
-                    };
-                }
-
-                let compression = match algorithm
-                    .ok_or_else(|| de::Error::missing_field("algorithm"))?
-                    .as_str()
-                {
-                    "none" => match level {
-                        Some(_) => Err(de::Error::unknown_field("level", &[])),
-                        None => Ok(Compression::None),
-                    },
-                    "gzip" => Ok(Compression::Gzip(level.unwrap_or_default())),
-                    "zlib" => Ok(Compression::Zlib(level.unwrap_or_default())),
-                    "zstd" => Ok(Compression::Zstd(level.unwrap_or_default())),
-                    algorithm => Err(de::Error::unknown_variant(
-                        algorithm,
-                        &["none", "gzip", "zlib", "zstd"],
-                    )),
-                }?;
-
-                if let CompressionLevel::Val(level) = compression.compression_level() {
-                    let max_level = compression.max_compression_level_val();
-                    if level > max_level {
-                        let msg = std::format!(
-                            "invalid value `{}`, expected value in range [0, {}]",
-                            level,
-                            max_level
-                        );
-                        return Err(de::Error::custom(msg));
+                        level = Some(map.next_value::<CompressionLevel>()?);
                     }
+                    _ => return Err(de::Error::unknown_field(&key, &["algorithm", "level"])),
+                };
+            }
+
+            // Algorithms without a configurable compression level reject `level` outright, rather
+            // than silently ignoring it.
+            let levelless = |compression| -> Result<Compression, A::Error> {
+                match level {
+                    Some(_) => Err(de::Error::unknown_field("level", &[])),
+                    None => Ok(compression),
                 }
+            };
 
-                Ok(compression)
+            let compression = match algorithm
+                .ok_or_else(|| de::Error::missing_field("algorithm"))?
+                .as_str()
+            {
+                "none" => levelless(Compression::None),
+                "gzip" => Ok(Compression::Gzip(level.unwrap_or_default())),
+                "zlib" => Ok(Compression::Zlib(level.unwrap_or_default())),
+                "zstd" => Ok(Compression::Zstd(level.unwrap_or_default())),
+                "snappy" if self.any => levelless(Compression::Snappy),
+                "snappy_framed" if self.any => levelless(Compression::SnappyFramed),
+                "lz4" if self.any => levelless(Compression::Lz4),
+                algorithm => Err(de::Error::unknown_variant(
+                    algorithm,
+                    if self.any { ANY_ALGORITHMS } else { ALGORITHMS },
+                )),
+            }?;
+
+            if let CompressionLevel::Val(level) = compression.compression_level() {
+                let max_level = compression.max_compression_level_val();
+                if level > max_level {
+                    let msg = std::format!(
+                        "invalid value `{}`, expected value in range [0, {}]",
+                        level,
+                        max_level
+                    );
+                    return Err(de::Error::custom(msg));
+                }
             }
+
+            Ok(compression)
         }
+    }
 
-        deserializer.deserialize_any(StringOrMap)
+    deserializer.deserialize_any(StringOrMap { any })
+}
+
+impl<'de> de::Deserialize<'de> for Compression {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: de::Deserializer<'de>,
+    {
+        deserialize_compression(deserializer, false)
     }
 }
 
@@ -252,6 +326,9 @@ impl ser::Serialize for Compression {
                     serializer.serialize_str("zstd")
                 }
             }
+            Compression::Snappy => serializer.serialize_str("snappy"),
+            Compression::SnappyFramed => serializer.serialize_str("snappy_framed"),
+            Compression::Lz4 => serializer.serialize_str("lz4"),
         }
     }
 }
@@ -272,94 +349,135 @@ impl Configurable for Compression {
     }
 
     fn generate_schema(gen: &RefCell<SchemaGenerator>) -> Result<SchemaObject, GenerateError> {
-        const ALGORITHM_NAME: &str = "algorithm";
-        const LEVEL_NAME: &str = "level";
-        const LOGICAL_NAME: &str = "logical_name";
-        const ENUM_TAGGING_MODE: &str = "docs::enum_tagging";
-
-        let generate_string_schema = |logical_name: &str,
-                                      title: Option<&'static str>,
-                                      description: &'static str|
-         -> SchemaObject {
-            let mut const_schema = generate_const_string_schema(logical_name.to_lowercase());
-            let mut const_metadata = Metadata::with_description(description);
-            if let Some(title) = title {
-                const_metadata.set_title(title);
-            }
-            const_metadata.add_custom_attribute(CustomAttribute::kv(LOGICAL_NAME, logical_name));
-            apply_base_metadata(&mut const_schema, const_metadata);
-            const_schema
-        };
-
-        // First, we'll create the string-only subschemas for each algorithm, and wrap those up
-        // within a one-of schema.
-        let mut string_metadata = Metadata::with_description("Compression algorithm.");
-        string_metadata.add_custom_attribute(CustomAttribute::kv(ENUM_TAGGING_MODE, "external"));
-
-        let none_string_subschema = generate_string_schema("None", None, "No compression.");
-        let gzip_string_subschema = generate_string_schema(
-            "Gzip",
-            Some("[Gzip][gzip] compression."),
-            "[gzip]: https://www.gzip.org/",
-        );
-        let zlib_string_subschema = generate_string_schema(
-            "Zlib",
-            Some("[Zlib][zlib] compression."),
-            "[zlib]: https://zlib.net/",
-        );
-
-        let zstd_string_subschema = generate_string_schema(
-            "Zstd",
-            Some("[Zstandard][zstd] compression."),
-            "[zstd]: https://facebook.github.io/zstd/",
-        );
-
-        let mut all_string_oneof_subschema = generate_one_of_schema(&[
-            none_string_subschema,
-            gzip_string_subschema,
-            zlib_string_subschema,
-            zstd_string_subschema,
+        generate_compression_schema(gen, false)
+    }
+}
+
+fn generate_compression_schema(
+    gen: &RefCell<SchemaGenerator>,
+    any: bool,
+) -> Result<SchemaObject, GenerateError> {
+    const ALGORITHM_NAME: &str = "algorithm";
+    const LEVEL_NAME: &str = "level";
+    const LOGICAL_NAME: &str = "logical_name";
+    const ENUM_TAGGING_MODE: &str = "docs::enum_tagging";
+
+    let generate_string_schema = |logical_name: &str,
+                                  value: &str,
+                                  title: Option<&'static str>,
+                                  description: &'static str|
+     -> SchemaObject {
+        let mut const_schema = generate_const_string_schema(value.to_string());
+        let mut const_metadata = Metadata::with_description(description);
+        if let Some(title) = title {
+            const_metadata.set_title(title);
+        }
+        const_metadata.add_custom_attribute(CustomAttribute::kv(LOGICAL_NAME, logical_name));
+        apply_base_metadata(&mut const_schema, const_metadata);
+        const_schema
+    };
+
+    // First, we'll create the string-only subschemas for each algorithm, and wrap those up
+    // within a one-of schema.
+    let mut string_metadata = Metadata::with_description("Compression algorithm.");
+    string_metadata.add_custom_attribute(CustomAttribute::kv(ENUM_TAGGING_MODE, "external"));
+
+    let none_string_subschema = generate_string_schema("None", "none", None, "No compression.");
+    let gzip_string_subschema = generate_string_schema(
+        "Gzip",
+        "gzip",
+        Some("[Gzip][gzip] compression."),
+        "[gzip]: https://www.gzip.org/",
+    );
+    let zlib_string_subschema = generate_string_schema(
+        "Zlib",
+        "zlib",
+        Some("[Zlib][zlib] compression."),
+        "[zlib]: https://zlib.net/",
+    );
+
+    let zstd_string_subschema = generate_string_schema(
+        "Zstd",
+        "zstd",
+        Some("[Zstandard][zstd] compression."),
+        "[zstd]: https://facebook.github.io/zstd/",
+    );
+
+    let snappy_string_subschema = generate_string_schema(
+        "Snappy",
+        "snappy",
+        Some("[Snappy][snappy] compression, using the raw block format."),
+        "[snappy]: https://github.com/google/snappy/blob/main/format_description.txt",
+    );
+
+    let snappy_framed_string_subschema = generate_string_schema(
+        "SnappyFramed",
+        "snappy_framed",
+        Some("[Snappy][snappy] compression, using the framing format."),
+        "[snappy]: https://github.com/google/snappy/blob/main/framing_format.txt",
+    );
+
+    let lz4_string_subschema = generate_string_schema(
+        "Lz4",
+        "lz4",
+        Some("[LZ4][lz4] compression, using the frame format."),
+        "[lz4]: https://github.com/lz4/lz4/blob/dev/doc/lz4_Frame_format.md",
+    );
+
+    let mut string_subschemas = vec![
+        none_string_subschema,
+        gzip_string_subschema,
+        zlib_string_subschema,
+        zstd_string_subschema,
+    ];
+    if any {
+        string_subschemas.extend([
+            snappy_string_subschema,
+            snappy_framed_string_subschema,
+            lz4_string_subschema,
         ]);
-        apply_base_metadata(&mut all_string_oneof_subschema, string_metadata);
-
-        // Next we'll create a full schema for the given algorithms.
-        //
-        // TODO: We're currently using all three algorithms in the enum subschema for `algorithm`,
-        // but in reality, `level` is never used when the algorithm is `none`. This is _currently_
-        // fine because the field is optional, and we don't use `deny_unknown_fields`, so if users
-        // specify it when the algorithm is `none`: no harm, no foul.
-        //
-        // However, it does lead to a suboptimal schema being generated, one that sort of implies it
-        // may have value when set, even if the algorithm is `none`. We do this because, otherwise,
-        // it's very hard to reconcile the resolved schemas during component documentation
-        // generation, where we need to be able to generate the right enum key/value pair for the
-        // `none` algorithm as part of the overall set of enum values declared for the `algorithm`
-        // field in the "full" schema version.
-        let compression_level_schema =
-            get_or_generate_schema(&CompressionLevel::as_configurable_ref(), gen, None)?;
-
-        let mut required = BTreeSet::new();
-        required.insert(ALGORITHM_NAME.to_string());
-
-        let mut properties = IndexMap::new();
-        properties.insert(
-            ALGORITHM_NAME.to_string(),
-            all_string_oneof_subschema.clone(),
-        );
-        properties.insert(LEVEL_NAME.to_string(), compression_level_schema);
-
-        let mut full_subschema = generate_struct_schema(properties, required, None);
-        let mut full_metadata =
-            Metadata::with_description("Compression algorithm and compression level.");
-        full_metadata.add_custom_attribute(CustomAttribute::flag("docs::hidden"));
-        apply_base_metadata(&mut full_subschema, full_metadata);
-
-        // Finally, we zip both schemas together.
-        Ok(generate_one_of_schema(&[
-            all_string_oneof_subschema,
-            full_subschema,
-        ]))
     }
+
+    let mut all_string_oneof_subschema = generate_one_of_schema(&string_subschemas);
+    apply_base_metadata(&mut all_string_oneof_subschema, string_metadata);
+
+    // Next we'll create a full schema for the given algorithms.
+    //
+    // TODO: We're currently using all three algorithms in the enum subschema for `algorithm`,
+    // but in reality, `level` is never used when the algorithm is `none`. This is _currently_
+    // fine because the field is optional, and we don't use `deny_unknown_fields`, so if users
+    // specify it when the algorithm is `none`: no harm, no foul.
+    //
+    // However, it does lead to a suboptimal schema being generated, one that sort of implies it
+    // may have value when set, even if the algorithm is `none`. We do this because, otherwise,
+    // it's very hard to reconcile the resolved schemas during component documentation
+    // generation, where we need to be able to generate the right enum key/value pair for the
+    // `none` algorithm as part of the overall set of enum values declared for the `algorithm`
+    // field in the "full" schema version.
+    let compression_level_schema =
+        get_or_generate_schema(&CompressionLevel::as_configurable_ref(), gen, None)?;
+
+    let mut required = BTreeSet::new();
+    required.insert(ALGORITHM_NAME.to_string());
+
+    let mut properties = IndexMap::new();
+    properties.insert(
+        ALGORITHM_NAME.to_string(),
+        all_string_oneof_subschema.clone(),
+    );
+    properties.insert(LEVEL_NAME.to_string(), compression_level_schema);
+
+    let mut full_subschema = generate_struct_schema(properties, required, None);
+    let mut full_metadata =
+        Metadata::with_description("Compression algorithm and compression level.");
+    full_metadata.add_custom_attribute(CustomAttribute::flag("docs::hidden"));
+    apply_base_metadata(&mut full_subschema, full_metadata);
+
+    // Finally, we zip both schemas together.
+    Ok(generate_one_of_schema(&[
+        all_string_oneof_subschema,
+        full_subschema,
+    ]))
 }
 
 impl ToValue for Compression {
@@ -368,6 +486,75 @@ impl ToValue for Compression {
     }
 }
 
+/// Compression configuration, including the algorithms that only some receivers accept.
+///
+/// Sinks opt into this, rather than `Compression`, when their receivers store or forward the
+/// payload as is, such as object stores, files, and generic HTTP endpoints.
+#[derive(Copy, Clone, Debug, Default, Eq, PartialEq)]
+pub struct AnyCompression(pub Compression);
+
+impl AnyCompression {
+    pub const fn gzip_default() -> AnyCompression {
+        AnyCompression(Compression::gzip_default())
+    }
+}
+
+impl From<Compression> for AnyCompression {
+    fn from(compression: Compression) -> Self {
+        Self(compression)
+    }
+}
+
+impl From<AnyCompression> for Compression {
+    fn from(compression: AnyCompression) -> Self {
+        compression.0
+    }
+}
+
+impl fmt::Display for AnyCompression {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+impl<'de> de::Deserialize<'de> for AnyCompression {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: de::Deserializer<'de>,
+    {
+        deserialize_compression(deserializer, true).map(Self)
+    }
+}
+
+impl ser::Serialize for AnyCompression {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: ser::Serializer,
+    {
+        self.0.serialize(serializer)
+    }
+}
+
+impl Configurable for AnyCompression {
+    fn referenceable_name() -> Option<&'static str> {
+        Some(std::any::type_name::<Self>())
+    }
+
+    fn metadata() -> Metadata {
+        Compression::metadata()
+    }
+
+    fn generate_schema(gen: &RefCell<SchemaGenerator>) -> Result<SchemaObject, GenerateError> {
+        generate_compression_schema(gen, true)
+    }
+}
+
+impl ToValue for AnyCompression {
+    fn to_value(&self) -> Value {
+        self.0.to_value()
+    }
+}
+
 /// Compression level.
 #[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
 pub enum CompressionLevel {
@@ -501,7 +688,7 @@ impl ToValue for CompressionLevel {
 
 #[cfg(test)]
 mod test {
-    use super::{Compression, CompressionLevel};
+    use super::{AnyCompression, Compression, CompressionLevel};
 
     #[test]
     fn deserialization_json() {
@@ -553,6 +740,14 @@ mod test {
                 r#"{"algorithm": "b42"}"#,
                 r#"unknown variant `b42`, expected one of `none`, `gzip`, `zlib`, `zstd` at line 1 column 20"#,
             ),
+            (
+                r#""lz4""#,
+                r#"invalid value: string "lz4", expected "none" or "gzip" or "zlib" or "zstd" at line 1 column 5"#,
+            ),
+            (
+                r#"{"algorithm": "snappy"}"#,
+                r#"unknown variant `snappy`, expected one of `none`, `gzip`, `zlib`, `zstd` at line 1 column 23"#,
+            ),
             (
                 r#"{"algorithm": "none", "level": "default"}"#,
                 r#"unknown field `level`, there are no fields at line 1 column 41"#,
@@ -625,4 +820,51 @@ mod test {
             serde_json::from_value::<Compression>(value).unwrap();
         }
     }
+
+    #[test]
+    fn any_compression_deserialization() {
+        let fixtures_valid = [
+            (r#""gzip""#, Compression::Gzip(CompressionLevel::default())),
+            (r#""snappy""#, Compression::Snappy),
+            (r#""snappy_framed""#, Compression::SnappyFramed),
+            (r#""lz4""#, Compression::Lz4),
+            (r#"{"algorithm": "snappy"}"#, Compression::Snappy),
+            (r#"{"algorithm": "lz4"}"#, Compression::Lz4),
+            (
+                r#"{"algorithm": "zstd", "level": 6}"#,
+                Compression::Zstd(CompressionLevel::Val(6)),
+            ),
+        ];
+        for (source, result) in fixtures_valid.iter() {
+            let deserialized: Result<AnyCompression, _> = serde_json::from_str(source);
+            assert_eq!(deserialized.expect("valid source"), AnyCompression(*result));
+
+            // Check serialize-deserialize round trip
+            let value = serde_json::to_value(AnyCompression(*result)).unwrap();
+            assert_eq!(
+                serde_json::from_value::<AnyCompression>(value).unwrap(),
+                AnyCompression(*result)
+            );
+        }
+
+        let fixtures_invalid = [
+            (
+                r#""b42""#,
+                r#"invalid value: string "b42", expected "none", "gzip", "zlib", "zstd", "snappy", "snappy_framed" or "lz4" at line 1 column 5"#,
+            ),
+            (
+                r#"{"algorithm": "b42"}"#,
+                r#"unknown variant `b42`, expected one of `none`, `gzip`, `zlib`, `zstd`, `snappy`, `snappy_framed`, `lz4` at line 1 column 20"#,
+            ),
+            (
+                r#"{"algorithm": "lz4", "level": 4}"#,
+                r#"unknown field `level`, there are no fields at line 1 column 32"#,
+            ),
+        ];
+        for (source, result) in fixtures_invalid.iter() {
+            let deserialized: Result<AnyCompression, _> = serde_json::from_str(source);
+            let error = deserialized.expect_err("invalid source");
+            assert_eq!(error.to_string().as_str(), *result);
+        }
+    }
 }
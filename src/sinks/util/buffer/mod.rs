@@ -5,6 +5,8 @@ use flate2::write::{GzEncoder, ZlibEncoder};
 
 use super::{
     batch::{err_event_too_large, Batch, BatchSize, PushResult},
+    lz4::Lz4Encoder,
+    snappy::{SnappyEncoder, SnappyFramedEncoder},
     zstd::ZstdEncoder,
 };
 
@@ -14,7 +16,7 @@ pub mod metrics;
 pub mod partition;
 pub mod vec;
 
-pub use compression::Compression;
+pub use compression::{AnyCompression, Compression};
 pub use partition::{Partition, PartitionBuffer, PartitionInnerBuffer};
 
 #[derive(Debug)]
@@ -32,6 +34,9 @@ pub enum InnerBuffer {
     Gzip(GzEncoder<bytes::buf::Writer<BytesMut>>),
     Zlib(ZlibEncoder<bytes::buf::Writer<BytesMut>>),
     Zstd(ZstdEncoder<bytes::buf::Writer<BytesMut>>),
+    Snappy(SnappyEncoder<bytes::buf::Writer<BytesMut>>),
+    SnappyFramed(SnappyFramedEncoder<bytes::buf::Writer<BytesMut>>),
+    Lz4(Lz4Encoder<bytes::buf::Writer<BytesMut>>),
 }
 
 impl Buffer {
@@ -62,6 +67,11 @@ impl Buffer {
                     ZstdEncoder::new(writer, level.into())
                         .expect("Zstd encoder should not fail on init."),
                 ),
+                Compression::Snappy => InnerBuffer::Snappy(SnappyEncoder::new(writer)),
+                Compression::SnappyFramed => {
+                    InnerBuffer::SnappyFramed(SnappyFramedEncoder::new(writer))
+                }
+                Compression::Lz4 => InnerBuffer::Lz4(Lz4Encoder::new(writer)),
             }
         })
     }
@@ -81,6 +91,15 @@ impl Buffer {
             InnerBuffer::Zstd(inner) => {
                 inner.write_all(input).unwrap();
             }
+            InnerBuffer::Snappy(inner) => {
+                inner.write_all(input).unwrap();
+            }
+            InnerBuffer::SnappyFramed(inner) => {
+                inner.write_all(input).unwrap();
+            }
+            InnerBuffer::Lz4(inner) => {
+                inner.write_all(input).unwrap();
+            }
         }
     }
 
@@ -92,6 +111,11 @@ impl Buffer {
                 InnerBuffer::Gzip(inner) => inner.get_ref().get_ref().is_empty(),
                 InnerBuffer::Zlib(inner) => inner.get_ref().get_ref().is_empty(),
                 InnerBuffer::Zstd(inner) => inner.get_ref().get_ref().is_empty(),
+                // These encoders hold on to their input until enough of it has been written, so
+                // the underlying buffer can still be empty after items have been pushed.
+                InnerBuffer::Snappy(_) | InnerBuffer::SnappyFramed(_) | InnerBuffer::Lz4(_) => {
+                    self.num_items == 0
+                }
             })
             .unwrap_or(true)
     }
@@ -142,6 +166,18 @@ impl Batch for Buffer {
                 .finish()
                 .expect("This can't fail because the inner writer is a Vec")
                 .into_inner(),
+            Some(InnerBuffer::Snappy(inner)) => inner
+                .finish()
+                .expect("This can't fail because the inner writer is a Vec")
+                .into_inner(),
+            Some(InnerBuffer::SnappyFramed(inner)) => inner
+                .finish()
+                .expect("This can't fail because the inner writer is a Vec")
+                .into_inner(),
+            Some(InnerBuffer::Lz4(inner)) => inner
+                .finish()
+                .expect("This can't fail because the inner writer is a Vec")
+                .into_inner(),
             None => BytesMut::new(),
         }
     }
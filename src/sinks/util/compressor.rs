@@ -3,13 +3,21 @@ use std::io;
 use bytes::{BufMut, BytesMut};
 use flate2::write::{GzEncoder, ZlibEncoder};
 
-use super::{zstd::ZstdEncoder, Compression};
+use super::{
+    lz4::Lz4Encoder,
+    snappy::{SnappyEncoder, SnappyFramedEncoder},
+    zstd::ZstdEncoder,
+    Compression,
+};
 
 enum Writer {
     Plain(bytes::buf::Writer<BytesMut>),
     Gzip(GzEncoder<bytes::buf::Writer<BytesMut>>),
     Zlib(ZlibEncoder<bytes::buf::Writer<BytesMut>>),
     Zstd(ZstdEncoder<bytes::buf::Writer<BytesMut>>),
+    Snappy(SnappyEncoder<bytes::buf::Writer<BytesMut>>),
+    SnappyFramed(SnappyFramedEncoder<bytes::buf::Writer<BytesMut>>),
+    Lz4(Lz4Encoder<bytes::buf::Writer<BytesMut>>),
 }
 
 impl Writer {
@@ -19,6 +27,21 @@ impl Writer {
             Writer::Gzip(inner) => inner.get_ref().get_ref(),
             Writer::Zlib(inner) => inner.get_ref().get_ref(),
             Writer::Zstd(inner) => inner.get_ref().get_ref(),
+            Writer::Snappy(inner) => inner.get_ref().get_ref(),
+            Writer::SnappyFramed(inner) => inner.get_ref().get_ref(),
+            Writer::Lz4(inner) => inner.get_ref().get_ref(),
+        }
+    }
+
+    pub fn get_mut(&mut self) -> &mut BytesMut {
+        match self {
+            Writer::Plain(inner) => inner.get_mut(),
+            Writer::Gzip(inner) => inner.get_mut().get_mut(),
+            Writer::Zlib(inner) => inner.get_mut().get_mut(),
+            Writer::Zstd(inner) => inner.get_mut().get_mut(),
+            Writer::Snappy(inner) => inner.get_mut().get_mut(),
+            Writer::SnappyFramed(inner) => inner.get_mut().get_mut(),
+            Writer::Lz4(inner) => inner.get_mut().get_mut(),
         }
     }
 }
@@ -35,6 +58,9 @@ impl From<Compression> for Writer {
                     .expect("Zstd encoder should not fail on init.");
                 Writer::Zstd(encoder)
             }
+            Compression::Snappy => Writer::Snappy(SnappyEncoder::new(writer)),
+            Compression::SnappyFramed => Writer::SnappyFramed(SnappyFramedEncoder::new(writer)),
+            Compression::Lz4 => Writer::Lz4(Lz4Encoder::new(writer)),
         }
     }
 }
@@ -47,6 +73,9 @@ impl io::Write for Writer {
             Writer::Gzip(writer) => writer.write(buf),
             Writer::Zlib(writer) => writer.write(buf),
             Writer::Zstd(writer) => writer.write(buf),
+            Writer::Snappy(writer) => writer.write(buf),
+            Writer::SnappyFramed(writer) => writer.write(buf),
+            Writer::Lz4(writer) => writer.write(buf),
         }
     }
 
@@ -56,6 +85,9 @@ impl io::Write for Writer {
             Writer::Gzip(writer) => writer.flush(),
             Writer::Zlib(writer) => writer.flush(),
             Writer::Zstd(writer) => writer.flush(),
+            Writer::Snappy(writer) => writer.flush(),
+            Writer::SnappyFramed(writer) => writer.flush(),
+            Writer::Lz4(writer) => writer.flush(),
         }
     }
 }
@@ -74,6 +106,16 @@ impl Compressor {
         self.inner.get_ref()
     }
 
+    /// Takes the compressed output that has been written to the underlying buffer so far.
+    ///
+    /// This allows streaming the compressed output to its destination as it's produced, rather
+    /// than holding it all in memory until the compressor is finished. Any output that is still
+    /// held by the encoder itself is left in place, and is returned when the compressor is
+    /// finished.
+    pub fn take_output(&mut self) -> BytesMut {
+        self.inner.get_mut().split()
+    }
+
     /// Gets whether or not this compressor will actually compress the input.
     ///
     /// While it may be counterintuitive for "compression" to not compress, this is simply a
@@ -98,6 +140,9 @@ impl Compressor {
             Writer::Gzip(writer) => writer.finish()?,
             Writer::Zlib(writer) => writer.finish()?,
             Writer::Zstd(writer) => writer.finish()?,
+            Writer::Snappy(writer) => writer.finish()?,
+            Writer::SnappyFramed(writer) => writer.finish()?,
+            Writer::Lz4(writer) => writer.finish()?,
         }
         .into_inner();
 
@@ -125,6 +170,15 @@ impl Compressor {
             Writer::Zstd(writer) => writer
                 .finish()
                 .expect("zstd writer should not fail to finish"),
+            Writer::Snappy(writer) => writer
+                .finish()
+                .expect("snappy writer should not fail to finish"),
+            Writer::SnappyFramed(writer) => writer
+                .finish()
+                .expect("snappy writer should not fail to finish"),
+            Writer::Lz4(writer) => writer
+                .finish()
+                .expect("lz4 writer should not fail to finish"),
         }
         .into_inner()
     }
@@ -149,3 +203,79 @@ impl From<Compression> for Compressor {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::io::{Read, Write};
+
+    use super::Compressor;
+    use crate::sinks::util::Compression;
+
+    const INPUT: &[u8] = b"It's going down, I'm yelling timber, You better move, you better dance";
+
+    fn compress(compression: Compression) -> Vec<u8> {
+        let mut compressor = Compressor::from(compression);
+        for _ in 0..1_000 {
+            compressor.write_all(INPUT).unwrap();
+        }
+        compressor.finish().unwrap().to_vec()
+    }
+
+    fn expected() -> Vec<u8> {
+        INPUT.repeat(1_000)
+    }
+
+    #[test]
+    fn snappy() {
+        let compressed = compress(Compression::Snappy);
+        assert!(compressed.len() < expected().len());
+
+        let decompressed = snap::raw::Decoder::new()
+            .decompress_vec(&compressed)
+            .unwrap();
+        assert_eq!(decompressed, expected());
+    }
+
+    #[test]
+    fn snappy_framed() {
+        let compressed = compress(Compression::SnappyFramed);
+        assert!(compressed.len() < expected().len());
+
+        let mut decompressed = Vec::new();
+        snap::read::FrameDecoder::new(&compressed[..])
+            .read_to_end(&mut decompressed)
+            .unwrap();
+        assert_eq!(decompressed, expected());
+    }
+
+    #[test]
+    fn lz4() {
+        let compressed = compress(Compression::Lz4);
+        assert!(compressed.len() < expected().len());
+
+        let mut decompressed = Vec::new();
+        lz4_flex::frame::FrameDecoder::new(&compressed[..])
+            .read_to_end(&mut decompressed)
+            .unwrap();
+        assert_eq!(decompressed, expected());
+    }
+
+    #[test]
+    fn take_output_streams_compressed_data() {
+        let mut compressor = Compressor::from(Compression::Lz4);
+        let mut compressed = Vec::new();
+        for _ in 0..100_000 {
+            compressor.write_all(INPUT).unwrap();
+            compressed.extend_from_slice(&compressor.take_output());
+        }
+        // The encoder should have produced output before being finished.
+        assert!(!compressed.is_empty());
+        compressed.extend_from_slice(&compressor.finish().unwrap());
+
+        let mut decompressed = Vec::new();
+        lz4_flex::frame::FrameDecoder::new(&compressed[..])
+            .read_to_end(&mut decompressed)
+            .unwrap();
+        assert_eq!(decompressed, INPUT.repeat(100_000));
+    }
+}
@@ -24,51 +24,104 @@ pub trait Encoder<T> {
 impl Encoder<Vec<Event>> for (Transformer, crate::codecs::Encoder<Framer>) {
     fn encode_input(
         &self,
-        mut events: Vec<Event>,
+        events: Vec<Event>,
         writer: &mut dyn io::Write,
     ) -> io::Result<(usize, GroupedCountByteSize)> {
-        let mut encoder = self.1.clone();
-        let mut bytes_written = 0;
-        let mut n_events_pending = events.len();
-        let batch_prefix = encoder.batch_prefix();
-        write_all(writer, n_events_pending, batch_prefix)?;
-        bytes_written += batch_prefix.len();
-
-        let mut byte_size = telemetry().create_request_count_byte_size();
+        encode_framed(&self.0, &self.1, events, writer)
+    }
+}
 
-        if let Some(last) = events.pop() {
-            for mut event in events {
-                self.0.transform(&mut event);
-                let mut bytes = BytesMut::new();
-                encoder
-                    .encode(event, &mut bytes)
-                    .map_err(|error| io::Error::new(io::ErrorKind::InvalidData, error))?;
-                write_all(writer, n_events_pending, &bytes)?;
-                bytes_written += bytes.len();
-                n_events_pending -= 1;
+impl Encoder<Vec<Event>> for (Transformer, crate::codecs::EncoderKind) {
+    fn encode_input(
+        &self,
+        events: Vec<Event>,
+        writer: &mut dyn io::Write,
+    ) -> io::Result<(usize, GroupedCountByteSize)> {
+        match &self.1 {
+            crate::codecs::EncoderKind::Framed(encoder) => {
+                encode_framed(&self.0, encoder, events, writer)
             }
-            let mut event = last;
-            self.0.transform(&mut event);
-
-            // Ensure the json size is calculated after any fields have been removed
-            // by the transformer.
-            byte_size.add_event(&event, event.estimated_json_encoded_size_of());
+            crate::codecs::EncoderKind::Batch(encoder) => {
+                encode_batch(&self.0, encoder, events, writer)
+            }
+        }
+    }
+}
 
+/// Encodes each event as a frame, surrounded by the batch prefix and suffix of the framer.
+fn encode_framed(
+    transformer: &Transformer,
+    encoder: &crate::codecs::Encoder<Framer>,
+    mut events: Vec<Event>,
+    writer: &mut dyn io::Write,
+) -> io::Result<(usize, GroupedCountByteSize)> {
+    let mut encoder = encoder.clone();
+    let mut bytes_written = 0;
+    let mut n_events_pending = events.len();
+    let batch_prefix = encoder.batch_prefix();
+    write_all(writer, n_events_pending, batch_prefix)?;
+    bytes_written += batch_prefix.len();
+
+    let mut byte_size = telemetry().create_request_count_byte_size();
+
+    if let Some(last) = events.pop() {
+        for mut event in events {
+            transformer.transform(&mut event);
             let mut bytes = BytesMut::new();
             encoder
-                .serialize(event, &mut bytes)
+                .encode(event, &mut bytes)
                 .map_err(|error| io::Error::new(io::ErrorKind::InvalidData, error))?;
             write_all(writer, n_events_pending, &bytes)?;
             bytes_written += bytes.len();
             n_events_pending -= 1;
         }
-        let batch_suffix = encoder.batch_suffix();
-        assert!(n_events_pending == 0);
-        write_all(writer, 0, batch_suffix)?;
-        bytes_written += batch_suffix.len();
+        let mut event = last;
+        transformer.transform(&mut event);
+
+        // Ensure the json size is calculated after any fields have been removed
+        // by the transformer.
+        byte_size.add_event(&event, event.estimated_json_encoded_size_of());
 
-        Ok((bytes_written, byte_size))
+        let mut bytes = BytesMut::new();
+        encoder
+            .serialize(event, &mut bytes)
+            .map_err(|error| io::Error::new(io::ErrorKind::InvalidData, error))?;
+        write_all(writer, n_events_pending, &bytes)?;
+        bytes_written += bytes.len();
+        n_events_pending -= 1;
+    }
+    let batch_suffix = encoder.batch_suffix();
+    assert!(n_events_pending == 0);
+    write_all(writer, 0, batch_suffix)?;
+    bytes_written += batch_suffix.len();
+
+    Ok((bytes_written, byte_size))
+}
+
+/// Encodes all the events at once with a batch encoder.
+fn encode_batch(
+    transformer: &Transformer,
+    encoder: &crate::codecs::BatchEncoder,
+    mut events: Vec<Event>,
+    writer: &mut dyn io::Write,
+) -> io::Result<(usize, GroupedCountByteSize)> {
+    let mut byte_size = telemetry().create_request_count_byte_size();
+    for event in events.iter_mut() {
+        transformer.transform(event);
+
+        // Ensure the json size is calculated after any fields have been removed
+        // by the transformer.
+        byte_size.add_event(event, event.estimated_json_encoded_size_of());
     }
+
+    let n_events = events.len();
+    let mut bytes = BytesMut::new();
+    encoder
+        .encode_batch(events, &mut bytes)
+        .map_err(|error| io::Error::new(io::ErrorKind::InvalidData, error))?;
+    write_all(writer, n_events, &bytes)?;
+
+    Ok((bytes.len(), byte_size))
 }
 
 impl Encoder<Event> for (Transformer, crate::codecs::Encoder<()>) {
@@ -147,8 +200,9 @@ mod tests {
     use std::collections::BTreeMap;
 
     use codecs::{
+        encoding::{ParquetColumnType, ParquetSerializerOptions},
         CharacterDelimitedEncoder, JsonSerializerConfig, NewlineDelimitedEncoder,
-        TextSerializerConfig,
+        ParquetSerializerConfig, TextSerializerConfig,
     };
     use vector_core::event::LogEvent;
     use vrl::value::Value;
@@ -315,6 +369,45 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_encode_batch_parquet() {
+        let serializer = ParquetSerializerConfig::new(ParquetSerializerOptions {
+            schema: Some(BTreeMap::from([(
+                String::from("key"),
+                ParquetColumnType::String,
+            )])),
+            ..Default::default()
+        })
+        .build()
+        .unwrap();
+        let encoding = (
+            Transformer::default(),
+            crate::codecs::EncoderKind::Batch(crate::codecs::BatchEncoder::new(serializer.into())),
+        );
+
+        let mut writer = Vec::new();
+        let (written, _json_size) = encoding
+            .encode_input(
+                vec![
+                    Event::Log(LogEvent::from(BTreeMap::from([(
+                        String::from("key"),
+                        Value::from("value1"),
+                    )]))),
+                    Event::Log(LogEvent::from(BTreeMap::from([(
+                        String::from("key"),
+                        Value::from("value2"),
+                    )]))),
+                ],
+                &mut writer,
+            )
+            .unwrap();
+        assert_eq!(written, writer.len());
+
+        // Parquet files start and end with the `PAR1` magic number.
+        assert!(writer.starts_with(b"PAR1"));
+        assert!(writer.ends_with(b"PAR1"));
+    }
+
     #[test]
     fn test_encode_event_json() {
         let encoding = (
@@ -0,0 +1,51 @@
+use std::io;
+
+use lz4_flex::frame::FrameEncoder;
+
+/// Encoder for the [LZ4 frame format][lz4].
+///
+/// [lz4]: https://github.com/lz4/lz4/blob/dev/doc/lz4_Frame_format.md
+pub struct Lz4Encoder<W: io::Write> {
+    inner: FrameEncoder<W>,
+}
+
+impl<W: io::Write> Lz4Encoder<W> {
+    pub fn new(writer: W) -> Self {
+        Self {
+            inner: FrameEncoder::new(writer),
+        }
+    }
+
+    pub fn finish(self) -> io::Result<W> {
+        self.inner
+            .finish()
+            .map_err(|error| io::Error::new(io::ErrorKind::Other, error))
+    }
+
+    pub fn get_ref(&self) -> &W {
+        self.inner.get_ref()
+    }
+
+    pub fn get_mut(&mut self) -> &mut W {
+        self.inner.get_mut()
+    }
+}
+
+impl<W: io::Write> io::Write for Lz4Encoder<W> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        #[allow(clippy::disallowed_methods)] // Caller handles the result of `write`.
+        self.inner.write(buf)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.inner.flush()
+    }
+}
+
+impl<W: io::Write + std::fmt::Debug> std::fmt::Debug for Lz4Encoder<W> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Lz4Encoder")
+            .field("inner", &self.get_ref())
+            .finish()
+    }
+}
@@ -5,6 +5,7 @@ pub mod builder;
 pub mod compressor;
 pub mod encoding;
 pub mod http;
+pub mod lz4;
 pub mod metadata;
 pub mod normalizer;
 pub mod partitioner;
@@ -13,6 +14,7 @@ pub mod request_builder;
 pub mod retries;
 pub mod service;
 pub mod sink;
+pub mod snappy;
 pub mod socket_bytes_sink;
 pub mod statistic;
 pub mod tcp;
@@ -35,7 +37,7 @@ pub use buffer::{
     json::{BoxedRawValue, JsonArrayBuffer},
     partition::Partition,
     vec::{EncodedLength, VecBuffer},
-    Buffer, Compression, PartitionBuffer, PartitionInnerBuffer,
+    AnyCompression, Buffer, Compression, PartitionBuffer, PartitionInnerBuffer,
 };
 pub use builder::SinkBuilderExt;
 pub use compressor::Compressor;
@@ -53,7 +55,7 @@ use vector_common::json_size::JsonSize;
 use crate::event::EventFinalizers;
 
 #[derive(Debug, Snafu)]
-enum SinkBuildError {
+pub(crate) enum SinkBuildError {
     #[snafu(display("Missing host in address field"))]
     MissingHost,
     #[snafu(display("Missing port in address field"))]
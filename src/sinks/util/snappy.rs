@@ -0,0 +1,107 @@
+use std::io;
+
+use snap::{raw, write::FrameEncoder};
+
+/// Encoder for the raw [Snappy][snappy] format.
+///
+/// The raw format is a single compressed block with no framing, so it can't be written
+/// incrementally: input is buffered and only compressed, and written to the inner writer, once the
+/// encoder is finished.
+///
+/// [snappy]: https://github.com/google/snappy/blob/main/format_description.txt
+pub struct SnappyEncoder<W: io::Write> {
+    writer: W,
+    input: Vec<u8>,
+}
+
+impl<W: io::Write> SnappyEncoder<W> {
+    pub const fn new(writer: W) -> Self {
+        Self {
+            writer,
+            input: Vec::new(),
+        }
+    }
+
+    pub fn finish(mut self) -> io::Result<W> {
+        let compressed = raw::Encoder::new()
+            .compress_vec(&self.input)
+            .map_err(io::Error::from)?;
+        self.writer.write_all(&compressed)?;
+        Ok(self.writer)
+    }
+
+    pub const fn get_ref(&self) -> &W {
+        &self.writer
+    }
+
+    pub fn get_mut(&mut self) -> &mut W {
+        &mut self.writer
+    }
+}
+
+impl<W: io::Write> io::Write for SnappyEncoder<W> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.input.extend_from_slice(buf);
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+impl<W: io::Write + std::fmt::Debug> std::fmt::Debug for SnappyEncoder<W> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("SnappyEncoder")
+            .field("inner", &self.get_ref())
+            .finish()
+    }
+}
+
+/// Encoder for the [Snappy framing format][framing].
+///
+/// [framing]: https://github.com/google/snappy/blob/main/framing_format.txt
+pub struct SnappyFramedEncoder<W: io::Write> {
+    inner: FrameEncoder<W>,
+}
+
+impl<W: io::Write> SnappyFramedEncoder<W> {
+    pub fn new(writer: W) -> Self {
+        Self {
+            inner: FrameEncoder::new(writer),
+        }
+    }
+
+    pub fn finish(self) -> io::Result<W> {
+        self.inner
+            .into_inner()
+            .map_err(|error| io::Error::new(error.error().kind(), error.to_string()))
+    }
+
+    pub fn get_ref(&self) -> &W {
+        self.inner.get_ref()
+    }
+
+    pub fn get_mut(&mut self) -> &mut W {
+        self.inner.get_mut()
+    }
+}
+
+impl<W: io::Write> io::Write for SnappyFramedEncoder<W> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        #[allow(clippy::disallowed_methods)] // Caller handles the result of `write`.
+        self.inner.write(buf)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.inner.flush()
+    }
+}
+
+impl<W: io::Write + std::fmt::Debug> std::fmt::Debug for SnappyFramedEncoder<W> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("SnappyFramedEncoder")
+            .field("inner", &self.get_ref())
+            .finish()
+    }
+}
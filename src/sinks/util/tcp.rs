@@ -42,7 +42,7 @@ use crate::{
 };
 
 #[derive(Debug, Snafu)]
-enum TcpError {
+pub(crate) enum TcpError {
     #[snafu(display("Connect error: {}", source))]
     ConnectError { source: TlsError },
     #[snafu(display("Unable to resolve DNS: {}", source))]
@@ -124,7 +124,7 @@ impl TcpSinkConfig {
 }
 
 #[derive(Clone)]
-struct TcpConnector {
+pub(crate) struct TcpConnector {
     host: String,
     port: u16,
     keepalive: Option<TcpKeepaliveConfig>,
@@ -133,7 +133,7 @@ struct TcpConnector {
 }
 
 impl TcpConnector {
-    const fn new(
+    pub(crate) const fn new(
         host: String,
         port: u16,
         keepalive: Option<TcpKeepaliveConfig>,
@@ -154,14 +154,14 @@ impl TcpConnector {
         Self::new(host, port, None, None.into(), None)
     }
 
-    const fn fresh_backoff() -> ExponentialBackoff {
+    pub(crate) const fn fresh_backoff() -> ExponentialBackoff {
         // TODO: make configurable
         ExponentialBackoff::from_millis(2)
             .factor(250)
             .max_delay(Duration::from_secs(60))
     }
 
-    async fn connect(&self) -> Result<MaybeTlsStream<TcpStream>, TcpError> {
+    pub(crate) async fn connect(&self) -> Result<MaybeTlsStream<TcpStream>, TcpError> {
         let ip = dns::Resolver
             .lookup_ip(self.host.clone())
             .await
@@ -209,7 +209,7 @@ impl TcpConnector {
         }
     }
 
-    async fn healthcheck(&self) -> crate::Result<()> {
+    pub(crate) async fn healthcheck(&self) -> crate::Result<()> {
         self.connect().await.map(|_| ()).map_err(Into::into)
     }
 }
@@ -5,7 +5,8 @@ use std::{
 };
 
 use async_trait::async_trait;
-use bytes::BytesMut;
+use bytes::{Bytes, BytesMut};
+use codecs::encoding::{ChunkedGelfEncoder, ChunkedGelfEncoderConfig};
 use futures::{stream::BoxStream, FutureExt, StreamExt};
 use snafu::{ResultExt, Snafu};
 use tokio::{net::UdpSocket, time::sleep};
@@ -22,7 +23,7 @@ use crate::{
     dns,
     event::{Event, EventStatus, Finalizable},
     internal_events::{
-        SocketEventsSent, SocketMode, SocketSendError, UdpSendIncompleteError,
+        EncoderFramingError, SocketEventsSent, SocketMode, SocketSendError, UdpSendIncompleteError,
         UdpSocketConnectionEstablished, UdpSocketOutgoingConnectionError,
     },
     net,
@@ -63,6 +64,29 @@ pub struct UdpSinkConfig {
     #[configurable(metadata(docs::type_unit = "bytes"))]
     #[configurable(metadata(docs::examples = 65536))]
     send_buffer_bytes: Option<usize>,
+
+    /// Chunking configuration.
+    ///
+    /// Chunking splits messages that are too large for a single datagram over several datagrams.
+    /// When not set, each message is sent as a single datagram, however large.
+    #[configurable(derived)]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    chunking: Option<ChunkingConfig>,
+}
+
+/// Chunking configuration.
+#[configurable_component]
+#[derive(Clone, Debug)]
+#[serde(tag = "method", rename_all = "snake_case")]
+#[configurable(metadata(docs::enum_tag_description = "The chunking method."))]
+pub enum ChunkingConfig {
+    /// Messages larger than the maximum chunk size are split into [chunked GELF][chunked_gelf]
+    /// messages.
+    ///
+    /// This is meant to be used along with the `gelf` codec.
+    ///
+    /// [chunked_gelf]: https://go2docs.graylog.org/current/getting_in_log_data/gelf.html#GELFviaUDP
+    ChunkedGelf(ChunkedGelfEncoderConfig),
 }
 
 impl UdpSinkConfig {
@@ -70,6 +94,7 @@ impl UdpSinkConfig {
         Self {
             address,
             send_buffer_bytes: None,
+            chunking: None,
         }
     }
 
@@ -86,7 +111,11 @@ impl UdpSinkConfig {
         encoder: impl Encoder<Event, Error = codecs::encoding::Error> + Clone + Send + Sync + 'static,
     ) -> crate::Result<(VectorSink, Healthcheck)> {
         let connector = self.build_connector()?;
-        let sink = UdpSink::new(connector.clone(), transformer, encoder);
+        let chunker = match &self.chunking {
+            Some(ChunkingConfig::ChunkedGelf(config)) => Some(config.build()?),
+            None => None,
+        };
+        let sink = UdpSink::new(connector.clone(), transformer, encoder, chunker);
         Ok((
             VectorSink::from_event_streamsink(sink),
             async move { connector.healthcheck().await }.boxed(),
@@ -169,6 +198,7 @@ where
     connector: UdpConnector,
     transformer: Transformer,
     encoder: E,
+    chunker: Option<ChunkedGelfEncoder>,
     bytes_sent: Registered<BytesSent>,
 }
 
@@ -176,11 +206,17 @@ impl<E> UdpSink<E>
 where
     E: Encoder<Event, Error = codecs::encoding::Error> + Clone + Send + Sync,
 {
-    fn new(connector: UdpConnector, transformer: Transformer, encoder: E) -> Self {
+    fn new(
+        connector: UdpConnector,
+        transformer: Transformer,
+        encoder: E,
+        chunker: Option<ChunkedGelfEncoder>,
+    ) -> Self {
         Self {
             connector,
             transformer,
             encoder,
+            chunker,
             bytes_sent: register!(BytesSent::from(Protocol::UDP)),
         }
     }
@@ -210,15 +246,26 @@ where
                     continue;
                 }
 
-                match udp_send(&mut socket, &bytes).await {
-                    Ok(()) => {
+                let datagrams = match &self.chunker {
+                    Some(chunker) => match chunker.chunk(bytes.freeze()) {
+                        Ok(datagrams) => datagrams,
+                        Err(error) => {
+                            emit!(EncoderFramingError { error: &error });
+                            continue;
+                        }
+                    },
+                    None => vec![bytes.freeze()],
+                };
+
+                match udp_send_all(&mut socket, &datagrams).await {
+                    Ok(sent) => {
                         emit!(SocketEventsSent {
                             mode: SocketMode::Udp,
                             count: 1,
                             byte_size,
                         });
 
-                        self.bytes_sent.emit(ByteSize(bytes.len()));
+                        self.bytes_sent.emit(ByteSize(sent));
                         finalizers.update_status(EventStatus::Delivered);
                     }
                     Err(error) => {
@@ -248,6 +295,15 @@ async fn udp_send(socket: &mut UdpSocket, buf: &[u8]) -> tokio::io::Result<()> {
     Ok(())
 }
 
+async fn udp_send_all(socket: &mut UdpSocket, datagrams: &[Bytes]) -> tokio::io::Result<usize> {
+    let mut sent = 0;
+    for datagram in datagrams {
+        udp_send(socket, datagram).await?;
+        sent += datagram.len();
+    }
+    Ok(sent)
+}
+
 fn find_bind_address(remote_addr: &SocketAddr) -> SocketAddr {
     match remote_addr {
         SocketAddr::V4(_) => SocketAddr::new(IpAddr::V4(Ipv4Addr::UNSPECIFIED), 0),
@@ -41,6 +41,10 @@ impl<W: io::Write> ZstdEncoder<W> {
     pub fn get_ref(&self) -> &W {
         self.inner.get_ref()
     }
+
+    pub fn get_mut(&mut self) -> &mut W {
+        self.inner.get_mut()
+    }
 }
 
 impl<W: io::Write> io::Write for ZstdEncoder<W> {
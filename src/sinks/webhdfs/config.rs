@@ -13,8 +13,8 @@ use crate::{
     sinks::{
         opendal_common::*,
         util::{
-            partitioner::KeyPartitioner, BatchConfig, BulkSizeBasedDefaultBatchSettings,
-            Compression,
+            partitioner::KeyPartitioner, AnyCompression, BatchConfig,
+            BulkSizeBasedDefaultBatchSettings,
         },
         Healthcheck,
     },
@@ -59,8 +59,8 @@ pub struct WebHdfsConfig {
     pub encoding: EncodingConfigWithFraming,
 
     #[configurable(derived)]
-    #[serde(default = "Compression::gzip_default")]
-    pub compression: Compression,
+    #[serde(default = "AnyCompression::gzip_default")]
+    pub compression: AnyCompression,
 
     #[configurable(derived)]
     #[serde(default)]
@@ -87,7 +87,7 @@ impl GenerateConfig for WebHdfsConfig {
                 JsonSerializerConfig::default(),
             )
                 .into(),
-            compression: Compression::gzip_default(),
+            compression: AnyCompression::gzip_default(),
             batch: BatchConfig::default(),
 
             acknowledgements: Default::default(),
@@ -142,7 +142,7 @@ impl WebHdfsConfig {
 
         let request_builder = OpenDalRequestBuilder {
             encoder: (transformer, encoder),
-            compression: self.compression,
+            compression: self.compression.into(),
         };
 
         // TODO: we can add tower middleware here.
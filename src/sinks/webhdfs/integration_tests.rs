@@ -127,7 +127,7 @@ fn config(endpoint: &str, batch_size: usize) -> WebHdfsConfig {
         endpoint: endpoint.to_string(),
 
         encoding: (None::<FramingConfig>, TextSerializerConfig::default()).into(),
-        compression: Compression::None,
+        compression: Compression::None.into(),
         batch,
         acknowledgements: Default::default(),
     }
@@ -22,7 +22,7 @@ fn default_config(encoding: EncodingConfigWithFraming) -> WebHdfsConfig {
         prefix: "%F/".to_string(),
         endpoint: "http://127.0.0.1:9870".to_string(),
         encoding,
-        compression: Compression::gzip_default(),
+        compression: Compression::gzip_default().into(),
         batch: Default::default(),
         acknowledgements: Default::default(),
     }
@@ -43,13 +43,13 @@ fn request_builder(sink_config: &WebHdfsConfig) -> OpenDalRequestBuilder {
 
     OpenDalRequestBuilder {
         encoder: (transformer, encoder),
-        compression: sink_config.compression,
+        compression: sink_config.compression.into(),
     }
 }
 
 fn build_request(compression: Compression) -> OpenDalRequest {
     let sink_config = WebHdfsConfig {
-        compression,
+        compression: compression.into(),
         ..default_config(
             (
                 Some(NewlineDelimitedEncoderConfig::new()),
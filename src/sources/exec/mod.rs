@@ -233,8 +233,8 @@ impl SourceConfig for ExecConfig {
             .framing
             .clone()
             .unwrap_or_else(|| self.decoding.default_stream_framing());
-        let decoder =
-            DecodingConfig::new(framing, self.decoding.clone(), LogNamespace::Legacy).build()?;
+        let decoder = DecodingConfig::new(framing, self.decoding.clone(), LogNamespace::Legacy)
+            .build_stream()?;
 
         match &self.mode {
             Mode::Scheduled => {
@@ -22,6 +22,7 @@ use vrl::value::{Kind, Value};
 
 use super::util::net::{SocketListenAddr, TcpSource, TcpSourceAck, TcpSourceAcker};
 use crate::{
+    common::fluent::{FluentEntry, FluentMessage, FluentRecord, FluentTag, FluentTimestamp},
     config::{
         log_schema, DataType, GenerateConfig, Resource, SourceAcknowledgementsConfig, SourceConfig,
         SourceContext, SourceOutput,
@@ -33,9 +34,6 @@ use crate::{
     tls::{MaybeTlsSettings, TlsSourceConfig},
 };
 
-mod message;
-use self::message::{FluentEntry, FluentMessage, FluentRecord, FluentTag, FluentTimestamp};
-
 /// Configuration for the `fluent` source.
 #[configurable_component(source("fluent", "Collect logs from a Fluentd or Fluent Bit agent."))]
 #[derive(Clone, Debug)]
@@ -642,8 +640,9 @@ mod tests {
     use vector_core::{event::Value, schema::Definition};
     use vrl::value::kind::Collection;
 
-    use super::{message::FluentMessageOptions, *};
+    use super::*;
     use crate::{
+        common::fluent::FluentMessageOptions,
         config::{SourceConfig, SourceContext},
         event::EventStatus,
         test_util::{self, next_addr, trace_init, wait_for_tcp},
@@ -3,20 +3,28 @@
 
 use bytes::{Bytes, BytesMut};
 use chrono::Utc;
-use futures_util::FutureExt;
+use futures_util::{
+    future::{BoxFuture, Shared},
+    FutureExt,
+};
 use http::{response::Parts, Uri};
 use serde_with::serde_as;
 use snafu::ResultExt;
-use std::{collections::HashMap, time::Duration};
+use std::{collections::HashMap, path::PathBuf, time::Duration};
 use tokio_util::codec::Decoder as _;
 
 use crate::{
     codecs::{Decoder, DecodingConfig},
     config::{SourceConfig, SourceContext},
     http::Auth,
+    internal_events::TemplateRenderingError,
     register_validatable_component,
     serde::{default_decoding, default_framing_message_based},
     sources,
+    sources::http_client::{
+        cursor::{Cursor, CursorConfig},
+        pagination::{with_optional_query_param, PageState, PaginationConfig},
+    },
     sources::util::{
         http::HttpMethod,
         http_client::{
@@ -24,6 +32,7 @@ use crate::{
             GenericHttpClientInputs, HttpClientBuilder,
         },
     },
+    template::Template,
     tls::{TlsConfig, TlsSettings},
     Result,
 };
@@ -32,10 +41,11 @@ use codecs::{
     decoding::{DeserializerConfig, FramingConfig},
     StreamDecodingError,
 };
+use vector_common::finalization::AddBatchNotifier;
 use vector_config::configurable_component;
 use vector_core::{
     config::{log_schema, LogNamespace, SourceOutput},
-    event::Event,
+    event::{BatchNotifier, BatchStatus, BatchStatusReceiver, Event, LogEvent},
 };
 
 /// Configuration for the `http_client` source.
@@ -55,6 +65,8 @@ pub struct HttpClientConfig {
     /// The interval between scrapes. Requests are run concurrently so if a scrape takes longer
     /// than the interval a new scrape will be started. This can take extra resources, set the timeout
     /// to a value lower than the scrape interval to prevent this from happening.
+    ///
+    /// When `cursor` is configured, a scrape only starts once the previous one has completed.
     #[serde(default = "default_interval")]
     #[serde_as(as = "serde_with::DurationSeconds<u64>")]
     #[serde(rename = "scrape_interval_secs")]
@@ -105,6 +117,37 @@ pub struct HttpClientConfig {
     #[serde(default = "default_http_method")]
     pub method: HttpMethod,
 
+    /// The body of the HTTP requests.
+    ///
+    /// The body is a [template][template], rendered before each request with the following fields:
+    ///
+    /// - `cursor`: the current cursor, if `cursor` is configured and a cursor is set.
+    /// - `next_token`: the token of the requested page, with the `next_token` pagination strategy.
+    /// - `offset`: the offset of the requested page.
+    ///
+    /// A request whose body fails to render is not sent, and the scrape is skipped.
+    ///
+    /// The `Content-Type` header of the requests can be set with the `headers` option.
+    ///
+    /// [template]: https://vector.dev/docs/reference/configuration/template-syntax/
+    #[configurable(metadata(docs::examples = "{\"since\": \"{{ cursor }}\"}"))]
+    pub body: Option<Template>,
+
+    #[configurable(derived)]
+    pub pagination: Option<PaginationConfig>,
+
+    #[configurable(derived)]
+    pub cursor: Option<CursorConfig>,
+
+    /// The directory used to persist the cursor.
+    ///
+    /// By default, the global `data_dir` option is used. Make sure the running user has write
+    /// permissions to this directory.
+    #[serde(default)]
+    #[configurable(metadata(docs::examples = "/var/lib/vector"))]
+    #[configurable(metadata(docs::human_name = "Data Directory"))]
+    pub data_dir: Option<PathBuf>,
+
     /// TLS configuration.
     #[configurable(derived)]
     pub tls: Option<TlsConfig>,
@@ -168,6 +211,10 @@ impl Default for HttpClientConfig {
             framing: default_framing_message_based(),
             headers: HashMap::new(),
             method: default_http_method(),
+            body: None,
+            pagination: None,
+            cursor: None,
+            data_dir: None,
             tls: None,
             auth: None,
             log_namespace: None,
@@ -198,10 +245,24 @@ impl SourceConfig for HttpClientConfig {
 
         let content_type = self.decoding.content_type(&self.framing).to_string();
 
-        // the only specific context needed is the codec decoding
+        let cursor = match &self.cursor {
+            Some(config) => {
+                let data_dir = cx
+                    .globals
+                    // source are only global, name can be used for subdir
+                    .resolve_and_make_data_subdir(self.data_dir.as_ref(), cx.key.id())?;
+                Some(Cursor::load(config.clone(), data_dir)?)
+            }
+            None => None,
+        };
+
         let context = HttpClientContext {
             decoder,
             log_namespace,
+            body: self.body.clone(),
+            pagination: self.pagination.clone(),
+            cursor,
+            scrape: ScrapeState::default(),
         };
 
         warn_if_interval_too_low(self.timeout, self.interval);
@@ -216,6 +277,8 @@ impl SourceConfig for HttpClientConfig {
             tls,
             proxy: cx.proxy.clone(),
             shutdown: cx.shutdown,
+            // each scrape starts from the cursor committed by the previous one
+            concurrent_scrapes: self.cursor.is_none(),
         };
 
         Ok(call(inputs, context, cx.out, self.method).boxed())
@@ -276,14 +339,49 @@ impl HttpClientConfig {
     }
 }
 
-/// Captures the configuration options required to decode the incoming requests into events.
+/// Captures the configuration options required to build the requests and decode the incoming
+/// responses into events, along with the state of the current scrape.
 #[derive(Clone)]
 pub struct HttpClientContext {
     pub decoder: Decoder,
     pub log_namespace: LogNamespace,
+    body: Option<Template>,
+    pagination: Option<PaginationConfig>,
+    cursor: Option<Cursor>,
+    scrape: ScrapeState,
+}
+
+/// The state of a single scrape.
+#[derive(Clone, Default)]
+struct ScrapeState {
+    /// The URL of the first page, once requested.
+    url: Option<Uri>,
+    /// The cursor the scrape started from.
+    cursor: Option<String>,
+    /// The cursor found in the events received so far.
+    next_cursor: Option<String>,
+    /// Whether the last page of the scrape was received.
+    complete: bool,
+    /// The notifier attached to the events of the scrape, when a cursor is configured, and the
+    /// receiver of their delivery status.
+    batch: Option<(BatchNotifier, Shared<BatchStatusReceiver>)>,
+    page: PageState,
 }
 
 impl HttpClientContext {
+    /// Builds the event the body template is rendered with.
+    fn template_event(&self) -> LogEvent {
+        let mut event = LogEvent::default();
+        if let Some(cursor) = &self.scrape.cursor {
+            event.insert("cursor", cursor.clone());
+        }
+        if let Some(next_token) = &self.scrape.page.next_token {
+            event.insert("next_token", next_token.clone());
+        }
+        event.insert("offset", self.scrape.page.offset as i64);
+        event
+    }
+
     /// Decode the events from the byte buffer
     fn decode_events(&mut self, buf: &mut BytesMut) -> Vec<Event> {
         let mut events = Vec::new();
@@ -310,13 +408,111 @@ impl HttpClientContext {
 impl HttpClientBuilder for HttpClientContext {
     type Context = HttpClientContext;
 
-    /// No additional context from request data is needed from this particular client.
+    /// Starts a new scrape from the current cursor.
     fn build(&self, _uri: &Uri) -> Self::Context {
-        self.clone()
+        let mut context = self.clone();
+        context.scrape = ScrapeState {
+            cursor: self.cursor.as_ref().and_then(Cursor::get),
+            batch: self.cursor.as_ref().map(|_| {
+                let (batch, receiver) = BatchNotifier::new_with_receiver();
+                (batch, receiver.shared())
+            }),
+            ..Default::default()
+        };
+        context
     }
 }
 
 impl http_client::HttpClientContext for HttpClientContext {
+    /// Adds the cursor and pagination parameters to the URL of the first page, and renders the
+    /// body template.
+    fn build_request(&mut self, url: &Uri) -> Option<(Uri, Bytes)> {
+        let url = match &self.scrape.url {
+            Some(_) => url.clone(),
+            None => {
+                let scrape_url = match (&self.cursor, &self.scrape.cursor) {
+                    (Some(cursor), Some(value)) => {
+                        with_optional_query_param(url, cursor.query_param(), value)
+                    }
+                    _ => url.clone(),
+                };
+                let page_url = match &self.pagination {
+                    Some(pagination) => pagination.first_page(&scrape_url),
+                    None => scrape_url.clone(),
+                };
+                self.scrape.url = Some(scrape_url);
+                page_url
+            }
+        };
+
+        let body = match &self.body {
+            Some(template) => match template.render_string(&self.template_event()) {
+                Ok(body) => Bytes::from(body),
+                Err(error) => {
+                    emit!(TemplateRenderingError {
+                        field: Some("body"),
+                        drop_event: false,
+                        error,
+                    });
+                    return None;
+                }
+            },
+            None => Bytes::new(),
+        };
+
+        Some((url, body))
+    }
+
+    /// Follows the pagination, and records the cursor found in the events.
+    fn next_page(
+        &mut self,
+        url: &Uri,
+        header: &Parts,
+        body: &Bytes,
+        events: &[Event],
+    ) -> Option<Uri> {
+        if let Some(value) = self.cursor.as_ref().and_then(|cursor| cursor.find(events)) {
+            self.scrape.next_cursor = Some(value);
+        }
+
+        let next = match (&self.pagination, &self.scrape.url) {
+            (Some(pagination), Some(scrape_url)) => pagination.next_page(
+                &mut self.scrape.page,
+                scrape_url,
+                url,
+                header,
+                body,
+                events.len(),
+            ),
+            _ => None,
+        };
+
+        self.scrape.complete = next.is_none();
+
+        next
+    }
+
+    /// Commits the cursor found in the events of a complete scrape, once they are all delivered.
+    fn on_scrape_end(&mut self) -> Option<BoxFuture<'static, ()>> {
+        // Dropping the notifier of the scrape lets the receiver resolve once its events are
+        // finalized.
+        let (_, receiver) = self.scrape.batch.take()?;
+        let cursor = self.cursor.clone()?;
+        if !self.scrape.complete {
+            return None;
+        }
+        let value = self.scrape.next_cursor.take()?;
+
+        Some(
+            async move {
+                if receiver.await == BatchStatus::Delivered {
+                    cursor.commit(value);
+                }
+            }
+            .boxed(),
+        )
+    }
+
     /// Decodes the HTTP response body into events per the decoder configured.
     fn on_response(&mut self, _url: &Uri, _header: &Parts, body: &Bytes) -> Option<Vec<Event>> {
         // get the body into a byte array
@@ -333,6 +529,9 @@ impl http_client::HttpClientContext for HttpClientContext {
         let now = Utc::now();
 
         for event in events {
+            if let Some((batch, _)) = &self.scrape.batch {
+                event.add_batch_notifier(batch.clone());
+            }
             match event {
                 Event::Log(ref mut log) => {
                     self.log_namespace.insert_standard_vector_source_metadata(
@@ -0,0 +1,156 @@
+//! Checkpointed cursor of the `http_client` source.
+
+use std::{
+    fs, io,
+    path::PathBuf,
+    sync::{Arc, Mutex},
+};
+
+use lookup::{lookup_v2::ConfigValuePath, PathPrefix};
+use vector_config::configurable_component;
+use vector_core::event::Event;
+
+use crate::internal_events::HttpClientCursorCheckpointError;
+
+const CHECKPOINT_FILENAME: &str = "cursor.txt";
+
+/// Cursor configuration.
+///
+/// The cursor marks how far the previous scrapes went, so that the next one only requests newer
+/// data. It is taken from the events received, and checkpointed under the data directory so that
+/// restarts resume from it rather than fetch everything again.
+#[configurable_component]
+#[derive(Clone, Debug)]
+pub struct CursorConfig {
+    /// The field of the events that holds the cursor.
+    ///
+    /// Once all the pages of a scrape are received, and all of their events are delivered, the
+    /// value of this field in the last event received becomes the cursor of the next scrape. The
+    /// cursor is left unchanged if any request of the scrape fails, if any event isn't delivered,
+    /// or if no event has this field.
+    #[configurable(metadata(docs::examples = "timestamp"))]
+    #[configurable(metadata(docs::examples = "id"))]
+    pub field: ConfigValuePath,
+
+    /// The query parameter to send the cursor in.
+    ///
+    /// When not set, the cursor is only available to the `body` template, as the `cursor` field.
+    #[configurable(metadata(docs::examples = "since"))]
+    pub query_param: Option<String>,
+
+    /// The cursor used until one is taken from the events.
+    ///
+    /// When not set, the first scrape is sent without a cursor.
+    #[configurable(metadata(docs::examples = "2023-01-01T00:00:00Z"))]
+    pub initial_value: Option<String>,
+}
+
+/// The current cursor of a source, shared by all of its scrapes.
+#[derive(Clone, Debug)]
+pub(super) struct Cursor {
+    config: CursorConfig,
+    filename: PathBuf,
+    value: Arc<Mutex<Option<String>>>,
+}
+
+impl Cursor {
+    /// Loads the checkpointed cursor from the data directory, if any.
+    pub(super) fn load(config: CursorConfig, data_dir: PathBuf) -> Result<Self, io::Error> {
+        let filename = data_dir.join(CHECKPOINT_FILENAME);
+        let value = match fs::read_to_string(&filename) {
+            Ok(text) => text.lines().next().map(str::to_owned),
+            Err(error) if error.kind() == io::ErrorKind::NotFound => None,
+            Err(error) => return Err(error),
+        }
+        .or_else(|| config.initial_value.clone());
+
+        Ok(Self {
+            config,
+            filename,
+            value: Arc::new(Mutex::new(value)),
+        })
+    }
+
+    pub(super) fn query_param(&self) -> Option<&String> {
+        self.config.query_param.as_ref()
+    }
+
+    /// Returns the current cursor.
+    pub(super) fn get(&self) -> Option<String> {
+        self.value.lock().expect("cursor lock poisoned").clone()
+    }
+
+    /// Returns the cursor found in the last event holding the cursor field, if any.
+    pub(super) fn find(&self, events: &[Event]) -> Option<String> {
+        events.iter().rev().find_map(|event| match event {
+            Event::Log(log) => log
+                .get((PathPrefix::Event, &self.config.field.0))
+                .map(|value| value.to_string_lossy().into_owned()),
+            _ => None,
+        })
+    }
+
+    /// Sets the cursor, and checkpoints it.
+    ///
+    /// The new cursor is kept even if the checkpoint fails, so that it is only lost on restart.
+    pub(super) fn commit(&self, value: String) {
+        if let Err(error) = self.checkpoint(&value) {
+            emit!(HttpClientCursorCheckpointError {
+                error,
+                filename: self.filename.to_string_lossy().into_owned(),
+            });
+        }
+        *self.value.lock().expect("cursor lock poisoned") = Some(value);
+    }
+
+    fn checkpoint(&self, value: &str) -> Result<(), io::Error> {
+        // Write to a temporary file first, so that a crash can't leave a truncated checkpoint.
+        let tmp_filename = self.filename.with_extension("tmp");
+        fs::write(&tmp_filename, format!("{}\n", value))?;
+        fs::rename(&tmp_filename, &self.filename)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use lookup::lookup_v2::parse_value_path;
+    use vector_core::event::LogEvent;
+
+    use super::*;
+
+    fn config(initial_value: Option<&str>) -> CursorConfig {
+        CursorConfig {
+            field: ConfigValuePath(parse_value_path("id").unwrap()),
+            query_param: None,
+            initial_value: initial_value.map(str::to_owned),
+        }
+    }
+
+    #[test]
+    fn find_in_last_event() {
+        let data_dir = tempfile::tempdir().unwrap();
+        let cursor = Cursor::load(config(None), data_dir.path().to_path_buf()).unwrap();
+
+        let events = vec![
+            Event::Log(LogEvent::from_iter([("id", 1_i64)])),
+            Event::Log(LogEvent::from_iter([("id", 2_i64)])),
+            Event::Log(LogEvent::from("no cursor")),
+        ];
+        assert_eq!(cursor.find(&events), Some("2".to_string()));
+        assert_eq!(cursor.find(&events[2..]), None);
+    }
+
+    #[test]
+    fn commit_persists_cursor() {
+        let data_dir = tempfile::tempdir().unwrap();
+
+        let cursor = Cursor::load(config(Some("0")), data_dir.path().to_path_buf()).unwrap();
+        assert_eq!(cursor.get(), Some("0".to_string()));
+
+        cursor.commit("42".to_string());
+        assert_eq!(cursor.get(), Some("42".to_string()));
+
+        let cursor = Cursor::load(config(Some("0")), data_dir.path().to_path_buf()).unwrap();
+        assert_eq!(cursor.get(), Some("42".to_string()));
+    }
+}
@@ -59,6 +59,10 @@ async fn invalid_endpoint() {
         framing: default_framing_message_based(),
         headers: HashMap::new(),
         method: HttpMethod::Get,
+        body: None,
+        pagination: None,
+        cursor: None,
+        data_dir: None,
         auth: None,
         tls: None,
         log_namespace: None,
@@ -78,6 +82,10 @@ async fn collected_logs_bytes() {
         framing: default_framing_message_based(),
         headers: HashMap::new(),
         method: HttpMethod::Get,
+        body: None,
+        pagination: None,
+        cursor: None,
+        data_dir: None,
         auth: None,
         tls: None,
         log_namespace: None,
@@ -103,6 +111,10 @@ async fn collected_logs_json() {
         framing: default_framing_message_based(),
         headers: HashMap::new(),
         method: HttpMethod::Get,
+        body: None,
+        pagination: None,
+        cursor: None,
+        data_dir: None,
         auth: None,
         tls: None,
         log_namespace: None,
@@ -128,6 +140,10 @@ async fn collected_metrics_native_json() {
         framing: default_framing_message_based(),
         headers: HashMap::new(),
         method: HttpMethod::Get,
+        body: None,
+        pagination: None,
+        cursor: None,
+        data_dir: None,
         auth: None,
         tls: None,
         log_namespace: None,
@@ -158,6 +174,10 @@ async fn collected_trace_native_json() {
         framing: default_framing_message_based(),
         headers: HashMap::new(),
         method: HttpMethod::Get,
+        body: None,
+        pagination: None,
+        cursor: None,
+        data_dir: None,
         auth: None,
         tls: None,
         log_namespace: None,
@@ -183,6 +203,10 @@ async fn unauthorized_no_auth() {
         framing: default_framing_message_based(),
         headers: HashMap::new(),
         method: HttpMethod::Get,
+        body: None,
+        pagination: None,
+        cursor: None,
+        data_dir: None,
         auth: None,
         tls: None,
         log_namespace: None,
@@ -202,6 +226,10 @@ async fn unauthorized_wrong_auth() {
         framing: default_framing_message_based(),
         headers: HashMap::new(),
         method: HttpMethod::Get,
+        body: None,
+        pagination: None,
+        cursor: None,
+        data_dir: None,
         tls: None,
         auth: Some(Auth::Basic {
             user: "white_rabbit".to_string(),
@@ -224,6 +252,10 @@ async fn authorized() {
         framing: default_framing_message_based(),
         headers: HashMap::new(),
         method: HttpMethod::Get,
+        body: None,
+        pagination: None,
+        cursor: None,
+        data_dir: None,
         tls: None,
         auth: Some(Auth::Basic {
             user: "user".to_string(),
@@ -246,6 +278,10 @@ async fn tls_invalid_ca() {
         framing: default_framing_message_based(),
         headers: HashMap::new(),
         method: HttpMethod::Get,
+        body: None,
+        pagination: None,
+        cursor: None,
+        data_dir: None,
         tls: Some(TlsConfig {
             ca_file: Some("tests/data/http-client/certs/invalid-ca-cert.pem".into()),
             ..Default::default()
@@ -268,6 +304,10 @@ async fn tls_valid() {
         framing: default_framing_message_based(),
         headers: HashMap::new(),
         method: HttpMethod::Get,
+        body: None,
+        pagination: None,
+        cursor: None,
+        data_dir: None,
         tls: Some(TlsConfig {
             ca_file: Some(tls::TEST_PEM_CA_PATH.into()),
             ..Default::default()
@@ -291,6 +331,10 @@ async fn shutdown() {
         framing: default_framing_message_based(),
         headers: HashMap::new(),
         method: HttpMethod::Get,
+        body: None,
+        pagination: None,
+        cursor: None,
+        data_dir: None,
         tls: None,
         auth: None,
         log_namespace: None,
@@ -1,5 +1,9 @@
 #[cfg(feature = "sources-http_client")]
 pub mod client;
+#[cfg(feature = "sources-http_client")]
+mod cursor;
+#[cfg(feature = "sources-http_client")]
+mod pagination;
 
 #[cfg(test)]
 mod tests;
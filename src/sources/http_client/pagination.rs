@@ -0,0 +1,333 @@
+//! Pagination strategies of the `http_client` source.
+
+use std::{collections::HashMap, num::NonZeroUsize};
+
+use bytes::Bytes;
+use http::{header::LINK, response::Parts, Uri};
+use lookup::lookup_v2::ConfigValuePath;
+use vector_config::configurable_component;
+use vector_core::event::Value;
+
+use crate::sources::util::http_client::build_url;
+
+/// Pagination configuration.
+///
+/// Each scrape requests the following pages of the response, until the last one is reached.
+#[configurable_component]
+#[derive(Clone, Debug)]
+pub struct PaginationConfig {
+    #[serde(flatten)]
+    pub strategy: PaginationStrategy,
+
+    /// The maximum number of pages requested by a single scrape.
+    ///
+    /// Once reached, the scrape ends as if the last page was received.
+    #[serde(default = "default_max_pages")]
+    pub max_pages: usize,
+}
+
+const fn default_max_pages() -> usize {
+    100
+}
+
+/// Pagination strategy.
+#[configurable_component]
+#[derive(Clone, Debug)]
+#[serde(tag = "strategy", rename_all = "snake_case")]
+#[configurable(metadata(docs::enum_tag_description = "The pagination strategy to use."))]
+pub enum PaginationStrategy {
+    /// Follows the URL of the `next` relation of the [`Link`][link] header of the responses.
+    ///
+    /// Pagination stops at the first response without such a link.
+    ///
+    /// [link]: https://datatracker.ietf.org/doc/html/rfc8288
+    LinkHeader,
+
+    /// Reads a token from the body of the responses, and sends it along with the next request.
+    ///
+    /// Pagination stops at the first response without a token.
+    NextToken(NextTokenOptions),
+
+    /// Requests pages by offset and limit query parameters.
+    ///
+    /// The offset advances by the number of events decoded from each page, and pagination stops
+    /// at the first page with fewer events than the limit. Each record must therefore decode to its
+    /// own event, for example with the `json` codec on a top-level array, or with newline-delimited
+    /// framing. A response wrapping its records in an object, such as `{"data": [...]}`, decodes to
+    /// a single event, so only its first page is requested.
+    Offset(OffsetOptions),
+}
+
+/// Options for the `next_token` pagination strategy.
+#[configurable_component]
+#[derive(Clone, Debug)]
+pub struct NextTokenOptions {
+    /// The path of the token in the JSON body of the responses.
+    ///
+    /// A missing, empty, or `null` token means that the response is the last page.
+    #[configurable(metadata(docs::examples = "meta.next_cursor"))]
+    pub token_path: ConfigValuePath,
+
+    /// The query parameter to send the token in.
+    ///
+    /// When not set, the token is only available to the `body` template, as the `next_token` field.
+    #[configurable(metadata(docs::examples = "cursor"))]
+    pub query_param: Option<String>,
+}
+
+/// Options for the `offset` pagination strategy.
+#[configurable_component]
+#[derive(Clone, Debug)]
+pub struct OffsetOptions {
+    /// The number of events requested per page.
+    #[configurable(metadata(docs::examples = 100))]
+    pub limit: NonZeroUsize,
+
+    /// The query parameter to send the offset in.
+    #[serde(default = "default_offset_param")]
+    pub offset_param: String,
+
+    /// The query parameter to send the limit in.
+    #[serde(default = "default_limit_param")]
+    pub limit_param: String,
+}
+
+fn default_offset_param() -> String {
+    "offset".to_string()
+}
+
+fn default_limit_param() -> String {
+    "limit".to_string()
+}
+
+/// The pagination state of a single scrape.
+#[derive(Clone, Debug, Default)]
+pub(super) struct PageState {
+    /// The number of pages received so far.
+    pub(super) received: usize,
+    /// The token of the next page, for the `next_token` strategy.
+    pub(super) next_token: Option<String>,
+    /// The offset of the next page, for the `offset` strategy.
+    pub(super) offset: usize,
+}
+
+impl PaginationConfig {
+    /// Adds the pagination query parameters of the first page to the URL of a scrape.
+    pub(super) fn first_page(&self, url: &Uri) -> Uri {
+        match &self.strategy {
+            PaginationStrategy::Offset(options) => options.page_url(url, 0),
+            PaginationStrategy::LinkHeader | PaginationStrategy::NextToken(_) => url.clone(),
+        }
+    }
+
+    /// Records a received page, and returns the URL of the next one if there is any.
+    ///
+    /// `scrape_url` is the URL of the first page of the scrape, and `url` the one of the page
+    /// that was just received.
+    pub(super) fn next_page(
+        &self,
+        state: &mut PageState,
+        scrape_url: &Uri,
+        url: &Uri,
+        header: &Parts,
+        body: &Bytes,
+        event_count: usize,
+    ) -> Option<Uri> {
+        state.received += 1;
+        if state.received >= self.max_pages {
+            return None;
+        }
+
+        match &self.strategy {
+            PaginationStrategy::LinkHeader => next_link(url, header),
+            PaginationStrategy::NextToken(options) => {
+                state.next_token = options.token(body);
+                let token = state.next_token.as_ref()?;
+                Some(match &options.query_param {
+                    Some(param) => with_query_param(scrape_url, param, token),
+                    None => scrape_url.clone(),
+                })
+            }
+            PaginationStrategy::Offset(options) => {
+                if event_count < options.limit.get() {
+                    return None;
+                }
+                state.offset += event_count;
+                Some(options.page_url(scrape_url, state.offset))
+            }
+        }
+    }
+}
+
+impl NextTokenOptions {
+    fn token(&self, body: &Bytes) -> Option<String> {
+        let body = serde_json::from_slice::<Value>(body).ok()?;
+        match body.get(&self.token_path.0)? {
+            Value::Null => None,
+            token => Some(token.to_string_lossy().into_owned()).filter(|token| !token.is_empty()),
+        }
+    }
+}
+
+impl OffsetOptions {
+    fn page_url(&self, url: &Uri, offset: usize) -> Uri {
+        let query = HashMap::from([
+            (self.offset_param.clone(), vec![offset.to_string()]),
+            (self.limit_param.clone(), vec![self.limit.to_string()]),
+        ]);
+        build_url(url, &query)
+    }
+}
+
+fn with_query_param(url: &Uri, param: &str, value: &str) -> Uri {
+    build_url(
+        url,
+        &HashMap::from([(param.to_string(), vec![value.to_string()])]),
+    )
+}
+
+/// Adds a query parameter to a URL, if set.
+pub(super) fn with_optional_query_param(url: &Uri, param: Option<&String>, value: &str) -> Uri {
+    match param {
+        Some(param) => with_query_param(url, param, value),
+        None => url.clone(),
+    }
+}
+
+/// Finds the target of the `next` relation in the `Link` headers of a response, resolved
+/// against the URL of the request.
+fn next_link(url: &Uri, header: &Parts) -> Option<Uri> {
+    let target = header
+        .headers
+        .get_all(LINK)
+        .iter()
+        .filter_map(|value| value.to_str().ok())
+        .flat_map(|value| value.split(','))
+        .find_map(|link| {
+            let mut params = link.split(';');
+            let target = params.next()?.trim().strip_prefix('<')?.strip_suffix('>')?;
+            params
+                .filter_map(|param| param.split_once('='))
+                .any(|(name, value)| {
+                    name.trim().eq_ignore_ascii_case("rel")
+                        && value
+                            .trim()
+                            .trim_matches('"')
+                            .split_whitespace()
+                            .any(|rel| rel.eq_ignore_ascii_case("next"))
+                })
+                .then_some(target)
+        })?;
+
+    let base = url::Url::parse(&url.to_string()).ok()?;
+    base.join(target).ok()?.as_str().parse().ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use http::Response;
+    use lookup::lookup_v2::parse_value_path;
+
+    use super::*;
+
+    fn parts(link: Option<&str>) -> Parts {
+        let mut builder = Response::builder();
+        if let Some(link) = link {
+            builder = builder.header(LINK, link);
+        }
+        builder.body(()).unwrap().into_parts().0
+    }
+
+    fn config(strategy: PaginationStrategy) -> PaginationConfig {
+        PaginationConfig {
+            strategy,
+            max_pages: default_max_pages(),
+        }
+    }
+
+    #[test]
+    fn link_header_next_page() {
+        let config = config(PaginationStrategy::LinkHeader);
+        let url = Uri::from_static("http://localhost/logs?page=1");
+        let mut state = PageState::default();
+
+        let header = parts(Some(
+            r#"<http://localhost/logs?page=1>; rel="prev", </logs?page=3>; rel="next""#,
+        ));
+        let next = config.next_page(&mut state, &url, &url, &header, &Bytes::new(), 1);
+        assert_eq!(next, Some(Uri::from_static("http://localhost/logs?page=3")));
+
+        let header = parts(Some(r#"<http://localhost/logs?page=1>; rel="prev""#));
+        let next = config.next_page(&mut state, &url, &url, &header, &Bytes::new(), 1);
+        assert_eq!(next, None);
+    }
+
+    #[test]
+    fn next_token_next_page() {
+        let config = config(PaginationStrategy::NextToken(NextTokenOptions {
+            token_path: ConfigValuePath(parse_value_path("meta.next").unwrap()),
+            query_param: Some("token".to_string()),
+        }));
+        let url = Uri::from_static("http://localhost/logs?since=1");
+        let mut state = PageState::default();
+
+        let body = Bytes::from(r#"{"data":[],"meta":{"next":"abc"}}"#);
+        let next = config.next_page(&mut state, &url, &url, &parts(None), &body, 1);
+        assert_eq!(
+            next,
+            Some(Uri::from_static("http://localhost/logs?since=1&token=abc"))
+        );
+        assert_eq!(state.next_token.as_deref(), Some("abc"));
+
+        let body = Bytes::from(r#"{"data":[],"meta":{"next":null}}"#);
+        let next = config.next_page(&mut state, &url, &url, &parts(None), &body, 1);
+        assert_eq!(next, None);
+    }
+
+    #[test]
+    fn offset_next_page() {
+        let config = config(PaginationStrategy::Offset(OffsetOptions {
+            limit: NonZeroUsize::new(2).unwrap(),
+            offset_param: default_offset_param(),
+            limit_param: default_limit_param(),
+        }));
+        let url = Uri::from_static("http://localhost/logs");
+        let mut state = PageState::default();
+
+        let first = config.first_page(&url);
+        let query = first.query().unwrap();
+        assert!(query.contains("offset=0") && query.contains("limit=2"));
+
+        let next = config
+            .next_page(&mut state, &url, &first, &parts(None), &Bytes::new(), 2)
+            .unwrap();
+        let query = next.query().unwrap();
+        assert!(query.contains("offset=2") && query.contains("limit=2"));
+
+        let next = config.next_page(&mut state, &url, &next, &parts(None), &Bytes::new(), 1);
+        assert_eq!(next, None);
+    }
+
+    #[test]
+    fn offset_rejects_zero_limit() {
+        assert!(toml::from_str::<PaginationConfig>(
+            r#"
+            strategy = "offset"
+            limit = 0
+            "#
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn max_pages() {
+        let mut config = config(PaginationStrategy::LinkHeader);
+        config.max_pages = 1;
+        let url = Uri::from_static("http://localhost/logs");
+        let mut state = PageState::default();
+
+        let header = parts(Some("</logs?page=2>; rel=next"));
+        let next = config.next_page(&mut state, &url, &url, &header, &Bytes::new(), 1);
+        assert_eq!(next, None);
+    }
+}
@@ -1,17 +1,31 @@
 use codecs::CharacterDelimitedDecoderConfig;
+use lookup::lookup_v2::{parse_value_path, ConfigValuePath};
 use std::collections::HashMap;
+use std::sync::{
+    atomic::{AtomicUsize, Ordering},
+    Arc,
+};
 use tokio::time::Duration;
-use warp::{http::HeaderMap, Filter};
+use warp::{http::HeaderMap, Filter, Reply};
 
 use crate::sources::util::http::HttpMethod;
 use crate::{serde::default_decoding, serde::default_framing_message_based};
 use codecs::decoding::{CharacterDelimitedDecoderOptions, DeserializerConfig, FramingConfig};
-use vector_core::event::Event;
+use vector_core::event::{Event, EventStatus};
 
-use super::HttpClientConfig;
-use crate::test_util::{
-    components::{run_and_assert_source_compliance, HTTP_PULL_SOURCE_TAGS},
-    next_addr, test_generate_config, wait_for_tcp,
+use super::{
+    cursor::CursorConfig,
+    pagination::{PaginationConfig, PaginationStrategy},
+    HttpClientConfig,
+};
+use crate::{
+    config::{SourceConfig, SourceContext},
+    test_util::{
+        collect_n,
+        components::{run_and_assert_source_compliance, HTTP_PULL_SOURCE_TAGS},
+        next_addr, test_generate_config, wait_for_tcp,
+    },
+    SourceSender,
 };
 
 pub(crate) const INTERVAL: Duration = Duration::from_secs(1);
@@ -55,6 +69,10 @@ async fn bytes_decoding() {
         framing: default_framing_message_based(),
         headers: HashMap::new(),
         method: HttpMethod::Get,
+        body: None,
+        pagination: None,
+        cursor: None,
+        data_dir: None,
         tls: None,
         auth: None,
         log_namespace: None,
@@ -84,6 +102,10 @@ async fn json_decoding_newline_delimited() {
         framing: FramingConfig::NewlineDelimited(Default::default()),
         headers: HashMap::new(),
         method: HttpMethod::Get,
+        body: None,
+        pagination: None,
+        cursor: None,
+        data_dir: None,
         tls: None,
         auth: None,
         log_namespace: None,
@@ -118,6 +140,10 @@ async fn json_decoding_character_delimited() {
         }),
         headers: HashMap::new(),
         method: HttpMethod::Get,
+        body: None,
+        pagination: None,
+        cursor: None,
+        data_dir: None,
         tls: None,
         auth: None,
         log_namespace: None,
@@ -152,6 +178,10 @@ async fn request_query_applied() {
         framing: default_framing_message_based(),
         headers: HashMap::new(),
         method: HttpMethod::Get,
+        body: None,
+        pagination: None,
+        cursor: None,
+        data_dir: None,
         tls: None,
         auth: None,
         log_namespace: None,
@@ -218,6 +248,10 @@ async fn headers_applied() {
             vec!["bazz".to_string(), "bizz".to_string()],
         )]),
         method: HttpMethod::Get,
+        body: None,
+        pagination: None,
+        cursor: None,
+        data_dir: None,
         auth: None,
         tls: None,
         log_namespace: None,
@@ -247,9 +281,157 @@ async fn accept_header_override() {
         framing: default_framing_message_based(),
         headers: HashMap::from([("ACCEPT".to_string(), vec!["application/json".to_string()])]),
         method: HttpMethod::Get,
+        body: None,
+        pagination: None,
+        cursor: None,
+        data_dir: None,
         auth: None,
         tls: None,
         log_namespace: None,
     })
     .await;
 }
+
+/// Pages linked by the `Link` header should all be requested by a scrape.
+#[tokio::test]
+async fn link_header_pagination() {
+    let in_addr = next_addr();
+
+    let dummy_endpoint = warp::path!("endpoint")
+        .and(warp::query::<HashMap<String, usize>>())
+        .map(|query: HashMap<String, usize>| {
+            let page = query.get("page").copied().unwrap_or(1);
+            let reply = warp::reply::json(&serde_json::json!({ "page": page }));
+            if page < 3 {
+                let link = format!(r#"</endpoint?page={}>; rel="next""#, page + 1);
+                warp::reply::with_header(reply, "Link", link).into_response()
+            } else {
+                reply.into_response()
+            }
+        });
+
+    tokio::spawn(warp::serve(dummy_endpoint).run(in_addr));
+    wait_for_tcp(in_addr).await;
+
+    let events = run_compliance(HttpClientConfig {
+        endpoint: format!("http://{}/endpoint", in_addr),
+        interval: Duration::from_secs(5),
+        decoding: DeserializerConfig::Json(Default::default()),
+        pagination: Some(PaginationConfig {
+            strategy: PaginationStrategy::LinkHeader,
+            max_pages: 10,
+        }),
+        ..Default::default()
+    })
+    .await;
+
+    let pages: Vec<_> = events
+        .into_iter()
+        .map(|event| event.into_log().get("page").unwrap().as_integer().unwrap())
+        .collect();
+    assert_eq!(pages, vec![1, 2, 3]);
+}
+
+/// The body template should be rendered with the cursor taken from the previous scrape.
+#[tokio::test]
+async fn body_with_cursor() {
+    let in_addr = next_addr();
+    let data_dir = tempfile::tempdir().unwrap();
+    let count = Arc::new(AtomicUsize::new(0));
+
+    let dummy_endpoint = warp::path!("endpoint")
+        .and(warp::post())
+        .and(warp::body::bytes())
+        .map(move |body: bytes::Bytes| {
+            let body: serde_json::Value = serde_json::from_slice(&body).unwrap();
+            let id = count.fetch_add(1, Ordering::SeqCst) + 1;
+            warp::reply::json(&serde_json::json!({ "id": id, "since": body["since"] }))
+        });
+
+    tokio::spawn(warp::serve(dummy_endpoint).run(in_addr));
+    wait_for_tcp(in_addr).await;
+
+    let events = run_compliance(HttpClientConfig {
+        endpoint: format!("http://{}/endpoint", in_addr),
+        interval: INTERVAL,
+        timeout: TIMEOUT,
+        decoding: DeserializerConfig::Json(Default::default()),
+        method: HttpMethod::Post,
+        body: Some(r#"{"since": "{{ cursor }}"}"#.try_into().unwrap()),
+        cursor: Some(CursorConfig {
+            field: ConfigValuePath(parse_value_path("id").unwrap()),
+            query_param: None,
+            initial_value: Some("0".to_string()),
+        }),
+        data_dir: Some(data_dir.path().to_path_buf()),
+        ..Default::default()
+    })
+    .await;
+
+    for event in events {
+        let log = event.into_log();
+        let id = log.get("id").unwrap().as_integer().unwrap();
+        let since = log.get("since").unwrap().to_string_lossy();
+        assert_eq!(since, (id - 1).to_string());
+    }
+}
+
+/// Runs the source with its events finalized with `status`, collecting the first `count` ones.
+async fn collect_with_status(
+    config: HttpClientConfig,
+    status: EventStatus,
+    count: usize,
+) -> Vec<Event> {
+    let (tx, rx) = SourceSender::new_test_finalize(status);
+    let source = config
+        .build(SourceContext::new_test(tx, None))
+        .await
+        .unwrap();
+    tokio::spawn(source);
+    collect_n(rx, count).await
+}
+
+/// The cursor should only be committed once the events of the scrape are delivered.
+#[tokio::test]
+async fn cursor_committed_once_delivered() {
+    for (status, delivered) in [
+        (EventStatus::Delivered, true),
+        (EventStatus::Rejected, false),
+    ] {
+        let in_addr = next_addr();
+        let data_dir = tempfile::tempdir().unwrap();
+        let count = Arc::new(AtomicUsize::new(0));
+
+        let dummy_endpoint = warp::path!("endpoint")
+            .and(warp::query::<HashMap<String, String>>())
+            .map(move |query: HashMap<String, String>| {
+                let id = count.fetch_add(1, Ordering::SeqCst) + 1;
+                warp::reply::json(&serde_json::json!({ "id": id, "since": query["since"] }))
+            });
+
+        tokio::spawn(warp::serve(dummy_endpoint).run(in_addr));
+        wait_for_tcp(in_addr).await;
+
+        let config = HttpClientConfig {
+            endpoint: format!("http://{}/endpoint", in_addr),
+            interval: INTERVAL,
+            timeout: TIMEOUT,
+            decoding: DeserializerConfig::Json(Default::default()),
+            cursor: Some(CursorConfig {
+                field: ConfigValuePath(parse_value_path("id").unwrap()),
+                query_param: Some("since".to_string()),
+                initial_value: Some("0".to_string()),
+            }),
+            data_dir: Some(data_dir.path().to_path_buf()),
+            ..Default::default()
+        };
+
+        for event in collect_with_status(config, status, 3).await {
+            let log = event.into_log();
+            let id = log.get("id").unwrap().as_integer().unwrap();
+            let since = log.get("since").unwrap().to_string_lossy();
+            let expected = if delivered { id - 1 } else { 0 };
+            assert_eq!(since, expected.to_string());
+        }
+    }
+}
@@ -34,6 +34,7 @@ use vrl::value::{kind::Collection, Kind};
 
 use crate::{
     codecs::{Decoder, DecodingConfig},
+    common::schema_registry::{SchemaRegistryConfig, SchemaRegistryDecoders},
     config::{
         log_schema, LogSchema, SourceAcknowledgementsConfig, SourceConfig, SourceContext,
         SourceOutput,
@@ -41,11 +42,12 @@ use crate::{
     event::{BatchNotifier, BatchStatus, Event, Value},
     internal_events::{
         KafkaBytesReceived, KafkaEventsReceived, KafkaOffsetUpdateError, KafkaReadError,
-        StreamClosedError,
+        KafkaSchemaRegistryError, StreamClosedError,
     },
     kafka,
     serde::{bool_or_struct, default_decoding, default_framing_message_based},
     shutdown::ShutdownSignal,
+    sinks::util::retries::ExponentialBackoff,
     SourceSender,
 };
 
@@ -202,6 +204,18 @@ pub struct KafkaSourceConfig {
     #[derivative(Default(value = "default_decoding()"))]
     decoding: DeserializerConfig,
 
+    /// The schema registry the messages are produced with.
+    ///
+    /// When set, the messages must use the Confluent wire format, where the data is prefixed with
+    /// the ID of its schema in the registry. With the `avro` codec, the data is decoded with the
+    /// schema it was written with, resolved to the configured schema. With the `protobuf` codec,
+    /// it's decoded with the configured message type. Other codecs are not supported.
+    ///
+    /// Failing to reach the registry pauses the consumption, retrying with a backoff. Messages that
+    /// can't be decoded, such as those without the header or with an unknown schema, are skipped.
+    #[configurable(metadata(docs::advanced))]
+    schema_registry: Option<SchemaRegistryConfig>,
+
     #[configurable(derived)]
     #[serde(default, deserialize_with = "bool_or_struct")]
     acknowledgements: SourceAcknowledgementsConfig,
@@ -220,6 +234,14 @@ impl KafkaSourceConfig {
     fn keys(&self) -> Keys {
         Keys::from(log_schema(), self)
     }
+
+    /// Whether the consumer commits the stored offsets, which `librdkafka_options` can disable.
+    fn auto_commit(&self) -> bool {
+        self.librdkafka_options
+            .as_ref()
+            .and_then(|options| options.get("enable.auto.commit"))
+            .map_or(true, |value| value != "false")
+    }
 }
 
 const fn default_session_timeout_ms() -> Duration {
@@ -294,15 +316,23 @@ impl SourceConfig for KafkaSourceConfig {
         let log_namespace = cx.log_namespace(self.log_namespace);
 
         let consumer = create_consumer(self)?;
-        let decoder =
-            DecodingConfig::new(self.framing.clone(), self.decoding.clone(), log_namespace)
-                .build()?;
+        let decoding =
+            DecodingConfig::new(self.framing.clone(), self.decoding.clone(), log_namespace);
+        let decoder = decoding.build()?;
+        let schema_registry = match &self.schema_registry {
+            Some(schema_registry) => Some(SchemaRegistryDecoders::new(
+                schema_registry.build(&cx.proxy)?,
+                &decoding,
+            )?),
+            None => None,
+        };
         let acknowledgements = cx.do_acknowledgements(self.acknowledgements);
 
         Ok(Box::pin(kafka_source(
             self.clone(),
             consumer,
             decoder,
+            schema_registry,
             cx.shutdown,
             cx.out,
             acknowledgements,
@@ -372,10 +402,12 @@ impl SourceConfig for KafkaSourceConfig {
     }
 }
 
+#[allow(clippy::too_many_arguments)]
 async fn kafka_source(
     config: KafkaSourceConfig,
     consumer: StreamConsumer<CustomContext>,
     decoder: Decoder,
+    schema_registry: Option<SchemaRegistryDecoders>,
     mut shutdown: ShutdownSignal,
     mut out: SourceSender,
     acknowledgements: bool,
@@ -419,60 +451,116 @@ async fn kafka_source(
                         partition: msg.partition(),
                     });
 
-                    parse_message(msg, decoder.clone(), config.keys(), &finalizer, &mut out, &consumer, log_namespace).await;
+                    parse_message(msg, decoder.clone(), &schema_registry, config.keys(), &finalizer, &mut out, &consumer, &shutdown, log_namespace).await;
                 }
             },
         }
     }
 
     // Since commits are async internally, we try one last sync commit inside the interval
-    // in case there have been acks.
-    if let Ok(current_assignment) = consumer.assignment() {
-        // not logging on error because it will error if there are no offsets stored for a partition,
-        // and this is best-effort cleanup anyway
-        _ = consumer.commit(&current_assignment, CommitMode::Sync);
+    // in case there have been acks. This is skipped when the offsets are committed by someone
+    // else, such as a transactional `kafka` sink.
+    if config.auto_commit() {
+        if let Ok(current_assignment) = consumer.assignment() {
+            // not logging on error because it will error if there are no offsets stored for a partition,
+            // and this is best-effort cleanup anyway
+            _ = consumer.commit(&current_assignment, CommitMode::Sync);
+        }
     }
     Ok(())
 }
 
+#[allow(clippy::too_many_arguments)]
 async fn parse_message(
     msg: BorrowedMessage<'_>,
     decoder: Decoder,
+    schema_registry: &Option<SchemaRegistryDecoders>,
     keys: Keys<'_>,
     finalizer: &Option<Arc<OrderedFinalizer<FinalizerEntry>>>,
     out: &mut SourceSender,
     consumer: &Arc<StreamConsumer<CustomContext>>,
+    shutdown: &ShutdownSignal,
     log_namespace: LogNamespace,
 ) {
-    if let Some((count, mut stream)) = parse_stream(&msg, decoder, keys, log_namespace) {
-        match finalizer {
-            Some(finalizer) => {
-                let (batch, receiver) = BatchNotifier::new_with_receiver();
-                let mut stream = stream.map(|event| event.with_batch_notifier(&batch));
-                match out.send_event_stream(&mut stream).await {
-                    Err(_) => {
-                        emit!(StreamClosedError { count });
-                    }
-                    Ok(_) => {
-                        // Drop stream to avoid borrowing `msg`: "[...] borrow might be used
-                        // here, when `stream` is dropped and runs the destructor [...]".
-                        drop(stream);
-                        finalizer.add(msg.into(), receiver);
-                    }
-                }
+    let Some(payload) = msg.payload() else {
+        return; // skip messages with empty payload
+    };
+    let (decoder, payload) = match schema_registry {
+        Some(schema_registry) => {
+            match resolve_decoder(schema_registry, &msg, payload, shutdown).await {
+                Some(resolved) => resolved,
+                None => return,
             }
-            None => match out.send_event_stream(&mut stream).await {
+        }
+        None => (decoder, payload),
+    };
+
+    let (count, mut stream) = parse_stream(&msg, payload, decoder, keys, log_namespace);
+    match finalizer {
+        Some(finalizer) => {
+            let (batch, receiver) = BatchNotifier::new_with_receiver();
+            let mut stream = stream.map(|event| event.with_batch_notifier(&batch));
+            match out.send_event_stream(&mut stream).await {
                 Err(_) => {
                     emit!(StreamClosedError { count });
                 }
                 Ok(_) => {
-                    if let Err(error) =
-                        consumer.store_offset(msg.topic(), msg.partition(), msg.offset())
-                    {
-                        emit!(KafkaOffsetUpdateError { error });
-                    }
+                    // Drop stream to avoid borrowing `msg`: "[...] borrow might be used
+                    // here, when `stream` is dropped and runs the destructor [...]".
+                    drop(stream);
+                    finalizer.add(msg.into(), receiver);
                 }
-            },
+            }
+        }
+        None => match out.send_event_stream(&mut stream).await {
+            Err(_) => {
+                emit!(StreamClosedError { count });
+            }
+            Ok(_) => {
+                if let Err(error) =
+                    consumer.store_offset(msg.topic(), msg.partition(), msg.offset())
+                {
+                    emit!(KafkaOffsetUpdateError { error });
+                }
+            }
+        },
+    }
+}
+
+/// Resolves the decoder of a message from the schema registry.
+///
+/// Errors reaching the registry, and its server errors, are retried with a backoff until the
+/// source shuts down, pausing the consumption, as skipping the message would let the offsets of
+/// the following ones be committed past it. Only messages that can never be decoded, such as those
+/// without a header or whose schema the registry refuses to serve, are skipped.
+async fn resolve_decoder<'a>(
+    schema_registry: &SchemaRegistryDecoders,
+    msg: &BorrowedMessage<'_>,
+    payload: &'a [u8],
+    shutdown: &ShutdownSignal,
+) -> Option<(Decoder, &'a [u8])> {
+    let mut backoff = ExponentialBackoff::from_millis(2)
+        .factor(250)
+        .max_delay(Duration::from_secs(30));
+    loop {
+        match schema_registry.decoder(payload).await {
+            Ok(resolved) => return Some(resolved),
+            Err(error) => {
+                emit!(KafkaSchemaRegistryError {
+                    error: &error,
+                    topic: msg.topic(),
+                    partition: msg.partition(),
+                });
+                if error.is_permanent() {
+                    return None;
+                }
+            }
+        }
+
+        let delay = backoff.next().expect("backoff never ends");
+        tokio::select! {
+            _ = shutdown.clone() => return None,
+            _ = tokio::time::sleep(delay) => {}
         }
     }
 }
@@ -480,12 +568,11 @@ async fn parse_message(
 // Turn the received message into a stream of parsed events.
 fn parse_stream<'a>(
     msg: &BorrowedMessage<'a>,
+    payload: &[u8],
     decoder: Decoder,
     keys: Keys<'a>,
     log_namespace: LogNamespace,
-) -> Option<(usize, impl Stream<Item = Event> + 'a)> {
-    let payload = msg.payload()?; // skip messages with empty payload
-
+) -> (usize, impl Stream<Item = Event> + 'a) {
     let rmsg = ReceivedMessage::from(msg);
 
     let payload = Cursor::new(Bytes::copy_from_slice(payload));
@@ -518,7 +605,7 @@ fn parse_stream<'a>(
         }
     }
     .boxed();
-    Some((count, stream))
+    (count, stream)
 }
 
 #[derive(Clone, Debug)]
@@ -897,6 +984,18 @@ mod test {
         };
         assert!(create_consumer(&config).is_err());
     }
+
+    #[test]
+    fn auto_commit_disabled_by_librdkafka_options() {
+        let mut config = make_config("topic", "group", LogNamespace::Legacy);
+        assert!(config.auto_commit());
+
+        config.librdkafka_options = Some(HashMap::from([(
+            "enable.auto.commit".to_owned(),
+            "false".to_owned(),
+        )]));
+        assert!(!config.auto_commit());
+    }
 }
 
 #[cfg(feature = "kafka-integration-tests")]
@@ -1162,6 +1261,7 @@ mod integration_test {
             config,
             consumer,
             decoder,
+            None,
             shutdown,
             tx,
             acknowledgements,
@@ -0,0 +1,510 @@
+use std::time::Duration;
+
+use chrono::Utc;
+use codecs::decoding::{DeserializerConfig, FramingConfig, StreamDecodingError};
+use futures::StreamExt;
+use lookup::{lookup_v2::OptionalValuePath, owned_value_path};
+use rumqttc::{AsyncClient, Event as MqttEvent, EventLoop, MqttOptions, Packet, SubscribeFilter};
+use snafu::{ResultExt, Snafu};
+use tokio_util::codec::FramedRead;
+use vector_common::{
+    internal_event::{
+        error_stage, ByteSize, BytesReceived, CountByteSize, EventsReceived,
+        InternalEventHandle as _, Protocol,
+    },
+    sensitive_string::SensitiveString,
+};
+use vector_config::configurable_component;
+use vector_core::{
+    config::{LegacyKey, LogNamespace},
+    EstimatedJsonEncodedSizeOf,
+};
+use vrl::value::Kind;
+
+use crate::{
+    codecs::{Decoder, DecodingConfig},
+    config::{GenerateConfig, SourceConfig, SourceContext, SourceOutput},
+    event::Event,
+    internal_events::{MqttConnectionError, StreamClosedError},
+    mqtt::{
+        client_id, configure_options, default_clean_session, default_keep_alive, default_port,
+        MqttConfigError, MqttQoS,
+    },
+    serde::{default_decoding, default_framing_message_based},
+    shutdown::ShutdownSignal,
+    tls::TlsEnableableConfig,
+    SourceSender,
+};
+
+/// The amount of time to wait before reconnecting after the connection to the broker is lost.
+const RECONNECT_DELAY: Duration = Duration::from_secs(1);
+
+#[derive(Debug, Snafu)]
+enum BuildError {
+    #[snafu(display("{}", source))]
+    Config { source: MqttConfigError },
+    #[snafu(display("`topics` must contain at least one topic filter"))]
+    NoTopics,
+}
+
+/// Configuration for the `mqtt` source.
+#[configurable_component(source("mqtt", "Collect observability data from an MQTT broker."))]
+#[derive(Clone, Debug, Derivative)]
+#[derivative(Default)]
+#[serde(deny_unknown_fields)]
+pub struct MqttSourceConfig {
+    /// The host of the MQTT broker to connect to.
+    #[configurable(metadata(docs::examples = "mqtt.example.com"))]
+    #[configurable(metadata(docs::examples = "127.0.0.1"))]
+    host: String,
+
+    /// The port of the MQTT broker to connect to.
+    #[serde(default = "default_port")]
+    #[derivative(Default(value = "default_port()"))]
+    port: u16,
+
+    /// The username to authenticate with.
+    ///
+    /// Must be set together with `password`.
+    #[configurable(metadata(docs::examples = "vector"))]
+    user: Option<String>,
+
+    /// The password to authenticate with.
+    ///
+    /// Must be set together with `user`.
+    #[configurable(metadata(docs::examples = "${MQTT_PASSWORD}"))]
+    password: Option<SensitiveString>,
+
+    /// The client ID to connect with.
+    ///
+    /// Must be set when `clean_session` is disabled, as persistent sessions are tied to the client
+    /// ID. Otherwise, a random client ID is generated if not set.
+    #[configurable(metadata(docs::examples = "vector"))]
+    client_id: Option<String>,
+
+    /// The interval, in seconds, at which to send keep-alive messages to the broker.
+    #[serde(default = "default_keep_alive")]
+    #[derivative(Default(value = "default_keep_alive()"))]
+    #[configurable(metadata(docs::type_unit = "seconds"))]
+    keep_alive: u16,
+
+    /// Whether or not to start a clean session when connecting to the broker.
+    ///
+    /// When disabled, the session is persistent: the broker keeps the subscriptions, and messages
+    /// published with a QoS of `at_least_once` or `exactly_once`, while Vector is disconnected,
+    /// and delivers those messages when it reconnects.
+    #[serde(default = "default_clean_session")]
+    #[derivative(Default(value = "default_clean_session()"))]
+    clean_session: bool,
+
+    /// The [topic filters][mqtt_topics] to subscribe to.
+    ///
+    /// Topic filters can use the `+` wildcard to match a single level of the topic, and the `#`
+    /// wildcard to match any number of levels at the end of the topic.
+    ///
+    /// [mqtt_topics]: https://www.hivemq.com/blog/mqtt-essentials-part-5-mqtt-topics-best-practices/
+    #[configurable(metadata(docs::examples = "sensors/+/temperature"))]
+    #[configurable(metadata(docs::examples = "logs/#"))]
+    topics: Vec<String>,
+
+    #[configurable(derived)]
+    #[serde(default)]
+    qos: MqttQoS,
+
+    /// Overrides the name of the log field used to add the topic to each event.
+    ///
+    /// The value is the topic the message was published to.
+    ///
+    /// By default, `"topic"` is used.
+    #[serde(default = "default_topic_key")]
+    #[derivative(Default(value = "default_topic_key()"))]
+    #[configurable(metadata(docs::examples = "topic"))]
+    topic_key: OptionalValuePath,
+
+    #[configurable(derived)]
+    tls: Option<TlsEnableableConfig>,
+
+    #[configurable(derived)]
+    #[serde(default = "default_framing_message_based")]
+    #[derivative(Default(value = "default_framing_message_based()"))]
+    framing: FramingConfig,
+
+    #[configurable(derived)]
+    #[serde(default = "default_decoding")]
+    #[derivative(Default(value = "default_decoding()"))]
+    decoding: DeserializerConfig,
+
+    /// The namespace to use for logs. This overrides the global setting.
+    #[configurable(metadata(docs::hidden))]
+    #[serde(default)]
+    log_namespace: Option<bool>,
+}
+
+fn default_topic_key() -> OptionalValuePath {
+    OptionalValuePath::from(owned_value_path!("topic"))
+}
+
+impl GenerateConfig for MqttSourceConfig {
+    fn generate_config() -> toml::Value {
+        toml::from_str(
+            r#"
+            host = "127.0.0.1"
+            topics = ["vector"]"#,
+        )
+        .unwrap()
+    }
+}
+
+#[async_trait::async_trait]
+#[typetag::serde(name = "mqtt")]
+impl SourceConfig for MqttSourceConfig {
+    async fn build(&self, cx: SourceContext) -> crate::Result<super::Source> {
+        let log_namespace = cx.log_namespace(self.log_namespace);
+        let decoder =
+            DecodingConfig::new(self.framing.clone(), self.decoding.clone(), log_namespace)
+                .build()?;
+
+        let (client, eventloop) = self.connect()?;
+
+        Ok(Box::pin(mqtt_source(
+            self.clone(),
+            client,
+            eventloop,
+            decoder,
+            log_namespace,
+            cx.shutdown,
+            cx.out,
+        )))
+    }
+
+    fn outputs(&self, global_log_namespace: LogNamespace) -> Vec<SourceOutput> {
+        let log_namespace = global_log_namespace.merge(self.log_namespace);
+        let legacy_topic_key = self.topic_key.clone().path.map(LegacyKey::InsertIfEmpty);
+        let schema_definition = self
+            .decoding
+            .schema_definition(log_namespace)
+            .with_standard_vector_source_metadata()
+            .with_source_metadata(
+                MqttSourceConfig::NAME,
+                legacy_topic_key,
+                &owned_value_path!("topic"),
+                Kind::bytes(),
+                None,
+            );
+
+        vec![SourceOutput::new_logs(
+            self.decoding.output_type(),
+            schema_definition,
+        )]
+    }
+
+    fn can_acknowledge(&self) -> bool {
+        false
+    }
+}
+
+impl MqttSourceConfig {
+    /// Creates the client, which connects to the broker once its event loop is polled.
+    fn connect(&self) -> Result<(AsyncClient, EventLoop), BuildError> {
+        if self.topics.is_empty() {
+            return Err(BuildError::NoTopics);
+        }
+
+        let client_id = client_id(&self.client_id, self.clean_session).context(ConfigSnafu)?;
+        let mut options = MqttOptions::new(client_id, &self.host, self.port);
+        options.set_keep_alive(Duration::from_secs(self.keep_alive.into()));
+        options.set_clean_session(self.clean_session);
+        configure_options(&mut options, &self.user, &self.password, &self.tls)
+            .context(ConfigSnafu)?;
+
+        Ok(AsyncClient::new(options, self.topics.len().max(10)))
+    }
+
+    fn subscribe_filters(&self) -> Vec<SubscribeFilter> {
+        self.topics
+            .iter()
+            .map(|topic| SubscribeFilter::new(topic.clone(), self.qos.into()))
+            .collect()
+    }
+}
+
+async fn mqtt_source(
+    config: MqttSourceConfig,
+    // Take ownership of the client so that the connection doesn't get closed.
+    client: AsyncClient,
+    mut eventloop: EventLoop,
+    decoder: Decoder,
+    log_namespace: LogNamespace,
+    mut shutdown: ShutdownSignal,
+    mut out: SourceSender,
+) -> Result<(), ()> {
+    let events_received = register!(EventsReceived);
+    let bytes_received = register!(BytesReceived::from(Protocol::TCP));
+
+    loop {
+        let notification = tokio::select! {
+            _ = &mut shutdown => break,
+            notification = eventloop.poll() => notification,
+        };
+
+        let publish = match notification {
+            // Subscriptions are only kept by the broker for persistent sessions, so they need to be
+            // made again whenever a new session is started.
+            Ok(MqttEvent::Incoming(Packet::ConnAck(ack))) => {
+                if !ack.session_present {
+                    if let Err(error) = client.subscribe_many(config.subscribe_filters()).await {
+                        error!(message = "Failed to subscribe to topics.", %error);
+                        return Err(());
+                    }
+                }
+                continue;
+            }
+            Ok(MqttEvent::Incoming(Packet::Publish(publish))) => publish,
+            Ok(_) => continue,
+            Err(error) => {
+                emit!(MqttConnectionError {
+                    error,
+                    stage: error_stage::RECEIVING,
+                });
+                // Polling the event loop again reconnects to the broker.
+                tokio::time::sleep(RECONNECT_DELAY).await;
+                continue;
+            }
+        };
+
+        bytes_received.emit(ByteSize(publish.payload.len()));
+
+        let mut stream = FramedRead::new(publish.payload.as_ref(), decoder.clone());
+        while let Some(next) = stream.next().await {
+            match next {
+                Ok((events, _byte_size)) => {
+                    let count = events.len();
+                    let byte_size = events.estimated_json_encoded_size_of();
+                    events_received.emit(CountByteSize(count, byte_size));
+
+                    let now = Utc::now();
+
+                    let events = events.into_iter().map(|mut event| {
+                        if let Event::Log(ref mut log) = event {
+                            log_namespace.insert_standard_vector_source_metadata(
+                                log,
+                                MqttSourceConfig::NAME,
+                                now,
+                            );
+
+                            let legacy_topic_key =
+                                config.topic_key.path.as_ref().map(LegacyKey::InsertIfEmpty);
+                            log_namespace.insert_source_metadata(
+                                MqttSourceConfig::NAME,
+                                log,
+                                legacy_topic_key,
+                                "topic",
+                                publish.topic.as_str(),
+                            )
+                        }
+                        event
+                    });
+
+                    out.send_batch(events).await.map_err(|_| {
+                        emit!(StreamClosedError { count });
+                    })?;
+                }
+                Err(error) => {
+                    // Error is logged by `crate::codecs`, no further
+                    // handling is needed here.
+                    if !error.can_continue() {
+                        break;
+                    }
+                }
+            }
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use lookup::{owned_value_path, OwnedTargetPath};
+    use vector_core::schema::Definition;
+    use vrl::value::{kind::Collection, Kind};
+
+    use super::*;
+
+    #[test]
+    fn generate_config() {
+        crate::test_util::test_generate_config::<MqttSourceConfig>();
+    }
+
+    #[test]
+    fn requires_topics() {
+        let config = MqttSourceConfig {
+            host: "127.0.0.1".into(),
+            ..Default::default()
+        };
+        assert!(matches!(config.connect(), Err(BuildError::NoTopics)));
+    }
+
+    #[test]
+    fn output_schema_definition_legacy_namespace() {
+        let config = MqttSourceConfig::default();
+        let definitions = config
+            .outputs(LogNamespace::Legacy)
+            .remove(0)
+            .schema_definition(true);
+
+        let expected_definition = Definition::new_with_default_metadata(
+            Kind::object(Collection::empty()),
+            [LogNamespace::Legacy],
+        )
+        .with_event_field(
+            &owned_value_path!("message"),
+            Kind::bytes(),
+            Some("message"),
+        )
+        .with_event_field(&owned_value_path!("timestamp"), Kind::timestamp(), None)
+        .with_event_field(&owned_value_path!("source_type"), Kind::bytes(), None)
+        .with_event_field(&owned_value_path!("topic"), Kind::bytes(), None);
+
+        assert_eq!(definitions, Some(expected_definition));
+    }
+
+    #[test]
+    fn output_schema_definition_vector_namespace() {
+        let config = MqttSourceConfig {
+            log_namespace: Some(true),
+            ..Default::default()
+        };
+        let definitions = config
+            .outputs(LogNamespace::Vector)
+            .remove(0)
+            .schema_definition(true);
+
+        let expected_definition =
+            Definition::new_with_default_metadata(Kind::bytes(), [LogNamespace::Vector])
+                .with_meaning(OwnedTargetPath::event_root(), "message")
+                .with_metadata_field(
+                    &owned_value_path!("vector", "source_type"),
+                    Kind::bytes(),
+                    None,
+                )
+                .with_metadata_field(
+                    &owned_value_path!("vector", "ingest_timestamp"),
+                    Kind::timestamp(),
+                    None,
+                )
+                .with_metadata_field(&owned_value_path!("mqtt", "topic"), Kind::bytes(), None);
+
+        assert_eq!(definitions, Some(expected_definition));
+    }
+}
+
+#[cfg(feature = "mqtt-integration-tests")]
+#[cfg(test)]
+mod integration_tests {
+    use rumqttc::QoS;
+    use vector_core::config::log_schema;
+
+    use super::*;
+    use crate::test_util::{
+        collect_n,
+        components::{assert_source_compliance, SOURCE_TAGS},
+        random_string,
+    };
+
+    fn mqtt_host() -> String {
+        std::env::var("MQTT_HOST").unwrap_or_else(|_| "localhost".into())
+    }
+
+    fn config(topics: Vec<String>) -> MqttSourceConfig {
+        MqttSourceConfig {
+            host: mqtt_host(),
+            topics,
+            ..Default::default()
+        }
+    }
+
+    /// Publishes messages from a separate client, once the source had time to subscribe.
+    async fn publish(messages: &[(&str, &str)]) {
+        let options = MqttOptions::new(format!("test-{}", random_string(10)), mqtt_host(), 1883);
+        let (client, mut eventloop) = AsyncClient::new(options, 100);
+        tokio::spawn(async move { while eventloop.poll().await.is_ok() {} });
+
+        tokio::time::sleep(Duration::from_secs(1)).await;
+        for (topic, payload) in messages {
+            client
+                .publish(*topic, QoS::AtLeastOnce, false, payload.as_bytes().to_vec())
+                .await
+                .unwrap();
+        }
+    }
+
+    async fn run(config: MqttSourceConfig, messages: &[(&str, &str)], n: usize) -> Vec<Event> {
+        assert_source_compliance(&SOURCE_TAGS, async move {
+            let (tx, rx) = SourceSender::new_test();
+            let decoder = DecodingConfig::new(
+                config.framing.clone(),
+                config.decoding.clone(),
+                LogNamespace::Legacy,
+            )
+            .build()
+            .unwrap();
+            let (client, eventloop) = config.connect().unwrap();
+            tokio::spawn(mqtt_source(
+                config,
+                client,
+                eventloop,
+                decoder,
+                LogNamespace::Legacy,
+                ShutdownSignal::noop(),
+                tx,
+            ));
+
+            publish(messages).await;
+            collect_n(rx, n).await
+        })
+        .await
+    }
+
+    #[tokio::test]
+    async fn mqtt_consumes_messages() {
+        let topic = format!("test/{}", random_string(10));
+
+        let events = run(config(vec![topic.clone()]), &[(&topic, "my message")], 1).await;
+
+        assert_eq!(
+            events[0].as_log()[log_schema().message_key().unwrap().to_string()],
+            "my message".into()
+        );
+        assert_eq!(events[0].as_log()["topic"], topic.into());
+    }
+
+    #[tokio::test]
+    async fn mqtt_subscribes_to_wildcards() {
+        let prefix = format!("test/{}", random_string(10));
+        let single = format!("{}/+/temperature", prefix);
+        let multi = format!("{}/logs/#", prefix);
+
+        let events = run(
+            config(vec![single, multi]),
+            &[
+                (&format!("{}/kitchen/temperature", prefix), "21"),
+                (&format!("{}/kitchen/humidity", prefix), "ignored"),
+                (&format!("{}/logs/app/web", prefix), "hello"),
+            ],
+            2,
+        )
+        .await;
+
+        let mut messages = events
+            .iter()
+            .map(|event| {
+                event.as_log()[log_schema().message_key().unwrap().to_string()]
+                    .to_string_lossy()
+                    .into_owned()
+            })
+            .collect::<Vec<_>>();
+        messages.sort();
+        assert_eq!(messages, vec!["21", "hello"]);
+    }
+}
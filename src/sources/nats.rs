@@ -1,11 +1,20 @@
+use std::time::{Duration, Instant};
+
+use async_stream::stream;
 use chrono::Utc;
 use codecs::decoding::{DeserializerConfig, FramingConfig, StreamDecodingError};
 use futures::{pin_mut, stream, Stream, StreamExt};
 use lookup::{lookup_v2::OptionalValuePath, owned_value_path};
+use serde::Deserialize;
 use snafu::{ResultExt, Snafu};
+use tokio::time;
 use tokio_util::codec::FramedRead;
-use vector_common::internal_event::{
-    ByteSize, BytesReceived, CountByteSize, EventsReceived, InternalEventHandle as _, Protocol,
+use vector_common::{
+    finalizer::UnorderedFinalizer,
+    internal_event::{
+        ByteSize, BytesReceived, CountByteSize, EventsReceived, InternalEventHandle as _, Protocol,
+        Registered,
+    },
 };
 use vector_config::configurable_component;
 use vector_core::{
@@ -16,11 +25,13 @@ use vrl::value::Kind;
 
 use crate::{
     codecs::{Decoder, DecodingConfig},
-    config::{GenerateConfig, SourceConfig, SourceContext, SourceOutput},
-    event::Event,
-    internal_events::StreamClosedError,
-    nats::{from_tls_auth_config, NatsAuthConfig, NatsConfigError},
-    serde::{default_decoding, default_framing_message_based},
+    config::{
+        GenerateConfig, SourceAcknowledgementsConfig, SourceConfig, SourceContext, SourceOutput,
+    },
+    event::{BatchNotifier, BatchStatus, Event},
+    internal_events::{NatsAckError, NatsReceiveError, StreamClosedError},
+    nats::{from_tls_auth_config, jetstream_request, NatsAuthConfig, NatsConfigError},
+    serde::{bool_or_struct, default_decoding, default_framing_message_based},
     shutdown::ShutdownSignal,
     tls::TlsEnableableConfig,
     SourceSender,
@@ -34,6 +45,10 @@ enum BuildError {
     Connect { source: std::io::Error },
     #[snafu(display("NATS Subscribe Error: {}", source))]
     Subscribe { source: std::io::Error },
+    #[snafu(display("NATS JetStream Consumer Error: {}", source))]
+    Consumer { source: std::io::Error },
+    #[snafu(display("`queue` can't be used together with `jetstream`"))]
+    QueueWithJetStream,
 }
 
 /// Configuration for the `nats` source.
@@ -71,8 +86,14 @@ pub struct NatsSourceConfig {
     subject: String,
 
     /// The NATS queue group to join.
+    ///
+    /// Can't be used together with `jetstream`, as instances that use the same durable consumer
+    /// already share the messages between them.
     queue: Option<String>,
 
+    #[configurable(derived)]
+    jetstream: Option<NatsJetStreamConfig>,
+
     /// The namespace to use for logs. This overrides the global setting.
     #[configurable(metadata(docs::hidden))]
     #[serde(default)]
@@ -97,12 +118,69 @@ pub struct NatsSourceConfig {
     /// The `NATS` subject key.
     #[serde(default = "default_subject_key_field")]
     subject_key_field: OptionalValuePath,
+
+    #[configurable(derived)]
+    #[serde(default, deserialize_with = "bool_or_struct")]
+    acknowledgements: SourceAcknowledgementsConfig,
 }
 
 fn default_subject_key_field() -> OptionalValuePath {
     OptionalValuePath::from(owned_value_path!("subject"))
 }
 
+/// Configuration for consuming messages through [JetStream][jetstream].
+///
+/// Instead of subscribing to `subject` directly, messages are pulled from a durable consumer on a
+/// stream that captures the subject. The stream keeps track of the messages the consumer has
+/// acknowledged, so messages published while Vector isn't running are consumed when it starts
+/// again.
+///
+/// Messages are acknowledged once the events they contain have been delivered, when end-to-end
+/// acknowledgements are enabled, or once they are sent to the next component otherwise. Messages
+/// whose events fail to be delivered are redelivered by the server.
+///
+/// [jetstream]: https://docs.nats.io/nats-concepts/jetstream
+#[configurable_component]
+#[derive(Clone, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct NatsJetStreamConfig {
+    /// The name of the stream to consume messages from.
+    ///
+    /// The stream must already exist, and capture messages published to `subject`.
+    #[configurable(metadata(docs::examples = "logs"))]
+    stream: String,
+
+    /// The name of the durable pull consumer to consume messages with.
+    ///
+    /// The consumer is created if it doesn't exist yet, with `subject` as its filter subject.
+    #[configurable(metadata(docs::examples = "vector"))]
+    durable_name: String,
+
+    /// The maximum number of messages to pull from the server at once.
+    #[serde(default = "default_batch_size")]
+    #[configurable(metadata(docs::type_unit = "messages"))]
+    batch_size: usize,
+
+    /// The amount of time, in seconds, the server waits for a message to be acknowledged before
+    /// redelivering it.
+    #[serde(default = "default_ack_wait_secs")]
+    #[configurable(metadata(docs::type_unit = "seconds"))]
+    ack_wait_secs: u64,
+
+    /// The maximum number of times a message is delivered before the server stops redelivering it.
+    ///
+    /// If not set, messages are redelivered until they are acknowledged.
+    max_deliver: Option<u64>,
+}
+
+const fn default_batch_size() -> usize {
+    100
+}
+
+const fn default_ack_wait_secs() -> u64 {
+    30
+}
+
 impl GenerateConfig for NatsSourceConfig {
     fn generate_config() -> toml::Value {
         toml::from_str(
@@ -120,20 +198,39 @@ impl GenerateConfig for NatsSourceConfig {
 impl SourceConfig for NatsSourceConfig {
     async fn build(&self, cx: SourceContext) -> crate::Result<super::Source> {
         let log_namespace = cx.log_namespace(self.log_namespace);
-        let (connection, subscription) = create_subscription(self).await?;
         let decoder =
             DecodingConfig::new(self.framing.clone(), self.decoding.clone(), log_namespace)
                 .build()?;
 
-        Ok(Box::pin(nats_source(
-            self.clone(),
-            connection,
-            subscription,
-            decoder,
-            log_namespace,
-            cx.shutdown,
-            cx.out,
-        )))
+        match &self.jetstream {
+            None => {
+                let (connection, subscription) = create_subscription(self).await?;
+
+                Ok(Box::pin(nats_source(
+                    self.clone(),
+                    connection,
+                    subscription,
+                    decoder,
+                    log_namespace,
+                    cx.shutdown,
+                    cx.out,
+                )))
+            }
+            Some(jetstream) => {
+                let acknowledgements = cx.do_acknowledgements(self.acknowledgements);
+                let consumer = create_jetstream_consumer(self, jetstream).await?;
+
+                Ok(Box::pin(nats_jetstream_source(
+                    self.clone(),
+                    consumer,
+                    decoder,
+                    log_namespace,
+                    acknowledgements,
+                    cx.shutdown,
+                    cx.out,
+                )))
+            }
+        }
     }
 
     fn outputs(&self, global_log_namespace: LogNamespace) -> Vec<SourceOutput> {
@@ -162,7 +259,8 @@ impl SourceConfig for NatsSourceConfig {
     }
 
     fn can_acknowledge(&self) -> bool {
-        false
+        // Core NATS has no notion of acknowledging messages, only JetStream does.
+        self.jetstream.is_some()
     }
 }
 
@@ -205,50 +303,78 @@ async fn nats_source(
     let bytes_received = register!(BytesReceived::from(Protocol::TCP));
     while let Some(msg) = stream.next().await {
         bytes_received.emit(ByteSize(msg.data.len()));
-        let mut stream = FramedRead::new(msg.data.as_ref(), decoder.clone());
-        while let Some(next) = stream.next().await {
-            match next {
-                Ok((events, _byte_size)) => {
-                    let count = events.len();
-                    let byte_size = events.estimated_json_encoded_size_of();
-                    events_received.emit(CountByteSize(count, byte_size));
-
-                    let now = Utc::now();
-
-                    let events = events.into_iter().map(|mut event| {
-                        if let Event::Log(ref mut log) = event {
-                            log_namespace.insert_standard_vector_source_metadata(
-                                log,
-                                NatsSourceConfig::NAME,
-                                now,
-                            );
-
-                            let legacy_subject_key_field = config
-                                .subject_key_field
-                                .path
-                                .as_ref()
-                                .map(LegacyKey::InsertIfEmpty);
-                            log_namespace.insert_source_metadata(
-                                NatsSourceConfig::NAME,
-                                log,
-                                legacy_subject_key_field,
-                                "subject",
-                                msg.subject.as_str(),
-                            )
-                        }
-                        event
-                    });
+        process_message(
+            &config,
+            &decoder,
+            log_namespace,
+            &events_received,
+            &msg,
+            None,
+            &mut out,
+        )
+        .await?;
+    }
+    Ok(())
+}
 
-                    out.send_batch(events).await.map_err(|_| {
-                        emit!(StreamClosedError { count });
-                    })?;
-                }
-                Err(error) => {
-                    // Error is logged by `crate::codecs`, no further
-                    // handling is needed here.
-                    if !error.can_continue() {
-                        break;
+/// Decodes the events in a message, and sends them to the next component.
+///
+/// If a batch notifier is given, it's attached to all of the events.
+async fn process_message(
+    config: &NatsSourceConfig,
+    decoder: &Decoder,
+    log_namespace: LogNamespace,
+    events_received: &Registered<EventsReceived>,
+    msg: &nats::asynk::Message,
+    batch: Option<&BatchNotifier>,
+    out: &mut SourceSender,
+) -> Result<(), ()> {
+    let mut stream = FramedRead::new(msg.data.as_ref(), decoder.clone());
+    while let Some(next) = stream.next().await {
+        match next {
+            Ok((events, _byte_size)) => {
+                let count = events.len();
+                let byte_size = events.estimated_json_encoded_size_of();
+                events_received.emit(CountByteSize(count, byte_size));
+
+                let now = Utc::now();
+
+                let events = events.into_iter().map(|mut event| {
+                    if let Event::Log(ref mut log) = event {
+                        log_namespace.insert_standard_vector_source_metadata(
+                            log,
+                            NatsSourceConfig::NAME,
+                            now,
+                        );
+
+                        let legacy_subject_key_field = config
+                            .subject_key_field
+                            .path
+                            .as_ref()
+                            .map(LegacyKey::InsertIfEmpty);
+                        log_namespace.insert_source_metadata(
+                            NatsSourceConfig::NAME,
+                            log,
+                            legacy_subject_key_field,
+                            "subject",
+                            msg.subject.as_str(),
+                        )
+                    }
+                    match batch {
+                        Some(batch) => event.with_batch_notifier(batch),
+                        None => event,
                     }
+                });
+
+                out.send_batch(events).await.map_err(|_| {
+                    emit!(StreamClosedError { count });
+                })?;
+            }
+            Err(error) => {
+                // Error is logged by `crate::codecs`, no further
+                // handling is needed here.
+                if !error.can_continue() {
+                    break;
                 }
             }
         }
@@ -271,6 +397,226 @@ async fn create_subscription(
     Ok((nc, subscription))
 }
 
+/// The amount of time a request for messages waits on the server for messages to be available.
+const JETSTREAM_PULL_EXPIRES: Duration = Duration::from_secs(10);
+
+/// The amount of time to back off for when a request for messages ends early without any messages,
+/// so that a consumer in a bad state doesn't cause a busy loop.
+const JETSTREAM_PULL_BACKOFF: Duration = Duration::from_secs(1);
+
+/// Response to creating a consumer.
+#[derive(Deserialize)]
+struct ConsumerInfo {
+    name: String,
+}
+
+/// A durable JetStream pull consumer.
+struct JetStreamConsumer {
+    connection: nats::asynk::Connection,
+    subscription: nats::asynk::Subscription,
+    inbox: String,
+    next_subject: String,
+    batch_size: usize,
+}
+
+impl JetStreamConsumer {
+    /// Requests the next batch of messages, which are delivered to the consumer's inbox.
+    async fn pull(&self) -> std::io::Result<()> {
+        let request = serde_json::json!({
+            "batch": self.batch_size,
+            "expires": duration_nanos(JETSTREAM_PULL_EXPIRES),
+        });
+        self.connection
+            .publish_request(&self.next_subject, &self.inbox, request.to_string())
+            .await
+    }
+
+    /// Gets the stream of messages pulled from the server.
+    ///
+    /// Errors are emitted, and the request is retried, rather than ending the stream.
+    fn messages(&self) -> impl Stream<Item = nats::asynk::Message> + '_ {
+        stream! {
+            loop {
+                let started = Instant::now();
+                if let Err(error) = self.pull().await {
+                    emit!(NatsReceiveError { error });
+                    time::sleep(JETSTREAM_PULL_BACKOFF).await;
+                    continue;
+                }
+
+                let mut received = 0;
+                while received < self.batch_size {
+                    // Normally the server ends every request with a status message at the latest
+                    // when the request expires, but don't rely on it in case it gets lost.
+                    let next = time::timeout(
+                        JETSTREAM_PULL_EXPIRES + JETSTREAM_PULL_BACKOFF,
+                        self.subscription.next(),
+                    );
+                    match next.await {
+                        Err(_) => break,
+                        Ok(None) => return,
+                        Ok(Some(msg)) if is_jetstream_message(&msg) => {
+                            received += 1;
+                            yield msg;
+                        }
+                        // A status message, such as the request having expired.
+                        Ok(Some(_)) => break,
+                    }
+                }
+
+                if received == 0 && started.elapsed() < JETSTREAM_PULL_EXPIRES / 2 {
+                    time::sleep(JETSTREAM_PULL_BACKOFF).await;
+                }
+            }
+        }
+    }
+
+    async fn acknowledge(&self, reply: &str, status: BatchStatus) {
+        // Rejected events will never be delivered, so there is no point in having the server
+        // redeliver the message they came from.
+        let ack: &[u8] = match status {
+            BatchStatus::Delivered => b"+ACK",
+            BatchStatus::Errored => b"-NAK",
+            BatchStatus::Rejected => b"+TERM",
+        };
+        if let Err(error) = self.connection.publish(reply, ack).await {
+            emit!(NatsAckError { error });
+        }
+    }
+}
+
+/// Messages delivered by JetStream can be told apart from status messages by their reply subject,
+/// which is used to acknowledge them.
+fn is_jetstream_message(msg: &nats::asynk::Message) -> bool {
+    msg.reply
+        .as_deref()
+        .map_or(false, |reply| reply.starts_with("$JS.ACK."))
+}
+
+fn duration_nanos(duration: Duration) -> u64 {
+    u64::try_from(duration.as_nanos()).unwrap_or(u64::MAX)
+}
+
+async fn create_jetstream_consumer(
+    config: &NatsSourceConfig,
+    jetstream: &NatsJetStreamConfig,
+) -> Result<JetStreamConsumer, BuildError> {
+    if config.queue.is_some() {
+        return Err(BuildError::QueueWithJetStream);
+    }
+
+    let connection = config.connect().await?;
+
+    let request = serde_json::json!({
+        "stream_name": jetstream.stream,
+        "config": {
+            "durable_name": jetstream.durable_name,
+            "filter_subject": config.subject,
+            "deliver_policy": "all",
+            "ack_policy": "explicit",
+            "ack_wait": duration_nanos(Duration::from_secs(jetstream.ack_wait_secs)),
+            // The server uses -1 for no limit.
+            "max_deliver": jetstream
+                .max_deliver
+                .map_or(-1, |max| i64::try_from(max).unwrap_or(i64::MAX)),
+        },
+    });
+    let consumer: ConsumerInfo = jetstream_request(
+        &connection,
+        &format!(
+            "$JS.API.CONSUMER.DURABLE.CREATE.{}.{}",
+            jetstream.stream, jetstream.durable_name
+        ),
+        request.to_string(),
+    )
+    .await
+    .context(ConsumerSnafu)?;
+    debug!(
+        message = "Created JetStream consumer.",
+        stream = %jetstream.stream,
+        consumer = %consumer.name,
+    );
+
+    let inbox = connection.new_inbox();
+    let subscription = connection.subscribe(&inbox).await.context(SubscribeSnafu)?;
+
+    Ok(JetStreamConsumer {
+        next_subject: format!(
+            "$JS.API.CONSUMER.MSG.NEXT.{}.{}",
+            jetstream.stream, consumer.name
+        ),
+        connection,
+        subscription,
+        inbox,
+        batch_size: jetstream.batch_size,
+    })
+}
+
+async fn nats_jetstream_source(
+    config: NatsSourceConfig,
+    consumer: JetStreamConsumer,
+    decoder: Decoder,
+    log_namespace: LogNamespace,
+    acknowledgements: bool,
+    mut shutdown: ShutdownSignal,
+    mut out: SourceSender,
+) -> Result<(), ()> {
+    let (finalizer, mut ack_stream) =
+        UnorderedFinalizer::<String>::maybe_new(acknowledgements, Some(shutdown.clone()));
+    let events_received = register!(EventsReceived);
+    let bytes_received = register!(BytesReceived::from(Protocol::TCP));
+
+    let messages = consumer.messages();
+    pin_mut!(messages);
+    loop {
+        tokio::select! {
+            _ = &mut shutdown => break,
+            entry = ack_stream.next() => {
+                if let Some((status, reply)) = entry {
+                    consumer.acknowledge(&reply, status).await;
+                }
+            },
+            msg = messages.next() => {
+                let Some(msg) = msg else { break };
+                let Some(reply) = msg.reply.clone() else { continue };
+                bytes_received.emit(ByteSize(msg.data.len()));
+
+                match &finalizer {
+                    Some(finalizer) => {
+                        let (batch, receiver) = BatchNotifier::new_with_receiver();
+                        process_message(
+                            &config,
+                            &decoder,
+                            log_namespace,
+                            &events_received,
+                            &msg,
+                            Some(&batch),
+                            &mut out,
+                        )
+                        .await?;
+                        finalizer.add(reply, receiver);
+                    }
+                    None => {
+                        process_message(
+                            &config,
+                            &decoder,
+                            log_namespace,
+                            &events_received,
+                            &msg,
+                            None,
+                            &mut out,
+                        )
+                        .await?;
+                        consumer.acknowledge(&reply, BatchStatus::Delivered).await;
+                    }
+                }
+            }
+        }
+    }
+
+    Ok(())
+}
+
 #[cfg(test)]
 mod tests {
     #![allow(clippy::print_stdout)] //tests
@@ -343,6 +689,48 @@ mod tests {
 
         assert_eq!(definitions, Some(expected_definition));
     }
+
+    #[test]
+    fn parses_jetstream_config() {
+        let config: NatsSourceConfig = toml::from_str(
+            r#"
+            url = "nats://localhost:4222"
+            subject = "logs.>"
+            connection_name = "vector"
+
+            [jetstream]
+            stream = "logs"
+            durable_name = "vector"
+            "#,
+        )
+        .unwrap();
+
+        let jetstream = config.jetstream.as_ref().unwrap();
+        assert_eq!(jetstream.batch_size, default_batch_size());
+        assert_eq!(jetstream.ack_wait_secs, default_ack_wait_secs());
+        assert_eq!(jetstream.max_deliver, None);
+        assert!(config.can_acknowledge());
+    }
+
+    #[tokio::test]
+    async fn jetstream_rejects_queue() {
+        let config: NatsSourceConfig = toml::from_str(
+            r#"
+            url = "nats://localhost:4222"
+            subject = "logs.>"
+            connection_name = "vector"
+            queue = "vector"
+
+            [jetstream]
+            stream = "logs"
+            durable_name = "vector"
+            "#,
+        )
+        .unwrap();
+
+        let result = create_jetstream_consumer(&config, config.jetstream.as_ref().unwrap()).await;
+        assert!(matches!(result, Err(BuildError::QueueWithJetStream)));
+    }
 }
 
 #[cfg(feature = "nats-integration-tests")]
@@ -353,6 +741,7 @@ mod integration_tests {
     use vector_core::config::log_schema;
 
     use super::*;
+    use crate::event::EventStatus;
     use crate::nats::{NatsAuthCredentialsFile, NatsAuthNKey, NatsAuthToken, NatsAuthUserPassword};
     use crate::test_util::{
         collect_n,
@@ -410,12 +799,14 @@ mod integration_tests {
             subject: subject.clone(),
             url,
             queue: None,
+            jetstream: None,
             framing: default_framing_message_based(),
             decoding: default_decoding(),
             tls: None,
             auth: None,
             log_namespace: None,
             subject_key_field: default_subject_key_field(),
+            acknowledgements: Default::default(),
         };
 
         let r = publish_and_check(conf).await;
@@ -437,6 +828,7 @@ mod integration_tests {
             subject: subject.clone(),
             url,
             queue: None,
+            jetstream: None,
             framing: default_framing_message_based(),
             decoding: default_decoding(),
             tls: None,
@@ -448,6 +840,7 @@ mod integration_tests {
             }),
             log_namespace: None,
             subject_key_field: default_subject_key_field(),
+            acknowledgements: Default::default(),
         };
 
         let r = publish_and_check(conf).await;
@@ -469,6 +862,7 @@ mod integration_tests {
             subject: subject.clone(),
             url,
             queue: None,
+            jetstream: None,
             framing: default_framing_message_based(),
             decoding: default_decoding(),
             tls: None,
@@ -480,6 +874,7 @@ mod integration_tests {
             }),
             log_namespace: None,
             subject_key_field: default_subject_key_field(),
+            acknowledgements: Default::default(),
         };
 
         let r = publish_and_check(conf).await;
@@ -501,6 +896,7 @@ mod integration_tests {
             subject: subject.clone(),
             url,
             queue: None,
+            jetstream: None,
             framing: default_framing_message_based(),
             decoding: default_decoding(),
             tls: None,
@@ -511,6 +907,7 @@ mod integration_tests {
             }),
             log_namespace: None,
             subject_key_field: default_subject_key_field(),
+            acknowledgements: Default::default(),
         };
 
         let r = publish_and_check(conf).await;
@@ -532,6 +929,7 @@ mod integration_tests {
             subject: subject.clone(),
             url,
             queue: None,
+            jetstream: None,
             framing: default_framing_message_based(),
             decoding: default_decoding(),
             tls: None,
@@ -542,6 +940,7 @@ mod integration_tests {
             }),
             log_namespace: None,
             subject_key_field: default_subject_key_field(),
+            acknowledgements: Default::default(),
         };
 
         let r = publish_and_check(conf).await;
@@ -563,6 +962,7 @@ mod integration_tests {
             subject: subject.clone(),
             url,
             queue: None,
+            jetstream: None,
             framing: default_framing_message_based(),
             decoding: default_decoding(),
             tls: None,
@@ -574,6 +974,7 @@ mod integration_tests {
             }),
             log_namespace: None,
             subject_key_field: default_subject_key_field(),
+            acknowledgements: Default::default(),
         };
 
         let r = publish_and_check(conf).await;
@@ -595,6 +996,7 @@ mod integration_tests {
             subject: subject.clone(),
             url,
             queue: None,
+            jetstream: None,
             framing: default_framing_message_based(),
             decoding: default_decoding(),
             tls: None,
@@ -606,6 +1008,7 @@ mod integration_tests {
             }),
             log_namespace: None,
             subject_key_field: default_subject_key_field(),
+            acknowledgements: Default::default(),
         };
 
         let r = publish_and_check(conf).await;
@@ -627,6 +1030,7 @@ mod integration_tests {
             subject: subject.clone(),
             url,
             queue: None,
+            jetstream: None,
             framing: default_framing_message_based(),
             decoding: default_decoding(),
             tls: Some(TlsEnableableConfig {
@@ -639,6 +1043,7 @@ mod integration_tests {
             auth: None,
             log_namespace: None,
             subject_key_field: default_subject_key_field(),
+            acknowledgements: Default::default(),
         };
 
         let r = publish_and_check(conf).await;
@@ -660,12 +1065,14 @@ mod integration_tests {
             subject: subject.clone(),
             url,
             queue: None,
+            jetstream: None,
             framing: default_framing_message_based(),
             decoding: default_decoding(),
             tls: None,
             auth: None,
             log_namespace: None,
             subject_key_field: default_subject_key_field(),
+            acknowledgements: Default::default(),
         };
 
         let r = publish_and_check(conf).await;
@@ -687,6 +1094,7 @@ mod integration_tests {
             subject: subject.clone(),
             url,
             queue: None,
+            jetstream: None,
             framing: default_framing_message_based(),
             decoding: default_decoding(),
             tls: Some(TlsEnableableConfig {
@@ -701,6 +1109,7 @@ mod integration_tests {
             auth: None,
             log_namespace: None,
             subject_key_field: default_subject_key_field(),
+            acknowledgements: Default::default(),
         };
 
         let r = publish_and_check(conf).await;
@@ -722,6 +1131,7 @@ mod integration_tests {
             subject: subject.clone(),
             url,
             queue: None,
+            jetstream: None,
             framing: default_framing_message_based(),
             decoding: default_decoding(),
             tls: Some(TlsEnableableConfig {
@@ -734,6 +1144,7 @@ mod integration_tests {
             auth: None,
             log_namespace: None,
             subject_key_field: default_subject_key_field(),
+            acknowledgements: Default::default(),
         };
 
         let r = publish_and_check(conf).await;
@@ -755,6 +1166,7 @@ mod integration_tests {
             subject: subject.clone(),
             url,
             queue: None,
+            jetstream: None,
             framing: default_framing_message_based(),
             decoding: default_decoding(),
             tls: Some(TlsEnableableConfig {
@@ -771,6 +1183,7 @@ mod integration_tests {
             }),
             log_namespace: None,
             subject_key_field: default_subject_key_field(),
+            acknowledgements: Default::default(),
         };
 
         let r = publish_and_check(conf).await;
@@ -792,6 +1205,7 @@ mod integration_tests {
             subject: subject.clone(),
             url,
             queue: None,
+            jetstream: None,
             framing: default_framing_message_based(),
             decoding: default_decoding(),
             tls: Some(TlsEnableableConfig {
@@ -808,6 +1222,7 @@ mod integration_tests {
             }),
             log_namespace: None,
             subject_key_field: default_subject_key_field(),
+            acknowledgements: Default::default(),
         };
 
         let r = publish_and_check(conf).await;
@@ -817,4 +1232,126 @@ mod integration_tests {
             r
         );
     }
+
+    async fn create_stream(nc: &nats::asynk::Connection, stream: &str, subject: &str) {
+        #[derive(Deserialize)]
+        struct StreamInfo {}
+
+        let request = serde_json::json!({ "name": stream, "subjects": [subject] });
+        let _: StreamInfo = jetstream_request(
+            nc,
+            &format!("$JS.API.STREAM.CREATE.{}", stream),
+            request.to_string(),
+        )
+        .await
+        .unwrap();
+    }
+
+    fn jetstream_config(subject: &str, stream: &str) -> NatsSourceConfig {
+        let url = std::env::var("NATS_JETSTREAM_ADDRESS")
+            .unwrap_or_else(|_| String::from("nats://localhost:4222"));
+
+        NatsSourceConfig {
+            connection_name: "".to_owned(),
+            subject: subject.to_owned(),
+            url,
+            queue: None,
+            jetstream: Some(NatsJetStreamConfig {
+                stream: stream.to_owned(),
+                durable_name: format!("{}-consumer", stream),
+                batch_size: default_batch_size(),
+                ack_wait_secs: 1,
+                max_deliver: None,
+            }),
+            framing: default_framing_message_based(),
+            decoding: default_decoding(),
+            tls: None,
+            auth: None,
+            log_namespace: None,
+            subject_key_field: default_subject_key_field(),
+            acknowledgements: true.into(),
+        }
+    }
+
+    async fn spawn_jetstream_source(
+        conf: &NatsSourceConfig,
+        status: EventStatus,
+    ) -> impl Stream<Item = Event> + Unpin {
+        let (tx, rx) = SourceSender::new_test_finalize(status);
+        let decoder = DecodingConfig::new(
+            conf.framing.clone(),
+            conf.decoding.clone(),
+            LogNamespace::Legacy,
+        )
+        .build()
+        .unwrap();
+        let consumer = create_jetstream_consumer(conf, conf.jetstream.as_ref().unwrap())
+            .await
+            .unwrap();
+        tokio::spawn(nats_jetstream_source(
+            conf.clone(),
+            consumer,
+            decoder,
+            LogNamespace::Legacy,
+            true,
+            ShutdownSignal::noop(),
+            tx,
+        ));
+        rx
+    }
+
+    #[tokio::test]
+    async fn nats_jetstream_consumes_and_acknowledges() {
+        let subject = format!("test-{}", random_string(10));
+        let stream = format!("stream-{}", random_string(10));
+        let conf = jetstream_config(&subject, &stream);
+
+        let nc = conf.connect().await.unwrap();
+        create_stream(&nc, &stream, &subject).await;
+
+        // Published before the consumer exists, to make sure it starts from the beginning of the
+        // stream.
+        nc.publish(&subject, "my message").await.unwrap();
+
+        let (events, mut rx) = assert_source_compliance(&SOURCE_TAGS, async {
+            let mut rx = spawn_jetstream_source(&conf, EventStatus::Delivered).await;
+            (collect_n(&mut rx, 1).await, rx)
+        })
+        .await;
+        assert_eq!(
+            events[0].as_log()[log_schema().message_key().unwrap().to_string()],
+            "my message".into()
+        );
+        assert_eq!(events[0].as_log()["subject"], subject.clone().into());
+
+        // Once acknowledged, the message isn't redelivered after the acknowledgement wait.
+        tokio::time::sleep(Duration::from_secs(2)).await;
+        nc.publish(&subject, "second message").await.unwrap();
+        let events = collect_n(&mut rx, 1).await;
+        assert_eq!(
+            events[0].as_log()[log_schema().message_key().unwrap().to_string()],
+            "second message".into()
+        );
+    }
+
+    #[tokio::test]
+    async fn nats_jetstream_redelivers_errored_messages() {
+        let subject = format!("test-{}", random_string(10));
+        let stream = format!("stream-{}", random_string(10));
+        let conf = jetstream_config(&subject, &stream);
+
+        let nc = conf.connect().await.unwrap();
+        create_stream(&nc, &stream, &subject).await;
+        nc.publish(&subject, "my message").await.unwrap();
+
+        // The errored message is negatively acknowledged, and so delivered again.
+        let rx = spawn_jetstream_source(&conf, EventStatus::Errored).await;
+        let events = collect_n(rx, 2).await;
+        for event in events {
+            assert_eq!(
+                event.as_log()[log_schema().message_key().unwrap().to_string()],
+                "my message".into()
+            );
+        }
+    }
 }
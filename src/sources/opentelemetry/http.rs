@@ -3,8 +3,10 @@ use std::net::SocketAddr;
 use bytes::Bytes;
 use futures_util::FutureExt;
 use http::StatusCode;
-use opentelemetry_proto::proto::collector::logs::v1::{
-    ExportLogsServiceRequest, ExportLogsServiceResponse,
+use opentelemetry_proto::proto::collector::{
+    logs::v1::{ExportLogsServiceRequest, ExportLogsServiceResponse},
+    metrics::v1::{ExportMetricsServiceRequest, ExportMetricsServiceResponse},
+    trace::v1::{ExportTraceServiceRequest, ExportTraceServiceResponse},
 };
 use prost::Message;
 use snafu::Snafu;
@@ -66,8 +68,55 @@ pub(crate) fn build_warp_filter(
     bytes_received: Registered<BytesReceived>,
     events_received: Registered<EventsReceived>,
 ) -> BoxedFilter<(Response,)> {
+    let log_events_received = events_received.clone();
+    let log_filters = build_ingest_filter::<ExportLogsServiceResponse, _>(
+        super::LOGS,
+        acknowledgements,
+        out.clone(),
+        bytes_received.clone(),
+        move |body| decode_log_body(body, log_namespace, &log_events_received),
+    );
+
+    let metric_events_received = events_received.clone();
+    let metric_filters = build_ingest_filter::<ExportMetricsServiceResponse, _>(
+        super::METRICS,
+        acknowledgements,
+        out.clone(),
+        bytes_received.clone(),
+        move |body| decode_metrics_body(body, &metric_events_received),
+    );
+
+    let trace_filters = build_ingest_filter::<ExportTraceServiceResponse, _>(
+        super::TRACES,
+        acknowledgements,
+        out,
+        bytes_received,
+        move |body| decode_trace_body(body, &events_received),
+    );
+
+    log_filters
+        .or(metric_filters)
+        .unify()
+        .or(trace_filters)
+        .unify()
+        .boxed()
+}
+
+fn build_ingest_filter<Resp, F>(
+    telemetry_type: &'static str,
+    acknowledgements: bool,
+    out: SourceSender,
+    bytes_received: Registered<BytesReceived>,
+    make_events: F,
+) -> BoxedFilter<(Response,)>
+where
+    Resp: prost::Message + Default + Send + 'static,
+    F: Fn(Bytes) -> Result<Vec<Event>, ErrorMessage> + Clone + Send + Sync + 'static,
+{
     warp::post()
-        .and(warp::path!("v1" / "logs"))
+        .and(warp::path("v1"))
+        .and(warp::path(telemetry_type))
+        .and(warp::path::end())
         .and(warp::header::exact_ignore_case(
             "content-type",
             "application/x-protobuf",
@@ -77,15 +126,21 @@ pub(crate) fn build_warp_filter(
         .and_then(move |encoding_header: Option<String>, body: Bytes| {
             let events = decode(&encoding_header, body).and_then(|body| {
                 bytes_received.emit(ByteSize(body.len()));
-                decode_body(body, log_namespace, &events_received)
+                make_events(body)
             });
 
-            handle_request(events, acknowledgements, out.clone(), super::LOGS)
+            handle_request(
+                events,
+                acknowledgements,
+                out.clone(),
+                telemetry_type,
+                Resp::default(),
+            )
         })
         .boxed()
 }
 
-fn decode_body(
+fn decode_log_body(
     body: Bytes,
     log_namespace: LogNamespace,
     events_received: &Registered<EventsReceived>,
@@ -111,12 +166,66 @@ fn decode_body(
     Ok(events)
 }
 
-async fn handle_request(
+fn decode_metrics_body(
+    body: Bytes,
+    events_received: &Registered<EventsReceived>,
+) -> Result<Vec<Event>, ErrorMessage> {
+    let request = ExportMetricsServiceRequest::decode(body).map_err(|error| {
+        ErrorMessage::new(
+            StatusCode::BAD_REQUEST,
+            format!("Could not decode request: {}", error),
+        )
+    })?;
+
+    let events: Vec<Event> = request
+        .resource_metrics
+        .into_iter()
+        .flat_map(|v| v.into_event_iter())
+        .collect();
+
+    events_received.emit(CountByteSize(
+        events.len(),
+        events.estimated_json_encoded_size_of(),
+    ));
+
+    Ok(events)
+}
+
+fn decode_trace_body(
+    body: Bytes,
+    events_received: &Registered<EventsReceived>,
+) -> Result<Vec<Event>, ErrorMessage> {
+    let request = ExportTraceServiceRequest::decode(body).map_err(|error| {
+        ErrorMessage::new(
+            StatusCode::BAD_REQUEST,
+            format!("Could not decode request: {}", error),
+        )
+    })?;
+
+    let events: Vec<Event> = request
+        .resource_spans
+        .into_iter()
+        .flat_map(|v| v.into_event_iter())
+        .collect();
+
+    events_received.emit(CountByteSize(
+        events.len(),
+        events.estimated_json_encoded_size_of(),
+    ));
+
+    Ok(events)
+}
+
+async fn handle_request<Resp>(
     events: Result<Vec<Event>, ErrorMessage>,
     acknowledgements: bool,
     mut out: SourceSender,
     output: &str,
-) -> Result<Response, Rejection> {
+    response: Resp,
+) -> Result<Response, Rejection>
+where
+    Resp: prost::Message,
+{
     match events {
         Ok(mut events) => {
             let receiver = BatchNotifier::maybe_apply_to(acknowledgements, &mut events);
@@ -128,11 +237,9 @@ async fn handle_request(
             })?;
 
             match receiver {
-                None => Ok(protobuf(ExportLogsServiceResponse {}).into_response()),
+                None => Ok(protobuf(response).into_response()),
                 Some(receiver) => match receiver.await {
-                    BatchStatus::Delivered => {
-                        Ok(protobuf(ExportLogsServiceResponse {}).into_response())
-                    }
+                    BatchStatus::Delivered => Ok(protobuf(response).into_response()),
                     BatchStatus::Errored => Err(warp::reject::custom(Status {
                         code: 2, // UNKNOWN - OTLP doesn't require use of status.code, but we can't encode a None here
                         message: "Error delivering contents to sink".into(),
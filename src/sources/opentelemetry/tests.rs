@@ -3,13 +3,26 @@ use futures::Stream;
 use futures_util::StreamExt;
 use lookup::path;
 use opentelemetry_proto::proto::{
-    collector::logs::v1::{logs_service_client::LogsServiceClient, ExportLogsServiceRequest},
-    common::v1::{any_value, AnyValue, KeyValue},
+    collector::{
+        logs::v1::{logs_service_client::LogsServiceClient, ExportLogsServiceRequest},
+        metrics::v1::{metrics_service_client::MetricsServiceClient, ExportMetricsServiceRequest},
+        trace::v1::{trace_service_client::TraceServiceClient, ExportTraceServiceRequest},
+    },
+    common::v1::{any_value, AnyValue, InstrumentationScope, KeyValue},
     logs::v1::{LogRecord, ResourceLogs, ScopeLogs},
+    metrics::v1::{
+        exponential_histogram_data_point::Buckets, metric::Data, number_data_point,
+        AggregationTemporality, ExponentialHistogram, ExponentialHistogramDataPoint, Gauge,
+        Histogram, HistogramDataPoint, Metric as OtelMetric, NumberDataPoint, ResourceMetrics,
+        ScopeMetrics, Sum,
+    },
     resource::v1::Resource as OtelResource,
+    trace::v1::{span, ResourceSpans, ScopeSpans, Span},
 };
+use prost::Message;
 use similar_asserts::assert_eq;
 use std::collections::BTreeMap;
+use std::net::SocketAddr;
 use std::sync::Arc;
 use tonic::Request;
 use vector_core::config::LogNamespace;
@@ -18,8 +31,12 @@ use vrl::value;
 use crate::config::OutputId;
 use crate::{
     config::{SourceConfig, SourceContext},
-    event::{into_event_stream, Event, EventStatus, LogEvent, Value},
-    sources::opentelemetry::{GrpcConfig, HttpConfig, OpentelemetryConfig, LOGS},
+    event::{
+        into_event_stream,
+        metric::{Bucket, MetricKind, MetricValue},
+        Event, EventStatus, LogEvent, Value,
+    },
+    sources::opentelemetry::{GrpcConfig, HttpConfig, OpentelemetryConfig, LOGS, METRICS, TRACES},
     test_util::{
         self,
         components::{assert_source_compliance, SOURCE_TAGS},
@@ -281,6 +298,339 @@ async fn receive_grpc_logs_legacy_namespace() {
     .await;
 }
 
+fn test_config(grpc_addr: SocketAddr, http_addr: SocketAddr) -> OpentelemetryConfig {
+    OpentelemetryConfig {
+        grpc: GrpcConfig {
+            address: grpc_addr,
+            tls: Default::default(),
+        },
+        http: HttpConfig {
+            address: http_addr,
+            tls: Default::default(),
+        },
+        acknowledgements: Default::default(),
+        log_namespace: Default::default(),
+    }
+}
+
+fn string_attribute(key: &str, value: &str) -> KeyValue {
+    KeyValue {
+        key: key.into(),
+        value: Some(AnyValue {
+            value: Some(any_value::Value::StringValue(value.into())),
+        }),
+    }
+}
+
+fn metrics_request() -> ExportMetricsServiceRequest {
+    ExportMetricsServiceRequest {
+        resource_metrics: vec![ResourceMetrics {
+            resource: Some(OtelResource {
+                attributes: vec![string_attribute("service.name", "checkout")],
+                dropped_attributes_count: 0,
+            }),
+            scope_metrics: vec![ScopeMetrics {
+                scope: Some(InstrumentationScope {
+                    name: "meter".into(),
+                    version: "1.0".into(),
+                    attributes: vec![],
+                    dropped_attributes_count: 0,
+                }),
+                metrics: vec![
+                    OtelMetric {
+                        name: "temperature".into(),
+                        description: String::new(),
+                        unit: String::new(),
+                        data: Some(Data::Gauge(Gauge {
+                            data_points: vec![NumberDataPoint {
+                                attributes: vec![string_attribute("room", "kitchen")],
+                                start_time_unix_nano: 0,
+                                time_unix_nano: 1,
+                                exemplars: vec![],
+                                flags: 0,
+                                value: Some(number_data_point::Value::AsDouble(21.5)),
+                            }],
+                        })),
+                    },
+                    OtelMetric {
+                        name: "requests".into(),
+                        description: String::new(),
+                        unit: String::new(),
+                        data: Some(Data::Sum(Sum {
+                            data_points: vec![NumberDataPoint {
+                                attributes: vec![],
+                                start_time_unix_nano: 0,
+                                time_unix_nano: 1,
+                                exemplars: vec![],
+                                flags: 0,
+                                value: Some(number_data_point::Value::AsInt(7)),
+                            }],
+                            aggregation_temporality: AggregationTemporality::Delta as i32,
+                            is_monotonic: true,
+                        })),
+                    },
+                    OtelMetric {
+                        name: "latency".into(),
+                        description: String::new(),
+                        unit: String::new(),
+                        data: Some(Data::Histogram(Histogram {
+                            data_points: vec![HistogramDataPoint {
+                                attributes: vec![],
+                                start_time_unix_nano: 0,
+                                time_unix_nano: 1,
+                                count: 6,
+                                sum: Some(12.0),
+                                bucket_counts: vec![1, 2, 3],
+                                explicit_bounds: vec![1.0, 2.0],
+                                exemplars: vec![],
+                                flags: 0,
+                                min: None,
+                                max: None,
+                            }],
+                            aggregation_temporality: AggregationTemporality::Cumulative as i32,
+                        })),
+                    },
+                    OtelMetric {
+                        name: "size".into(),
+                        description: String::new(),
+                        unit: String::new(),
+                        data: Some(Data::ExponentialHistogram(ExponentialHistogram {
+                            data_points: vec![ExponentialHistogramDataPoint {
+                                attributes: vec![],
+                                start_time_unix_nano: 0,
+                                time_unix_nano: 1,
+                                count: 4,
+                                sum: Some(10.0),
+                                scale: 0,
+                                zero_count: 1,
+                                positive: Some(Buckets {
+                                    offset: 0,
+                                    bucket_counts: vec![1, 2],
+                                }),
+                                negative: None,
+                                flags: 0,
+                                exemplars: vec![],
+                                min: None,
+                                max: None,
+                            }],
+                            aggregation_temporality: AggregationTemporality::Delta as i32,
+                        })),
+                    },
+                ],
+                schema_url: "v1".into(),
+            }],
+            schema_url: "v1".into(),
+        }],
+    }
+}
+
+fn assert_metrics(output: Vec<Event>) {
+    assert_eq!(output.len(), 4);
+
+    let gauge = output[0].as_metric();
+    assert_eq!(gauge.name(), "temperature");
+    assert_eq!(gauge.kind(), MetricKind::Absolute);
+    assert_eq!(gauge.value(), &MetricValue::Gauge { value: 21.5 });
+    assert_eq!(gauge.timestamp(), Some(Utc.timestamp_nanos(1)));
+    let tags = gauge.tags().unwrap();
+    assert_eq!(tags.get("room"), Some("kitchen"));
+    assert_eq!(tags.get("resource.service.name"), Some("checkout"));
+    assert_eq!(tags.get("scope.name"), Some("meter"));
+    assert_eq!(tags.get("scope.version"), Some("1.0"));
+
+    let counter = output[1].as_metric();
+    assert_eq!(counter.name(), "requests");
+    assert_eq!(counter.kind(), MetricKind::Incremental);
+    assert_eq!(counter.value(), &MetricValue::Counter { value: 7.0 });
+
+    let histogram = output[2].as_metric();
+    assert_eq!(histogram.name(), "latency");
+    assert_eq!(histogram.kind(), MetricKind::Absolute);
+    assert_eq!(
+        histogram.value(),
+        &MetricValue::AggregatedHistogram {
+            buckets: vec![
+                Bucket {
+                    upper_limit: 1.0,
+                    count: 1
+                },
+                Bucket {
+                    upper_limit: 2.0,
+                    count: 2
+                },
+            ],
+            count: 6,
+            sum: 12.0,
+        }
+    );
+
+    let exponential = output[3].as_metric();
+    assert_eq!(exponential.name(), "size");
+    assert_eq!(exponential.kind(), MetricKind::Incremental);
+    assert_eq!(
+        exponential.value(),
+        &MetricValue::AggregatedHistogram {
+            buckets: vec![
+                Bucket {
+                    upper_limit: 0.0,
+                    count: 1
+                },
+                Bucket {
+                    upper_limit: 2.0,
+                    count: 1
+                },
+                Bucket {
+                    upper_limit: 4.0,
+                    count: 2
+                },
+            ],
+            count: 4,
+            sum: 10.0,
+        }
+    );
+}
+
+#[tokio::test]
+async fn receive_grpc_metrics() {
+    assert_source_compliance(&SOURCE_TAGS, async {
+        let grpc_addr = next_addr();
+        let http_addr = next_addr();
+
+        let source = test_config(grpc_addr, http_addr);
+        let (sender, metrics_output) = new_source_with_port(EventStatus::Delivered, METRICS);
+        let server = source
+            .build(SourceContext::new_test(sender, None))
+            .await
+            .unwrap();
+        tokio::spawn(server);
+        test_util::wait_for_tcp(grpc_addr).await;
+
+        let mut client = MetricsServiceClient::connect(format!("http://{}", grpc_addr))
+            .await
+            .unwrap();
+        _ = client.export(Request::new(metrics_request())).await;
+
+        assert_metrics(test_util::collect_ready(metrics_output).await);
+    })
+    .await;
+}
+
+#[tokio::test]
+async fn receive_http_metrics() {
+    assert_source_compliance(&SOURCE_TAGS, async {
+        let grpc_addr = next_addr();
+        let http_addr = next_addr();
+
+        let source = test_config(grpc_addr, http_addr);
+        let (sender, metrics_output) = new_source_with_port(EventStatus::Delivered, METRICS);
+        let server = source
+            .build(SourceContext::new_test(sender, None))
+            .await
+            .unwrap();
+        tokio::spawn(server);
+        test_util::wait_for_tcp(http_addr).await;
+
+        let response = reqwest::Client::new()
+            .post(format!("http://{}/v1/metrics", http_addr))
+            .header("Content-Type", "application/x-protobuf")
+            .body(metrics_request().encode_to_vec())
+            .send()
+            .await
+            .unwrap();
+        assert!(response.status().is_success());
+
+        assert_metrics(test_util::collect_ready(metrics_output).await);
+    })
+    .await;
+}
+
+#[tokio::test]
+async fn receive_grpc_traces() {
+    assert_source_compliance(&SOURCE_TAGS, async {
+        let grpc_addr = next_addr();
+        let http_addr = next_addr();
+
+        let source = test_config(grpc_addr, http_addr);
+        let (sender, traces_output) = new_source_with_port(EventStatus::Delivered, TRACES);
+        let server = source
+            .build(SourceContext::new_test(sender, None))
+            .await
+            .unwrap();
+        tokio::spawn(server);
+        test_util::wait_for_tcp(grpc_addr).await;
+
+        let mut client = TraceServiceClient::connect(format!("http://{}", grpc_addr))
+            .await
+            .unwrap();
+        let req = Request::new(ExportTraceServiceRequest {
+            resource_spans: vec![ResourceSpans {
+                resource: Some(OtelResource {
+                    attributes: vec![string_attribute("res_key", "res_val")],
+                    dropped_attributes_count: 0,
+                }),
+                scope_spans: vec![ScopeSpans {
+                    scope: None,
+                    spans: vec![Span {
+                        trace_id: str_into_hex_bytes("4ac52aadf321c2e531db005df08792f5"),
+                        span_id: str_into_hex_bytes("0b9e4bda2a55530d"),
+                        trace_state: "foo=bar".into(),
+                        parent_span_id: str_into_hex_bytes("b7ad6b7169203331"),
+                        name: "GET /users".into(),
+                        kind: span::SpanKind::Server as i32,
+                        start_time_unix_nano: 1,
+                        end_time_unix_nano: 2,
+                        attributes: vec![string_attribute("attr_key", "attr_val")],
+                        dropped_attributes_count: 3,
+                        events: vec![],
+                        dropped_events_count: 4,
+                        links: vec![],
+                        dropped_links_count: 5,
+                        status: None,
+                    }],
+                    schema_url: "v1".into(),
+                }],
+                schema_url: "v1".into(),
+            }],
+        });
+        _ = client.export(req).await;
+
+        let mut output = test_util::collect_ready(traces_output).await;
+        assert_eq!(output.len(), 1);
+        let trace = output.pop().unwrap().into_trace();
+
+        assert_eq!(
+            &trace.as_map()["trace_id"],
+            &value!("4ac52aadf321c2e531db005df08792f5")
+        );
+        assert_eq!(&trace.as_map()["span_id"], &value!("0b9e4bda2a55530d"));
+        assert_eq!(
+            &trace.as_map()["parent_span_id"],
+            &value!("b7ad6b7169203331")
+        );
+        assert_eq!(&trace.as_map()["trace_state"], &value!("foo=bar"));
+        assert_eq!(&trace.as_map()["name"], &value!("GET /users"));
+        assert_eq!(&trace.as_map()["kind"], &value!(2));
+        assert_eq!(
+            &trace.as_map()["start_time_unix_nano"],
+            &value!(Utc.timestamp_nanos(1))
+        );
+        assert_eq!(
+            &trace.as_map()["end_time_unix_nano"],
+            &value!(Utc.timestamp_nanos(2))
+        );
+        assert_eq!(
+            &trace.as_map()["attributes"],
+            &value!({attr_key: "attr_val"})
+        );
+        assert_eq!(&trace.as_map()["resources"], &value!({res_key: "res_val"}));
+        assert_eq!(&trace.as_map()["dropped_attributes_count"], &value!(3));
+        assert_eq!(&trace.as_map()["dropped_events_count"], &value!(4));
+        assert_eq!(&trace.as_map()["dropped_links_count"], &value!(5));
+        assert_eq!(&trace.as_map()["source_type"], &value!("opentelemetry"));
+    })
+    .await;
+}
+
 pub(super) fn new_source(
     status: EventStatus,
 ) -> (
@@ -295,6 +645,17 @@ pub(super) fn new_source(
     (sender, logs_output, recv)
 }
 
+fn new_source_with_port(
+    status: EventStatus,
+    port: &str,
+) -> (SourceSender, impl Stream<Item = Event>) {
+    let (mut sender, _) = SourceSender::new_test_finalize(status);
+    let output = sender
+        .add_outputs(status, port.to_string())
+        .flat_map(into_event_stream);
+    (sender, output)
+}
+
 fn str_into_hex_bytes(s: &str) -> Vec<u8> {
     // unwrap is okay in test
     hex::decode(s).unwrap()
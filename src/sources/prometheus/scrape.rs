@@ -166,6 +166,7 @@ impl SourceConfig for PrometheusScrapeConfig {
             tls,
             proxy: cx.proxy.clone(),
             shutdown: cx.shutdown,
+            concurrent_scrapes: true,
         };
 
         Ok(call(inputs, builder, cx.out, HttpMethod::Get).boxed())
@@ -20,14 +20,18 @@ use vrl::value::Kind;
 
 use crate::{
     codecs::{Decoder, DecodingConfig},
-    config::{log_schema, GenerateConfig, SourceConfig, SourceContext, SourceOutput},
-    event::Event,
+    config::{
+        log_schema, GenerateConfig, SourceAcknowledgementsConfig, SourceConfig, SourceContext,
+        SourceOutput,
+    },
+    event::{BatchNotifier, Event},
     internal_events::{EventsReceived, StreamClosedError},
-    serde::{default_decoding, default_framing_message_based},
+    serde::{bool_or_struct, default_decoding, default_framing_message_based},
 };
 
 mod channel;
 mod list;
+mod stream;
 
 #[derive(Debug, Snafu)]
 enum BuildError {
@@ -49,6 +53,17 @@ pub enum DataTypeConfig {
     ///
     /// This is based on Redis' Pub/Sub capabilities.
     Channel,
+
+    /// The `stream` data type.
+    ///
+    /// Entries are read from a [stream][redis_streams] as part of a consumer group, and are
+    /// acknowledged once they have been processed. Entries that are never acknowledged, such as
+    /// when Vector stops before processing them, are claimed again after some time.
+    ///
+    /// Requires Redis 6.2 or later.
+    ///
+    /// [redis_streams]: https://redis.io/docs/data-types/streams/
+    Stream,
 }
 
 /// Options for the Redis `list` data type.
@@ -60,6 +75,68 @@ pub struct ListOption {
     method: Method,
 }
 
+/// Options for the Redis `stream` data type.
+#[configurable_component]
+#[derive(Clone, Debug, Derivative)]
+#[derivative(Default)]
+#[serde(deny_unknown_fields)]
+pub struct StreamOption {
+    /// The consumer group to read entries as.
+    ///
+    /// The group is created if it doesn't exist yet, and starts from the beginning of the stream.
+    #[serde(default = "default_group")]
+    #[derivative(Default(value = "default_group()"))]
+    #[configurable(metadata(docs::examples = "vector"))]
+    group: String,
+
+    /// The name of the consumer within the consumer group.
+    ///
+    /// Each instance of Vector reading from the same group must use a different name. If not set,
+    /// the hostname is used.
+    #[configurable(metadata(docs::examples = "vector-0"))]
+    consumer: Option<String>,
+
+    /// The field of the stream entries that contains the message.
+    ///
+    /// Entries that don't have this field are dropped.
+    #[serde(default = "default_field")]
+    #[derivative(Default(value = "default_field()"))]
+    #[configurable(metadata(docs::examples = "message"))]
+    field: String,
+
+    /// The maximum number of entries to read at once.
+    #[serde(default = "default_batch_size")]
+    #[derivative(Default(value = "default_batch_size()"))]
+    #[configurable(metadata(docs::type_unit = "entries"))]
+    batch_size: usize,
+
+    /// The amount of time, in seconds, that an entry must have been pending for before it's claimed
+    /// from the consumer it was delivered to.
+    ///
+    /// Entries are left pending when their events fail to be delivered, or when the consumer they
+    /// were delivered to stops before acknowledging them.
+    #[serde(default = "default_claim_min_idle_secs")]
+    #[derivative(Default(value = "default_claim_min_idle_secs()"))]
+    #[configurable(metadata(docs::type_unit = "seconds"))]
+    claim_min_idle_secs: u64,
+}
+
+fn default_group() -> String {
+    String::from("vector")
+}
+
+fn default_field() -> String {
+    String::from("message")
+}
+
+const fn default_batch_size() -> usize {
+    100
+}
+
+const fn default_claim_min_idle_secs() -> u64 {
+    60
+}
+
 /// Method for getting events from the `list` data type.
 #[configurable_component]
 #[derive(Clone, Copy, Debug, Derivative, Eq, PartialEq)]
@@ -98,13 +175,16 @@ impl From<&redis::ConnectionInfo> for ConnectionInfo {
 #[derive(Clone, Debug, Derivative)]
 #[serde(deny_unknown_fields)]
 pub struct RedisSourceConfig {
-    /// The Redis data type (`list` or `channel`) to use.
+    /// The Redis data type (`list`, `channel`, or `stream`) to use.
     #[serde(default)]
     data_type: DataTypeConfig,
 
     #[configurable(derived)]
     list: Option<ListOption>,
 
+    #[configurable(derived)]
+    stream: Option<StreamOption>,
+
     /// The Redis URL to connect to.
     ///
     /// The URL must take the form of `protocol://server:port/db` where the `protocol` can either be `redis` or `rediss` for connections secured using TLS.
@@ -137,6 +217,10 @@ pub struct RedisSourceConfig {
     #[configurable(metadata(docs::hidden))]
     #[serde(default)]
     log_namespace: Option<bool>,
+
+    #[configurable(derived)]
+    #[serde(default, deserialize_with = "bool_or_struct")]
+    acknowledgements: SourceAcknowledgementsConfig,
 }
 
 impl GenerateConfig for RedisSourceConfig {
@@ -176,6 +260,7 @@ impl SourceConfig for RedisSourceConfig {
             connection_info.protocol
         )));
         let events_received = register!(EventsReceived);
+        let acknowledgements = cx.do_acknowledgements(self.acknowledgements);
         let handler = InputHandler {
             client,
             bytes_received: bytes_received.clone(),
@@ -193,6 +278,10 @@ impl SourceConfig for RedisSourceConfig {
                 handler.watch(method).await
             }
             DataTypeConfig::Channel => handler.subscribe(connection_info).await,
+            DataTypeConfig::Stream => {
+                let option = self.stream.clone().unwrap_or_default();
+                handler.consume(option, acknowledgements).await
+            }
         }
     }
 
@@ -224,7 +313,8 @@ impl SourceConfig for RedisSourceConfig {
     }
 
     fn can_acknowledge(&self) -> bool {
-        false
+        // Only entries read from streams can be acknowledged.
+        matches!(self.data_type, DataTypeConfig::Stream)
     }
 }
 
@@ -240,7 +330,7 @@ pub(self) struct InputHandler {
 }
 
 impl InputHandler {
-    async fn handle_line(&mut self, line: String) -> Result<(), ()> {
+    async fn handle_line(&mut self, line: String, batch: Option<&BatchNotifier>) -> Result<(), ()> {
         let now = Utc::now();
 
         self.bytes_received.emit(ByteSize(line.len()));
@@ -277,7 +367,10 @@ impl InputHandler {
                             );
                         };
 
-                        event
+                        match batch {
+                            Some(batch) => event.with_batch_notifier(batch),
+                            None => event,
+                        }
                     });
 
                     if (self.cx.out.send_batch(events).await).is_err() {
@@ -310,11 +403,12 @@ mod test {
 
 #[cfg(all(test, feature = "redis-integration-tests"))]
 mod integration_test {
-    use redis::AsyncCommands;
+    use redis::{streams::StreamPendingReply, AsyncCommands};
 
     use super::*;
     use crate::{
         config::log_schema,
+        event::EventStatus,
         test_util::{
             collect_n,
             components::{run_and_assert_source_compliance_n, SOURCE_TAGS},
@@ -345,12 +439,14 @@ mod integration_test {
             list: Some(ListOption {
                 method: Method::Rpop,
             }),
+            stream: None,
             url: REDIS_SERVER.to_owned(),
             key: key.clone(),
             redis_key: None,
             framing: default_framing_message_based(),
             decoding: default_decoding(),
             log_namespace: Some(false),
+            acknowledgements: Default::default(),
         };
 
         let events = run_and_assert_source_compliance_n(config, 3, &SOURCE_TAGS).await;
@@ -386,12 +482,14 @@ mod integration_test {
             list: Some(ListOption {
                 method: Method::Rpop,
             }),
+            stream: None,
             url: REDIS_SERVER.to_owned(),
             key: key.clone(),
             redis_key: Some(OptionalValuePath::from(owned_value_path!("remapped_key"))),
             framing: default_framing_message_based(),
             decoding: default_decoding(),
             log_namespace: Some(true),
+            acknowledgements: Default::default(),
         };
 
         let events = run_and_assert_source_compliance_n(config, 1, &SOURCE_TAGS).await;
@@ -427,12 +525,14 @@ mod integration_test {
             list: Some(ListOption {
                 method: Method::Lpop,
             }),
+            stream: None,
             url: REDIS_SERVER.to_owned(),
             key: key.clone(),
             redis_key: None,
             framing: default_framing_message_based(),
             decoding: default_decoding(),
             log_namespace: Some(false),
+            acknowledgements: Default::default(),
         };
 
         let events = run_and_assert_source_compliance_n(config, 3, &SOURCE_TAGS).await;
@@ -460,12 +560,14 @@ mod integration_test {
         let config = RedisSourceConfig {
             data_type: DataTypeConfig::Channel,
             list: None,
+            stream: None,
             url: REDIS_SERVER.to_owned(),
             key: key.clone(),
             redis_key: None,
             framing: default_framing_message_based(),
             decoding: default_decoding(),
             log_namespace: Some(false),
+            acknowledgements: Default::default(),
         };
 
         let (tx, rx) = SourceSender::new_test();
@@ -509,4 +611,84 @@ mod integration_test {
             );
         }
     }
+
+    fn stream_config(key: &str, acknowledgements: bool) -> RedisSourceConfig {
+        RedisSourceConfig {
+            data_type: DataTypeConfig::Stream,
+            list: None,
+            stream: Some(StreamOption {
+                consumer: Some(String::from("vector-test")),
+                claim_min_idle_secs: 1,
+                ..Default::default()
+            }),
+            url: REDIS_SERVER.to_owned(),
+            key: key.to_owned(),
+            redis_key: None,
+            framing: default_framing_message_based(),
+            decoding: default_decoding(),
+            log_namespace: Some(false),
+            acknowledgements: acknowledgements.into(),
+        }
+    }
+
+    #[tokio::test]
+    async fn redis_source_stream_consume_and_ack() {
+        let client = redis::Client::open(REDIS_SERVER).unwrap();
+        let mut conn = client.get_tokio_connection_manager().await.unwrap();
+
+        let key = format!("test-stream-{}", random_string(10));
+        debug!("Test key name: {}.", key);
+
+        // Added before the consumer group exists, to make sure it starts from the beginning.
+        for i in 1..=3 {
+            let _: String = conn
+                .xadd(&key, "*", &[("message", i.to_string())])
+                .await
+                .unwrap();
+        }
+
+        let events =
+            run_and_assert_source_compliance_n(stream_config(&key, false), 3, &SOURCE_TAGS).await;
+
+        for (i, event) in events.iter().enumerate() {
+            assert_eq!(
+                event.as_log()[log_schema().message_key().unwrap().to_string()],
+                (i + 1).to_string().into()
+            );
+        }
+
+        tokio::time::sleep(tokio::time::Duration::from_secs(1)).await;
+        let pending: StreamPendingReply = conn.xpending(&key, "vector").await.unwrap();
+        assert_eq!(pending.count(), 0);
+    }
+
+    #[tokio::test]
+    async fn redis_source_stream_redelivers_errored_entries() {
+        let client = redis::Client::open(REDIS_SERVER).unwrap();
+        let mut conn = client.get_tokio_connection_manager().await.unwrap();
+
+        let key = format!("test-stream-{}", random_string(10));
+        let _: String = conn
+            .xadd(&key, "*", &[("message", "retry me")])
+            .await
+            .unwrap();
+
+        let (tx, rx) = SourceSender::new_test_finalize(EventStatus::Errored);
+        let context = SourceContext::new_test(tx, None);
+        let source = stream_config(&key, true)
+            .build(context)
+            .await
+            .expect("source should not fail to build");
+        tokio::spawn(source);
+
+        // The entry is left pending when its events fail to be delivered, and is then claimed
+        // again.
+        let events = collect_n(rx, 2).await;
+        for event in events {
+            assert_eq!(
+                event.as_log()[log_schema().message_key().unwrap().to_string()],
+                "retry me".into()
+            );
+        }
+    }
 }
@@ -0,0 +1,326 @@
+use std::{
+    collections::HashMap,
+    time::{Duration, Instant},
+};
+
+use futures::StreamExt;
+use redis::{
+    aio::ConnectionManager,
+    streams::{StreamId, StreamReadOptions, StreamReadReply},
+    AsyncCommands, ErrorKind, FromRedisValue, RedisError, RedisResult, Value,
+};
+use snafu::{ResultExt, Snafu};
+use vector_common::finalizer::UnorderedFinalizer;
+
+use super::{InputHandler, StreamOption};
+use crate::{
+    event::{BatchNotifier, BatchStatus},
+    internal_events::{RedisAckEventError, RedisReceiveEventError},
+    sources::Source,
+};
+
+/// The amount of time a read blocks for while waiting for new entries.
+///
+/// This bounds how long it takes to notice that stuck entries need to be claimed.
+const BLOCK_MS: usize = 1_000;
+
+#[derive(Debug, Snafu)]
+enum BuildError {
+    #[snafu(display("Failed to create connection: {}", source))]
+    Connection { source: RedisError },
+    #[snafu(display("Failed to create consumer group: {}", source))]
+    CreateGroup { source: RedisError },
+    #[snafu(display("Failed to get hostname for the consumer name: {}", source))]
+    Hostname { source: std::io::Error },
+}
+
+impl InputHandler {
+    pub(super) async fn consume(
+        mut self,
+        option: StreamOption,
+        acknowledgements: bool,
+    ) -> crate::Result<Source> {
+        let mut conn = self
+            .client
+            .get_tokio_connection_manager()
+            .await
+            .context(ConnectionSnafu {})?;
+        // Reads block the connection they are sent on, so acknowledgements use their own.
+        let ack_conn = self
+            .client
+            .get_tokio_connection_manager()
+            .await
+            .context(ConnectionSnafu {})?;
+
+        let consumer = match option.consumer {
+            Some(consumer) => consumer,
+            None => crate::get_hostname().context(HostnameSnafu {})?,
+        };
+
+        // Consumer groups that are created start from the beginning of the stream, so that entries
+        // added before Vector first ran are consumed as well.
+        if let Err(error) = conn
+            .xgroup_create_mkstream::<_, _, _, ()>(&self.key, &option.group, "0")
+            .await
+        {
+            if error.code() != Some("BUSYGROUP") {
+                return Err(BuildError::CreateGroup { source: error }.into());
+            }
+        }
+
+        let acker = Acker {
+            conn: ack_conn,
+            key: self.key.clone(),
+            group: option.group.clone(),
+        };
+        let read_options = StreamReadOptions::default()
+            .group(&option.group, &consumer)
+            .count(option.batch_size)
+            .block(BLOCK_MS);
+        let claim_min_idle = Duration::from_secs(option.claim_min_idle_secs);
+
+        Ok(Box::pin(async move {
+            let mut shutdown = self.cx.shutdown.clone();
+            let (finalizer, mut ack_stream) =
+                UnorderedFinalizer::<String>::maybe_new(acknowledgements, Some(shutdown.clone()));
+            if finalizer.is_some() {
+                let acker = acker.clone();
+                tokio::spawn(async move {
+                    while let Some((status, id)) = ack_stream.next().await {
+                        acker.acknowledge(status, &id).await;
+                    }
+                });
+            }
+
+            // Entries that were delivered to this consumer before it was last stopped, but never
+            // acknowledged, are read first. Afterwards, only new entries are read.
+            let mut read_id = String::from("0");
+            let mut claim_cursor = String::from("0-0");
+            let mut last_claim = Instant::now();
+            loop {
+                // Entries are claimed in passes over all pending entries, with a pass starting
+                // whenever entries may have become idle for long enough since the last one.
+                let claiming = claim_cursor != "0-0" || last_claim.elapsed() >= claim_min_idle;
+                let res = if claiming {
+                    let res = tokio::select! {
+                        res = autoclaim(
+                            &mut conn,
+                            &self.key,
+                            &option.group,
+                            &consumer,
+                            claim_min_idle,
+                            &claim_cursor,
+                            option.batch_size,
+                        ) => res,
+                        _ = &mut shutdown => break
+                    };
+                    res.map(|(next, entries)| {
+                        if next == "0-0" {
+                            last_claim = Instant::now();
+                        }
+                        claim_cursor = next;
+                        entries
+                    })
+                } else {
+                    let res = tokio::select! {
+                        res = conn.xread_options::<_, _, StreamReadReply>(
+                            &[&self.key],
+                            &[&read_id],
+                            &read_options,
+                        ) => res,
+                        _ = &mut shutdown => break
+                    };
+                    res.map(|reply| {
+                        let entries = reply
+                            .keys
+                            .into_iter()
+                            .flat_map(|key| key.ids)
+                            .collect::<Vec<_>>();
+                        if read_id != ">" {
+                            read_id = entries
+                                .last()
+                                .map_or_else(|| String::from(">"), |entry| entry.id.clone());
+                        }
+                        entries
+                    })
+                };
+
+                let entries = match res {
+                    Ok(entries) => entries,
+                    Err(error) => {
+                        let kind = error.kind();
+                        emit!(RedisReceiveEventError::from(error));
+                        if kind == ErrorKind::IoError {
+                            tokio::time::sleep(Duration::from_secs(1)).await;
+                        }
+                        continue;
+                    }
+                };
+
+                for entry in entries {
+                    let line = match entry.get::<String>(&option.field) {
+                        Some(line) => line,
+                        None => {
+                            // The entry can never be processed, so there's no point in having it
+                            // claimed over and over again.
+                            emit!(RedisReceiveEventError::from(RedisError::from((
+                                ErrorKind::TypeError,
+                                "Stream entry is missing the message field",
+                                entry.id.clone(),
+                            ))));
+                            acker.acknowledge(BatchStatus::Rejected, &entry.id).await;
+                            continue;
+                        }
+                    };
+
+                    match &finalizer {
+                        Some(finalizer) => {
+                            let (batch, receiver) = BatchNotifier::new_with_receiver();
+                            if let Err(()) = self.handle_line(line, Some(&batch)).await {
+                                return Ok(());
+                            }
+                            finalizer.add(entry.id, receiver);
+                        }
+                        None => {
+                            if let Err(()) = self.handle_line(line, None).await {
+                                return Ok(());
+                            }
+                            acker.acknowledge(BatchStatus::Delivered, &entry.id).await;
+                        }
+                    }
+                }
+            }
+            Ok(())
+        }))
+    }
+}
+
+#[derive(Clone)]
+struct Acker {
+    conn: ConnectionManager,
+    key: String,
+    group: String,
+}
+
+impl Acker {
+    async fn acknowledge(&self, status: BatchStatus, id: &str) {
+        // Entries whose events failed to be delivered are left pending, so that they are claimed
+        // again once they have been idle for long enough. Rejected events will never be delivered,
+        // so there is no point in having their entries claimed again.
+        match status {
+            BatchStatus::Delivered | BatchStatus::Rejected => {
+                let mut conn = self.conn.clone();
+                if let Err(error) = conn
+                    .xack::<_, _, _, ()>(&self.key, &self.group, &[id])
+                    .await
+                {
+                    emit!(RedisAckEventError::from(error));
+                }
+            }
+            BatchStatus::Errored => {}
+        }
+    }
+}
+
+/// Claims entries that have been pending for other consumers for longer than `min_idle`.
+///
+/// Returns the cursor to continue claiming from, which is `0-0` once all pending entries have been
+/// looked at, along with the claimed entries.
+async fn autoclaim(
+    conn: &mut ConnectionManager,
+    key: &str,
+    group: &str,
+    consumer: &str,
+    min_idle: Duration,
+    cursor: &str,
+    count: usize,
+) -> RedisResult<(String, Vec<StreamId>)> {
+    let reply: Value = redis::cmd("XAUTOCLAIM")
+        .arg(key)
+        .arg(group)
+        .arg(consumer)
+        .arg(min_idle.as_millis() as u64)
+        .arg(cursor)
+        .arg("COUNT")
+        .arg(count)
+        .query_async(conn)
+        .await?;
+    parse_autoclaim_reply(&reply)
+}
+
+/// Parses the reply of `XAUTOCLAIM`.
+///
+/// Redis 7 adds the IDs of entries that were deleted while pending as a third element, which isn't
+/// needed here, so the reply isn't parsed as a tuple.
+fn parse_autoclaim_reply(reply: &Value) -> RedisResult<(String, Vec<StreamId>)> {
+    let invalid = || {
+        RedisError::from((
+            ErrorKind::TypeError,
+            "Unexpected XAUTOCLAIM reply",
+            format!("{:?}", reply),
+        ))
+    };
+
+    let items = match reply {
+        Value::Bulk(items) if items.len() >= 2 => items,
+        _ => return Err(invalid()),
+    };
+    let cursor = String::from_redis_value(&items[0])?;
+    let entries = match &items[1] {
+        Value::Bulk(entries) => entries,
+        _ => return Err(invalid()),
+    };
+    let entries = entries
+        .iter()
+        // Entries that were deleted while pending are returned without any fields by Redis 6.
+        .filter(|entry| !matches!(entry, Value::Bulk(parts) if matches!(parts.get(1), Some(Value::Nil))))
+        .map(|entry| {
+            let (id, map): (String, HashMap<String, Value>) =
+                FromRedisValue::from_redis_value(entry)?;
+            Ok(StreamId { id, map })
+        })
+        .collect::<RedisResult<_>>()?;
+
+    Ok((cursor, entries))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn data(s: &str) -> Value {
+        Value::Data(s.as_bytes().to_vec())
+    }
+
+    #[test]
+    fn parses_autoclaim_reply() {
+        let entry = Value::Bulk(vec![
+            data("1-0"),
+            Value::Bulk(vec![data("message"), data("hello")]),
+        ]);
+        let deleted = Value::Bulk(vec![data("2-0"), Value::Nil]);
+
+        // Redis 6.2
+        let (cursor, entries) = parse_autoclaim_reply(&Value::Bulk(vec![
+            data("3-0"),
+            Value::Bulk(vec![entry.clone(), deleted]),
+        ]))
+        .unwrap();
+        assert_eq!(cursor, "3-0");
+        assert_eq!(entries.len(), 1);
+        assert_eq!(entries[0].id, "1-0");
+        assert_eq!(entries[0].get::<String>("message").unwrap(), "hello");
+
+        // Redis 7
+        let (cursor, entries) = parse_autoclaim_reply(&Value::Bulk(vec![
+            data("0-0"),
+            Value::Bulk(vec![entry]),
+            Value::Bulk(vec![data("2-0")]),
+        ]))
+        .unwrap();
+        assert_eq!(cursor, "0-0");
+        assert_eq!(entries.len(), 1);
+
+        assert!(parse_autoclaim_reply(&Value::Nil).is_err());
+    }
+}
@@ -124,7 +124,7 @@ impl SourceConfig for SocketConfig {
                     decoding,
                     log_namespace,
                 )
-                .build()?;
+                .build_stream()?;
 
                 let tcp = tcp::RawTcpSource::new(config.clone(), decoder, log_namespace);
                 let tls_config = config.tls().as_ref().map(|tls| tls.tls_config.clone());
@@ -193,7 +193,7 @@ impl SourceConfig for SocketConfig {
                     decoding,
                     log_namespace,
                 )
-                .build()?;
+                .build_stream()?;
 
                 unix::unix_stream(config, decoder, cx.shutdown, cx.out, log_namespace)
             }
@@ -332,6 +332,7 @@ mod test {
     };
 
     use bytes::{BufMut, Bytes, BytesMut};
+    use codecs::ChunkedGelfDecoderConfig;
     use codecs::NewlineDelimitedDecoderConfig;
     #[cfg(unix)]
     use codecs::{decoding::CharacterDelimitedDecoderOptions, CharacterDelimitedDecoderConfig};
@@ -551,6 +552,18 @@ mod test {
         .await;
     }
 
+    #[tokio::test]
+    async fn tcp_rejects_chunked_gelf_framing() {
+        let mut config = TcpConfig::from_address(next_addr().into());
+        config.set_framing(Some(ChunkedGelfDecoderConfig::default().into()));
+
+        let (tx, _rx) = SourceSender::new_test();
+        let result = SocketConfig::from(config)
+            .build(SourceContext::new_test(tx, None))
+            .await;
+        assert!(result.is_err());
+    }
+
     #[tokio::test]
     async fn tcp_with_tls() {
         assert_source_compliance(&SOCKET_HIGH_CARDINALITY_PUSH_SOURCE_TAGS, async {
@@ -8,7 +8,7 @@ use hyper::Body;
 use std::{convert::Infallible, net::SocketAddr};
 use tonic::{
     body::BoxBody,
-    transport::server::{NamedService, Server},
+    transport::server::{NamedService, Routes, Server},
 };
 use tower::Service;
 use tracing::{Instrument, Span};
@@ -58,3 +58,33 @@ where
 
     Ok(())
 }
+
+/// Runs a gRPC server serving multiple services at once.
+///
+/// This is the same as [`run_grpc_server`], except that the caller provides the full set of
+/// [`Routes`] to serve, allowing a single listener to expose more than one service.
+pub async fn run_grpc_server_with_routes(
+    address: SocketAddr,
+    tls_settings: MaybeTlsSettings,
+    routes: Routes,
+    shutdown: ShutdownSignal,
+) -> crate::Result<()> {
+    let span = Span::current();
+    let (tx, rx) = tokio::sync::oneshot::channel::<ShutdownSignalToken>();
+    let listener = tls_settings.bind(&address).await?;
+    let stream = listener.accept_stream();
+
+    info!(%address, "Building gRPC server.");
+
+    Server::builder()
+        .trace_fn(move |_| span.clone())
+        .layer(DecompressionAndMetricsLayer)
+        .add_routes(routes)
+        .serve_with_incoming_shutdown(stream, shutdown.map(|token| tx.send(token).unwrap()))
+        .in_current_span()
+        .await?;
+
+    drop(rx.await);
+
+    Ok(())
+}
@@ -9,11 +9,12 @@
 //!     context.
 
 use bytes::Bytes;
-use futures_util::{stream, FutureExt, StreamExt, TryFutureExt};
+use futures_util::{future::BoxFuture, stream, Stream, StreamExt};
 use http::{response::Parts, Uri};
 use hyper::{Body, Request};
+use std::collections::HashMap;
+use std::sync::Arc;
 use std::time::{Duration, Instant};
-use std::{collections::HashMap, future::ready};
 use tokio_stream::wrappers::IntervalStream;
 use vector_common::json_size::JsonSize;
 
@@ -46,6 +47,8 @@ pub(crate) struct GenericHttpClientInputs {
     pub tls: TlsSettings,
     pub proxy: ProxyConfig,
     pub shutdown: ShutdownSignal,
+    /// Whether a scrape can start while the previous scrape of the same URL is still running.
+    pub concurrent_scrapes: bool,
 }
 
 /// The default interval to call the HTTP endpoint if none is configured.
@@ -79,6 +82,34 @@ pub(crate) trait HttpClientContext {
     // metadata. This function should be used rather than internal enrichment so
     // that accurate byte count metrics can be emitted.
     fn enrich_events(&mut self, _events: &mut Vec<Event>) {}
+
+    /// (Optional) Called before each HTTP request to get the URL and body to send.
+    ///
+    /// `url` is the URL being scraped for the first request of a scrape, and the URL returned by
+    /// `next_page` for the following ones. Returning `None` ends the scrape without a request.
+    fn build_request(&mut self, url: &Uri) -> Option<(Uri, Bytes)> {
+        Some((url.clone(), Bytes::new()))
+    }
+
+    /// (Optional) Called after each successful HTTP response, before the events are enriched, to
+    /// get the URL of the next page of the scrape. Returning `None` ends the scrape, which is the
+    /// default.
+    fn next_page(
+        &mut self,
+        _url: &Uri,
+        _header: &Parts,
+        _body: &Bytes,
+        _events: &[Event],
+    ) -> Option<Uri> {
+        None
+    }
+
+    /// (Optional) Called once a scrape ends, after all of its events are sent. The scrape only
+    /// completes, and the next one of the same URL can only start, once the returned future
+    /// resolves, which lets the context wait for the events to be delivered.
+    fn on_scrape_end(&mut self) -> Option<BoxFuture<'static, ()>> {
+        None
+    }
 }
 
 /// Builds a url for the HTTP requests.
@@ -136,125 +167,24 @@ pub(crate) async fn call<
     // proxy and tls settings.
     let client =
         HttpClient::new(inputs.tls.clone(), &inputs.proxy).expect("Building HTTP client failed");
+    let settings = Arc::new(RequestSettings {
+        method: http_method,
+        headers: inputs.headers,
+        content_type: inputs.content_type,
+        auth: inputs.auth,
+        timeout: inputs.timeout,
+    });
+    // Scrapes that depend on the outcome of the previous one must not overlap.
+    let max_concurrent_scrapes = (!inputs.concurrent_scrapes).then_some(1);
     let mut stream = IntervalStream::new(tokio::time::interval(inputs.interval))
         .take_until(inputs.shutdown)
         .map(move |_| stream::iter(inputs.urls.clone()))
         .flatten()
         .map(move |url| {
-            let client = client.clone();
-            let endpoint = url.to_string();
-
-            let context_builder = context_builder.clone();
-            let mut context = context_builder.build(&url);
-
-            let mut builder = match http_method {
-                HttpMethod::Head => Request::head(&url),
-                HttpMethod::Get => Request::get(&url),
-                HttpMethod::Post => Request::post(&url),
-                HttpMethod::Put => Request::put(&url),
-                HttpMethod::Patch => Request::patch(&url),
-                HttpMethod::Delete => Request::delete(&url),
-            };
-
-            // add user specified headers
-            for (header, values) in &inputs.headers {
-                for value in values {
-                    builder = builder.header(header, value);
-                }
-            }
-
-            // set ACCEPT header if not user specified
-            if !inputs.headers.contains_key(http::header::ACCEPT.as_str()) {
-                builder = builder.header(http::header::ACCEPT, &inputs.content_type);
-            }
-
-            // building an empty request should be infallible
-            let mut request = builder.body(Body::empty()).expect("error creating request");
-
-            if let Some(auth) = &inputs.auth {
-                auth.apply(&mut request);
-            }
-
-            let start = Instant::now();
-            tokio::time::timeout(inputs.timeout, client.send(request))
-                .then(move |result| async move {
-                    match result {
-                        Ok(Ok(response)) => Ok(response),
-                        Ok(Err(error)) => Err(error.into()),
-                        Err(_) => Err(format!(
-                            "Timeout error: request exceeded {}s",
-                            inputs.timeout.as_secs_f64()
-                        )
-                        .into()),
-                    }
-                })
-                .and_then(|response| async move {
-                    let (header, body) = response.into_parts();
-                    let body = hyper::body::to_bytes(body).await?;
-                    emit!(EndpointBytesReceived {
-                        byte_size: body.len(),
-                        protocol: "http",
-                        endpoint: endpoint.as_str(),
-                    });
-                    Ok((header, body))
-                })
-                .into_stream()
-                .filter_map(move |response| {
-                    ready(match response {
-                        Ok((header, body)) if header.status == hyper::StatusCode::OK => {
-                            emit!(RequestCompleted {
-                                start,
-                                end: Instant::now()
-                            });
-                            context.on_response(&url, &header, &body).map(|mut events| {
-                                let byte_size = if events.is_empty() {
-                                    // We need to explicitly set the byte size
-                                    // to 0 since
-                                    // `estimated_json_encoded_size_of` returns
-                                    // at least 1 for an empty collection. For
-                                    // the purposes of the
-                                    // HttpClientEventsReceived event, we should
-                                    // emit 0 when there aren't any usable
-                                    // metrics.
-                                    JsonSize::zero()
-                                } else {
-                                    events.estimated_json_encoded_size_of()
-                                };
-
-                                emit!(HttpClientEventsReceived {
-                                    byte_size,
-                                    count: events.len(),
-                                    url: url.to_string()
-                                });
-
-                                // We'll enrich after receiving the events so
-                                // that the byte sizes are accurate.
-                                context.enrich_events(&mut events);
-
-                                stream::iter(events)
-                            })
-                        }
-                        Ok((header, _)) => {
-                            context.on_http_response_error(&url, &header);
-                            emit!(HttpClientHttpResponseError {
-                                code: header.status,
-                                url: url.to_string(),
-                            });
-                            None
-                        }
-                        Err(error) => {
-                            emit!(HttpClientHttpError {
-                                error,
-                                url: url.to_string()
-                            });
-                            None
-                        }
-                    })
-                })
-                .flatten()
-                .boxed()
+            let context = context_builder.build(&url);
+            scrape(client.clone(), Arc::clone(&settings), url, context).boxed()
         })
-        .flatten_unordered(None)
+        .flatten_unordered(max_concurrent_scrapes)
         .boxed();
 
     match out.send_event_stream(&mut stream).await {
@@ -269,3 +199,155 @@ pub(crate) async fn call<
         }
     }
 }
+
+/// The settings applied to every HTTP request of the source.
+struct RequestSettings {
+    method: HttpMethod,
+    headers: HashMap<String, Vec<String>>,
+    content_type: String,
+    auth: Option<Auth>,
+    timeout: Duration,
+}
+
+impl RequestSettings {
+    fn build_request(&self, url: &Uri, body: Bytes) -> Request<Body> {
+        let mut builder = match self.method {
+            HttpMethod::Head => Request::head(url),
+            HttpMethod::Get => Request::get(url),
+            HttpMethod::Post => Request::post(url),
+            HttpMethod::Put => Request::put(url),
+            HttpMethod::Patch => Request::patch(url),
+            HttpMethod::Delete => Request::delete(url),
+        };
+
+        // add user specified headers
+        for (header, values) in &self.headers {
+            for value in values {
+                builder = builder.header(header, value);
+            }
+        }
+
+        // set ACCEPT header if not user specified
+        if !self.headers.contains_key(http::header::ACCEPT.as_str()) {
+            builder = builder.header(http::header::ACCEPT, &self.content_type);
+        }
+
+        let body = if body.is_empty() {
+            Body::empty()
+        } else {
+            Body::from(body)
+        };
+
+        // building a request from a valid URL should be infallible
+        let mut request = builder.body(body).expect("error creating request");
+
+        if let Some(auth) = &self.auth {
+            auth.apply(&mut request);
+        }
+
+        request
+    }
+
+    async fn send(
+        &self,
+        client: &HttpClient,
+        request: Request<Body>,
+    ) -> crate::Result<(Parts, Bytes)> {
+        let endpoint = request.uri().to_string();
+        let response = match tokio::time::timeout(self.timeout, client.send(request)).await {
+            Ok(Ok(response)) => response,
+            Ok(Err(error)) => return Err(error.into()),
+            Err(_) => {
+                return Err(format!(
+                    "Timeout error: request exceeded {}s",
+                    self.timeout.as_secs_f64()
+                )
+                .into())
+            }
+        };
+
+        let (header, body) = response.into_parts();
+        let body = hyper::body::to_bytes(body).await?;
+        emit!(EndpointBytesReceived {
+            byte_size: body.len(),
+            protocol: "http",
+            endpoint: endpoint.as_str(),
+        });
+        Ok((header, body))
+    }
+}
+
+/// Runs a single scrape of a URL, following its pages for as long as the context returns one.
+fn scrape<C: HttpClientContext + Send>(
+    client: HttpClient,
+    settings: Arc<RequestSettings>,
+    url: Uri,
+    mut context: C,
+) -> impl Stream<Item = Event> + Send {
+    async_stream::stream! {
+        let mut next_url = Some(url);
+        while let Some(page_url) = next_url.take() {
+            let Some((url, body)) = context.build_request(&page_url) else {
+                break;
+            };
+            let request = settings.build_request(&url, body);
+
+            let start = Instant::now();
+            match settings.send(&client, request).await {
+                Ok((header, body)) if header.status == hyper::StatusCode::OK => {
+                    emit!(RequestCompleted {
+                        start,
+                        end: Instant::now()
+                    });
+                    let Some(mut events) = context.on_response(&url, &header, &body) else {
+                        break;
+                    };
+
+                    let byte_size = if events.is_empty() {
+                        // We need to explicitly set the byte size to 0 since
+                        // `estimated_json_encoded_size_of` returns at least 1
+                        // for an empty collection. For the purposes of the
+                        // HttpClientEventsReceived event, we should emit 0 when
+                        // there aren't any usable metrics.
+                        JsonSize::zero()
+                    } else {
+                        events.estimated_json_encoded_size_of()
+                    };
+
+                    emit!(HttpClientEventsReceived {
+                        byte_size,
+                        count: events.len(),
+                        url: url.to_string()
+                    });
+
+                    next_url = context.next_page(&url, &header, &body, &events);
+
+                    // We'll enrich after receiving the events so that the byte
+                    // sizes are accurate.
+                    context.enrich_events(&mut events);
+
+                    for event in events {
+                        yield event;
+                    }
+                }
+                Ok((header, _)) => {
+                    context.on_http_response_error(&url, &header);
+                    emit!(HttpClientHttpResponseError {
+                        code: header.status,
+                        url: url.to_string(),
+                    });
+                }
+                Err(error) => {
+                    emit!(HttpClientHttpError {
+                        error,
+                        url: url.to_string()
+                    });
+                }
+            }
+        }
+
+        if let Some(end) = context.on_scrape_end() {
+            end.await;
+        }
+    }
+}
@@ -422,6 +422,28 @@ pub fn lines_from_zstd_file<P: AsRef<Path>>(path: P) -> Vec<String> {
     output.lines().map(|s| s.to_owned()).collect()
 }
 
+#[cfg(test)]
+pub fn lines_from_lz4_file<P: AsRef<Path>>(path: P) -> Vec<String> {
+    trace!(message = "Reading lz4 file.", path = %path.as_ref().display());
+    let file = File::open(path).unwrap();
+    let mut output = String::new();
+    lz4_flex::frame::FrameDecoder::new(file)
+        .read_to_string(&mut output)
+        .unwrap();
+    output.lines().map(|s| s.to_owned()).collect()
+}
+
+#[cfg(test)]
+pub fn lines_from_snappy_framed_file<P: AsRef<Path>>(path: P) -> Vec<String> {
+    trace!(message = "Reading snappy file.", path = %path.as_ref().display());
+    let file = File::open(path).unwrap();
+    let mut output = String::new();
+    snap::read::FrameDecoder::new(file)
+        .read_to_string(&mut output)
+        .unwrap();
+    output.lines().map(|s| s.to_owned()).collect()
+}
+
 pub fn runtime() -> runtime::Runtime {
     runtime::Builder::new_multi_thread()
         .enable_all()
@@ -27,7 +27,7 @@ use vector_core::{
             builder::TopologyBuilder,
             channel::{BufferReceiver, BufferSender},
         },
-        BufferType, WhenFull,
+        WhenFull,
     },
     schema::Definition,
     EstimatedJsonEncodedSizeOf,
@@ -541,10 +541,12 @@ impl<'a> Builder<'a> {
             let (tx, rx) = if let Some(buffer) = self.buffers.remove(key) {
                 buffer
             } else {
-                let buffer_type = match sink.buffer.stages().first().expect("cant ever be empty") {
-                    BufferType::Memory { .. } => "memory",
-                    BufferType::DiskV2 { .. } => "disk",
-                };
+                let buffer_type = sink
+                    .buffer
+                    .stages()
+                    .first()
+                    .expect("cant ever be empty")
+                    .type_name();
                 let buffer_span = error_span!("sink", buffer_type);
                 let buffer = sink
                     .buffer
@@ -1,10 +1,12 @@
 use std::{
-    collections::{hash_map::Entry, HashMap},
+    collections::{hash_map::Entry, HashMap, VecDeque},
+    num::NonZeroU32,
     pin::Pin,
     time::Duration,
 };
 
 use async_stream::stream;
+use chrono::{DateTime, Utc};
 use futures::{Stream, StreamExt};
 use vector_config::configurable_component;
 use vector_core::config::LogNamespace;
@@ -28,6 +30,79 @@ pub struct AggregateConfig {
     #[serde(default = "default_interval_ms")]
     #[configurable(metadata(docs::human_name = "Flush Interval"))]
     pub interval_ms: u64,
+
+    #[configurable(derived)]
+    #[serde(default)]
+    pub mode: AggregationMode,
+
+    /// The length of the aggregation window, in milliseconds.
+    ///
+    /// When larger than `interval_ms`, windows slide: every flush emits the aggregation of the
+    /// metrics received during the last `window_ms`. This must be a multiple of `interval_ms`.
+    ///
+    /// By default, the window is the same as the flush interval, and every metric is only emitted
+    /// once.
+    #[serde(default)]
+    #[configurable(metadata(docs::human_name = "Window Length"))]
+    #[configurable(metadata(docs::examples = 60000))]
+    pub window_ms: Option<u64>,
+
+    /// Whether to convert incremental counters into per-second rates.
+    ///
+    /// The aggregated value of each incremental counter is divided by the time it was aggregated
+    /// over, in seconds, and emitted as an absolute gauge. That's the window length, except until
+    /// a sliding window fills up.
+    #[serde(default)]
+    pub rate: bool,
+
+    #[configurable(derived)]
+    #[serde(default)]
+    pub timestamp: WindowTimestamp,
+}
+
+/// The function used to aggregate the metrics of a series within a window.
+///
+/// Except for `auto` and `latest`, the modes only change how counters and gauges are aggregated.
+/// Other metric types are aggregated as with `auto`.
+#[configurable_component]
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum AggregationMode {
+    /// Incremental metrics are added together, and absolute metrics are replaced by the latest
+    /// value.
+    #[default]
+    Auto,
+
+    /// Values are added together, whether the metrics are incremental or absolute.
+    Sum,
+
+    /// The largest value is kept.
+    Max,
+
+    /// The smallest value is kept.
+    Min,
+
+    /// Values are averaged.
+    Mean,
+
+    /// The latest value is kept, whether the metrics are incremental or absolute.
+    Latest,
+}
+
+/// The timestamp of the aggregated metrics.
+#[configurable_component]
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum WindowTimestamp {
+    /// Keep the timestamps of the aggregated metrics.
+    #[default]
+    Unchanged,
+
+    /// Set the timestamp to the start of the window, and the interval to the window length.
+    WindowStart,
+
+    /// Set the timestamp to the end of the window, and the interval to the window length.
+    WindowEnd,
 }
 
 const fn default_interval_ms() -> u64 {
@@ -57,44 +132,153 @@ impl TransformConfig for AggregateConfig {
     }
 }
 
-type MetricEntry = (metric::MetricData, EventMetadata);
+#[derive(Clone, Debug)]
+struct MetricEntry {
+    data: metric::MetricData,
+    metadata: EventMetadata,
+    /// The number of metrics aggregated into this entry, used to compute averages.
+    count: usize,
+}
+
+impl MetricEntry {
+    const fn new(data: metric::MetricData, metadata: EventMetadata) -> Self {
+        Self {
+            data,
+            metadata,
+            count: 1,
+        }
+    }
+
+    const fn is_scalar(&self) -> bool {
+        matches!(
+            self.data.value,
+            metric::MetricValue::Counter { .. } | metric::MetricValue::Gauge { .. }
+        )
+    }
+
+    /// Aggregates the more recent `other` entry into this one.
+    fn merge(&mut self, other: Self, mode: AggregationMode) {
+        let mode = if self.is_scalar() && other.is_scalar() {
+            mode
+        } else if mode == AggregationMode::Latest {
+            mode
+        } else {
+            AggregationMode::Auto
+        };
+
+        match mode {
+            AggregationMode::Auto => match other.data.kind {
+                metric::MetricKind::Incremental => self.add(other),
+                // Always replace
+                metric::MetricKind::Absolute => *self = other,
+            },
+            AggregationMode::Sum | AggregationMode::Mean => self.add(other),
+            AggregationMode::Max | AggregationMode::Min => {
+                if self.data.kind != other.data.kind
+                    || std::mem::discriminant(&self.data.value)
+                        != std::mem::discriminant(&other.data.value)
+                {
+                    emit!(AggregateUpdateFailed);
+                    *self = other;
+                    return;
+                }
+
+                let replace = match mode {
+                    AggregationMode::Max => scalar(&other.data) > scalar(&self.data),
+                    _ => scalar(&other.data) < scalar(&self.data),
+                };
+                self.metadata.merge(other.metadata);
+                self.count += other.count;
+                if replace {
+                    self.data = other.data;
+                }
+            }
+            AggregationMode::Latest => *self = other,
+        }
+    }
+
+    fn add(&mut self, other: Self) {
+        // In order to update (add) the new and old kind's must match
+        if self.data.kind == other.data.kind && self.data.update(&other.data) {
+            self.metadata.merge(other.metadata);
+            self.count += other.count;
+        } else {
+            emit!(AggregateUpdateFailed);
+            *self = other;
+        }
+    }
+}
+
+fn scalar(data: &metric::MetricData) -> f64 {
+    match data.value {
+        metric::MetricValue::Counter { value } | metric::MetricValue::Gauge { value } => value,
+        _ => 0.0,
+    }
+}
+
+/// The metrics received during one flush interval.
+#[derive(Debug)]
+struct Window {
+    start: DateTime<Utc>,
+    map: HashMap<metric::MetricSeries, MetricEntry>,
+}
+
+impl Window {
+    fn new(start: DateTime<Utc>) -> Self {
+        Self {
+            start,
+            map: HashMap::new(),
+        }
+    }
+}
 
 #[derive(Debug)]
 pub struct Aggregate {
     interval: Duration,
-    map: HashMap<metric::MetricSeries, MetricEntry>,
+    window: Duration,
+    mode: AggregationMode,
+    rate: bool,
+    timestamp: WindowTimestamp,
+    /// The windows covered by the next flush, oldest first. New metrics are recorded in the last one.
+    windows: VecDeque<Window>,
+    /// The number of flush intervals in a window.
+    windows_per_flush: usize,
 }
 
 impl Aggregate {
     pub fn new(config: &AggregateConfig) -> crate::Result<Self> {
+        if config.interval_ms == 0 {
+            return Err("`interval_ms` must be greater than zero.".into());
+        }
+        let window_ms = config.window_ms.unwrap_or(config.interval_ms);
+        if window_ms < config.interval_ms || window_ms % config.interval_ms != 0 {
+            return Err("`window_ms` must be a multiple of `interval_ms`.".into());
+        }
+
         Ok(Self {
             interval: Duration::from_millis(config.interval_ms),
-            map: Default::default(),
+            window: Duration::from_millis(window_ms),
+            mode: config.mode,
+            rate: config.rate,
+            timestamp: config.timestamp,
+            windows: VecDeque::from([Window::new(Utc::now())]),
+            windows_per_flush: (window_ms / config.interval_ms) as usize,
         })
     }
 
     fn record(&mut self, event: Event) {
         let (series, data, metadata) = event.into_metric().into_parts();
+        let entry = MetricEntry::new(data, metadata);
+        let mode = self.mode;
+        let window = self
+            .windows
+            .back_mut()
+            .expect("there is always a window to record into");
 
-        match data.kind {
-            metric::MetricKind::Incremental => match self.map.entry(series) {
-                Entry::Occupied(mut entry) => {
-                    let existing = entry.get_mut();
-                    // In order to update (add) the new and old kind's must match
-                    if existing.0.kind == data.kind && existing.0.update(&data) {
-                        existing.1.merge(metadata);
-                    } else {
-                        emit!(AggregateUpdateFailed);
-                        *existing = (data, metadata);
-                    }
-                }
-                Entry::Vacant(entry) => {
-                    entry.insert((data, metadata));
-                }
-            },
-            metric::MetricKind::Absolute => {
-                // Always replace/store
-                self.map.insert(series, (data, metadata));
+        match window.map.entry(series) {
+            Entry::Occupied(mut existing) => existing.get_mut().merge(entry, mode),
+            Entry::Vacant(vacant) => {
+                vacant.insert(entry);
             }
         };
 
@@ -102,14 +286,100 @@ impl Aggregate {
     }
 
     fn flush_into(&mut self, output: &mut Vec<Event>) {
-        let map = std::mem::take(&mut self.map);
+        let end = Utc::now();
+        let start = self.windows.front().map_or(end, |window| window.start);
+
+        let map = if self.windows_per_flush == 1 {
+            self.windows
+                .pop_front()
+                .map(|window| window.map)
+                .unwrap_or_default()
+        } else {
+            // Older windows are still part of the following flushes, so their entries are merged
+            // into a copy.
+            let mut merged = HashMap::<metric::MetricSeries, MetricEntry>::new();
+            for window in &self.windows {
+                for (series, entry) in &window.map {
+                    match merged.entry(series.clone()) {
+                        Entry::Occupied(mut existing) => {
+                            existing.get_mut().merge(entry.clone(), self.mode)
+                        }
+                        Entry::Vacant(vacant) => {
+                            vacant.insert(entry.clone());
+                        }
+                    }
+                }
+            }
+            merged
+        };
+
+        self.windows.push_back(Window::new(end));
+        while self.windows.len() > self.windows_per_flush {
+            self.windows.pop_front();
+        }
+
         for (series, entry) in map.into_iter() {
-            let metric = metric::Metric::from_parts(series, entry.0, entry.1);
+            let (data, metadata) = self.finalize(entry, start, end);
+            let metric = metric::Metric::from_parts(series, data, metadata);
             output.push(Event::Metric(metric));
         }
 
         emit!(AggregateFlushed);
     }
+
+    /// Applies the per-window conversions (averages, rates and timestamps) to an aggregated entry.
+    fn finalize(
+        &self,
+        entry: MetricEntry,
+        start: DateTime<Utc>,
+        end: DateTime<Utc>,
+    ) -> (metric::MetricData, EventMetadata) {
+        let MetricEntry {
+            data,
+            metadata,
+            count,
+        } = entry;
+        let (mut time, mut kind, mut value) = data.into_parts();
+
+        if self.mode == AggregationMode::Mean && count > 1 {
+            if let metric::MetricValue::Counter { value } | metric::MetricValue::Gauge { value } =
+                &mut value
+            {
+                *value /= count as f64;
+            }
+        }
+
+        if self.rate && kind == metric::MetricKind::Incremental {
+            if let metric::MetricValue::Counter { value: total } = value {
+                // Until a sliding window fills up, and for flushes that come early, the entry
+                // covers less than the full window.
+                let span = (end - start)
+                    .to_std()
+                    .map(|span| span.as_secs_f64())
+                    .ok()
+                    .filter(|secs| *secs > 0.0)
+                    .unwrap_or_else(|| self.window.as_secs_f64());
+                kind = metric::MetricKind::Absolute;
+                value = metric::MetricValue::Gauge {
+                    value: total / span,
+                };
+            }
+        }
+
+        let timestamp = match self.timestamp {
+            WindowTimestamp::Unchanged => None,
+            WindowTimestamp::WindowStart => Some(start),
+            WindowTimestamp::WindowEnd => Some(end),
+        };
+        if let Some(timestamp) = timestamp {
+            time.timestamp = Some(timestamp);
+            time.interval_ms = u32::try_from(self.window.as_millis())
+                .ok()
+                .and_then(NonZeroU32::new);
+        }
+
+        (metric::MetricData::from_parts(time, kind, value), metadata)
+    }
 }
 
 impl TaskTransform<Event> for Aggregate {
@@ -189,6 +459,7 @@ mod tests {
     fn incremental() {
         let mut agg = Aggregate::new(&AggregateConfig {
             interval_ms: 1000_u64,
+            ..Default::default()
         })
         .unwrap();
 
@@ -259,6 +530,7 @@ mod tests {
     fn absolute() {
         let mut agg = Aggregate::new(&AggregateConfig {
             interval_ms: 1000_u64,
+            ..Default::default()
         })
         .unwrap();
 
@@ -324,6 +596,7 @@ mod tests {
     fn conflicting_value_type() {
         let mut agg = Aggregate::new(&AggregateConfig {
             interval_ms: 1000_u64,
+            ..Default::default()
         })
         .unwrap();
 
@@ -381,6 +654,7 @@ mod tests {
     fn conflicting_kinds() {
         let mut agg = Aggregate::new(&AggregateConfig {
             interval_ms: 1000_u64,
+            ..Default::default()
         })
         .unwrap();
 
@@ -567,4 +841,188 @@ interval_ms = 999999
         })
         .await;
     }
+
+    fn gauge(value: f64) -> Event {
+        make_metric(
+            "gauge_a",
+            metric::MetricKind::Absolute,
+            metric::MetricValue::Gauge { value },
+        )
+    }
+
+    fn flush_value(agg: &mut Aggregate) -> Option<metric::MetricValue> {
+        let mut out = vec![];
+        agg.flush_into(&mut out);
+        assert!(out.len() <= 1);
+        out.pop().map(|event| event.into_metric().value().clone())
+    }
+
+    #[test]
+    fn modes() {
+        for (mode, expected) in [
+            (AggregationMode::Auto, 2.0),
+            (AggregationMode::Sum, 9.0),
+            (AggregationMode::Max, 4.0),
+            (AggregationMode::Min, 2.0),
+            (AggregationMode::Mean, 3.0),
+            (AggregationMode::Latest, 2.0),
+        ] {
+            let mut agg = Aggregate::new(&AggregateConfig {
+                interval_ms: 1000_u64,
+                mode,
+                ..Default::default()
+            })
+            .unwrap();
+
+            agg.record(gauge(3.0));
+            agg.record(gauge(4.0));
+            agg.record(gauge(2.0));
+            assert_eq!(
+                flush_value(&mut agg),
+                Some(metric::MetricValue::Gauge { value: expected }),
+                "mode {:?}",
+                mode
+            );
+        }
+    }
+
+    #[test]
+    fn rate() {
+        let mut agg = Aggregate::new(&AggregateConfig {
+            interval_ms: 2000_u64,
+            rate: true,
+            ..Default::default()
+        })
+        .unwrap();
+        agg.windows[0].start = Utc::now() - chrono::Duration::seconds(2);
+
+        for value in [3.0, 5.0] {
+            agg.record(make_metric(
+                "counter_a",
+                metric::MetricKind::Incremental,
+                metric::MetricValue::Counter { value },
+            ));
+        }
+
+        let mut out = vec![];
+        agg.flush_into(&mut out);
+        assert_eq!(1, out.len());
+        let metric = out.pop().unwrap().into_metric();
+        assert_eq!(metric.kind(), metric::MetricKind::Absolute);
+        assert_rate(metric.value(), 4.0);
+    }
+
+    #[test]
+    fn rate_over_partial_window() {
+        let mut agg = Aggregate::new(&AggregateConfig {
+            interval_ms: 2000_u64,
+            window_ms: Some(8000),
+            rate: true,
+            ..Default::default()
+        })
+        .unwrap();
+        // Only the first interval of the sliding window has elapsed.
+        agg.windows[0].start = Utc::now() - chrono::Duration::seconds(2);
+
+        agg.record(make_metric(
+            "counter_a",
+            metric::MetricKind::Incremental,
+            metric::MetricValue::Counter { value: 8.0 },
+        ));
+
+        let mut out = vec![];
+        agg.flush_into(&mut out);
+        let metric = out.pop().unwrap().into_metric();
+        assert_rate(metric.value(), 4.0);
+    }
+
+    fn assert_rate(value: &metric::MetricValue, expected: f64) {
+        match value {
+            metric::MetricValue::Gauge { value } => {
+                // The flush happens a little after the backdated start of the window.
+                assert!((value - expected).abs() < 0.1, "{} != {}", value, expected);
+            }
+            value => panic!("unexpected value {:?}", value),
+        }
+    }
+
+    #[test]
+    fn sliding_window() {
+        let mut agg = Aggregate::new(&AggregateConfig {
+            interval_ms: 1000_u64,
+            window_ms: Some(3000),
+            mode: AggregationMode::Sum,
+            ..Default::default()
+        })
+        .unwrap();
+
+        agg.record(gauge(1.0));
+        assert_eq!(
+            flush_value(&mut agg),
+            Some(metric::MetricValue::Gauge { value: 1.0 })
+        );
+        agg.record(gauge(2.0));
+        assert_eq!(
+            flush_value(&mut agg),
+            Some(metric::MetricValue::Gauge { value: 3.0 })
+        );
+        agg.record(gauge(4.0));
+        assert_eq!(
+            flush_value(&mut agg),
+            Some(metric::MetricValue::Gauge { value: 7.0 })
+        );
+        // The first interval slides out of the window.
+        assert_eq!(
+            flush_value(&mut agg),
+            Some(metric::MetricValue::Gauge { value: 6.0 })
+        );
+        assert_eq!(
+            flush_value(&mut agg),
+            Some(metric::MetricValue::Gauge { value: 4.0 })
+        );
+        assert_eq!(flush_value(&mut agg), None);
+    }
+
+    #[test]
+    fn window_timestamp() {
+        let mut agg = Aggregate::new(&AggregateConfig {
+            interval_ms: 1000_u64,
+            timestamp: WindowTimestamp::WindowStart,
+            ..Default::default()
+        })
+        .unwrap();
+        let start = agg.windows[0].start;
+
+        agg.record(gauge(1.0));
+        let mut out = vec![];
+        agg.flush_into(&mut out);
+        let metric = out.pop().unwrap().into_metric();
+        assert_eq!(metric.timestamp(), Some(start));
+        assert_eq!(metric.interval_ms(), NonZeroU32::new(1000));
+    }
+
+    #[test]
+    fn invalid_window() {
+        for window_ms in [500, 1500] {
+            assert!(Aggregate::new(&AggregateConfig {
+                interval_ms: 1000_u64,
+                window_ms: Some(window_ms),
+                ..Default::default()
+            })
+            .is_err());
+        }
+    }
+
+    #[test]
+    fn invalid_interval() {
+        let error = Aggregate::new(&AggregateConfig {
+            interval_ms: 0,
+            ..Default::default()
+        })
+        .unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "`interval_ms` must be greater than zero."
+        );
+    }
 }
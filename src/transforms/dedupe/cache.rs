@@ -0,0 +1,422 @@
+//! Storage backends for the deduplication cache.
+//!
+//! The in-memory backend stores cache entries as-is. The disk and Redis backends store a SHA-256
+//! digest of each entry instead, which gives them a compact, stable key that can be written to a
+//! file or shared between Vector instances.
+
+use std::{
+    fs,
+    hash::Hash,
+    io::{self, BufReader, BufWriter},
+    num::NonZeroUsize,
+    path::{Path, PathBuf},
+    time::{Duration, Instant, SystemTime, UNIX_EPOCH},
+};
+
+use lru::LruCache;
+use redis::aio::ConnectionManager;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+use super::{CacheBackend, CacheConfig, CacheEntry};
+use crate::{config::TransformContext, internal_events::DedupeCacheError};
+
+const STATE_FILE_NAME: &str = "dedupe_cache.json";
+const TMP_FILE_NAME: &str = "dedupe_cache.json.tmp";
+
+type CacheKey = [u8; 32];
+
+pub(super) enum DedupeCache {
+    Memory(LocalCache<CacheEntry>),
+    Disk(DiskCache),
+    Redis(RedisCache),
+}
+
+impl DedupeCache {
+    pub(super) fn memory(config: &CacheConfig) -> Self {
+        Self::Memory(LocalCache::new(config))
+    }
+
+    pub(super) async fn build(
+        config: &CacheConfig,
+        context: &TransformContext,
+    ) -> crate::Result<Self> {
+        let component_id = context.key.as_ref().map_or("dedupe", |key| key.id());
+
+        match &config.backend {
+            CacheBackend::Memory => Ok(Self::memory(config)),
+            CacheBackend::Disk {
+                data_dir,
+                flush_interval_secs,
+            } => {
+                let data_dir = context
+                    .globals
+                    .resolve_and_make_data_subdir(data_dir.as_ref(), component_id)?;
+                Ok(Self::Disk(DiskCache::open(
+                    LocalCache::new(config),
+                    &data_dir,
+                    Duration::from_secs(*flush_interval_secs),
+                )))
+            }
+            CacheBackend::Redis { url, key_prefix } => {
+                let ttl_secs = config
+                    .ttl_secs
+                    .ok_or("`cache.ttl_secs` must be set when using the `redis` backend.")?;
+                let client = redis::Client::open(url.as_str())?;
+                let connection = client.get_tokio_connection_manager().await?;
+                let key_prefix = key_prefix
+                    .clone()
+                    .unwrap_or_else(|| format!("vector:dedupe:{}:", component_id));
+
+                Ok(Self::Redis(RedisCache {
+                    connection,
+                    key_prefix,
+                    ttl_secs: ttl_secs.get(),
+                }))
+            }
+        }
+    }
+
+    /// Records a batch of entries as seen, returning for each of them whether it was already
+    /// present in the cache, including as an earlier entry of the same batch.
+    pub(super) async fn check_and_insert(&mut self, entries: Vec<CacheEntry>) -> Vec<bool> {
+        match self {
+            Self::Memory(cache) => {
+                let now = SystemTime::now();
+                entries
+                    .into_iter()
+                    .map(|entry| cache.check_and_insert(entry, now))
+                    .collect()
+            }
+            Self::Disk(cache) => cache.check_and_insert(entries.iter().map(digest)).await,
+            Self::Redis(cache) => cache.check_and_insert(entries.iter().map(digest)).await,
+        }
+    }
+
+    /// Persists any pending changes, for the backends that need it.
+    pub(super) async fn flush(&mut self) {
+        if let Self::Disk(cache) = self {
+            cache.flush().await;
+        }
+    }
+}
+
+/// An LRU cache remembering when each entry was first seen, so entries can expire.
+pub(super) struct LocalCache<K: Hash + Eq> {
+    cache: LruCache<K, SystemTime>,
+    ttl: Option<Duration>,
+}
+
+impl<K: Hash + Eq> LocalCache<K> {
+    fn new(config: &CacheConfig) -> Self {
+        Self::with_capacity(
+            config.num_events,
+            config.ttl_secs.map(|ttl| Duration::from_secs(ttl.get())),
+        )
+    }
+
+    fn with_capacity(capacity: NonZeroUsize, ttl: Option<Duration>) -> Self {
+        Self {
+            cache: LruCache::new(capacity),
+            ttl,
+        }
+    }
+
+    fn is_expired(&self, seen_at: SystemTime, now: SystemTime) -> bool {
+        self.ttl.map_or(false, |ttl| {
+            now.duration_since(seen_at).map_or(false, |age| age >= ttl)
+        })
+    }
+
+    fn check_and_insert(&mut self, key: K, now: SystemTime) -> bool {
+        let seen_at = self.cache.get(&key).copied();
+        let duplicate = seen_at.map_or(false, |seen_at| !self.is_expired(seen_at, now));
+        if !duplicate {
+            self.cache.put(key, now);
+        }
+        duplicate
+    }
+}
+
+/// The on-disk representation of the disk backend.
+#[derive(Deserialize, Serialize)]
+#[serde(tag = "version", rename_all = "snake_case")]
+enum State {
+    #[serde(rename = "1")]
+    V1 { entries: Vec<PersistedEntry> },
+}
+
+#[derive(Deserialize, Serialize)]
+struct PersistedEntry {
+    key: String,
+    seen_at: u64,
+}
+
+/// A local cache that is periodically written to the data directory, and reloaded on startup.
+pub(super) struct DiskCache {
+    cache: LocalCache<CacheKey>,
+    state_path: PathBuf,
+    tmp_path: PathBuf,
+    flush_interval: Duration,
+    last_flush: Instant,
+    dirty: bool,
+}
+
+impl DiskCache {
+    fn open(mut cache: LocalCache<CacheKey>, data_dir: &Path, flush_interval: Duration) -> Self {
+        let state_path = data_dir.join(STATE_FILE_NAME);
+        let tmp_path = data_dir.join(TMP_FILE_NAME);
+
+        match read_state(&state_path) {
+            Ok(State::V1 { entries }) => {
+                let now = SystemTime::now();
+                // Entries are stored from least to most recently used, so re-inserting them in
+                // order restores the LRU ordering.
+                for entry in entries {
+                    let seen_at = UNIX_EPOCH + Duration::from_secs(entry.seen_at);
+                    let key = hex::decode(&entry.key)
+                        .ok()
+                        .and_then(|key| CacheKey::try_from(key).ok());
+                    if let Some(key) = key {
+                        if !cache.is_expired(seen_at, now) {
+                            cache.cache.put(key, seen_at);
+                        }
+                    }
+                }
+            }
+            Err(error) if error.kind() == io::ErrorKind::NotFound => {}
+            Err(error) => emit!(DedupeCacheError {
+                error,
+                backend: "disk",
+            }),
+        }
+
+        Self {
+            cache,
+            state_path,
+            tmp_path,
+            flush_interval,
+            last_flush: Instant::now(),
+            dirty: false,
+        }
+    }
+
+    async fn check_and_insert(&mut self, keys: impl Iterator<Item = CacheKey>) -> Vec<bool> {
+        let now = SystemTime::now();
+        let duplicates = keys
+            .map(|key| self.cache.check_and_insert(key, now))
+            .collect();
+        // Even duplicates change the LRU ordering, so the state always needs to be written again.
+        self.dirty = true;
+        if self.last_flush.elapsed() >= self.flush_interval {
+            self.flush().await;
+        }
+        duplicates
+    }
+
+    async fn flush(&mut self) {
+        if !self.dirty {
+            return;
+        }
+
+        let entries = self
+            .cache
+            .cache
+            .iter()
+            .rev()
+            .map(|(key, seen_at)| PersistedEntry {
+                key: hex::encode(key),
+                seen_at: seen_at
+                    .duration_since(UNIX_EPOCH)
+                    .map_or(0, |since_epoch| since_epoch.as_secs()),
+            })
+            .collect();
+        let tmp_path = self.tmp_path.clone();
+        let state_path = self.state_path.clone();
+
+        // Serializing and writing the whole cache can take a while, so it's kept off the async
+        // runtime threads.
+        let result =
+            tokio::task::spawn_blocking(move || write_state(&tmp_path, &state_path, entries))
+                .await
+                .expect("writing the dedupe cache panicked");
+        if let Err(error) = result {
+            emit!(DedupeCacheError {
+                error,
+                backend: "disk",
+            });
+        }
+        self.dirty = false;
+        self.last_flush = Instant::now();
+    }
+}
+
+fn write_state(tmp_path: &Path, state_path: &Path, entries: Vec<PersistedEntry>) -> io::Result<()> {
+    // Write the new state to a tmp file and flush it fully to disk, then rename it over the
+    // previous state so a crash never leaves a partially written file behind.
+    let mut writer = BufWriter::new(fs::File::create(tmp_path)?);
+    serde_json::to_writer(&mut writer, &State::V1 { entries })?;
+    writer.into_inner()?.sync_all()?;
+    fs::rename(tmp_path, state_path)
+}
+
+fn read_state(path: &Path) -> io::Result<State> {
+    let reader = BufReader::new(fs::File::open(path)?);
+    Ok(serde_json::from_reader(reader)?)
+}
+
+/// A cache stored in Redis, shared by every instance using the same server and key prefix.
+pub(super) struct RedisCache {
+    connection: ConnectionManager,
+    key_prefix: String,
+    ttl_secs: u64,
+}
+
+impl RedisCache {
+    async fn check_and_insert(&mut self, keys: impl Iterator<Item = CacheKey>) -> Vec<bool> {
+        // `SET NX` only stores the key if it doesn't exist yet, so checking for and recording the
+        // entry is a single atomic operation, even with several instances sharing the server. The
+        // commands of a batch are pipelined, so that the whole batch takes a single round trip.
+        let mut pipeline = redis::pipe();
+        let mut count = 0;
+        for key in keys {
+            pipeline
+                .cmd("SET")
+                .arg(format!("{}{}", self.key_prefix, hex::encode(key)))
+                .arg(1)
+                .arg("NX")
+                .arg("EX")
+                .arg(self.ttl_secs);
+            count += 1;
+        }
+
+        let result: redis::RedisResult<Vec<Option<String>>> =
+            pipeline.query_async(&mut self.connection).await;
+
+        match result {
+            Ok(stored) => stored.iter().map(Option::is_none).collect(),
+            Err(error) => {
+                // Forward the events rather than risk dropping them when the cache is unreachable.
+                emit!(DedupeCacheError {
+                    error,
+                    backend: "redis",
+                });
+                vec![false; count]
+            }
+        }
+    }
+}
+
+/// Computes a stable digest of a cache entry.
+///
+/// Every variable length value is prefixed with its length so that different entries can never
+/// produce the same byte sequence.
+fn digest(entry: &CacheEntry) -> CacheKey {
+    fn update_bytes(hasher: &mut Sha256, bytes: &[u8]) {
+        hasher.update((bytes.len() as u64).to_le_bytes());
+        hasher.update(bytes);
+    }
+
+    let mut hasher = Sha256::new();
+    match entry {
+        CacheEntry::Match(fields) => {
+            hasher.update([0]);
+            for field in fields {
+                match field {
+                    Some((type_id, value)) => {
+                        hasher.update([1, *type_id]);
+                        update_bytes(&mut hasher, value);
+                    }
+                    None => hasher.update([0]),
+                }
+            }
+        }
+        CacheEntry::Ignore(fields) => {
+            hasher.update([1]);
+            for (name, type_id, value) in fields {
+                update_bytes(&mut hasher, name.as_bytes());
+                hasher.update([*type_id]);
+                update_bytes(&mut hasher, value);
+            }
+        }
+    }
+    hasher.finalize().into()
+}
+
+#[cfg(test)]
+mod tests {
+    use bytes::Bytes;
+
+    use super::*;
+
+    fn entry(value: &'static str) -> CacheEntry {
+        CacheEntry::Match(vec![Some((0, Bytes::from_static(value.as_bytes())))])
+    }
+
+    #[test]
+    fn local_cache_expires_entries() {
+        let mut cache = LocalCache::with_capacity(
+            NonZeroUsize::new(10).unwrap(),
+            Some(Duration::from_secs(60)),
+        );
+        let start = SystemTime::now();
+
+        assert!(!cache.check_and_insert("a", start));
+        assert!(cache.check_and_insert("a", start + Duration::from_secs(59)));
+        assert!(!cache.check_and_insert("a", start + Duration::from_secs(60)));
+        assert!(cache.check_and_insert("a", start + Duration::from_secs(61)));
+    }
+
+    #[test]
+    fn local_cache_evicts_least_recently_used() {
+        let mut cache = LocalCache::with_capacity(NonZeroUsize::new(2).unwrap(), None);
+        let now = SystemTime::now();
+
+        assert!(!cache.check_and_insert("a", now));
+        assert!(!cache.check_and_insert("b", now));
+        assert!(cache.check_and_insert("a", now));
+        assert!(!cache.check_and_insert("c", now));
+        assert!(cache.check_and_insert("a", now));
+        assert!(!cache.check_and_insert("b", now));
+    }
+
+    #[test]
+    fn digest_distinguishes_entries() {
+        assert_eq!(digest(&entry("a")), digest(&entry("a")));
+        assert_ne!(digest(&entry("a")), digest(&entry("b")));
+        assert_ne!(
+            digest(&CacheEntry::Match(vec![None])),
+            digest(&CacheEntry::Ignore(vec![]))
+        );
+    }
+
+    #[tokio::test]
+    async fn disk_cache_survives_reopening() {
+        let data_dir = tempfile::tempdir().unwrap();
+        let new_cache = || {
+            LocalCache::with_capacity(NonZeroUsize::new(2).unwrap(), Some(Duration::from_secs(60)))
+        };
+        let keys = |values: &[&'static str]| {
+            values
+                .iter()
+                .map(|value| digest(&entry(*value)))
+                .collect::<Vec<_>>()
+                .into_iter()
+        };
+
+        let mut cache = DiskCache::open(new_cache(), data_dir.path(), Duration::from_secs(60));
+        assert_eq!(
+            cache.check_and_insert(keys(&["a", "b", "c", "c"])).await,
+            [false, false, false, true]
+        );
+        cache.flush().await;
+        assert!(!data_dir.path().join(TMP_FILE_NAME).exists());
+
+        let mut cache = DiskCache::open(new_cache(), data_dir.path(), Duration::from_secs(60));
+        // "a" was evicted before the cache was persisted.
+        assert_eq!(
+            cache.check_and_insert(keys(&["a", "c"])).await,
+            [false, true]
+        );
+    }
+}
@@ -1,8 +1,12 @@
-use std::{future::ready, num::NonZeroUsize, pin::Pin};
+use std::{
+    num::{NonZeroU64, NonZeroUsize},
+    path::PathBuf,
+    pin::Pin,
+};
 
+use async_stream::stream;
 use bytes::Bytes;
 use futures::{Stream, StreamExt};
-use lru::LruCache;
 use vector_config::configurable_component;
 use vector_core::config::{clone_input_definitions, LogNamespace};
 
@@ -17,6 +21,13 @@ use crate::{
     transforms::{TaskTransform, Transform},
 };
 
+mod cache;
+
+use self::cache::DedupeCache;
+
+/// The maximum number of events checked against the cache at once.
+const MAX_BATCH_SIZE: usize = 1000;
+
 /// Options to control what fields to match against.
 ///
 /// When no field matching configuration is specified, events are matched using the `timestamp`,
@@ -65,7 +76,82 @@ pub enum FieldMatchConfig {
 #[serde(deny_unknown_fields)]
 pub struct CacheConfig {
     /// Number of events to cache and use for comparing incoming events to previously seen events.
+    ///
+    /// This limit does not apply to the `redis` backend, where entries are only removed once they
+    /// expire.
     pub num_events: NonZeroUsize,
+
+    /// How long an event is remembered for after it was first seen, in seconds.
+    ///
+    /// Once an entry expires, the next matching event is forwarded and cached again. By default,
+    /// entries are only removed from the cache when evicted to make room for newer ones.
+    ///
+    /// This option is required when using the `redis` backend.
+    #[serde(default)]
+    #[configurable(metadata(docs::type_unit = "seconds"))]
+    #[configurable(metadata(docs::examples = 3600))]
+    pub ttl_secs: Option<NonZeroU64>,
+
+    #[configurable(derived)]
+    #[serde(default)]
+    pub backend: CacheBackend,
+}
+
+/// Where the deduplication cache is stored.
+#[configurable_component]
+#[derive(Clone, Debug, Default)]
+#[serde(tag = "type", rename_all = "snake_case", deny_unknown_fields)]
+#[configurable(metadata(docs::enum_tag_description = "The storage backend of the cache."))]
+pub enum CacheBackend {
+    /// Store the cache in memory.
+    ///
+    /// The cache is lost whenever Vector restarts or the transform is reloaded.
+    #[default]
+    Memory,
+
+    /// Store the cache in memory, and periodically persist it to disk.
+    ///
+    /// The persisted cache is loaded again when the transform starts, so events seen before a
+    /// restart or reload are still deduplicated.
+    Disk {
+        /// The directory used to persist the cache.
+        ///
+        /// By default, the [global `data_dir` option][global_data_dir] is used. Make sure the running user has write
+        /// permissions to this directory.
+        ///
+        /// [global_data_dir]: https://vector.dev/docs/reference/configuration/global-options/#data_dir
+        #[serde(default)]
+        #[configurable(metadata(docs::examples = "/var/local/lib/vector/"))]
+        #[configurable(metadata(docs::human_name = "Data Directory"))]
+        data_dir: Option<PathBuf>,
+
+        /// How often the cache is persisted to disk, in seconds.
+        ///
+        /// The cache is also persisted when the transform shuts down.
+        #[serde(default = "default_flush_interval_secs")]
+        #[configurable(metadata(docs::type_unit = "seconds"))]
+        flush_interval_secs: u64,
+    },
+
+    /// Store the cache in a Redis-compatible server.
+    ///
+    /// Every Vector instance using the same server and key prefix shares the same cache, so
+    /// duplicates are detected across replicas.
+    Redis {
+        /// The URL of the Redis server.
+        #[configurable(metadata(docs::examples = "redis://127.0.0.1:6379/0"))]
+        url: String,
+
+        /// The prefix of the keys storing the cache entries.
+        ///
+        /// Defaults to `vector:dedupe:<component id>:`.
+        #[configurable(metadata(docs::examples = "vector:dedupe:"))]
+        key_prefix: Option<String>,
+    },
+}
+
+const fn default_flush_interval_secs() -> u64 {
+    5
 }
 
 /// Configuration for the `dedupe` transform.
@@ -85,6 +171,8 @@ pub struct DedupeConfig {
 fn default_cache_config() -> CacheConfig {
     CacheConfig {
         num_events: NonZeroUsize::new(5000).expect("static non-zero number"),
+        ttl_secs: None,
+        backend: CacheBackend::Memory,
     }
 }
 
@@ -132,7 +220,7 @@ impl DedupeConfig {
 
 pub struct Dedupe {
     fields: FieldMatchConfig,
-    cache: LruCache<CacheEntry, bool>,
+    cache: DedupeCache,
 }
 
 impl GenerateConfig for DedupeConfig {
@@ -148,8 +236,11 @@ impl GenerateConfig for DedupeConfig {
 #[async_trait::async_trait]
 #[typetag::serde(name = "dedupe")]
 impl TransformConfig for DedupeConfig {
-    async fn build(&self, _context: &TransformContext) -> crate::Result<Transform> {
-        Ok(Transform::event_task(Dedupe::new(self.clone())))
+    async fn build(&self, context: &TransformContext) -> crate::Result<Transform> {
+        Ok(Transform::event_task(Dedupe {
+            fields: self.fill_default_fields_match(),
+            cache: DedupeCache::build(&self.cache, context).await?,
+        }))
     }
 
     fn input(&self) -> Input {
@@ -216,23 +307,33 @@ const fn type_id_for_value(val: &Value) -> TypeId {
 }
 
 impl Dedupe {
+    /// Creates a `Dedupe` keeping its cache in memory, regardless of the configured backend.
     pub fn new(config: DedupeConfig) -> Self {
-        let num_entries = config.cache.num_events;
         let fields = config.fill_default_fields_match();
         Self {
             fields,
-            cache: LruCache::new(num_entries),
+            cache: DedupeCache::memory(&config.cache),
         }
     }
 
-    fn transform_one(&mut self, event: Event) -> Option<Event> {
-        let cache_entry = build_cache_entry(&event, &self.fields);
-        if self.cache.put(cache_entry, true).is_some() {
-            emit!(DedupeEventsDropped { count: 1 });
-            None
-        } else {
-            Some(event)
+    /// Checks a batch of events against the cache, returning the ones that aren't duplicates.
+    async fn transform_batch(&mut self, events: Vec<Event>) -> Vec<Event> {
+        let entries = events
+            .iter()
+            .map(|event| build_cache_entry(event, &self.fields))
+            .collect();
+        let duplicates = self.cache.check_and_insert(entries).await;
+
+        let count = duplicates.iter().filter(|duplicate| **duplicate).count();
+        if count > 0 {
+            emit!(DedupeEventsDropped { count });
         }
+
+        events
+            .into_iter()
+            .zip(duplicates)
+            .filter_map(|(event, duplicate)| (!duplicate).then_some(event))
+            .collect()
     }
 }
 
@@ -281,7 +382,17 @@ impl TaskTransform<Event> for Dedupe {
         Self: 'static,
     {
         let mut inner = self;
-        Box::pin(task.filter_map(move |v| ready(inner.transform_one(v))))
+        // The events that are already available are checked together, so that remote caches
+        // are queried once per batch rather than once per event.
+        let mut input = task.ready_chunks(MAX_BATCH_SIZE);
+        Box::pin(stream! {
+            while let Some(events) = input.next().await {
+                for event in inner.transform_batch(events).await {
+                    yield event;
+                }
+            }
+            inner.cache.flush().await;
+        })
     }
 }
 
@@ -294,12 +405,15 @@ mod tests {
     use vector_common::config::ComponentKey;
     use vector_core::config::OutputId;
 
+    use futures::{stream, StreamExt};
+
     use crate::config::schema::Definition;
     use crate::{
+        config::{TransformConfig, TransformContext},
         event::{Event, LogEvent, Value},
         test_util::components::assert_transform_compliance,
         transforms::{
-            dedupe::{CacheConfig, DedupeConfig, FieldMatchConfig},
+            dedupe::{CacheBackend, CacheConfig, DedupeConfig, FieldMatchConfig},
             test::create_topology,
         },
     };
@@ -313,6 +427,8 @@ mod tests {
         DedupeConfig {
             cache: CacheConfig {
                 num_events: std::num::NonZeroUsize::new(num_events).expect("non-zero num_events"),
+                ttl_secs: None,
+                backend: CacheBackend::Memory,
             },
             fields: Some(FieldMatchConfig::MatchFields(fields)),
         }
@@ -326,6 +442,8 @@ mod tests {
         DedupeConfig {
             cache: CacheConfig {
                 num_events: std::num::NonZeroUsize::new(num_events).expect("non-zero num_events"),
+                ttl_secs: None,
+                backend: CacheBackend::Memory,
             },
             fields: Some(FieldMatchConfig::IgnoreFields(fields)),
         }
@@ -755,4 +873,31 @@ mod tests {
         })
         .await;
     }
+
+    #[tokio::test]
+    async fn dedupe_disk_backend_survives_restart() {
+        let data_dir = tempfile::tempdir().unwrap();
+        let mut config = make_match_transform_config(5, vec!["matched".into()]);
+        config.cache.backend = CacheBackend::Disk {
+            data_dir: Some(data_dir.path().to_path_buf()),
+            flush_interval_secs: 60,
+        };
+
+        let mut event = Event::Log(LogEvent::from("message"));
+        event.as_mut_log().insert("matched", "some value");
+
+        // The second run starts from the cache persisted when the first one shut down.
+        for expected in [1, 0] {
+            let transform = config
+                .build(&TransformContext::default())
+                .await
+                .unwrap()
+                .into_task();
+            let output = transform
+                .transform_events(Box::pin(stream::iter(vec![event.clone()])))
+                .collect::<Vec<_>>()
+                .await;
+            assert_eq!(output.len(), expected);
+        }
+    }
 }
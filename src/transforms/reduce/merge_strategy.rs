@@ -4,6 +4,7 @@ use bytes::{Bytes, BytesMut};
 use chrono::{DateTime, Utc};
 use ordered_float::NotNan;
 use vector_config::configurable_component;
+use vector_core::metrics::AgentDDSketch;
 
 use crate::event::{LogEvent, Value};
 
@@ -49,6 +50,33 @@ pub enum MergeStrategy {
 
     /// Create a flattened array of all unique values.
     FlatUnique,
+
+    /// Keep the average of all numeric values.
+    Avg,
+
+    /// Count the values seen.
+    Count,
+
+    /// Count the unique values seen.
+    CountDistinct,
+
+    /// Estimate the median of all numeric values.
+    ///
+    /// The estimate is computed with a sketch, so it is approximate but has a bounded size.
+    P50,
+
+    /// Estimate the 90th percentile of all numeric values.
+    ///
+    /// The estimate is computed with a sketch, so it is approximate but has a bounded size.
+    P90,
+
+    /// Estimate the 99th percentile of all numeric values.
+    ///
+    /// The estimate is computed with a sketch, so it is approximate but has a bounded size.
+    P99,
+
+    /// Keep the (population) standard deviation of all numeric values.
+    Stddev,
 }
 
 #[derive(Debug, Clone)]
@@ -514,6 +542,157 @@ impl ReduceValueMerger for MinNumberMerger {
     }
 }
 
+fn numeric_value(v: &Value) -> Result<f64, String> {
+    match v {
+        Value::Integer(i) => Ok(*i as f64),
+        Value::Float(f) => Ok(f.into_inner()),
+        _ => Err(format!(
+            "expected numeric value, found: '{}'",
+            v.to_string_lossy()
+        )),
+    }
+}
+
+fn insert_float(k: String, f: f64, v: &mut LogEvent) -> Result<(), String> {
+    let f = NotNan::new(f).map_err(|_| format!("computed NaN value for '{}'", k))?;
+    v.insert(k.as_str(), Value::Float(f));
+    Ok(())
+}
+
+#[derive(Debug, Clone)]
+struct AvgMerger {
+    sum: f64,
+    count: u64,
+}
+
+impl AvgMerger {
+    const fn new(v: f64) -> Self {
+        Self { sum: v, count: 1 }
+    }
+}
+
+impl ReduceValueMerger for AvgMerger {
+    fn add(&mut self, v: Value) -> Result<(), String> {
+        self.sum += numeric_value(&v)?;
+        self.count += 1;
+        Ok(())
+    }
+
+    fn insert_into(self: Box<Self>, k: String, v: &mut LogEvent) -> Result<(), String> {
+        insert_float(k, self.sum / self.count as f64, v)
+    }
+}
+
+#[derive(Debug, Clone)]
+struct CountMerger {
+    count: i64,
+}
+
+impl CountMerger {
+    const fn new() -> Self {
+        Self { count: 1 }
+    }
+}
+
+impl ReduceValueMerger for CountMerger {
+    fn add(&mut self, _v: Value) -> Result<(), String> {
+        self.count += 1;
+        Ok(())
+    }
+
+    fn insert_into(self: Box<Self>, k: String, v: &mut LogEvent) -> Result<(), String> {
+        v.insert(k.as_str(), Value::Integer(self.count));
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone)]
+struct CountDistinctMerger {
+    v: HashSet<Value>,
+}
+
+impl CountDistinctMerger {
+    #[allow(clippy::mutable_key_type)] // false positive due to bytes::Bytes
+    fn new(v: Value) -> Self {
+        Self {
+            v: HashSet::from([v]),
+        }
+    }
+}
+
+impl ReduceValueMerger for CountDistinctMerger {
+    fn add(&mut self, v: Value) -> Result<(), String> {
+        self.v.insert(v);
+        Ok(())
+    }
+
+    fn insert_into(self: Box<Self>, k: String, v: &mut LogEvent) -> Result<(), String> {
+        v.insert(k.as_str(), Value::Integer(self.v.len() as i64));
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone)]
+struct QuantileMerger {
+    sketch: AgentDDSketch,
+    quantile: f64,
+}
+
+impl QuantileMerger {
+    fn new(v: f64, quantile: f64) -> Self {
+        let mut sketch = AgentDDSketch::with_agent_defaults();
+        sketch.insert(v);
+        Self { sketch, quantile }
+    }
+}
+
+impl ReduceValueMerger for QuantileMerger {
+    fn add(&mut self, v: Value) -> Result<(), String> {
+        self.sketch.insert(numeric_value(&v)?);
+        Ok(())
+    }
+
+    fn insert_into(self: Box<Self>, k: String, v: &mut LogEvent) -> Result<(), String> {
+        match self.sketch.quantile(self.quantile) {
+            Some(q) => insert_float(k, q, v),
+            None => Err(format!("no values to compute the quantile of '{}'", k)),
+        }
+    }
+}
+
+/// Computes the standard deviation in a single pass, using Welford's algorithm.
+#[derive(Debug, Clone)]
+struct StddevMerger {
+    count: u64,
+    mean: f64,
+    m2: f64,
+}
+
+impl StddevMerger {
+    const fn new(v: f64) -> Self {
+        Self {
+            count: 1,
+            mean: v,
+            m2: 0.0,
+        }
+    }
+}
+
+impl ReduceValueMerger for StddevMerger {
+    fn add(&mut self, v: Value) -> Result<(), String> {
+        let v = numeric_value(&v)?;
+        self.count += 1;
+        let delta = v - self.mean;
+        self.mean += delta / self.count as f64;
+        self.m2 += delta * (v - self.mean);
+        Ok(())
+    }
+
+    fn insert_into(self: Box<Self>, k: String, v: &mut LogEvent) -> Result<(), String> {
+        insert_float(k, (self.m2 / self.count as f64).sqrt(), v)
+    }
+}
+
 pub trait ReduceValueMerger: std::fmt::Debug + Send + Sync {
     fn add(&mut self, v: Value) -> Result<(), String>;
     fn insert_into(self: Box<Self>, k: String, v: &mut LogEvent) -> Result<(), String>;
@@ -604,6 +783,13 @@ pub(crate) fn get_value_merger(
         MergeStrategy::Discard => Ok(Box::new(DiscardMerger::new(v))),
         MergeStrategy::Retain => Ok(Box::new(RetainMerger::new(v))),
         MergeStrategy::FlatUnique => Ok(Box::new(FlatUniqueMerger::new(v))),
+        MergeStrategy::Avg => Ok(Box::new(AvgMerger::new(numeric_value(&v)?))),
+        MergeStrategy::Count => Ok(Box::new(CountMerger::new())),
+        MergeStrategy::CountDistinct => Ok(Box::new(CountDistinctMerger::new(v))),
+        MergeStrategy::P50 => Ok(Box::new(QuantileMerger::new(numeric_value(&v)?, 0.5))),
+        MergeStrategy::P90 => Ok(Box::new(QuantileMerger::new(numeric_value(&v)?, 0.9))),
+        MergeStrategy::P99 => Ok(Box::new(QuantileMerger::new(numeric_value(&v)?, 0.99))),
+        MergeStrategy::Stddev => Ok(Box::new(StddevMerger::new(numeric_value(&v)?))),
     }
 }
 
@@ -869,6 +1055,74 @@ mod test {
         }
     }
 
+    #[test]
+    fn statistics() {
+        for strategy in [
+            MergeStrategy::Avg,
+            MergeStrategy::P50,
+            MergeStrategy::P90,
+            MergeStrategy::P99,
+            MergeStrategy::Stddev,
+        ] {
+            assert!(get_value_merger(42.into(), &strategy).is_ok());
+            assert!(get_value_merger(4.2.into(), &strategy).is_ok());
+            assert!(get_value_merger("foo".into(), &strategy).is_err());
+            assert!(merge(42.into(), "foo".into(), &strategy).is_err());
+        }
+        assert!(get_value_merger("foo".into(), &MergeStrategy::Count).is_ok());
+        assert!(get_value_merger("foo".into(), &MergeStrategy::CountDistinct).is_ok());
+
+        let values: Vec<Value> = vec![
+            2.into(),
+            4.into(),
+            4.into(),
+            4.into(),
+            5.0.into(),
+            5.into(),
+            7.into(),
+            9.into(),
+        ];
+        assert_eq!(merge_all(&values, &MergeStrategy::Avg), Ok(5.0.into()));
+        match merge_all(&values, &MergeStrategy::Stddev) {
+            Ok(Value::Float(stddev)) => assert!((stddev.into_inner() - 2.0).abs() < 1e-9),
+            other => panic!("unexpected stddev: {:?}", other),
+        }
+        assert_eq!(merge_all(&values, &MergeStrategy::Count), Ok(8.into()));
+        // `5` and `5.0` are distinct values.
+        assert_eq!(
+            merge_all(&values, &MergeStrategy::CountDistinct),
+            Ok(6.into())
+        );
+
+        let values: Vec<Value> = (1..=100).map(Value::from).collect();
+        for (strategy, expected) in [
+            (MergeStrategy::P50, 50.0),
+            (MergeStrategy::P90, 90.0),
+            (MergeStrategy::P99, 99.0),
+        ] {
+            let Value::Float(estimate) = merge_all(&values, &strategy).unwrap() else {
+                panic!("expected a float");
+            };
+            // The sketch has a relative accuracy of about 1%.
+            assert!(
+                (estimate.into_inner() - expected).abs() / expected < 0.02,
+                "{:?}: {}",
+                strategy,
+                estimate
+            );
+        }
+    }
+
+    fn merge_all(values: &[Value], strategy: &MergeStrategy) -> Result<Value, String> {
+        let mut merger = get_value_merger(values[0].clone(), strategy)?;
+        for value in &values[1..] {
+            merger.add(value.clone())?;
+        }
+        let mut output = LogEvent::default();
+        merger.insert_into("out".into(), &mut output)?;
+        Ok(output.remove("out").unwrap())
+    }
+
     fn merge(initial: Value, additional: Value, strategy: &MergeStrategy) -> Result<Value, String> {
         let mut merger = get_value_merger(initial, strategy)?;
         merger.add(additional)?;
@@ -203,6 +203,19 @@ impl TransformConfig for ReduceConfig {
                         Kind::undefined()
                     }
                 }
+                MergeStrategy::Avg
+                | MergeStrategy::P50
+                | MergeStrategy::P90
+                | MergeStrategy::P99
+                | MergeStrategy::Stddev => {
+                    // always produce a float from integer / float values
+                    if input_kind.contains_integer() || input_kind.contains_float() {
+                        Kind::float()
+                    } else {
+                        Kind::undefined()
+                    }
+                }
+                MergeStrategy::Count | MergeStrategy::CountDistinct => Kind::integer(),
                 MergeStrategy::FlatUnique => {
                     let mut array_elements = input_kind.without_array().without_object();
                     if let Some(array) = input_kind.as_array() {
@@ -1,23 +1,30 @@
-use std::{num::NonZeroU32, pin::Pin, time::Duration};
+use std::{
+    collections::HashMap,
+    num::NonZeroU32,
+    sync::Arc,
+    time::{Duration, Instant},
+};
 
-use async_stream::stream;
-use futures::{Stream, StreamExt};
-use governor::{clock, Quota, RateLimiter};
+use enrichment::{Case, IndexHandle, TableSearch};
+use governor::{clock, state::keyed::DashMapStateStore, Quota, RateLimiter};
 use serde_with::serde_as;
 use snafu::Snafu;
 use vector_config::configurable_component;
 use vector_core::config::{clone_input_definitions, LogNamespace};
+use vrl::value::Value;
 
 use crate::{
     conditions::{AnyCondition, Condition},
     config::{DataType, Input, OutputId, TransformConfig, TransformContext, TransformOutput},
     event::Event,
-    internal_events::{TemplateRenderingError, ThrottleEventDiscarded},
+    internal_events::{TemplateRenderingError, ThrottleEventDiscarded, ThrottleThresholdError},
     schema,
     template::Template,
-    transforms::{TaskTransform, Transform},
+    transforms::{SyncTransform, Transform, TransformOutputsBuf},
 };
 
+const DROPPED: &str = "dropped";
+
 /// Configuration for the `throttle` transform.
 #[serde_as]
 #[configurable_component(transform("throttle", "Rate limit logs passing through a topology."))]
@@ -26,7 +33,8 @@ use crate::{
 pub struct ThrottleConfig {
     /// The number of events allowed for a given bucket per configured `window_secs`.
     ///
-    /// Each unique key has its own `threshold`.
+    /// Each unique key has its own `threshold`. This is the default for keys whose threshold is
+    /// not set by `threshold_field` or `threshold_table`.
     threshold: u32,
 
     /// The time window in which the configured `threshold` is applied, in seconds.
@@ -34,6 +42,15 @@ pub struct ThrottleConfig {
     #[configurable(metadata(docs::human_name = "Time Window"))]
     window_secs: Duration,
 
+    /// The number of events a bucket can let through at once.
+    ///
+    /// Buckets refill at the steady-state rate of `threshold` events per `window_secs`, and hold
+    /// up to `burst` events. This applies to the keys using the default `threshold`. If left
+    /// unspecified, the burst is the same as `threshold`.
+    #[serde(default)]
+    #[configurable(metadata(docs::examples = 100))]
+    burst: Option<u32>,
+
     /// The value to group events into separate buckets to be rate limited independently.
     ///
     /// If left unspecified, or if the event doesn't have `key_field`, then the event is not rate
@@ -41,8 +58,52 @@ pub struct ThrottleConfig {
     #[configurable(metadata(docs::examples = "{{ message }}", docs::examples = "{{ hostname }}",))]
     key_field: Option<Template>,
 
+    /// The threshold of the bucket of an event.
+    ///
+    /// The template must render to a positive integer, and the burst of the bucket is the same as
+    /// its threshold. If the event doesn't have the fields used by the template, or if the template
+    /// doesn't render to a positive integer, the threshold is looked up in `threshold_table`, or
+    /// `threshold` is used. Only the latter is reported as an error.
+    #[configurable(metadata(docs::examples = "{{ tenant_threshold }}"))]
+    threshold_field: Option<Template>,
+
+    #[configurable(derived)]
+    threshold_table: Option<ThresholdTableConfig>,
+
     /// A logical condition used to exclude events from sampling.
     exclude: Option<AnyCondition>,
+
+    /// Whether to send events exceeding the rate limit to the `dropped` output instead of
+    /// discarding them.
+    ///
+    /// This can be used to route the overflow to cheaper storage.
+    #[serde(default)]
+    reroute_dropped: bool,
+}
+
+/// Per-key thresholds loaded from an enrichment table.
+#[configurable_component]
+#[derive(Clone, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct ThresholdTableConfig {
+    /// The name of the enrichment table.
+    #[configurable(metadata(docs::examples = "tenant_tiers"))]
+    table: String,
+
+    /// The column matched against the rendered `key_field`.
+    #[configurable(metadata(docs::examples = "tenant"))]
+    key_column: String,
+
+    /// The column holding the threshold of the key.
+    #[configurable(metadata(docs::examples = "threshold"))]
+    threshold_column: String,
+
+    /// The column holding the burst of the key.
+    ///
+    /// If left unspecified, or if the row doesn't have a burst, the burst is the same as the
+    /// threshold of the key.
+    #[configurable(metadata(docs::examples = "burst"))]
+    burst_column: Option<String>,
 }
 
 impl_generate_config_from_default!(ThrottleConfig);
@@ -51,7 +112,7 @@ impl_generate_config_from_default!(ThrottleConfig);
 #[typetag::serde(name = "throttle")]
 impl TransformConfig for ThrottleConfig {
     async fn build(&self, context: &TransformContext) -> crate::Result<Transform> {
-        Throttle::new(self, context, clock::MonotonicClock).map(Transform::event_task)
+        Throttle::new(self, context, clock::MonotonicClock).map(Transform::synchronous)
     }
 
     fn input(&self) -> Input {
@@ -65,19 +126,111 @@ impl TransformConfig for ThrottleConfig {
         _: LogNamespace,
     ) -> Vec<TransformOutput> {
         // The event is not modified, so the definition is passed through as-is
-        vec![TransformOutput::new(
-            DataType::Log,
-            clone_input_definitions(input_definitions),
-        )]
+        let default_output =
+            TransformOutput::new(DataType::Log, clone_input_definitions(input_definitions));
+
+        if self.reroute_dropped {
+            vec![
+                default_output,
+                TransformOutput::new(DataType::Log, clone_input_definitions(input_definitions))
+                    .with_port(DROPPED),
+            ]
+        } else {
+            vec![default_output]
+        }
+    }
+}
+
+/// The steady-state rate and burst of a bucket.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+struct Limits {
+    threshold: NonZeroU32,
+    burst: NonZeroU32,
+}
+
+impl Limits {
+    fn new(threshold: u32, burst: Option<u32>) -> Option<Self> {
+        let threshold = NonZeroU32::new(threshold)?;
+        let burst = match burst {
+            Some(burst) => NonZeroU32::new(burst)?,
+            None => threshold,
+        };
+        Some(Self { threshold, burst })
+    }
+
+    fn quota(&self, window: Duration) -> Quota {
+        let period =
+            Duration::from_secs_f64(window.as_secs_f64() / f64::from(self.threshold.get()));
+        Quota::with_period(period.max(Duration::from_nanos(1)))
+            .expect("period is non-zero")
+            .allow_burst(self.burst)
+    }
+}
+
+#[derive(Clone)]
+struct ThresholdTable {
+    config: ThresholdTableConfig,
+    tables: TableSearch,
+    index: IndexHandle,
+}
+
+impl ThresholdTable {
+    /// Looks up the limits of a key, returning `None` if the table has no row for it.
+    fn lookup(&self, key: &str) -> Result<Option<Limits>, String> {
+        let condition = [enrichment::Condition::Equals {
+            field: &self.config.key_column,
+            value: Value::from(key),
+        }];
+        let rows = self.tables.find_table_rows(
+            &self.config.table,
+            Case::Sensitive,
+            &condition,
+            None,
+            Some(self.index),
+        )?;
+        let row = match rows.as_slice() {
+            [] => return Ok(None),
+            [row] => row,
+            _ => return Err(format!("several rows for key `{}`", key)),
+        };
+
+        let threshold = column_as_u32(row, &self.config.threshold_column)
+            .ok_or_else(|| format!("invalid `{}` column", self.config.threshold_column))?;
+        let burst = self
+            .config
+            .burst_column
+            .as_ref()
+            .and_then(|column| column_as_u32(row, column));
+        Limits::new(threshold, burst)
+            .map(Some)
+            .ok_or_else(|| format!("zero threshold for key `{}`", key))
+    }
+}
+
+fn column_as_u32(row: &std::collections::BTreeMap<String, Value>, column: &str) -> Option<u32> {
+    match row.get(column)? {
+        Value::Integer(value) => u32::try_from(*value).ok(),
+        Value::Bytes(bytes) => std::str::from_utf8(bytes).ok()?.trim().parse().ok(),
+        _ => None,
     }
 }
 
+type KeyedRateLimiter<C> = RateLimiter<Option<String>, DashMapStateStore<Option<String>>, C>;
+
 #[derive(Clone)]
 pub struct Throttle<C: clock::Clock<Instant = I>, I: clock::Reference> {
-    quota: Quota,
+    window: Duration,
+    limits: Limits,
     flush_keys_interval: Duration,
     key_field: Option<Template>,
+    threshold_field: Option<Template>,
+    threshold_table: Option<ThresholdTable>,
     exclude: Option<Condition>,
+    reroute_dropped: bool,
+    /// One rate limiter per distinct set of limits, each holding the buckets of the keys using
+    /// those limits. The limiters left without any bucket are removed periodically.
+    limiters: HashMap<Limits, Arc<KeyedRateLimiter<C>>>,
+    last_flush: Instant,
     clock: C,
 }
 
@@ -91,125 +244,172 @@ where
         context: &TransformContext,
         clock: C,
     ) -> crate::Result<Self> {
-        let flush_keys_interval = config.window_secs;
+        let window = config.window_secs;
 
-        let threshold = match NonZeroU32::new(config.threshold) {
-            Some(threshold) => threshold,
+        let limits = match Limits::new(config.threshold, config.burst) {
+            Some(limits) => limits,
             None => return Err(Box::new(ConfigError::NonZero)),
         };
+        if window.is_zero() {
+            return Err(Box::new(ConfigError::NonZero));
+        }
 
-        let quota = match Quota::with_period(Duration::from_secs_f64(
-            flush_keys_interval.as_secs_f64() / f64::from(threshold.get()),
-        )) {
-            Some(quota) => quota.allow_burst(threshold),
-            None => return Err(Box::new(ConfigError::NonZero)),
-        };
         let exclude = config
             .exclude
             .as_ref()
             .map(|condition| condition.build(&context.enrichment_tables))
             .transpose()?;
 
+        let threshold_table = match &config.threshold_table {
+            Some(table_config) => {
+                if config.key_field.is_none() {
+                    return Err(Box::new(ConfigError::TableWithoutKey));
+                }
+                let mut tables = context.enrichment_tables.clone();
+                let index = tables
+                    .add_index(
+                        &table_config.table,
+                        Case::Sensitive,
+                        &[&table_config.key_column],
+                    )
+                    .map_err(|message| ConfigError::Table { message })?;
+                Some(ThresholdTable {
+                    config: table_config.clone(),
+                    tables: tables.as_readonly(),
+                    index,
+                })
+            }
+            None => None,
+        };
+
         Ok(Self {
-            quota,
-            clock,
-            flush_keys_interval,
+            window,
+            limits,
+            flush_keys_interval: window * 2,
             key_field: config.key_field.clone(),
+            threshold_field: config.threshold_field.clone(),
+            threshold_table,
             exclude,
+            reroute_dropped: config.reroute_dropped,
+            limiters: HashMap::new(),
+            last_flush: Instant::now(),
+            clock,
+        })
+    }
+
+    /// Returns the limits of the bucket of the given event.
+    fn limits_for(&self, event: &Event, key: Option<&str>) -> Limits {
+        if let Some(template) = &self.threshold_field {
+            // Events without the fields of the template use the default threshold, like keys
+            // missing from the table.
+            if let Ok(rendered) = template.render_string(event) {
+                match rendered
+                    .trim()
+                    .parse()
+                    .ok()
+                    .and_then(|threshold| Limits::new(threshold, None))
+                {
+                    Some(limits) => return limits,
+                    None => emit!(ThrottleThresholdError {
+                        error: format!("`threshold_field` rendered to `{}`", rendered),
+                    }),
+                }
+            }
+        }
+
+        if let (Some(table), Some(key)) = (&self.threshold_table, key) {
+            match table.lookup(key) {
+                Ok(Some(limits)) => return limits,
+                // Keys missing from the table use the default threshold.
+                Ok(None) => {}
+                Err(error) => emit!(ThrottleThresholdError { error }),
+            }
+        }
+
+        self.limits
+    }
+
+    fn limiter(&mut self, limits: Limits) -> &KeyedRateLimiter<C> {
+        let window = self.window;
+        let clock = &self.clock;
+        self.limiters.entry(limits).or_insert_with(|| {
+            Arc::new(RateLimiter::dashmap_with_clock(limits.quota(window), clock))
         })
     }
 }
 
-impl<C, I> TaskTransform<Event> for Throttle<C, I>
+impl<C, I> SyncTransform for Throttle<C, I>
 where
-    C: clock::Clock<Instant = I> + Send + 'static,
-    I: clock::Reference + Send + 'static,
+    C: clock::Clock<Instant = I> + Send + Sync + 'static,
+    I: clock::Reference + 'static,
 {
-    fn transform(
-        self: Box<Self>,
-        mut input_rx: Pin<Box<dyn Stream<Item = Event> + Send>>,
-    ) -> Pin<Box<dyn Stream<Item = Event> + Send>>
-    where
-        Self: 'static,
-    {
-        let mut flush_keys = tokio::time::interval(self.flush_keys_interval * 2);
-
-        let limiter = RateLimiter::dashmap_with_clock(self.quota, &self.clock);
-
-        Box::pin(stream! {
-          loop {
-            let done = tokio::select! {
-                biased;
-
-                maybe_event = input_rx.next() => {
-                    match maybe_event {
-                        None => true,
-                        Some(event) => {
-                            let (throttle, event) = match self.exclude.as_ref() {
-                                Some(condition) => {
-                                    let (result, event) = condition.check(event);
-                                    (!result, event)
-                                },
-                                _ => (true, event)
-                            };
-                            let output = if throttle {
-                                let key = self.key_field.as_ref().and_then(|t| {
-                                    t.render_string(&event)
-                                        .map_err(|error| {
-                                            emit!(TemplateRenderingError {
-                                                error,
-                                                field: Some("key_field"),
-                                                drop_event: false,
-                                            })
-                                        })
-                                        .ok()
-                                });
-
-                                match limiter.check_key(&key) {
-                                    Ok(()) => {
-                                        Some(event)
-                                    }
-                                    _ => {
-                                        if let Some(key) = key {
-                                            emit!(ThrottleEventDiscarded{key})
-                                        } else {
-                                            emit!(ThrottleEventDiscarded{key: "None".to_string()})
-                                        }
-                                        None
-                                    }
-                                }
-                            } else {
-                                Some(event)
-                            };
-                            if let Some(event) = output {
-                                yield event;
-                            }
-                            false
-                        }
-                    }
-                }
-                _ = flush_keys.tick() => {
-                    limiter.retain_recent();
-                    false
+    fn transform(&mut self, event: Event, output: &mut TransformOutputsBuf) {
+        if self.last_flush.elapsed() >= self.flush_keys_interval {
+            // A limiter without buckets behaves like a new one, so it can be dropped, which keeps
+            // the limits that are no longer in use from accumulating.
+            self.limiters.retain(|_, limiter| {
+                limiter.retain_recent();
+                limiter.shrink_to_fit();
+                !limiter.is_empty()
+            });
+            self.last_flush = Instant::now();
+        }
+
+        let (throttle, event) = match self.exclude.as_ref() {
+            Some(condition) => {
+                let (result, event) = condition.check(event);
+                (!result, event)
+            }
+            _ => (true, event),
+        };
+        if !throttle {
+            output.push(None, event);
+            return;
+        }
+
+        let key = self.key_field.as_ref().and_then(|t| {
+            t.render_string(&event)
+                .map_err(|error| {
+                    emit!(TemplateRenderingError {
+                        error,
+                        field: Some("key_field"),
+                        drop_event: false,
+                    })
+                })
+                .ok()
+        });
+
+        let limits = self.limits_for(&event, key.as_deref());
+        match self.limiter(limits).check_key(&key) {
+            Ok(()) => output.push(None, event),
+            _ => {
+                if self.reroute_dropped {
+                    output.push(Some(DROPPED), event);
+                } else {
+                    emit!(ThrottleEventDiscarded {
+                        key: key.unwrap_or_else(|| "None".to_string())
+                    });
                 }
-            };
-            if done { break }
-          }
-        })
+            }
+        }
     }
 }
 
 #[derive(Debug, Snafu)]
 pub enum ConfigError {
-    #[snafu(display("`threshold`, and `window_secs` must be non-zero"))]
+    #[snafu(display("`threshold`, `burst`, and `window_secs` must be non-zero"))]
     NonZero,
+    #[snafu(display("`threshold_table` requires `key_field` to be set"))]
+    TableWithoutKey,
+    #[snafu(display("invalid `threshold_table`: {}", message))]
+    Table { message: String },
 }
 
 #[cfg(test)]
 mod tests {
-    use std::task::Poll;
+    use std::collections::BTreeMap;
 
-    use futures::SinkExt;
+    use enrichment::{Table, TableRegistry};
 
     use super::*;
     use crate::{
@@ -224,8 +424,36 @@ mod tests {
         crate::test_util::test_generate_config::<ThrottleConfig>();
     }
 
-    #[tokio::test]
-    async fn throttle_events() {
+    /// Runs one event through the transform, returning the events sent to the default and
+    /// `dropped` outputs.
+    fn transform_one(throttle: &mut dyn SyncTransform, event: Event) -> (Vec<Event>, Vec<Event>) {
+        let mut outputs = TransformOutputsBuf::new_with_capacity(
+            vec![
+                TransformOutput::new(DataType::Log, HashMap::new()),
+                TransformOutput::new(DataType::Log, HashMap::new()).with_port(DROPPED),
+            ],
+            1,
+        );
+        throttle.transform(event, &mut outputs);
+        let dropped = outputs.drain_named(DROPPED).collect();
+        (outputs.drain().collect(), dropped)
+    }
+
+    fn passes(throttle: &mut dyn SyncTransform, event: impl Into<Event>) -> bool {
+        let (passed, _) = transform_one(throttle, event.into());
+        !passed.is_empty()
+    }
+
+    fn log_with(fields: &[(&'static str, &str)]) -> LogEvent {
+        let mut log = LogEvent::default();
+        for (field, value) in fields {
+            log.insert(*field, value.to_string());
+        }
+        log
+    }
+
+    #[test]
+    fn throttle_events() {
         let clock = clock::FakeRelativeClock::default();
         let config = toml::from_str::<ThrottleConfig>(
             r#"
@@ -235,60 +463,25 @@ window_secs = 5
         )
         .unwrap();
 
-        let throttle = Throttle::new(&config, &TransformContext::default(), clock.clone())
-            .map(Transform::event_task)
-            .unwrap();
-
-        let throttle = throttle.into_task();
+        let mut throttle =
+            Throttle::new(&config, &TransformContext::default(), clock.clone()).unwrap();
 
-        let (mut tx, rx) = futures::channel::mpsc::channel(10);
-        let mut out_stream = throttle.transform_events(Box::pin(rx));
-
-        // tokio interval is always immediately ready, so we poll once to make sure
-        // we trip it/set the interval in the future
-        assert_eq!(Poll::Pending, futures::poll!(out_stream.next()));
-
-        tx.send(LogEvent::default().into()).await.unwrap();
-        tx.send(LogEvent::default().into()).await.unwrap();
-
-        let mut count = 0_u8;
-        while count < 2 {
-            if let Some(_event) = out_stream.next().await {
-                count += 1;
-            } else {
-                panic!("Unexpectedly received None in output stream");
-            }
-        }
-        assert_eq!(2, count);
+        assert!(passes(&mut throttle, LogEvent::default()));
+        assert!(passes(&mut throttle, LogEvent::default()));
 
         clock.advance(Duration::from_secs(2));
 
-        tx.send(LogEvent::default().into()).await.unwrap();
-
-        // We should be back to pending, having the second event dropped
-        assert_eq!(Poll::Pending, futures::poll!(out_stream.next()));
+        // The bucket has not refilled yet, so the event is dropped
+        assert!(!passes(&mut throttle, LogEvent::default()));
 
         clock.advance(Duration::from_secs(3));
 
-        tx.send(LogEvent::default().into()).await.unwrap();
-
         // The rate limiter should now be refreshed and allow an additional event through
-        if let Some(_event) = out_stream.next().await {
-        } else {
-            panic!("Unexpectedly received None in output stream");
-        }
-
-        // We should be back to pending, having nothing waiting for us
-        assert_eq!(Poll::Pending, futures::poll!(out_stream.next()));
-
-        tx.disconnect();
-
-        // And still nothing there
-        assert_eq!(Poll::Ready(None), futures::poll!(out_stream.next()));
+        assert!(passes(&mut throttle, LogEvent::default()));
     }
 
-    #[tokio::test]
-    async fn throttle_exclude() {
+    #[test]
+    fn throttle_exclude() {
         let clock = clock::FakeRelativeClock::default();
         let config = toml::from_str::<ThrottleConfig>(
             r#"
@@ -301,116 +494,270 @@ exists(.special)
         )
         .unwrap();
 
-        let throttle = Throttle::new(&config, &TransformContext::default(), clock.clone())
-            .map(Transform::event_task)
-            .unwrap();
+        let mut throttle =
+            Throttle::new(&config, &TransformContext::default(), clock.clone()).unwrap();
 
-        let throttle = throttle.into_task();
+        assert!(passes(&mut throttle, LogEvent::default()));
+        assert!(passes(&mut throttle, LogEvent::default()));
 
-        let (mut tx, rx) = futures::channel::mpsc::channel(10);
-        let mut out_stream = throttle.transform_events(Box::pin(rx));
+        clock.advance(Duration::from_secs(2));
 
-        // tokio interval is always immediately ready, so we poll once to make sure
-        // we trip it/set the interval in the future
-        assert_eq!(Poll::Pending, futures::poll!(out_stream.next()));
+        assert!(!passes(&mut throttle, LogEvent::default()));
 
-        tx.send(LogEvent::default().into()).await.unwrap();
-        tx.send(LogEvent::default().into()).await.unwrap();
+        // The rate limiter should allow this log through regardless of current limit
+        assert!(passes(&mut throttle, log_with(&[("special", "true")])));
 
-        let mut count = 0_u8;
-        while count < 2 {
-            if let Some(_event) = out_stream.next().await {
-                count += 1;
-            } else {
-                panic!("Unexpectedly received None in output stream");
-            }
-        }
-        assert_eq!(2, count);
+        clock.advance(Duration::from_secs(3));
 
-        clock.advance(Duration::from_secs(2));
+        // The rate limiter should now be refreshed and allow an additional event through
+        assert!(passes(&mut throttle, LogEvent::default()));
+    }
 
-        tx.send(LogEvent::default().into()).await.unwrap();
+    #[test]
+    fn throttle_buckets() {
+        let clock = clock::FakeRelativeClock::default();
+        let config = toml::from_str::<ThrottleConfig>(
+            r#"
+threshold = 1
+window_secs = 5
+key_field = "{{ bucket }}"
+"#,
+        )
+        .unwrap();
 
-        // We should be back to pending, having the second event dropped
-        assert_eq!(Poll::Pending, futures::poll!(out_stream.next()));
+        let mut throttle = Throttle::new(&config, &TransformContext::default(), clock).unwrap();
 
-        let mut special_log = LogEvent::default();
-        special_log.insert("special", "true");
-        tx.send(special_log.into()).await.unwrap();
-        // The rate limiter should allow this log through regardless of current limit
-        if let Some(_event) = out_stream.next().await {
-        } else {
-            panic!("Unexpectedly received None in output stream");
-        }
+        assert!(passes(&mut throttle, log_with(&[("bucket", "a")])));
+        assert!(passes(&mut throttle, log_with(&[("bucket", "b")])));
+        assert!(!passes(&mut throttle, log_with(&[("bucket", "a")])));
+    }
 
-        clock.advance(Duration::from_secs(3));
+    #[test]
+    fn throttle_burst() {
+        let clock = clock::FakeRelativeClock::default();
+        let config = toml::from_str::<ThrottleConfig>(
+            r#"
+threshold = 1
+window_secs = 2
+burst = 3
+"#,
+        )
+        .unwrap();
 
-        tx.send(LogEvent::default().into()).await.unwrap();
+        let mut throttle =
+            Throttle::new(&config, &TransformContext::default(), clock.clone()).unwrap();
 
-        // The rate limiter should now be refreshed and allow an additional event through
-        if let Some(_event) = out_stream.next().await {
-        } else {
-            panic!("Unexpectedly received None in output stream");
+        for _ in 0..3 {
+            assert!(passes(&mut throttle, LogEvent::default()));
         }
+        assert!(!passes(&mut throttle, LogEvent::default()));
+
+        // The bucket refills at the steady-state rate, not the burst.
+        clock.advance(Duration::from_secs(2));
+        assert!(passes(&mut throttle, LogEvent::default()));
+        assert!(!passes(&mut throttle, LogEvent::default()));
+    }
 
-        // We should be back to pending, having nothing waiting for us
-        assert_eq!(Poll::Pending, futures::poll!(out_stream.next()));
+    #[test]
+    fn throttle_reroute_dropped() {
+        let clock = clock::FakeRelativeClock::default();
+        let config = toml::from_str::<ThrottleConfig>(
+            r#"
+threshold = 1
+window_secs = 5
+reroute_dropped = true
+"#,
+        )
+        .unwrap();
 
-        tx.disconnect();
+        let mut throttle = Throttle::new(&config, &TransformContext::default(), clock).unwrap();
 
-        // And still nothing there
-        assert_eq!(Poll::Ready(None), futures::poll!(out_stream.next()));
+        let (passed, dropped) = transform_one(&mut throttle, LogEvent::from("first").into());
+        assert_eq!((passed.len(), dropped.len()), (1, 0));
+
+        let (passed, dropped) = transform_one(&mut throttle, LogEvent::from("second").into());
+        assert_eq!((passed.len(), dropped.len()), (0, 1));
+        assert_eq!(
+            dropped[0].as_log()["message"],
+            LogEvent::from("second")["message"]
+        );
     }
 
-    #[tokio::test]
-    async fn throttle_buckets() {
+    #[test]
+    fn throttle_threshold_field() {
         let clock = clock::FakeRelativeClock::default();
         let config = toml::from_str::<ThrottleConfig>(
             r#"
 threshold = 1
 window_secs = 5
-key_field = "{{ bucket }}"
+key_field = "{{ tenant }}"
+threshold_field = "{{ limit }}"
 "#,
         )
         .unwrap();
 
-        let throttle = Throttle::new(&config, &TransformContext::default(), clock.clone())
-            .map(Transform::event_task)
-            .unwrap();
+        let mut throttle = Throttle::new(&config, &TransformContext::default(), clock).unwrap();
 
-        let throttle = throttle.into_task();
+        let premium = || log_with(&[("tenant", "premium"), ("limit", "3")]);
+        for _ in 0..3 {
+            assert!(passes(&mut throttle, premium()));
+        }
+        assert!(!passes(&mut throttle, premium()));
 
-        let (mut tx, rx) = futures::channel::mpsc::channel(10);
-        let mut out_stream = throttle.transform_events(Box::pin(rx));
+        // Keys without a valid threshold fall back to the default threshold.
+        let free = || log_with(&[("tenant", "free"), ("limit", "lots")]);
+        assert!(passes(&mut throttle, free()));
+        assert!(!passes(&mut throttle, free()));
 
-        // tokio interval is always immediately ready, so we poll once to make sure
-        // we trip it/set the interval in the future
-        assert_eq!(Poll::Pending, futures::poll!(out_stream.next()));
+        // So do events without the threshold field.
+        let basic = || log_with(&[("tenant", "basic")]);
+        assert!(passes(&mut throttle, basic()));
+        assert!(!passes(&mut throttle, basic()));
+    }
 
-        let mut log_a = LogEvent::default();
-        log_a.insert("bucket", "a");
-        let mut log_b = LogEvent::default();
-        log_b.insert("bucket", "b");
-        tx.send(log_a.into()).await.unwrap();
-        tx.send(log_b.into()).await.unwrap();
+    #[derive(Clone)]
+    struct TiersTable;
+
+    impl Table for TiersTable {
+        fn find_table_row(
+            &self,
+            case: Case,
+            condition: &[enrichment::Condition],
+            select: Option<&[String]>,
+            index: Option<IndexHandle>,
+        ) -> Result<BTreeMap<String, Value>, String> {
+            let mut rows = self.find_table_rows(case, condition, select, index)?;
+            rows.pop().ok_or_else(|| "no rows found".to_string())
+        }
 
-        let mut count = 0_u8;
-        while count < 2 {
-            if let Some(_event) = out_stream.next().await {
-                count += 1;
-            } else {
-                panic!("Unexpectedly received None in output stream");
-            }
+        fn find_table_rows(
+            &self,
+            _case: Case,
+            condition: &[enrichment::Condition],
+            _select: Option<&[String]>,
+            _index: Option<IndexHandle>,
+        ) -> Result<Vec<BTreeMap<String, Value>>, String> {
+            Ok(match condition {
+                [enrichment::Condition::Equals { field, value }]
+                    if *field == "tenant" && *value == Value::from("gold") =>
+                {
+                    vec![BTreeMap::from([
+                        ("tenant".to_string(), Value::from("gold")),
+                        ("threshold".to_string(), Value::from(1_i64)),
+                        ("burst".to_string(), Value::from(2_i64)),
+                    ])]
+                }
+                _ => Vec::new(),
+            })
+        }
+
+        fn add_index(&mut self, _case: Case, _fields: &[&str]) -> Result<IndexHandle, String> {
+            Ok(IndexHandle(0))
+        }
+
+        fn index_fields(&self) -> Vec<(Case, Vec<String>)> {
+            Vec::new()
+        }
+
+        fn needs_reload(&self) -> bool {
+            false
         }
-        assert_eq!(2, count);
+    }
+
+    #[test]
+    fn throttle_threshold_table() {
+        let registry = TableRegistry::default();
+        registry.load(HashMap::from([(
+            "tiers".to_string(),
+            Box::new(TiersTable) as Box<dyn Table + Send + Sync>,
+        )]));
+        let context = TransformContext {
+            enrichment_tables: registry.clone(),
+            ..Default::default()
+        };
+
+        let clock = clock::FakeRelativeClock::default();
+        let config = toml::from_str::<ThrottleConfig>(
+            r#"
+threshold = 3
+window_secs = 5
+key_field = "{{ tenant }}"
+threshold_table.table = "tiers"
+threshold_table.key_column = "tenant"
+threshold_table.threshold_column = "threshold"
+threshold_table.burst_column = "burst"
+"#,
+        )
+        .unwrap();
 
-        // We should be back to pending, having nothing waiting for us
-        assert_eq!(Poll::Pending, futures::poll!(out_stream.next()));
+        let mut throttle = Throttle::new(&config, &context, clock).unwrap();
+        registry.finish_load();
 
-        tx.disconnect();
+        let gold = || log_with(&[("tenant", "gold")]);
+        assert!(passes(&mut throttle, gold()));
+        assert!(passes(&mut throttle, gold()));
+        assert!(!passes(&mut throttle, gold()));
 
-        // And still nothing there
-        assert_eq!(Poll::Ready(None), futures::poll!(out_stream.next()));
+        // Keys missing from the table use the default threshold.
+        let other = || log_with(&[("tenant", "other")]);
+        for _ in 0..3 {
+            assert!(passes(&mut throttle, other()));
+        }
+        assert!(!passes(&mut throttle, other()));
+    }
+
+    #[test]
+    fn throttle_evicts_idle_limiters() {
+        let clock = clock::FakeRelativeClock::default();
+        let config = toml::from_str::<ThrottleConfig>(
+            r#"
+threshold = 3
+window_secs = 5
+key_field = "{{ tenant }}"
+threshold_field = "{{ limit }}"
+"#,
+        )
+        .unwrap();
+        let mut throttle =
+            Throttle::new(&config, &TransformContext::default(), clock.clone()).unwrap();
+
+        for (tenant, limit) in [("a", "1"), ("b", "2"), ("c", "3")] {
+            assert!(passes(
+                &mut throttle,
+                log_with(&[("tenant", tenant), ("limit", limit)])
+            ));
+        }
+        assert_eq!(throttle.limiters.len(), 3);
+
+        // Once the buckets have refilled, the limiters are dropped at the next flush.
+        clock.advance(Duration::from_secs(10));
+        throttle.last_flush = Instant::now() - throttle.flush_keys_interval;
+        assert!(passes(
+            &mut throttle,
+            log_with(&[("tenant", "d"), ("limit", "4")])
+        ));
+        assert_eq!(throttle.limiters.len(), 1);
+    }
+
+    #[test]
+    fn threshold_table_requires_key_field() {
+        let config = toml::from_str::<ThrottleConfig>(
+            r#"
+threshold = 3
+window_secs = 5
+threshold_table.table = "tiers"
+threshold_table.key_column = "tenant"
+threshold_table.threshold_column = "threshold"
+"#,
+        )
+        .unwrap();
+
+        assert!(Throttle::new(
+            &config,
+            &TransformContext::default(),
+            clock::FakeRelativeClock::default()
+        )
+        .is_err());
     }
 
     #[tokio::test]
@@ -419,8 +766,7 @@ key_field = "{{ bucket }}"
             let config = ThrottleConfig {
                 threshold: 1,
                 window_secs: Duration::from_secs_f64(1.0),
-                key_field: None,
-                exclude: None,
+                ..Default::default()
             };
             let (tx, rx) = mpsc::channel(1);
             let (topology, mut out) = create_topology(ReceiverStream::new(rx), config).await;
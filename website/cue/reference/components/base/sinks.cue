@@ -12,6 +12,39 @@ base: components: sinks: configuration: {
 			"""
 		required: false
 		type: object: options: {
+			encryption: {
+				description: """
+					Encryption at rest for disk buffers.
+
+					Records are encrypted with XChaCha20-Poly1305 before being written to disk. Keys are 256 bits,
+					encoded as 64 hexadecimal characters, and are best provided through the secrets subsystem (for
+					example, `SECRET[backend.buffer_key]`) rather than directly in the configuration.
+					"""
+				relevant_when: "type = \"disk\""
+				required:      false
+				type: object: options: {
+					key: {
+						description: "The key used to encrypt records written to the buffer."
+						required:    true
+						type: string: examples: ["SECRET[backend.buffer_key]"]
+					}
+					previous_keys: {
+						description: """
+							Keys that records already in the buffer may have been encrypted with.
+
+							When rotating keys, move the old key here so that records written before the rotation can
+							still be read. The buffer refuses to start if it contains records encrypted with a key that
+							is neither the current key nor one of the previous keys. A previous key can be removed once
+							the buffer has been fully drained.
+							"""
+						required: false
+						type: array: {
+							default: []
+							items: type: string: {}
+						}
+					}
+				}
+			}
 			max_events: {
 				description:   "The maximum number of events allowed in the buffer."
 				relevant_when: "type = \"memory\""
@@ -71,6 +104,16 @@ base: components: sinks: configuration: {
 														highest priority, and it is preferable to temporarily lose events rather than cause a
 														slowdown in the acceptance/consumption of events.
 														"""
+						overflow: """
+														Overflows to the next stage in the buffer topology.
+
+														If the current buffer stage is full, attempt to send this event to the next buffer stage.
+														That stage may also be configured overflow, and so on, but ultimately the last stage in a
+														buffer topology must use one of the other handling behaviors. This means that next stage may
+														potentially be able to buffer the event, but it may also block or drop the event.
+
+														This mode can only be used when two or more buffer stages are configured.
+														"""
 					}
 				}
 			}
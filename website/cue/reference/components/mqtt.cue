@@ -0,0 +1,63 @@
+package metadata
+
+components: _mqtt: {
+	features: {
+		collect: from: {
+			service: services.mqtt
+			interface: {
+				socket: {
+					api: {
+						title: "MQTT protocol"
+						url:   urls.mqtt
+					}
+					direction: "incoming"
+					port:      1883
+					protocols: ["tcp"]
+					ssl: "optional"
+				}
+			}
+		}
+
+		send: to: {
+			service: services.mqtt
+			interface: {
+				socket: {
+					api: {
+						title: "MQTT protocol"
+						url:   urls.mqtt
+					}
+					direction: "outgoing"
+					protocols: ["tcp"]
+					ssl: "optional"
+				}
+			}
+		}
+	}
+
+	support: {
+		requirements: []
+		notices: []
+		warnings: []
+	}
+
+	how_it_works: {
+		rumqttc: {
+			title: "rumqttc"
+			body:  """
+				The `mqtt` source/sink uses [`rumqttc`](\(urls.rumqttc)) under the hood, and supports
+				version 3.1.1 of the MQTT protocol.
+				"""
+		}
+
+		persistent_sessions: {
+			title: "Persistent sessions"
+			body: """
+				When `clean_session` is disabled, the broker keeps the session identified by `client_id`
+				across reconnections. For the `mqtt` source, this includes its subscriptions, along with
+				the messages published to them while Vector is disconnected, provided that they are
+				published and subscribed to with a QoS of `at_least_once` or `exactly_once`. Those
+				messages are delivered once Vector reconnects.
+				"""
+		}
+	}
+}
@@ -181,6 +181,21 @@ components: sinks: [Name=string]: {
 														[gelf]: https://docs.graylog.org/docs/gelf
 														"""
 												}
+												if codec == "syslog" {
+													syslog: """
+														Encodes an event as a [syslog][syslog] message, in either the RFC 5424 or RFC 3164 format.
+
+														[syslog]: https://en.wikipedia.org/wiki/Syslog
+														"""
+												}
+												if codec == "protobuf" {
+													protobuf: """
+														Encodes an event as a [Protocol Buffers][protobuf] message, using a message type loaded
+														from a descriptor set file.
+
+														[protobuf]: https://protobuf.dev/
+														"""
+												}
 												if codec == "avro" {
 													avro: """
 														Encodes an event as an [Apache Avro][apache_avro] message.
@@ -188,6 +203,20 @@ components: sinks: [Name=string]: {
 														[apache_avro]: https://avro.apache.org/
 														"""
 												}
+												if codec == "cef" {
+													cef: """
+														Encodes an event as an [ArcSight CEF][cef] message.
+
+														[cef]: https://www.microfocus.com/documentation/arcsight/arcsight-smartconnectors/pdfdoc/common-event-format-v25/common-event-format-v25.pdf
+														"""
+												}
+												if codec == "leef" {
+													leef: """
+														Encodes an event as an [IBM QRadar LEEF][leef] message.
+
+														[leef]: https://www.ibm.com/docs/en/dsm?topic=leef-overview
+														"""
+												}
 											}
 										}
 									}
@@ -216,6 +245,45 @@ components: sinks: [Name=string]: {
 												}
 											}
 										}
+										if codec == "protobuf" {
+											protobuf: {
+												description:   "Options for the Protobuf serializer."
+												required:      true
+												relevant_when: "codec = `protobuf`"
+												type: object: options: {
+													desc_file: {
+														description: "The path to the protobuf descriptor set file."
+														required:    true
+														type: string: {
+															examples: ["/etc/vector/protobuf_descriptor_set.desc"]
+														}
+													}
+													message_type: {
+														description: "The name of the message type to use for serializing."
+														required:    true
+														type: string: {
+															examples: ["package.Message"]
+														}
+													}
+												}
+											}
+										}
+										if codec == "leef" {
+											leef: {
+												description:   "LEEF-specific encoding options."
+												required:      false
+												relevant_when: "codec = `leef`"
+												type: object: options: {
+													delimiter: {
+														description: "The ASCII (7-bit) character that separates the attributes of LEEF 2.0 messages."
+														required:    false
+														type: uint: {
+															default: 9
+														}
+													}
+												}
+											}
+										}
 									}
 								}
 
@@ -279,6 +347,7 @@ components: sinks: [Name=string]: {
 												character_delimited: "Byte frames are delimited by a chosen character."
 												length_delimited:    "Byte frames are prefixed by an unsigned big-endian 32-bit integer indicating the length."
 												newline_delimited:   "Byte frames are delimited by a newline character."
+												octet_counting:      "Byte frames are prefixed by their length in ASCII decimal digits followed by a space."
 											}
 										}
 									}
@@ -655,6 +724,8 @@ components: sinks: [Name=string]: {
 		buffer_byte_size:                     components.sources.internal_metrics.output.metrics.buffer_byte_size
 		buffer_discarded_events_total:        components.sources.internal_metrics.output.metrics.buffer_discarded_events_total
 		buffer_events:                        components.sources.internal_metrics.output.metrics.buffer_events
+		buffer_overflowed_bytes_total:        components.sources.internal_metrics.output.metrics.buffer_overflowed_bytes_total
+		buffer_overflowed_events_total:       components.sources.internal_metrics.output.metrics.buffer_overflowed_events_total
 		buffer_received_events_total:         components.sources.internal_metrics.output.metrics.buffer_received_events_total
 		buffer_received_event_bytes_total:    components.sources.internal_metrics.output.metrics.buffer_received_event_bytes_total
 		buffer_sent_events_total:             components.sources.internal_metrics.output.metrics.buffer_sent_events_total
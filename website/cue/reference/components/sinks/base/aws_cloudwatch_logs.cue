@@ -237,6 +237,16 @@ base: components: sinks: aws_cloudwatch_logs: configuration: {
 
 						[apache_avro]: https://avro.apache.org/
 						"""
+					cef: """
+						Encodes an event as an [ArcSight CEF][cef] message.
+
+						The header is built from the `cefVersion`, `deviceVendor`, `deviceProduct`,
+						`deviceVersion`, `deviceEventClassId`, `name`, and `severity` fields, as set by the `cef`
+						decoder. All of them are required, except for `cefVersion`, which defaults to `0`. Every
+						other top-level field is encoded as an extension.
+
+						[cef]: https://www.microfocus.com/documentation/arcsight/arcsight-smartconnectors/pdfdoc/common-event-format-v25/common-event-format-v25.pdf
+						"""
 					csv: """
 						Encodes an event as a CSV message.
 
@@ -252,6 +262,17 @@ base: components: sinks: aws_cloudwatch_logs: configuration: {
 
 						[json]: https://www.json.org/
 						"""
+					leef: """
+						Encodes an event as an [IBM QRadar LEEF][leef] message.
+
+						The header is built from the `leefVersion`, `deviceVendor`, `deviceProduct`,
+						`deviceVersion`, and `eventId` fields, as set by the `leef` decoder. All of them are
+						required, except for `leefVersion`, which defaults to `2.0`. Every other top-level field is
+						encoded as an attribute. Values containing the attribute delimiter cause the event to be
+						rejected, since LEEF doesn't support escaping.
+
+						[leef]: https://www.ibm.com/docs/en/dsm?topic=leef-overview
+						"""
 					logfmt: """
 						Encodes an event as a [logfmt][logfmt] message.
 
@@ -273,6 +294,17 @@ base: components: sinks: aws_cloudwatch_logs: configuration: {
 						[vector_native_json]: https://github.com/vectordotdev/vector/blob/master/lib/codecs/tests/data/native_encoding/schema.cue
 						[experimental]: https://vector.dev/highlights/2022-03-31-native-event-codecs
 						"""
+					protobuf: """
+						Encodes an event as a [Protocol Buffers][protobuf] message.
+
+						Fields of the event are mapped to the fields of the configured message type by name,
+						including nested messages, repeated fields, maps, and enums (by name or number).
+						Timestamps are encoded as `google.protobuf.Timestamp` messages. Event fields that do not
+						exist in the message type are ignored, while values that cannot be converted to the type of
+						their field cause the event to be rejected.
+
+						[protobuf]: https://protobuf.dev/
+						"""
 					raw_message: """
 						No encoding.
 
@@ -282,6 +314,20 @@ base: components: sinks: aws_cloudwatch_logs: configuration: {
 						transform) and removing the message field while doing additional parsing on it, as this
 						could lead to the encoding emitting empty strings for the given event.
 						"""
+					syslog: """
+						Encodes an event as a [syslog][syslog] message.
+
+						Both the [RFC 5424][rfc5424] and the legacy [RFC 3164][rfc3164] formats are supported.
+
+						The timestamp, hostname, and message are taken from the timestamp, host, and message fields
+						of the event. The other parts of the message are taken from the configured event fields,
+						which default to the fields set by the `syslog` decoder. Setting a field to an empty string
+						leaves that part of the message unset.
+
+						[syslog]: https://en.wikipedia.org/wiki/Syslog
+						[rfc5424]: https://datatracker.ietf.org/doc/html/rfc5424
+						[rfc3164]: https://datatracker.ietf.org/doc/html/rfc3164
+						"""
 					text: """
 						Plain text encoding.
 
@@ -317,6 +363,21 @@ base: components: sinks: aws_cloudwatch_logs: configuration: {
 				required:    false
 				type: array: items: type: string: {}
 			}
+			leef: {
+				description:   "LEEF-specific encoding options."
+				relevant_when: "codec = \"leef\""
+				required:      false
+				type: object: options: delimiter: {
+					description: """
+						The ASCII (7-bit) character that separates the attributes of LEEF 2.0 messages.
+
+						The delimiter is announced in the header of each message. LEEF 1.0 messages always use a
+						tab.
+						"""
+					required: false
+					type: uint: default: 9
+				}
+			}
 			metric_tag_values: {
 				description: """
 					Controls how metric tag values are encoded.
@@ -343,6 +404,112 @@ base: components: sinks: aws_cloudwatch_logs: configuration: {
 				required:    false
 				type: array: items: type: string: {}
 			}
+			protobuf: {
+				description:   "Options for the Protobuf serializer."
+				relevant_when: "codec = \"protobuf\""
+				required:      true
+				type: object: options: {
+					desc_file: {
+						description: """
+							The path to the protobuf descriptor set file.
+
+							This file is the output of `protoc -o <path> ...`, and must contain the definition of
+							`message_type` along with any message types it depends on.
+							"""
+						required: true
+						type: string: examples: ["/etc/vector/protobuf_descriptor_set.desc"]
+					}
+					message_type: {
+						description: "The name of the message type to use for serializing."
+						required:    true
+						type: string: examples: ["package.Message"]
+					}
+				}
+			}
+			syslog: {
+				description:   "Syslog-specific encoding options."
+				relevant_when: "codec = \"syslog\""
+				required:      false
+				type: object: options: {
+					app_name_field: {
+						description: """
+							The field containing the name of the application that originated the message.
+
+							This is used as the tag of RFC 3164 messages.
+							"""
+						required: false
+						type: string: default: "appname"
+					}
+					facility_field: {
+						description: """
+							The field containing the facility of the message.
+
+							The facility can either be a name, such as `local0`, or its numerical code. If the field is
+							missing or has an invalid value, the `user` facility is used.
+							"""
+						required: false
+						type: string: default: "facility"
+					}
+					msg_id_field: {
+						description: """
+							The field containing the type of the message.
+
+							This is only used by RFC 5424 messages.
+							"""
+						required: false
+						type: string: default: "msgid"
+					}
+					proc_id_field: {
+						description: "The field containing the ID of the process that originated the message."
+						required:    false
+						type: string: default: "procid"
+					}
+					rfc: {
+						description: "The syslog protocol to encode messages with."
+						required:    false
+						type: string: {
+							default: "rfc5424"
+							enum: {
+								rfc3164: """
+									The legacy [BSD syslog protocol][rfc3164].
+
+									Structured data isn't supported by this protocol, and is left out of the message.
+
+									[rfc3164]: https://datatracker.ietf.org/doc/html/rfc3164
+									"""
+								rfc5424: """
+									The [syslog protocol][rfc5424].
+
+									[rfc5424]: https://datatracker.ietf.org/doc/html/rfc5424
+									"""
+							}
+						}
+					}
+					severity_field: {
+						description: """
+							The field containing the severity of the message.
+
+							The severity can either be a name, such as `err` or `warning`, or its numerical code. If the
+							field is missing or has an invalid value, the `informational` severity is used.
+							"""
+						required: false
+						type: string: default: "severity"
+					}
+					structured_data_field: {
+						description: """
+							The field containing the structured data of the message.
+
+							The field must be a map of structured data IDs to maps of parameter names to their values,
+							such as `{"exampleSDID@32473": {"iut": "3", "eventSource": "Application"}}`. Arrays of
+							values are encoded as repeated parameters.
+
+							This is only used by RFC 5424 messages.
+							"""
+						required: false
+						type: string: examples: ["structured_data"]
+					}
+				}
+			}
 			timestamp_format: {
 				description: "Format used for timestamp fields."
 				required:    false
@@ -236,6 +236,113 @@ base: components: sinks: aws_s3: configuration: {
 			}
 		}
 	}
+	batch_encoding: {
+		description: """
+			Batch encoding configuration.
+
+			When set, each batch of events is encoded as a single object with the configured codec,
+			such as a Parquet file, instead of as a stream of framed events. The codec and framing of
+			`encoding` are then ignored, but its other options still apply. As the codec handles its own
+			compression, `compression` is ignored as well.
+			"""
+		required: false
+		type: object: options: {
+			codec: {
+				description: "The codec to use for encoding batches of events."
+				required:    true
+				type: string: enum: parquet: """
+					Encodes each batch of events as an [Apache Parquet][parquet] file.
+
+					Each event is written as a row, whose columns are filled from the top-level fields of the
+					event. The schema of the file is either configured, or derived from the schema definition
+					of the events.
+
+					[parquet]: https://parquet.apache.org/
+					"""
+			}
+			parquet: {
+				description:   "Parquet-specific encoding options."
+				relevant_when: "codec = \"parquet\""
+				required:      false
+				type: object: options: {
+					compression: {
+						description: "The compression applied to the pages of the Parquet files."
+						required:    false
+						type: string: {
+							default: "snappy"
+							enum: {
+								gzip: """
+									[Gzip][gzip] compression.
+
+									[gzip]: https://www.gzip.org/
+									"""
+								lz4: """
+									[LZ4][lz4] compression.
+
+									[lz4]: https://lz4.github.io/lz4/
+									"""
+								none: "No compression."
+								snappy: """
+									[Snappy][snappy] compression.
+
+									[snappy]: https://github.com/google/snappy
+									"""
+								zstd: """
+									[Zstandard][zstd] compression.
+
+									[zstd]: https://facebook.github.io/zstd/
+									"""
+							}
+						}
+					}
+					row_group_size: {
+						description: """
+							The maximum number of rows in each row group.
+
+							Batches with more events are written as several row groups in the same file.
+							"""
+						required: false
+						type: uint: default: 1048576
+					}
+					schema: {
+						description: """
+							The columns of the Parquet files, and their types.
+
+							Each column is filled from the top-level event field of the same name. Missing fields and
+							`null` values are written as nulls, while values that cannot be converted to the type of
+							their column cause the whole batch to be rejected. Other fields are not written.
+
+							When not set, the columns are derived from the fields known by the [schema
+							definition][schema] of the events, which requires schema support to be enabled. Fields
+							with more than one possible type, other than integers and floats, are written as JSON.
+
+							[schema]: https://vector.dev/docs/reference/configuration/global-options/#schema
+							"""
+						required: false
+						type: object: {
+							examples: [{
+								message:   "string"
+								status:    "int64"
+								timestamp: "timestamp"
+							}]
+							options: "*": {
+								description: "The type of a column."
+								required:    true
+								type: string: enum: {
+									boolean:   "Booleans."
+									float64:   "64-bit floating point numbers. Integers are converted to floats."
+									int64:     "64-bit signed integers."
+									json:      "Any value, written as a JSON string."
+									string:    "UTF-8 strings. Values that aren't strings are written as their string representation."
+									timestamp: "Timestamps, with a precision of microseconds, in UTC."
+								}
+							}
+						}
+					}
+				}
+			}
+		}
+	}
 	bucket: {
 		description: """
 			The S3 bucket name.
@@ -263,7 +370,22 @@ base: components: sinks: aws_s3: configuration: {
 
 					[gzip]: https://www.gzip.org/
 					"""
+				lz4: """
+					[LZ4][lz4] compression, using the frame format.
+
+					[lz4]: https://github.com/lz4/lz4/blob/dev/doc/lz4_Frame_format.md
+					"""
 				none: "No compression."
+				snappy: """
+					[Snappy][snappy] compression, using the raw block format.
+
+					[snappy]: https://github.com/google/snappy/blob/main/format_description.txt
+					"""
+				snappy_framed: """
+					[Snappy][snappy] compression, using the framing format.
+
+					[snappy]: https://github.com/google/snappy/blob/main/framing_format.txt
+					"""
 				zlib: """
 					[Zlib][zlib] compression.
 
@@ -325,6 +447,16 @@ base: components: sinks: aws_s3: configuration: {
 
 						[apache_avro]: https://avro.apache.org/
 						"""
+					cef: """
+						Encodes an event as an [ArcSight CEF][cef] message.
+
+						The header is built from the `cefVersion`, `deviceVendor`, `deviceProduct`,
+						`deviceVersion`, `deviceEventClassId`, `name`, and `severity` fields, as set by the `cef`
+						decoder. All of them are required, except for `cefVersion`, which defaults to `0`. Every
+						other top-level field is encoded as an extension.
+
+						[cef]: https://www.microfocus.com/documentation/arcsight/arcsight-smartconnectors/pdfdoc/common-event-format-v25/common-event-format-v25.pdf
+						"""
 					csv: """
 						Encodes an event as a CSV message.
 
@@ -340,6 +472,17 @@ base: components: sinks: aws_s3: configuration: {
 
 						[json]: https://www.json.org/
 						"""
+					leef: """
+						Encodes an event as an [IBM QRadar LEEF][leef] message.
+
+						The header is built from the `leefVersion`, `deviceVendor`, `deviceProduct`,
+						`deviceVersion`, and `eventId` fields, as set by the `leef` decoder. All of them are
+						required, except for `leefVersion`, which defaults to `2.0`. Every other top-level field is
+						encoded as an attribute. Values containing the attribute delimiter cause the event to be
+						rejected, since LEEF doesn't support escaping.
+
+						[leef]: https://www.ibm.com/docs/en/dsm?topic=leef-overview
+						"""
 					logfmt: """
 						Encodes an event as a [logfmt][logfmt] message.
 
@@ -361,6 +504,17 @@ base: components: sinks: aws_s3: configuration: {
 						[vector_native_json]: https://github.com/vectordotdev/vector/blob/master/lib/codecs/tests/data/native_encoding/schema.cue
 						[experimental]: https://vector.dev/highlights/2022-03-31-native-event-codecs
 						"""
+					protobuf: """
+						Encodes an event as a [Protocol Buffers][protobuf] message.
+
+						Fields of the event are mapped to the fields of the configured message type by name,
+						including nested messages, repeated fields, maps, and enums (by name or number).
+						Timestamps are encoded as `google.protobuf.Timestamp` messages. Event fields that do not
+						exist in the message type are ignored, while values that cannot be converted to the type of
+						their field cause the event to be rejected.
+
+						[protobuf]: https://protobuf.dev/
+						"""
 					raw_message: """
 						No encoding.
 
@@ -370,6 +524,20 @@ base: components: sinks: aws_s3: configuration: {
 						transform) and removing the message field while doing additional parsing on it, as this
 						could lead to the encoding emitting empty strings for the given event.
 						"""
+					syslog: """
+						Encodes an event as a [syslog][syslog] message.
+
+						Both the [RFC 5424][rfc5424] and the legacy [RFC 3164][rfc3164] formats are supported.
+
+						The timestamp, hostname, and message are taken from the timestamp, host, and message fields
+						of the event. The other parts of the message are taken from the configured event fields,
+						which default to the fields set by the `syslog` decoder. Setting a field to an empty string
+						leaves that part of the message unset.
+
+						[syslog]: https://en.wikipedia.org/wiki/Syslog
+						[rfc5424]: https://datatracker.ietf.org/doc/html/rfc5424
+						[rfc3164]: https://datatracker.ietf.org/doc/html/rfc3164
+						"""
 					text: """
 						Plain text encoding.
 
@@ -405,6 +573,21 @@ base: components: sinks: aws_s3: configuration: {
 				required:    false
 				type: array: items: type: string: {}
 			}
+			leef: {
+				description:   "LEEF-specific encoding options."
+				relevant_when: "codec = \"leef\""
+				required:      false
+				type: object: options: delimiter: {
+					description: """
+						The ASCII (7-bit) character that separates the attributes of LEEF 2.0 messages.
+
+						The delimiter is announced in the header of each message. LEEF 1.0 messages always use a
+						tab.
+						"""
+					required: false
+					type: uint: default: 9
+				}
+			}
 			metric_tag_values: {
 				description: """
 					Controls how metric tag values are encoded.
@@ -431,6 +614,112 @@ base: components: sinks: aws_s3: configuration: {
 				required:    false
 				type: array: items: type: string: {}
 			}
+			protobuf: {
+				description:   "Options for the Protobuf serializer."
+				relevant_when: "codec = \"protobuf\""
+				required:      true
+				type: object: options: {
+					desc_file: {
+						description: """
+							The path to the protobuf descriptor set file.
+
+							This file is the output of `protoc -o <path> ...`, and must contain the definition of
+							`message_type` along with any message types it depends on.
+							"""
+						required: true
+						type: string: examples: ["/etc/vector/protobuf_descriptor_set.desc"]
+					}
+					message_type: {
+						description: "The name of the message type to use for serializing."
+						required:    true
+						type: string: examples: ["package.Message"]
+					}
+				}
+			}
+			syslog: {
+				description:   "Syslog-specific encoding options."
+				relevant_when: "codec = \"syslog\""
+				required:      false
+				type: object: options: {
+					app_name_field: {
+						description: """
+							The field containing the name of the application that originated the message.
+
+							This is used as the tag of RFC 3164 messages.
+							"""
+						required: false
+						type: string: default: "appname"
+					}
+					facility_field: {
+						description: """
+							The field containing the facility of the message.
+
+							The facility can either be a name, such as `local0`, or its numerical code. If the field is
+							missing or has an invalid value, the `user` facility is used.
+							"""
+						required: false
+						type: string: default: "facility"
+					}
+					msg_id_field: {
+						description: """
+							The field containing the type of the message.
+
+							This is only used by RFC 5424 messages.
+							"""
+						required: false
+						type: string: default: "msgid"
+					}
+					proc_id_field: {
+						description: "The field containing the ID of the process that originated the message."
+						required:    false
+						type: string: default: "procid"
+					}
+					rfc: {
+						description: "The syslog protocol to encode messages with."
+						required:    false
+						type: string: {
+							default: "rfc5424"
+							enum: {
+								rfc3164: """
+									The legacy [BSD syslog protocol][rfc3164].
+
+									Structured data isn't supported by this protocol, and is left out of the message.
+
+									[rfc3164]: https://datatracker.ietf.org/doc/html/rfc3164
+									"""
+								rfc5424: """
+									The [syslog protocol][rfc5424].
+
+									[rfc5424]: https://datatracker.ietf.org/doc/html/rfc5424
+									"""
+							}
+						}
+					}
+					severity_field: {
+						description: """
+							The field containing the severity of the message.
+
+							The severity can either be a name, such as `err` or `warning`, or its numerical code. If the
+							field is missing or has an invalid value, the `informational` severity is used.
+							"""
+						required: false
+						type: string: default: "severity"
+					}
+					structured_data_field: {
+						description: """
+							The field containing the structured data of the message.
+
+							The field must be a map of structured data IDs to maps of parameter names to their values,
+							such as `{"exampleSDID@32473": {"iut": "3", "eventSource": "Application"}}`. Arrays of
+							values are encoded as repeated parameters.
+
+							This is only used by RFC 5424 messages.
+							"""
+						required: false
+						type: string: examples: ["structured_data"]
+					}
+				}
+			}
 			timestamp_format: {
 				description: "Format used for timestamp fields."
 				required:    false
@@ -464,7 +753,8 @@ base: components: sinks: aws_s3: configuration: {
 		description: """
 			The filename extension to use in the object key.
 
-			This overrides setting the extension based on the configured `compression`.
+			This overrides setting the extension based on the configured `compression`, or on the
+			configured `batch_encoding`.
 			"""
 		required: false
 		type: string: examples: [
@@ -519,6 +809,14 @@ base: components: sinks: aws_s3: configuration: {
 						The prefix is a 32-bit unsigned integer, little endian.
 						"""
 					newline_delimited: "Event data is delimited by a newline (LF) character."
+					octet_counting: """
+						Event data is prefixed with its length in bytes, as ASCII decimal digits followed by a
+						space, according to the [octet counting][octet_counting] format.
+
+						This allows messages to contain newlines, and is expected by syslog receivers over TCP.
+
+						[octet_counting]: https://tools.ietf.org/html/rfc6587#section-3.4.1
+						"""
 				}
 			}
 		}
@@ -0,0 +1,289 @@
+package metadata
+
+base: components: sinks: fluent: configuration: {
+	ack_timeout_secs: {
+		description: "The amount of time to wait for the acknowledgement of a message."
+		required:    false
+		type: uint: {
+			default: 190
+			unit:    "seconds"
+		}
+	}
+	acknowledgements: {
+		description: """
+			Controls how acknowledgements are handled for this sink.
+
+			See [End-to-end Acknowledgements][e2e_acks] for more information on how event acknowledgement is handled.
+
+			[e2e_acks]: https://vector.dev/docs/about/under-the-hood/architecture/end-to-end-acknowledgements/
+			"""
+		required: false
+		type: object: options: enabled: {
+			description: """
+				Whether or not end-to-end acknowledgements are enabled.
+
+				When enabled for a sink, any source connected to that sink, where the source supports
+				end-to-end acknowledgements as well, waits for events to be acknowledged by the sink
+				before acknowledging them at the source.
+
+				Enabling or disabling acknowledgements at the sink level takes precedence over any global
+				[`acknowledgements`][global_acks] configuration.
+
+				[global_acks]: https://vector.dev/docs/reference/configuration/global-options/#acknowledgements
+				"""
+			required: false
+			type: bool: {}
+		}
+	}
+	address: {
+		description: """
+			The address to connect to.
+
+			Both IP address and hostname are accepted formats.
+
+			The address _must_ include a port.
+			"""
+		required: true
+		type: string: examples: ["127.0.0.1:24224", "fluentd.example.com:24224"]
+	}
+	auth: {
+		description: """
+			Shared key authentication configuration.
+
+			The server must be configured with the same shared key, in the `<security>` section of
+			Fluentd, or with the `Shared_Key` option of Fluent Bit.
+			"""
+		required: false
+		type: object: options: {
+			password: {
+				description: "The password, for servers that also require user authentication."
+				required:    false
+				type: string: examples: ["${FLUENT_PASSWORD}"]
+			}
+			self_hostname: {
+				description: """
+					The hostname sent to the server during the handshake.
+
+					By default, the hostname of the machine running Vector is used.
+					"""
+				required: false
+				type: string: examples: ["vector.example.com"]
+			}
+			shared_key: {
+				description: "The shared key used to authenticate with the server."
+				required:    true
+				type: string: examples: ["${FLUENT_SHARED_KEY}"]
+			}
+			username: {
+				description: "The username, for servers that also require user authentication."
+				required:    false
+				type: string: examples: ["vector"]
+			}
+		}
+	}
+	batch: {
+		description: "Event batching behavior."
+		required:    false
+		type: object: options: {
+			max_bytes: {
+				description: """
+					The maximum size of a batch that is processed by a sink.
+
+					This is based on the uncompressed size of the batched events, before they are
+					serialized/compressed.
+					"""
+				required: false
+				type: uint: unit: "bytes"
+			}
+			max_events: {
+				description: "The maximum size of a batch before it is flushed."
+				required:    false
+				type: uint: {
+					default: 1000
+					unit:    "events"
+				}
+			}
+			timeout_secs: {
+				description: "The maximum age of a batch before it is flushed."
+				required:    false
+				type: float: {
+					default: 1.0
+					unit:    "seconds"
+				}
+			}
+		}
+	}
+	encoding: {
+		description: "Transformations to prepare an event for serialization."
+		required:    false
+		type: object: options: {
+			except_fields: {
+				description: "List of fields that are excluded from the encoded event."
+				required:    false
+				type: array: items: type: string: {}
+			}
+			only_fields: {
+				description: "List of fields that are included in the encoded event."
+				required:    false
+				type: array: items: type: string: {}
+			}
+			timestamp_format: {
+				description: "Format used for timestamp fields."
+				required:    false
+				type: string: enum: {
+					rfc3339: "Represent the timestamp as a RFC 3339 timestamp."
+					unix:    "Represent the timestamp as a Unix timestamp."
+				}
+			}
+		}
+	}
+	keepalive: {
+		description: "TCP keepalive settings for socket-based components."
+		required:    false
+		type: object: options: time_secs: {
+			description: "The time to wait before starting to send TCP keepalive probes on an idle connection."
+			required:    false
+			type: uint: unit: "seconds"
+		}
+	}
+	mode: {
+		description: """
+			The [event mode][event_modes] of the messages.
+
+			[event_modes]: https://github.com/fluent/fluentd/wiki/Forward-Protocol-Specification-v1#event-modes
+			"""
+		required: false
+		type: string: {
+			default: "packed_forward"
+			enum: {
+				compressed_packed_forward: "Same as `packed_forward`, with the stream of entries compressed with gzip."
+				forward:                   "Each message holds its events as an array of entries."
+				packed_forward: """
+					Each message holds its events as a binary stream of entries, which is cheaper for the
+					server to decode.
+					"""
+			}
+		}
+	}
+	require_ack: {
+		description: """
+			Whether to require the server to acknowledge each message.
+
+			When enabled, each message is sent with a `chunk` option, and its events are only marked
+			as delivered once the server has acknowledged it. A message that is not acknowledged in
+			time is sent again on a new connection, up to five times in total, before its events are
+			marked as failed.
+
+			This should be used along with end-to-end acknowledgements, for at-least-once delivery.
+			"""
+		required: false
+		type: bool: default: false
+	}
+	tag: {
+		description: """
+			The tag of the messages.
+
+			Events are grouped in messages by tag.
+			"""
+		required: true
+		type: string: {
+			examples: ["vector", "{{ tag }}"]
+			syntax: "template"
+		}
+	}
+	tls: {
+		description: "Configures the TLS options for incoming/outgoing connections."
+		required:    false
+		type: object: options: {
+			alpn_protocols: {
+				description: """
+					Sets the list of supported ALPN protocols.
+
+					Declare the supported ALPN protocols, which are used during negotiation with peer. They are prioritized in the order
+					that they are defined.
+					"""
+				required: false
+				type: array: items: type: string: examples: ["h2"]
+			}
+			ca_file: {
+				description: """
+					Absolute path to an additional CA certificate file.
+
+					The certificate must be in the DER or PEM (X.509) format. Additionally, the certificate can be provided as an inline string in PEM format.
+					"""
+				required: false
+				type: string: examples: ["/path/to/certificate_authority.crt"]
+			}
+			crt_file: {
+				description: """
+					Absolute path to a certificate file used to identify this server.
+
+					The certificate must be in DER, PEM (X.509), or PKCS#12 format. Additionally, the certificate can be provided as
+					an inline string in PEM format.
+
+					If this is set, and is not a PKCS#12 archive, `key_file` must also be set.
+					"""
+				required: false
+				type: string: examples: ["/path/to/host_certificate.crt"]
+			}
+			enabled: {
+				description: """
+					Whether or not to require TLS for incoming or outgoing connections.
+
+					When enabled and used for incoming connections, an identity certificate is also required. See `tls.crt_file` for
+					more information.
+					"""
+				required: false
+				type: bool: {}
+			}
+			key_file: {
+				description: """
+					Absolute path to a private key file used to identify this server.
+
+					The key must be in DER or PEM (PKCS#8) format. Additionally, the key can be provided as an inline string in PEM format.
+					"""
+				required: false
+				type: string: examples: ["/path/to/host_certificate.key"]
+			}
+			key_pass: {
+				description: """
+					Passphrase used to unlock the encrypted key file.
+
+					This has no effect unless `key_file` is set.
+					"""
+				required: false
+				type: string: examples: ["${KEY_PASS_ENV_VAR}", "PassWord1"]
+			}
+			verify_certificate: {
+				description: """
+					Enables certificate verification.
+
+					If enabled, certificates must not be expired and must be issued by a trusted
+					issuer. This verification operates in a hierarchical manner, checking that the leaf certificate (the
+					certificate presented by the client/server) is not only valid, but that the issuer of that certificate is also valid, and
+					so on until the verification process reaches a root certificate.
+
+					Relevant for both incoming and outgoing connections.
+
+					Do NOT set this to `false` unless you understand the risks of not verifying the validity of certificates.
+					"""
+				required: false
+				type: bool: {}
+			}
+			verify_hostname: {
+				description: """
+					Enables hostname verification.
+
+					If enabled, the hostname used to connect to the remote host must be present in the TLS certificate presented by
+					the remote host, either as the Common Name or as an entry in the Subject Alternative Name extension.
+
+					Only relevant for outgoing connections.
+
+					Do NOT set this to `false` unless you understand the risks of not verifying the remote hostname.
+					"""
+				required: false
+				type: bool: {}
+			}
+		}
+	}
+}
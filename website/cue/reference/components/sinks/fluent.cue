@@ -0,0 +1,117 @@
+package metadata
+
+components: sinks: fluent: {
+	title: "Fluent"
+
+	classes: {
+		commonly_used: false
+		delivery:      "at_least_once"
+		development:   "beta"
+		egress_method: "batch"
+		service_providers: []
+		stateful: false
+	}
+
+	features: {
+		auto_generated:   true
+		acknowledgements: true
+		healthcheck: enabled: true
+		send: {
+			batch: {
+				enabled:      true
+				common:       false
+				max_events:   1000
+				max_bytes:    null
+				timeout_secs: 1.0
+			}
+			compression: enabled: false
+			encoding: {
+				enabled: true
+				codec: enabled: false
+			}
+			keepalive: enabled: true
+			request: enabled:   false
+			tls: {
+				enabled:                true
+				can_verify_certificate: true
+				can_verify_hostname:    true
+				enabled_default:        false
+				enabled_by_scheme:      false
+			}
+			to: {
+				service: services.fluent
+
+				interface: {
+					socket: {
+						api: {
+							title: "Fluent Forward Protocol"
+							url:   urls.fluent
+						}
+						direction: "outgoing"
+						protocols: ["tcp"]
+						ssl: "optional"
+					}
+				}
+			}
+		}
+	}
+
+	support: {
+		requirements: []
+		warnings: []
+		notices: []
+	}
+
+	configuration: base.components.sinks.fluent.configuration
+
+	input: {
+		logs:    true
+		metrics: null
+		traces:  false
+	}
+
+	how_it_works: {
+		event_modes: {
+			title: "Event modes"
+			body: """
+				Events are grouped in messages by tag, and sent in the [event mode](\(urls.fluent)) set by the
+				`mode` option. The `packed_forward` and `compressed_packed_forward` modes are the cheapest for
+				the server to decode, while the `forward` mode is the most widely supported.
+
+				The timestamp of each entry is the timestamp of its event, with nanosecond precision. Events
+				without a timestamp are timestamped with the time they were sent at.
+				"""
+		}
+
+		acknowledgements: {
+			title: "Acknowledgements"
+			body: """
+				When `require_ack` is enabled, each message is sent with a `chunk` option, and the server must
+				acknowledge it before its events are marked as delivered. Combined with end-to-end
+				acknowledgements, this gives at-least-once delivery: messages that are not acknowledged in
+				time, or whose connection breaks, are sent again on a new connection with a backoff, and
+				only after five attempts are their events reported as failed to the sources that support it.
+				"""
+		}
+
+		shared_key_authentication: {
+			title: "Shared key authentication"
+			body: """
+				When `auth` is set, Vector performs the shared key handshake of the Forward protocol on each new
+				connection, and checks that the server knows the shared key too. For Fluentd, the server is
+				configured with a `<security>` section:
+
+				```text
+				<source>
+				  @type forward
+				  port 24224
+				  <security>
+				    self_hostname fluentd
+				    shared_key secret
+				  </security>
+				</source>
+				```
+				"""
+		}
+	}
+}
@@ -34,7 +34,7 @@ components: sinks: kafka: {
 				enabled: true
 				codec: {
 					enabled: true
-					enum: ["json", "text"]
+					enum: ["json", "text", "protobuf"]
 				}
 			}
 			request: enabled: false
@@ -66,7 +66,38 @@ components: sinks: kafka: {
 		traces: false
 	}
 
-	how_it_works: components._kafka.how_it_works
+	how_it_works: components._kafka.how_it_works & {
+		transactions: {
+			title: "Exactly-once delivery"
+			body: """
+				When `transaction.transactional_id` is set, the sink batches events and produces each
+				batch in its own Kafka transaction. Events are only acknowledged once their transaction
+				is committed, and the messages of a failed transaction are aborted, so consumers reading
+				with `isolation.level` set to `read_committed` see each batch exactly once.
+
+				For consume-transform-produce pipelines reading from the `kafka` source, set
+				`transaction.source_offsets.group_id` to the `group_id` of the source, and set
+				`librdkafka_options."enable.auto.commit"` to `"false"` on the source. The offsets of the
+				consumed messages are then committed in the same transaction as the messages produced
+				from them. This requires all the events of the source to be delivered by the sink.
+
+				The sink isn't a member of the consumer group of the source, so the broker can't fence
+				off a source that lost its partitions in a rebalance, and the events it consumed before
+				the rebalance may still be delivered after another member resumed from the committed
+				offsets. Delivery is then at-least-once across rebalances.
+				"""
+		}
+		schema_registry: {
+			title: "Schema registry"
+			body: """
+				When `schema_registry` is set, messages are produced in the Confluent wire format, prefixed
+				with a zero magic byte and the ID of their schema in the registry, so that registry-aware
+				consumers can decode them. With the `avro` codec, the schema is registered under the
+				subject. With the `protobuf` codec, the latest schema of the subject is used, and the
+				header also holds the indexes of the message type in its file.
+				"""
+		}
+	}
 
 	telemetry: metrics: {
 		kafka_queue_messages:                components.sources.internal_metrics.output.metrics.kafka_queue_messages
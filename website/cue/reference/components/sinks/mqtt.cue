@@ -0,0 +1,66 @@
+package metadata
+
+components: sinks: mqtt: {
+	title: "MQTT"
+
+	classes: {
+		commonly_used: false
+		delivery:      "best_effort"
+		development:   "beta"
+		egress_method: "stream"
+		service_providers: []
+		stateful: false
+	}
+
+	features: {
+		auto_generated:   true
+		acknowledgements: true
+		healthcheck: enabled: true
+		send: {
+			compression: enabled: false
+			encoding: {
+				enabled: true
+				codec: {
+					enabled: true
+					enum: ["json", "text"]
+				}
+			}
+			request: enabled: false
+			tls: {
+				enabled:                true
+				can_verify_certificate: true
+				can_verify_hostname:    true
+				enabled_default:        false
+				enabled_by_scheme:      false
+			}
+			to: components._mqtt.features.send.to
+		}
+	}
+
+	support: components._mqtt.support
+
+	configuration: base.components.sinks.mqtt.configuration
+
+	input: {
+		logs:    true
+		metrics: null
+		traces:  false
+	}
+
+	how_it_works: components._mqtt.how_it_works & {
+		acknowledgements: {
+			title: "Acknowledgements"
+			body: """
+				When end-to-end acknowledgements are enabled, events published with a QoS of
+				`at_least_once` are acknowledged once the broker replies with `PUBACK`, and those
+				published with a QoS of `exactly_once` once it replies with `PUBCOMP`. Events published
+				with a QoS of `at_most_once` are acknowledged as soon as they are sent, as the broker
+				doesn't reply to them.
+				"""
+		}
+	}
+
+	telemetry: metrics: {
+		send_errors_total: components.sources.internal_metrics.output.metrics.send_errors_total
+	}
+}
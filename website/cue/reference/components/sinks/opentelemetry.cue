@@ -0,0 +1,109 @@
+package metadata
+
+components: sinks: opentelemetry: {
+	title: "OpenTelemetry"
+
+	description: """
+		Sends logs, metrics and traces to any service implementing the OpenTelemetry Protocol
+		(OTLP), such as the OpenTelemetry Collector, over gRPC or HTTP.
+		"""
+
+	classes: {
+		commonly_used: false
+		delivery:      "at_least_once"
+		development:   "beta"
+		egress_method: "batch"
+		service_providers: []
+		stateful: false
+	}
+
+	features: {
+		acknowledgements: true
+		auto_generated:   true
+		healthcheck: enabled: false
+		send: {
+			batch: {
+				enabled:      true
+				common:       false
+				max_bytes:    10_000_000
+				timeout_secs: 1.0
+			}
+			compression: {
+				enabled: true
+				default: "none"
+				algorithms: ["none", "gzip"]
+				levels: ["none", "fast", "default", "best", 0, 1, 2, 3, 4, 5, 6, 7, 8, 9]
+			}
+			encoding: enabled: false
+			request: {
+				enabled: true
+				headers: false
+			}
+			tls: {
+				enabled:                true
+				can_verify_certificate: true
+				can_verify_hostname:    true
+				enabled_default:        false
+				enabled_by_scheme:      true
+			}
+			to: {
+				service: services.opentelemetry
+
+				interface: {
+					socket: {
+						direction: "outgoing"
+						protocols: ["http"]
+						ssl: "optional"
+					}
+				}
+			}
+		}
+	}
+
+	support: {
+		requirements: []
+		warnings: []
+		notices: []
+	}
+
+	input: {
+		logs: true
+		metrics: {
+			counter:      true
+			distribution: true
+			gauge:        true
+			histogram:    true
+			summary:      true
+			set:          true
+		}
+		traces: true
+	}
+
+	configuration: base.components.sinks.opentelemetry.configuration
+
+	how_it_works: {
+		batching: {
+			title: "Batching per signal"
+			body: """
+				Logs, metrics and traces are batched separately, and each batch is exported as a
+				single `ExportLogsServiceRequest`, `ExportMetricsServiceRequest` or
+				`ExportTraceServiceRequest`. Events sharing the same resource attributes are grouped
+				under a single resource entry.
+				"""
+		}
+
+		metric_conversion: {
+			title: "Metric conversion"
+			body: """
+				Counters are exported as monotonic sums and incremental gauges as non-monotonic
+				sums, using delta or cumulative temporality depending on the metric kind. Absolute
+				gauges and sets are exported as gauges. Aggregated histograms and summaries map onto
+				their OTLP counterparts, distributions are exported as histograms with one bucket per
+				distinct sample value, and sketches are exported as summaries. Tags prefixed with
+				`resource.` or `scope.` are restored as resource and instrumentation scope
+				attributes, matching the convention used by the `opentelemetry` source. The namespace
+				of a metric, if any, is prefixed to its name, separated by a `.`.
+				"""
+		}
+	}
+}
@@ -0,0 +1,120 @@
+package metadata
+
+components: sinks: postgres: {
+	title: "PostgreSQL"
+
+	classes: {
+		commonly_used: false
+		delivery:      "at_least_once"
+		development:   "beta"
+		egress_method: "batch"
+		service_providers: []
+		stateful: false
+	}
+
+	features: {
+		auto_generated:   true
+		acknowledgements: true
+		healthcheck: enabled: true
+		send: {
+			batch: {
+				enabled:      true
+				common:       false
+				max_bytes:    10_000_000
+				timeout_secs: 1.0
+			}
+			compression: enabled: false
+			encoding: {
+				enabled: true
+				codec: enabled: false
+			}
+			request: {
+				enabled: true
+				headers: false
+			}
+			tls: {
+				enabled:                true
+				can_verify_certificate: true
+				can_verify_hostname:    true
+				enabled_default:        false
+				enabled_by_scheme:      false
+			}
+			to: {
+				service: {
+					name:     "PostgreSQL Server"
+					thing:    "an \(name)"
+					url:      urls.postgresql
+					versions: null
+				}
+
+				interface: {
+					socket: {
+						direction: "outgoing"
+						protocols: ["tcp", "unix"]
+						ssl: "optional"
+					}
+				}
+			}
+		}
+	}
+
+	support: {
+		requirements: []
+		warnings: []
+		notices: []
+	}
+
+	configuration: base.components.sinks.postgres.configuration
+
+	input: {
+		logs:    true
+		metrics: null
+		traces:  false
+	}
+
+	how_it_works: {
+		columns: {
+			title: "Mapping events to columns"
+			body: """
+				Each entry of `columns` fills a column of the table with an event field, and the
+				`fallback_column`, if set, is filled with a JSON object of all the other fields of the event.
+				For example, with the following table:
+
+				```sql
+				CREATE TABLE logs (timestamp timestamptz, message text, data jsonb);
+				```
+
+				Events can be inserted with:
+
+				```toml
+				table = "logs"
+				columns.timestamp = "timestamp"
+				columns.message = "message"
+				fallback_column = "data"
+				```
+
+				Field values are converted to the type of their column by PostgreSQL. Objects and arrays are
+				inserted as JSON.
+				"""
+		}
+
+		insert_modes: {
+			title: "Insert modes"
+			body: """
+				By default, batches are streamed to PostgreSQL with a [`COPY ... FROM STDIN`](\(urls.postgresql_copy))
+				statement, which is the fastest way to load data. The `insert` mode sends a multi-row `INSERT`
+				statement instead, which also works on views and fires the rules of the table.
+				"""
+		}
+
+		acknowledgements: {
+			title: "Acknowledgements"
+			body: """
+				Each batch is inserted in its own transaction, and its events are only acknowledged once that
+				transaction is committed. Batches that fail because of a connection error, a serialization
+				failure or a deadlock are retried, while batches rejected by the table, such as those with
+				values of the wrong type, are not.
+				"""
+		}
+	}
+}
@@ -23,7 +23,7 @@ components: sinks: socket: {
 				codec: {
 					enabled: true
 					framing: true
-					enum: ["json", "text", "gelf"]
+					enum: ["json", "text", "gelf", "syslog", "cef", "leef"]
 				}
 			}
 			send_buffer_bytes: {
@@ -196,6 +196,42 @@ components: sources: [Name=string]: {
 											[rfc3164]: https://www.ietf.org/rfc/rfc3164.txt
 											[rfc5424]: https://www.ietf.org/rfc/rfc5424.txt
 											"""
+										avro: """
+											Decodes the raw bytes as an [Apache Avro][apache_avro] datum.
+
+											[apache_avro]: https://avro.apache.org/
+											"""
+										csv: """
+											Decodes the raw bytes as [CSV][csv] rows.
+
+											Each row is decoded into a separate event.
+
+											[csv]: https://datatracker.ietf.org/doc/html/rfc4180
+											"""
+										logfmt: """
+											Decodes the raw bytes as a [logfmt][logfmt] message.
+
+											Values are decoded as strings, and keys without a value are decoded as `true`.
+
+											[logfmt]: https://brandur.org/logfmt
+											"""
+										cef: """
+											Decodes the raw bytes as an [ArcSight CEF][cef] message.
+
+											Header fields and extensions are decoded as strings. Anything before the `CEF:` prefix, such
+											as a syslog header, is ignored.
+
+											[cef]: https://www.microfocus.com/documentation/arcsight/arcsight-smartconnectors/pdfdoc/common-event-format-v25/common-event-format-v25.pdf
+											"""
+										leef: """
+											Decodes the raw bytes as an [IBM QRadar LEEF][leef] message.
+
+											Header fields and attributes are decoded as strings. The attribute delimiter of LEEF 2.0
+											messages is read from their header. Anything before the `LEEF:` prefix, such as a syslog
+											header, is ignored.
+
+											[leef]: https://www.ibm.com/docs/en/dsm?topic=leef-overview
+											"""
 										native: """
 											Decodes the raw bytes as Vector’s [native Protocol Buffers format][vector_native_protobuf].
 
@@ -35,13 +35,56 @@ base: components: sources: datadog_agent: configuration: {
 		description: "Configures how events are decoded from raw bytes."
 		required:    false
 		type: object: options: {
+			avro: {
+				description:   "Apache Avro-specific decoder options."
+				relevant_when: "codec = \"avro\""
+				required:      true
+				type: object: options: {
+					schema: {
+						description: "The Avro schema."
+						required:    true
+						type: string: examples: ["{ \"type\": \"record\", \"name\": \"log\", \"fields\": [{ \"name\": \"message\", \"type\": \"string\" }] }"]
+					}
+					strip_schema_id_prefix: {
+						description: """
+							Whether to strip the schema ID header from each message before decoding it.
+
+							Schema registry clients, such as the Confluent serializers, prepend a five byte header to
+							each message: a zero magic byte followed by the ID of the schema as a 32-bit big-endian
+							integer. When enabled, messages without this header are rejected.
+							"""
+						required: false
+						type: bool: default: false
+					}
+				}
+			}
 			codec: {
 				description: "The codec to use for decoding events."
 				required:    false
 				type: string: {
 					default: "bytes"
 					enum: {
+						avro: """
+															Decodes the raw bytes as an [Apache Avro][apache_avro] datum.
+
+															[apache_avro]: https://avro.apache.org/
+															"""
 						bytes: "Uses the raw bytes as-is."
+						cef: """
+															Decodes the raw bytes as an [ArcSight CEF][cef] message.
+
+															Header fields and extensions are decoded as strings. Anything before the `CEF:` prefix, such
+															as a syslog header, is ignored.
+
+															[cef]: https://www.microfocus.com/documentation/arcsight/arcsight-smartconnectors/pdfdoc/common-event-format-v25/common-event-format-v25.pdf
+															"""
+						csv: """
+															Decodes the raw bytes as [CSV][csv] rows.
+
+															Each row is decoded into a separate event.
+
+															[csv]: https://datatracker.ietf.org/doc/html/rfc4180
+															"""
 						gelf: """
 															Decodes the raw bytes as a [GELF][gelf] message.
 
@@ -52,6 +95,22 @@ base: components: sources: datadog_agent: configuration: {
 
 															[json]: https://www.json.org/
 															"""
+						leef: """
+															Decodes the raw bytes as an [IBM QRadar LEEF][leef] message.
+
+															Header fields and attributes are decoded as strings. The attribute delimiter of LEEF 2.0
+															messages is read from their header. Anything before the `LEEF:` prefix, such as a syslog
+															header, is ignored.
+
+															[leef]: https://www.ibm.com/docs/en/dsm?topic=leef-overview
+															"""
+						logfmt: """
+															Decodes the raw bytes as a [logfmt][logfmt] message.
+
+															Values are decoded as strings, and keys without a value are decoded as `true`.
+
+															[logfmt]: https://brandur.org/logfmt
+															"""
 						native: """
 															Decodes the raw bytes as Vector’s [native Protocol Buffers format][vector_native_protobuf].
 
@@ -85,6 +144,63 @@ base: components: sources: datadog_agent: configuration: {
 					}
 				}
 			}
+			csv: {
+				description:   "CSV-specific decoding options."
+				relevant_when: "codec = \"csv\""
+				required:      true
+				type: object: options: {
+					columns: {
+						description: """
+							The names of the columns, in the order in which they appear in each row.
+
+							Each value of a row is inserted into the field named after its column. Rows with more
+							values than there are columns are rejected, while missing values at the end of a row are
+							left unset.
+							"""
+						required: true
+						type: array: items: type: string: examples: ["timestamp", "message"]
+					}
+					delimiter: {
+						description: "The ASCII (7-bit) character that separates the values of a row."
+						required:    false
+						type: uint: default: 44
+					}
+					skip_header: {
+						description: """
+							Whether to skip header rows.
+
+							When enabled, rows whose values are identical to the configured column names are dropped.
+							"""
+						required: false
+						type: bool: default: false
+					}
+					types: {
+						description: """
+							The types to coerce the values of columns to.
+
+							The available types are `bool`, `float`, `int`, `string`, `timestamp` (parsed from a number
+							of common formats), and `timestamp|<format>` (parsed with the given [`strptime`][strptime]
+							format). Empty values of typed columns are decoded as `null`. Values of columns without a
+							type are kept as strings.
+
+							[strptime]: https://docs.rs/chrono/latest/chrono/format/strftime/index.html#specifiers
+							"""
+						required: false
+						type: object: {
+							examples: [{
+								duration:  "float"
+								status:    "int"
+								timestamp: "timestamp|%F %T"
+							}]
+							options: "*": {
+								description: "The type of a column."
+								required:    true
+								type: string: {}
+							}
+						}
+					}
+				}
+			}
 			gelf: {
 				description:   "GELF-specific decoding options."
 				relevant_when: "codec = \"gelf\""
@@ -222,6 +338,77 @@ base: components: sources: datadog_agent: configuration: {
 					}
 				}
 			}
+			chunked_gelf: {
+				description:   "Options for the chunked GELF decoder."
+				relevant_when: "method = \"chunked_gelf\""
+				required:      false
+				type: object: options: {
+					decompression: {
+						description: "The decompression applied to messages once reassembled."
+						required:    false
+						type: string: {
+							default: "auto"
+							enum: {
+								auto: """
+																Detects the compression of each message from its leading bytes, and decompresses
+																[Gzip][gzip] and [Zlib][zlib] messages.
+
+																[gzip]: https://www.gzip.org/
+																[zlib]: https://www.zlib.net/
+																"""
+								gzip: """
+																Decompresses messages with [Gzip][gzip].
+
+																[gzip]: https://www.gzip.org/
+																"""
+								none: "Passes messages through as-is."
+								zlib: """
+																Decompresses messages with [Zlib][zlib].
+
+																[zlib]: https://www.zlib.net/
+																"""
+							}
+						}
+					}
+					max_length: {
+						description: """
+																The maximum length of a message, in bytes.
+
+																Messages whose chunks add up to more than this length, or that decompress to more than this
+																length, are discarded.
+																"""
+						required: false
+						type: uint: {
+							default: 8388608
+							unit:    "bytes"
+						}
+					}
+					pending_messages_limit: {
+						description: """
+																The maximum number of incomplete messages to hold at once.
+
+																Once reached, the chunks of new messages are discarded until some of the pending messages
+																complete or time out. Along with `max_length`, this bounds the memory used to reassemble
+																messages.
+																"""
+						required: false
+						type: uint: default: 1000
+					}
+					timeout_secs: {
+						description: """
+																The time to wait for all the chunks of a message to be received.
+
+																The chunks of messages that are still incomplete after this amount of time, counted from
+																their first chunk, are discarded.
+																"""
+						required: false
+						type: uint: {
+							default: 5
+							unit:    "seconds"
+						}
+					}
+				}
+			}
 			method: {
 				description: "The framing method."
 				required:    false
@@ -230,8 +417,18 @@ base: components: sources: datadog_agent: configuration: {
 					enum: {
 						bytes:               "Byte frames are passed through as-is according to the underlying I/O boundaries (for example, split between messages or stream segments)."
 						character_delimited: "Byte frames which are delimited by a chosen character."
-						length_delimited:    "Byte frames which are prefixed by an unsigned big-endian 32-bit integer indicating the length."
-						newline_delimited:   "Byte frames which are delimited by a newline character."
+						chunked_gelf: """
+															Byte frames which are [chunked GELF][chunked_gelf] messages, one chunk per message.
+
+															The chunks of each message are reassembled into a single frame, which is then decompressed.
+															Messages that are not chunked are passed through as-is. This is only supported by
+															message-based sources, such as the `socket` source in UDP mode, along with the `gelf` codec.
+															Stream-based sources, such as the `socket` source in TCP mode or the `stdin` source, reject it.
+
+															[chunked_gelf]: https://go2docs.graylog.org/current/getting_in_log_data/gelf.html#GELFviaUDP
+															"""
+						length_delimited:  "Byte frames which are prefixed by an unsigned big-endian 32-bit integer indicating the length."
+						newline_delimited: "Byte frames which are delimited by a newline character."
 						octet_counting: """
 															Byte frames according to the [octet counting][octet_counting] format.
 
@@ -392,6 +392,18 @@ components: sources: internal_metrics: {
 			default_namespace: "vector"
 			tags:              _component_tags
 		}
+		buffer_overflowed_bytes_total: {
+			description:       "The number of bytes overflowed by this buffer to the next buffer stage."
+			type:              "counter"
+			default_namespace: "vector"
+			tags:              _component_tags
+		}
+		buffer_overflowed_events_total: {
+			description:       "The number of events overflowed by this buffer to the next buffer stage."
+			type:              "counter"
+			default_namespace: "vector"
+			tags:              _component_tags
+		}
 		buffer_received_event_bytes_total: {
 			description:       "The number of bytes received by this buffer."
 			type:              "counter"
@@ -101,5 +101,20 @@ components: sources: kafka: {
 		kafka_consumer_lag:                   components.sources.internal_metrics.output.metrics.kafka_consumer_lag
 	}
 
-	how_it_works: components._kafka.how_it_works
+	how_it_works: components._kafka.how_it_works & {
+		schema_registry: {
+			title: "Schema registry"
+			body: """
+				When `schema_registry` is set, messages must be in the Confluent wire format, prefixed
+				with a zero magic byte and the ID of their schema in the registry. With the `avro` codec,
+				the schema of each ID is fetched from the registry once, and used to decode the data
+				before resolving it to `decoding.avro.schema`. With the `protobuf` codec, the message
+				indexes following the ID are skipped, and the data is decoded as
+				`decoding.protobuf.message_type`. Messages without the header are dropped. Failures to
+				reach the registry, server errors, timeouts and throttling are retried, pausing the
+				consumption, while messages whose schema the registry refuses to serve, for example
+				because of invalid credentials, are dropped.
+				"""
+		}
+	}
 }
@@ -39,11 +39,7 @@ components: sources: opentelemetry: {
 
 	support: {
 		requirements: []
-		warnings: [
-			"""
-				The `opentelemetry` source only supports log events at this time.
-				""",
-		]
+		warnings: []
 		notices: []
 	}
 
@@ -60,6 +56,18 @@ components: sources: opentelemetry: {
 				Received log events will go to this output stream. Use `<component_id>.logs` as an input to downstream transforms and sinks.
 				"""
 		},
+		{
+			name: "metrics"
+			description: """
+				Received metric events will go to this output stream. Use `<component_id>.metrics` as an input to downstream transforms and sinks.
+				"""
+		},
+		{
+			name: "traces"
+			description: """
+				Received trace events will go to this output stream. Use `<component_id>.traces` as an input to downstream transforms and sinks.
+				"""
+		},
 	]
 
 	output: {
@@ -192,9 +200,58 @@ components: sources: opentelemetry: {
 				}
 			}
 		}
+		metrics: {
+			counter:   output._passthrough_counter
+			gauge:     output._passthrough_gauge
+			histogram: output._passthrough_histogram
+			summary:   output._passthrough_summary
+		}
+		traces: {
+			description: "A span received through an OTLP request."
+			fields: {
+				trace_id: {
+					description: "The hex-encoded trace ID of the span."
+					required:    true
+					type: string: examples: ["4ac52aadf321c2e531db005df08792f5"]
+				}
+				span_id: {
+					description: "The hex-encoded ID of the span."
+					required:    true
+					type: string: examples: ["0b9e4bda2a55530d"]
+				}
+				parent_span_id: {
+					description: "The hex-encoded ID of the parent span, empty for root spans."
+					required:    true
+					type: string: examples: ["b7ad6b7169203331"]
+				}
+				name: {
+					description: "A description of the span's operation."
+					required:    true
+					type: string: examples: ["GET /users"]
+				}
+				source_type: {
+					description: "The name of the source type."
+					required:    true
+					type: string: examples: ["opentelemetry"]
+				}
+			}
+		}
 	}
 
 	how_it_works: {
+		metric_conversion: {
+			title: "Metric conversion"
+			body: """
+				OTLP gauges are converted to gauges, monotonic sums to counters, and non-monotonic sums
+				to gauges. Histograms and exponential histograms are both converted to aggregated
+				histograms, and summaries to aggregated summaries. Data points with delta temporality
+				are emitted as incremental metrics, all others as absolute metrics.
+
+				Data point attributes become metric tags. Resource attributes are added as tags
+				prefixed with `resource.`, and the instrumentation scope name and version as
+				`scope.name` and `scope.version`.
+				"""
+		}
 		tls: {
 			title: "Transport Layer Security (TLS)"
 			body:  """
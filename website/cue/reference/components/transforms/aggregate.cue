@@ -173,6 +173,26 @@ components: transforms: aggregate: {
 				"""
 		}
 
+		aggregation_modes: {
+			title: "Aggregation Modes"
+			body: """
+				The `mode` option changes how counters and gauges of the same series are combined. `sum`,
+				`max`, `min`, and `mean` apply to both `incremental` and `absolute` metrics, while `latest`
+				always keeps the newest metric. Setting `rate` to `true` turns aggregated `incremental`
+				counters into `absolute` gauges holding the per-second rate over the window.
+				"""
+		}
+
+		sliding_windows: {
+			title: "Sliding Windows"
+			body: """
+				When `window_ms` is larger than `interval_ms`, each flush emits the aggregation of the
+				metrics received during the last `window_ms`, so a metric contributes to several consecutive
+				flushes. The `timestamp` option can set the timestamp of the emitted metrics to the start or
+				the end of their window.
+				"""
+		}
+
 		advantages: {
 			title: "Advantages of Use"
 			body: """
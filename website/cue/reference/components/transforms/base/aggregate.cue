@@ -1,11 +1,72 @@
 package metadata
 
-base: components: transforms: aggregate: configuration: interval_ms: {
-	description: """
-		The interval between flushes, in milliseconds.
-
-		During this time frame, metrics with the same series data (name, namespace, tags, and so on) are aggregated.
-		"""
-	required: false
-	type: uint: default: 10000
+base: components: transforms: aggregate: configuration: {
+	interval_ms: {
+		description: """
+			The interval between flushes, in milliseconds.
+
+			During this time frame, metrics with the same series data (name, namespace, tags, and so on) are aggregated.
+			"""
+		required: false
+		type: uint: default: 10000
+	}
+	mode: {
+		description: """
+			The function used to aggregate the metrics of a series within a window.
+
+			Except for `auto` and `latest`, the modes only change how counters and gauges are aggregated.
+			Other metric types are aggregated as with `auto`.
+			"""
+		required: false
+		type: string: {
+			default: "auto"
+			enum: {
+				auto: """
+					Incremental metrics are added together, and absolute metrics are replaced by the latest
+					value.
+					"""
+				latest: "The latest value is kept, whether the metrics are incremental or absolute."
+				max:    "The largest value is kept."
+				mean:   "Values are averaged."
+				min:    "The smallest value is kept."
+				sum:    "Values are added together, whether the metrics are incremental or absolute."
+			}
+		}
+	}
+	rate: {
+		description: """
+			Whether to convert incremental counters into per-second rates.
+
+			The aggregated value of each incremental counter is divided by the time it was aggregated
+			over, in seconds, and emitted as an absolute gauge. That's the window length, except until
+			a sliding window fills up.
+			"""
+		required: false
+		type: bool: default: false
+	}
+	timestamp: {
+		description: "The timestamp of the aggregated metrics."
+		required:    false
+		type: string: {
+			default: "unchanged"
+			enum: {
+				unchanged:    "Keep the timestamps of the aggregated metrics."
+				window_end:   "Set the timestamp to the end of the window, and the interval to the window length."
+				window_start: "Set the timestamp to the start of the window, and the interval to the window length."
+			}
+		}
+	}
+	window_ms: {
+		description: """
+			The length of the aggregation window, in milliseconds.
+
+			When larger than `interval_ms`, windows slide: every flush emits the aggregation of the
+			metrics received during the last `window_ms`. This must be a multiple of `interval_ms`.
+
+			By default, the window is the same as the flush interval, and every metric is only emitted
+			once.
+			"""
+		required: false
+		type: uint: examples: [60000]
+	}
 }
@@ -4,10 +4,106 @@ base: components: transforms: dedupe: configuration: {
 	cache: {
 		description: "Caching configuration for deduplication."
 		required:    false
-		type: object: options: num_events: {
-			description: "Number of events to cache and use for comparing incoming events to previously seen events."
-			required:    false
-			type: uint: default: 5000
+		type: object: options: {
+			backend: {
+				description: "Where the deduplication cache is stored."
+				required:    false
+				type: object: options: {
+					data_dir: {
+						description: """
+							The directory used to persist the cache.
+
+							By default, the [global `data_dir` option][global_data_dir] is used. Make sure the running user has write
+							permissions to this directory.
+
+							[global_data_dir]: https://vector.dev/docs/reference/configuration/global-options/#data_dir
+							"""
+						relevant_when: "type = \"disk\""
+						required:      false
+						type: string: examples: ["/var/local/lib/vector/"]
+					}
+					flush_interval_secs: {
+						description: """
+							How often the cache is persisted to disk, in seconds.
+
+							The cache is also persisted when the transform shuts down.
+							"""
+						relevant_when: "type = \"disk\""
+						required:      false
+						type: uint: {
+							default: 5
+							unit:    "seconds"
+						}
+					}
+					key_prefix: {
+						description: """
+							The prefix of the keys storing the cache entries.
+
+							Defaults to `vector:dedupe:<component id>:`.
+							"""
+						relevant_when: "type = \"redis\""
+						required:      false
+						type: string: examples: ["vector:dedupe:"]
+					}
+					type: {
+						description: "The storage backend of the cache."
+						required:    false
+						type: string: {
+							default: "memory"
+							enum: {
+								disk: """
+									Store the cache in memory, and periodically persist it to disk.
+
+									The persisted cache is loaded again when the transform starts, so events seen before a
+									restart or reload are still deduplicated.
+									"""
+								memory: """
+									Store the cache in memory.
+
+									The cache is lost whenever Vector restarts or the transform is reloaded.
+									"""
+								redis: """
+									Store the cache in a Redis-compatible server.
+
+									Every Vector instance using the same server and key prefix shares the same cache, so
+									duplicates are detected across replicas.
+									"""
+							}
+						}
+					}
+					url: {
+						description:   "The URL of the Redis server."
+						relevant_when: "type = \"redis\""
+						required:      true
+						type: string: examples: ["redis://127.0.0.1:6379/0"]
+					}
+				}
+			}
+			num_events: {
+				description: """
+					Number of events to cache and use for comparing incoming events to previously seen events.
+
+					This limit does not apply to the `redis` backend, where entries are only removed once they
+					expire.
+					"""
+				required: false
+				type: uint: default: 5000
+			}
+			ttl_secs: {
+				description: """
+					How long an event is remembered for after it was first seen, in seconds.
+
+					Once an entry expires, the next matching event is forwarded and cached again. By default,
+					entries are only removed from the cache when evicted to make room for newer ones.
+
+					This option is required when using the `redis` backend.
+					"""
+				required: false
+				type: uint: {
+					examples: [3600]
+					unit: "seconds"
+				}
+			}
 		}
 	}
 	fields: {
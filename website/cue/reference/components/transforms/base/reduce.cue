@@ -72,20 +72,39 @@ base: components: transforms: reduce: configuration: {
 			required:    true
 			type: string: enum: {
 				array:          "Append each value to an array."
+				avg:            "Keep the average of all numeric values."
 				concat:         "Concatenate each string value, delimited with a space."
 				concat_newline: "Concatenate each string value, delimited with a newline."
 				concat_raw:     "Concatenate each string, without a delimiter."
+				count:          "Count the values seen."
+				count_distinct: "Count the unique values seen."
 				discard:        "Discard all but the first value found."
 				flat_unique:    "Create a flattened array of all unique values."
 				longest_array:  "Keep the longest array seen."
 				max:            "Keep the maximum numeric value seen."
 				min:            "Keep the minimum numeric value seen."
+				p50: """
+					Estimate the median of all numeric values.
+
+					The estimate is computed with a sketch, so it is approximate but has a bounded size.
+					"""
+				p90: """
+					Estimate the 90th percentile of all numeric values.
+
+					The estimate is computed with a sketch, so it is approximate but has a bounded size.
+					"""
+				p99: """
+					Estimate the 99th percentile of all numeric values.
+
+					The estimate is computed with a sketch, so it is approximate but has a bounded size.
+					"""
 				retain: """
 					Discard all but the last value found.
 
 					Works as a way to coalesce by not retaining `null`.
 					"""
 				shortest_array: "Keep the shortest array seen."
+				stddev:         "Keep the (population) standard deviation of all numeric values."
 				sum:            "Sum all numeric values."
 			}
 		}
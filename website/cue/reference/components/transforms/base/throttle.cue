@@ -1,6 +1,17 @@
 package metadata
 
 base: components: transforms: throttle: configuration: {
+	burst: {
+		description: """
+			The number of events a bucket can let through at once.
+
+			Buckets refill at the steady-state rate of `threshold` events per `window_secs`, and hold
+			up to `burst` events. This applies to the keys using the default `threshold`. If left
+			unspecified, the burst is the same as `threshold`.
+			"""
+		required: false
+		type: uint: examples: [100]
+	}
 	exclude: {
 		description: "A logical condition used to exclude events from sampling."
 		required:    false
@@ -19,15 +30,72 @@ base: components: transforms: throttle: configuration: {
 			syntax: "template"
 		}
 	}
+	reroute_dropped: {
+		description: """
+			Whether to send events exceeding the rate limit to the `dropped` output instead of
+			discarding them.
+
+			This can be used to route the overflow to cheaper storage.
+			"""
+		required: false
+		type: bool: default: false
+	}
 	threshold: {
 		description: """
 			The number of events allowed for a given bucket per configured `window_secs`.
 
-			Each unique key has its own `threshold`.
+			Each unique key has its own `threshold`. This is the default for keys whose threshold is
+			not set by `threshold_field` or `threshold_table`.
 			"""
 		required: true
 		type: uint: {}
 	}
+	threshold_field: {
+		description: """
+			The threshold of the bucket of an event.
+
+			The template must render to a positive integer, and the burst of the bucket is the same as
+			its threshold. If the event doesn't have the fields used by the template, or if the template
+			doesn't render to a positive integer, the threshold is looked up in `threshold_table`, or
+			`threshold` is used. Only the latter is reported as an error.
+			"""
+		required: false
+		type: string: {
+			examples: ["{{ tenant_threshold }}"]
+			syntax: "template"
+		}
+	}
+	threshold_table: {
+		description: "Per-key thresholds loaded from an enrichment table."
+		required:    false
+		type: object: options: {
+			burst_column: {
+				description: """
+					The column holding the burst of the key.
+
+					If left unspecified, or if the row doesn't have a burst, the burst is the same as the
+					threshold of the key.
+					"""
+				required: false
+				type: string: examples: ["burst"]
+			}
+			key_column: {
+				description: "The column matched against the rendered `key_field`."
+				required:    true
+				type: string: examples: ["tenant"]
+			}
+			table: {
+				description: "The name of the enrichment table."
+				required:    true
+				type: string: examples: ["tenant_tiers"]
+			}
+			threshold_column: {
+				description: "The column holding the threshold of the key."
+				required:    true
+				type: string: examples: ["threshold"]
+			}
+		}
+	}
 	window_secs: {
 		description: "The time window in which the configured `threshold` is applied, in seconds."
 		required:    true
@@ -87,6 +87,10 @@ components: transforms: throttle: {
 					body: """
 						The `throttle` transform buckets events into rate limiters based on the provided `key_field`, or a
 						single bucket if not provided. Each bucket is rate limited separately.
+
+						Buckets are kept in memory, and aren't shared between Vector instances. When several instances
+						throttle the same stream, each of them lets up to `threshold` events per `window_secs` through.
+						Distributed rate limiting, with buckets shared between instances, isn't supported.
 						"""
 				},
 				{
@@ -96,9 +100,20 @@ components: transforms: throttle: {
 						pass through a rate limiter. Each event passing through the transform consumes an available cell,
 						if there is no available cell the event will be rate limited.
 
-						A rate limiter is created with a maximum number of cells equal to the `threshold`, and cells replenish
-						at a rate of `window_secs` divided by `threshold`. For example, a `window_secs` of 60 with a `threshold` of 10
-						replenishes a cell every 6 seconds and allows a burst of up to 10 events.
+						A rate limiter is created with a maximum number of cells equal to the `burst`, which defaults to the
+						`threshold`, and cells replenish at a rate of `window_secs` divided by `threshold`. For example, a
+						`window_secs` of 60 with a `threshold` of 10 replenishes a cell every 6 seconds and allows a burst of up
+						to 10 events, or up to `burst` events if set.
+						"""
+				},
+				{
+					title: "Per-key Thresholds"
+					body: """
+						The threshold of each bucket can be set per key, for example to give tenants different rate limits
+						based on their tier. `threshold_field` renders the threshold from the event, and `threshold_table`
+						looks it up, along with an optional burst, in an enrichment table row matching the rendered
+						`key_field`. Keys missing from the table, or without a valid threshold, use the default
+						`threshold` and `burst`.
 						"""
 				},
 				{
@@ -107,10 +122,25 @@ components: transforms: throttle: {
 						The rate limiter will allow up to `threshold` number of events through and drop any further events
 						for that particular bucket when the rate limiter is at capacity. Any event passed when the rate
 						limiter is at capacity will be discarded and tracked by an `events_discarded_total` metric tagged
-						by the bucket's `key`.
+						by the bucket's `key`. When `reroute_dropped` is set to `true`, these events are sent to the `dropped`
+						output instead.
 						"""
 				},
 			]
 		}
 	}
+
+	outputs: [
+		components._default_output,
+		{
+			name: "dropped"
+			description: """
+				This transform also implements an additional `dropped` output. When `reroute_dropped`
+				is set to `true`, events exceeding the rate limit of their bucket are sent to the
+				`dropped` output instead of being discarded. For a transform component named `foo`,
+				this output can be accessed by specifying `foo.dropped` as the input to another
+				component.
+				"""
+		},
+	]
 }
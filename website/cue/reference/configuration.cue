@@ -449,7 +449,7 @@ configuration: {
 			common: false
 			description: """
 				Configuration options to retrieve secrets from external backend in order to avoid storing secrets in plaintext
-				in Vector config. Currently, only the exec backend is supported. Multiple backends can be configured. To signify
+				in Vector config. The `exec`, `file`, `directory`, and `vault` backends are supported. Multiple backends can be configured. To signify
 				Vector that it should look for a secret to retrieve use the `SECRET[<backend_name>.<secret_key>]`. This placeholder
 				will then be replaced by the secret retrieved from the relevant backend.
 				"""
@@ -504,6 +504,113 @@ configuration: {
 						}
 					}
 				}
+				file: {
+					required: true
+					description: """
+						Read secrets from a local file.
+
+						The file must contain a single JSON object, or YAML mapping, of secret keys to their values. Files
+						ending in `.json` are parsed as JSON, and all other files are parsed as YAML:
+
+						```json
+						{"secret1": "secret_value", "secret2": "other_secret_value"}
+						```
+
+						The file is read whenever Vector loads its configuration. When Vector is run with `--watch-config`,
+						changes to the file trigger a configuration reload.
+						"""
+					type: object: options: {
+						path: {
+							description: "The path of the file to read secrets from."
+							required:    true
+							type: string: examples: ["/etc/vector/secrets.json"]
+						}
+					}
+				}
+				directory: {
+					required: true
+					description: """
+						Read secrets from the files in a local directory.
+
+						Each secret is read from the file in the directory named after the secret key, which matches the
+						layout of secrets mounted as volumes in Kubernetes. For example, `SECRET[backend.password]` is
+						read from the `password` file in the directory.
+
+						The files are read whenever Vector loads its configuration. When Vector is run with
+						`--watch-config`, changes to the directory trigger a configuration reload.
+						"""
+					type: object: options: {
+						path: {
+							description: "The path of the directory to read secrets from."
+							required:    true
+							type: string: examples: ["/var/run/secrets/vector"]
+						}
+						remove_trailing_whitespace: {
+							description: """
+								Remove trailing whitespace from the secret values.
+
+								Files that hold secrets often end with a newline, which is usually not part of the secret.
+								"""
+							required: false
+							common:   false
+							type: bool: default: false
+						}
+					}
+				}
+				vault: {
+					required: true
+					description: """
+						Read secrets from a HashiCorp Vault [KV version 2](https://developer.hashicorp.com/vault/docs/secrets/kv/kv-v2)
+						secrets engine, or any server implementing the same API.
+
+						Secrets are read from a single secret, with each secret key being looked up as a field of that
+						secret. For example, `SECRET[backend.password]` is replaced with the `password` field of the
+						secret at `path`.
+						"""
+					type: object: options: {
+						address: {
+							description: "The address of the Vault server."
+							required:    true
+							type: string: examples: ["https://vault.example.com:8200"]
+						}
+						mount: {
+							description: "The path the KV secrets engine is mounted at."
+							required:    false
+							common:      false
+							type: string: default: "secret"
+						}
+						path: {
+							description: "The path of the secret within the KV secrets engine."
+							required:    true
+							type: string: examples: ["vector/production"]
+						}
+						token: {
+							description: """
+								The token used to authenticate with the Vault server.
+
+								As secrets can't be used to configure secret backends, this is best provided through an
+								environment variable.
+								"""
+							required: true
+							type: string: examples: ["${VAULT_TOKEN}"]
+						}
+						namespace: {
+							description: "The namespace of the KV secrets engine, when using Vault Enterprise."
+							required:    false
+							common:      false
+							type: string: examples: ["ns1"]
+						}
+						timeout: {
+							description: "The amount of time Vector will wait for the Vault server to respond."
+							required:    false
+							common:      false
+							type: uint: {
+								default: 5
+								unit:    "seconds"
+							}
+						}
+					}
+				}
 			}
 		}
 